@@ -0,0 +1,129 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ldpc::codes::{CssCode, LinearCode};
+use ldpc::decoders::{BpDecoder, CssDecoder, CssErasureDecoder, ErasureDecoder, SyndromeDecoder};
+use ldpc::noise::{
+    BinarySymmetricChannel, DepolarizingNoise, ErasureChannel, NoiseModel, Probability,
+    SamplingStrategy,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const LENGTHS: [usize; 3] = [100, 1000, 10000];
+
+fn regular_code(num_bits: usize) -> LinearCode {
+    LinearCode::random_regular_code()
+        .num_bits(num_bits)
+        .num_checks(num_bits * 3 / 4)
+        .bit_degree(3)
+        .check_degree(4)
+        .sample_with(&mut StdRng::seed_from_u64(123))
+        .unwrap()
+}
+
+fn syndrome_computation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("syndrome");
+    let mut rng = StdRng::seed_from_u64(123);
+    let noise = BinarySymmetricChannel::with_probability(Probability::new(0.05));
+    for length in LENGTHS {
+        let code = regular_code(length);
+        let error = code.random_error(&noise, &mut rng);
+        group.throughput(Throughput::Elements(length as u64));
+        group.bench_with_input(BenchmarkId::new("regular", length), &error, |b, error| {
+            b.iter(|| code.syndrome_of(black_box(error)))
+        });
+    }
+    let noise = DepolarizingNoise::with_probability(Probability::new(0.05));
+    for distance in [4, 8, 16] {
+        let code = CssCode::toric_code(distance);
+        let error = code.random_error(&noise, &mut rng);
+        group.throughput(Throughput::Elements(code.len() as u64));
+        group.bench_with_input(BenchmarkId::new("toric", distance), &error, |b, error| {
+            b.iter(|| code.syndrome_of(black_box(error)))
+        });
+    }
+    group.finish();
+}
+
+fn decoders(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decoder");
+    let mut rng = StdRng::seed_from_u64(123);
+    let probability = Probability::new(0.02);
+    let noise = BinarySymmetricChannel::with_probability(probability);
+    for length in LENGTHS {
+        let code = regular_code(length);
+        let decoder = BpDecoder::new(code.parity_check_matrix(), probability, 10);
+        let syndrome = code.syndrome_of(&code.random_error(&noise, &mut rng));
+        group.throughput(Throughput::Elements(length as u64));
+        group.bench_with_input(BenchmarkId::new("bp", length), &syndrome, |b, syndrome| {
+            b.iter(|| decoder.correction_for(black_box(syndrome.as_view())))
+        });
+        let reordered = code.reordered_for_decoding();
+        let decoder = BpDecoder::new(reordered.code().parity_check_matrix(), probability, 10);
+        let syndrome = reordered.reorder_checks(&syndrome);
+        group.bench_with_input(
+            BenchmarkId::new("bp_reordered", length),
+            &syndrome,
+            |b, syndrome| b.iter(|| decoder.correction_for(black_box(syndrome.as_view()))),
+        );
+    }
+    let noise = DepolarizingNoise::with_probability(probability);
+    for distance in [4, 8, 16] {
+        let code = CssCode::toric_code(distance);
+        let decoder = CssDecoder {
+            x: BpDecoder::new(&code.stabilizers.x, probability, 10),
+            z: BpDecoder::new(&code.stabilizers.z, probability, 10),
+        };
+        let syndrome = code.syndrome_of(&code.random_error(&noise, &mut rng));
+        group.throughput(Throughput::Elements(code.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("css_bp_toric", distance),
+            &syndrome,
+            |b, syndrome| b.iter(|| decoder.correction_for(black_box(syndrome.as_view()))),
+        );
+    }
+    let noise = ErasureChannel::with_probability(Probability::new(0.1));
+    for distance in [4, 8, 16] {
+        let code = CssCode::toric_code(distance);
+        let decoder = CssErasureDecoder::new(&code);
+        let erasure = noise.sample_error_of_length(code.len(), &mut rng);
+        group.throughput(Throughput::Elements(code.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("css_erasure_toric", distance),
+            &erasure,
+            |b, erasure| b.iter(|| decoder.recovery_probability(black_box(erasure.as_view()))),
+        );
+    }
+    group.finish();
+}
+
+fn noise_sampling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("noise");
+    let mut rng = StdRng::seed_from_u64(123);
+    let probability = Probability::new(0.01);
+    for length in LENGTHS {
+        group.throughput(Throughput::Elements(length as u64));
+        for strategy in [SamplingStrategy::PerPosition, SamplingStrategy::WeightFirst] {
+            let name = format!("{:?}", strategy);
+            let noise = BinarySymmetricChannel::with_probability(probability)
+                .with_sampling_strategy(strategy);
+            group.bench_function(BenchmarkId::new(format!("bsc_{}", name), length), |b| {
+                b.iter(|| noise.sample_error_of_length(black_box(length), &mut rng))
+            });
+            let noise =
+                ErasureChannel::with_probability(probability).with_sampling_strategy(strategy);
+            group.bench_function(BenchmarkId::new(format!("erasure_{}", name), length), |b| {
+                b.iter(|| noise.sample_error_of_length(black_box(length), &mut rng))
+            });
+            let noise =
+                DepolarizingNoise::with_probability(probability).with_sampling_strategy(strategy);
+            group.bench_function(
+                BenchmarkId::new(format!("depolarizing_{}", name), length),
+                |b| b.iter(|| noise.sample_error_of_length(black_box(length), &mut rng)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, syndrome_computation, decoders, noise_sampling);
+criterion_main!(benches);
@@ -0,0 +1,74 @@
+//! Generates a markdown table of the benchmark results.
+//!
+//! Run the benchmarks first with `cargo bench --features bench`,
+//! then `cargo run --example bench_baseline [criterion directory]`.
+//! The directory defaults to `target/criterion`.
+
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let directory = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target/criterion"));
+    let mut rows = Vec::new();
+    collect_rows(&directory, &mut rows);
+    rows.sort();
+    println!("| Benchmark | Mean time | Throughput |");
+    println!("|---|---|---|");
+    for row in rows {
+        println!("| {} | {} | {} |", row.0, row.1, row.2);
+    }
+}
+
+fn collect_rows(directory: &Path, rows: &mut Vec<(String, String, String)>) {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if path.ends_with("new") {
+            if let Some(row) = read_row(&path) {
+                rows.push(row);
+            }
+        } else if path.is_dir() && !path.ends_with("report") {
+            collect_rows(&path, rows);
+        }
+    }
+}
+
+fn read_row(directory: &Path) -> Option<(String, String, String)> {
+    let benchmark = read_json(&directory.join("benchmark.json"))?;
+    let estimates = read_json(&directory.join("estimates.json"))?;
+    let nanoseconds = estimates["mean"]["point_estimate"].as_f64()?;
+    let throughput = benchmark["throughput"]["Elements"]
+        .as_f64()
+        .map(|elements| format!("{:.3e} elements/s", elements / nanoseconds * 1e9))
+        .unwrap_or_else(|| String::from("-"));
+    Some((
+        benchmark["full_id"].as_str()?.to_owned(),
+        format_duration(nanoseconds),
+        throughput,
+    ))
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn format_duration(nanoseconds: f64) -> String {
+    if nanoseconds < 1e3 {
+        format!("{:.1} ns", nanoseconds)
+    } else if nanoseconds < 1e6 {
+        format!("{:.1} µs", nanoseconds / 1e3)
+    } else if nanoseconds < 1e9 {
+        format!("{:.1} ms", nanoseconds / 1e6)
+    } else {
+        format!("{:.2} s", nanoseconds / 1e9)
+    }
+}
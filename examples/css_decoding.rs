@@ -1,25 +1,13 @@
 use ldpc::codes::{CssCode, LinearCode};
-use ldpc::decoders::{BpDecoder, CssDecoder, SyndromeDecoder};
-use ldpc::noise::{DepolarizingNoise, Probability};
-use pauli::PauliOperator;
+use ldpc::noise::Probability;
+use ldpc::simulation::{run_css_memory_experiment, CssMemoryConfig};
 use rand::thread_rng;
 
 fn main() {
-    let max_iterations = 100;
-    let probability = Probability::new(0.01);
     let code = code();
-    let decoder = decoder(&code, probability, max_iterations);
-    let noise = DepolarizingNoise::with_probability(probability);
-    let mut failures = 0;
-    for _ in 0..1000 {
-        let error = code.random_error(&noise, &mut thread_rng());
-        let syndrome = code.syndrome_of(&error);
-        let correction: PauliOperator = decoder.correction_for(syndrome.as_view()).into();
-        if !code.has_stabilizer(&(&error * &correction)) {
-            failures += 1;
-        }
-    }
-    println!("{}", failures);
+    let config = CssMemoryConfig::new(&code, Probability::new(0.01));
+    let result = run_css_memory_experiment(&config, &mut thread_rng());
+    println!("{}", result.num_failures());
 }
 
 fn code() -> CssCode {
@@ -32,14 +20,3 @@ fn code() -> CssCode {
         .unwrap();
     CssCode::hypergraph_product(&code, &code)
 }
-
-fn decoder(
-    code: &CssCode,
-    probability: Probability,
-    max_iterations: usize,
-) -> CssDecoder<BpDecoder> {
-    CssDecoder {
-        x: BpDecoder::new(&code.stabilizers.x, probability, max_iterations),
-        z: BpDecoder::new(&code.stabilizers.z, probability, max_iterations),
-    }
-}
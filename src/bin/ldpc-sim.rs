@@ -0,0 +1,549 @@
+//! Runs the experiment described in a TOML or JSON file
+//! and writes the results as CSV.
+//!
+//! Build it with the `cli` feature and run
+//! `ldpc-sim experiment.toml [results.csv]`.
+//! The results are written to the standard output if no file is given.
+//! If the output file ends with `.json`, the results are instead written as a
+//! [`ShardResult`](ldpc::simulation::ShardResult) and the shards run with
+//! different seeds, for example on different machines, are combined with
+//! `ldpc-sim merge experiment.toml shard-1.json shard-2.json ...`
+//! which writes the merged results as CSV.
+//!
+//! An experiment file looks like
+//!
+//! ```toml
+//! code = "surface(5)"
+//! noise = "depolarizing"
+//! sweep = [0.01, 0.02, 0.05]
+//! trials = 1000
+//! seed = 123
+//! max_rounds = 1
+//!
+//! [decoder]
+//! kind = "bp"
+//! iterations = 20
+//! ```
+//!
+//! The code is any specification understood by the default
+//! [`CodeRegistry`](ldpc::codes::CodeRegistry) and the sweep lists the
+//! probabilities of the noise.
+//! CSS codes with depolarizing noise run a memory experiment
+//! decoded by belief propagation, the only decoder for now,
+//! and a trial fails if the memory fails within the maximum number of rounds.
+//! Linear codes with erasure noise run an erasure experiment
+//! and a trial fails if the erasure can't be repaired.
+//!
+//! With `checkpoint = "path/to/run"`, the progress of the `i`-th probability
+//! of the sweep is saved to `path/to/run.i.json` after each batch of trials.
+//! Running the same experiment again resumes from these files.
+//!
+//! `ldpc-sim check <code>` prints the parameters of a code, its rank, girth,
+//! degree profile and redundant checks, and exits with an error if the code is
+//! inconsistent.
+//! The code is a `.alist` parity check matrix, a `.json` linear or CSS code
+//! or a specification of the registry.
+//! `ldpc-sim check <x.alist> <z.alist>` audits the pair of codes whose checks would be
+//! the X and Z stabilizers of a CSS code and lists the checks that don't commute.
+//! The same audits are available in the library, next to the other checks of the codes,
+//! as [`LinearCode::audit`](ldpc::codes::LinearCode::audit),
+//! [`CssCode::audit`](ldpc::codes::CssCode::audit)
+//! and [`CssCode::audit_pair`](ldpc::codes::CssCode::audit_pair).
+
+use ldpc::codes::{CodeRegistry, CssCode, LinearCode, RegisteredCode};
+use ldpc::decoders::{BpDecoder, CssDecoder};
+use ldpc::noise::{DepolarizingNoise, ErasureChannel, Probability};
+use ldpc::simulation::{
+    merge_results, CheckpointedRun, ErasureExperiment, FailureCount, MemoryExperiment, ShardResult,
+};
+use ldpc::Error;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, BufReader, Write};
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Experiment {
+    code: String,
+    noise: NoiseKind,
+    #[serde(default)]
+    decoder: DecoderConfig,
+    sweep: Vec<f64>,
+    trials: usize,
+    seed: u64,
+    #[serde(default = "default_max_rounds")]
+    max_rounds: usize,
+    #[serde(default)]
+    checkpoint: Option<String>,
+}
+
+fn default_max_rounds() -> usize {
+    1
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum NoiseKind {
+    Depolarizing,
+    Erasure,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+enum DecoderConfig {
+    Bp {
+        #[serde(default = "default_iterations")]
+        iterations: usize,
+    },
+}
+
+fn default_iterations() -> usize {
+    20
+}
+
+impl Default for DecoderConfig {
+    fn default() -> Self {
+        Self::Bp {
+            iterations: default_iterations(),
+        }
+    }
+}
+
+impl Experiment {
+    // Everything shared by the shards of an experiment,
+    // that is all but the seed, the trials and the checkpoint.
+    fn config(&self) -> String {
+        serde_json::json!({
+            "code": self.code,
+            "noise": self.noise,
+            "decoder": self.decoder,
+            "sweep": self.sweep,
+            "max_rounds": self.max_rounds,
+        })
+        .to_string()
+    }
+}
+
+const HEADER: &str = "code,noise,decoder,probability,trials,failures,failure_rate";
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("ldpc-sim: {}", error);
+        std::process::exit(1);
+    }
+}
+
+const USAGE: &str = "usage: ldpc-sim <experiment.toml|experiment.json> [results.csv|shard.json]
+       ldpc-sim merge <experiment.toml|experiment.json> <shard.json>...
+       ldpc-sim check <code.alist|code.json|spec> [z-code.alist|z-code.json|spec]";
+
+fn usage() -> Error {
+    Error::InvalidInput(USAGE.to_string())
+}
+
+fn run() -> ldpc::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("merge") => merge(&args[1..]),
+        Some("check") => check(&args[1..]),
+        Some(_) => run_experiment(&args),
+        None => Err(usage()),
+    }
+}
+
+fn run_experiment(args: &[String]) -> ldpc::Result<()> {
+    let experiment = read_experiment(Path::new(&args[0]))?;
+    let counts = simulate(&experiment)?;
+    match args.get(1).map(Path::new) {
+        Some(path) if path.extension().and_then(|extension| extension.to_str()) == Some("json") => {
+            let shard = ShardResult::new(&experiment.config(), experiment.seed, counts);
+            fs::write(path, serde_json::to_string(&shard)?)?;
+        }
+        Some(path) => write_csv(&mut fs::File::create(path)?, &experiment, &counts)?,
+        None => write_csv(&mut io::stdout(), &experiment, &counts)?,
+    }
+    Ok(())
+}
+
+fn merge(args: &[String]) -> ldpc::Result<()> {
+    let (experiment, shards) = args.split_first().ok_or_else(usage)?;
+    let experiment = read_experiment(Path::new(experiment))?;
+    let shards = shards
+        .iter()
+        .map(|path| -> ldpc::Result<ShardResult> {
+            let shard: ShardResult = serde_json::from_str(&fs::read_to_string(path)?)?;
+            if !shard.has_config(&experiment.config()) {
+                return Err(Error::InvalidInput(format!(
+                    "{} is a shard of another experiment",
+                    path
+                )));
+            }
+            Ok(shard)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let merged = merge_results(&shards)?;
+    write_csv(&mut io::stdout(), &experiment, merged.counts())?;
+    Ok(())
+}
+
+fn check(args: &[String]) -> ldpc::Result<()> {
+    let summary = match args {
+        [code] => match read_code(code)? {
+            RegisteredCode::Css(code) => {
+                let audit = code.audit();
+                (audit.to_string(), audit.is_consistent())
+            }
+            RegisteredCode::Linear(code) => {
+                let audit = code.audit();
+                (audit.to_string(), audit.is_consistent())
+            }
+        },
+        [x_code, z_code] => match (read_code(x_code)?, read_code(z_code)?) {
+            (RegisteredCode::Linear(x_code), RegisteredCode::Linear(z_code)) => {
+                let audit = CssCode::audit_pair(&x_code, &z_code);
+                (audit.to_string(), audit.is_consistent())
+            }
+            _ => {
+                return Err(Error::InvalidInput(
+                    "a pair of codes must be two linear codes".to_string(),
+                ))
+            }
+        },
+        _ => return Err(usage()),
+    };
+    let (summary, is_consistent) = summary;
+    print!("{}", summary);
+    if is_consistent {
+        Ok(())
+    } else {
+        Err(Error::InvalidInput("inconsistent code".to_string()))
+    }
+}
+
+// Reads a code from an alist or JSON file or resolves it with the registry.
+fn read_code(code: &str) -> ldpc::Result<RegisteredCode> {
+    let path = Path::new(code);
+    if !path.exists() {
+        return Ok(CodeRegistry::default().resolve(code)?);
+    }
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("alist") => Ok(RegisteredCode::Linear(LinearCode::from_alist(
+            BufReader::new(fs::File::open(path)?),
+        )?)),
+        Some("json") => {
+            let content = fs::read_to_string(path)?;
+            let css_error = match serde_json::from_str::<CssCode>(&content) {
+                Ok(code) => return Ok(RegisteredCode::Css(code)),
+                Err(error) => error,
+            };
+            let linear_error = match serde_json::from_str::<LinearCode>(&content) {
+                Ok(code) => return Ok(RegisteredCode::Linear(code)),
+                Err(error) => error,
+            };
+            Err(Error::InvalidInput(format!(
+                "{} is neither a CSS code ({}) nor a linear code ({})",
+                path.display(),
+                css_error,
+                linear_error
+            )))
+        }
+        _ => Err(Error::InvalidInput(format!(
+            "{} is neither a .alist nor a .json file",
+            path.display()
+        ))),
+    }
+}
+
+fn read_experiment(path: &Path) -> ldpc::Result<Experiment> {
+    let content = fs::read_to_string(path)?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => toml::from_str(&content)
+            .map_err(|error| Error::InvalidInput(format!("invalid experiment: {}", error))),
+        Some("json") => Ok(serde_json::from_str(&content)?),
+        _ => Err(Error::InvalidInput(format!(
+            "{} is neither a .toml nor a .json file",
+            path.display()
+        ))),
+    }
+}
+
+fn simulate(experiment: &Experiment) -> ldpc::Result<Vec<FailureCount>> {
+    let code = CodeRegistry::default().resolve(&experiment.code)?;
+    let probabilities = experiment
+        .sweep
+        .iter()
+        .map(|probability| {
+            Probability::try_new(*probability).ok_or_else(|| {
+                Error::InvalidInput(format!("{} is not a probability", probability))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match (&code, experiment.noise) {
+        (RegisteredCode::Css(_), NoiseKind::Depolarizing)
+        | (RegisteredCode::Linear(_), NoiseKind::Erasure) => {}
+        _ => {
+            return Err(Error::InvalidInput(format!(
+                "no experiment for {:?} noise on {}",
+                experiment.noise, experiment.code
+            )))
+        }
+    }
+    let DecoderConfig::Bp { iterations } = experiment.decoder;
+    let count_failures = |probability: Probability, num_trials: usize, rng: &mut StdRng| match &code
+    {
+        RegisteredCode::Css(code) => {
+            let decoder = CssDecoder {
+                x: BpDecoder::new(&code.stabilizers.x, probability, iterations),
+                z: BpDecoder::new(&code.stabilizers.z, probability, iterations),
+            };
+            let noise = DepolarizingNoise::with_probability(probability);
+            MemoryExperiment::new(code, &noise, &decoder)
+                .max_rounds(experiment.max_rounds)
+                .run_with(num_trials, rng)
+                .num_failures()
+        }
+        RegisteredCode::Linear(code) => {
+            let noise = ErasureChannel::with_probability(probability);
+            ErasureExperiment::new(code, &noise)
+                .run_with(num_trials, rng)
+                .num_failures()
+        }
+    };
+    // Each point of the sweep gets its own seed derived from the seed of the experiment,
+    // such that the points are independent with or without checkpoints.
+    let mut seeds = StdRng::seed_from_u64(experiment.seed);
+    probabilities
+        .into_iter()
+        .enumerate()
+        .map(|(point, probability)| {
+            let seed = seeds.gen();
+            let failures = match &experiment.checkpoint {
+                Some(prefix) => CheckpointedRun::new(format!("{}.{}.json", prefix, point), seed)
+                    .run(experiment.trials, 0, |failures, num_trials, rng| {
+                        *failures += count_failures(probability, num_trials, rng)
+                    })?,
+                None => count_failures(
+                    probability,
+                    experiment.trials,
+                    &mut StdRng::seed_from_u64(seed),
+                ),
+            };
+            Ok(FailureCount::new(experiment.trials, failures))
+        })
+        .collect()
+}
+
+fn write_csv<W: Write>(
+    output: &mut W,
+    experiment: &Experiment,
+    counts: &[FailureCount],
+) -> io::Result<()> {
+    writeln!(output, "{}", HEADER)?;
+    let noise = match experiment.noise {
+        NoiseKind::Depolarizing => "depolarizing",
+        NoiseKind::Erasure => "erasure",
+    };
+    let decoder = match experiment.decoder {
+        DecoderConfig::Bp { iterations } => format!("bp({})", iterations),
+    };
+    for (probability, count) in experiment.sweep.iter().zip(counts) {
+        writeln!(
+            output,
+            "{},{},{},{},{},{},{}",
+            csv_field(&experiment.code),
+            noise,
+            csv_field(&decoder),
+            probability,
+            count.num_trials(),
+            count.num_failures(),
+            count.failure_rate()
+        )?;
+    }
+    Ok(())
+}
+
+// Quotes the field if it contains a comma or a quote.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn toml_and_json_descriptions_are_equivalent() {
+        let from_toml: Experiment = toml::from_str(
+            r#"
+            code = "bb[[72,12,6]]"
+            noise = "depolarizing"
+            sweep = [0.01, 0.02]
+            trials = 10
+            seed = 123
+
+            [decoder]
+            kind = "bp"
+            "#,
+        )
+        .unwrap();
+        let from_json: Experiment = serde_json::from_str(
+            r#"{
+                "code": "bb[[72,12,6]]",
+                "noise": "depolarizing",
+                "sweep": [0.01, 0.02],
+                "trials": 10,
+                "seed": 123,
+                "decoder": { "kind": "bp", "iterations": 20 }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(from_toml, from_json);
+        assert_eq!(from_toml.max_rounds, 1);
+    }
+
+    #[test]
+    fn erasure_sweep_writes_one_row_per_probability() {
+        let experiment = Experiment {
+            code: "hamming(3)".to_string(),
+            noise: NoiseKind::Erasure,
+            decoder: DecoderConfig::default(),
+            sweep: vec![0.0, 1.0],
+            trials: 5,
+            seed: 123,
+            max_rounds: 1,
+            checkpoint: None,
+        };
+        let counts = simulate(&experiment).unwrap();
+        assert_eq!(
+            counts,
+            vec![FailureCount::new(5, 0), FailureCount::new(5, 5)]
+        );
+
+        let mut output = Vec::new();
+        write_csv(&mut output, &experiment, &counts).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some(HEADER));
+        assert_eq!(lines.next(), Some("hamming(3),erasure,bp(20),0,5,0,0"));
+        assert_eq!(lines.next(), Some("hamming(3),erasure,bp(20),1,5,5,1"));
+    }
+
+    // A path in the temporary directory unique to this process and name,
+    // such that concurrent runs of the tests don't share files.
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ldpc_sim_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn checkpointed_sweep_resumes_from_files() {
+        let prefix = temp_path("checkpoint");
+        let files = || (0..2).map(|point| format!("{}.{}.json", prefix.display(), point));
+        files().for_each(|file| {
+            fs::remove_file(file).ok();
+        });
+        let experiment = Experiment {
+            code: "hamming(3)".to_string(),
+            noise: NoiseKind::Erasure,
+            decoder: DecoderConfig::default(),
+            sweep: vec![0.2, 0.4],
+            trials: 2000,
+            seed: 123,
+            max_rounds: 1,
+            checkpoint: Some(prefix.display().to_string()),
+        };
+        let counts = simulate(&experiment).unwrap();
+        assert!(files().all(|file| Path::new(&file).exists()));
+        assert_eq!(simulate(&experiment).unwrap(), counts);
+        let seeds = files()
+            .map(|file| {
+                let checkpoint: serde_json::Value =
+                    serde_json::from_str(&fs::read_to_string(file).unwrap()).unwrap();
+                checkpoint["seed"].as_u64().unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_ne!(seeds[0], seeds[1]);
+        files().for_each(|file| {
+            fs::remove_file(file).ok();
+        });
+    }
+
+    #[test]
+    fn shards_only_differ_by_seed_and_trials() {
+        let experiment = |seed: u64, trials: usize, max_rounds: usize| Experiment {
+            code: "steane".to_string(),
+            noise: NoiseKind::Depolarizing,
+            decoder: DecoderConfig::default(),
+            sweep: vec![0.1],
+            trials,
+            seed,
+            max_rounds,
+            checkpoint: None,
+        };
+        assert_eq!(experiment(1, 10, 1).config(), experiment(2, 20, 1).config());
+        assert_ne!(experiment(1, 10, 1).config(), experiment(1, 10, 2).config());
+    }
+
+    #[test]
+    fn codes_are_read_from_alist_json_and_specifications() {
+        let path = temp_path("check.json");
+        fs::write(
+            &path,
+            serde_json::to_string(&CssCode::steane_code()).unwrap(),
+        )
+        .unwrap();
+        let code = read_code(&path.display().to_string()).unwrap();
+        assert_eq!(code, RegisteredCode::Css(CssCode::steane_code()));
+        fs::remove_file(&path).ok();
+
+        // The parity checks of the 3 bits repetition code.
+        let path = temp_path("check.alist");
+        fs::write(&path, "3 2\n2 2\n1 2 1\n2 2\n1\n1 2\n2\n1 2\n2 3\n").unwrap();
+        match read_code(&path.display().to_string()).unwrap() {
+            RegisteredCode::Linear(code) => assert_eq!(
+                code.parity_check_matrix(),
+                LinearCode::repetition_code(3).parity_check_matrix()
+            ),
+            RegisteredCode::Css(_) => panic!("an alist file is a linear code"),
+        }
+        fs::remove_file(&path).ok();
+
+        assert!(read_code("hamming(3)").is_ok());
+        assert!(check(&["steane".to_string()]).is_ok());
+        assert!(check(&["hamming(3)".to_string(), "repetition(7)".to_string()]).is_err());
+    }
+
+    #[test]
+    fn invalid_json_codes_report_both_parse_errors() {
+        let path = temp_path("invalid.json");
+        fs::write(&path, "{}").unwrap();
+        let error = read_code(&path.display().to_string())
+            .unwrap_err()
+            .to_string();
+        fs::remove_file(&path).ok();
+        assert!(error.contains("neither a CSS code (missing field"));
+        assert!(error.contains("nor a linear code (missing field"));
+    }
+
+    #[test]
+    fn mismatched_code_and_noise_is_an_error() {
+        let experiment = Experiment {
+            code: "steane".to_string(),
+            noise: NoiseKind::Erasure,
+            decoder: DecoderConfig::default(),
+            sweep: vec![0.1],
+            trials: 1,
+            seed: 123,
+            max_rounds: 1,
+            checkpoint: None,
+        };
+        assert!(simulate(&experiment).is_err());
+    }
+}
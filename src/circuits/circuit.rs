@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An operation on the qubits of a [`Circuit`](Circuit).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum Operation {
+    /// Resets a qubit to the |0> state.
+    Reset(usize),
+    /// Applies a Hadamard gate to a qubit.
+    Hadamard(usize),
+    /// Applies a CNOT gate from a control qubit to a target qubit.
+    Cnot(usize, usize),
+    /// Measures a qubit in the Z basis.
+    Measure(usize),
+}
+
+impl Operation {
+    /// Returns the qubits acted on by the operation.
+    pub fn qubits(&self) -> Vec<usize> {
+        match self {
+            Self::Reset(qubit) | Self::Hadamard(qubit) | Self::Measure(qubit) => vec![*qubit],
+            Self::Cnot(control, target) => vec![*control, *target],
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Reset(qubit) => write!(f, "R {}", qubit),
+            Self::Hadamard(qubit) => write!(f, "H {}", qubit),
+            Self::Cnot(control, target) => write!(f, "CX {} {}", control, target),
+            Self::Measure(qubit) => write!(f, "M {}", qubit),
+        }
+    }
+}
+
+/// A quantum circuit as a sequence of moments.
+///
+/// Each moment is a set of operations acting on disjoint qubits
+/// that are applied at the same time.
+/// Measurement outcomes are indexed in the order the measurements are added.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::circuits::{Circuit, Operation};
+///
+/// // Measures the parity of the first two qubits with the third one.
+/// let mut circuit = Circuit::new(3);
+/// circuit.push(Operation::Reset(2));
+/// circuit.tick();
+/// circuit.push(Operation::Cnot(0, 2));
+/// circuit.tick();
+/// circuit.push(Operation::Cnot(1, 2));
+/// circuit.tick();
+/// circuit.push(Operation::Measure(2));
+///
+/// assert_eq!(circuit.num_moments(), 4);
+/// assert_eq!(circuit.num_measurements(), 1);
+/// assert_eq!(circuit.to_string(), "R 2\nTICK\nCX 0 2\nTICK\nCX 1 2\nTICK\nM 2\n");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct Circuit {
+    num_qubits: usize,
+    moments: Vec<Vec<Operation>>,
+    num_measurements: usize,
+}
+
+impl Circuit {
+    /// Creates an empty circuit on the given number of qubits.
+    pub fn new(num_qubits: usize) -> Self {
+        Self {
+            num_qubits,
+            moments: vec![Vec::new()],
+            num_measurements: 0,
+        }
+    }
+
+    /// Adds an operation to the last moment.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a qubit is out of bound, if a CNOT acts twice on the same qubit
+    /// or if a qubit is already used in the last moment.
+    pub fn push(&mut self, operation: Operation) -> &mut Self {
+        let qubits = operation.qubits();
+        if let Some(qubit) = qubits.iter().find(|qubit| **qubit >= self.num_qubits) {
+            panic!(
+                "qubit {} is out of bound for {} qubits",
+                qubit, self.num_qubits
+            );
+        }
+        if qubits.len() == 2 && qubits[0] == qubits[1] {
+            panic!("{} acts twice on the same qubit", operation);
+        }
+        if !self.is_free(&qubits) {
+            panic!("{} acts on a qubit already used in this moment", operation);
+        }
+        if let Operation::Measure(_) = operation {
+            self.num_measurements += 1;
+        }
+        self.moments.last_mut().unwrap().push(operation);
+        self
+    }
+
+    /// Starts a new moment unless the last one is empty.
+    pub fn tick(&mut self) -> &mut Self {
+        if !self.moments.last().unwrap().is_empty() {
+            self.moments.push(Vec::new());
+        }
+        self
+    }
+
+    /// Checks if none of the qubits are used in the last moment.
+    pub fn is_free(&self, qubits: &[usize]) -> bool {
+        self.moments
+            .last()
+            .unwrap()
+            .iter()
+            .flat_map(|operation| operation.qubits())
+            .all(|qubit| !qubits.contains(&qubit))
+    }
+
+    /// Returns the number of qubits.
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// Returns the number of non empty moments.
+    pub fn num_moments(&self) -> usize {
+        self.moments().len()
+    }
+
+    /// Returns the number of measurements.
+    pub fn num_measurements(&self) -> usize {
+        self.num_measurements
+    }
+
+    /// Returns the non empty moments.
+    pub fn moments(&self) -> &[Vec<Operation>] {
+        match self.moments.last() {
+            Some(moment) if moment.is_empty() => &self.moments[..self.moments.len() - 1],
+            _ => &self.moments,
+        }
+    }
+
+    /// Returns an iterator over all the operations in order.
+    pub fn operations(&self) -> impl Iterator<Item = &Operation> {
+        self.moments.iter().flatten()
+    }
+}
+
+impl fmt::Display for Circuit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, moment) in self.moments().iter().enumerate() {
+            if index > 0 {
+                writeln!(f, "TICK")?;
+            }
+            for operation in moment.iter() {
+                writeln!(f, "{}", operation)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tick_skips_empty_moments() {
+        let mut circuit = Circuit::new(2);
+        circuit.tick().tick();
+        assert_eq!(circuit.num_moments(), 0);
+        circuit
+            .push(Operation::Hadamard(0))
+            .push(Operation::Reset(1));
+        circuit.tick().tick();
+        assert_eq!(circuit.num_moments(), 1);
+        assert_eq!(circuit.operations().count(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn qubits_are_used_once_per_moment() {
+        let mut circuit = Circuit::new(2);
+        circuit
+            .push(Operation::Hadamard(0))
+            .push(Operation::Cnot(1, 0));
+    }
+}
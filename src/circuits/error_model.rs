@@ -0,0 +1,376 @@
+use super::frame::{non_trivial_pairs, non_trivial_paulis};
+use super::{Circuit, CircuitNoise, Operation, PauliFrame};
+use indexmap::IndexMap;
+use pauli::Pauli;
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+
+/// The effect of each single fault of a circuit on its detectors and observables.
+///
+/// Every fault of a [`CircuitNoise`](CircuitNoise) is propagated
+/// through the rest of the circuit to find the measurements it flips.
+/// Each Pauli of a depolarizing fault is a distinct fault
+/// with a share of the probability, that is a third for a single qubit
+/// and a fifteenth for two qubits.
+/// Faults flipping the same detectors and observables are merged
+/// into a single fault happening when an odd number of them happen
+/// and faults without effect are dropped.
+///
+/// The check matrix has a row for each detector and a column for each fault.
+/// Decoding its syndromes with the [priors](DetectorErrorModel::priors)
+/// gives a likely set of faults whose effect on the observables
+/// is the correction.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::circuits::{Circuit, CircuitNoise, DetectorErrorModel, Operation};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinMat;
+///
+/// // Measures the parity of two qubits twice.
+/// let mut circuit = Circuit::new(3);
+/// for _ in 0..2 {
+///     circuit.push(Operation::Reset(2));
+///     circuit.tick().push(Operation::Cnot(0, 2));
+///     circuit.tick().push(Operation::Cnot(1, 2));
+///     circuit.tick().push(Operation::Measure(2));
+///     circuit.tick();
+/// }
+///
+/// // The second outcome is compared to the first without observable.
+/// let detectors = SparseBinMat::new(2, vec![vec![0, 1]]);
+/// let observables = SparseBinMat::new(2, vec![]);
+/// let noise = CircuitNoise::noiseless().with_measurement(Probability::new(0.1));
+/// let model = DetectorErrorModel::new(&circuit, &detectors, &observables, noise);
+///
+/// // Both measurement faults flip the only detector.
+/// assert_eq!(model.num_faults(), 1);
+/// assert!((model.probabilities()[0] - 0.18).abs() < 1e-12);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct DetectorErrorModel {
+    check_matrix: SparseBinMat,
+    observables: SparseBinMat,
+    probabilities: Vec<f64>,
+}
+
+impl DetectorErrorModel {
+    /// Enumerates the faults of the circuit under the given noise.
+    ///
+    /// Each row of the detectors and observables is given by
+    /// the measurements whose parity defines it.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of columns of the detectors or of the observables
+    /// is different from the number of measurements.
+    pub fn new(
+        circuit: &Circuit,
+        detectors: &SparseBinMat,
+        observables: &SparseBinMat,
+        noise: CircuitNoise,
+    ) -> Self {
+        for (name, matrix) in [("detectors", detectors), ("observables", observables)] {
+            if matrix.number_of_columns() != circuit.num_measurements() {
+                panic!(
+                    "{} on {} measurements for circuit with {} measurements",
+                    name,
+                    matrix.number_of_columns(),
+                    circuit.num_measurements()
+                );
+            }
+        }
+        let mut faults: IndexMap<(SparseBinVec, SparseBinVec), f64> = IndexMap::new();
+        for (flips, probability) in FaultPropagator::new(circuit).faults(noise) {
+            let flips = SparseBinVec::new(circuit.num_measurements(), flips);
+            let effect = (detectors * &flips, observables * &flips);
+            if effect.0.is_zero() && effect.1.is_zero() {
+                continue;
+            }
+            let total = faults.entry(effect).or_insert(0.0);
+            *total = *total * (1.0 - probability) + probability * (1.0 - *total);
+        }
+        let (check_columns, observable_columns) = faults
+            .keys()
+            .map(|(detectors, observables)| {
+                (
+                    detectors.non_trivial_positions().collect::<Vec<_>>(),
+                    observables.non_trivial_positions().collect::<Vec<_>>(),
+                )
+            })
+            .unzip();
+        Self {
+            check_matrix: SparseBinMat::new(detectors.number_of_rows(), check_columns).transposed(),
+            observables: SparseBinMat::new(observables.number_of_rows(), observable_columns)
+                .transposed(),
+            probabilities: faults.values().cloned().collect(),
+        }
+    }
+
+    /// Returns the matrix with a row for each detector and a column for each fault.
+    pub fn check_matrix(&self) -> &SparseBinMat {
+        &self.check_matrix
+    }
+
+    /// Returns the matrix with a row for each observable and a column for each fault.
+    pub fn observables(&self) -> &SparseBinMat {
+        &self.observables
+    }
+
+    /// Returns the probability of each fault.
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probabilities
+    }
+
+    /// Returns the prior log-likelihood ratio `ln((1 - p) / p)` of each fault.
+    pub fn priors(&self) -> Vec<f64> {
+        self.probabilities
+            .iter()
+            .map(|probability| ((1.0 - probability) / probability).ln())
+            .collect()
+    }
+
+    /// Returns the number of faults.
+    pub fn num_faults(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    /// Returns the number of detectors.
+    pub fn num_detectors(&self) -> usize {
+        self.check_matrix.number_of_rows()
+    }
+
+    /// Returns the number of observables.
+    pub fn num_observables(&self) -> usize {
+        self.observables.number_of_rows()
+    }
+
+    /// Returns the observables flipped by the given faults.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length of the faults is different from the number of faults.
+    pub fn observables_flipped_by(&self, faults: &SparseBinVec) -> SparseBinVec {
+        if faults.len() != self.num_faults() {
+            panic!(
+                "{} faults for model with {} faults",
+                faults.len(),
+                self.num_faults()
+            );
+        }
+        &self.observables * faults
+    }
+}
+
+// Finds the measurements flipped by each fault of a circuit.
+struct FaultPropagator<'a> {
+    circuit: &'a Circuit,
+    // The number of measurements before each moment.
+    measurement_offsets: Vec<usize>,
+}
+
+impl<'a> FaultPropagator<'a> {
+    fn new(circuit: &'a Circuit) -> Self {
+        let measurement_offsets = circuit
+            .moments()
+            .iter()
+            .scan(0, |offset, moment| {
+                let current = *offset;
+                *offset += moment
+                    .iter()
+                    .filter(|operation| matches!(operation, Operation::Measure(_)))
+                    .count();
+                Some(current)
+            })
+            .collect();
+        Self {
+            circuit,
+            measurement_offsets,
+        }
+    }
+
+    // Returns the flipped measurements and the probability of each fault.
+    fn faults(&self, noise: CircuitNoise) -> Vec<(Vec<usize>, f64)> {
+        let mut faults = Vec::new();
+        let mut is_idle = vec![true; self.circuit.num_qubits()];
+        for (index, moment) in self.circuit.moments().iter().enumerate() {
+            is_idle.iter_mut().for_each(|idle| *idle = true);
+            let mut next_measurement = self.measurement_offsets[index];
+            for operation in moment {
+                for qubit in operation.qubits() {
+                    is_idle[qubit] = false;
+                }
+                match *operation {
+                    Operation::Measure(_) => {
+                        if noise.measurement() > 0.0 {
+                            faults.push((vec![next_measurement], noise.measurement()));
+                        }
+                        next_measurement += 1;
+                    }
+                    Operation::Reset(qubit) => {
+                        if noise.reset() > 0.0 {
+                            faults.push((self.flips_after(index, qubit, Pauli::X), noise.reset()));
+                        }
+                    }
+                    Operation::Hadamard(qubit) => {
+                        self.push_single_qubit_faults(
+                            &mut faults,
+                            index,
+                            qubit,
+                            noise.single_qubit_gate(),
+                        );
+                    }
+                    Operation::Cnot(control, target) => {
+                        if noise.two_qubit_gate() > 0.0 {
+                            faults.extend(non_trivial_pairs().into_iter().map(
+                                |(first, second)| {
+                                    let mut frame = PauliFrame::new(self.circuit.num_qubits());
+                                    frame
+                                        .multiply_at(control, first)
+                                        .multiply_at(target, second);
+                                    (
+                                        self.propagate(index + 1, frame),
+                                        noise.two_qubit_gate() / 15.0,
+                                    )
+                                },
+                            ));
+                        }
+                    }
+                }
+            }
+            for (qubit, idle) in is_idle.iter().enumerate() {
+                if *idle {
+                    self.push_single_qubit_faults(&mut faults, index, qubit, noise.idle());
+                }
+            }
+        }
+        faults
+    }
+
+    fn push_single_qubit_faults(
+        &self,
+        faults: &mut Vec<(Vec<usize>, f64)>,
+        moment: usize,
+        qubit: usize,
+        probability: f64,
+    ) {
+        if probability > 0.0 {
+            faults.extend(
+                non_trivial_paulis()
+                    .into_iter()
+                    .map(|pauli| (self.flips_after(moment, qubit, pauli), probability / 3.0)),
+            );
+        }
+    }
+
+    // The measurements flipped by a Pauli on the qubit at the end of the moment.
+    fn flips_after(&self, moment: usize, qubit: usize, pauli: Pauli) -> Vec<usize> {
+        let mut frame = PauliFrame::new(self.circuit.num_qubits());
+        frame.multiply_at(qubit, pauli);
+        self.propagate(moment + 1, frame)
+    }
+
+    // Operations in a moment act on distinct qubits,
+    // so a fault in a moment only interacts with the following moments.
+    fn propagate(&self, first_moment: usize, mut frame: PauliFrame) -> Vec<usize> {
+        let mut flips = Vec::new();
+        let moments = self.circuit.moments().iter().enumerate().skip(first_moment);
+        for (index, moment) in moments {
+            let mut next_measurement = self.measurement_offsets[index];
+            for operation in moment {
+                if let Some(is_flipped) = frame.apply(operation) {
+                    if is_flipped {
+                        flips.push(next_measurement);
+                    }
+                    next_measurement += 1;
+                }
+            }
+            if frame.is_identity() {
+                break;
+            }
+        }
+        flips
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuits::{CircuitSampler, MemoryCircuit};
+    use crate::codes::{CssCode, LinearCode};
+    use crate::noise::Probability;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn surface_code() -> CssCode {
+        let repetition_code = LinearCode::repetition_code(3);
+        CssCode::hypergraph_product(&repetition_code, &repetition_code)
+    }
+
+    #[test]
+    fn detectable_faults_of_the_surface_code_memory() {
+        for basis in [Pauli::X, Pauli::Z] {
+            let memory = MemoryCircuit::new(&surface_code(), 3, basis);
+            let model = memory.error_model(CircuitNoise::uniform(Probability::new(0.001)));
+            assert_eq!(model.num_detectors(), 6 + 2 * 12 + 6);
+            assert_eq!(model.num_observables(), 1);
+            // A single fault can't flip the logical qubit of a distance 3 code
+            // without being detected.
+            for fault in 0..model.num_faults() {
+                let faults = SparseBinVec::new(model.num_faults(), vec![fault]);
+                assert!(
+                    !(model.check_matrix() * &faults).is_zero(),
+                    "undetected fault {} in basis {:?}",
+                    fault,
+                    basis
+                );
+            }
+            assert!(model
+                .probabilities()
+                .iter()
+                .all(|probability| *probability > 0.0 && *probability < 0.01));
+        }
+    }
+
+    #[test]
+    fn measurement_faults_are_timelike_edges() {
+        let memory = MemoryCircuit::new(&surface_code(), 3, Pauli::Z);
+        let noise = CircuitNoise::noiseless().with_measurement(Probability::new(0.01));
+        let model = memory.error_model(noise);
+        // Each of the 3 outcomes of the 12 stabilizers and the 13 data measurements.
+        assert_eq!(model.num_faults(), 3 * 12 + 13);
+        assert!(model
+            .check_matrix()
+            .transposed()
+            .rows()
+            .all(|fault| fault.weight() <= 2));
+        assert!(model
+            .probabilities()
+            .iter()
+            .all(|probability| (probability - 0.01).abs() < 1e-12));
+    }
+
+    #[test]
+    fn sampled_detectors_mostly_match_single_faults() {
+        let memory = MemoryCircuit::new(&surface_code(), 2, Pauli::Z);
+        let noise = CircuitNoise::noiseless().with_two_qubit_gate(Probability::new(0.0002));
+        let model = memory.error_model(noise);
+        let columns = model.check_matrix().transposed();
+        let sampler = CircuitSampler::new(memory.circuit(), noise);
+        let mut rng = StdRng::seed_from_u64(123);
+        let mut num_events = 0;
+        let mut num_single_faults = 0;
+        for _ in 0..1000 {
+            let syndrome = sampler.sample_detectors(memory.detectors(), &mut rng);
+            if !syndrome.is_zero() {
+                num_events += 1;
+                if columns.rows().any(|column| column == syndrome.as_view()) {
+                    num_single_faults += 1;
+                }
+            }
+        }
+        // Two faults in the same shot are rare at this probability.
+        assert!(num_events > 0);
+        assert!(10 * num_single_faults >= 9 * num_events);
+    }
+}
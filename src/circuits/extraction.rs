@@ -0,0 +1,452 @@
+use super::{Circuit, Operation};
+use crate::codes::CssCode;
+use crate::css::{Css, CssSyndrome};
+use pauli::{Pauli, PauliOperator};
+use sparse_bin_mat::SparseBinMat;
+
+/// A circuit measuring each stabilizer of a CSS code once with an ancilla,
+/// optionally with flag qubits.
+///
+/// The first qubits of the circuit are the data qubits,
+/// followed by the ancillas of the X stabilizers, those of the Z stabilizers
+/// and finally the flags.
+/// All X stabilizers are measured before the Z stabilizers
+/// and the CNOTs of a stabilizer are applied in the order of its support.
+///
+/// A flag catches the faults on the ancilla that spread to more than one data qubit.
+/// The X stabilizers use a flag in the |0> state coupled with two CNOTs from the ancilla,
+/// one after the first CNOT to the data and one before the last.
+/// The Z stabilizers use a flag in the |+> state coupled with two CNOTs to the ancilla
+/// and measured in the X basis.
+/// When a flag is raised, its [hook errors](Flag::hook_errors) list the possible
+/// data errors caused by a single fault.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::circuits::ExtractionCircuit;
+///
+/// let code = CssCode::steane_code();
+/// let extraction = ExtractionCircuit::flagged(&code, 4);
+///
+/// // 7 data qubits, 6 ancillas and one flag for each weight 4 stabilizer.
+/// assert_eq!(extraction.circuit().num_qubits(), 19);
+/// assert_eq!(extraction.circuit().num_measurements(), 12);
+/// assert_eq!(extraction.flags().len(), 6);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExtractionCircuit {
+    circuit: Circuit,
+    num_data_qubits: usize,
+    measurements: Css<Vec<usize>>,
+    flags: Vec<Flag>,
+}
+
+impl ExtractionCircuit {
+    /// Creates the measurement circuit of the code without flags.
+    pub fn new(code: &CssCode) -> Self {
+        Self::generate(code, None)
+    }
+
+    /// Creates the measurement circuit of the code with a flag
+    /// for each stabilizer of weight at least the given weight.
+    ///
+    /// Stabilizers of weight less than 3 are never flagged
+    /// since a single fault can't spread to more than one qubit up to a stabilizer.
+    pub fn flagged(code: &CssCode, min_weight: usize) -> Self {
+        Self::generate(code, Some(min_weight.max(3)))
+    }
+
+    fn generate(code: &CssCode, min_flag_weight: Option<usize>) -> Self {
+        let mut circuit = Circuit::new(num_extraction_qubits(code, min_flag_weight));
+        let (measurements, flags) = append_extraction(&mut circuit, code, min_flag_weight);
+        Self {
+            circuit,
+            num_data_qubits: code.len(),
+            measurements,
+            flags,
+        }
+    }
+
+    /// Returns the circuit.
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// Returns the number of data qubits.
+    pub fn num_data_qubits(&self) -> usize {
+        self.num_data_qubits
+    }
+
+    /// Returns the index of the measurement giving the outcome of the given X stabilizer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the stabilizer is out of bound.
+    pub fn x_measurement(&self, stabilizer: usize) -> usize {
+        self.measurements.x[stabilizer]
+    }
+
+    /// Returns the index of the measurement giving the outcome of the given Z stabilizer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the stabilizer is out of bound.
+    pub fn z_measurement(&self, stabilizer: usize) -> usize {
+        self.measurements.z[stabilizer]
+    }
+
+    /// Returns the detectors of the circuit as a matrix
+    /// with a row for each detector and a column for each measurement.
+    ///
+    /// Starting from a state of the code, each stabilizer and flag outcome
+    /// is deterministic. Thus, each measurement is a detector,
+    /// starting with the X stabilizers, then the Z stabilizers and finally the flags.
+    pub fn detectors(&self) -> SparseBinMat {
+        let rows = self
+            .measurements
+            .x
+            .iter()
+            .chain(self.measurements.z.iter())
+            .cloned()
+            .chain(self.flags.iter().map(|flag| flag.measurement()))
+            .map(|measurement| vec![measurement])
+            .collect();
+        SparseBinMat::new(self.circuit.num_measurements(), rows)
+    }
+
+    /// Returns the flags of the circuit, X stabilizers first.
+    pub fn flags(&self) -> &[Flag] {
+        &self.flags
+    }
+}
+
+/// A flag qubit of an [`ExtractionCircuit`](ExtractionCircuit)
+/// with the table of the data errors to correct when it is raised.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Flag {
+    stabilizer_type: Pauli,
+    stabilizer: usize,
+    qubit: usize,
+    measurement: usize,
+    hook_errors: Vec<(CssSyndrome, PauliOperator)>,
+}
+
+impl Flag {
+    fn new(
+        code: &CssCode,
+        stabilizer_type: Pauli,
+        stabilizer: usize,
+        qubit: usize,
+        measurement: usize,
+        stabilizers: &SparseBinMat,
+    ) -> Self {
+        let support = stabilizers
+            .row(stabilizer)
+            .unwrap()
+            .non_trivial_positions()
+            .collect::<Vec<_>>();
+        let operator = |qubits: &[usize]| {
+            PauliOperator::new(
+                code.len(),
+                qubits.to_vec(),
+                vec![stabilizer_type; qubits.len()],
+            )
+        };
+        // A fault on the ancilla after the CNOT to qubit k - 1 spreads to
+        // the qubits k and after, which is equivalent to the qubits before k.
+        let hook_errors = std::iter::once(operator(&[]))
+            .chain((1..support.len()).map(|k| {
+                if 2 * (support.len() - k) > support.len() {
+                    operator(&support[..k])
+                } else {
+                    operator(&support[k..])
+                }
+            }))
+            .map(|error| (code.syndrome_of(&error), error))
+            .collect();
+        Self {
+            stabilizer_type,
+            stabilizer,
+            qubit,
+            measurement,
+            hook_errors,
+        }
+    }
+
+    /// Returns X or Z for a flag of an X or a Z stabilizer.
+    pub fn stabilizer_type(&self) -> Pauli {
+        self.stabilizer_type
+    }
+
+    /// Returns the index of the flagged stabilizer among those of the same type.
+    pub fn stabilizer(&self) -> usize {
+        self.stabilizer
+    }
+
+    /// Returns the flag qubit in the circuit.
+    pub fn qubit(&self) -> usize {
+        self.qubit
+    }
+
+    /// Returns the index of the measurement of the flag.
+    pub fn measurement(&self) -> usize {
+        self.measurement
+    }
+
+    /// Returns the data errors that a single fault raising the flag can cause,
+    /// starting with the identity.
+    pub fn hook_errors(&self) -> impl Iterator<Item = &PauliOperator> {
+        self.hook_errors.iter().map(|(_, error)| error)
+    }
+
+    /// Returns the first hook error with the given syndrome
+    /// or None if no hook error matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::circuits::ExtractionCircuit;
+    /// use pauli::{PauliOperator, Z};
+    ///
+    /// let code = CssCode::steane_code();
+    /// let extraction = ExtractionCircuit::flagged(&code, 4);
+    /// let flag = &extraction.flags()[0];
+    ///
+    /// let hook_error = flag.hook_errors().last().unwrap().clone();
+    /// let syndrome = code.syndrome_of(&hook_error);
+    /// assert_eq!(flag.correction_for(&syndrome), Some(&hook_error));
+    ///
+    /// // The hook errors of an X stabilizer are X errors.
+    /// let error = PauliOperator::new(7, vec![0], vec![Z]);
+    /// assert_eq!(flag.correction_for(&code.syndrome_of(&error)), None);
+    /// ```
+    pub fn correction_for(&self, syndrome: &CssSyndrome) -> Option<&PauliOperator> {
+        self.hook_errors
+            .iter()
+            .find(|(hook_syndrome, _)| hook_syndrome == syndrome)
+            .map(|(_, error)| error)
+    }
+}
+
+// The number of data qubits, ancillas and flags to measure the stabilizers of the code.
+pub(super) fn num_extraction_qubits(code: &CssCode, min_flag_weight: Option<usize>) -> usize {
+    let num_flags = code
+        .stabilizers
+        .x
+        .rows()
+        .chain(code.stabilizers.z.rows())
+        .filter(|stabilizer| is_flagged(stabilizer.weight(), min_flag_weight))
+        .count();
+    code.len() + code.num_x_stabs() + code.num_z_stabs() + num_flags
+}
+
+fn is_flagged(weight: usize, min_flag_weight: Option<usize>) -> bool {
+    matches!(min_flag_weight, Some(min) if weight >= min)
+}
+
+// Appends the measurement of each stabilizer to the circuit
+// and returns the measurement indices of the stabilizers and the flags.
+pub(super) fn append_extraction(
+    circuit: &mut Circuit,
+    code: &CssCode,
+    min_flag_weight: Option<usize>,
+) -> (Css<Vec<usize>>, Vec<Flag>) {
+    let mut next_flag = code.len() + code.num_x_stabs() + code.num_z_stabs();
+    let mut flags = Vec::new();
+    let mut measurements = Css {
+        x: Vec::new(),
+        z: Vec::new(),
+    };
+    let phases = [
+        (
+            Pauli::X,
+            &code.stabilizers.x,
+            code.len(),
+            &mut measurements.x,
+        ),
+        (
+            Pauli::Z,
+            &code.stabilizers.z,
+            code.len() + code.num_x_stabs(),
+            &mut measurements.z,
+        ),
+    ];
+    for (pauli, stabilizers, first_ancilla, measurements) in phases {
+        let mut flag_qubits = Vec::new();
+        let sequences = stabilizers
+            .rows()
+            .enumerate()
+            .map(|(stabilizer, support)| {
+                let support = support.non_trivial_positions().collect::<Vec<_>>();
+                let flag = if is_flagged(support.len(), min_flag_weight) {
+                    next_flag += 1;
+                    flag_qubits.push((stabilizer, next_flag - 1));
+                    Some(next_flag - 1)
+                } else {
+                    None
+                };
+                measurement_steps(pauli, first_ancilla + stabilizer, &support, flag)
+            })
+            .collect();
+        let records = schedule(circuit, sequences);
+        measurements.extend(records.iter().map(|records| records[0]));
+        for (stabilizer, qubit) in flag_qubits {
+            flags.push(Flag::new(
+                code,
+                pauli,
+                stabilizer,
+                qubit,
+                records[stabilizer][1],
+                stabilizers,
+            ));
+        }
+    }
+    (measurements, flags)
+}
+
+// The steps to measure a stabilizer.
+// The operations of a step are applied in the same moment.
+fn measurement_steps(
+    pauli: Pauli,
+    ancilla: usize,
+    support: &[usize],
+    flag: Option<usize>,
+) -> Vec<Vec<Operation>> {
+    let coupling = |qubit: usize| match pauli {
+        Pauli::X => vec![Operation::Cnot(ancilla, qubit)],
+        _ => vec![Operation::Cnot(qubit, ancilla)],
+    };
+    // The qubit prepared and measured in the X basis.
+    let plus_qubit = match pauli {
+        Pauli::X => Some(ancilla),
+        _ => flag,
+    };
+    let mut steps = vec![std::iter::once(Operation::Reset(ancilla))
+        .chain(flag.map(Operation::Reset))
+        .collect()];
+    steps.extend(plus_qubit.map(|qubit| vec![Operation::Hadamard(qubit)]));
+    match flag {
+        Some(flag) => {
+            let last = support.len() - 1;
+            steps.push(coupling(support[0]));
+            steps.push(coupling(flag));
+            steps.extend(support[1..last].iter().map(|qubit| coupling(*qubit)));
+            steps.push(coupling(flag));
+            steps.push(coupling(support[last]));
+        }
+        None => steps.extend(support.iter().map(|qubit| coupling(*qubit))),
+    }
+    steps.extend(plus_qubit.map(|qubit| vec![Operation::Hadamard(qubit)]));
+    steps.push(
+        std::iter::once(Operation::Measure(ancilla))
+            .chain(flag.map(Operation::Measure))
+            .collect(),
+    );
+    steps
+}
+
+// Applies the sequences of steps in parallel as early as possible
+// and returns the measurement indices of each sequence.
+fn schedule(circuit: &mut Circuit, sequences: Vec<Vec<Vec<Operation>>>) -> Vec<Vec<usize>> {
+    let mut next_steps = vec![0; sequences.len()];
+    let mut records = vec![Vec::new(); sequences.len()];
+    while next_steps
+        .iter()
+        .zip(sequences.iter())
+        .any(|(next, steps)| *next < steps.len())
+    {
+        for (sequence, steps) in sequences.iter().enumerate() {
+            let step = match steps.get(next_steps[sequence]) {
+                Some(step) => step,
+                None => continue,
+            };
+            let qubits = step
+                .iter()
+                .flat_map(|operation| operation.qubits())
+                .collect::<Vec<_>>();
+            if circuit.is_free(&qubits) {
+                for operation in step {
+                    if let Operation::Measure(_) = operation {
+                        records[sequence].push(circuit.num_measurements());
+                    }
+                    circuit.push(*operation);
+                }
+                next_steps[sequence] += 1;
+            }
+        }
+        circuit.tick();
+    }
+    records
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+
+    #[test]
+    fn surface_code_extraction_is_parallel() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+        let extraction = ExtractionCircuit::new(&code);
+        let circuit = extraction.circuit();
+        assert_eq!(circuit.num_qubits(), 25);
+        assert_eq!(circuit.num_measurements(), 12);
+        assert_eq!(
+            circuit
+                .operations()
+                .filter(|operation| matches!(operation, Operation::Cnot(_, _)))
+                .count(),
+            code.stabilizers.x.number_of_ones() + code.stabilizers.z.number_of_ones()
+        );
+        // Much less moments than operations.
+        assert!(circuit.num_moments() < 20);
+        let mut measurements = (0..6)
+            .map(|stabilizer| extraction.x_measurement(stabilizer))
+            .chain((0..6).map(|stabilizer| extraction.z_measurement(stabilizer)))
+            .collect::<Vec<_>>();
+        measurements.sort_unstable();
+        assert_eq!(measurements, (0..12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn flags_surround_the_inner_cnots() {
+        let code = CssCode::shor_code();
+        let extraction = ExtractionCircuit::flagged(&code, 6);
+        assert_eq!(extraction.flags().len(), 2);
+        let flag = &extraction.flags()[0];
+        assert_eq!(flag.stabilizer_type(), Pauli::X);
+        assert_eq!(flag.qubit(), 17);
+        let ancilla = 9;
+        let ancilla_cnots = extraction
+            .circuit()
+            .operations()
+            .filter_map(|operation| match operation {
+                Operation::Cnot(control, target) if *control == ancilla => Some(*target),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ancilla_cnots, vec![0, 17, 1, 2, 3, 4, 17, 5]);
+        // The identity and the 5 suffixes of the support up to the stabilizer.
+        assert_eq!(flag.hook_errors().count(), 6);
+        assert!(flag
+            .hook_errors()
+            .all(|error| error.weight() <= 3 && error.x_part() == *error));
+    }
+
+    #[test]
+    fn flag_measurements_follow_ancilla_measurements() {
+        let code = CssCode::steane_code();
+        let extraction = ExtractionCircuit::flagged(&code, 4);
+        for flag in extraction.flags() {
+            let ancilla_measurement = match flag.stabilizer_type() {
+                Pauli::X => extraction.x_measurement(flag.stabilizer()),
+                _ => extraction.z_measurement(flag.stabilizer()),
+            };
+            assert_eq!(flag.measurement(), ancilla_measurement + 1);
+        }
+    }
+}
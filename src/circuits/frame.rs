@@ -0,0 +1,296 @@
+use super::Operation;
+use crate::css::CssOperator;
+use pauli::{Pauli, PauliOperator};
+use rand::Rng;
+use sparse_bin_mat::SparseBinVec;
+
+const PAULIS: [Pauli; 4] = [Pauli::I, Pauli::X, Pauli::Z, Pauli::Y];
+
+/// The Pauli error on each qubit while it goes through Clifford operations.
+///
+/// Pushing a Pauli error through a Clifford operation gives another Pauli error,
+/// up to a phase that is ignored.
+/// For example, a Hadamard exchanges X and Z errors and
+/// a CNOT copies the X error of its control to its target
+/// and the Z error of its target to its control.
+///
+/// A measurement in the Z basis is flipped when the qubit has an X or a Y error.
+/// A reset removes the error of its qubit.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::circuits::{Operation, PauliFrame};
+/// use pauli::{PauliOperator, X, Z};
+///
+/// let mut frame = PauliFrame::from_operator(&PauliOperator::new(3, vec![0], vec![X]));
+/// frame.cnot(0, 1).hadamard(1);
+/// assert_eq!(frame.to_operator(), PauliOperator::new(3, vec![0, 1], vec![X, Z]));
+///
+/// // The X error of the first qubit flips its measurement but not the one of the last.
+/// assert_eq!(frame.apply(&Operation::Measure(0)), Some(true));
+/// assert_eq!(frame.apply(&Operation::Measure(2)), Some(false));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct PauliFrame {
+    x: Vec<bool>,
+    z: Vec<bool>,
+}
+
+impl PauliFrame {
+    /// Creates a frame without error on the given number of qubits.
+    pub fn new(num_qubits: usize) -> Self {
+        Self {
+            x: vec![false; num_qubits],
+            z: vec![false; num_qubits],
+        }
+    }
+
+    /// Creates a frame with the given error.
+    pub fn from_operator(operator: &PauliOperator) -> Self {
+        let mut frame = Self::new(operator.len());
+        let operator = CssOperator::from(operator);
+        for qubit in operator.x.non_trivial_positions() {
+            frame.x[qubit] = true;
+        }
+        for qubit in operator.z.non_trivial_positions() {
+            frame.z[qubit] = true;
+        }
+        frame
+    }
+
+    /// Returns the error of the frame.
+    pub fn to_operator(&self) -> PauliOperator {
+        let positions = |components: &[bool]| {
+            components
+                .iter()
+                .enumerate()
+                .filter(|(_, component)| **component)
+                .map(|(qubit, _)| qubit)
+                .collect()
+        };
+        PauliOperator::from(CssOperator {
+            x: SparseBinVec::new(self.num_qubits(), positions(&self.x)),
+            z: SparseBinVec::new(self.num_qubits(), positions(&self.z)),
+        })
+    }
+
+    /// Returns the number of qubits.
+    pub fn num_qubits(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Returns the error on the given qubit.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the qubit is out of bound.
+    pub fn pauli_at(&self, qubit: usize) -> Pauli {
+        match (self.x[qubit], self.z[qubit]) {
+            (false, false) => Pauli::I,
+            (true, false) => Pauli::X,
+            (true, true) => Pauli::Y,
+            (false, true) => Pauli::Z,
+        }
+    }
+
+    /// Checks if there is no error on any qubit.
+    pub fn is_identity(&self) -> bool {
+        self.x
+            .iter()
+            .chain(self.z.iter())
+            .all(|component| !component)
+    }
+
+    /// Multiplies the error on the given qubit by the Pauli.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the qubit is out of bound.
+    pub fn multiply_at(&mut self, qubit: usize, pauli: Pauli) -> &mut Self {
+        self.x[qubit] ^= matches!(pauli, Pauli::X | Pauli::Y);
+        self.z[qubit] ^= matches!(pauli, Pauli::Z | Pauli::Y);
+        self
+    }
+
+    /// Propagates the error through a Hadamard gate on the given qubit.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the qubit is out of bound.
+    pub fn hadamard(&mut self, qubit: usize) -> &mut Self {
+        std::mem::swap(&mut self.x[qubit], &mut self.z[qubit]);
+        self
+    }
+
+    /// Propagates the error through a phase gate on the given qubit,
+    /// exchanging X and Y errors.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the qubit is out of bound.
+    pub fn phase(&mut self, qubit: usize) -> &mut Self {
+        self.z[qubit] ^= self.x[qubit];
+        self
+    }
+
+    /// Propagates the error through a CNOT gate.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a qubit is out of bound or if the control is the target.
+    pub fn cnot(&mut self, control: usize, target: usize) -> &mut Self {
+        if control == target {
+            panic!("CNOT acts twice on qubit {}", control);
+        }
+        self.x[target] ^= self.x[control];
+        self.z[control] ^= self.z[target];
+        self
+    }
+
+    /// Returns true if the error flips the outcome
+    /// of a Z measurement of the given qubit.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the qubit is out of bound.
+    pub fn measure(&self, qubit: usize) -> bool {
+        self.x[qubit]
+    }
+
+    /// Removes the error on the given qubit.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the qubit is out of bound.
+    pub fn reset(&mut self, qubit: usize) -> &mut Self {
+        self.x[qubit] = false;
+        self.z[qubit] = false;
+        self
+    }
+
+    /// Propagates the error through the operation.
+    ///
+    /// Returns whether the outcome is flipped for a measurement and None otherwise.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a qubit of the operation is out of bound.
+    pub fn apply(&mut self, operation: &Operation) -> Option<bool> {
+        match *operation {
+            Operation::Reset(qubit) => {
+                self.reset(qubit);
+            }
+            Operation::Hadamard(qubit) => {
+                self.hadamard(qubit);
+            }
+            Operation::Cnot(control, target) => {
+                self.cnot(control, target);
+            }
+            Operation::Measure(qubit) => return Some(self.measure(qubit)),
+        }
+        None
+    }
+
+    pub(super) fn depolarize<R: Rng>(&mut self, qubit: usize, probability: f64, rng: &mut R) {
+        if probability > 0.0 && rng.gen_bool(probability) {
+            self.multiply_at(qubit, PAULIS[rng.gen_range(1..4)]);
+        }
+    }
+
+    pub(super) fn depolarize_pair<R: Rng>(
+        &mut self,
+        first: usize,
+        second: usize,
+        probability: f64,
+        rng: &mut R,
+    ) {
+        if probability > 0.0 && rng.gen_bool(probability) {
+            let (first_pauli, second_pauli) = non_trivial_pairs()[rng.gen_range(0..15)];
+            self.multiply_at(first, first_pauli)
+                .multiply_at(second, second_pauli);
+        }
+    }
+}
+
+impl<'a> From<&'a PauliOperator> for PauliFrame {
+    fn from(operator: &'a PauliOperator) -> Self {
+        Self::from_operator(operator)
+    }
+}
+
+// The 3 Paulis different from the identity.
+pub(super) fn non_trivial_paulis() -> [Pauli; 3] {
+    [PAULIS[1], PAULIS[2], PAULIS[3]]
+}
+
+// The 15 pairs of Paulis except the identity on both qubits.
+pub(super) fn non_trivial_pairs() -> Vec<(Pauli, Pauli)> {
+    (1..16)
+        .map(|index| (PAULIS[index & 3], PAULIS[index >> 2]))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pauli::{X, Y, Z};
+
+    fn conjugate(pauli: Pauli, gate: impl Fn(&mut PauliFrame)) -> Pauli {
+        let mut frame = PauliFrame::new(1);
+        frame.multiply_at(0, pauli);
+        gate(&mut frame);
+        frame.pauli_at(0)
+    }
+
+    #[test]
+    fn single_qubit_gates() {
+        let hadamard = |frame: &mut PauliFrame| {
+            frame.hadamard(0);
+        };
+        assert_eq!(conjugate(X, hadamard), Z);
+        assert_eq!(conjugate(Y, hadamard), Y);
+        assert_eq!(conjugate(Z, hadamard), X);
+        let phase = |frame: &mut PauliFrame| {
+            frame.phase(0);
+        };
+        assert_eq!(conjugate(X, phase), Y);
+        assert_eq!(conjugate(Y, phase), X);
+        assert_eq!(conjugate(Z, phase), Z);
+    }
+
+    #[test]
+    fn cnot_spreads_x_forward_and_z_backward() {
+        let mut frame = PauliFrame::new(3);
+        frame.multiply_at(0, X).multiply_at(2, Z);
+        frame.apply(&Operation::Cnot(0, 1));
+        frame.apply(&Operation::Cnot(1, 2));
+        assert_eq!(frame.x, vec![true, true, true]);
+        assert_eq!(frame.z, vec![false, true, true]);
+        frame.apply(&Operation::Hadamard(0));
+        frame.apply(&Operation::Reset(2));
+        assert_eq!(frame.x, vec![false, true, false]);
+        assert_eq!(frame.z, vec![true, true, false]);
+    }
+
+    #[test]
+    fn operator_roundtrip() {
+        let operator = PauliOperator::new(5, vec![0, 2, 4], vec![X, Y, Z]);
+        let frame = PauliFrame::from(&operator);
+        assert_eq!(frame.pauli_at(1), Pauli::I);
+        assert_eq!(frame.pauli_at(2), Y);
+        assert_eq!(frame.to_operator(), operator);
+        assert!(PauliFrame::new(5).is_identity());
+    }
+
+    #[test]
+    fn all_pairs_are_distinct() {
+        let pairs = non_trivial_pairs();
+        assert_eq!(pairs.len(), 15);
+        assert!(!pairs.contains(&(Pauli::I, Pauli::I)));
+        assert!(pairs
+            .iter()
+            .enumerate()
+            .all(|(index, pair)| !pairs[..index].contains(pair)));
+    }
+}
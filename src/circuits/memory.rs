@@ -0,0 +1,176 @@
+use super::extraction::{append_extraction, num_extraction_qubits};
+use super::{Circuit, CircuitNoise, DetectorErrorModel, Flag, Operation};
+use crate::codes::CssCode;
+use crate::css::Css;
+use pauli::Pauli;
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice};
+
+/// A memory experiment preserving a logical state of a CSS code
+/// through repeated rounds of stabilizer measurements.
+///
+/// The data qubits are prepared in the |0> state for a Z basis memory
+/// or in the |+> state for an X basis memory.
+/// Then, each round measures all the stabilizers as an
+/// [`ExtractionCircuit`](super::ExtractionCircuit) and the data qubits
+/// are finally measured in the memory basis.
+///
+/// The detectors are
+/// - the stabilizers of the memory basis in the first round,
+/// - the parity of two consecutive outcomes of each stabilizer,
+/// - the flags of each round,
+/// - the parity of the last outcome of each stabilizer of the memory basis
+///   with the final measurements of its support.
+///
+/// The observables are the logical operators of the memory basis
+/// computed from the final measurements.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::circuits::MemoryCircuit;
+/// use pauli::Pauli;
+///
+/// let code = CssCode::steane_code();
+/// let memory = MemoryCircuit::new(&code, 3, Pauli::Z);
+///
+/// // 3 rounds of 6 stabilizers followed by the 7 data qubits.
+/// assert_eq!(memory.circuit().num_measurements(), 25);
+/// // 3 Z stabilizers in the first round, 6 for each following round
+/// // and 3 for the final measurements.
+/// assert_eq!(memory.detectors().number_of_rows(), 18);
+/// assert_eq!(memory.observables().number_of_rows(), 1);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MemoryCircuit {
+    circuit: Circuit,
+    detectors: SparseBinMat,
+    observables: SparseBinMat,
+}
+
+impl MemoryCircuit {
+    /// Creates a memory experiment with the given number of rounds
+    /// in the X or Z basis.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the basis is not X or Z or if there is no round.
+    pub fn new(code: &CssCode, num_rounds: usize, basis: Pauli) -> Self {
+        Self::generate(code, num_rounds, basis, None)
+    }
+
+    /// Creates a memory experiment where each round uses a flag
+    /// for each stabilizer of weight at least the given weight.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the basis is not X or Z or if there is no round.
+    pub fn flagged(code: &CssCode, num_rounds: usize, basis: Pauli, min_weight: usize) -> Self {
+        Self::generate(code, num_rounds, basis, Some(min_weight.max(3)))
+    }
+
+    fn generate(
+        code: &CssCode,
+        num_rounds: usize,
+        basis: Pauli,
+        min_flag_weight: Option<usize>,
+    ) -> Self {
+        if !matches!(basis, Pauli::X | Pauli::Z) {
+            panic!("memory basis must be X or Z");
+        }
+        if num_rounds == 0 {
+            panic!("memory experiment without round");
+        }
+        let mut circuit = Circuit::new(num_extraction_qubits(code, min_flag_weight));
+        let data_layer = |circuit: &mut Circuit, operation: fn(usize) -> Operation| {
+            for qubit in 0..code.len() {
+                circuit.push(operation(qubit));
+            }
+            circuit.tick();
+        };
+        data_layer(&mut circuit, Operation::Reset);
+        if basis == Pauli::X {
+            data_layer(&mut circuit, Operation::Hadamard);
+        }
+        let rounds = (0..num_rounds)
+            .map(|_| append_extraction(&mut circuit, code, min_flag_weight))
+            .collect::<Vec<_>>();
+        if basis == Pauli::X {
+            data_layer(&mut circuit, Operation::Hadamard);
+        }
+        let first_data_measurement = circuit.num_measurements();
+        data_layer(&mut circuit, Operation::Measure);
+
+        let data_measurements = |support: SparseBinSlice| {
+            support
+                .non_trivial_positions()
+                .map(|qubit| first_data_measurement + qubit)
+                .collect::<Vec<_>>()
+        };
+        let (stabilizers, logicals) = match basis {
+            Pauli::X => (&code.stabilizers.x, &code.logicals.x),
+            _ => (&code.stabilizers.z, &code.logicals.z),
+        };
+        let basis_measurements = |measurements: &Css<Vec<usize>>| match basis {
+            Pauli::X => measurements.x.clone(),
+            _ => measurements.z.clone(),
+        };
+        let flag_detectors = |flags: &[Flag]| {
+            flags
+                .iter()
+                .map(|flag| vec![flag.measurement()])
+                .collect::<Vec<_>>()
+        };
+        let mut detectors = basis_measurements(&rounds[0].0)
+            .into_iter()
+            .map(|measurement| vec![measurement])
+            .collect::<Vec<_>>();
+        detectors.extend(flag_detectors(&rounds[0].1));
+        for pair in rounds.windows(2) {
+            let (previous, current) = (&pair[0].0, &pair[1].0);
+            let consecutive = previous
+                .x
+                .iter()
+                .zip(current.x.iter())
+                .chain(previous.z.iter().zip(current.z.iter()));
+            detectors.extend(consecutive.map(|(previous, current)| vec![*previous, *current]));
+            detectors.extend(flag_detectors(&pair[1].1));
+        }
+        let last_measurements = basis_measurements(&rounds[num_rounds - 1].0);
+        detectors.extend(stabilizers.rows().zip(last_measurements).map(
+            |(stabilizer, measurement)| {
+                let mut detector = vec![measurement];
+                detector.extend(data_measurements(stabilizer));
+                detector
+            },
+        ));
+        let observables = logicals.rows().map(data_measurements).collect();
+        Self {
+            detectors: SparseBinMat::new(circuit.num_measurements(), detectors),
+            observables: SparseBinMat::new(circuit.num_measurements(), observables),
+            circuit,
+        }
+    }
+
+    /// Returns the circuit.
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// Returns the detectors as a matrix
+    /// with a row for each detector and a column for each measurement.
+    pub fn detectors(&self) -> &SparseBinMat {
+        &self.detectors
+    }
+
+    /// Returns the observables as a matrix
+    /// with a row for each logical operator and a column for each measurement.
+    pub fn observables(&self) -> &SparseBinMat {
+        &self.observables
+    }
+
+    /// Enumerates the single faults of the circuit under the given noise.
+    pub fn error_model(&self, noise: CircuitNoise) -> DetectorErrorModel {
+        DetectorErrorModel::new(&self.circuit, &self.detectors, &self.observables, noise)
+    }
+}
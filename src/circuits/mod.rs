@@ -0,0 +1,40 @@
+//! Quantum circuits implementing codes.
+//!
+//! A [`Circuit`](Circuit) is a sequence of moments of [operations](Operation).
+//! The [`ExtractionCircuit`](ExtractionCircuit) measures the stabilizers
+//! of a CSS code, optionally using [flag qubits](Flag) to catch
+//! the faults spreading to many data qubits.
+//!
+//! The [`CircuitSampler`](CircuitSampler) propagates a [`PauliFrame`](PauliFrame)
+//! through circuits under [circuit-level noise](CircuitNoise)
+//! to sample detector outcomes.
+//! A [`MemoryCircuit`](MemoryCircuit) repeats the stabilizer measurements
+//! of a code and its [`DetectorErrorModel`](DetectorErrorModel) lists the detectors
+//! and observables flipped by each single fault, which can be decoded
+//! like a classical code.
+//! The [`GadgetVerifier`](GadgetVerifier) enumerates the single and double faults
+//! of an extraction circuit to check that it is fault tolerant.
+
+mod circuit;
+pub use circuit::{Circuit, Operation};
+
+mod error_model;
+pub use error_model::DetectorErrorModel;
+
+mod extraction;
+pub use extraction::{ExtractionCircuit, Flag};
+
+mod frame;
+pub use frame::PauliFrame;
+
+mod memory;
+pub use memory::MemoryCircuit;
+
+mod noise;
+pub use noise::CircuitNoise;
+
+mod sampler;
+pub use sampler::CircuitSampler;
+
+mod verification;
+pub use verification::{CircuitFault, GadgetVerifier};
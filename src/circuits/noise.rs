@@ -0,0 +1,114 @@
+use crate::noise::Probability;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The probabilities of the faults of each operation of a circuit.
+///
+/// - After each CNOT, one of the 15 non-trivial two-qubit Paulis is applied
+///   with the two-qubit gate probability.
+/// - After each Hadamard, one of the 3 non-trivial Paulis is applied
+///   with the single-qubit gate probability.
+/// - Each qubit not used in a moment gets one of the 3 non-trivial Paulis
+///   with the idle probability.
+/// - Each measurement outcome is flipped with the measurement probability.
+/// - Each reset prepares the |1> state instead of the |0> state
+///   with the reset probability.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::circuits::CircuitNoise;
+/// use ldpc::noise::Probability;
+///
+/// let noise = CircuitNoise::uniform(Probability::new(0.001))
+///     .with_idle(Probability::new(0.0001));
+///
+/// assert_eq!(noise.two_qubit_gate(), 0.001);
+/// assert_eq!(noise.idle(), 0.0001);
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct CircuitNoise {
+    two_qubit_gate: f64,
+    single_qubit_gate: f64,
+    idle: f64,
+    measurement: f64,
+    reset: f64,
+}
+
+impl CircuitNoise {
+    /// Creates a noise model where every fault has the given probability.
+    pub fn uniform(probability: Probability) -> Self {
+        Self {
+            two_qubit_gate: probability.value(),
+            single_qubit_gate: probability.value(),
+            idle: probability.value(),
+            measurement: probability.value(),
+            reset: probability.value(),
+        }
+    }
+
+    /// Creates a noise model without fault.
+    pub fn noiseless() -> Self {
+        Self::uniform(Probability::new(0.0))
+    }
+
+    /// Sets the probability of a fault after each CNOT.
+    pub fn with_two_qubit_gate(mut self, probability: Probability) -> Self {
+        self.two_qubit_gate = probability.value();
+        self
+    }
+
+    /// Sets the probability of a fault after each Hadamard.
+    pub fn with_single_qubit_gate(mut self, probability: Probability) -> Self {
+        self.single_qubit_gate = probability.value();
+        self
+    }
+
+    /// Sets the probability of a fault on each idle qubit in each moment.
+    pub fn with_idle(mut self, probability: Probability) -> Self {
+        self.idle = probability.value();
+        self
+    }
+
+    /// Sets the probability of flipping each measurement outcome.
+    pub fn with_measurement(mut self, probability: Probability) -> Self {
+        self.measurement = probability.value();
+        self
+    }
+
+    /// Sets the probability of a wrong state after each reset.
+    pub fn with_reset(mut self, probability: Probability) -> Self {
+        self.reset = probability.value();
+        self
+    }
+
+    pub fn two_qubit_gate(&self) -> f64 {
+        self.two_qubit_gate
+    }
+
+    pub fn single_qubit_gate(&self) -> f64 {
+        self.single_qubit_gate
+    }
+
+    pub fn idle(&self) -> f64 {
+        self.idle
+    }
+
+    pub fn measurement(&self) -> f64 {
+        self.measurement
+    }
+
+    pub fn reset(&self) -> f64 {
+        self.reset
+    }
+}
+
+impl fmt::Display for CircuitNoise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Circuit Noise (two-qubit gate = {}, single-qubit gate = {}, idle = {}, measurement = {}, reset = {})",
+            self.two_qubit_gate, self.single_qubit_gate, self.idle, self.measurement, self.reset
+        )
+    }
+}
@@ -0,0 +1,145 @@
+use super::{Circuit, CircuitNoise, Operation, PauliFrame};
+use pauli::Pauli;
+use rand::Rng;
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+
+/// Samples the measurement outcomes of a noisy circuit relative to the noiseless circuit.
+///
+/// The sampler tracks the [`PauliFrame`](PauliFrame), that is the Pauli error
+/// accumulated on each qubit, through the operations of the circuit.
+/// Faults are inserted following a [`CircuitNoise`](CircuitNoise)
+/// and a measurement outcome is flipped when an X or Y error
+/// reaches the measured qubit.
+/// Resets remove the errors on their qubit.
+///
+/// Flips are enough to compute detectors, that is
+/// parities of measurements that are deterministic without noise.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::circuits::{Circuit, CircuitNoise, CircuitSampler, Operation};
+/// use ldpc::noise::Probability;
+/// use rand::thread_rng;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let mut circuit = Circuit::new(2);
+/// circuit.push(Operation::Reset(0)).push(Operation::Reset(1));
+/// circuit.tick().push(Operation::Cnot(0, 1));
+/// circuit.tick().push(Operation::Measure(0)).push(Operation::Measure(1));
+///
+/// // Only resets are faulty and the X error spreading from the first qubit
+/// // cancels the one of the second qubit.
+/// let noise = CircuitNoise::noiseless().with_reset(Probability::new(1.0));
+/// let sampler = CircuitSampler::new(&circuit, noise);
+///
+/// let flips = sampler.sample_measurement_flips(&mut thread_rng());
+/// assert_eq!(flips, SparseBinVec::new(2, vec![0]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitSampler<'a> {
+    circuit: &'a Circuit,
+    noise: CircuitNoise,
+}
+
+impl<'a> CircuitSampler<'a> {
+    /// Creates a sampler for the circuit with the given noise.
+    pub fn new(circuit: &'a Circuit, noise: CircuitNoise) -> Self {
+        Self { circuit, noise }
+    }
+
+    /// Samples which measurement outcomes are flipped by the noise.
+    pub fn sample_measurement_flips<R: Rng>(&self, rng: &mut R) -> SparseBinVec {
+        let mut frame = PauliFrame::new(self.circuit.num_qubits());
+        let mut flips = Vec::new();
+        let mut num_measurements = 0;
+        let mut is_idle = vec![true; self.circuit.num_qubits()];
+        for moment in self.circuit.moments() {
+            is_idle.iter_mut().for_each(|idle| *idle = true);
+            for operation in moment {
+                for qubit in operation.qubits() {
+                    is_idle[qubit] = false;
+                }
+                match *operation {
+                    Operation::Measure(qubit) => {
+                        if frame.measure(qubit) ^ rng.gen_bool(self.noise.measurement()) {
+                            flips.push(num_measurements);
+                        }
+                        num_measurements += 1;
+                    }
+                    Operation::Reset(qubit) => {
+                        frame.reset(qubit);
+                        if rng.gen_bool(self.noise.reset()) {
+                            frame.multiply_at(qubit, Pauli::X);
+                        }
+                    }
+                    Operation::Hadamard(qubit) => {
+                        frame.apply(operation);
+                        frame.depolarize(qubit, self.noise.single_qubit_gate(), rng);
+                    }
+                    Operation::Cnot(control, target) => {
+                        frame.apply(operation);
+                        frame.depolarize_pair(control, target, self.noise.two_qubit_gate(), rng);
+                    }
+                }
+            }
+            for (qubit, idle) in is_idle.iter().enumerate() {
+                if *idle {
+                    frame.depolarize(qubit, self.noise.idle(), rng);
+                }
+            }
+        }
+        SparseBinVec::new(self.circuit.num_measurements(), flips)
+    }
+
+    /// Samples which detectors are flipped by the noise.
+    ///
+    /// Each row of the matrix is a detector given by the measurements
+    /// whose parity is deterministic without noise.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of columns of the detectors is different
+    /// from the number of measurements.
+    pub fn sample_detectors<R: Rng>(&self, detectors: &SparseBinMat, rng: &mut R) -> SparseBinVec {
+        if detectors.number_of_columns() != self.circuit.num_measurements() {
+            panic!(
+                "detectors on {} measurements for circuit with {} measurements",
+                detectors.number_of_columns(),
+                self.circuit.num_measurements()
+            );
+        }
+        detectors * &self.sample_measurement_flips(rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::circuits::ExtractionCircuit;
+    use crate::codes::CssCode;
+    use crate::noise::Probability;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn noiseless_extraction_has_no_detection_event() {
+        let code = CssCode::steane_code();
+        let extraction = ExtractionCircuit::flagged(&code, 4);
+        let sampler = CircuitSampler::new(extraction.circuit(), CircuitNoise::noiseless());
+        let mut rng = StdRng::seed_from_u64(123);
+        let detectors = sampler.sample_detectors(&extraction.detectors(), &mut rng);
+        assert!(detectors.is_zero());
+    }
+
+    #[test]
+    fn measurement_faults_flip_every_detector() {
+        let code = CssCode::steane_code();
+        let extraction = ExtractionCircuit::flagged(&code, 4);
+        let noise = CircuitNoise::noiseless().with_measurement(Probability::new(1.0));
+        let sampler = CircuitSampler::new(extraction.circuit(), noise);
+        let mut rng = StdRng::seed_from_u64(123);
+        let detectors = sampler.sample_detectors(&extraction.detectors(), &mut rng);
+        assert_eq!(detectors.weight(), 12);
+    }
+}
@@ -0,0 +1,345 @@
+use super::frame::{non_trivial_pairs, non_trivial_paulis};
+use super::{ExtractionCircuit, Operation, PauliFrame};
+use crate::codes::CssCode;
+use crate::css::{CssOperator, CssSyndrome};
+use pauli::{Pauli, PauliOperator};
+use sparse_bin_mat::SparseBinVec;
+use std::collections::HashMap;
+
+/// A single fault of a circuit.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CircuitFault {
+    /// A Pauli error on the qubits of an operation at the end of the given moment.
+    Pauli { moment: usize, error: PauliOperator },
+    /// A flipped outcome of the measurement with the given index.
+    Measurement(usize),
+}
+
+/// Enumerates the single and double faults of an [`ExtractionCircuit`](ExtractionCircuit)
+/// to verify that it is fault tolerant for a code correcting a single error.
+///
+/// Each operation is a fault location followed by every non-trivial Pauli on its qubits
+/// and each measurement outcome can be flipped. Idle qubits are not fault locations.
+/// A fault is propagated to the end of the circuit where it leaves an error on the data
+/// and possibly raises some flags.
+///
+/// The faults are judged with an ideal round of measurements afterward.
+/// When flags are raised, the data error is first multiplied by one of their
+/// [hook errors](super::Flag::hook_errors).
+/// The faults are benign if the remaining error is equivalent
+/// to an error of weight at most 1 up to a stabilizer, such that it is corrected,
+/// and malignant otherwise.
+///
+/// The circuit is fault tolerant if no single fault is malignant.
+/// Counting the malignant pairs of faults, as in the extended rectangle analysis,
+/// bounds the logical error rate of the gadget
+/// by about the number of malignant pairs times the square of the fault probability.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::circuits::{ExtractionCircuit, GadgetVerifier};
+///
+/// let code = CssCode::steane_code();
+///
+/// // A fault on an ancilla spreads to 2 data qubits without flags.
+/// let extraction = ExtractionCircuit::new(&code);
+/// let verifier = GadgetVerifier::new(&code, &extraction);
+/// assert!(!verifier.is_fault_tolerant());
+///
+/// let extraction = ExtractionCircuit::flagged(&code, 4);
+/// let verifier = GadgetVerifier::new(&code, &extraction);
+/// assert!(verifier.is_fault_tolerant());
+/// ```
+#[derive(Debug, Clone)]
+pub struct GadgetVerifier<'a> {
+    code: &'a CssCode,
+    extraction: &'a ExtractionCircuit,
+    faults: Vec<CircuitFault>,
+    effects: Vec<FaultEffect>,
+    // A correction of weight at most 1 for each syndrome that has one.
+    corrections: HashMap<CssSyndrome, PauliOperator>,
+}
+
+// The error left on the data and the flags raised by some faults.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+struct FaultEffect {
+    data: CssOperator,
+    raised_flags: Vec<usize>,
+}
+
+impl FaultEffect {
+    fn combined_with(&self, other: &Self) -> Self {
+        let mut raised_flags = self
+            .raised_flags
+            .iter()
+            .filter(|flag| !other.raised_flags.contains(flag))
+            .chain(
+                other
+                    .raised_flags
+                    .iter()
+                    .filter(|flag| !self.raised_flags.contains(flag)),
+            )
+            .cloned()
+            .collect::<Vec<_>>();
+        raised_flags.sort_unstable();
+        Self {
+            data: CssOperator {
+                x: &self.data.x + &other.data.x,
+                z: &self.data.z + &other.data.z,
+            },
+            raised_flags,
+        }
+    }
+}
+
+impl<'a> GadgetVerifier<'a> {
+    /// Enumerates the single faults of the extraction circuit of the code.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of data qubits of the circuit is different from the code length.
+    pub fn new(code: &'a CssCode, extraction: &'a ExtractionCircuit) -> Self {
+        if extraction.num_data_qubits() != code.len() {
+            panic!(
+                "circuit with {} data qubits for code of length {}",
+                extraction.num_data_qubits(),
+                code.len()
+            );
+        }
+        let faults = enumerate_faults(extraction);
+        let effects = faults
+            .iter()
+            .map(|fault| effect_of(extraction, fault))
+            .collect();
+        let identity = PauliOperator::new(code.len(), Vec::new(), Vec::new());
+        let mut corrections = HashMap::new();
+        let single_qubit_errors = (0..code.len()).flat_map(|qubit| {
+            non_trivial_paulis()
+                .into_iter()
+                .map(move |pauli| PauliOperator::new(code.len(), vec![qubit], vec![pauli]))
+        });
+        for error in std::iter::once(identity).chain(single_qubit_errors) {
+            corrections.entry(code.syndrome_of(&error)).or_insert(error);
+        }
+        Self {
+            code,
+            extraction,
+            faults,
+            effects,
+            corrections,
+        }
+    }
+
+    /// Returns the single faults of the circuit.
+    pub fn faults(&self) -> &[CircuitFault] {
+        &self.faults
+    }
+
+    /// Returns the single faults that are malignant.
+    pub fn single_fault_violations(&self) -> Vec<&CircuitFault> {
+        let mut cache = HashMap::new();
+        self.faults
+            .iter()
+            .zip(self.effects.iter())
+            .filter(|(_, effect)| !self.is_benign_cached(effect, &mut cache))
+            .map(|(fault, _)| fault)
+            .collect()
+    }
+
+    /// Checks if no single fault is malignant.
+    pub fn is_fault_tolerant(&self) -> bool {
+        self.single_fault_violations().is_empty()
+    }
+
+    /// Returns the pairs of distinct faults that are malignant together.
+    pub fn malignant_pairs(&self) -> Vec<(&CircuitFault, &CircuitFault)> {
+        let mut cache = HashMap::new();
+        let mut pairs = Vec::new();
+        for (first, first_effect) in self.effects.iter().enumerate() {
+            for (second, second_effect) in self.effects.iter().enumerate().skip(first + 1) {
+                let effect = first_effect.combined_with(second_effect);
+                if !self.is_benign_cached(&effect, &mut cache) {
+                    pairs.push((&self.faults[first], &self.faults[second]));
+                }
+            }
+        }
+        pairs
+    }
+
+    fn is_benign_cached(
+        &self,
+        effect: &FaultEffect,
+        cache: &mut HashMap<FaultEffect, bool>,
+    ) -> bool {
+        if let Some(is_benign) = cache.get(effect) {
+            return *is_benign;
+        }
+        let is_benign = self.is_benign(effect);
+        cache.insert(effect.clone(), is_benign);
+        is_benign
+    }
+
+    fn is_benign(&self, effect: &FaultEffect) -> bool {
+        let error = PauliOperator::from(&effect.data);
+        let identity = PauliOperator::new(self.code.len(), Vec::new(), Vec::new());
+        let hook_errors = effect
+            .raised_flags
+            .iter()
+            .flat_map(|flag| self.extraction.flags()[*flag].hook_errors())
+            .collect::<Vec<_>>();
+        let candidates = if hook_errors.is_empty() {
+            vec![&identity]
+        } else {
+            hook_errors
+        };
+        candidates.into_iter().any(|hook_error| {
+            let remaining = &error * hook_error;
+            self.corrections
+                .get(&self.code.syndrome_of(&remaining))
+                .is_some_and(|correction| self.code.has_stabilizer(&(&remaining * correction)))
+        })
+    }
+}
+
+// Each operation followed by every Pauli on its qubits and each flipped measurement.
+fn enumerate_faults(extraction: &ExtractionCircuit) -> Vec<CircuitFault> {
+    let num_qubits = extraction.circuit().num_qubits();
+    let mut faults = Vec::new();
+    let mut next_measurement = 0;
+    for (moment, operations) in extraction.circuit().moments().iter().enumerate() {
+        for operation in operations {
+            match *operation {
+                Operation::Measure(_) => {
+                    faults.push(CircuitFault::Measurement(next_measurement));
+                    next_measurement += 1;
+                }
+                Operation::Reset(qubit) | Operation::Hadamard(qubit) => {
+                    faults.extend(non_trivial_paulis().into_iter().map(|pauli| {
+                        let mut frame = PauliFrame::new(num_qubits);
+                        frame.multiply_at(qubit, pauli);
+                        CircuitFault::Pauli {
+                            moment,
+                            error: frame.to_operator(),
+                        }
+                    }));
+                }
+                Operation::Cnot(control, target) => {
+                    faults.extend(non_trivial_pairs().into_iter().map(|(first, second)| {
+                        let mut frame = PauliFrame::new(num_qubits);
+                        frame
+                            .multiply_at(control, first)
+                            .multiply_at(target, second);
+                        CircuitFault::Pauli {
+                            moment,
+                            error: frame.to_operator(),
+                        }
+                    }));
+                }
+            }
+        }
+    }
+    faults
+}
+
+// Propagates the fault to the end of the circuit.
+fn effect_of(extraction: &ExtractionCircuit, fault: &CircuitFault) -> FaultEffect {
+    let num_data_qubits = extraction.num_data_qubits();
+    let (moment, error) = match fault {
+        CircuitFault::Measurement(measurement) => {
+            let raised_flags = extraction
+                .flags()
+                .iter()
+                .enumerate()
+                .filter(|(_, flag)| flag.measurement() == *measurement)
+                .map(|(index, _)| index)
+                .collect();
+            return FaultEffect {
+                data: CssOperator {
+                    x: SparseBinVec::zeros(num_data_qubits),
+                    z: SparseBinVec::zeros(num_data_qubits),
+                },
+                raised_flags,
+            };
+        }
+        CircuitFault::Pauli { moment, error } => (*moment, error),
+    };
+    let mut frame = PauliFrame::from_operator(error);
+    let mut flipped = Vec::new();
+    let mut next_measurement = 0;
+    for (index, operations) in extraction.circuit().moments().iter().enumerate() {
+        for operation in operations {
+            if index <= moment {
+                if let Operation::Measure(_) = operation {
+                    next_measurement += 1;
+                }
+                continue;
+            }
+            if let Some(is_flipped) = frame.apply(operation) {
+                if is_flipped {
+                    flipped.push(next_measurement);
+                }
+                next_measurement += 1;
+            }
+        }
+    }
+    let raised_flags = extraction
+        .flags()
+        .iter()
+        .enumerate()
+        .filter(|(_, flag)| flipped.contains(&flag.measurement()))
+        .map(|(index, _)| index)
+        .collect();
+    let data = |pauli: Pauli| {
+        SparseBinVec::new(
+            num_data_qubits,
+            (0..num_data_qubits)
+                .filter(|qubit| {
+                    let current = frame.pauli_at(*qubit);
+                    current == pauli || current == Pauli::Y
+                })
+                .collect(),
+        )
+    };
+    FaultEffect {
+        data: CssOperator {
+            x: data(Pauli::X),
+            z: data(Pauli::Z),
+        },
+        raised_flags,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn malignant_pairs_of_the_flagged_steane_code() {
+        let code = CssCode::steane_code();
+        let extraction = ExtractionCircuit::flagged(&code, 4);
+        let verifier = GadgetVerifier::new(&code, &extraction);
+        assert!(verifier.single_fault_violations().is_empty());
+        let pairs = verifier.malignant_pairs();
+        let num_faults = verifier.faults().len();
+        assert!(!pairs.is_empty());
+        assert!(pairs.len() < num_faults * (num_faults - 1) / 2);
+        // Flipped stabilizer outcomes are forgotten by the ideal round afterward.
+        let flag_measurements = extraction
+            .flags()
+            .iter()
+            .map(|flag| flag.measurement())
+            .collect::<Vec<_>>();
+        assert!(pairs
+            .iter()
+            .all(|pair| [pair.0, pair.1].iter().all(|fault| {
+                match fault {
+                    CircuitFault::Measurement(measurement) => {
+                        flag_measurements.contains(measurement)
+                    }
+                    _ => true,
+                }
+            })));
+    }
+}
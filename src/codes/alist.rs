@@ -0,0 +1,297 @@
+use sparse_bin_mat::SparseBinMat;
+use std::fmt;
+use std::io::{self, BufRead};
+
+/// An error when reading a matrix in the alist format.
+///
+/// Lines are counted from 1.
+#[derive(Debug)]
+pub enum AlistError {
+    Io(io::Error),
+    /// The input ends before the given line.
+    UnexpectedEnd {
+        line: usize,
+    },
+    /// The given line is malformed or inconsistent with the previous ones.
+    InvalidLine {
+        line: usize,
+        reason: String,
+    },
+}
+
+impl fmt::Display for AlistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "can't read alist: {}", error),
+            Self::UnexpectedEnd { line } => write!(f, "alist ends before line {}", line),
+            Self::InvalidLine { line, reason } => {
+                write!(f, "invalid alist line {}: {}", line, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AlistError {}
+
+impl From<io::Error> for AlistError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Reads a matrix in the alist format one line at a time.
+///
+/// The first line is the number of columns and rows,
+/// the second line is the maximum column and row weights,
+/// followed by the line of column weights, the line of row weights,
+/// then one line for each column and one line for each row
+/// with the positions of their ones counted from 1,
+/// optionally padded with zeros.
+/// Empty lines are skipped.
+///
+/// Only the matrix is kept in memory, never the whole input,
+/// such that very large matrices can be read from a buffered file.
+/// Every line is validated: the weights must match the declared weights
+/// and the maximums, the positions must be increasing and in bound
+/// and the rows must be the transpose of the columns.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::{read_alist, AlistError};
+/// use sparse_bin_mat::SparseBinMat;
+///
+/// // The parity checks of the 3 bits repetition code.
+/// let alist = "3 2\n2 2\n1 2 1\n2 2\n1\n1 2\n2\n1 2\n2 3\n";
+/// let matrix = read_alist(alist.as_bytes()).unwrap();
+/// assert_eq!(matrix, SparseBinMat::new(3, vec![vec![0, 1], vec![1, 2]]));
+///
+/// // The last row doesn't match the columns.
+/// let alist = "3 2\n2 2\n1 2 1\n2 2\n1\n1 2\n2\n1 2\n1 3\n";
+/// assert!(matches!(
+///     read_alist(alist.as_bytes()),
+///     Err(AlistError::InvalidLine { line: 9, .. })
+/// ));
+/// ```
+pub fn read_alist<R: BufRead>(reader: R) -> Result<SparseBinMat, AlistError> {
+    let mut lines = Lines::new(reader);
+    let (num_columns, num_rows) = lines.pair("number of columns and rows")?;
+    let (max_column_weight, max_row_weight) = lines.pair("maximum weights")?;
+    if max_column_weight > num_rows || max_row_weight > num_columns {
+        return Err(lines.invalid(format!(
+            "the maximum weights {} {} exceed the dimensions",
+            max_column_weight, max_row_weight
+        )));
+    }
+    let column_weights = lines.weights(num_columns, max_column_weight, "column")?;
+    let row_weights = lines.weights(num_rows, max_row_weight, "row")?;
+    let mut rows = vec![Vec::new(); row_weights.len()];
+    // Without any one, the lines of the columns and rows are empty and skipped.
+    if max_column_weight == 0 {
+        return lines.end(SparseBinMat::new(num_columns, rows));
+    }
+    for (column, weight) in column_weights.iter().enumerate() {
+        for row in lines.positions(*weight, num_rows, "column", column)? {
+            rows[row].push(column);
+        }
+    }
+    for (row, weight) in row_weights.iter().enumerate() {
+        let positions = lines.positions(*weight, num_columns, "row", row)?;
+        if positions != rows[row] {
+            return Err(lines.invalid(format!(
+                "row {} is not the transpose of the columns",
+                row + 1
+            )));
+        }
+    }
+    lines.end(SparseBinMat::new(num_columns, rows))
+}
+
+// The non-empty lines of the input parsed as integers.
+struct Lines<R> {
+    reader: R,
+    buffer: String,
+    line: usize,
+}
+
+impl<R: BufRead> Lines<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+            line: 0,
+        }
+    }
+
+    fn invalid(&self, reason: String) -> AlistError {
+        AlistError::InvalidLine {
+            line: self.line,
+            reason,
+        }
+    }
+
+    fn next_values(&mut self) -> Result<Option<Vec<usize>>, AlistError> {
+        loop {
+            self.buffer.clear();
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                return Ok(None);
+            }
+            self.line += 1;
+            if self.buffer.trim().is_empty() {
+                continue;
+            }
+            let values = self
+                .buffer
+                .split_whitespace()
+                .map(|token| {
+                    token.parse().map_err(|_| {
+                        self.invalid(format!("{} is not a non-negative integer", token))
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            return Ok(Some(values));
+        }
+    }
+
+    fn end(&mut self, matrix: SparseBinMat) -> Result<SparseBinMat, AlistError> {
+        match self.next_values()? {
+            Some(_) => Err(self.invalid("unexpected content after the rows".to_string())),
+            None => Ok(matrix),
+        }
+    }
+
+    fn expect_values(&mut self) -> Result<Vec<usize>, AlistError> {
+        self.next_values()?.ok_or(AlistError::UnexpectedEnd {
+            line: self.line + 1,
+        })
+    }
+
+    fn pair(&mut self, name: &str) -> Result<(usize, usize), AlistError> {
+        match self.expect_values()?.as_slice() {
+            [first, second] => Ok((*first, *second)),
+            values => Err(self.invalid(format!(
+                "expected the {} but found {} values",
+                name,
+                values.len()
+            ))),
+        }
+    }
+
+    fn weights(
+        &mut self,
+        length: usize,
+        max_weight: usize,
+        name: &str,
+    ) -> Result<Vec<usize>, AlistError> {
+        let weights = if length == 0 {
+            Vec::new()
+        } else {
+            self.expect_values()?
+        };
+        if weights.len() != length {
+            return Err(self.invalid(format!(
+                "expected {} {} weights but found {}",
+                length,
+                name,
+                weights.len()
+            )));
+        }
+        if weights.iter().max().cloned().unwrap_or(0) != max_weight {
+            return Err(self.invalid(format!("the maximum {} weight is not {}", name, max_weight)));
+        }
+        Ok(weights)
+    }
+
+    // The positions counted from 0 of a column or a row with the given weight
+    // among the given number of positions.
+    fn positions(
+        &mut self,
+        weight: usize,
+        bound: usize,
+        name: &str,
+        index: usize,
+    ) -> Result<Vec<usize>, AlistError> {
+        let values = self.expect_values()?;
+        let num_ones = values.iter().take_while(|value| **value != 0).count();
+        if values[num_ones..].iter().any(|value| *value != 0) {
+            return Err(self.invalid(format!(
+                "{} {} has a position after the padding",
+                name,
+                index + 1
+            )));
+        }
+        if num_ones != weight {
+            return Err(self.invalid(format!(
+                "{} {} has {} positions instead of {}",
+                name,
+                index + 1,
+                num_ones,
+                weight
+            )));
+        }
+        if let Some(position) = values[..num_ones].iter().find(|value| **value > bound) {
+            return Err(self.invalid(format!(
+                "position {} of {} {} is out of bound for {} positions",
+                position,
+                name,
+                index + 1,
+                bound
+            )));
+        }
+        if values[..num_ones].windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(self.invalid(format!(
+                "positions of {} {} are not increasing",
+                name,
+                index + 1
+            )));
+        }
+        Ok(values[..num_ones].iter().map(|value| value - 1).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::{LinearCode, ProductMatrix};
+
+    #[test]
+    fn written_alist_is_read_back() {
+        let code = LinearCode::repetition_code(3);
+        let checks = ProductMatrix::hypergraph_product(&code, &code);
+        let mut alist = Vec::new();
+        checks.z.write_alist(&mut alist).unwrap();
+        let matrix = read_alist(alist.as_slice()).unwrap();
+        assert_eq!(matrix, checks.z.to_sparse_bin_mat());
+    }
+
+    #[test]
+    fn malformed_lines_are_located() {
+        let cases = [
+            ("3 2\n2 2\n1 2 1\n2 2\n", 5),
+            ("3 2\n2 2\n1 2 1\n2 2\n1\n2 1\n", 6),
+            ("3 2\n2 2\n1 2 1\n2 2\n1\n1 3\n", 6),
+            ("3 2\n2 2\n1 2 1\n2 2\n1\n1 x\n", 6),
+            ("3 2\n1 3\n1 2 1\n2 2\n", 3),
+            ("3 2\n2 2\n1 2 1\n2 2\n1\n\n1 2\n2\n1 2\n2 3\n4\n", 11),
+            ("3 2\n3 2\n3 2 1\n2 2\n", 2),
+        ];
+        for (alist, expected) in cases {
+            match read_alist(alist.as_bytes()) {
+                Err(AlistError::InvalidLine { line, .. })
+                | Err(AlistError::UnexpectedEnd { line }) => {
+                    assert_eq!(line, expected, "{}", alist)
+                }
+                other => panic!("unexpected result {:?} for {}", other, alist),
+            }
+        }
+    }
+
+    #[test]
+    fn huge_weights_are_rejected_before_allocating() {
+        let alist = "1 1\n1 100000000000000\n1\n100000000000000\n1\n1\n";
+        assert!(matches!(
+            read_alist(alist.as_bytes()),
+            Err(AlistError::InvalidLine { line: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+use super::linear_code::DegreeProfile;
+use super::{CssCode, CssInconsistency, InformationSet, LinearCode, LinearCodeInconsistency};
+use sparse_bin_mat::SparseBinMat;
+use std::fmt;
+
+impl LinearCode {
+    /// Returns the parameters and inconsistencies of the code.
+    ///
+    /// This gathers in a single pass what is usually checked one by one
+    /// after importing a code, such as the rank of the parity check matrix,
+    /// the girth of the Tanner graph and the [inconsistencies](LinearCode::validate).
+    /// The audit is displayed as a human-readable summary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use sparse_bin_mat::SparseBinMat;
+    ///
+    /// let audit = LinearCode::hamming_code().audit();
+    /// assert_eq!((audit.length, audit.dimension), (7, 4));
+    /// assert_eq!(audit.girth, Some(4));
+    /// assert!(audit.is_consistent());
+    ///
+    /// // The third check is the sum of the first two.
+    /// let checks = SparseBinMat::new(4, vec![vec![0, 1], vec![1, 2], vec![0, 2]]);
+    /// let audit = LinearCode::from_parity_check_matrix(checks).audit();
+    /// assert_eq!(audit.num_redundant_checks, 1);
+    /// assert!(!audit.is_consistent());
+    /// assert!(audit.to_string().contains("bit 3 is not checked"));
+    /// ```
+    pub fn audit(&self) -> LinearCodeAudit {
+        let rank = rank(self.parity_check_matrix());
+        LinearCodeAudit {
+            length: self.len(),
+            num_checks: self.num_checks(),
+            rank,
+            dimension: self.len() - rank,
+            num_redundant_checks: self.num_checks() - rank,
+            girth: self.girth(),
+            degree_profile: self.degree_profile(),
+            inconsistencies: self.validate().err().unwrap_or_default(),
+        }
+    }
+}
+
+impl CssCode {
+    /// Returns the parameters of the X and Z stabilizers
+    /// and the inconsistencies of the code.
+    ///
+    /// The inconsistencies are those found by [`validate`](CssCode::validate).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let audit = CssCode::steane_code().audit();
+    /// assert_eq!(audit.num_logical_qubits, 1);
+    /// assert_eq!(audit.x.rank, 3);
+    /// assert!(audit.is_orthogonal());
+    /// assert!(audit.is_consistent());
+    /// ```
+    pub fn audit(&self) -> CssCodeAudit {
+        CssCodeAudit::new(
+            &self.x_code(),
+            &self.z_code(),
+            self.validate().err().unwrap_or_default(),
+        )
+    }
+
+    /// Returns the parameters of the codes that would be the X and Z stabilizers
+    /// of a CSS code and the pairs of X and Z checks that don't commute.
+    ///
+    /// Unlike [`try_new`](CssCode::try_new),
+    /// this reports all the anticommuting checks of codes that are not orthogonal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::{CssCode, LinearCode};
+    /// let hamming_code = LinearCode::hamming_code();
+    /// assert!(CssCode::audit_pair(&hamming_code, &hamming_code).is_consistent());
+    ///
+    /// let repetition_code = LinearCode::repetition_code(7);
+    /// let audit = CssCode::audit_pair(&hamming_code, &repetition_code);
+    /// assert!(!audit.is_orthogonal());
+    /// assert!(!audit.is_consistent());
+    /// ```
+    pub fn audit_pair(x_code: &LinearCode, z_code: &LinearCode) -> CssCodeAudit {
+        let inconsistencies = if x_code.len() != z_code.len() {
+            vec![CssInconsistency::DifferentLengths(
+                x_code.len(),
+                z_code.len(),
+            )]
+        } else {
+            (x_code.parity_check_matrix() * &z_code.parity_check_matrix().transposed())
+                .non_trivial_elements()
+                .map(|(x, z)| CssInconsistency::NonCommutingStabilizers(x, z))
+                .collect()
+        };
+        CssCodeAudit::new(x_code, z_code, inconsistencies)
+    }
+}
+
+fn rank(matrix: &SparseBinMat) -> usize {
+    let columns = (0..matrix.number_of_columns()).collect::<Vec<_>>();
+    InformationSet::new(matrix, &columns).rank()
+}
+
+/// The parameters and inconsistencies of a [`LinearCode`]
+/// returned by [`audit`](LinearCode::audit).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LinearCodeAudit {
+    /// The number of bits.
+    pub length: usize,
+    /// The number of rows of the parity check matrix.
+    pub num_checks: usize,
+    /// The rank of the parity check matrix.
+    pub rank: usize,
+    /// The length minus the rank of the parity check matrix.
+    pub dimension: usize,
+    /// The number of checks that are sums of other checks.
+    pub num_redundant_checks: usize,
+    /// The length of the shortest cycle of the Tanner graph or None if it is a forest.
+    pub girth: Option<usize>,
+    /// The degrees of the bits and the checks.
+    pub degree_profile: DegreeProfile,
+    /// Everything found by [`validate`](LinearCode::validate).
+    pub inconsistencies: Vec<LinearCodeInconsistency>,
+}
+
+impl LinearCodeAudit {
+    /// Checks if no inconsistency was found.
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+}
+
+impl fmt::Display for LinearCodeAudit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "length: {}", self.length)?;
+        writeln!(f, "dimension: {}", self.dimension)?;
+        write_checks(f, "", self)?;
+        write_inconsistencies(f, &self.inconsistencies)
+    }
+}
+
+/// The parameters of the stabilizers and the inconsistencies of a [`CssCode`]
+/// returned by [`audit`](CssCode::audit) or [`audit_pair`](CssCode::audit_pair).
+///
+/// Since the X and Z stabilizers are audited as separate codes,
+/// a qubit without stabilizer of one type is reported as an unchecked bit
+/// of that code while it doesn't make the CSS code inconsistent.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CssCodeAudit {
+    /// The audit of the code whose checks are the X stabilizers.
+    pub x: LinearCodeAudit,
+    /// The audit of the code whose checks are the Z stabilizers.
+    pub z: LinearCodeAudit,
+    /// The number of qubits minus the ranks of the X and Z stabilizers.
+    pub num_logical_qubits: usize,
+    /// The inconsistencies of the CSS code.
+    pub inconsistencies: Vec<CssInconsistency>,
+}
+
+impl CssCodeAudit {
+    fn new(
+        x_code: &LinearCode,
+        z_code: &LinearCode,
+        inconsistencies: Vec<CssInconsistency>,
+    ) -> Self {
+        let x = x_code.audit();
+        let z = z_code.audit();
+        Self {
+            num_logical_qubits: x.length.saturating_sub(x.rank + z.rank),
+            x,
+            z,
+            inconsistencies,
+        }
+    }
+
+    /// Checks if the X and Z stabilizers have the same length and commute.
+    pub fn is_orthogonal(&self) -> bool {
+        !self.inconsistencies.iter().any(|inconsistency| {
+            matches!(
+                inconsistency,
+                CssInconsistency::DifferentLengths(..)
+                    | CssInconsistency::NonCommutingStabilizers(..)
+            )
+        })
+    }
+
+    /// Checks if no inconsistency of the CSS code was found.
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+}
+
+impl fmt::Display for CssCodeAudit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "qubits: {}", self.x.length)?;
+        writeln!(f, "logical qubits: {}", self.num_logical_qubits)?;
+        write_checks(f, "x ", &self.x)?;
+        write_checks(f, "z ", &self.z)?;
+        writeln!(
+            f,
+            "orthogonal: {}",
+            if self.is_orthogonal() { "yes" } else { "no" }
+        )?;
+        write_inconsistencies(f, &self.inconsistencies)
+    }
+}
+
+// Writes the rank, girth and degrees of the checks of a code.
+fn write_checks(f: &mut fmt::Formatter<'_>, prefix: &str, audit: &LinearCodeAudit) -> fmt::Result {
+    writeln!(
+        f,
+        "{}checks: {} of rank {} with {} redundant",
+        prefix, audit.num_checks, audit.rank, audit.num_redundant_checks
+    )?;
+    match audit.girth {
+        Some(girth) => writeln!(f, "{}girth: {}", prefix, girth)?,
+        None => writeln!(f, "{}girth: none", prefix)?,
+    }
+    writeln!(
+        f,
+        "{}bit degrees: {:?}",
+        prefix,
+        audit.degree_profile.bits.counts()
+    )?;
+    writeln!(
+        f,
+        "{}check degrees: {:?}",
+        prefix,
+        audit.degree_profile.checks.counts()
+    )
+}
+
+// Writes one inconsistency per line or a line saying there is none.
+fn write_inconsistencies<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    inconsistencies: &[T],
+) -> fmt::Result {
+    if inconsistencies.is_empty() {
+        return writeln!(f, "inconsistencies: none");
+    }
+    writeln!(f, "inconsistencies: {}", inconsistencies.len())?;
+    for inconsistency in inconsistencies {
+        writeln!(f, "  {}", inconsistency)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn surface_code_audit() {
+        let audit = CssCode::rotated_surface_code(3).audit();
+        assert_eq!(audit.x.length, 9);
+        assert_eq!((audit.x.num_checks, audit.z.num_checks), (4, 4));
+        assert_eq!(audit.num_logical_qubits, 1);
+        assert!(audit.is_consistent());
+        let summary = audit.to_string();
+        assert!(summary.starts_with("qubits: 9\nlogical qubits: 1\n"));
+        assert!(summary.ends_with("orthogonal: yes\ninconsistencies: none\n"));
+    }
+
+    #[test]
+    fn pairs_of_different_lengths_are_not_orthogonal() {
+        let audit =
+            CssCode::audit_pair(&LinearCode::hamming_code(), &LinearCode::repetition_code(3));
+        assert_eq!(
+            audit.inconsistencies,
+            vec![CssInconsistency::DifferentLengths(7, 3)]
+        );
+        assert!(!audit.is_orthogonal());
+    }
+}
@@ -0,0 +1,183 @@
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+use std::collections::HashMap;
+
+/// A permutation of the bits of a code mapping each check
+/// of its parity check matrix to another check.
+///
+/// Bit `i` is moved to position `bits()[i]` and
+/// check `j` is moved to position `checks()[j]`.
+/// Thus, permuting an error and its syndrome preserves
+/// the relation between them.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::Automorphism;
+/// use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+///
+/// // The cyclic repetition code.
+/// let parity_mat = SparseBinMat::new(3, vec![vec![0, 1], vec![1, 2], vec![0, 2]]);
+/// let shift = Automorphism::new(&parity_mat, vec![1, 2, 0]);
+/// assert_eq!(shift.checks(), &[1, 2, 0]);
+///
+/// let error = SparseBinVec::new(3, vec![0]);
+/// let syndrome = &parity_mat * &error;
+/// assert_eq!(
+///     &parity_mat * &shift.permute_bits(error.as_view()),
+///     shift.permute_checks(syndrome.as_view())
+/// );
+///
+/// // Any permutation of the bits maps a check to a check but the bits must be distinct.
+/// assert!(Automorphism::try_new(&parity_mat, vec![1, 0, 2]).is_some());
+/// assert!(Automorphism::try_new(&parity_mat, vec![0, 0, 2]).is_none());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Automorphism {
+    bits: Vec<usize>,
+    checks: Vec<usize>,
+}
+
+impl Automorphism {
+    /// Creates the automorphism of the parity check matrix moving bit `i`
+    /// to position `bits[i]`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the bits are not a permutation of the columns of the matrix
+    /// or if a check is not mapped to a check.
+    pub fn new(parity_mat: &SparseBinMat, bits: Vec<usize>) -> Self {
+        Self::try_new(parity_mat, bits).expect("not an automorphism of the parity check matrix")
+    }
+
+    /// Creates the automorphism of the parity check matrix moving bit `i`
+    /// to position `bits[i]` or returns None if it is not an automorphism.
+    pub fn try_new(parity_mat: &SparseBinMat, bits: Vec<usize>) -> Option<Self> {
+        if !is_permutation(&bits, parity_mat.number_of_columns()) {
+            return None;
+        }
+        let rows = parity_mat
+            .rows()
+            .enumerate()
+            .map(|(check, row)| (row.non_trivial_positions().collect::<Vec<_>>(), check))
+            .collect::<HashMap<_, _>>();
+        let checks = parity_mat
+            .rows()
+            .map(|row| {
+                let mut image = row
+                    .non_trivial_positions()
+                    .map(|bit| bits[bit])
+                    .collect::<Vec<_>>();
+                image.sort_unstable();
+                rows.get(&image).cloned()
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if is_permutation(&checks, parity_mat.number_of_rows()) {
+            Some(Self { bits, checks })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cyclic shifts of the bits by 1 to n - 1 positions
+    /// that are automorphisms of the parity check matrix.
+    pub fn cyclic_shifts(parity_mat: &SparseBinMat) -> Vec<Self> {
+        let length = parity_mat.number_of_columns();
+        (1..length)
+            .filter_map(|shift| {
+                let bits = (0..length).map(|bit| (bit + shift) % length).collect();
+                Self::try_new(parity_mat, bits)
+            })
+            .collect()
+    }
+
+    /// Returns the position of each bit after the permutation.
+    pub fn bits(&self) -> &[usize] {
+        &self.bits
+    }
+
+    /// Returns the position of each check after the permutation.
+    pub fn checks(&self) -> &[usize] {
+        &self.checks
+    }
+
+    /// Returns the inverse automorphism.
+    pub fn inverse(&self) -> Self {
+        Self {
+            bits: inverse(&self.bits),
+            checks: inverse(&self.checks),
+        }
+    }
+
+    /// Moves each bit of the vector to its new position.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vector length is different from the number of bits.
+    pub fn permute_bits(&self, vector: SparseBinSlice) -> SparseBinVec {
+        permute(&self.bits, vector)
+    }
+
+    /// Moves each check of the syndrome to its new position.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    pub fn permute_checks(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        permute(&self.checks, syndrome)
+    }
+}
+
+fn is_permutation(permutation: &[usize], length: usize) -> bool {
+    let mut is_used = vec![false; length];
+    permutation.len() == length
+        && permutation
+            .iter()
+            .all(|position| *position < length && !std::mem::replace(&mut is_used[*position], true))
+}
+
+fn inverse(permutation: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; permutation.len()];
+    for (index, position) in permutation.iter().enumerate() {
+        inverse[*position] = index;
+    }
+    inverse
+}
+
+fn permute(permutation: &[usize], vector: SparseBinSlice) -> SparseBinVec {
+    if vector.len() != permutation.len() {
+        panic!(
+            "vector of length {} for permutation of length {}",
+            vector.len(),
+            permutation.len()
+        );
+    }
+    let mut positions = vector
+        .non_trivial_positions()
+        .map(|position| permutation[position])
+        .collect::<Vec<_>>();
+    positions.sort_unstable();
+    SparseBinVec::new(vector.len(), positions)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inverse_undoes_the_permutation() {
+        let parity_mat = SparseBinMat::new(4, vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![0, 3]]);
+        let shifts = Automorphism::cyclic_shifts(&parity_mat);
+        assert_eq!(shifts.len(), 3);
+        let vector = SparseBinVec::new(4, vec![0, 1]);
+        for shift in shifts {
+            let permuted = shift.permute_bits(vector.as_view());
+            assert_eq!(shift.inverse().permute_bits(permuted.as_view()), vector);
+        }
+    }
+
+    #[test]
+    fn open_chain_has_no_cyclic_shift() {
+        let parity_mat = SparseBinMat::new(3, vec![vec![0, 1], vec![1, 2]]);
+        assert!(Automorphism::cyclic_shifts(&parity_mat).is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+use super::DenseCodeword;
+use sparse_bin_mat::SparseBinMat;
+
+/// The Gaussian elimination over GF(2) used to build codes,
+/// such that it can run on another representation of the matrices.
+///
+/// Codes always store their matrices as [`SparseBinMat`]
+/// and every other operation of codes and decoders uses them directly.
+/// A backend only computes the generator matrix of a code from its parity check matrix,
+/// see [`LinearCode::from_parity_check_matrix_with`](super::LinearCode::from_parity_check_matrix_with).
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::{BinaryMatrix, DenseBinMat};
+/// use sparse_bin_mat::SparseBinMat;
+///
+/// // The parity checks of the 3 bits repetition code.
+/// let checks = SparseBinMat::new(3, vec![vec![0, 1], vec![1, 2]]);
+/// let matrix = DenseBinMat::from_sparse_bin_mat(&checks);
+///
+/// assert_eq!(matrix.rank(), 2);
+/// assert_eq!(
+///     matrix.nullspace().to_sparse_bin_mat(),
+///     SparseBinMat::new(3, vec![vec![0, 1, 2]])
+/// );
+/// ```
+pub trait BinaryMatrix: Sized {
+    /// Converts a sparse matrix to this representation.
+    fn from_sparse_bin_mat(matrix: &SparseBinMat) -> Self;
+
+    /// Converts the matrix to a sparse matrix.
+    fn to_sparse_bin_mat(&self) -> SparseBinMat;
+
+    /// Returns the rank over GF(2).
+    fn rank(&self) -> usize;
+
+    /// Returns a matrix whose rows are a basis of the vectors orthogonal to all rows.
+    fn nullspace(&self) -> Self;
+}
+
+impl BinaryMatrix for SparseBinMat {
+    fn from_sparse_bin_mat(matrix: &SparseBinMat) -> Self {
+        matrix.clone()
+    }
+
+    fn to_sparse_bin_mat(&self) -> SparseBinMat {
+        self.clone()
+    }
+
+    fn rank(&self) -> usize {
+        SparseBinMat::rank(self)
+    }
+
+    fn nullspace(&self) -> Self {
+        SparseBinMat::nullspace(self)
+    }
+}
+
+/// A binary matrix whose rows are packed into machine words.
+///
+/// The Gaussian elimination of the rank and the nullspace adds rows
+/// a word at a time, which is faster than with a [`SparseBinMat`]
+/// for matrices that become dense during the elimination.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DenseBinMat {
+    number_of_columns: usize,
+    rows: Vec<DenseCodeword>,
+}
+
+impl DenseBinMat {
+    /// Creates a matrix with the given number of columns from the positions
+    /// of the ones of each row.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a position is out of bound.
+    pub fn from_rows(number_of_columns: usize, rows: Vec<Vec<usize>>) -> Self {
+        let rows = rows
+            .into_iter()
+            .map(|positions| Self::packed_row(number_of_columns, positions))
+            .collect();
+        Self {
+            number_of_columns,
+            rows,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn number_of_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of columns.
+    pub fn number_of_columns(&self) -> usize {
+        self.number_of_columns
+    }
+
+    /// Returns the positions of the ones of each row.
+    pub fn rows(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        self.rows
+            .iter()
+            .map(|row| row.non_trivial_positions().collect())
+    }
+
+    /// Returns the transposed matrix.
+    pub fn transposed(&self) -> Self {
+        let mut columns = vec![Vec::new(); self.number_of_columns];
+        for (index, row) in self.rows.iter().enumerate() {
+            for column in row.non_trivial_positions() {
+                columns[column].push(index);
+            }
+        }
+        Self::from_rows(self.rows.len(), columns)
+    }
+
+    fn packed_row<I: IntoIterator<Item = usize>>(length: usize, positions: I) -> DenseCodeword {
+        let mut row = DenseCodeword::zeros(length);
+        for position in positions {
+            row.flip(position);
+        }
+        row
+    }
+
+    // The rows in reduced row echelon form and the pivot column of each row.
+    fn reduced_row_echelon_form(&self) -> (Vec<DenseCodeword>, Vec<usize>) {
+        let mut rows = self.rows.clone();
+        let mut pivots = Vec::new();
+        for column in 0..self.number_of_columns {
+            let rank = pivots.len();
+            let Some(pivot_row) =
+                (rank..rows.len()).find(|row| rows[*row].is_one_at(column) == Some(true))
+            else {
+                continue;
+            };
+            rows.swap(rank, pivot_row);
+            let pivot = rows[rank].clone();
+            for (index, row) in rows.iter_mut().enumerate() {
+                if index != rank && row.is_one_at(column) == Some(true) {
+                    *row += &pivot;
+                }
+            }
+            pivots.push(column);
+        }
+        rows.truncate(pivots.len());
+        (rows, pivots)
+    }
+}
+
+impl BinaryMatrix for DenseBinMat {
+    fn from_sparse_bin_mat(matrix: &SparseBinMat) -> Self {
+        let rows = matrix
+            .rows()
+            .map(|row| Self::packed_row(matrix.number_of_columns(), row.non_trivial_positions()))
+            .collect();
+        Self {
+            number_of_columns: matrix.number_of_columns(),
+            rows,
+        }
+    }
+
+    fn to_sparse_bin_mat(&self) -> SparseBinMat {
+        SparseBinMat::new(self.number_of_columns, self.rows().collect())
+    }
+
+    fn rank(&self) -> usize {
+        self.reduced_row_echelon_form().1.len()
+    }
+
+    fn nullspace(&self) -> Self {
+        let (rows, pivots) = self.reduced_row_echelon_form();
+        let mut is_pivot = vec![false; self.number_of_columns];
+        for pivot in pivots.iter() {
+            is_pivot[*pivot] = true;
+        }
+        // Each free column is set with the pivots cancelling its ones.
+        let basis = (0..self.number_of_columns)
+            .filter(|column| !is_pivot[*column])
+            .map(|free| {
+                let mut positions = rows
+                    .iter()
+                    .zip(pivots.iter())
+                    .filter(|(row, _)| row.is_one_at(free) == Some(true))
+                    .map(|(_, pivot)| *pivot)
+                    .chain(std::iter::once(free))
+                    .collect::<Vec<_>>();
+                positions.sort_unstable();
+                positions
+            })
+            .collect();
+        Self::from_rows(self.number_of_columns, basis)
+    }
+}
+
+impl From<&SparseBinMat> for DenseBinMat {
+    fn from(matrix: &SparseBinMat) -> Self {
+        Self::from_sparse_bin_mat(matrix)
+    }
+}
+
+impl From<&DenseBinMat> for SparseBinMat {
+    fn from(matrix: &DenseBinMat) -> Self {
+        matrix.to_sparse_bin_mat()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+
+    #[test]
+    fn dense_backend_matches_sparse_matrices() {
+        let code = LinearCode::extended_golay_code();
+        let sparse = code.parity_check_matrix();
+        let dense = DenseBinMat::from(sparse);
+        assert_eq!(dense.rank(), sparse.rank());
+        assert_eq!(DenseBinMat::from(&sparse.transposed()), dense.transposed());
+        let nullspace = dense.nullspace().to_sparse_bin_mat();
+        assert_eq!(nullspace.rank(), code.dimension());
+        assert!((sparse * &nullspace.transposed()).is_zero());
+    }
+}
@@ -0,0 +1,96 @@
+use rayon::prelude::*;
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+
+// The number of vectors processed in a single pass over the checks.
+const BATCH_SIZE: usize = u64::BITS as usize;
+
+// Returns the product of the checks with each vector.
+//
+// The vectors are processed by batches of 64 such that the checks
+// are visited once per batch instead of once per vector.
+pub(crate) fn syndromes_of_batch(
+    checks: &SparseBinMat,
+    vectors: &[SparseBinVec],
+) -> Vec<SparseBinVec> {
+    validate(checks, vectors);
+    vectors
+        .chunks(BATCH_SIZE)
+        .flat_map(|batch| batch_syndromes(checks, batch))
+        .collect()
+}
+
+// Same as syndromes_of_batch, but the batches are processed in parallel.
+pub(crate) fn par_syndromes_of_batch(
+    checks: &SparseBinMat,
+    vectors: &[SparseBinVec],
+) -> Vec<SparseBinVec> {
+    validate(checks, vectors);
+    vectors
+        .par_chunks(BATCH_SIZE)
+        .flat_map_iter(|batch| batch_syndromes(checks, batch))
+        .collect()
+}
+
+fn validate(checks: &SparseBinMat, vectors: &[SparseBinVec]) {
+    if let Some(vector) = vectors
+        .iter()
+        .find(|vector| vector.len() != checks.number_of_columns())
+    {
+        panic!(
+            "message of length {} is invalid for code with length {}",
+            vector.len(),
+            checks.number_of_columns()
+        );
+    }
+}
+
+// Each bit stores in a word which vectors of the batch flip it,
+// such that the outcome of a check for the whole batch is the sum of the words of its bits.
+fn batch_syndromes(checks: &SparseBinMat, batch: &[SparseBinVec]) -> Vec<SparseBinVec> {
+    let mut words = vec![0_u64; checks.number_of_columns()];
+    for (index, vector) in batch.iter().enumerate() {
+        for bit in vector.non_trivial_positions() {
+            words[bit] ^= 1 << index;
+        }
+    }
+    let mut positions = vec![Vec::new(); batch.len()];
+    for (check, row) in checks.rows().enumerate() {
+        let mut word = row
+            .non_trivial_positions()
+            .fold(0, |word, bit| word ^ words[bit]);
+        while word != 0 {
+            positions[word.trailing_zeros() as usize].push(check);
+            word &= word - 1;
+        }
+    }
+    positions
+        .into_iter()
+        .map(|positions| SparseBinVec::new(checks.number_of_rows(), positions))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::noise::{BinarySymmetricChannel, NoiseModel, Probability};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn batches_match_single_syndromes() {
+        let code = LinearCode::extended_golay_code();
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.2));
+        let mut rng = StdRng::seed_from_u64(5);
+        let errors = (0..150)
+            .map(|_| noise.sample_error_of_length(code.len(), &mut rng))
+            .collect::<Vec<_>>();
+        let expected = errors
+            .iter()
+            .map(|error| code.syndrome_of(error))
+            .collect::<Vec<_>>();
+        let checks = code.parity_check_matrix();
+        assert_eq!(syndromes_of_batch(checks, &errors), expected);
+        assert_eq!(par_syndromes_of_batch(checks, &errors), expected);
+    }
+}
@@ -0,0 +1,289 @@
+use super::CssCode;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinMat;
+use std::collections::HashMap;
+
+impl CssCode {
+    /// Returns the largest weight of a stabilizer or 0 if there is no stabilizer.
+    ///
+    /// This is the number of CNOTs a single ancilla needs to measure each stabilizer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// assert_eq!(CssCode::steane_code().max_stabilizer_weight(), 4);
+    /// assert_eq!(CssCode::shor_code().max_stabilizer_weight(), 6);
+    /// ```
+    pub fn max_stabilizer_weight(&self) -> usize {
+        self.stabilizers
+            .x
+            .rows()
+            .chain(self.stabilizers.z.rows())
+            .map(|stabilizer| stabilizer.weight())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the largest number of stabilizers acting on the same qubit
+    /// or 0 if there is no stabilizer.
+    ///
+    /// The stabilizers acting on a qubit can't be measured at the same time,
+    /// thus this is a lower bound on the number of
+    /// [measurement layers](CssCode::measurement_layers).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// assert_eq!(CssCode::steane_code().max_qubit_degree(), 6);
+    /// assert_eq!(CssCode::toric_code(3).max_qubit_degree(), 4);
+    /// ```
+    pub fn max_qubit_degree(&self) -> usize {
+        stabilizers_of_qubits(self)
+            .rows()
+            .map(|stabilizers| stabilizers.weight())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of qubits shared by each pair of stabilizers.
+    ///
+    /// The stabilizers are the X stabilizers followed by the Z stabilizers
+    /// and pairs of both types are included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let overlaps = CssCode::steane_code().stabilizer_overlaps();
+    ///
+    /// // Each X stabilizer shares all its qubits with the Z stabilizer of the same support.
+    /// assert_eq!(overlaps.counts(), &[0, 0, 12, 0, 3]);
+    /// assert_eq!(overlaps.max(), 4);
+    /// assert_eq!(overlaps.num_overlapping_pairs(), 15);
+    /// ```
+    pub fn stabilizer_overlaps(&self) -> StabilizerOverlaps {
+        let mut overlaps = HashMap::new();
+        for stabilizers in stabilizers_of_qubits(self).rows() {
+            let stabilizers = stabilizers.non_trivial_positions().collect::<Vec<_>>();
+            for (position, first) in stabilizers.iter().enumerate() {
+                for second in stabilizers[position + 1..].iter() {
+                    *overlaps.entry((*first, *second)).or_insert(0) += 1;
+                }
+            }
+        }
+        let num_stabilizers = self.num_x_stabs() + self.num_z_stabs();
+        let num_pairs = num_stabilizers * num_stabilizers.saturating_sub(1) / 2;
+        let mut counts = vec![num_pairs - overlaps.len()];
+        for overlap in overlaps.into_values() {
+            if overlap >= counts.len() {
+                counts.resize(overlap + 1, 0);
+            }
+            counts[overlap] += 1;
+        }
+        StabilizerOverlaps { counts }
+    }
+
+    /// Partitions the stabilizers into layers of at most the given size
+    /// such that the stabilizers of a layer act on disjoint qubits.
+    ///
+    /// The stabilizers are the X stabilizers followed by the Z stabilizers.
+    /// Since the stabilizers of a layer commute and don't share qubits,
+    /// they can be measured at the same time with a single ancilla each
+    /// and without reusing a qubit.
+    ///
+    /// The layers are found greedily by assigning the stabilizers
+    /// from the most to the least overlapping to the first layer they fit in.
+    /// Hence, the number of layers is not always the smallest possible,
+    /// but it is at least the [max qubit degree](CssCode::max_qubit_degree).
+    ///
+    /// # Panic
+    ///
+    /// Panics if the maximum layer size is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::toric_code(4);
+    /// let layers = code.measurement_layers(8);
+    ///
+    /// assert_eq!(layers.len(), 4);
+    /// assert!(layers.iter().all(|layer| layer.len() == 8));
+    /// ```
+    pub fn measurement_layers(&self, max_layer_size: usize) -> Vec<Vec<usize>> {
+        if max_layer_size == 0 {
+            panic!("layers of size 0");
+        }
+        let num_stabilizers = self.num_x_stabs() + self.num_z_stabs();
+        let mut neighbors = vec![Vec::new(); num_stabilizers];
+        for stabilizers in stabilizers_of_qubits(self).rows() {
+            for first in stabilizers.non_trivial_positions() {
+                neighbors[first].extend(
+                    stabilizers
+                        .non_trivial_positions()
+                        .filter(|second| *second != first),
+                );
+            }
+        }
+        for neighbors in neighbors.iter_mut() {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+        let mut order = (0..num_stabilizers).collect::<Vec<_>>();
+        order.sort_by_key(|stabilizer| std::cmp::Reverse(neighbors[*stabilizer].len()));
+        let mut layer_of = vec![None; num_stabilizers];
+        let mut layers: Vec<Vec<usize>> = Vec::new();
+        for stabilizer in order {
+            let mut is_blocked = vec![false; layers.len()];
+            for neighbor in neighbors[stabilizer].iter() {
+                if let Some(layer) = layer_of[*neighbor] {
+                    is_blocked[layer] = true;
+                }
+            }
+            let layer = (0..layers.len())
+                .find(|layer| !is_blocked[*layer] && layers[*layer].len() < max_layer_size)
+                .unwrap_or_else(|| {
+                    layers.push(Vec::new());
+                    layers.len() - 1
+                });
+            layers[layer].push(stabilizer);
+            layer_of[stabilizer] = Some(layer);
+        }
+        for layer in layers.iter_mut() {
+            layer.sort_unstable();
+        }
+        layers
+    }
+
+    /// Checks if the stabilizers can be measured in at most the given number of layers
+    /// of at most the given size.
+    ///
+    /// This uses the greedy [measurement layers](CssCode::measurement_layers),
+    /// thus a negative answer doesn't prove that no such layers exist,
+    /// unless a qubit is shared by more stabilizers than the number of layers
+    /// or there are more stabilizers than the layers can hold.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the maximum layer size is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::toric_code(4);
+    ///
+    /// assert!(code.fits_in_measurement_layers(4, 8));
+    /// assert!(!code.fits_in_measurement_layers(4, 7));
+    /// assert!(!code.fits_in_measurement_layers(3, 16));
+    /// ```
+    pub fn fits_in_measurement_layers(&self, num_layers: usize, max_layer_size: usize) -> bool {
+        self.measurement_layers(max_layer_size).len() <= num_layers
+    }
+}
+
+// The stabilizers acting on each qubit with the X stabilizers first.
+fn stabilizers_of_qubits(code: &CssCode) -> SparseBinMat {
+    code.stabilizers
+        .x
+        .vertical_concat_with(&code.stabilizers.z)
+        .transposed()
+}
+
+/// The number of pairs of stabilizers sharing each number of qubits.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct StabilizerOverlaps {
+    counts: Vec<usize>,
+}
+
+impl StabilizerOverlaps {
+    /// Returns the number of pairs sharing each number of qubits
+    /// from 0 to the largest overlap.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// Returns the number of pairs sharing the given number of qubits.
+    pub fn count(&self, overlap: usize) -> usize {
+        self.counts.get(overlap).cloned().unwrap_or(0)
+    }
+
+    /// Returns the largest number of qubits shared by a pair of stabilizers.
+    pub fn max(&self) -> usize {
+        self.counts.len() - 1
+    }
+
+    /// Returns the number of pairs sharing at least one qubit.
+    pub fn num_overlapping_pairs(&self) -> usize {
+        self.counts[1..].iter().sum()
+    }
+
+    /// Returns the average overlap of the pairs sharing at least one qubit
+    /// or None if there is no such pair.
+    pub fn mean_overlap(&self) -> Option<f64> {
+        let num_pairs = self.num_overlapping_pairs();
+        if num_pairs == 0 {
+            return None;
+        }
+        let num_shared: usize = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(overlap, count)| overlap * count)
+            .sum();
+        Some(num_shared as f64 / num_pairs as f64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use sparse_bin_mat::SparseBinVec;
+
+    #[test]
+    fn layers_are_disjoint_and_cover_all_stabilizers() {
+        let hamming = LinearCode::hamming_code();
+        let code = CssCode::hypergraph_product(&hamming, &hamming);
+        let stabilizers = code
+            .x_stabs_binary()
+            .vertical_concat_with(code.z_stabs_binary());
+        for max_layer_size in [1, 5, 100] {
+            let layers = code.measurement_layers(max_layer_size);
+            assert!(layers.len() >= code.max_qubit_degree());
+            let mut covered = layers.iter().flatten().cloned().collect::<Vec<_>>();
+            covered.sort_unstable();
+            assert_eq!(
+                covered,
+                (0..stabilizers.number_of_rows()).collect::<Vec<_>>()
+            );
+            for layer in layers.iter() {
+                assert!(layer.len() <= max_layer_size);
+                let weight: usize = layer
+                    .iter()
+                    .map(|stabilizer| stabilizers.row(*stabilizer).unwrap().weight())
+                    .sum();
+                // The weight of the sum is the sum of the weights if the supports are disjoint.
+                let sum = layer
+                    .iter()
+                    .fold(SparseBinVec::zeros(code.len()), |sum, stabilizer| {
+                        &sum + &stabilizers.row(*stabilizer).unwrap()
+                    });
+                let support = sum.weight();
+                assert_eq!(weight, support);
+            }
+        }
+    }
+
+    #[test]
+    fn code_without_stabilizers() {
+        let code = CssCode::new(&LinearCode::empty(), &LinearCode::empty());
+        assert_eq!(code.max_stabilizer_weight(), 0);
+        assert_eq!(code.max_qubit_degree(), 0);
+        assert_eq!(code.stabilizer_overlaps().counts(), &[0]);
+        assert_eq!(code.stabilizer_overlaps().mean_overlap(), None);
+        assert!(code.measurement_layers(3).is_empty());
+    }
+}
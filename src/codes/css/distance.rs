@@ -0,0 +1,174 @@
+use super::CssCode;
+use crate::codes::DenseCodeword;
+use crate::css::CssOperator;
+use itertools::Itertools;
+use pauli::PauliOperator;
+use rayon::prelude::*;
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Exhaustively searches for a non trivial logical operator of weight
+// less than the given weight.
+//
+// Each weight class is processed in parallel, as are the supports
+// of a class sharing their first position.
+// The lowest weight of a logical found so far is shared between the classes,
+// such that the search of a class stops as soon as a logical
+// is found in a lower weight class.
+// The returned operator is always the first one in lexicographic order
+// of the smallest weight class containing a logical, X type first.
+pub(super) fn find_logical_of_weight_less_than(
+    code: &CssCode,
+    weight: usize,
+) -> Option<PauliOperator> {
+    let x_sector = Sector::new(code.z_stabs_binary(), code.z_logicals_binary());
+    let z_sector = Sector::new(code.x_stabs_binary(), code.x_logicals_binary());
+    let lowest_weight = AtomicUsize::new(weight);
+    (1..weight)
+        .into_par_iter()
+        .find_map_first(|weight| {
+            let logical = x_sector
+                .find_logical_of_weight(weight, &lowest_weight)
+                .map(|support| CssOperator {
+                    x: support,
+                    z: SparseBinVec::zeros(code.len()),
+                })
+                .or_else(|| {
+                    z_sector
+                        .find_logical_of_weight(weight, &lowest_weight)
+                        .map(|support| CssOperator {
+                            x: SparseBinVec::zeros(code.len()),
+                            z: support,
+                        })
+                });
+            if logical.is_some() {
+                lowest_weight.fetch_min(weight, Ordering::Relaxed);
+            }
+            logical
+        })
+        .map(PauliOperator::from)
+}
+
+// The binary vectors that commute with the stabilizers
+// and anticommute with at least one of the logicals
+// are the non trivial logical operators of a sector.
+struct Sector {
+    stabilizer_columns: Vec<DenseCodeword>,
+    logical_columns: Vec<DenseCodeword>,
+}
+
+impl Sector {
+    fn new(stabilizers: &SparseBinMat, logicals: &SparseBinMat) -> Self {
+        Self {
+            stabilizer_columns: Self::dense_columns(stabilizers),
+            logical_columns: Self::dense_columns(logicals),
+        }
+    }
+
+    fn dense_columns(matrix: &SparseBinMat) -> Vec<DenseCodeword> {
+        matrix
+            .transposed()
+            .rows()
+            .map(|column| DenseCodeword::from(&column))
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.stabilizer_columns.len()
+    }
+
+    // Gives up with None as soon as a logical of lower weight is found by another search.
+    fn find_logical_of_weight(
+        &self,
+        weight: usize,
+        lowest_weight: &AtomicUsize,
+    ) -> Option<SparseBinVec> {
+        (0..self.len())
+            .into_par_iter()
+            .find_map_first(|first| {
+                (first + 1..self.len())
+                    .combinations(weight - 1)
+                    .take_while(|_| lowest_weight.load(Ordering::Relaxed) >= weight)
+                    .map(|rest| std::iter::once(first).chain(rest).collect::<Vec<_>>())
+                    .find(|support| self.is_non_trivial_logical(support))
+            })
+            .map(|support| SparseBinVec::new(self.len(), support))
+    }
+
+    fn is_non_trivial_logical(&self, support: &[usize]) -> bool {
+        Self::has_zero_sum(&self.stabilizer_columns, support)
+            && !Self::has_zero_sum(&self.logical_columns, support)
+    }
+
+    fn has_zero_sum(columns: &[DenseCodeword], support: &[usize]) -> bool {
+        let mut sum = DenseCodeword::zeros(columns.first().map_or(0, DenseCodeword::len));
+        for column in support {
+            sum += &columns[*column];
+        }
+        sum.is_zero()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn steane_code_has_distance_3() {
+        let code = CssCode::steane_code();
+        assert!(find_logical_of_weight_less_than(&code, 3).is_none());
+        let logical = find_logical_of_weight_less_than(&code, 4).unwrap();
+        assert_eq!(logical.weight(), 3);
+        assert!(code.has_logical(&logical));
+        assert!(!code.has_stabilizer(&logical));
+    }
+
+    #[test]
+    fn shor_code_has_distance_3() {
+        let code = CssCode::shor_code();
+        assert!(find_logical_of_weight_less_than(&code, 3).is_none());
+        assert!(find_logical_of_weight_less_than(&code, 4).is_some());
+    }
+
+    #[test]
+    fn toric_code_has_distance_4() {
+        let code = CssCode::toric_code(4);
+        assert!(find_logical_of_weight_less_than(&code, 4).is_none());
+        assert_eq!(
+            find_logical_of_weight_less_than(&code, 5).map(|logical| logical.weight()),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn parallel_search_returns_first_logical_in_lexicographic_order() {
+        let code = CssCode::toric_code(3);
+        let sector = Sector::new(code.z_stabs_binary(), code.z_logicals_binary());
+        let first = (0..code.len())
+            .combinations(3)
+            .find(|support| sector.is_non_trivial_logical(support))
+            .unwrap();
+        let expected = PauliOperator::from(CssOperator {
+            x: SparseBinVec::new(code.len(), first),
+            z: SparseBinVec::zeros(code.len()),
+        });
+        for _ in 0..10 {
+            assert_eq!(
+                find_logical_of_weight_less_than(&code, 4),
+                Some(expected.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn search_gives_up_after_a_lighter_logical_is_found() {
+        let code = CssCode::toric_code(3);
+        let sector = Sector::new(code.z_stabs_binary(), code.z_logicals_binary());
+        assert!(sector
+            .find_logical_of_weight(3, &AtomicUsize::new(3))
+            .is_some());
+        assert!(sector
+            .find_logical_of_weight(3, &AtomicUsize::new(2))
+            .is_none());
+    }
+}
@@ -0,0 +1,269 @@
+use super::{cycle_code, rotated_surface_plaquettes};
+use crate::codes::{CssCode, LinearCode};
+use crate::css::Css;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The positions of the qubits and stabilizers of a CSS code on a grid.
+///
+/// Positions are given as (row, column) pairs
+/// and each qubit and stabilizer occupies a different cell.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::Layout;
+/// let layout = Layout::toric_code(3);
+///
+/// assert_eq!(layout.num_rows(), 6);
+/// assert_eq!(layout.num_columns(), 6);
+/// assert_eq!(layout.qubit(4), Some((2, 2)));
+/// assert_eq!(layout.x_stabilizer(0), Some((0, 1)));
+/// assert_eq!(layout.z_stabilizer(0), Some((1, 0)));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct Layout {
+    qubits: Vec<(usize, usize)>,
+    stabilizers: Css<Vec<(usize, usize)>>,
+}
+
+impl Layout {
+    /// Creates a layout from the positions of the qubits
+    /// and of the X and Z stabilizers.
+    ///
+    /// # Panic
+    ///
+    /// Panics if two elements share the same position.
+    pub fn new(
+        qubits: Vec<(usize, usize)>,
+        x_stabilizers: Vec<(usize, usize)>,
+        z_stabilizers: Vec<(usize, usize)>,
+    ) -> Self {
+        let mut positions = HashSet::new();
+        for position in qubits
+            .iter()
+            .chain(x_stabilizers.iter())
+            .chain(z_stabilizers.iter())
+        {
+            if !positions.insert(position) {
+                panic!("position {:?} is used twice", position);
+            }
+        }
+        Self {
+            qubits,
+            stabilizers: Css {
+                x: x_stabilizers,
+                z: z_stabilizers,
+            },
+        }
+    }
+
+    /// Returns the layout of the
+    /// [hypergraph product](crate::codes::CssCode::hypergraph_product) of two codes.
+    ///
+    /// The qubit associated with the bits `i` and `j` of the first and second codes
+    /// is at position `(2i, 2j)` and the qubit associated with the checks `a` and `b`
+    /// is at position `(2a + 1, 2b + 1)`.
+    /// The X stabilizer associated with the bit `i` and the check `b`
+    /// is at position `(2i, 2b + 1)` and the Z stabilizer associated with
+    /// the check `a` and the bit `j` is at position `(2a + 1, 2j)`.
+    ///
+    /// For the product of two repetition codes, this is the usual
+    /// picture of the surface code.
+    pub fn hypergraph_product(first_code: &LinearCode, second_code: &LinearCode) -> Self {
+        let bits = grid(first_code.len(), second_code.len(), |i, j| (2 * i, 2 * j));
+        let checks = grid(first_code.num_checks(), second_code.num_checks(), |a, b| {
+            (2 * a + 1, 2 * b + 1)
+        });
+        Self::new(
+            bits.chain(checks).collect(),
+            grid(first_code.len(), second_code.num_checks(), |i, b| {
+                (2 * i, 2 * b + 1)
+            })
+            .collect(),
+            grid(first_code.num_checks(), second_code.len(), |a, j| {
+                (2 * a + 1, 2 * j)
+            })
+            .collect(),
+        )
+    }
+
+    /// Returns the layout of the [toric code](crate::codes::CssCode::toric_code)
+    /// with the given distance.
+    pub fn toric_code(distance: usize) -> Self {
+        let code = cycle_code(distance);
+        Self::hypergraph_product(&code, &code)
+    }
+
+    /// Returns the layout of the
+    /// [rotated surface code](crate::codes::CssCode::rotated_surface_code)
+    /// with the given distance.
+    ///
+    /// The qubit in row `r` and column `c` is at position `(2r + 1, 2c + 1)`
+    /// and the stabilizer on the face between the rows `a - 1` and `a`
+    /// and the columns `b - 1` and `b` is at position `(2a, 2b)`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the distance is less than 2.
+    pub fn rotated_surface_code(distance: usize) -> Self {
+        let plaquettes = rotated_surface_plaquettes(distance);
+        let position = |(a, b): (usize, usize)| (2 * a, 2 * b);
+        Self::new(
+            grid(distance, distance, |r, c| (2 * r + 1, 2 * c + 1)).collect(),
+            plaquettes.x.into_iter().map(position).collect(),
+            plaquettes.z.into_iter().map(position).collect(),
+        )
+    }
+
+    /// Returns the number of qubits.
+    pub fn num_qubits(&self) -> usize {
+        self.qubits.len()
+    }
+
+    /// Returns the number of X stabilizers.
+    pub fn num_x_stabs(&self) -> usize {
+        self.stabilizers.x.len()
+    }
+
+    /// Returns the number of Z stabilizers.
+    pub fn num_z_stabs(&self) -> usize {
+        self.stabilizers.z.len()
+    }
+
+    /// Checks if the layout has the same number of qubits
+    /// and of X and Z stabilizers as the code.
+    pub fn fits(&self, code: &CssCode) -> bool {
+        self.num_qubits() == code.len()
+            && self.num_x_stabs() == code.num_x_stabs()
+            && self.num_z_stabs() == code.num_z_stabs()
+    }
+
+    /// Returns the number of rows of the smallest grid containing all the elements.
+    pub fn num_rows(&self) -> usize {
+        self.positions().map(|(row, _)| row + 1).max().unwrap_or(0)
+    }
+
+    /// Returns the number of columns of the smallest grid containing all the elements.
+    pub fn num_columns(&self) -> usize {
+        self.positions()
+            .map(|(_, column)| column + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the position of the given qubit or None if it is out of bound.
+    pub fn qubit(&self, qubit: usize) -> Option<(usize, usize)> {
+        self.qubits.get(qubit).cloned()
+    }
+
+    /// Returns the position of the given X stabilizer or None if it is out of bound.
+    pub fn x_stabilizer(&self, stabilizer: usize) -> Option<(usize, usize)> {
+        self.stabilizers.x.get(stabilizer).cloned()
+    }
+
+    /// Returns the position of the given Z stabilizer or None if it is out of bound.
+    pub fn z_stabilizer(&self, stabilizer: usize) -> Option<(usize, usize)> {
+        self.stabilizers.z.get(stabilizer).cloned()
+    }
+
+    /// Returns the positions of all the qubits.
+    pub fn qubits(&self) -> &[(usize, usize)] {
+        &self.qubits
+    }
+
+    /// Returns the positions of all the X and Z stabilizers.
+    pub fn stabilizers(&self) -> Css<&[(usize, usize)]> {
+        Css {
+            x: &self.stabilizers.x,
+            z: &self.stabilizers.z,
+        }
+    }
+
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.qubits
+            .iter()
+            .chain(self.stabilizers.x.iter())
+            .chain(self.stabilizers.z.iter())
+            .cloned()
+    }
+}
+
+fn grid<F>(rows: usize, columns: usize, position: F) -> impl Iterator<Item = (usize, usize)>
+where
+    F: Fn(usize, usize) -> (usize, usize),
+{
+    (0..rows)
+        .flat_map(move |row| (0..columns).map(move |column| (row, column)))
+        .map(move |(row, column)| position(row, column))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hypergraph_product_layout_matches_code_size() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let hamming_code = LinearCode::hamming_code();
+        let code = CssCode::hypergraph_product(&repetition_code, &hamming_code);
+        let layout = Layout::hypergraph_product(&repetition_code, &hamming_code);
+        assert!(layout.fits(&code));
+        assert_eq!(layout.num_rows(), 5);
+        assert_eq!(layout.num_columns(), 13);
+    }
+
+    #[test]
+    fn stabilizers_are_next_to_their_qubits_in_surface_code() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+        let layout = Layout::hypergraph_product(&repetition_code, &repetition_code);
+        let stabilizers = code.stabilizers.as_ref().pair(layout.stabilizers());
+        for (matrix, positions) in [stabilizers.x, stabilizers.z] {
+            for (row, position) in matrix.rows().zip(positions.iter()) {
+                for qubit in row.non_trivial_positions() {
+                    let qubit = layout.qubit(qubit).unwrap();
+                    let distance = (qubit.0 as isize - position.0 as isize).abs()
+                        + (qubit.1 as isize - position.1 as isize).abs();
+                    assert_eq!(distance, 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn coordinates_survive_serialization() {
+        let code = CssCode::toric_code(3);
+        let json = serde_json::to_string(&code).unwrap();
+        let deserialized: CssCode = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.coordinates(), Some(&Layout::toric_code(3)));
+
+        let code = CssCode::steane_code();
+        let json = serde_json::to_string(&code).unwrap();
+        assert!(!json.contains("coordinates"));
+        let deserialized: CssCode = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, code);
+    }
+
+    #[test]
+    #[should_panic]
+    fn positions_must_be_distinct() {
+        Layout::new(vec![(0, 0), (0, 1)], vec![(1, 0)], vec![(0, 1)]);
+    }
+
+    #[test]
+    fn stabilizers_are_at_the_corners_of_their_qubits_in_rotated_surface_code() {
+        let code = CssCode::rotated_surface_code(5);
+        let layout = code.coordinates().unwrap();
+        let stabilizers = code.stabilizers.as_ref().pair(layout.stabilizers());
+        for (matrix, positions) in [stabilizers.x, stabilizers.z] {
+            for (row, position) in matrix.rows().zip(positions.iter()) {
+                for qubit in row.non_trivial_positions() {
+                    let qubit = layout.qubit(qubit).unwrap();
+                    assert_eq!((qubit.0 as isize - position.0 as isize).abs(), 1);
+                    assert_eq!((qubit.1 as isize - position.1 as isize).abs(), 1);
+                }
+            }
+        }
+    }
+}
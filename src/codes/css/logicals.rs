@@ -10,6 +10,35 @@ pub(super) fn from_linear_codes(x_code: &LinearCode, z_code: &LinearCode) -> Css
     Logicals::new(x_code, z_code).compute()
 }
 
+// Completes the given logicals into a full set of anticommuting pairs.
+// The given logicals are paired first such that pairs that already anticommute
+// and commute with the stabilizers are kept unchanged.
+// Logicals that don't commute with the stabilizers are discarded.
+pub(super) fn complete(
+    x_code: &LinearCode,
+    z_code: &LinearCode,
+    logicals: &Css<SparseBinMat>,
+) -> Css<SparseBinMat> {
+    let mut generators = Logicals::new(x_code, z_code);
+    let x_logicals = logicals
+        .x
+        .rows()
+        .filter(|row| (z_code.parity_check_matrix() * row).is_zero())
+        .map(|row| row.to_vec())
+        .collect::<Vec<_>>();
+    // X generators are popped from the back while Z generators are searched from the front.
+    generators
+        .raw_x_generators
+        .extend(x_logicals.into_iter().rev());
+    let z_logicals = logicals
+        .z
+        .rows()
+        .filter(|row| (x_code.parity_check_matrix() * row).is_zero())
+        .map(|row| row.to_vec());
+    generators.raw_z_generators.splice(0..0, z_logicals);
+    generators.compute()
+}
+
 struct Logicals {
     raw_x_generators: Vec<SparseBinVec>,
     raw_z_generators: Vec<SparseBinVec>,
@@ -136,6 +165,37 @@ mod test {
         assert_commutations(logicals, Css {x: code.parity_check_matrix(), z: code.parity_check_matrix()});
     }
 
+    #[test]
+    fn completion_keeps_given_pairs() {
+        let code = crate::codes::CssCode::toric_code(3);
+        let (x_code, z_code) = (code.x_code(), code.z_code());
+        let logicals = from_linear_codes(&x_code, &z_code);
+        let partial = logicals.map(|logicals| logicals.keep_only_rows(&[1]).unwrap());
+        let completed = complete(&x_code, &z_code, &partial);
+        assert_eq!(completed.x.number_of_rows(), 2);
+        assert_eq!(completed.x.row(0), partial.x.row(0));
+        assert_eq!(completed.z.row(0), partial.z.row(0));
+        assert!((z_code.parity_check_matrix() * &completed.x.transposed()).is_zero());
+        assert!((x_code.parity_check_matrix() * &completed.z.transposed()).is_zero());
+        assert_eq!(
+            &completed.x * &completed.z.transposed(),
+            SparseBinMat::identity(2)
+        );
+    }
+
+    #[test]
+    fn restricted_logicals_are_completed_back() {
+        let code = crate::codes::CssCode::toric_code(3);
+        let restricted = code.restrict_logicals(&[1, 0]);
+        assert_eq!(restricted.logicals.x.row(0), code.logicals.x.row(1));
+        assert_eq!(restricted.logicals.z.row(1), code.logicals.z.row(0));
+        let mut single = code.restrict_logicals(&[0]);
+        assert!(!single.has_complete_logicals());
+        single.complete_logicals();
+        assert!(single.has_complete_logicals());
+        assert_eq!(single.logicals.x.row(0), code.logicals.x.row(0));
+    }
+
     fn assert_commutations(logicals: Css<SparseBinMat>, par_matrices: Css<&SparseBinMat>) {
         assert_logicals_commute_with_stabilizers(&logicals.x, par_matrices.x);
         assert_logicals_commute_with_stabilizers(&logicals.z, par_matrices.z);
@@ -1,16 +1,43 @@
 use crate::{
-    codes::LinearCode,
-    css::{Css, CssOperator, CssSyndrome},
-    noise::NoiseModel,
+    codes::{
+        par_syndromes_of_batch, syndromes_of_batch, Fingerprint, FingerprintHasher, InformationSet,
+        LinearCode,
+    },
+    css::{Css, CssOperator, CssSyndrome, CssSyndromeView},
+    decoders::{
+        lowest_weight_solution, CssErasureDecoder, ErasureDecoder, IsdAlgorithm, SyndromeDecoder,
+        TieBreaking,
+    },
+    noise::{ErasureChannel, NoiseModel, Probability},
 };
-use pauli::{Pauli, PauliOperator};
+use itertools::Itertools;
+use pauli::{Pauli, PauliOperator, X, Y, Z};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
-use sparse_bin_mat::{SparseBinMat, SparseBinSlice};
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+use std::hash::{Hash, Hasher};
+
+mod deployability;
+pub use deployability::StabilizerOverlaps;
+
+mod distance;
+
+mod layout;
+pub use layout::Layout;
 
 mod logicals;
 use logicals::from_linear_codes;
 
+mod symplectic;
+
+mod weights;
+pub use weights::{CosetWeightDistribution, WeightDistributionError};
+
+mod validation;
+pub use validation::CssInconsistency;
+
+mod zoo;
+
 /// A quantum CSS code is defined from a pair of orthogonal linear codes.
 /// The checks of the first code are used as a binary representation
 /// of the X stabilizers while the checks of the second code are used
@@ -21,10 +48,40 @@ use logicals::from_linear_codes;
 ///
 /// Any stabilizer generator or logical generator of a CSS code is
 /// either composed of only Is and Xs or only Is and Zs.
-#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+///
+/// A code can optionally carry the [coordinates](CssCode::coordinates)
+/// of its qubits and stabilizers.
+/// Two codes are equal if they have the same stabilizers and logicals,
+/// whatever their coordinates.
+///
+/// With the `checked-serde` feature, deserialization fails
+/// if the code is not [consistent](CssCode::validate)
+/// or if the coordinates don't fit the code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "checked-serde",
+    serde(try_from = "validation::UncheckedCssCode")
+)]
 pub struct CssCode {
     pub stabilizers: Css<SparseBinMat>,
     pub logicals: Css<SparseBinMat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    coordinates: Option<Layout>,
+}
+
+impl PartialEq for CssCode {
+    fn eq(&self, other: &Self) -> bool {
+        self.stabilizers == other.stabilizers && self.logicals == other.logicals
+    }
+}
+
+impl Eq for CssCode {}
+
+impl Hash for CssCode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.stabilizers.hash(state);
+        self.logicals.hash(state);
+    }
 }
 
 impl CssCode {
@@ -46,6 +103,7 @@ impl CssCode {
                 z: z_code.parity_check_matrix().clone(),
             },
             logicals: from_linear_codes(x_code, z_code),
+            coordinates: None,
         })
     }
 
@@ -77,22 +135,72 @@ impl CssCode {
                 x: SparseBinMat::new(9, vec![vec![0, 1, 2]]),
                 z: SparseBinMat::new(9, vec![vec![0, 3, 6]]),
             },
+            coordinates: None,
         }
     }
 
     /// Returns an instance of the toric code with given distance.
+    ///
+    /// The code comes with the coordinates of the
+    /// [toric code layout](Layout::toric_code).
     pub fn toric_code(distance: usize) -> Self {
-        let checks = (0..distance - 1)
-            .map(|c| vec![c, c + 1])
-            .chain(std::iter::once(vec![0, distance - 1]))
-            .collect();
-        let matrix = SparseBinMat::new(distance, checks);
-        let code = LinearCode::from_parity_check_matrix(matrix);
+        let code = cycle_code(distance);
         Self::hypergraph_product(&code, &code)
     }
 
+    /// Returns an instance of the rotated surface code with given distance.
+    ///
+    /// The qubits are on a `distance` by `distance` grid, the qubit in row `r`
+    /// and column `c` being `r * distance + c`.
+    /// Each face of the grid supports a stabilizer of weight 4,
+    /// alternating between X and Z like a checkerboard,
+    /// and weight 2 stabilizers complete the left and right boundaries with X
+    /// and the top and bottom boundaries with Z.
+    /// The stabilizers are sorted by position.
+    ///
+    /// The code comes with the coordinates of the
+    /// [rotated surface code layout](Layout::rotated_surface_code).
+    ///
+    /// # Panic
+    ///
+    /// Panics if the distance is less than 2.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::rotated_surface_code(3);
+    ///
+    /// assert_eq!(code.len(), 9);
+    /// assert_eq!((code.num_x_stabs(), code.num_z_stabs()), (4, 4));
+    /// assert_eq!(code.num_logical_qubits(), 1);
+    /// assert!(code.verify_distance_at_least(3));
+    /// ```
+    pub fn rotated_surface_code(distance: usize) -> Self {
+        let checks = |plaquettes: Vec<(usize, usize)>| {
+            let checks = plaquettes
+                .into_iter()
+                .map(|plaquette| {
+                    rotated_surface_corners(distance, plaquette)
+                        .iter()
+                        .flatten()
+                        .cloned()
+                        .sorted()
+                        .collect()
+                })
+                .collect();
+            LinearCode::from_parity_check_matrix(SparseBinMat::new(distance * distance, checks))
+        };
+        let plaquettes = rotated_surface_plaquettes(distance);
+        Self::new(&checks(plaquettes.x), &checks(plaquettes.z))
+            .with_coordinates(Layout::rotated_surface_code(distance))
+    }
+
     /// Returns the hypergraph product of two linear codes.
     ///
+    /// The code comes with the coordinates of the
+    /// [hypergraph product layout](Layout::hypergraph_product).
+    ///
     /// # Example
     ///
     /// ```
@@ -116,6 +224,7 @@ impl CssCode {
             &LinearCode::from_parity_check_matrix(x_checks),
             &LinearCode::from_parity_check_matrix(z_checks),
         )
+        .with_coordinates(Layout::hypergraph_product(first_code, second_code))
     }
 
     fn hypergraph_product_x_checks(
@@ -145,6 +254,62 @@ impl CssCode {
             )
     }
 
+    /// Attaches the positions of the qubits and stabilizers to the code.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the layout has a different number of qubits or stabilizers than the code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::{CssCode, Layout};
+    /// let layout = Layout::new(
+    ///     (0..9).map(|qubit| (2 * (qubit / 3), 2 * (qubit % 3))).collect(),
+    ///     vec![(1, 2), (3, 2)],
+    ///     (0..6).map(|stabilizer| (2 * (stabilizer / 2), 2 * (stabilizer % 2) + 1)).collect(),
+    /// );
+    /// let code = CssCode::shor_code().with_coordinates(layout.clone());
+    ///
+    /// assert_eq!(code.coordinates(), Some(&layout));
+    /// assert_eq!(CssCode::shor_code().coordinates(), None);
+    /// ```
+    pub fn with_coordinates(mut self, layout: Layout) -> Self {
+        if !layout.fits(&self) {
+            panic!("layout doesn't fit the code");
+        }
+        self.coordinates = Some(layout);
+        self
+    }
+
+    /// Returns the positions of the qubits and stabilizers
+    /// or None if the code has no geometry.
+    ///
+    /// The [toric code](CssCode::toric_code) and the
+    /// [hypergraph products](CssCode::hypergraph_product) set their coordinates automatically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::{CssCode, LinearCode};
+    /// let code = CssCode::toric_code(3);
+    /// let coordinates = code.coordinates().unwrap();
+    ///
+    /// assert_eq!(coordinates.qubit(0), Some((0, 0)));
+    /// assert_eq!(coordinates.x_stabilizer(0), Some((0, 1)));
+    ///
+    /// // The coordinates don't change the identity of the code.
+    /// let same_code = CssCode::new(
+    ///     &LinearCode::from_parity_check_matrix(code.stabilizers.x.clone()),
+    ///     &LinearCode::from_parity_check_matrix(code.stabilizers.z.clone()),
+    /// );
+    /// assert_eq!(same_code.coordinates(), None);
+    /// assert_eq!(code, same_code);
+    /// ```
+    pub fn coordinates(&self) -> Option<&Layout> {
+        self.coordinates.as_ref()
+    }
+
     /// Returns the number of physical qubits in the code.
     pub fn len(&self) -> usize {
         self.stabilizers.x.number_of_columns()
@@ -175,6 +340,191 @@ impl CssCode {
         self.logicals.z.number_of_rows()
     }
 
+    /// Returns the number of logical qubits encoded by the code,
+    /// that is the length minus the ranks of the X and Z stabilizers.
+    ///
+    /// This doesn't depend on the stored logical generators.
+    /// If the stabilizers don't commute, their ranks can add up to more than the length
+    /// and 0 is returned. Such a code is reported by [`validate`](Self::validate).
+    pub fn num_logical_qubits(&self) -> usize {
+        let (x_rank, z_rank) = self.stabilizer_ranks();
+        self.len().saturating_sub(x_rank + z_rank)
+    }
+
+    // The ranks of the X and Z stabilizers.
+    fn stabilizer_ranks(&self) -> (usize, usize) {
+        let columns = (0..self.len()).collect::<Vec<_>>();
+        let rank = |matrix| InformationSet::new(matrix, &columns).rank();
+        (rank(&self.stabilizers.x), rank(&self.stabilizers.z))
+    }
+
+    /// Returns the number of logical qubits per physical qubit
+    /// or 0 if the code has no qubit.
+    ///
+    /// The number of logical qubits is computed from the ranks of the stabilizers,
+    /// such that redundant stabilizers are not counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// // The toric code has 18 qubits, 9 X and 9 Z stabilizers, but 2 logical qubits.
+    /// let code = CssCode::toric_code(3);
+    /// assert_eq!(code.encoding_rate(), 2.0 / 18.0);
+    /// ```
+    pub fn encoding_rate(&self) -> f64 {
+        if self.is_empty() {
+            0.0
+        } else {
+            self.num_logical_qubits() as f64 / self.len() as f64
+        }
+    }
+
+    /// Returns the number of physical qubits per logical qubit
+    /// or None if the code encodes no logical qubit.
+    ///
+    /// Only the data qubits are counted, not the ancillas measuring the stabilizers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// assert_eq!(CssCode::steane_code().qubit_overhead_per_logical(), Some(7.0));
+    /// assert_eq!(CssCode::toric_code(3).qubit_overhead_per_logical(), Some(9.0));
+    /// ```
+    pub fn qubit_overhead_per_logical(&self) -> Option<f64> {
+        match self.num_logical_qubits() {
+            0 => None,
+            num_logicals => Some(self.len() as f64 / num_logicals as f64),
+        }
+    }
+
+    /// Checks if the stored logical generators form an anticommuting pair
+    /// of X and Z logicals for each logical qubit
+    /// and commute with the stabilizers of the other type.
+    pub fn has_complete_logicals(&self) -> bool {
+        let num_pairs = self.num_logical_qubits();
+        self.logicals.x.number_of_rows() == num_pairs
+            && self.logicals.z.number_of_rows() == num_pairs
+            && (&self.stabilizers.z * &self.logicals.x.transposed()).is_zero()
+            && (&self.stabilizers.x * &self.logicals.z.transposed()).is_zero()
+            && &self.logicals.x * &self.logicals.z.transposed() == SparseBinMat::identity(num_pairs)
+    }
+
+    /// Completes the stored logical generators
+    /// such that there is an anticommuting pair for each logical qubit.
+    ///
+    /// Stored pairs that already anticommute with each other
+    /// and commute with the stabilizers are kept in the same order
+    /// while the missing pairs are found by linear algebra.
+    /// Stored logicals that don't commute with the stabilizers are discarded.
+    /// This does nothing if the logicals are already
+    /// [complete](Self::has_complete_logicals).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let mut code = CssCode::toric_code(3);
+    /// let first_pair = code.logicals.map(|logicals| logicals.keep_only_rows(&[0]).unwrap());
+    /// code.logicals = first_pair.clone();
+    /// assert!(!code.has_complete_logicals());
+    ///
+    /// code.complete_logicals();
+    /// assert!(code.has_complete_logicals());
+    /// assert_eq!(code.num_x_logicals(), 2);
+    /// assert_eq!(code.logicals.x.row(0), first_pair.x.row(0));
+    /// ```
+    pub fn complete_logicals(&mut self) {
+        if !self.has_complete_logicals() {
+            self.logicals = logicals::complete(&self.x_code(), &self.z_code(), &self.logicals);
+        }
+    }
+
+    /// Returns a copy of the code tracking only the given pairs of logicals.
+    ///
+    /// The logicals are first [completed](Self::complete_logicals)
+    /// and only the selected pairs are kept in the given order.
+    /// The other logical qubits are treated as gauge qubits,
+    /// such that an operator acting only on them is a [stabilizer](Self::has_stabilizer)
+    /// of the restricted code and a simulation doesn't count it as a failure.
+    ///
+    /// The restricted code has fewer logicals than logical qubits,
+    /// so it is not [complete](Self::has_complete_logicals)
+    /// and [`validate`](Self::validate) reports the missing logicals.
+    ///
+    /// # Panic
+    ///
+    /// Panics if an index is not smaller than the number of logical qubits
+    /// or if an index is repeated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::toric_code(3);
+    /// let restricted = code.restrict_logicals(&[1]);
+    /// assert_eq!(restricted.num_x_logicals(), 1);
+    ///
+    /// // The first X logical acts only on the untracked logical qubit.
+    /// let logical = code.logicals().next().unwrap();
+    /// assert!(!code.has_stabilizer(&logical));
+    /// assert!(restricted.has_stabilizer(&logical));
+    /// ```
+    pub fn restrict_logicals(&self, indices: &[usize]) -> Self {
+        let mut code = self.clone();
+        code.complete_logicals();
+        let num_pairs = code.num_x_logicals();
+        for (position, index) in indices.iter().enumerate() {
+            if *index >= num_pairs {
+                panic!(
+                    "logical {} is out of bound for code with {} logical qubits",
+                    index, num_pairs
+                );
+            }
+            if indices[..position].contains(index) {
+                panic!("logical {} is selected twice", index);
+            }
+        }
+        code.logicals = code.logicals.map(|logicals| {
+            let rows = indices
+                .iter()
+                .map(|index| {
+                    logicals
+                        .row(*index)
+                        .unwrap()
+                        .non_trivial_positions()
+                        .collect()
+                })
+                .collect();
+            SparseBinMat::new(logicals.number_of_columns(), rows)
+        });
+        code
+    }
+
+    /// Returns a stable fingerprint of the X and Z stabilizers.
+    ///
+    /// See [`Fingerprint`](crate::codes::Fingerprint) for the canonicalization of the checks.
+    /// The fingerprint is different from the fingerprint of a linear code
+    /// with the same checks and swapping the X and Z stabilizers
+    /// changes the fingerprint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::steane_code();
+    ///
+    /// assert_eq!(code.fingerprint(), CssCode::steane_code().fingerprint());
+    /// assert_ne!(code.fingerprint(), CssCode::shor_code().fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> Fingerprint {
+        let mut hasher = FingerprintHasher::new("CssCode");
+        hasher.write_checks(&self.stabilizers.x);
+        hasher.write_checks(&self.stabilizers.z);
+        hasher.finish()
+    }
+
     /// Returns both the X and Z parts of the syndrome of the given operator.
     ///
     /// The X part is the syndrome obtained from the X stabilizers and
@@ -204,6 +554,67 @@ impl CssCode {
             .map(|(stabs, operator)| *stabs * operator)
     }
 
+    /// Returns the syndrome of each operator.
+    ///
+    /// Same as [`LinearCode::syndromes_of_batch`]
+    /// for both the X and Z stabilizers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use pauli::{X, Z, PauliOperator};
+    ///
+    /// let code = CssCode::shor_code();
+    /// let errors = vec![
+    ///     PauliOperator::new(9, vec![1, 7], vec![X, Z]),
+    ///     PauliOperator::new(9, vec![4], vec![Z]),
+    /// ];
+    /// let syndromes = code.syndromes_of_batch(&errors);
+    ///
+    /// assert_eq!(syndromes[0], code.syndrome_of(&errors[0]));
+    /// assert_eq!(syndromes[1], code.syndrome_of(&errors[1]));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if an operator have a different length then the code.
+    pub fn syndromes_of_batch(&self, operators: &[PauliOperator]) -> Vec<CssSyndrome> {
+        self.syndromes_of_batch_with(operators, syndromes_of_batch)
+    }
+
+    /// Same as [`syndromes_of_batch`](Self::syndromes_of_batch),
+    /// but the batches are processed in parallel.
+    ///
+    /// # Panic
+    ///
+    /// Panics if an operator have a different length then the code.
+    pub fn par_syndromes_of_batch(&self, operators: &[PauliOperator]) -> Vec<CssSyndrome> {
+        self.syndromes_of_batch_with(operators, par_syndromes_of_batch)
+    }
+
+    fn syndromes_of_batch_with<F>(
+        &self,
+        operators: &[PauliOperator],
+        syndromes: F,
+    ) -> Vec<CssSyndrome>
+    where
+        F: Fn(&SparseBinMat, &[SparseBinVec]) -> Vec<SparseBinVec>,
+    {
+        let (z_parts, x_parts): (Vec<_>, Vec<_>) = operators
+            .iter()
+            .map(|operator| {
+                let operator = CssOperator::from(operator);
+                (operator.z, operator.x)
+            })
+            .unzip();
+        syndromes(&self.stabilizers.x, &z_parts)
+            .into_iter()
+            .zip(syndromes(&self.stabilizers.z, &x_parts))
+            .map(|(x, z)| CssSyndrome { x, z })
+            .collect()
+    }
+
     /// Checks if an operator is a (potentially trivial) logical operator of the code.
     ///
     /// # Example
@@ -251,6 +662,459 @@ impl CssCode {
                 .all(|logical| logical.commutes_with(operator))
     }
 
+    /// Returns the number of qubits where two operators differ,
+    /// that is the weight of their product.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use pauli::{PauliOperator, X, Y, Z};
+    ///
+    /// let code = CssCode::steane_code();
+    /// let first = PauliOperator::new(7, vec![0, 1, 2], vec![X, Y, Z]);
+    /// let second = PauliOperator::new(7, vec![1, 2], vec![Y, X]);
+    ///
+    /// assert_eq!(code.weight_of_difference(&first, &second), 2);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if an operator length is different from the length of the code.
+    pub fn weight_of_difference(&self, first: &PauliOperator, second: &PauliOperator) -> usize {
+        if first.len() != self.len() || second.len() != self.len() {
+            panic!(
+                "operators of lengths {} and {} are invalid for code with length {}",
+                first.len(),
+                second.len(),
+                self.len()
+            );
+        }
+        (first * second).weight()
+    }
+
+    /// Checks that the code has no non trivial logical operator
+    /// of weight less than the given distance.
+    ///
+    /// See [`logical_of_weight_less_than`](CssCode::logical_of_weight_less_than).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::steane_code();
+    ///
+    /// assert!(code.verify_distance_at_least(3));
+    /// assert!(!code.verify_distance_at_least(4));
+    /// ```
+    pub fn verify_distance_at_least(&self, distance: usize) -> bool {
+        self.logical_of_weight_less_than(distance).is_none()
+    }
+
+    /// Returns a non trivial logical operator of weight less than the given weight
+    /// or None if there is no such operator.
+    ///
+    /// The returned operator is either of X type or Z type and has the smallest
+    /// possible weight.
+    ///
+    /// # Warning
+    ///
+    /// This enumerates all operators of weight less than the given weight.
+    /// The weight classes are processed in parallel and the search stops as soon
+    /// as a logical operator is found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use pauli::{PauliOperator, X};
+    ///
+    /// let code = CssCode::shor_code();
+    ///
+    /// assert_eq!(code.logical_of_weight_less_than(3), None);
+    /// assert_eq!(
+    ///     code.logical_of_weight_less_than(4),
+    ///     Some(PauliOperator::new(9, vec![0, 1, 2], vec![X; 3]))
+    /// );
+    /// ```
+    pub fn logical_of_weight_less_than(&self, weight: usize) -> Option<PauliOperator> {
+        distance::find_logical_of_weight_less_than(self, weight)
+    }
+
+    /// Returns the lowest weight non trivial logical operator found
+    /// by information set decoding or None if no logical is found.
+    ///
+    /// For each logical, this searches for low weight operators
+    /// commuting with the stabilizers and anticommuting with the logical
+    /// using the given number of iterations.
+    /// The weight of the returned operator is an upper bound on the distance of the code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::decoders::IsdAlgorithm;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let code = CssCode::toric_code(4);
+    /// let logical = code
+    ///     .minimum_weight_logical_isd(IsdAlgorithm::Prange, 200, &mut StdRng::seed_from_u64(123))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(logical.weight(), 4);
+    /// assert!(code.has_logical(&logical));
+    /// ```
+    pub fn minimum_weight_logical_isd<R: Rng>(
+        &self,
+        algorithm: IsdAlgorithm,
+        num_iterations: usize,
+        rng: &mut R,
+    ) -> Option<PauliOperator> {
+        let mut best: Option<PauliOperator> = None;
+        let sectors = [
+            (self.z_stabs_binary(), self.z_logicals_binary(), true),
+            (self.x_stabs_binary(), self.x_logicals_binary(), false),
+        ];
+        for (stabilizers, logicals, is_x_type) in sectors {
+            let syndrome = SparseBinVec::new(
+                stabilizers.number_of_rows() + 1,
+                vec![stabilizers.number_of_rows()],
+            );
+            for logical in logicals.rows() {
+                let matrix = stabilizers.vertical_concat_with(&SparseBinMat::new(
+                    self.len(),
+                    vec![logical.as_slice().to_vec()],
+                ));
+                let support = match lowest_weight_solution(
+                    &matrix,
+                    syndrome.as_view(),
+                    algorithm,
+                    num_iterations,
+                    TieBreaking::FirstFound,
+                    rng,
+                ) {
+                    Some(support) => support,
+                    None => continue,
+                };
+                let zeros = SparseBinVec::zeros(self.len());
+                let candidate = PauliOperator::from(if is_x_type {
+                    CssOperator {
+                        x: support,
+                        z: zeros,
+                    }
+                } else {
+                    CssOperator {
+                        x: zeros,
+                        z: support,
+                    }
+                });
+                match &best {
+                    Some(best) if best.weight() <= candidate.weight() => (),
+                    _ => best = Some(candidate),
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns a non trivial logical operator of minimum weight
+    /// or None if the code doesn't encode any qubit.
+    ///
+    /// For each logical, this solves an integer program searching for the smallest operator
+    /// commuting with the stabilizers and anticommuting with the logical.
+    /// The weight of the returned operator is the distance of the code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::toric_code(4);
+    /// let logical = code.minimum_weight_logical_ilp().unwrap().unwrap();
+    ///
+    /// assert_eq!(logical.weight(), 4);
+    /// assert!(code.has_logical(&logical));
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// This requires the `ilp` feature and returns an error
+    /// if the solver fails.
+    #[cfg(feature = "ilp")]
+    pub fn minimum_weight_logical_ilp(
+        &self,
+    ) -> Result<Option<PauliOperator>, good_lp::ResolutionError> {
+        self.minimum_weight_logical_with(crate::codes::ilp::minimum_weight_kernel_vector)
+    }
+
+    /// Returns a non trivial logical operator of minimum weight
+    /// or None if the code doesn't encode any qubit.
+    ///
+    /// This is the same as [`minimum_weight_logical_ilp`](Self::minimum_weight_logical_ilp),
+    /// but each search is encoded as a boolean formula solved by a SAT solver
+    /// while bisecting the weight.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::toric_code(3);
+    /// let logical = code.minimum_weight_logical_sat().unwrap().unwrap();
+    ///
+    /// assert_eq!(logical.weight(), 3);
+    /// assert!(code.has_logical(&logical));
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// This requires the `sat` feature and returns an error
+    /// if the solver fails.
+    #[cfg(feature = "sat")]
+    pub fn minimum_weight_logical_sat(
+        &self,
+    ) -> Result<Option<PauliOperator>, varisat::solver::SolverError> {
+        self.minimum_weight_logical_with(crate::codes::sat::minimum_weight_kernel_vector)
+    }
+
+    // The lowest weight logical found by searching, for each logical,
+    // the smallest operator commuting with the stabilizers and anticommuting with the logical.
+    #[cfg(any(feature = "ilp", feature = "sat"))]
+    fn minimum_weight_logical_with<E, F>(&self, mut search: F) -> Result<Option<PauliOperator>, E>
+    where
+        F: FnMut(&SparseBinMat, Option<SparseBinSlice>) -> Result<Option<SparseBinVec>, E>,
+    {
+        let mut best: Option<PauliOperator> = None;
+        let sectors = [
+            (self.z_stabs_binary(), self.z_logicals_binary(), true),
+            (self.x_stabs_binary(), self.x_logicals_binary(), false),
+        ];
+        for (stabilizers, logicals, is_x_type) in sectors {
+            for logical in logicals.rows() {
+                let support = match search(stabilizers, Some(logical))? {
+                    Some(support) => support,
+                    None => continue,
+                };
+                let zeros = SparseBinVec::zeros(self.len());
+                let candidate = PauliOperator::from(if is_x_type {
+                    CssOperator {
+                        x: support,
+                        z: zeros,
+                    }
+                } else {
+                    CssOperator {
+                        x: zeros,
+                        z: support,
+                    }
+                });
+                match &best {
+                    Some(best) if best.weight() <= candidate.weight() => (),
+                    _ => best = Some(candidate),
+                }
+            }
+        }
+        Ok(best)
+    }
+
+    /// Returns the exact weight distributions of the logical cosets
+    /// of the X and Z sectors.
+    ///
+    /// The X distribution counts the X operators commuting with the Z stabilizers
+    /// grouped by the Z logicals they anticommute with and vice-versa.
+    ///
+    /// # Warning
+    ///
+    /// This enumerates all the `2^(n - r)` operators commuting with
+    /// the stabilizers of the other sector where `r` is the rank of these stabilizers.
+    /// Returns an error if `n - r` is 64 or more
+    /// or if there are too many logicals to index their classes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::steane_code();
+    /// let distributions = code.coset_weight_distributions().unwrap();
+    ///
+    /// assert_eq!(distributions.x.stabilizer_counts(), &[1, 0, 0, 0, 7, 0, 0, 0]);
+    /// assert_eq!(distributions.x.counts(1), &[0, 0, 0, 7, 0, 0, 0, 1]);
+    /// ```
+    pub fn coset_weight_distributions(
+        &self,
+    ) -> Result<Css<CosetWeightDistribution>, WeightDistributionError> {
+        Ok(Css {
+            x: CosetWeightDistribution::exact(&self.stabilizers.z.nullspace(), &self.logicals.z)?,
+            z: CosetWeightDistribution::exact(&self.stabilizers.x.nullspace(), &self.logicals.x)?,
+        })
+    }
+
+    /// Estimates the weight distributions of the logical cosets
+    /// of the X and Z sectors from the given number of uniform samples.
+    ///
+    /// This is the sampled version of
+    /// [`coset_weight_distributions`](CssCode::coset_weight_distributions)
+    /// for codes too large to enumerate.
+    /// Returns an error if there are too many logicals to index their classes.
+    pub fn sample_coset_weight_distributions<R: Rng>(
+        &self,
+        num_samples: u64,
+        rng: &mut R,
+    ) -> Result<Css<CosetWeightDistribution>, WeightDistributionError> {
+        Ok(Css {
+            x: CosetWeightDistribution::sampled(
+                &self.stabilizers.z.nullspace(),
+                &self.logicals.z,
+                num_samples,
+                rng,
+            )?,
+            z: CosetWeightDistribution::sampled(
+                &self.stabilizers.x.nullspace(),
+                &self.logicals.x,
+                num_samples,
+                rng,
+            )?,
+        })
+    }
+
+    /// Estimates the probability to recover from an erasure
+    /// of each qubit with the given probability.
+    ///
+    /// This averages the [recovery probability](ErasureDecoder::recovery_probability)
+    /// of erasures sampled from an [`ErasureChannel`].
+    /// Comparing the estimates for growing codes of a family locates the erasure threshold.
+    /// Use [`exact_erasure_recovery`](CssCode::exact_erasure_recovery) for small codes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::noise::Probability;
+    /// use rand::thread_rng;
+    ///
+    /// let code = CssCode::toric_code(4);
+    /// let low = code.erasure_threshold_estimate(Probability::new(0.1), 1000, &mut thread_rng());
+    /// let high = code.erasure_threshold_estimate(Probability::new(0.9), 1000, &mut thread_rng());
+    /// assert!(low > high);
+    /// ```
+    pub fn erasure_threshold_estimate<R: Rng>(
+        &self,
+        probability: Probability,
+        num_trials: usize,
+        rng: &mut R,
+    ) -> f64 {
+        if num_trials == 0 {
+            return 0.0;
+        }
+        let channel = ErasureChannel::with_probability(probability);
+        let decoder = CssErasureDecoder::new(self);
+        let total = (0..num_trials)
+            .map(|_| {
+                let erasure = channel.sample_error_of_length(self.len(), rng);
+                decoder.recovery_probability(erasure.as_view())
+            })
+            .sum::<f64>();
+        total / num_trials as f64
+    }
+
+    /// Returns the probability to recover from an erasure
+    /// of each qubit with the given probability.
+    ///
+    /// This is the exact version of
+    /// [`erasure_threshold_estimate`](CssCode::erasure_threshold_estimate).
+    ///
+    /// # Warning
+    ///
+    /// This enumerates all the `2^n` erasures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::noise::Probability;
+    ///
+    /// let code = CssCode::steane_code();
+    /// assert_eq!(code.exact_erasure_recovery(Probability::new(0.0)), 1.0);
+    /// assert_eq!(code.exact_erasure_recovery(Probability::new(1.0)), 0.5_f64.powi(2));
+    ///
+    /// // Any 2 erasures are recovered since the distance is 3.
+    /// let probability = code.exact_erasure_recovery(Probability::new(0.01));
+    /// assert!(probability > 1.0 - 35.0 * 0.01_f64.powi(3));
+    /// ```
+    pub fn exact_erasure_recovery(&self, probability: Probability) -> f64 {
+        let decoder = CssErasureDecoder::new(self);
+        let p = probability.value();
+        (0..=self.len())
+            .map(|weight| {
+                let recovered = (0..self.len())
+                    .combinations(weight)
+                    .map(|positions| {
+                        let erasure = SparseBinVec::new(self.len(), positions);
+                        decoder.recovery_probability(erasure.as_view())
+                    })
+                    .sum::<f64>();
+                recovered * p.powi(weight as i32) * (1.0 - p).powi((self.len() - weight) as i32)
+            })
+            .sum()
+    }
+
+    /// Checks that the decoder corrects every Pauli error of weight at most `max_weight`
+    /// or returns the first error that is not corrected.
+    ///
+    /// An error is corrected if the product of the error with the correction
+    /// returned by the decoder for its syndrome is a stabilizer.
+    /// Errors are enumerated by increasing weight.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::{CssCode, LinearCode};
+    /// use ldpc::decoders::{BpDecoder, CssDecoder};
+    /// use ldpc::noise::Probability;
+    ///
+    /// let repetition_code = LinearCode::repetition_code(3);
+    /// let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+    /// let decoder = CssDecoder {
+    ///     x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.1), 10),
+    ///     z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.1), 10),
+    /// };
+    ///
+    /// assert!(code.corrects_all_errors_up_to(1, &decoder).is_ok());
+    ///
+    /// let counterexample = code.corrects_all_errors_up_to(2, &decoder).unwrap_err();
+    /// assert_eq!(counterexample.weight(), 2);
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// The number of errors to decode scales exponentially with the maximum weight.
+    pub fn corrects_all_errors_up_to<D>(
+        &self,
+        max_weight: usize,
+        decoder: &D,
+    ) -> Result<(), PauliOperator>
+    where
+        D: for<'a> SyndromeDecoder<CssSyndromeView<'a>, CssOperator>,
+    {
+        for error in (0..=max_weight).flat_map(|weight| self.errors_of_weight(weight)) {
+            let syndrome = self.syndrome_of(&error);
+            let correction: PauliOperator = decoder.correction_for(syndrome.as_view()).into();
+            if !self.has_stabilizer(&(&error * &correction)) {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    fn errors_of_weight(&self, weight: usize) -> impl Iterator<Item = PauliOperator> + '_ {
+        (0..self.len())
+            .combinations(weight)
+            .flat_map(move |positions| {
+                (0..weight)
+                    .map(|_| [X, Y, Z])
+                    .multi_cartesian_product()
+                    .map(move |paulis| PauliOperator::new(self.len(), positions.clone(), paulis))
+            })
+    }
+
     /// Returns the binary matrix representing the X stabilizer
     /// generators in binary form.
     pub fn x_stabs_binary(&self) -> &SparseBinMat {
@@ -275,6 +1139,39 @@ impl CssCode {
         &self.logicals.z
     }
 
+    /// Returns the classical code whose parity checks are the X stabilizers.
+    ///
+    /// The syndrome of the Z part of an error for this code
+    /// is its syndrome for the X stabilizers,
+    /// such that classical decoders can be applied to Z errors directly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::css::CssOperator;
+    /// use pauli::{PauliOperator, Z};
+    ///
+    /// let code = CssCode::steane_code();
+    /// let error = PauliOperator::new(7, vec![2], vec![Z]);
+    ///
+    /// let x_code = code.x_code();
+    /// let z_part = CssOperator::from(&error).z;
+    /// assert_eq!(x_code.syndrome_of(&z_part), code.syndrome_of(&error).x);
+    /// assert_eq!(CssCode::new(&x_code, &code.z_code()), code);
+    /// ```
+    pub fn x_code(&self) -> LinearCode {
+        LinearCode::from_parity_check_matrix(self.stabilizers.x.clone())
+    }
+
+    /// Returns the classical code whose parity checks are the Z stabilizers.
+    ///
+    /// The syndrome of the X part of an error for this code
+    /// is its syndrome for the Z stabilizers.
+    pub fn z_code(&self) -> LinearCode {
+        LinearCode::from_parity_check_matrix(self.stabilizers.z.clone())
+    }
+
     /// Returns an iterator throught all stabilizer generators of the code.
     ///
     /// # Example
@@ -338,6 +1235,36 @@ impl CssCode {
         )
     }
 
+    /// Returns a uniformly random element of the stabilizer group.
+    ///
+    /// Each stabilizer generator is included with probability one half.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use rand::thread_rng;
+    ///
+    /// let code = CssCode::steane_code();
+    /// let stabilizer = code.random_stabilizer(&mut thread_rng());
+    ///
+    /// assert!(code.has_stabilizer(&stabilizer));
+    /// ```
+    pub fn random_stabilizer<R: Rng>(&self, rng: &mut R) -> PauliOperator {
+        let operator = CssOperator {
+            x: Self::random_combination_of_rows(&self.stabilizers.x, rng),
+            z: Self::random_combination_of_rows(&self.stabilizers.z, rng),
+        };
+        operator.into()
+    }
+
+    fn random_combination_of_rows<R: Rng>(matrix: &SparseBinMat, rng: &mut R) -> SparseBinVec {
+        matrix.rows().filter(|_| rng.gen()).fold(
+            SparseBinVec::zeros(matrix.number_of_columns()),
+            |sum, row| &sum + &row,
+        )
+    }
+
     /// Generates a random error with the given noise model.
     ///
     /// # Example
@@ -363,10 +1290,67 @@ impl CssCode {
     }
 }
 
+// The repetition code with periodic boundary.
+fn cycle_code(distance: usize) -> LinearCode {
+    let checks = (0..distance - 1)
+        .map(|c| vec![c, c + 1])
+        .chain(std::iter::once(vec![0, distance - 1]))
+        .collect();
+    LinearCode::from_parity_check_matrix(SparseBinMat::new(distance, checks))
+}
+
+// The faces (a, b) of the rotated surface code supporting X and Z stabilizers,
+// where the face (a, b) has the qubits of rows a - 1 and a and columns b - 1 and b.
+// The faces of the X stabilizers are those where a + b is odd.
+fn rotated_surface_plaquettes(distance: usize) -> Css<Vec<(usize, usize)>> {
+    if distance < 2 {
+        panic!("distance {} is less than 2", distance);
+    }
+    let mut plaquettes = Css {
+        x: Vec::new(),
+        z: Vec::new(),
+    };
+    for (a, b) in (0..=distance).cartesian_product(0..=distance) {
+        let is_x = (a + b) % 2 == 1;
+        let is_vertical_boundary = b == 0 || b == distance;
+        let is_horizontal_boundary = a == 0 || a == distance;
+        match (is_x, is_horizontal_boundary, is_vertical_boundary) {
+            (_, true, true) => (),
+            (true, false, _) => plaquettes.x.push((a, b)),
+            (false, _, false) => plaquettes.z.push((a, b)),
+            _ => (),
+        }
+    }
+    plaquettes
+}
+
+// The north west, north east, south west and south east qubits of a face
+// of the rotated surface code or None for those outside of the grid.
+pub(crate) fn rotated_surface_corners(
+    distance: usize,
+    (a, b): (usize, usize),
+) -> [Option<usize>; 4] {
+    let qubit = |row: Option<usize>, column: Option<usize>| {
+        row.zip(column)
+            .filter(|(row, column)| *row < distance && *column < distance)
+            .map(|(row, column)| row * distance + column)
+    };
+    let (north, south) = (a.checked_sub(1), Some(a));
+    let (west, east) = (b.checked_sub(1), Some(b));
+    [
+        qubit(north, west),
+        qubit(north, east),
+        qubit(south, west),
+        qubit(south, east),
+    ]
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum CssError {
     DifferentXandZLength(usize, usize),
     NonOrthogonalCodes,
+    OddSymplecticLength(usize),
+    MixedSymplecticCheck(usize),
 }
 
 impl std::fmt::Display for CssError {
@@ -376,6 +1360,12 @@ impl std::fmt::Display for CssError {
                 write!(f, "different x and z lengths: {} & {}", x_length, z_length)
             }
             Self::NonOrthogonalCodes => write!(f, "codes are not orthogonal"),
+            Self::OddSymplecticLength(length) => {
+                write!(f, "symplectic code of odd length {}", length)
+            }
+            Self::MixedSymplecticCheck(check) => {
+                write!(f, "check {} acts on both X and Z parts", check)
+            }
         }
     }
 }
@@ -0,0 +1,120 @@
+use super::{CssCode, CssError};
+use crate::codes::LinearCode;
+use sparse_bin_mat::SparseBinMat;
+
+impl CssCode {
+    /// Returns the binary symplectic representation of the stabilizers
+    /// as a linear code over twice as many bits.
+    ///
+    /// The parity check matrix is `[Hx | 0 ; 0 | Hz]`,
+    /// that is the X stabilizers act on the first half of the bits
+    /// and the Z stabilizers act on the second half.
+    /// This gives access to the analysis tools of linear codes,
+    /// such as the girth or the export to alist, for quantum codes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use sparse_bin_mat::SparseBinMat;
+    ///
+    /// let code = CssCode::shor_code();
+    /// let symplectic = code.to_symplectic_linear_code();
+    ///
+    /// assert_eq!(symplectic.len(), 18);
+    /// assert_eq!(symplectic.num_checks(), 8);
+    /// assert_eq!(symplectic.check(2).unwrap().as_slice(), &[9, 10]);
+    ///
+    /// let recovered = CssCode::from_symplectic_linear_code(&symplectic).unwrap();
+    /// assert_eq!(recovered.stabilizers, code.stabilizers);
+    /// ```
+    pub fn to_symplectic_linear_code(&self) -> LinearCode {
+        let length = self.len();
+        let x_checks = self
+            .stabilizers
+            .x
+            .rows()
+            .map(|row| row.non_trivial_positions().collect());
+        let z_checks = self.stabilizers.z.rows().map(|row| {
+            row.non_trivial_positions()
+                .map(|bit| bit + length)
+                .collect()
+        });
+        LinearCode::from_parity_check_matrix(SparseBinMat::new(
+            2 * length,
+            x_checks.chain(z_checks).collect(),
+        ))
+    }
+
+    /// Builds a CSS code from the binary symplectic representation
+    /// of its stabilizers.
+    ///
+    /// This is the inverse of [`to_symplectic_linear_code`](Self::to_symplectic_linear_code).
+    /// Checks acting only on the first half of the bits are X stabilizers
+    /// and checks acting only on the second half are Z stabilizers.
+    /// Empty checks are dropped.
+    ///
+    /// Returns an error if the length is odd, if a check acts on both halves
+    /// or if the X and Z stabilizers don't commute.
+    pub fn from_symplectic_linear_code(code: &LinearCode) -> Result<Self, CssError> {
+        if code.len() % 2 != 0 {
+            return Err(CssError::OddSymplecticLength(code.len()));
+        }
+        let length = code.len() / 2;
+        let mut x_checks = Vec::new();
+        let mut z_checks = Vec::new();
+        for (index, check) in code.parity_check_matrix().rows().enumerate() {
+            let positions = check.as_slice();
+            match (positions.first(), positions.last()) {
+                (Some(_), Some(last)) if *last < length => x_checks.push(positions.to_vec()),
+                (Some(first), Some(_)) if *first >= length => {
+                    z_checks.push(positions.iter().map(|bit| bit - length).collect())
+                }
+                (Some(_), Some(_)) => return Err(CssError::MixedSymplecticCheck(index)),
+                _ => (),
+            }
+        }
+        Self::try_new(
+            &LinearCode::from_parity_check_matrix(SparseBinMat::new(length, x_checks)),
+            &LinearCode::from_parity_check_matrix(SparseBinMat::new(length, z_checks)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn toric_code_round_trip() {
+        let code = CssCode::toric_code(3);
+        let symplectic = code.to_symplectic_linear_code();
+        assert_eq!(
+            symplectic.parity_check_matrix().number_of_ones(),
+            code.stabilizers.x.number_of_ones() + code.stabilizers.z.number_of_ones()
+        );
+        let recovered = CssCode::from_symplectic_linear_code(&symplectic).unwrap();
+        assert_eq!(recovered.stabilizers, code.stabilizers);
+        assert_eq!(recovered.num_logical_qubits(), 2);
+    }
+
+    #[test]
+    fn invalid_symplectic_codes_are_rejected() {
+        let odd = LinearCode::repetition_code(3);
+        assert_eq!(
+            CssCode::from_symplectic_linear_code(&odd),
+            Err(CssError::OddSymplecticLength(3))
+        );
+        let mixed = LinearCode::repetition_code(4);
+        assert_eq!(
+            CssCode::from_symplectic_linear_code(&mixed),
+            Err(CssError::MixedSymplecticCheck(1))
+        );
+        let anticommuting =
+            LinearCode::from_parity_check_matrix(SparseBinMat::new(4, vec![vec![0], vec![2]]));
+        assert_eq!(
+            CssCode::from_symplectic_linear_code(&anticommuting),
+            Err(CssError::NonOrthogonalCodes)
+        );
+    }
+}
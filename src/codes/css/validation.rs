@@ -0,0 +1,297 @@
+use super::CssCode;
+use pauli::Pauli;
+use sparse_bin_mat::SparseBinMat;
+use std::fmt;
+
+impl CssCode {
+    /// Checks the internal consistency of the code
+    /// and returns every inconsistency found.
+    ///
+    /// That is, all matrices have the same length, the X and Z stabilizers commute,
+    /// the logicals commute with the stabilizers of the other type,
+    /// the X and Z logicals anticommute exactly pairwise
+    /// and there is a pair of logicals for each [logical qubit](Self::num_logical_qubits).
+    /// If the lengths are different, the other checks are skipped.
+    /// If the ranks of the stabilizers exceed the length,
+    /// the number of logicals is not checked.
+    ///
+    /// This is useful for codes built by hand or loaded from a file
+    /// since the stabilizers and logicals are public.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::codes::CssInconsistency;
+    /// use pauli::Pauli;
+    /// use sparse_bin_mat::SparseBinMat;
+    ///
+    /// let mut code = CssCode::steane_code();
+    /// assert_eq!(code.validate(), Ok(()));
+    ///
+    /// code.logicals.x = SparseBinMat::new(7, vec![vec![0, 1]]);
+    /// let inconsistencies = code.validate().unwrap_err();
+    /// assert!(inconsistencies.contains(&CssInconsistency::NonCommutingLogical(Pauli::X, 0, 2)));
+    /// assert!(inconsistencies.contains(&CssInconsistency::UnpairedLogicals(0, 0)));
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<CssInconsistency>> {
+        let length = self.len();
+        let other_lengths = [
+            self.stabilizers.z.number_of_columns(),
+            self.logicals.x.number_of_columns(),
+            self.logicals.z.number_of_columns(),
+        ];
+        if let Some(other) = other_lengths.into_iter().find(|other| *other != length) {
+            return Err(vec![CssInconsistency::DifferentLengths(length, other)]);
+        }
+        let mut inconsistencies = Vec::new();
+        inconsistencies.extend(
+            anticommuting_pairs(&self.stabilizers.x, &self.stabilizers.z)
+                .map(|(x, z)| CssInconsistency::NonCommutingStabilizers(x, z)),
+        );
+        inconsistencies.extend(
+            anticommuting_pairs(&self.logicals.x, &self.stabilizers.z).map(
+                |(logical, stabilizer)| {
+                    CssInconsistency::NonCommutingLogical(Pauli::X, logical, stabilizer)
+                },
+            ),
+        );
+        inconsistencies.extend(
+            anticommuting_pairs(&self.logicals.z, &self.stabilizers.x).map(
+                |(logical, stabilizer)| {
+                    CssInconsistency::NonCommutingLogical(Pauli::Z, logical, stabilizer)
+                },
+            ),
+        );
+        let pairs = anticommuting_pairs(&self.logicals.x, &self.logicals.z).collect::<Vec<_>>();
+        let num_pairs = self.num_x_logicals().min(self.num_z_logicals());
+        inconsistencies.extend(
+            pairs
+                .iter()
+                .copied()
+                .filter(|(x, z)| x != z)
+                .chain(
+                    (0..num_pairs)
+                        .map(|pair| (pair, pair))
+                        .filter(|pair| !pairs.contains(pair)),
+                )
+                .map(|(x, z)| CssInconsistency::UnpairedLogicals(x, z)),
+        );
+        let (x_rank, z_rank) = self.stabilizer_ranks();
+        match length.checked_sub(x_rank + z_rank) {
+            None => inconsistencies.push(CssInconsistency::TooManyStabilizers(x_rank, z_rank)),
+            Some(num_logical_qubits)
+                if self.logicals.x.number_of_rows() != num_logical_qubits
+                    || self.logicals.z.number_of_rows() != num_logical_qubits =>
+            {
+                inconsistencies.push(CssInconsistency::WrongNumberOfLogicals(
+                    num_logical_qubits,
+                    self.logicals.x.number_of_rows(),
+                    self.logicals.z.number_of_rows(),
+                ))
+            }
+            Some(_) => (),
+        }
+        if inconsistencies.is_empty() {
+            Ok(())
+        } else {
+            Err(inconsistencies)
+        }
+    }
+}
+
+// The pairs of rows of the first and second matrices with odd overlap.
+fn anticommuting_pairs(
+    first: &SparseBinMat,
+    second: &SparseBinMat,
+) -> impl Iterator<Item = (usize, usize)> {
+    let pairs = if first.is_empty() || second.is_empty() {
+        Vec::new()
+    } else {
+        (first * &second.transposed())
+            .non_trivial_elements()
+            .collect()
+    };
+    pairs.into_iter()
+}
+
+// The fields of a serialized code before they are validated.
+#[cfg(feature = "checked-serde")]
+#[derive(serde::Deserialize)]
+pub(super) struct UncheckedCssCode {
+    stabilizers: crate::css::Css<SparseBinMat>,
+    logicals: crate::css::Css<SparseBinMat>,
+    #[serde(default)]
+    coordinates: Option<super::Layout>,
+}
+
+#[cfg(feature = "checked-serde")]
+impl TryFrom<UncheckedCssCode> for CssCode {
+    type Error = String;
+
+    fn try_from(code: UncheckedCssCode) -> Result<Self, Self::Error> {
+        let code = CssCode {
+            stabilizers: code.stabilizers,
+            logicals: code.logicals,
+            coordinates: code.coordinates,
+        };
+        if let Err(inconsistencies) = code.validate() {
+            let inconsistencies = inconsistencies
+                .iter()
+                .map(|inconsistency| inconsistency.to_string())
+                .collect::<Vec<_>>();
+            return Err(format!("inconsistent code: {}", inconsistencies.join(", ")));
+        }
+        if code.coordinates().is_some_and(|layout| !layout.fits(&code)) {
+            return Err("layout doesn't fit the code".to_string());
+        }
+        Ok(code)
+    }
+}
+
+/// An inconsistency between the matrices of a [`CssCode`]
+/// found by [`validate`](CssCode::validate).
+///
+/// Indices are the rows of the stabilizer and logical matrices.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum CssInconsistency {
+    /// The X stabilizers and another matrix have different lengths.
+    DifferentLengths(usize, usize),
+    /// An X stabilizer and a Z stabilizer anticommute.
+    NonCommutingStabilizers(usize, usize),
+    /// A logical of the given type anticommutes with a stabilizer of the other type.
+    NonCommutingLogical(Pauli, usize, usize),
+    /// An X and a Z logical anticommute while they are not a pair
+    /// or a pair of logicals commute.
+    UnpairedLogicals(usize, usize),
+    /// The number of X and Z logicals are not the number of logical qubits.
+    WrongNumberOfLogicals(usize, usize, usize),
+    /// The ranks of the X and Z stabilizers add up to more than the length,
+    /// such that the number of logical qubits is undefined.
+    TooManyStabilizers(usize, usize),
+}
+
+impl fmt::Display for CssInconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DifferentLengths(x_length, other_length) => write!(
+                f,
+                "x stabilizers and other matrix of different lengths: {} & {}",
+                x_length, other_length
+            ),
+            Self::NonCommutingStabilizers(x, z) => {
+                write!(f, "x stabilizer {} and z stabilizer {} don't commute", x, z)
+            }
+            Self::NonCommutingLogical(pauli, logical, stabilizer) => write!(
+                f,
+                "{:?} logical {} doesn't commute with stabilizer {} of the other type",
+                pauli, logical, stabilizer
+            ),
+            Self::UnpairedLogicals(x, z) if x == z => {
+                write!(f, "x and z logicals {} commute", x)
+            }
+            Self::UnpairedLogicals(x, z) => {
+                write!(f, "x logical {} and z logical {} anticommute", x, z)
+            }
+            Self::WrongNumberOfLogicals(expected, x, z) => write!(
+                f,
+                "{} x and {} z logicals for {} logical qubits",
+                x, z, expected
+            ),
+            Self::TooManyStabilizers(x_rank, z_rank) => write!(
+                f,
+                "x and z stabilizers of ranks {} & {} exceed the length",
+                x_rank, z_rank
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CssInconsistency {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn standard_codes_are_consistent() {
+        let codes = [
+            CssCode::steane_code(),
+            CssCode::shor_code(),
+            CssCode::toric_code(3),
+            CssCode::tesseract_code(),
+        ];
+        for code in codes {
+            assert_eq!(code.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn missing_and_swapped_logicals_are_reported() {
+        let mut code = CssCode::toric_code(3);
+        let mut rows = code
+            .logicals
+            .z
+            .rows()
+            .map(|row| row.to_vec().to_positions_vec())
+            .collect::<Vec<_>>();
+        rows.reverse();
+        code.logicals.z = SparseBinMat::new(18, rows);
+        assert_eq!(
+            code.validate(),
+            Err(vec![
+                CssInconsistency::UnpairedLogicals(0, 1),
+                CssInconsistency::UnpairedLogicals(1, 0),
+                CssInconsistency::UnpairedLogicals(0, 0),
+                CssInconsistency::UnpairedLogicals(1, 1),
+            ])
+        );
+        code.logicals.z = code.logicals.z.keep_only_rows(&[1]).unwrap();
+        assert_eq!(
+            code.validate(),
+            Err(vec![CssInconsistency::WrongNumberOfLogicals(2, 2, 1)])
+        );
+    }
+
+    #[test]
+    fn non_commuting_stabilizers_are_reported() {
+        let mut code = CssCode::steane_code();
+        code.stabilizers.z = SparseBinMat::new(7, vec![vec![6]]);
+        let inconsistencies = code.validate().unwrap_err();
+        assert!(inconsistencies.iter().any(|inconsistency| matches!(
+            inconsistency,
+            CssInconsistency::NonCommutingStabilizers(_, 0)
+        )));
+        code.logicals.x = SparseBinMat::new(5, vec![vec![0]]);
+        assert_eq!(
+            code.validate(),
+            Err(vec![CssInconsistency::DifferentLengths(7, 5)])
+        );
+    }
+
+    #[test]
+    fn full_rank_stabilizers_are_reported() {
+        let mut code = CssCode::steane_code();
+        code.stabilizers.x = SparseBinMat::identity(7);
+        code.stabilizers.z = SparseBinMat::identity(7);
+        let inconsistencies = code.validate().unwrap_err();
+        assert!(inconsistencies.contains(&CssInconsistency::TooManyStabilizers(7, 7)));
+        assert_eq!(code.num_logical_qubits(), 0);
+        assert_eq!(code.encoding_rate(), 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "checked-serde")]
+    fn deserialization_rejects_inconsistent_codes() {
+        let code = CssCode::toric_code(3);
+        let mut json = serde_json::to_value(&code).unwrap();
+        assert_eq!(
+            serde_json::from_value::<CssCode>(json.clone()).unwrap(),
+            code
+        );
+
+        json["logicals"]["z"]["rows"][0] = serde_json::json!([0]);
+        let error = serde_json::from_value::<CssCode>(json).unwrap_err();
+        assert!(error.to_string().starts_with("inconsistent code"));
+    }
+}
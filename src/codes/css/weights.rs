@@ -0,0 +1,266 @@
+use crate::codes::DenseCodeword;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinMat;
+use std::fmt;
+
+/// The weight distributions of the logical cosets of one sector of a CSS code.
+///
+/// The operators of a sector commuting with the stabilizers of the other sector
+/// are split into cosets of the stabilizer group according to their logical class.
+/// The class of an operator is the bit mask of the logical operators
+/// of the other sector that anticommute with it.
+/// Class 0 is the stabilizer group itself.
+///
+/// Distributions are either exact counts obtained by enumerating
+/// all the operators or estimates obtained from uniform samples.
+/// In both cases, counts are stored together with the number of samples.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct CosetWeightDistribution {
+    counts: Vec<Vec<u64>>,
+    num_samples: u64,
+    dimension: usize,
+}
+
+impl CosetWeightDistribution {
+    // Enumerates all the binary combinations of the kernel basis.
+    pub(super) fn exact(
+        kernel: &SparseBinMat,
+        logicals: &SparseBinMat,
+    ) -> Result<Self, WeightDistributionError> {
+        if kernel.number_of_rows() >= u64::BITS as usize {
+            return Err(WeightDistributionError::TooManyOperators(
+                kernel.number_of_rows(),
+            ));
+        }
+        let mut distribution = Self::empty(kernel, logicals)?;
+        let generators = Generators::new(kernel, logicals);
+        let mut current = DenseCodeword::zeros(kernel.number_of_columns());
+        let mut class = 0;
+        distribution.counts[class][0] += 1;
+        // Gray code order such that each step adds a single generator.
+        for step in 1_u64..(1 << generators.len()) {
+            let generator = step.trailing_zeros() as usize;
+            current += &generators.rows[generator];
+            class ^= generators.classes[generator];
+            distribution.counts[class][current.weight()] += 1;
+        }
+        distribution.num_samples = 1 << generators.len();
+        Ok(distribution)
+    }
+
+    // Samples uniformly random binary combinations of the kernel basis.
+    pub(super) fn sampled<R: Rng>(
+        kernel: &SparseBinMat,
+        logicals: &SparseBinMat,
+        num_samples: u64,
+        rng: &mut R,
+    ) -> Result<Self, WeightDistributionError> {
+        let mut distribution = Self::empty(kernel, logicals)?;
+        let generators = Generators::new(kernel, logicals);
+        for _ in 0..num_samples {
+            let mut current = DenseCodeword::zeros(kernel.number_of_columns());
+            let mut class = 0;
+            for generator in 0..generators.len() {
+                if rng.gen() {
+                    current += &generators.rows[generator];
+                    class ^= generators.classes[generator];
+                }
+            }
+            distribution.counts[class][current.weight()] += 1;
+        }
+        distribution.num_samples = num_samples;
+        Ok(distribution)
+    }
+
+    // The classes are bit masks of the logicals stored in a usize.
+    fn empty(
+        kernel: &SparseBinMat,
+        logicals: &SparseBinMat,
+    ) -> Result<Self, WeightDistributionError> {
+        if logicals.number_of_rows() >= usize::BITS as usize {
+            return Err(WeightDistributionError::TooManyLogicals(
+                logicals.number_of_rows(),
+            ));
+        }
+        let num_classes = 1 << logicals.number_of_rows();
+        Ok(Self {
+            counts: vec![vec![0; kernel.number_of_columns() + 1]; num_classes],
+            num_samples: 0,
+            dimension: kernel.number_of_rows(),
+        })
+    }
+
+    /// Returns the number of logical classes.
+    pub fn num_classes(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the number of enumerated or sampled operators.
+    pub fn num_samples(&self) -> u64 {
+        self.num_samples
+    }
+
+    /// Returns the number of sampled operators of the given
+    /// logical class for each weight.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the class is out of bound.
+    pub fn counts(&self, class: usize) -> &[u64] {
+        &self.counts[class]
+    }
+
+    /// Returns the estimated number of operators of the given
+    /// logical class and weight.
+    ///
+    /// This is exact for enumerated distributions.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the class is out of bound.
+    pub fn estimated_count(&self, class: usize, weight: usize) -> f64 {
+        let count = self.counts[class].get(weight).cloned().unwrap_or(0);
+        count as f64 / self.num_samples as f64 * 2.0_f64.powi(self.dimension as i32)
+    }
+
+    /// Returns the number of sampled operators in the stabilizer group for each weight.
+    pub fn stabilizer_counts(&self) -> &[u64] {
+        self.counts(0)
+    }
+
+    /// Returns the number of sampled non trivial logical operators for each weight.
+    pub fn logical_counts(&self) -> Vec<u64> {
+        let mut counts = vec![0; self.counts[0].len()];
+        for class_counts in self.counts.iter().skip(1) {
+            for (count, class_count) in counts.iter_mut().zip(class_counts) {
+                *count += class_count;
+            }
+        }
+        counts
+    }
+
+    /// Returns the smallest weight of a sampled non trivial logical operator
+    /// or None if no such operator was sampled.
+    ///
+    /// This is the distance of the sector for enumerated distributions.
+    pub fn minimum_logical_weight(&self) -> Option<usize> {
+        self.logical_counts().iter().position(|count| *count > 0)
+    }
+}
+
+/// An error when the weight distributions of a code can't be computed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WeightDistributionError {
+    /// The 2 to the given power operators to enumerate can't be counted in 64 bits.
+    TooManyOperators(usize),
+    /// The 2 to the given power logical classes can't be indexed.
+    TooManyLogicals(usize),
+}
+
+impl fmt::Display for WeightDistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyOperators(dimension) => {
+                write!(f, "can't enumerate 2^{} operators", dimension)
+            }
+            Self::TooManyLogicals(num_logicals) => {
+                write!(
+                    f,
+                    "can't split operators between 2^{} classes",
+                    num_logicals
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for WeightDistributionError {}
+
+struct Generators {
+    rows: Vec<DenseCodeword>,
+    classes: Vec<usize>,
+}
+
+impl Generators {
+    fn new(kernel: &SparseBinMat, logicals: &SparseBinMat) -> Self {
+        let rows = kernel.rows().map(|row| DenseCodeword::from(&row)).collect();
+        let classes = kernel
+            .rows()
+            .map(|row| {
+                (logicals * &row)
+                    .non_trivial_positions()
+                    .fold(0, |class, logical| class | 1 << logical)
+            })
+            .collect();
+        Self { rows, classes }
+    }
+
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::{CssCode, LinearCode};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn steane_code_distributions() {
+        let code = CssCode::steane_code();
+        let distributions = code.coset_weight_distributions().unwrap();
+        for distribution in [distributions.x, distributions.z] {
+            assert_eq!(distribution.num_samples(), 16);
+            assert_eq!(distribution.stabilizer_counts(), &[1, 0, 0, 0, 7, 0, 0, 0]);
+            assert_eq!(distribution.logical_counts(), vec![0, 0, 0, 7, 0, 0, 0, 1]);
+            assert_eq!(distribution.minimum_logical_weight(), Some(3));
+        }
+    }
+
+    #[test]
+    fn sampled_distributions_only_contain_valid_weights() {
+        let code = CssCode::steane_code();
+        let distributions = code
+            .sample_coset_weight_distributions(1000, &mut StdRng::seed_from_u64(123))
+            .unwrap();
+        let distribution = distributions.x;
+        assert_eq!(distribution.num_samples(), 1000);
+        assert_eq!(distribution.stabilizer_counts()[1], 0);
+        assert_eq!(distribution.logical_counts()[4], 0);
+        let estimate = distribution.estimated_count(1, 3);
+        assert!((estimate - 7.0).abs() < 1.5);
+    }
+
+    #[test]
+    fn repetition_hypergraph_product_has_distance_3() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+        let distributions = code.coset_weight_distributions().unwrap();
+        assert_eq!(distributions.x.minimum_logical_weight(), Some(3));
+        assert_eq!(distributions.z.minimum_logical_weight(), Some(3));
+    }
+
+    #[test]
+    fn too_many_operators_are_not_enumerated() {
+        let kernel = SparseBinMat::identity(64);
+        let logicals = SparseBinMat::new(64, Vec::new());
+        assert_eq!(
+            CosetWeightDistribution::exact(&kernel, &logicals),
+            Err(WeightDistributionError::TooManyOperators(64))
+        );
+    }
+
+    #[test]
+    fn too_many_logicals_are_not_split_into_classes() {
+        let kernel = SparseBinMat::identity(70);
+        let distribution =
+            CosetWeightDistribution::sampled(&kernel, &kernel, 10, &mut StdRng::seed_from_u64(123));
+        assert_eq!(
+            distribution,
+            Err(WeightDistributionError::TooManyLogicals(70))
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use super::CssCode;
+use crate::codes::{BinaryPolynomial, LinearCode};
+use itertools::Itertools;
+use sparse_bin_mat::SparseBinMat;
+
+impl CssCode {
+    /// Returns the [[4,2,2]] code
+    /// whose stabilizers are XXXX and ZZZZ.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::four_two_two_code();
+    /// assert_eq!((code.len(), code.num_x_logicals()), (4, 2));
+    /// assert!(code.verify_distance_at_least(2));
+    /// ```
+    pub fn four_two_two_code() -> Self {
+        let checks = checks(4, vec![vec![0, 1, 2, 3]]);
+        Self::new(&checks, &checks)
+    }
+
+    /// Returns the [[6,2,2]] code
+    /// whose X and Z stabilizers are both supported on
+    /// the qubits 0 to 3 and the qubits 2 to 5.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::six_two_two_code();
+    /// assert_eq!((code.len(), code.num_x_logicals()), (6, 2));
+    /// assert!(code.verify_distance_at_least(2));
+    /// ```
+    pub fn six_two_two_code() -> Self {
+        let checks = checks(6, vec![vec![0, 1, 2, 3], vec![2, 3, 4, 5]]);
+        Self::new(&checks, &checks)
+    }
+
+    /// Returns the [[8,3,2]] color code with a qubit on each vertex of a cube.
+    ///
+    /// The X stabilizer acts on all qubits
+    /// and the Z stabilizers act on the faces of the cube.
+    /// Qubit `i` is the vertex whose coordinates are the bits of `i`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::eight_three_two_code();
+    /// assert_eq!((code.len(), code.num_x_logicals()), (8, 3));
+    /// assert!(code.verify_distance_at_least(2));
+    /// ```
+    pub fn eight_three_two_code() -> Self {
+        Self::new(
+            &checks(8, vec![(0..8).collect()]),
+            &checks(8, hypercube_facets(3)),
+        )
+    }
+
+    /// Returns the [[15,1,3]] quantum Reed-Muller code.
+    ///
+    /// Qubit `i` is the non zero binary vector of 4 bits representing `i + 1`.
+    /// The X stabilizers are the 4 rows of the punctured first order Reed-Muller code,
+    /// that is qubits with a given bit set to 1,
+    /// and the Z stabilizers are these rows together with their 6 pairwise products.
+    /// This code has a transversal T gate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::quantum_reed_muller_code();
+    /// assert_eq!((code.len(), code.num_x_logicals()), (15, 1));
+    /// assert!(code.verify_distance_at_least(3));
+    /// assert!(!code.verify_distance_at_least(4));
+    /// ```
+    pub fn quantum_reed_muller_code() -> Self {
+        let has_bits = |bits: &[usize]| -> Vec<usize> {
+            (0..15)
+                .filter(|qubit| bits.iter().all(|bit| (qubit + 1) >> bit & 1 == 1))
+                .collect()
+        };
+        let x_checks = (0..4).map(|bit| has_bits(&[bit])).collect::<Vec<_>>();
+        let z_checks = x_checks
+            .iter()
+            .cloned()
+            .chain((0..4).tuple_combinations().map(|(a, b)| has_bits(&[a, b])))
+            .collect();
+        Self::new(&checks(15, x_checks), &checks(15, z_checks))
+    }
+
+    /// Returns the generalized bicycle code of the two polynomials
+    /// modulo `x^length + 1`.
+    ///
+    /// With `A` and `B` the [circulants](BinaryPolynomial::circulant) of the polynomials,
+    /// the X stabilizers are the rows of `[A | B]` and the Z stabilizers
+    /// are the rows of `[B^T | A^T]`.
+    /// They commute since circulant matrices commute.
+    /// The number of logical qubits is twice the degree of the greatest common divisor
+    /// of both polynomials and `x^length + 1`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::codes::{BinaryPolynomial, CssCode};
+    ///
+    /// // The [[254, 28]] code of Panteleev and Kalachev.
+    /// let a = BinaryPolynomial::new(vec![0, 15, 20, 28, 66]);
+    /// let b = BinaryPolynomial::new(vec![0, 58, 59, 100, 121]);
+    /// let code = CssCode::generalized_bicycle_code(127, &a, &b);
+    ///
+    /// assert_eq!(code.len(), 254);
+    /// assert_eq!(code.num_x_logicals(), 28);
+    /// ```
+    pub fn generalized_bicycle_code(
+        length: usize,
+        first: &BinaryPolynomial,
+        second: &BinaryPolynomial,
+    ) -> Self {
+        let (a, b) = (first.circulant(length), second.circulant(length));
+        let x_checks = a.horizontal_concat_with(&b);
+        let z_checks = b.transposed().horizontal_concat_with(&a.transposed());
+        Self::new(
+            &LinearCode::from_parity_check_matrix(x_checks),
+            &LinearCode::from_parity_check_matrix(z_checks),
+        )
+    }
+
+    /// Returns the [[16,6,4]] tesseract code with a qubit on each vertex of a 4-cube.
+    ///
+    /// Both the X and Z stabilizers act on the 8 cubic cells of the 4-cube.
+    /// Qubit `i` is the vertex whose coordinates are the bits of `i`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let code = CssCode::tesseract_code();
+    /// assert_eq!((code.len(), code.num_x_logicals()), (16, 6));
+    /// assert!(code.verify_distance_at_least(4));
+    /// ```
+    pub fn tesseract_code() -> Self {
+        let checks = checks(16, hypercube_facets(4));
+        Self::new(&checks, &checks)
+    }
+}
+
+fn checks(length: usize, checks: Vec<Vec<usize>>) -> LinearCode {
+    LinearCode::from_parity_check_matrix(SparseBinMat::new(length, checks))
+}
+
+// The facets of the hypercube of the given dimension
+// where a vertex is labeled by the integer whose bits are its coordinates.
+fn hypercube_facets(dimension: usize) -> Vec<Vec<usize>> {
+    (0..dimension)
+        .cartesian_product([0, 1])
+        .map(|(bit, value)| {
+            (0..1 << dimension)
+                .filter(|vertex| vertex >> bit & 1 == value)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn zoo_codes_have_expected_parameters() {
+        let codes = [
+            (CssCode::four_two_two_code(), [4, 2, 2]),
+            (CssCode::six_two_two_code(), [6, 2, 2]),
+            (CssCode::eight_three_two_code(), [8, 3, 2]),
+            (CssCode::quantum_reed_muller_code(), [15, 1, 3]),
+            (CssCode::tesseract_code(), [16, 6, 4]),
+        ];
+        for (code, [length, dimension, distance]) in codes {
+            assert_eq!(code.len(), length);
+            assert_eq!(code.num_x_logicals(), dimension);
+            assert_eq!(code.num_z_logicals(), dimension);
+            assert!(code.verify_distance_at_least(distance));
+            assert!(!code.verify_distance_at_least(distance + 1));
+        }
+    }
+}
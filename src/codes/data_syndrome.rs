@@ -0,0 +1,290 @@
+use super::LinearCode;
+use crate::decoders::BpDecoder;
+use crate::noise::{NoiseModel, Probability};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+
+/// A classical code whose syndrome is measured with errors.
+///
+/// The measurements are sums of checks of a [`LinearCode`],
+/// usually each check once followed by some redundant combinations of checks.
+/// Both the data bits and the measured syndrome bits can be flipped,
+/// such that the observed syndrome of a data error `e` and a measurement error `f`
+/// is `M e + f` where `M` is the matrix of the measured checks.
+///
+/// The joint error `(e, f)` is an error of the code whose parity check matrix
+/// is the augmented matrix `[M | I]`.
+/// Thus, the data and measurement errors are decoded together
+/// by any classical decoder over the augmented matrix,
+/// for example [belief propagation](DataSyndromeCode::bp_decoder).
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::{DataSyndromeCode, LinearCode};
+/// use ldpc::decoders::SyndromeDecoder;
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = DataSyndromeCode::new(&LinearCode::hamming_code());
+/// assert_eq!(code.augmented_check_matrix().number_of_columns(), 10);
+///
+/// // A single flipped measurement looks like a measurement error,
+/// // not like a data error that would flip at least two checks.
+/// let error = code.join(&SparseBinVec::zeros(7), &SparseBinVec::new(3, vec![1]));
+/// let decoder = code.bp_decoder(Probability::new(0.05), Probability::new(0.05), 10);
+/// let correction = decoder.correction_for(code.syndrome_of(&error).as_view());
+/// assert!(code.has_recovered_data(&error, &correction));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DataSyndromeCode {
+    measured_checks: SparseBinMat,
+    augmented_check_matrix: SparseBinMat,
+}
+
+impl DataSyndromeCode {
+    /// Creates the data-syndrome code measuring each check of the code once.
+    pub fn new(code: &LinearCode) -> Self {
+        Self::from_measured_checks(code.parity_check_matrix().clone())
+    }
+
+    /// Creates the data-syndrome code measuring each check of the code once
+    /// followed by the given combinations of checks.
+    ///
+    /// Each row of the combinations is the set of checks whose sum is measured.
+    /// The redundant measurements let the decoder distinguish
+    /// measurement errors from data errors.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the combinations don't have one column per check of the code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::{DataSyndromeCode, LinearCode};
+    /// use sparse_bin_mat::SparseBinMat;
+    ///
+    /// // Also measuring the sum of all the checks of the Hamming code.
+    /// let combinations = SparseBinMat::new(3, vec![vec![0, 1, 2]]);
+    /// let code = DataSyndromeCode::with_redundant_checks(&LinearCode::hamming_code(), &combinations);
+    ///
+    /// assert_eq!(code.num_measurements(), 4);
+    /// assert_eq!(
+    ///     code.measured_checks().row(3).unwrap().non_trivial_positions().collect::<Vec<_>>(),
+    ///     vec![0, 1, 3, 6]
+    /// );
+    /// ```
+    pub fn with_redundant_checks(code: &LinearCode, combinations: &SparseBinMat) -> Self {
+        if combinations.number_of_columns() != code.num_checks() {
+            panic!(
+                "combinations of {} checks for {} checks",
+                combinations.number_of_columns(),
+                code.num_checks()
+            );
+        }
+        let redundant_checks = combinations * code.parity_check_matrix();
+        Self::from_measured_checks(
+            code.parity_check_matrix()
+                .vertical_concat_with(&redundant_checks),
+        )
+    }
+
+    fn from_measured_checks(measured_checks: SparseBinMat) -> Self {
+        let identity = SparseBinMat::identity(measured_checks.number_of_rows());
+        Self {
+            augmented_check_matrix: measured_checks.horizontal_concat_with(&identity),
+            measured_checks,
+        }
+    }
+
+    /// Returns the number of data bits.
+    pub fn len(&self) -> usize {
+        self.measured_checks.number_of_columns()
+    }
+
+    /// Checks if there is no data bit.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of measured syndrome bits.
+    pub fn num_measurements(&self) -> usize {
+        self.measured_checks.number_of_rows()
+    }
+
+    /// Returns the checks measured on the data bits, one per row.
+    pub fn measured_checks(&self) -> &SparseBinMat {
+        &self.measured_checks
+    }
+
+    /// Returns the measured checks followed by the identity on the measurement bits.
+    ///
+    /// The columns are the data bits followed by the measurement bits.
+    pub fn augmented_check_matrix(&self) -> &SparseBinMat {
+        &self.augmented_check_matrix
+    }
+
+    /// Returns the joint error of the given data and measurement errors.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the lengths of the errors are not
+    /// the number of data bits and the number of measurements.
+    pub fn join(
+        &self,
+        data_error: &SparseBinVec,
+        measurement_error: &SparseBinVec,
+    ) -> SparseBinVec {
+        if data_error.len() != self.len() || measurement_error.len() != self.num_measurements() {
+            panic!(
+                "errors of lengths ({}, {}) for {} data bits and {} measurements",
+                data_error.len(),
+                measurement_error.len(),
+                self.len(),
+                self.num_measurements()
+            );
+        }
+        data_error.concat(measurement_error)
+    }
+
+    /// Returns the data and measurement parts of a joint error.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length of the error is not the length of the augmented matrix.
+    pub fn split(&self, error: &SparseBinVec) -> (SparseBinVec, SparseBinVec) {
+        self.check_length(error);
+        let data = error
+            .non_trivial_positions()
+            .filter(|position| *position < self.len())
+            .collect();
+        let measurements = error
+            .non_trivial_positions()
+            .filter(|position| *position >= self.len())
+            .map(|position| position - self.len())
+            .collect();
+        (
+            SparseBinVec::new(self.len(), data),
+            SparseBinVec::new(self.num_measurements(), measurements),
+        )
+    }
+
+    /// Returns the observed syndrome of a joint error.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length of the error is not the length of the augmented matrix.
+    pub fn syndrome_of(&self, error: &SparseBinVec) -> SparseBinVec {
+        self.check_length(error);
+        &self.augmented_check_matrix * error
+    }
+
+    /// Samples a joint error with the data and measurement errors
+    /// from the given noise models.
+    pub fn random_error<D, M, R>(
+        &self,
+        data_noise: &D,
+        measurement_noise: &M,
+        rng: &mut R,
+    ) -> SparseBinVec
+    where
+        D: NoiseModel<Error = SparseBinVec>,
+        M: NoiseModel<Error = SparseBinVec>,
+        R: Rng,
+    {
+        let data_error = data_noise.sample_error_of_length(self.len(), rng);
+        let measurement_error =
+            measurement_noise.sample_error_of_length(self.num_measurements(), rng);
+        data_error.concat(&measurement_error)
+    }
+
+    /// Checks if the data part of the correction is the data part of the error.
+    ///
+    /// The measurement errors don't need to be corrected
+    /// since the syndrome is measured again in the next round.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length of the error or the correction
+    /// is not the length of the augmented matrix.
+    pub fn has_recovered_data(&self, error: &SparseBinVec, correction: &SparseBinVec) -> bool {
+        self.split(error).0 == self.split(correction).0
+    }
+
+    /// Returns a belief propagation decoder over the augmented matrix
+    /// with the given probabilities of data and measurement errors.
+    pub fn bp_decoder(
+        &self,
+        data_probability: Probability,
+        measurement_probability: Probability,
+        num_iterations: usize,
+    ) -> BpDecoder {
+        let probabilities = std::iter::repeat_n(data_probability, self.len())
+            .chain(std::iter::repeat_n(
+                measurement_probability,
+                self.num_measurements(),
+            ))
+            .collect::<Vec<_>>();
+        BpDecoder::new(
+            &self.augmented_check_matrix,
+            data_probability,
+            num_iterations,
+        )
+        .with_bit_probabilities(&probabilities)
+    }
+
+    fn check_length(&self, error: &SparseBinVec) {
+        let length = self.augmented_check_matrix.number_of_columns();
+        if error.len() != length {
+            panic!("error of length {} for {} bits", error.len(), length);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::SyndromeDecoder;
+    use crate::noise::BinarySymmetricChannel;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn joint_errors_are_split_back() {
+        let code = DataSyndromeCode::new(&LinearCode::repetition_code(4));
+        let data = SparseBinVec::new(4, vec![0, 3]);
+        let measurements = SparseBinVec::new(3, vec![1]);
+        let error = code.join(&data, &measurements);
+        assert_eq!(error, SparseBinVec::new(7, vec![0, 3, 5]));
+        assert_eq!(code.split(&error), (data, measurements));
+        assert_eq!(
+            code.syndrome_of(&error),
+            SparseBinVec::new(3, vec![0, 1, 2])
+        );
+    }
+
+    #[test]
+    fn redundant_checks_catch_more_measurement_errors() {
+        let hamming_code = LinearCode::hamming_code();
+        let combinations = SparseBinMat::new(3, vec![vec![0, 1], vec![1, 2], vec![0, 2]]);
+        let codes = [
+            DataSyndromeCode::new(&hamming_code),
+            DataSyndromeCode::with_redundant_checks(&hamming_code, &combinations),
+        ];
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.03));
+        let num_failures = codes.map(|code| {
+            let decoder = code.bp_decoder(Probability::new(0.03), Probability::new(0.03), 20);
+            let mut rng = StdRng::seed_from_u64(123);
+            (0..2000)
+                .filter(|_| {
+                    let error = code.random_error(&noise, &noise, &mut rng);
+                    let correction = decoder.correction_for(code.syndrome_of(&error).as_view());
+                    !code.has_recovered_data(&error, &correction)
+                })
+                .count()
+        });
+        assert!(num_failures[1] < num_failures[0]);
+    }
+}
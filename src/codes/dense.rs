@@ -0,0 +1,232 @@
+use sparse_bin_mat::{SparseBinVec, SparseBinVecBase};
+use std::ops::{Add, AddAssign, Deref};
+
+/// The machine word storing the bits of a [`DenseCodeword`].
+///
+/// This is `u64` by default and `u128` with the `u128-words` feature.
+#[cfg(not(feature = "u128-words"))]
+pub type Word = u64;
+
+/// The machine word storing the bits of a [`DenseCodeword`].
+///
+/// This is `u64` by default and `u128` with the `u128-words` feature.
+#[cfg(feature = "u128-words")]
+pub type Word = u128;
+
+const WORD_BITS: usize = Word::BITS as usize;
+
+/// A binary vector of arbitrary length packed into machine words.
+///
+/// Bit `i` is bit `i % w` of word `i / w` where `w` is the number of bits of a [`Word`].
+/// Sums and weights are computed a word at a time with XOR and popcount,
+/// which is much faster than with a [`SparseBinVec`] when enumerating
+/// many dense combinations of codewords.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::DenseCodeword;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let first = DenseCodeword::from(&SparseBinVec::new(200, vec![0, 70, 150]));
+/// let second = DenseCodeword::from(&SparseBinVec::new(200, vec![70, 199]));
+///
+/// let sum = &first + &second;
+/// assert_eq!(sum.weight(), 3);
+/// assert_eq!(first.distance_to(&second), 3);
+/// assert_eq!(SparseBinVec::from(&sum), SparseBinVec::new(200, vec![0, 150, 199]));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DenseCodeword {
+    len: usize,
+    words: Vec<Word>,
+}
+
+impl DenseCodeword {
+    /// Creates the zero vector of the given length.
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            len,
+            words: vec![0; len.div_ceil(WORD_BITS)],
+        }
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the vector has no bit.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bits set to 1.
+    pub fn weight(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Checks if all bits are 0.
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    /// Checks if the given bit is 1 or returns None if it is out of bound.
+    pub fn is_one_at(&self, bit: usize) -> Option<bool> {
+        (bit < self.len).then(|| self.words[bit / WORD_BITS] >> (bit % WORD_BITS) & 1 == 1)
+    }
+
+    /// Flips the given bit.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the bit is out of bound.
+    pub fn flip(&mut self, bit: usize) {
+        if bit >= self.len {
+            panic!("bit {} is out of bound for length {}", bit, self.len);
+        }
+        self.words[bit / WORD_BITS] ^= 1 << (bit % WORD_BITS);
+    }
+
+    /// Returns the number of bits where the vectors differ
+    /// without allocating their sum.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vectors have different lengths.
+    pub fn distance_to(&self, other: &Self) -> usize {
+        self.assert_same_length(other);
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .map(|(first, second)| (first ^ second).count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns the parity of the number of positions where both vectors are 1.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vectors have different lengths.
+    pub fn dot_with(&self, other: &Self) -> bool {
+        self.assert_same_length(other);
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .fold(0, |parity, (first, second)| {
+                parity ^ (first & second).count_ones()
+            })
+            & 1
+            == 1
+    }
+
+    /// Returns the positions of the bits set to 1 in increasing order.
+    pub fn non_trivial_positions(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    index * WORD_BITS + bit
+                })
+            })
+        })
+    }
+
+    fn assert_same_length(&self, other: &Self) {
+        if self.len != other.len {
+            panic!(
+                "vectors of lengths {} and {} can't be combined",
+                self.len, other.len
+            );
+        }
+    }
+}
+
+impl<T> From<&SparseBinVecBase<T>> for DenseCodeword
+where
+    T: Deref<Target = [usize]>,
+{
+    fn from(vector: &SparseBinVecBase<T>) -> Self {
+        let mut dense = Self::zeros(vector.len());
+        for bit in vector.non_trivial_positions() {
+            dense.words[bit / WORD_BITS] |= 1 << (bit % WORD_BITS);
+        }
+        dense
+    }
+}
+
+impl From<&DenseCodeword> for SparseBinVec {
+    fn from(vector: &DenseCodeword) -> Self {
+        SparseBinVec::new(vector.len, vector.non_trivial_positions().collect())
+    }
+}
+
+/// # Panic
+///
+/// Panics if the vectors have different lengths.
+impl AddAssign<&DenseCodeword> for DenseCodeword {
+    // Addition modulo 2 is the XOR of the words.
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn add_assign(&mut self, other: &DenseCodeword) {
+        self.assert_same_length(other);
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word ^= other;
+        }
+    }
+}
+
+/// # Panic
+///
+/// Panics if the vectors have different lengths.
+impl Add<&DenseCodeword> for &DenseCodeword {
+    type Output = DenseCodeword;
+
+    fn add(self, other: &DenseCodeword) -> DenseCodeword {
+        let mut sum = self.clone();
+        sum += other;
+        sum
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn conversions_round_trip_across_words() {
+        for len in [0, 1, 63, 64, 65, 127, 128, 129, 300] {
+            let positions = (0..len)
+                .filter(|bit| bit % 7 == 0 || bit % 64 == 63)
+                .collect();
+            let sparse = SparseBinVec::new(len, positions);
+            let dense = DenseCodeword::from(&sparse);
+            assert_eq!(dense.len(), len);
+            assert_eq!(dense.weight(), sparse.weight());
+            assert_eq!(SparseBinVec::from(&dense), sparse);
+        }
+    }
+
+    #[test]
+    fn operations_match_sparse_vectors() {
+        let first = SparseBinVec::new(150, vec![1, 64, 100, 149]);
+        let second = SparseBinVec::new(150, vec![0, 64, 149]);
+        let (dense_first, dense_second) =
+            (DenseCodeword::from(&first), DenseCodeword::from(&second));
+        assert_eq!(
+            SparseBinVec::from(&(&dense_first + &dense_second)),
+            &first + &second
+        );
+        assert_eq!(dense_first.distance_to(&dense_second), 3);
+        assert!(!dense_first.dot_with(&dense_second));
+        let mut flipped = dense_first.clone();
+        flipped.flip(0);
+        assert!(flipped.dot_with(&dense_second));
+        assert_eq!(flipped.is_one_at(0), Some(true));
+        assert_eq!(flipped.is_one_at(150), None);
+    }
+}
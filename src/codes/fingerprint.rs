@@ -0,0 +1,184 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinMat;
+use std::fmt;
+use std::str::FromStr;
+
+/// A stable 128 bits hash of the checks of a code.
+///
+/// The checks are canonicalized before hashing by sorting them
+/// and removing empty and repeated checks,
+/// such that the order of the checks doesn't change the fingerprint.
+/// Fingerprints don't depend on the platform or the compiler version
+/// and can be stored in result files to identify the code of an experiment.
+///
+/// Codes with the same codespace but different checks
+/// have different fingerprints.
+///
+/// A fingerprint is displayed and parsed as 32 hexadecimal digits.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::{Fingerprint, LinearCode};
+/// use sparse_bin_mat::SparseBinMat;
+///
+/// let code = LinearCode::repetition_code(3);
+/// let reordered = LinearCode::from_parity_check_matrix(
+///     SparseBinMat::new(3, vec![vec![1, 2], vec![0, 1], vec![1, 2]]),
+/// );
+/// assert_eq!(code.fingerprint(), reordered.fingerprint());
+///
+/// let fingerprint = code.fingerprint();
+/// assert_eq!(fingerprint.to_string().parse::<Fingerprint>(), Ok(fingerprint));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct Fingerprint(u128);
+
+impl Fingerprint {
+    /// Returns the fingerprint as an integer.
+    pub fn value(&self) -> u128 {
+        self.0
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = FingerprintParseError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        // Unlike from_str_radix, this rejects short strings and signs.
+        if string.len() != 32 || !string.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(FingerprintParseError(string.to_string()));
+        }
+        u128::from_str_radix(string, 16)
+            .map(Fingerprint)
+            .map_err(|_| FingerprintParseError(string.to_string()))
+    }
+}
+
+/// An error when parsing a string that is not a fingerprint.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FingerprintParseError(pub String);
+
+impl fmt::Display for FingerprintParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid fingerprint: {}", self.0)
+    }
+}
+
+impl std::error::Error for FingerprintParseError {}
+
+// The 128 bits FNV-1a hash which, unlike the standard library hashers,
+// is specified and stable across versions.
+pub(crate) struct FingerprintHasher {
+    state: u128,
+}
+
+impl FingerprintHasher {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    // The label separates the fingerprints of different kinds of codes.
+    pub(crate) fn new(label: &str) -> Self {
+        let mut hasher = Self {
+            state: Self::OFFSET_BASIS,
+        };
+        hasher.write_usize(label.len());
+        hasher.write_bytes(label.as_bytes());
+        hasher
+    }
+
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u128;
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_bytes(&(value as u64).to_le_bytes());
+    }
+
+    pub(crate) fn write_positions(&mut self, positions: &[usize]) {
+        self.write_usize(positions.len());
+        for position in positions {
+            self.write_usize(*position);
+        }
+    }
+
+    pub(crate) fn write_checks(&mut self, matrix: &SparseBinMat) {
+        let checks = matrix
+            .rows()
+            .filter(|check| !check.is_zero())
+            .map(|check| check.non_trivial_positions().collect_vec())
+            .sorted()
+            .dedup()
+            .collect_vec();
+        self.write_usize(matrix.number_of_columns());
+        self.write_usize(checks.len());
+        for check in checks {
+            self.write_usize(check.len());
+            for bit in check {
+                self.write_usize(bit);
+            }
+        }
+    }
+
+    pub(crate) fn finish(&self) -> Fingerprint {
+        Fingerprint(self.state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fnv_hash_of_empty_input_is_offset_basis() {
+        let hasher = FingerprintHasher {
+            state: FingerprintHasher::OFFSET_BASIS,
+        };
+        assert_eq!(hasher.finish().value(), FingerprintHasher::OFFSET_BASIS);
+    }
+
+    #[test]
+    fn fnv_hash_of_known_input() {
+        // Reference value of the 128 bits FNV-1a hash of "a".
+        let mut hasher = FingerprintHasher {
+            state: FingerprintHasher::OFFSET_BASIS,
+        };
+        hasher.write_bytes(b"a");
+        assert_eq!(
+            hasher.finish().to_string(),
+            "d228cb696f1a8caf78912b704e4a8964"
+        );
+    }
+
+    #[test]
+    fn only_32_hexadecimal_digits_are_fingerprints() {
+        let digits = "d228cb696f1a8caf78912b704e4a8964";
+        assert!(digits.parse::<Fingerprint>().is_ok());
+        assert!(digits.to_uppercase().parse::<Fingerprint>().is_ok());
+        for string in ["", "f", "+f", &format!("+{}", &digits[1..]), &format!("{}0", digits)] {
+            assert_eq!(
+                string.parse::<Fingerprint>(),
+                Err(FingerprintParseError(string.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn number_of_bits_changes_the_fingerprint() {
+        let mut first = FingerprintHasher::new("test");
+        first.write_checks(&SparseBinMat::new(3, vec![vec![0, 1]]));
+        let mut second = FingerprintHasher::new("test");
+        second.write_checks(&SparseBinMat::new(4, vec![vec![0, 1]]));
+        assert_ne!(first.finish(), second.finish());
+    }
+}
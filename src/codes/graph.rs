@@ -0,0 +1,213 @@
+use super::{CssCode, LinearCode};
+use itertools::Itertools;
+use petgraph::graph::{NodeIndex, UnGraph};
+use sparse_bin_mat::SparseBinMat;
+use std::collections::BTreeSet;
+
+/// A node of the [Tanner graph](LinearCode::tanner_graph) of a classical code.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum TannerNode {
+    /// The bit of the given index.
+    Bit(usize),
+    /// The check of the given index.
+    Check(usize),
+}
+
+/// A node of the [Tanner graph](CssCode::tanner_graph) of a CSS code.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum CssTannerNode {
+    /// The qubit of the given index.
+    Qubit(usize),
+    /// The X stabilizer of the given index.
+    XStabilizer(usize),
+    /// The Z stabilizer of the given index.
+    ZStabilizer(usize),
+}
+
+impl LinearCode {
+    /// Returns the Tanner graph of the code as a petgraph graph.
+    ///
+    /// The nodes are the bits followed by the checks,
+    /// such that the node index of bit `i` is `i`
+    /// and the node index of check `j` is `j` plus the length of the code.
+    /// There is an edge between a bit and a check if the check involves the bit.
+    ///
+    /// The graph is built with one allocation for the nodes and one for the edges.
+    /// It is a copy, thus it doesn't change with the code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::{LinearCode, TannerNode};
+    /// use petgraph::algo::{connected_components, is_cyclic_undirected};
+    /// use petgraph::graph::NodeIndex;
+    ///
+    /// let code = LinearCode::hamming_code();
+    /// let graph = code.tanner_graph();
+    ///
+    /// assert_eq!((graph.node_count(), graph.edge_count()), (10, 12));
+    /// assert_eq!(graph[NodeIndex::new(8)], TannerNode::Check(1));
+    /// assert_eq!(connected_components(&graph), 1);
+    /// assert!(is_cyclic_undirected(&graph));
+    ///
+    /// assert!(!is_cyclic_undirected(&LinearCode::repetition_code(5).tanner_graph()));
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// This requires the `petgraph` feature.
+    pub fn tanner_graph(&self) -> UnGraph<TannerNode, ()> {
+        bipartite_graph(
+            (0..self.len()).map(TannerNode::Bit),
+            &[(self.parity_check_matrix(), TannerNode::Check)],
+        )
+    }
+}
+
+impl CssCode {
+    /// Returns the Tanner graph of the code as a petgraph graph.
+    ///
+    /// The nodes are the qubits followed by the X stabilizers
+    /// and then by the Z stabilizers.
+    /// There is an edge between a qubit and each stabilizer acting on it.
+    ///
+    /// The graphs of the X and Z stabilizers alone are
+    /// the Tanner graphs of the [`x_code`](CssCode::x_code)
+    /// and of the [`z_code`](CssCode::z_code).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::{CssCode, CssTannerNode};
+    /// use petgraph::algo::is_bipartite_undirected;
+    /// use petgraph::graph::NodeIndex;
+    ///
+    /// let code = CssCode::steane_code();
+    /// let graph = code.tanner_graph();
+    ///
+    /// assert_eq!((graph.node_count(), graph.edge_count()), (13, 24));
+    /// assert_eq!(graph[NodeIndex::new(10)], CssTannerNode::ZStabilizer(0));
+    /// assert!(is_bipartite_undirected(&graph, NodeIndex::new(0)));
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// This requires the `petgraph` feature.
+    pub fn tanner_graph(&self) -> UnGraph<CssTannerNode, ()> {
+        bipartite_graph(
+            (0..self.len()).map(CssTannerNode::Qubit),
+            &[
+                (self.x_stabs_binary(), CssTannerNode::XStabilizer),
+                (self.z_stabs_binary(), CssTannerNode::ZStabilizer),
+            ],
+        )
+    }
+
+    /// Returns the graph of the qubits that interact in a stabilizer measurement.
+    ///
+    /// The node of index `i` is qubit `i`
+    /// and two qubits are adjacent if a stabilizer acts on both of them.
+    /// A coloring of this graph, for example, splits the qubits
+    /// into groups without two qubits acted on by the same stabilizer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// let graph = CssCode::steane_code().qubit_graph();
+    ///
+    /// // Two qubits of the Steane code share a stabilizer
+    /// // unless their columns in the Hamming code don't overlap.
+    /// assert_eq!((graph.node_count(), graph.edge_count()), (7, 15));
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// This requires the `petgraph` feature.
+    pub fn qubit_graph(&self) -> UnGraph<usize, ()> {
+        let edges = self
+            .x_stabs_binary()
+            .rows()
+            .chain(self.z_stabs_binary().rows())
+            .flat_map(|stabilizer| {
+                stabilizer
+                    .non_trivial_positions()
+                    .tuple_combinations::<(usize, usize)>()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<BTreeSet<_>>();
+        let mut graph = UnGraph::with_capacity(self.len(), edges.len());
+        for qubit in 0..self.len() {
+            graph.add_node(qubit);
+        }
+        for (first, second) in edges {
+            graph.add_edge(NodeIndex::new(first), NodeIndex::new(second), ());
+        }
+        graph
+    }
+}
+
+// A matrix whose rows are nodes with the node of each row index.
+type RowNodes<'a, N> = (&'a SparseBinMat, fn(usize) -> N);
+
+// The graph between the given nodes and the rows of each matrix
+// in which each row is adjacent to the nodes of its non trivial positions.
+// The nodes of the rows are added after the given nodes, one matrix after the other.
+fn bipartite_graph<N, I>(nodes: I, matrices: &[RowNodes<N>]) -> UnGraph<N, ()>
+where
+    I: ExactSizeIterator<Item = N>,
+{
+    let num_nodes = nodes.len();
+    let num_rows = matrices
+        .iter()
+        .map(|(matrix, _)| matrix.number_of_rows())
+        .sum::<usize>();
+    let num_edges = matrices
+        .iter()
+        .map(|(matrix, _)| matrix.number_of_ones())
+        .sum::<usize>();
+    let mut graph = UnGraph::with_capacity(num_nodes + num_rows, num_edges);
+    for node in nodes {
+        graph.add_node(node);
+    }
+    for (matrix, row_node) in matrices {
+        for (index, row) in matrix.rows().enumerate() {
+            let row_index = graph.add_node(row_node(index));
+            for position in row.non_trivial_positions() {
+                graph.add_edge(NodeIndex::new(position), row_index, ());
+            }
+        }
+    }
+    graph
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tanner_graph_edges_are_the_code_edges() {
+        let code = LinearCode::hamming_code();
+        let graph = code.tanner_graph();
+        let edges = graph
+            .edge_indices()
+            .map(|edge| {
+                let (bit, check) = graph.edge_endpoints(edge).unwrap();
+                (graph[bit], graph[check])
+            })
+            .collect::<Vec<_>>();
+        let expected = code
+            .edges()
+            .map(|edge| (TannerNode::Bit(edge.bit), TannerNode::Check(edge.check)))
+            .collect::<Vec<_>>();
+        assert_eq!(edges, expected);
+    }
+
+    #[test]
+    fn surface_code_qubit_graph() {
+        let graph = CssCode::rotated_surface_code(3).qubit_graph();
+        assert_eq!(graph.node_count(), 9);
+        // The center qubit shares a weight 4 stabilizer with all the other qubits.
+        assert_eq!(graph.neighbors(NodeIndex::new(4)).count(), 8);
+    }
+}
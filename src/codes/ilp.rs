@@ -0,0 +1,77 @@
+use good_lp::{
+    constraint, default_solver, variable, Expression, ProblemVariables, ResolutionError, Solution,
+    SolverModel, Variable,
+};
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+
+// Finds a minimum weight binary vector in the kernel of the checks
+// using an integer program.
+//
+// Each parity constraint is linearized with an integer slack variable
+// such that the sum of the bits in the check is twice the slack.
+// If an odd check is given, the vector must have an odd overlap with it.
+// Otherwise, the vector must be non zero.
+//
+// Returns None if no such vector exists.
+pub(crate) fn minimum_weight_kernel_vector(
+    checks: &SparseBinMat,
+    odd_check: Option<SparseBinSlice>,
+) -> Result<Option<SparseBinVec>, ResolutionError> {
+    let is_non_zero_required = odd_check.is_none();
+    let mut variables = ProblemVariables::new();
+    let bits: Vec<Variable> = variables.add_vector(variable().binary(), checks.number_of_columns());
+    let parities: Vec<(Expression, Variable, f64)> = checks
+        .rows()
+        .map(|check| (check, 0.0))
+        .chain(odd_check.map(|check| (check, 1.0)))
+        .map(|(check, parity)| {
+            let sum: Expression = check.non_trivial_positions().map(|bit| bits[bit]).sum();
+            let slack = variables.add(variable().integer().min(0).max(check.weight() as f64 / 2.0));
+            (sum, slack, parity)
+        })
+        .collect();
+    let weight: Expression = bits.iter().copied().sum();
+    let mut problem = variables.minimise(weight.clone()).using(default_solver);
+    for (sum, slack, parity) in parities {
+        problem = problem.with(constraint!(sum - 2.0 * slack == parity));
+    }
+    if is_non_zero_required {
+        problem = problem.with(constraint!(weight >= 1.0));
+    }
+    match problem.solve() {
+        Ok(solution) => {
+            let positions = bits
+                .iter()
+                .enumerate()
+                .filter(|(_, bit)| solution.value(**bit) > 0.5)
+                .map(|(position, _)| position)
+                .collect();
+            Ok(Some(SparseBinVec::new(
+                checks.number_of_columns(),
+                positions,
+            )))
+        }
+        Err(ResolutionError::Infeasible) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codes::{CssCode, LinearCode};
+
+    #[test]
+    fn hamming_code_has_distance_3() {
+        let code = LinearCode::hamming_code();
+        assert_eq!(code.minimal_distance_ilp().unwrap(), Some(3));
+    }
+
+    #[test]
+    fn toric_code_has_distance_4() {
+        let code = CssCode::toric_code(4);
+        let logical = code.minimum_weight_logical_ilp().unwrap().unwrap();
+        assert_eq!(logical.weight(), 4);
+        assert!(code.has_logical(&logical));
+        assert!(!code.has_stabilizer(&logical));
+    }
+}
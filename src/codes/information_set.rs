@@ -0,0 +1,354 @@
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+
+/// A set of linearly independent columns of a parity check matrix
+/// chosen greedily in a given order,
+/// together with the elimination solving syndromes on these columns.
+///
+/// Each column is added if it is independent of the columns already chosen
+/// until the chosen columns span the same space as all the considered columns.
+/// These pivots are the complement of an information set of the code
+/// when all columns are considered.
+///
+/// This is the shared machinery of ordered statistics decoding
+/// where the columns are ordered by reliability,
+/// of information set decoding where they are ordered at random
+/// and of erasure decoding where only erased columns are considered.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::{InformationSet, LinearCode};
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::hamming_code();
+/// let information_set = InformationSet::new(code.parity_check_matrix(), &[6, 5, 4, 3, 2, 1, 0]);
+/// assert_eq!(information_set.pivots(), &[6, 5, 4]);
+///
+/// // The unique error on the pivots with the syndrome of bit 0.
+/// let syndrome = code.syndrome_of(&SparseBinVec::new(7, vec![0]));
+/// let error = information_set.solve(syndrome.as_view()).unwrap();
+/// assert_eq!(error, SparseBinVec::new(7, vec![5, 6]));
+///
+/// // Fixing the bits outside the pivots.
+/// let fixed = SparseBinVec::new(7, vec![0]);
+/// let error = information_set.solve_with(syndrome.as_view(), fixed.as_view()).unwrap();
+/// assert_eq!(error, fixed);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct InformationSet {
+    parity_check_matrix: SparseBinMat,
+    pivots: Vec<usize>,
+    // The parity check matrix after the row operations.
+    // The first rows are the identity on the pivots.
+    reduced_rows: Vec<Vec<u64>>,
+    // The row operations bringing the pivot columns to the identity.
+    // The rows after the rank must vanish on syndromes of errors on the considered columns.
+    transformation: Vec<Vec<u64>>,
+}
+
+impl InformationSet {
+    /// Chooses the pivots among the given columns in order.
+    ///
+    /// Columns that are not in the order are never pivots.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a column is out of bound or repeated.
+    pub fn new(parity_check_matrix: &SparseBinMat, column_order: &[usize]) -> Self {
+        let num_columns = parity_check_matrix.number_of_columns();
+        let num_rows = parity_check_matrix.number_of_rows();
+        let mut is_used = vec![false; num_columns];
+        for column in column_order {
+            if *column >= num_columns {
+                panic!(
+                    "column {} is out of bound for {} columns",
+                    column, num_columns
+                );
+            }
+            if std::mem::replace(&mut is_used[*column], true) {
+                panic!("column {} is repeated", column);
+            }
+        }
+        let mut rows = parity_check_matrix
+            .rows()
+            .map(|row| to_bitset(row.as_slice(), num_columns))
+            .collect::<Vec<_>>();
+        let mut transformation = (0..num_rows)
+            .map(|row| to_bitset(&[row], num_rows))
+            .collect::<Vec<_>>();
+        let mut pivots = Vec::new();
+        for column in column_order {
+            if pivots.len() == num_rows {
+                break;
+            }
+            let rank = pivots.len();
+            let Some(pivot_row) = (rank..num_rows).find(|row| is_one_at(&rows[*row], *column))
+            else {
+                continue;
+            };
+            rows.swap(rank, pivot_row);
+            transformation.swap(rank, pivot_row);
+            for row in 0..num_rows {
+                if row != rank && is_one_at(&rows[row], *column) {
+                    let (target, pivot) = pair_mut(&mut rows, row, rank);
+                    add_to(target, pivot);
+                    let (target, pivot) = pair_mut(&mut transformation, row, rank);
+                    add_to(target, pivot);
+                }
+            }
+            pivots.push(*column);
+        }
+        Self {
+            parity_check_matrix: parity_check_matrix.clone(),
+            pivots,
+            reduced_rows: rows,
+            transformation,
+        }
+    }
+
+    /// Chooses the pivots among all columns by decreasing reliability.
+    ///
+    /// This is the most reliable basis of ordered statistics decoding
+    /// when the reliabilities are the probabilities that the bits are flipped.
+    /// Ties are broken by the order of the columns.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of reliabilities is different from the number of columns.
+    pub fn most_reliable(parity_check_matrix: &SparseBinMat, reliabilities: &[f64]) -> Self {
+        if reliabilities.len() != parity_check_matrix.number_of_columns() {
+            panic!(
+                "{} reliabilities for {} columns",
+                reliabilities.len(),
+                parity_check_matrix.number_of_columns()
+            );
+        }
+        let mut order = (0..reliabilities.len()).collect::<Vec<_>>();
+        order.sort_by(|first, second| reliabilities[*second].total_cmp(&reliabilities[*first]));
+        Self::new(parity_check_matrix, &order)
+    }
+
+    /// Returns the chosen columns in the order they were chosen.
+    pub fn pivots(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    /// Returns the rank of the considered columns.
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// Returns the error supported on the pivots with the given syndrome
+    /// or None if there is no error on the considered columns with this syndrome.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of rows.
+    pub fn solve(&self, syndrome: SparseBinSlice) -> Option<SparseBinVec> {
+        let num_rows = self.parity_check_matrix.number_of_rows();
+        if syndrome.len() != num_rows {
+            panic!(
+                "syndrome of length {} for {} rows",
+                syndrome.len(),
+                num_rows
+            );
+        }
+        let reduced = self.reduced_syndrome(syndrome)?;
+        let mut positions = self
+            .pivots
+            .iter()
+            .enumerate()
+            .filter(|(row, _)| is_one_at(&reduced, *row))
+            .map(|(_, pivot)| *pivot)
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        Some(SparseBinVec::new(
+            self.parity_check_matrix.number_of_columns(),
+            positions,
+        ))
+    }
+
+    // The syndrome after the row operations restricted to the rows of the pivots,
+    // that is the values of the pivots of the error of the syndrome,
+    // or None if no error on the considered columns has this syndrome.
+    pub(crate) fn reduced_syndrome(&self, syndrome: SparseBinSlice) -> Option<Vec<u64>> {
+        let syndrome = to_bitset(syndrome.as_slice(), syndrome.len());
+        let is_flipped = |row: &Vec<u64>| {
+            row.iter()
+                .zip(syndrome.iter())
+                .fold(0, |parity, (first, second)| parity ^ (first & second))
+                .count_ones()
+                % 2
+                == 1
+        };
+        if self.transformation[self.rank()..].iter().any(is_flipped) {
+            return None;
+        }
+        let mut reduced = to_bitset(&[], self.rank());
+        for (row, transformation) in self.transformation[..self.rank()].iter().enumerate() {
+            if is_flipped(transformation) {
+                flip(&mut reduced, row);
+            }
+        }
+        Some(reduced)
+    }
+
+    // The given column after the row operations restricted to the rows of the pivots,
+    // that is the pivots to flip together with the column to preserve the syndrome.
+    pub(crate) fn reduced_column(&self, column: usize) -> Vec<u64> {
+        let mut reduced = to_bitset(&[], self.rank());
+        for (row, reduced_row) in self.reduced_rows[..self.rank()].iter().enumerate() {
+            if is_one_at(reduced_row, column) {
+                flip(&mut reduced, row);
+            }
+        }
+        reduced
+    }
+
+    /// Returns the error with the given syndrome equal to the fixed vector
+    /// outside the pivots or None if there is no such error.
+    ///
+    /// This is the reprocessing step of ordered statistics decoding
+    /// where a few bits outside the most reliable basis are flipped.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of rows
+    /// or if the fixed vector length is different from the number of columns.
+    pub fn solve_with(
+        &self,
+        syndrome: SparseBinSlice,
+        fixed: SparseBinSlice,
+    ) -> Option<SparseBinVec> {
+        if fixed.len() != self.parity_check_matrix.number_of_columns() {
+            panic!(
+                "vector of length {} for {} columns",
+                fixed.len(),
+                self.parity_check_matrix.number_of_columns()
+            );
+        }
+        let mut is_pivot = vec![false; fixed.len()];
+        for pivot in self.pivots.iter() {
+            is_pivot[*pivot] = true;
+        }
+        let fixed = SparseBinVec::new(
+            fixed.len(),
+            fixed
+                .non_trivial_positions()
+                .filter(|position| !is_pivot[*position])
+                .collect(),
+        );
+        let remaining = &syndrome + &(&self.parity_check_matrix * &fixed);
+        self.solve(remaining.as_view()).map(|error| &error + &fixed)
+    }
+}
+
+pub(crate) fn to_bitset(positions: &[usize], length: usize) -> Vec<u64> {
+    let mut bitset = vec![0; length.div_ceil(64)];
+    for position in positions {
+        flip(&mut bitset, *position);
+    }
+    bitset
+}
+
+pub(crate) fn flip(bitset: &mut [u64], position: usize) {
+    bitset[position / 64] ^= 1 << (position % 64);
+}
+
+pub(crate) fn is_one_at(bitset: &[u64], position: usize) -> bool {
+    bitset[position / 64] >> (position % 64) & 1 == 1
+}
+
+pub(crate) fn add_to(bitset: &mut [u64], other: &[u64]) {
+    bitset
+        .iter_mut()
+        .zip(other)
+        .for_each(|(word, other)| *word ^= other);
+}
+
+pub(crate) fn weight(bitset: &[u64]) -> usize {
+    bitset.iter().map(|word| word.count_ones() as usize).sum()
+}
+
+// Mutable access to a row together with another row.
+fn pair_mut(rows: &mut [Vec<u64>], target: usize, other: usize) -> (&mut Vec<u64>, &Vec<u64>) {
+    if target < other {
+        let (first, second) = rows.split_at_mut(other);
+        (&mut first[target], &second[0])
+    } else {
+        let (first, second) = rows.split_at_mut(target);
+        (&mut second[0], &first[other])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn pivots_span_the_considered_columns() {
+        let code = LinearCode::random_regular_code()
+            .num_bits(30)
+            .num_checks(20)
+            .bit_degree(2)
+            .check_degree(3)
+            .sample_with(&mut StdRng::seed_from_u64(123))
+            .unwrap();
+        let matrix = code.parity_check_matrix();
+        let mut rng = StdRng::seed_from_u64(456);
+        for _ in 0..50 {
+            let mut order = (0..30).collect::<Vec<_>>();
+            order.shuffle(&mut rng);
+            order.truncate(rng.gen_range(0..=30));
+            let information_set = InformationSet::new(matrix, &order);
+            let mut columns = order.clone();
+            columns.sort_unstable();
+            // Empty rows are counted in the rank.
+            let rows = matrix
+                .keep_only_columns(&columns)
+                .unwrap()
+                .rows()
+                .filter(|row| !row.is_zero())
+                .map(|row| row.as_slice().to_vec())
+                .collect();
+            let rank = SparseBinMat::new(columns.len(), rows).rank();
+            assert_eq!(information_set.rank(), rank);
+
+            let error = SparseBinVec::new(
+                30,
+                columns
+                    .iter()
+                    .cloned()
+                    .filter(|_| rng.gen_bool(0.5))
+                    .collect(),
+            );
+            let syndrome = code.syndrome_of(&error);
+            let solution = information_set.solve(syndrome.as_view()).unwrap();
+            assert_eq!(code.syndrome_of(&solution), syndrome);
+            assert!(solution
+                .non_trivial_positions()
+                .all(|bit| information_set.pivots().contains(&bit)));
+        }
+    }
+
+    #[test]
+    fn syndrome_outside_the_span_has_no_solution() {
+        let code = LinearCode::repetition_code(4);
+        let information_set = InformationSet::new(code.parity_check_matrix(), &[0, 1]);
+        assert_eq!(information_set.pivots(), &[0, 1]);
+        let syndrome = code.syndrome_of(&SparseBinVec::new(4, vec![3]));
+        assert_eq!(information_set.solve(syndrome.as_view()), None);
+    }
+
+    #[test]
+    fn most_reliable_columns_are_chosen_first() {
+        let code = LinearCode::repetition_code(4);
+        let information_set =
+            InformationSet::most_reliable(code.parity_check_matrix(), &[0.1, 0.9, 0.5, 0.7]);
+        assert_eq!(information_set.pivots(), &[1, 3, 2]);
+    }
+}
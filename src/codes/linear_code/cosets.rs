@@ -0,0 +1,180 @@
+use super::LinearCode;
+use crate::codes::{DenseCodeword, InformationSet};
+use sparse_bin_mat::{SparseBinVec, SparseBinVecBase};
+use std::cmp::Ordering;
+
+/// Returns the number of positions where two binary vectors differ,
+/// that is the weight of their sum.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::hamming_distance;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let first = SparseBinVec::new(5, vec![0, 1, 3]);
+/// let second = SparseBinVec::new(5, vec![1, 4]);
+/// assert_eq!(hamming_distance(&first, &second.as_view()), 3);
+/// ```
+///
+/// # Panic
+///
+/// Panics if the vectors have different lengths.
+pub fn hamming_distance<S, T>(first: &SparseBinVecBase<S>, second: &SparseBinVecBase<T>) -> usize
+where
+    S: std::ops::Deref<Target = [usize]>,
+    T: std::ops::Deref<Target = [usize]>,
+{
+    if first.len() != second.len() {
+        panic!(
+            "vectors of lengths {} and {} can't be compared",
+            first.len(),
+            second.len()
+        );
+    }
+    let mut first = first.non_trivial_positions().peekable();
+    let mut second = second.non_trivial_positions().peekable();
+    let mut distance = 0;
+    loop {
+        match (first.peek(), second.peek()) {
+            (Some(left), Some(right)) => match left.cmp(right) {
+                Ordering::Less => {
+                    first.next();
+                    distance += 1;
+                }
+                Ordering::Greater => {
+                    second.next();
+                    distance += 1;
+                }
+                Ordering::Equal => {
+                    first.next();
+                    second.next();
+                }
+            },
+            _ => return distance + first.count() + second.count(),
+        }
+    }
+}
+
+impl LinearCode {
+    /// Returns the number of bits where two vectors differ.
+    ///
+    /// This is the same as [`hamming_distance`]
+    /// for vectors of the length of the code.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a vector length is different from the length of the code.
+    pub fn weight_of_difference<S, T>(
+        &self,
+        first: &SparseBinVecBase<S>,
+        second: &SparseBinVecBase<T>,
+    ) -> usize
+    where
+        S: std::ops::Deref<Target = [usize]>,
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if first.len() != self.len() || second.len() != self.len() {
+            panic!(
+                "vectors of lengths {} and {} are invalid for code with length {}",
+                first.len(),
+                second.len(),
+                self.len()
+            );
+        }
+        hamming_distance(first, second)
+    }
+
+    /// Returns a vector of minimum weight with the given syndrome
+    /// or None if no vector has this syndrome.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use sparse_bin_mat::SparseBinVec;
+    ///
+    /// let code = LinearCode::repetition_code(5);
+    /// let error = SparseBinVec::new(5, vec![0, 1, 2]);
+    /// let syndrome = code.syndrome_of(&error);
+    ///
+    /// assert_eq!(
+    ///     code.coset_leader(&syndrome),
+    ///     Some(SparseBinVec::new(5, vec![3, 4]))
+    /// );
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// This enumerates all codewords and is only suited for small codes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    pub fn coset_leader<T>(&self, syndrome: &SparseBinVecBase<T>) -> Option<SparseBinVec>
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if syndrome.len() != self.num_checks() {
+            panic!(
+                "syndrome of length {} is invalid for code with {} checks",
+                syndrome.len(),
+                self.num_checks()
+            );
+        }
+        let columns = (0..self.len()).collect::<Vec<_>>();
+        let solution =
+            InformationSet::new(self.parity_check_matrix(), &columns).solve(syndrome.as_view())?;
+        let generators = self
+            .generator_matrix()
+            .rows()
+            .map(|generator| DenseCodeword::from(&generator))
+            .collect::<Vec<_>>();
+        let mut current = DenseCodeword::from(&solution);
+        let mut leader = current.clone();
+        // Visits every coset element by adding a single generator at a time in Gray code order.
+        for step in 1_usize..1 << generators.len() {
+            current += &generators[step.trailing_zeros() as usize];
+            if current.weight() < leader.weight() {
+                leader = current.clone();
+            }
+        }
+        Some(SparseBinVec::from(&leader))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn coset_leaders_of_hamming_code_have_weight_at_most_one() {
+        let code = LinearCode::hamming_code();
+        for positions in (0..3).powerset() {
+            let syndrome = SparseBinVec::new(3, positions);
+            let leader = code.coset_leader(&syndrome).unwrap();
+            assert_eq!(code.syndrome_of(&leader), syndrome);
+            assert_eq!(leader.weight(), (!syndrome.is_zero()) as usize);
+        }
+    }
+
+    #[test]
+    fn distance_is_symmetric_weight_of_sum() {
+        let first = SparseBinVec::new(8, vec![0, 2, 5, 7]);
+        let second = SparseBinVec::new(8, vec![1, 2, 3]);
+        assert_eq!(
+            hamming_distance(&first, &second),
+            (&first + &second).weight()
+        );
+        assert_eq!(hamming_distance(&second, &first), 5);
+        assert_eq!(hamming_distance(&first, &first), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn vectors_must_have_the_code_length() {
+        let code = LinearCode::hamming_code();
+        code.weight_of_difference(&SparseBinVec::zeros(7), &SparseBinVec::zeros(6));
+    }
+}
@@ -0,0 +1,237 @@
+use super::LinearCode;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use sparse_bin_mat::SparseBinMat;
+use std::collections::{HashMap, VecDeque};
+
+// A mutable Tanner graph used to perform edge swaps.
+pub(super) struct TannerGraph {
+    checks: Vec<Vec<usize>>,
+    bits: Vec<Vec<usize>>,
+}
+
+impl TannerGraph {
+    pub(super) fn from_code(code: &LinearCode) -> Self {
+        Self {
+            checks: Self::adjacencies(code.parity_check_matrix()),
+            bits: Self::adjacencies(code.bit_adjacencies()),
+        }
+    }
+
+    fn adjacencies(matrix: &SparseBinMat) -> Vec<Vec<usize>> {
+        matrix
+            .rows()
+            .map(|row| row.non_trivial_positions().collect())
+            .collect()
+    }
+
+    pub(super) fn num_four_cycles(&self) -> usize {
+        (0..self.checks.len())
+            .map(|check| self.num_four_cycles_through(check))
+            .sum::<usize>()
+            / 2
+    }
+
+    // The length of the shortest cycle found by a breadth first search from each bit.
+    // The nodes are the bits followed by the checks.
+    pub(super) fn girth(&self) -> Option<usize> {
+        let num_bits = self.bits.len();
+        let neighbors = |node: usize| -> &[usize] {
+            if node < num_bits {
+                &self.bits[node]
+            } else {
+                &self.checks[node - num_bits]
+            }
+        };
+        let mut girth: Option<usize> = None;
+        for start in 0..num_bits {
+            let mut distances = vec![None; num_bits + self.checks.len()];
+            let mut parents = vec![usize::MAX; distances.len()];
+            distances[start] = Some(0);
+            let mut queue = VecDeque::from([start]);
+            while let Some(node) = queue.pop_front() {
+                let distance = distances[node].unwrap();
+                // Longer cycles through the start can't improve the girth.
+                if girth.is_some_and(|girth| 2 * distance + 1 >= girth) {
+                    break;
+                }
+                for neighbor in neighbors(node).iter().map(|neighbor| {
+                    if node < num_bits {
+                        neighbor + num_bits
+                    } else {
+                        *neighbor
+                    }
+                }) {
+                    match distances[neighbor] {
+                        None => {
+                            distances[neighbor] = Some(distance + 1);
+                            parents[neighbor] = node;
+                            queue.push_back(neighbor);
+                        }
+                        Some(other) if parents[node] != neighbor => {
+                            let length = distance + other + 1;
+                            girth = Some(girth.map_or(length, |girth| girth.min(length)));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        girth
+    }
+
+    // Maps each other check sharing a bit with the given check to the number of shared bits.
+    fn overlaps(&self, check: usize) -> HashMap<usize, usize> {
+        let mut overlaps = HashMap::new();
+        for bit in self.checks[check].iter() {
+            for other in self.bits[*bit].iter().filter(|other| **other != check) {
+                *overlaps.entry(*other).or_insert(0) += 1;
+            }
+        }
+        overlaps
+    }
+
+    // Two checks sharing k bits are in k choose 2 cycles of length 4.
+    fn num_four_cycles_through(&self, check: usize) -> usize {
+        self.overlaps(check)
+            .values()
+            .map(|overlap| overlap * (overlap - 1) / 2)
+            .sum()
+    }
+
+    fn num_four_cycles_through_either(&self, first: usize, second: usize) -> usize {
+        let shared = self.overlaps(first).get(&second).cloned().unwrap_or(0);
+        self.num_four_cycles_through(first) + self.num_four_cycles_through(second)
+            - shared * shared.saturating_sub(1) / 2
+    }
+
+    fn edges_in_four_cycles(&self) -> Vec<(usize, usize)> {
+        let mut edges = Vec::new();
+        for (check, bits) in self.checks.iter().enumerate() {
+            let overlaps = self.overlaps(check);
+            for bit in bits.iter() {
+                let is_in_cycle = self.bits[*bit]
+                    .iter()
+                    .any(|other| matches!(overlaps.get(other), Some(overlap) if *overlap > 1));
+                if is_in_cycle {
+                    edges.push((check, *bit));
+                }
+            }
+        }
+        edges
+    }
+
+    fn random_edge<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        let check = rng.gen_range(0..self.checks.len());
+        self.checks[check].choose(rng).map(|bit| (check, *bit))
+    }
+
+    // Replaces the edges (c1, b1) and (c2, b2) by (c1, b2) and (c2, b1).
+    fn swap(
+        &mut self,
+        (first_check, first_bit): (usize, usize),
+        (second_check, second_bit): (usize, usize),
+    ) {
+        Self::replace(&mut self.checks[first_check], first_bit, second_bit);
+        Self::replace(&mut self.checks[second_check], second_bit, first_bit);
+        Self::replace(&mut self.bits[first_bit], first_check, second_check);
+        Self::replace(&mut self.bits[second_bit], second_check, first_check);
+    }
+
+    fn replace(adjacency: &mut [usize], old: usize, new: usize) {
+        if let Some(node) = adjacency.iter_mut().find(|node| **node == old) {
+            *node = new;
+        }
+    }
+
+    // Swaps random pairs of edges, one of them in a 4-cycle,
+    // keeping only the swaps reducing the number of 4-cycles.
+    pub(super) fn remove_four_cycles<R: Rng>(&mut self, max_attempts: usize, rng: &mut R) {
+        if self.checks.is_empty() {
+            return;
+        }
+        let mut edges_in_cycles = self.edges_in_four_cycles();
+        for _ in 0..max_attempts {
+            let first = match edges_in_cycles.choose(rng) {
+                Some(edge) => *edge,
+                None => return,
+            };
+            let second = match self.random_edge(rng) {
+                Some(edge) => edge,
+                None => continue,
+            };
+            if first.0 == second.0
+                || self.checks[first.0].contains(&second.1)
+                || self.checks[second.0].contains(&first.1)
+            {
+                continue;
+            }
+            let num_cycles = self.num_four_cycles_through_either(first.0, second.0);
+            self.swap(first, second);
+            if self.num_four_cycles_through_either(first.0, second.0) < num_cycles {
+                edges_in_cycles = self.edges_in_four_cycles();
+            } else {
+                self.swap((first.0, second.1), (second.0, first.1));
+            }
+        }
+    }
+
+    pub(super) fn into_parity_check_matrix(self) -> SparseBinMat {
+        let rows = self
+            .checks
+            .into_iter()
+            .map(|mut bits| {
+                bits.sort_unstable();
+                bits
+            })
+            .collect();
+        SparseBinMat::new(self.bits.len(), rows)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn random_code() -> LinearCode {
+        LinearCode::random_regular_code()
+            .num_bits(60)
+            .num_checks(45)
+            .bit_degree(3)
+            .check_degree(4)
+            .sample_with(&mut StdRng::seed_from_u64(123))
+            .unwrap()
+    }
+
+    #[test]
+    fn hamming_code_cycles() {
+        assert_eq!(LinearCode::hamming_code().num_four_cycles(), 3);
+        assert_eq!(LinearCode::repetition_code(5).num_four_cycles(), 0);
+    }
+
+    #[test]
+    fn girth_of_small_codes() {
+        assert_eq!(LinearCode::hamming_code().girth(), Some(4));
+        assert_eq!(LinearCode::repetition_code(5).girth(), None);
+        let code = random_code().remove_short_cycles(10_000, &mut StdRng::seed_from_u64(123));
+        assert!(code.girth().unwrap() >= 6);
+    }
+
+    #[test]
+    fn removing_cycles_preserves_degrees() {
+        let code = random_code();
+        assert!(code.num_four_cycles() > 0);
+        let new_code = code.remove_short_cycles(10_000, &mut StdRng::seed_from_u64(123));
+        assert_eq!(new_code.degree_profile(), code.degree_profile());
+        assert_eq!(new_code.num_four_cycles(), 0);
+    }
+
+    #[test]
+    fn no_attempt_keeps_the_code() {
+        let code = random_code();
+        let new_code = code.remove_short_cycles(0, &mut StdRng::seed_from_u64(123));
+        assert_eq!(new_code.parity_check_matrix(), code.parity_check_matrix());
+    }
+}
@@ -0,0 +1,128 @@
+use super::LinearCode;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinMat;
+
+/// The degree distributions of the bits and checks of a code.
+///
+/// The degree of a bit is the number of checks it participates in
+/// and the degree of a check is the number of bits it involves.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DegreeProfile {
+    pub bits: DegreeDistribution,
+    pub checks: DegreeDistribution,
+}
+
+impl DegreeProfile {
+    pub(super) fn new(code: &LinearCode) -> Self {
+        Self {
+            bits: DegreeDistribution::from_rows_of(code.bit_adjacencies()),
+            checks: DegreeDistribution::from_rows_of(code.parity_check_matrix()),
+        }
+    }
+
+    /// Checks if all bits have the same degree and all checks have the same degree.
+    pub fn is_regular(&self) -> bool {
+        self.bits.is_regular() && self.checks.is_regular()
+    }
+}
+
+/// A histogram of the degrees of a set of nodes.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DegreeDistribution {
+    counts: Vec<usize>,
+}
+
+impl DegreeDistribution {
+    fn from_rows_of(matrix: &SparseBinMat) -> Self {
+        let mut counts = Vec::new();
+        for degree in matrix.rows().map(|row| row.weight()) {
+            if degree >= counts.len() {
+                counts.resize(degree + 1, 0);
+            }
+            counts[degree] += 1;
+        }
+        Self { counts }
+    }
+
+    /// Returns the number of nodes of each degree.
+    ///
+    /// The last entry is the number of nodes of maximal degree.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// Returns the number of nodes of the given degree.
+    pub fn count(&self, degree: usize) -> usize {
+        self.counts.get(degree).cloned().unwrap_or(0)
+    }
+
+    /// Returns the number of nodes.
+    pub fn num_nodes(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the smallest degree or None if there is no node.
+    pub fn min(&self) -> Option<usize> {
+        self.counts.iter().position(|count| *count > 0)
+    }
+
+    /// Returns the largest degree or None if there is no node.
+    pub fn max(&self) -> Option<usize> {
+        self.counts.iter().rposition(|count| *count > 0)
+    }
+
+    /// Returns the average degree or None if there is no node.
+    pub fn mean(&self) -> Option<f64> {
+        let num_nodes = self.num_nodes();
+        if num_nodes == 0 {
+            return None;
+        }
+        let num_edges: usize = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(degree, count)| degree * count)
+            .sum();
+        Some(num_edges as f64 / num_nodes as f64)
+    }
+
+    /// Checks if all nodes have the same degree.
+    pub fn is_regular(&self) -> bool {
+        self.min() == self.max()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sparse_bin_mat::SparseBinMat;
+
+    #[test]
+    fn hamming_code_profile() {
+        let profile = LinearCode::hamming_code().degree_profile();
+        assert_eq!(profile.bits.counts(), &[0, 3, 3, 1]);
+        assert_eq!(profile.bits.min(), Some(1));
+        assert_eq!(profile.bits.max(), Some(3));
+        assert_eq!(profile.bits.mean(), Some(12.0 / 7.0));
+        assert_eq!(profile.checks.counts(), &[0, 0, 0, 0, 3]);
+        assert!(profile.checks.is_regular());
+        assert!(!profile.is_regular());
+    }
+
+    #[test]
+    fn unchecked_bits_are_counted() {
+        let matrix = SparseBinMat::new(4, vec![vec![0, 1, 2]]);
+        let profile = LinearCode::from_parity_check_matrix(matrix).degree_profile();
+        assert_eq!(profile.bits.counts(), &[1, 3]);
+        assert_eq!(profile.bits.min(), Some(0));
+        assert_eq!(profile.checks.counts(), &[0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn empty_code_has_regular_profile() {
+        let profile = LinearCode::empty().degree_profile();
+        assert_eq!(profile.bits.num_nodes(), 0);
+        assert_eq!(profile.bits.mean(), None);
+        assert!(profile.is_regular());
+    }
+}
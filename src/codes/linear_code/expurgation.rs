@@ -0,0 +1,188 @@
+use super::LinearCode;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec, SparseBinVecBase};
+
+/// A subcode of a linear code where some bits are always 0,
+/// together with the record of these bits.
+///
+/// The bits are either frozen, in which case they stay in the code,
+/// or removed, in which case the remaining bits are relabeled
+/// in increasing order.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct ExpurgatedCode {
+    code: LinearCode,
+    expurgated_bits: Vec<usize>,
+    original_positions: Vec<usize>,
+    original_length: usize,
+}
+
+impl ExpurgatedCode {
+    pub(super) fn with_frozen_bits(code: &LinearCode, bits: &[usize]) -> Self {
+        let bits = Self::validate(code, bits);
+        let frozen_checks =
+            SparseBinMat::new(code.len(), bits.iter().map(|bit| vec![*bit]).collect());
+        let parity_check_matrix = code
+            .parity_check_matrix()
+            .vertical_concat_with(&frozen_checks);
+        Self {
+            code: LinearCode::from_parity_check_matrix(parity_check_matrix),
+            expurgated_bits: bits,
+            original_positions: (0..code.len()).collect(),
+            original_length: code.len(),
+        }
+    }
+
+    pub(super) fn with_removed_bits(code: &LinearCode, bits: &[usize]) -> Self {
+        let bits = Self::validate(code, bits);
+        let original_positions = (0..code.len())
+            .filter(|bit| bits.binary_search(bit).is_err())
+            .collect_vec();
+        let checks = code
+            .parity_check_matrix()
+            .keep_only_columns(&original_positions)
+            .unwrap();
+        // Checks only involving removed bits are trivially satisfied.
+        let rows = checks
+            .rows()
+            .filter(|check| !check.is_zero())
+            .map(|check| check.non_trivial_positions().collect())
+            .collect();
+        Self {
+            code: LinearCode::from_parity_check_matrix(SparseBinMat::new(
+                original_positions.len(),
+                rows,
+            )),
+            expurgated_bits: bits,
+            original_positions,
+            original_length: code.len(),
+        }
+    }
+
+    fn validate(code: &LinearCode, bits: &[usize]) -> Vec<usize> {
+        if let Some(bit) = bits.iter().find(|bit| **bit >= code.len()) {
+            panic!(
+                "bit {} is out of bound for code of length {}",
+                bit,
+                code.len()
+            );
+        }
+        bits.iter().cloned().sorted().dedup().collect()
+    }
+
+    /// Returns the expurgated code.
+    pub fn code(&self) -> &LinearCode {
+        &self.code
+    }
+
+    /// Returns the expurgated code, dropping the record of the expurgated bits.
+    pub fn into_code(self) -> LinearCode {
+        self.code
+    }
+
+    /// Returns the sorted positions of the frozen or removed bits in the original code.
+    pub fn expurgated_bits(&self) -> &[usize] {
+        &self.expurgated_bits
+    }
+
+    /// Returns the length of the original code.
+    pub fn original_length(&self) -> usize {
+        self.original_length
+    }
+
+    /// Returns the position in the original code of the given bit
+    /// or None if the bit is out of bound.
+    pub fn original_position(&self, bit: usize) -> Option<usize> {
+        self.original_positions.get(bit).cloned()
+    }
+
+    /// Maps a vector of the expurgated code to the original code
+    /// with zeros on the removed bits.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vector length is different from the length of the expurgated code.
+    pub fn lift<T>(&self, vector: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if vector.len() != self.code.len() {
+            panic!(
+                "vector of length {} for code of length {}",
+                vector.len(),
+                self.code.len()
+            );
+        }
+        let positions = vector
+            .non_trivial_positions()
+            .map(|bit| self.original_positions[bit])
+            .collect();
+        SparseBinVec::new(self.original_length, positions)
+    }
+
+    /// Restricts a vector of the original code to the bits kept in the expurgated code.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vector length is different from the length of the original code.
+    pub fn restrict<T>(&self, vector: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if vector.len() != self.original_length {
+            panic!(
+                "vector of length {} for code of length {}",
+                vector.len(),
+                self.original_length
+            );
+        }
+        let positions = vector
+            .non_trivial_positions()
+            .filter_map(|bit| self.original_positions.binary_search(&bit).ok())
+            .collect();
+        SparseBinVec::new(self.code.len(), positions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn freezing_bits_of_hamming_code() {
+        let code = LinearCode::hamming_code();
+        let expurgated = code.freeze_bits(&[6, 0, 6]);
+        assert_eq!(expurgated.expurgated_bits(), &[0, 6]);
+        assert_eq!(expurgated.code().len(), 7);
+        assert_eq!(expurgated.code().dimension(), 2);
+        assert!(expurgated
+            .code()
+            .generator_matrix()
+            .rows()
+            .all(|codeword| !codeword.is_one_at(0).unwrap() && !codeword.is_one_at(6).unwrap()));
+    }
+
+    #[test]
+    fn removing_bits_of_hamming_code() {
+        let code = LinearCode::hamming_code();
+        let expurgated = code.remove_bits(&[0, 6]);
+        assert_eq!(expurgated.code().len(), 5);
+        assert_eq!(expurgated.code().dimension(), 2);
+        assert_eq!(expurgated.original_position(0), Some(1));
+        assert_eq!(expurgated.original_position(4), Some(5));
+        for codeword in expurgated.code().generator_matrix().rows() {
+            let lifted = expurgated.lift(&codeword);
+            assert!(code.has_codeword(&lifted));
+            assert_eq!(expurgated.restrict(&lifted).as_view(), codeword);
+        }
+    }
+
+    #[test]
+    fn removing_all_bits_of_a_check() {
+        let code = LinearCode::repetition_code(3);
+        let expurgated = code.remove_bits(&[0, 1]);
+        assert_eq!(expurgated.code().len(), 1);
+        assert_eq!(expurgated.code().num_checks(), 1);
+        assert_eq!(expurgated.code().dimension(), 0);
+    }
+}
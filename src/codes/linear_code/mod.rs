@@ -1,14 +1,56 @@
+use crate::codes::{
+    approximate_rank, par_syndromes_of_batch, read_alist, syndromes_of_batch, AlistError,
+    BinaryMatrix, DenseCodeword, Fingerprint, FingerprintHasher, InformationSet, Provenance,
+};
+use crate::decoders::{lowest_weight_solution, IsdAlgorithm, SyndromeDecoder, TieBreaking};
 use crate::noise::NoiseModel;
 use itertools::Itertools;
+use rand::seq::SliceRandom;
 use rand::Rng;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec, SparseBinVecBase};
+use std::hash::{Hash, Hasher};
+use std::io::BufRead;
+
+mod cosets;
+pub use cosets::hamming_distance;
+
+mod cycles;
+use cycles::TannerGraph;
+
+mod degrees;
+pub use degrees::{DegreeDistribution, DegreeProfile};
 
 mod edges;
 pub use edges::{Edge, Edges};
 
+mod expurgation;
+pub use expurgation::ExpurgatedCode;
+
+mod quantum;
+pub use quantum::{QuantumCandidate, QuantumConstruction};
+
 mod random;
-pub use self::random::RandomRegularCode;
+pub use self::random::{RandomRegularCode, SamplingError};
+
+mod reordering;
+pub use reordering::ReorderedCode;
+
+mod spectrum;
+pub use spectrum::TannerSpectrum;
+
+mod splitting;
+pub use splitting::SplitCode;
+
+mod validation;
+pub use validation::LinearCodeInconsistency;
+
+mod zoo;
+
+// The number of generators whose sums define the chunks
+// enumerated in parallel when computing the minimal distance.
+const MAX_FIXED_GENERATORS: usize = 10;
 
 /// An implementation of linear codes optimized for LDPC codes.
 ///
@@ -48,11 +90,57 @@ pub use self::random::RandomRegularCode;
 /// use [`has_the_same_codespace`](LinearCode::has_the_same_codespace) method
 /// if you want to know if 2 codes define the same codespace even
 /// if they may have different parity check matrix or generator matrix.
-#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+///
+/// Metadata such as a known distance or the provenance is not part of the comparison.
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// let code = LinearCode::repetition_code(3);
+/// let same_code = LinearCode::from_parity_check_matrix(code.parity_check_matrix().clone());
+/// assert_eq!(code, same_code);
+/// ```
+///
+/// # Deserialization
+///
+/// With the `checked-serde` feature, the bit adjacencies are recomputed
+/// from the parity check matrix and deserialization fails
+/// if the generator matrix is not [consistent](LinearCode::validate)
+/// with the parity check matrix.
+/// Otherwise, the matrices are trusted as they are.
+///
+/// The [known distance](LinearCode::known_distance) can't be checked
+/// and is not serialized, such that it is only known for codes built in memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "checked-serde",
+    serde(try_from = "validation::UncheckedLinearCode")
+)]
 pub struct LinearCode {
     parity_check_matrix: SparseBinMat,
     generator_matrix: SparseBinMat,
     bit_adjacencies: SparseBinMat,
+    #[serde(skip)]
+    known_distance: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    provenance: Option<Provenance>,
+}
+
+// The bit adjacencies are the transposed parity check matrix
+// and the remaining fields are metadata.
+impl PartialEq for LinearCode {
+    fn eq(&self, other: &Self) -> bool {
+        self.parity_check_matrix == other.parity_check_matrix
+            && self.generator_matrix == other.generator_matrix
+    }
+}
+
+impl Eq for LinearCode {}
+
+impl Hash for LinearCode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.parity_check_matrix.hash(state);
+        self.generator_matrix.hash(state);
+    }
 }
 
 impl LinearCode {
@@ -67,7 +155,9 @@ impl LinearCode {
         Self {
             generator_matrix,
             parity_check_matrix,
-            bit_adjacencies
+            bit_adjacencies,
+            known_distance: None,
+            provenance: None,
         }
     }
 
@@ -94,9 +184,60 @@ impl LinearCode {
             parity_check_matrix,
             generator_matrix,
             bit_adjacencies,
+            known_distance: None,
+            provenance: None,
         }
     }
 
+    /// Creates a new linear code from the given parity check matrix
+    /// computing the generator matrix with the given matrix backend.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::codes::DenseBinMat;
+    ///
+    /// let checks = LinearCode::extended_golay_code().parity_check_matrix().clone();
+    /// let code = LinearCode::from_parity_check_matrix_with::<DenseBinMat>(checks);
+    ///
+    /// assert_eq!(code.dimension(), 12);
+    /// assert!(code.has_same_codespace(&LinearCode::extended_golay_code()));
+    /// ```
+    pub fn from_parity_check_matrix_with<M: BinaryMatrix>(
+        parity_check_matrix: SparseBinMat,
+    ) -> Self {
+        let generator_matrix = M::from_sparse_bin_mat(&parity_check_matrix)
+            .nullspace()
+            .to_sparse_bin_mat();
+        let bit_adjacencies = parity_check_matrix.transposed();
+        Self {
+            parity_check_matrix,
+            generator_matrix,
+            bit_adjacencies,
+            known_distance: None,
+            provenance: None,
+        }
+    }
+
+    /// Creates a new linear code from a parity check matrix in the alist format,
+    /// see [`read_alist`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let alist = "3 2\n2 2\n1 2 1\n2 2\n1\n1 2\n2\n1 2\n2 3\n";
+    /// let code = LinearCode::from_alist(alist.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(code.len(), 3);
+    /// assert_eq!(code.dimension(), 1);
+    /// assert!(code.has_same_codespace(&LinearCode::repetition_code(3)));
+    /// ```
+    pub fn from_alist<R: BufRead>(reader: R) -> Result<Self, AlistError> {
+        read_alist(reader).map(Self::from_parity_check_matrix)
+    }
+
     /// Creates a new linear code from the given generator matrix.
     ///
     /// # Example
@@ -120,6 +261,8 @@ impl LinearCode {
             parity_check_matrix,
             generator_matrix,
             bit_adjacencies,
+            known_distance: None,
+            provenance: None,
         }
     }
 
@@ -139,7 +282,7 @@ impl LinearCode {
     pub fn repetition_code(length: usize) -> Self {
         let checks = (0..length - 1).map(|c| vec![c, c + 1]).collect();
         let matrix = SparseBinMat::new(length, checks);
-        Self::from_parity_check_matrix(matrix)
+        Self::from_parity_check_matrix(matrix).with_known_distance(length)
     }
 
     /// Returns the Hamming code.
@@ -163,7 +306,173 @@ impl LinearCode {
             7,
             vec![vec![3, 4, 5, 6], vec![1, 2, 5, 6], vec![0, 2, 4, 6]],
         );
-        Self::from_parity_check_matrix(parity_check_matrix)
+        Self::from_parity_check_matrix(parity_check_matrix).with_known_distance(3)
+    }
+
+    /// Returns the polar code of the given length where the
+    /// inputs of the polar transform in the frozen set are fixed to 0.
+    ///
+    /// The polar transform is the `m`-th Kronecker power of `[[1, 0], [1, 1]]`
+    /// without bit-reversal permutation.
+    /// There is one generator for each input outside the frozen set
+    /// and one check for each frozen input, in increasing order.
+    /// Decoding is done with the [`PolarDecoder`](crate::decoders::PolarDecoder).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// // The first order Reed-Muller code of length 8.
+    /// let code = LinearCode::polar_code(8, &[0, 1, 2, 4]);
+    ///
+    /// assert_eq!(code.len(), 8);
+    /// assert_eq!(code.dimension(), 4);
+    /// assert_eq!(code.minimal_distance(), Some(4));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length is not a power of 2 or if a frozen input is out of bound.
+    pub fn polar_code(length: usize, frozen_set: &[usize]) -> Self {
+        if !length.is_power_of_two() {
+            panic!("polar code length {} is not a power of 2", length);
+        }
+        if let Some(input) = frozen_set.iter().find(|input| **input >= length) {
+            panic!(
+                "frozen input {} is out of bound for length {}",
+                input, length
+            );
+        }
+        let frozen_set = frozen_set.iter().cloned().sorted().dedup().collect_vec();
+        // The row i of the transform has ones on the subsets of i
+        // and the column i has ones on the supersets of i.
+        let generators = (0..length)
+            .filter(|input| frozen_set.binary_search(input).is_err())
+            .map(|input| (0..length).filter(|bit| bit & input == *bit).collect())
+            .collect();
+        let checks = frozen_set
+            .iter()
+            .map(|input| (0..length).filter(|bit| bit & input == *input).collect())
+            .collect();
+        Self::from_both_matrices(
+            SparseBinMat::new(length, generators),
+            SparseBinMat::new(length, checks),
+        )
+    }
+
+    /// Returns the tensor product, also known as the product code, of two codes.
+    ///
+    /// The bits are arranged in an array with one row for each bit of the first code
+    /// and one column for each bit of the second code,
+    /// such that the bit in row `i` and column `j` has index `i * n2 + j`.
+    /// A codeword is an array where each row is a codeword of the second code
+    /// and each column is a codeword of the first code.
+    ///
+    /// The checks of each row, in order, come first
+    /// followed by the checks of the columns.
+    /// The check `k` of column `j` has index `num_row_checks + k * n2 + j`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::hamming_code().tensor_product(&LinearCode::repetition_code(3));
+    ///
+    /// assert_eq!(code.len(), 21);
+    /// assert_eq!(code.dimension(), 4);
+    /// assert_eq!(code.minimal_distance(), Some(9));
+    /// ```
+    pub fn tensor_product(&self, other: &Self) -> Self {
+        let (row_checks, column_checks) = self.tensor_product_checks(other);
+        Self::from_both_matrices(
+            self.generator_matrix.kron_with(&other.generator_matrix),
+            row_checks.vertical_concat_with(&column_checks),
+        )
+    }
+
+    // The checks of the rows and of the columns of the tensor product.
+    pub(crate) fn tensor_product_checks(&self, other: &Self) -> (SparseBinMat, SparseBinMat) {
+        (
+            SparseBinMat::identity(self.len()).kron_with(other.parity_check_matrix()),
+            self.parity_check_matrix
+                .kron_with(&SparseBinMat::identity(other.len())),
+        )
+    }
+
+    /// Returns the concatenation of this outer code with the inner code.
+    ///
+    /// The bits are split in blocks of the inner code length,
+    /// such that the bit `i` of block `b` has index `b * n + i`.
+    /// Each block is a codeword of the inner code encoding a message of `k` bits,
+    /// which are its bits outside the pivots of the
+    /// [`InformationSet`](crate::codes::InformationSet) of the inner checks in natural order,
+    /// and the messages of all blocks form a codeword of the outer code,
+    /// the message bit `j` of block `b` being the bit `b * k + j` of the outer code.
+    ///
+    /// The checks of each block, in order, come first
+    /// followed by the checks of the outer code on the message bits.
+    /// The distance is at least the product of the distances of both codes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the inner code has dimension 0 or if the outer code length
+    /// is not a multiple of the inner code dimension.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let inner = LinearCode::repetition_code(3);
+    /// let code = LinearCode::hamming_code().concatenation(&inner);
+    ///
+    /// assert_eq!(code.len(), 21);
+    /// assert_eq!(code.dimension(), 4);
+    /// assert_eq!(code.minimal_distance(), Some(9));
+    /// ```
+    pub fn concatenation(&self, inner: &Self) -> Self {
+        let (inner_checks, outer_checks) = self.concatenation_checks(inner);
+        Self::from_parity_check_matrix(inner_checks.vertical_concat_with(&outer_checks))
+    }
+
+    // The checks of the blocks and of the outer code of the concatenation.
+    pub(crate) fn concatenation_checks(&self, inner: &Self) -> (SparseBinMat, SparseBinMat) {
+        let messages = inner.message_positions();
+        if messages.is_empty() {
+            panic!("inner code has dimension 0");
+        }
+        if self.len() % messages.len() != 0 {
+            panic!(
+                "outer code length {} is not a multiple of inner code dimension {}",
+                self.len(),
+                messages.len()
+            );
+        }
+        let num_blocks = self.len() / messages.len();
+        let bit_of = |message_bit: usize| {
+            let (block, bit) = (message_bit / messages.len(), message_bit % messages.len());
+            block * inner.len() + messages[bit]
+        };
+        let outer_checks = self
+            .parity_check_matrix
+            .rows()
+            .map(|check| check.non_trivial_positions().map(bit_of).sorted().collect())
+            .collect();
+        (
+            SparseBinMat::identity(num_blocks).kron_with(inner.parity_check_matrix()),
+            SparseBinMat::new(num_blocks * inner.len(), outer_checks),
+        )
+    }
+
+    // The bits outside the pivots of the checks in natural order,
+    // which carry the message of a codeword.
+    pub(crate) fn message_positions(&self) -> Vec<usize> {
+        let columns = (0..self.len()).collect::<Vec<_>>();
+        let information_set = InformationSet::new(&self.parity_check_matrix, &columns);
+        let mut is_pivot = vec![false; self.len()];
+        for pivot in information_set.pivots() {
+            is_pivot[*pivot] = true;
+        }
+        columns.into_iter().filter(|bit| !is_pivot[*bit]).collect()
     }
 
     /// Returns a code of length 0 encoding 0 bits and without checks.
@@ -213,7 +522,7 @@ impl LinearCode {
     ///
     /// That is, this returns the row of the parity check matrix
     /// with the given index.
-    pub fn check(&self, index: usize) -> Option<SparseBinSlice> {
+    pub fn check(&self, index: usize) -> Option<SparseBinSlice<'_>> {
         self.parity_check_matrix.row(index)
     }
 
@@ -227,7 +536,7 @@ impl LinearCode {
     ///
     /// That is, this returns the row of the generator matrix
     /// with the given index.
-    pub fn generator(&self, index: usize) -> Option<SparseBinSlice> {
+    pub fn generator(&self, index: usize) -> Option<SparseBinSlice<'_>> {
         self.generator_matrix.row(index)
     }
 
@@ -239,7 +548,7 @@ impl LinearCode {
 
     /// Returns the checks adjacents to the given bit or
     /// None if the bit is out of bound.
-    pub fn checks_adjacent_to_bit(&self, bit: usize) -> Option<SparseBinSlice> {
+    pub fn checks_adjacent_to_bit(&self, bit: usize) -> Option<SparseBinSlice<'_>> {
         self.bit_adjacencies.row(bit)
     }
 
@@ -279,6 +588,11 @@ impl LinearCode {
         self.parity_check_matrix.number_of_columns()
     }
 
+    /// Checks if the code has no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Returns the number of rows of the parity check matrix
     /// of the code.
     pub fn num_checks(&self) -> usize {
@@ -293,6 +607,9 @@ impl LinearCode {
 
     /// Returns the number of linearly independent codewords.
     ///
+    /// For parity check matrices too large to build a code,
+    /// use [`approximate_dimension_of`](Self::approximate_dimension_of) instead.
+    ///
     /// # Example
     ///
     /// ```
@@ -310,32 +627,571 @@ impl LinearCode {
         self.generator_matrix.rank()
     }
 
+    /// Returns the dimension of the code with the given parity check matrix
+    /// with high probability.
+    ///
+    /// This is the length minus the [`approximate_rank`](crate::codes::approximate_rank)
+    /// of the checks.
+    /// Unlike [`dimension`](Self::dimension), this doesn't require the generator matrix
+    /// computed by elimination when a code is created,
+    /// which makes it practical for sparse matrices too large to build a code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use rand::thread_rng;
+    /// use sparse_bin_mat::SparseBinMat;
+    ///
+    /// // A repetition code with a redundant check.
+    /// let parity_check_matrix = SparseBinMat::new(3, vec![vec![0, 1], vec![1, 2], vec![0, 2]]);
+    /// let dimension =
+    ///     LinearCode::approximate_dimension_of(&parity_check_matrix, &mut thread_rng());
+    /// assert_eq!(dimension, 1);
+    /// ```
+    pub fn approximate_dimension_of<R: Rng>(
+        parity_check_matrix: &SparseBinMat,
+        rng: &mut R,
+    ) -> usize {
+        parity_check_matrix.number_of_columns() - approximate_rank(parity_check_matrix, rng)
+    }
+
+    /// Returns the dimension of the code divided by its length
+    /// or 0 if the code has no bit.
+    ///
+    /// The dimension is the rank of the generator matrix,
+    /// such that redundant checks don't lower the rate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use sparse_bin_mat::SparseBinMat;
+    ///
+    /// assert_eq!(LinearCode::hamming_code().rate(), 4.0 / 7.0);
+    ///
+    /// // A repetition code with a redundant check.
+    /// let parity_check_matrix = SparseBinMat::new(3, vec![vec![0, 1], vec![1, 2], vec![0, 2]]);
+    /// let code = LinearCode::from_parity_check_matrix(parity_check_matrix);
+    /// assert_eq!(code.rate(), 1.0 / 3.0);
+    /// ```
+    pub fn rate(&self) -> f64 {
+        if self.is_empty() {
+            0.0
+        } else {
+            self.dimension() as f64 / self.len() as f64
+        }
+    }
+
+    /// Returns the distance of the code if it is known from its construction.
+    ///
+    /// This is the case for the standard codes such as the
+    /// [`hamming_code`](Self::hamming_code) or the
+    /// [`extended_golay_code`](Self::extended_golay_code),
+    /// but not for codes built from arbitrary matrices.
+    /// The known distance is not kept when the code is serialized.
+    pub fn known_distance(&self) -> Option<usize> {
+        self.known_distance
+    }
+
+    /// Returns the length, the dimension and the distance of the code.
+    ///
+    /// The distance is the [known distance](Self::known_distance) if any.
+    /// Otherwise, it is computed with [`minimal_distance`](Self::minimal_distance)
+    /// and is None if the code has no codeword.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::extended_golay_code();
+    /// assert_eq!(code.parameters(), (24, 12, Some(8)));
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// Computing an unknown distance scales exponentially with the dimension of the code.
+    pub fn parameters(&self) -> (usize, usize, Option<usize>) {
+        let distance = self.known_distance.or_else(|| self.minimal_distance());
+        (self.len(), self.dimension(), distance)
+    }
+
+    fn with_known_distance(mut self, distance: usize) -> Self {
+        self.known_distance = Some(distance);
+        self
+    }
+
+    /// Returns the seed of the random number generator that sampled the code
+    /// if it was sampled from a seed.
+    ///
+    /// See [`RandomRegularCode::sample`](RandomRegularCode::sample).
+    pub fn seed(&self) -> Option<u64> {
+        self.provenance.as_ref().map(Provenance::seed)
+    }
+
+    /// Returns how the code was sampled if it was sampled from a seed.
+    ///
+    /// The provenance is kept when the code is serialized.
+    /// Codes built otherwise, including codes derived from a sampled code,
+    /// have no provenance.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
+    /// Returns a stable fingerprint of the parity check matrix.
+    ///
+    /// See [`Fingerprint`](crate::codes::Fingerprint) for the canonicalization of the checks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::hamming_code();
+    ///
+    /// assert_eq!(code.fingerprint(), LinearCode::hamming_code().fingerprint());
+    /// assert_ne!(code.fingerprint(), LinearCode::repetition_code(7).fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> Fingerprint {
+        let mut hasher = FingerprintHasher::new("LinearCode");
+        hasher.write_checks(&self.parity_check_matrix);
+        hasher.finish()
+    }
+
+    /// Returns the degree distributions of the bits and the checks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let profile = LinearCode::repetition_code(4).degree_profile();
+    ///
+    /// assert_eq!(profile.bits.counts(), &[0, 2, 2]);
+    /// assert_eq!(profile.bits.mean(), Some(1.5));
+    /// assert_eq!(profile.checks.min(), Some(2));
+    /// assert_eq!(profile.checks.max(), Some(2));
+    /// ```
+    pub fn degree_profile(&self) -> DegreeProfile {
+        DegreeProfile::new(self)
+    }
+
+    /// Checks if all bits have the same degree and all checks have the same degree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use sparse_bin_mat::SparseBinMat;
+    ///
+    /// let matrix = SparseBinMat::new(4, vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![0, 3]]);
+    /// let code = LinearCode::from_parity_check_matrix(matrix);
+    ///
+    /// assert!(code.is_regular());
+    /// assert!(!LinearCode::repetition_code(4).is_regular());
+    /// ```
+    pub fn is_regular(&self) -> bool {
+        self.degree_profile().is_regular()
+    }
+
+    /// Returns the subcode where the given bits are always 0.
+    ///
+    /// The frozen bits stay in the code and are enforced by additional
+    /// checks of weight 1 appended to the parity check matrix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::hamming_code();
+    /// let expurgated = code.freeze_bits(&[0, 6]);
+    ///
+    /// assert_eq!(expurgated.expurgated_bits(), &[0, 6]);
+    /// assert_eq!(expurgated.code().len(), 7);
+    /// assert_eq!(expurgated.code().dimension(), 2);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if a bit is out of bound.
+    pub fn freeze_bits(&self, bits: &[usize]) -> ExpurgatedCode {
+        ExpurgatedCode::with_frozen_bits(self, bits)
+    }
+
+    /// Returns the code obtained by keeping only the codewords that are 0
+    /// on the given bits and then removing these bits.
+    ///
+    /// The remaining bits are relabeled in increasing order.
+    /// The returned code keeps track of the original positions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::hamming_code();
+    /// let expurgated = code.remove_bits(&[0, 6]);
+    ///
+    /// assert_eq!(expurgated.code().len(), 5);
+    /// assert_eq!(expurgated.code().dimension(), 2);
+    /// assert_eq!(expurgated.original_position(0), Some(1));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if a bit is out of bound.
+    pub fn remove_bits(&self, bits: &[usize]) -> ExpurgatedCode {
+        ExpurgatedCode::with_removed_bits(self, bits)
+    }
+
+    /// Returns a code where each check of weight above the limit is split
+    /// into a chain of checks of weight at most the limit using auxiliary bits.
+    ///
+    /// The auxiliary bits are appended after the bits of the code
+    /// and the returned code keeps track of the chain of each original check.
+    /// This preserves the dimension of the code
+    /// and the codewords restricted to the original bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::hamming_code();
+    /// let split = code.split_checks(3);
+    ///
+    /// assert_eq!(split.code().len(), 10);
+    /// assert_eq!(split.code().num_checks(), 6);
+    /// assert_eq!(split.code().dimension(), 4);
+    /// assert_eq!(split.chain_of(0), Some(&[0, 1][..]));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the maximum weight is less than 3.
+    pub fn split_checks(&self, max_weight: usize) -> SplitCode {
+        SplitCode::with_max_weight(self, max_weight)
+    }
+
+    /// Returns the given number of largest eigenvalues of the adjacency matrix
+    /// of the Tanner graph of the code together with expansion estimates.
+    ///
+    /// The eigenvalues are approximated by at most `3k + 30` steps of the Lanczos algorithm
+    /// for `k` eigenvalues, which is exact when the code has fewer checks or bits than that.
+    /// The largest eigenvalues converge first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let spectrum = LinearCode::hamming_code().tanner_spectrum(2);
+    ///
+    /// assert!((spectrum.largest().unwrap() - 8.0_f64.sqrt()).abs() < 1e-6);
+    /// assert!((spectrum.second_largest().unwrap() - 2.0_f64.sqrt()).abs() < 1e-6);
+    /// assert!(spectrum.is_ramanujan());
+    /// ```
+    pub fn tanner_spectrum(&self, num_eigenvalues: usize) -> TannerSpectrum {
+        TannerSpectrum::lanczos(self, num_eigenvalues)
+    }
+
+    /// Returns the number of cycles of length 4 in the Tanner graph of the code.
+    ///
+    /// There is such a cycle for each pair of bits shared by two checks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// assert_eq!(LinearCode::hamming_code().num_four_cycles(), 3);
+    /// assert_eq!(LinearCode::repetition_code(5).num_four_cycles(), 0);
+    /// ```
+    pub fn num_four_cycles(&self) -> usize {
+        TannerGraph::from_code(self).num_four_cycles()
+    }
+
+    /// Returns the length of the shortest cycle in the Tanner graph of the code
+    /// or None if the graph has no cycle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// assert_eq!(LinearCode::hamming_code().girth(), Some(4));
+    /// assert_eq!(LinearCode::repetition_code(5).girth(), None);
+    /// ```
+    pub fn girth(&self) -> Option<usize> {
+        TannerGraph::from_code(self).girth()
+    }
+
+    /// Returns a code with the same degree profile and fewer cycles
+    /// of length 4 in its Tanner graph.
+    ///
+    /// Each attempt swaps the bits of a random edge in a 4-cycle and of another random edge.
+    /// That is, edges (c1, b1) and (c2, b2) are replaced by (c1, b2) and (c2, b1).
+    /// A swap is only kept if it reduces the number of 4-cycles.
+    /// This stops when there is no 4-cycle left or when all attempts are used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use rand::thread_rng;
+    ///
+    /// let code = LinearCode::random_regular_code()
+    ///     .num_bits(60)
+    ///     .num_checks(45)
+    ///     .bit_degree(3)
+    ///     .check_degree(4)
+    ///     .sample_with(&mut thread_rng())
+    ///     .unwrap();
+    /// let new_code = code.remove_short_cycles(1000, &mut thread_rng());
+    ///
+    /// assert!(new_code.num_four_cycles() <= code.num_four_cycles());
+    /// assert_eq!(new_code.degree_profile(), code.degree_profile());
+    /// ```
+    pub fn remove_short_cycles<R: Rng>(&self, max_attempts: usize, rng: &mut R) -> Self {
+        let mut graph = TannerGraph::from_code(self);
+        graph.remove_four_cycles(max_attempts, rng);
+        Self::from_parity_check_matrix(graph.into_parity_check_matrix())
+    }
+
+    /// Returns a code with the same codespace and a different basis of checks.
+    ///
+    /// As many random row operations as there are checks are applied,
+    /// each adding a check to another one, and the checks are then shuffled.
+    /// The generator matrix and the known distance are kept.
+    /// Belief propagation depends on the parity check matrix
+    /// and not only on the codespace, such that averaging over randomized codes
+    /// quantifies the effect of the representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use rand::thread_rng;
+    ///
+    /// let code = LinearCode::hamming_code();
+    /// let randomized = code.randomize_equivalent(&mut thread_rng());
+    ///
+    /// assert!(randomized.has_same_codespace(&code));
+    /// assert_eq!(randomized.num_checks(), code.num_checks());
+    /// ```
+    pub fn randomize_equivalent<R: Rng>(&self, rng: &mut R) -> Self {
+        let mut checks = self
+            .parity_check_matrix
+            .rows()
+            .map(|check| check.to_vec())
+            .collect::<Vec<_>>();
+        if checks.len() > 1 {
+            for _ in 0..checks.len() {
+                let target = rng.gen_range(0..checks.len());
+                let source = (target + rng.gen_range(1..checks.len())) % checks.len();
+                checks[target] = &checks[target] + &checks[source];
+            }
+        }
+        checks.shuffle(rng);
+        let parity_check_matrix = SparseBinMat::new(
+            self.len(),
+            checks
+                .into_iter()
+                .map(|check| check.to_positions_vec())
+                .collect(),
+        );
+        Self {
+            bit_adjacencies: parity_check_matrix.transposed(),
+            parity_check_matrix,
+            generator_matrix: self.generator_matrix.clone(),
+            known_distance: self.known_distance,
+            provenance: None,
+        }
+    }
+
+    /// Returns the largest distance between two bits of the same check.
+    ///
+    /// A small bandwidth means that the bits of each check are close in memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// assert_eq!(LinearCode::repetition_code(5).bandwidth(), 1);
+    /// assert_eq!(LinearCode::hamming_code().bandwidth(), 6);
+    /// ```
+    pub fn bandwidth(&self) -> usize {
+        self.parity_check_matrix
+            .rows()
+            .filter_map(|check| {
+                let positions = check.as_slice();
+                Some(positions.last()? - positions.first()?)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns an equivalent code with bits and checks relabeled
+    /// to improve the memory locality of decoders, together with the permutations.
+    ///
+    /// The labels follow a Cuthill-McKee breadth first search of the Tanner graph
+    /// such that the bits of a check and the checks of a bit have close labels.
+    /// This reduces the cache misses of the message arrays of belief propagation
+    /// for large codes whose checks are scattered over the bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+    ///
+    /// // A repetition code with scattered bits.
+    /// let code = LinearCode::from_parity_check_matrix(SparseBinMat::new(
+    ///     5,
+    ///     vec![vec![0, 3], vec![1, 3], vec![1, 4], vec![2, 4]],
+    /// ));
+    /// let reordered = code.reordered_for_decoding();
+    /// assert_eq!(code.bandwidth(), 3);
+    /// assert_eq!(reordered.code().bandwidth(), 1);
+    ///
+    /// // Syndromes are mapped to the reordered code
+    /// // and corrections are mapped back.
+    /// let error = SparseBinVec::new(5, vec![1]);
+    /// let syndrome = reordered.reorder_checks(&code.syndrome_of(&error));
+    /// let correction = reordered.reorder_bits(&error);
+    /// assert_eq!(reordered.code().syndrome_of(&correction), syndrome);
+    /// assert_eq!(reordered.restore_bits(&correction), error);
+    /// ```
+    pub fn reordered_for_decoding(&self) -> ReorderedCode {
+        ReorderedCode::cuthill_mckee(self)
+    }
+
     /// Returns the weight of the smallest non trivial codeword
     /// or None if the code have no codeword.
     ///
+    /// The codewords are split into chunks enumerated in parallel.
+    ///
     /// # Warning
     ///
     /// The execution time of this method scale exponentially with the
     /// dimension of the code.
     pub fn minimal_distance(&self) -> Option<usize> {
-        (1..=self.num_generators())
-            .flat_map(|n| self.generator_matrix.rows().combinations(n))
-            .filter_map(|generators| {
-                let weight = generators
-                    .into_iter()
-                    .fold(SparseBinVec::zeros(self.len()), |sum, generator| {
-                        &sum + &generator
-                    })
-                    .weight();
-                if weight > 0 {
-                    Some(weight)
-                } else {
-                    None
+        let generators = self
+            .generator_matrix
+            .rows()
+            .map(|generator| DenseCodeword::from(&generator))
+            .collect::<Vec<_>>();
+        // Each chunk is a fixed sum of the last generators
+        // to which are added all sums of the other generators.
+        let num_fixed = generators.len().min(MAX_FIXED_GENERATORS);
+        let num_free = generators.len() - num_fixed;
+        (0_usize..1 << num_fixed)
+            .into_par_iter()
+            .filter_map(|chunk| {
+                let mut codeword = DenseCodeword::zeros(self.len());
+                for generator in (0..num_fixed).filter(|generator| chunk >> generator & 1 == 1) {
+                    codeword += &generators[num_free + generator];
                 }
+                let first_weight = codeword.weight();
+                // Visits every codeword of the chunk by adding a single generator at a time
+                // in Gray code order.
+                std::iter::once(first_weight)
+                    .chain((1_usize..1 << num_free).map(|step| {
+                        codeword += &generators[step.trailing_zeros() as usize];
+                        codeword.weight()
+                    }))
+                    .filter(|weight| *weight > 0)
+                    .min()
             })
             .min()
     }
 
+    /// Returns the weight of the smallest non trivial codeword
+    /// or None if the code have no codeword.
+    ///
+    /// The codeword is found by solving an integer program
+    /// which is usually much faster than [`minimal_distance`](Self::minimal_distance)
+    /// for mid-sized codes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::hamming_code();
+    /// assert_eq!(code.minimal_distance_ilp().unwrap(), Some(3));
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// This requires the `ilp` feature and returns an error
+    /// if the solver fails.
+    #[cfg(feature = "ilp")]
+    pub fn minimal_distance_ilp(&self) -> Result<Option<usize>, good_lp::ResolutionError> {
+        crate::codes::ilp::minimum_weight_kernel_vector(self.parity_check_matrix(), None)
+            .map(|codeword| codeword.map(|codeword| codeword.weight()))
+    }
+
+    /// Returns the weight of the smallest non trivial codeword
+    /// or None if the code have no codeword.
+    ///
+    /// The codeword is found by a SAT solver bisecting the weight
+    /// which is an alternative to [`minimal_distance_ilp`](Self::minimal_distance_ilp)
+    /// that doesn't need a native solver.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::hamming_code();
+    /// assert_eq!(code.minimal_distance_sat().unwrap(), Some(3));
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// This requires the `sat` feature and returns an error
+    /// if the solver fails.
+    #[cfg(feature = "sat")]
+    pub fn minimal_distance_sat(&self) -> Result<Option<usize>, varisat::solver::SolverError> {
+        crate::codes::sat::minimum_weight_kernel_vector(self.parity_check_matrix(), None)
+            .map(|codeword| codeword.map(|codeword| codeword.weight()))
+    }
+
+    /// Returns the weight of the smallest non trivial codeword found
+    /// by information set decoding or None if no codeword is found.
+    ///
+    /// This is an upper bound on the distance that is usually tight
+    /// for codes far too large for [`minimal_distance`](Self::minimal_distance)
+    /// given enough iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::decoders::IsdAlgorithm;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let code = LinearCode::hamming_code().tensor_product(&LinearCode::hamming_code());
+    /// let algorithm = IsdAlgorithm::Stern { weight: 1, window: 8 };
+    /// let mut rng = StdRng::seed_from_u64(123);
+    /// assert_eq!(code.minimal_distance_isd(algorithm, 100, &mut rng), Some(9));
+    /// ```
+    pub fn minimal_distance_isd<R: Rng>(
+        &self,
+        algorithm: IsdAlgorithm,
+        num_iterations: usize,
+        rng: &mut R,
+    ) -> Option<usize> {
+        let syndrome = SparseBinVec::zeros(self.num_checks());
+        lowest_weight_solution(
+            self.parity_check_matrix(),
+            syndrome.as_view(),
+            algorithm,
+            num_iterations,
+            TieBreaking::FirstFound,
+            rng,
+        )
+        .map(|codeword| codeword.weight())
+    }
+
     /// Returns an iterator over all edges of the Tanner graph associated with
     /// the parity check matrix of the code.
     ///
@@ -363,7 +1219,7 @@ impl LinearCode {
     /// assert_eq!(edges.next(), Some(Edge { bit: 2, check: 2}));
     /// assert_eq!(edges.next(), None);
     /// ```
-    pub fn edges(&self) -> Edges {
+    pub fn edges(&self) -> Edges<'_> {
         Edges::new(self)
     }
 
@@ -404,6 +1260,47 @@ impl LinearCode {
         &self.parity_check_matrix * message
     }
 
+    /// Returns the syndrome of each message.
+    ///
+    /// The messages are processed by batches of 64
+    /// such that the parity check matrix is visited once per batch.
+    /// This is faster than calling [`syndrome_of`](Self::syndrome_of)
+    /// for each message when sampling many errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use sparse_bin_mat::SparseBinVec;
+    ///
+    /// let code = LinearCode::hamming_code();
+    /// let messages = vec![
+    ///     SparseBinVec::new(7, vec![0]),
+    ///     SparseBinVec::new(7, vec![1, 2]),
+    /// ];
+    /// let syndromes = code.syndromes_of_batch(&messages);
+    ///
+    /// assert_eq!(syndromes[0], code.syndrome_of(&messages[0]));
+    /// assert_eq!(syndromes[1], code.syndrome_of(&messages[1]));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if a message have a different length then the code.
+    pub fn syndromes_of_batch(&self, messages: &[SparseBinVec]) -> Vec<SparseBinVec> {
+        syndromes_of_batch(&self.parity_check_matrix, messages)
+    }
+
+    /// Same as [`syndromes_of_batch`](Self::syndromes_of_batch),
+    /// but the batches are processed in parallel.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a message have a different length then the code.
+    pub fn par_syndromes_of_batch(&self, messages: &[SparseBinVec]) -> Vec<SparseBinVec> {
+        par_syndromes_of_batch(&self.parity_check_matrix, messages)
+    }
+
     /// Checks if a message has zero syndrome.
     ///
     /// # Example
@@ -435,6 +1332,171 @@ impl LinearCode {
         self.syndrome_of(operator).is_zero()
     }
 
+    /// Checks if the bits at the non trivial positions of an erasure can be recovered.
+    ///
+    /// That is, checks that no non trivial codeword is supported only on erased bits
+    /// or, equivalently, that the erased columns of the parity check matrix are
+    /// linearly independent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use sparse_bin_mat::SparseBinVec;
+    ///
+    /// let code = LinearCode::hamming_code();
+    ///
+    /// let erasure = SparseBinVec::new(7, vec![0, 1]);
+    /// assert!(code.can_recover_erasure(&erasure));
+    ///
+    /// // Support of the codeword 0011001.
+    /// let erasure = SparseBinVec::new(7, vec![2, 3, 6]);
+    /// assert!(!code.can_recover_erasure(&erasure));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the erasure have a different length then the code.
+    pub fn can_recover_erasure<T>(&self, erasure: &SparseBinVecBase<T>) -> bool
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if erasure.len() != self.len() {
+            panic!(
+                "erasure of length {} is invalid for code with length {}",
+                erasure.len(),
+                self.len()
+            );
+        }
+        let erased_columns: Vec<Vec<usize>> = erasure
+            .non_trivial_positions()
+            .map(|bit| self.bit_adjacencies.row(bit).unwrap().as_slice().to_vec())
+            .collect();
+        // A bit without checks is a codeword by itself.
+        // This is also required since empty rows are counted in the rank.
+        erased_columns.iter().all(|checks| !checks.is_empty())
+            && SparseBinMat::new(self.num_checks(), erased_columns).rank() == erasure.weight()
+    }
+
+    /// Checks if the values of the given logical observables can be recovered
+    /// when the bits at the non trivial positions of an erasure are lost.
+    ///
+    /// That is, checks that every codeword supported only on erased bits
+    /// has a zero dot product with each row of the logical matrix.
+    /// This is weaker than [`can_recover_erasure`](Self::can_recover_erasure)
+    /// when only some information bits matter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+    ///
+    /// let code = LinearCode::hamming_code();
+    ///
+    /// // Support of the codeword 0011001.
+    /// let erasure = SparseBinVec::new(7, vec![2, 3, 6]);
+    /// let first_bit = SparseBinMat::new(7, vec![vec![0]]);
+    /// let third_bit = SparseBinMat::new(7, vec![vec![2]]);
+    /// assert!(code.can_recover_logicals_of_erasure(&erasure, &first_bit));
+    /// assert!(!code.can_recover_logicals_of_erasure(&erasure, &third_bit));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the erasure or the logicals have a different length then the code.
+    pub fn can_recover_logicals_of_erasure<T>(
+        &self,
+        erasure: &SparseBinVecBase<T>,
+        logicals: &SparseBinMat,
+    ) -> bool
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if erasure.len() != self.len() || logicals.number_of_columns() != self.len() {
+            panic!(
+                "erasure of length {} and logicals of length {} are invalid for code with length {}",
+                erasure.len(),
+                logicals.number_of_columns(),
+                self.len()
+            );
+        }
+        let logical_columns = logicals.transposed();
+        let width = self.num_checks() + logicals.number_of_rows();
+        // Empty rows are skipped since they are counted in the rank.
+        let rank = |with_logicals: bool| {
+            let rows = erasure
+                .non_trivial_positions()
+                .map(|bit| {
+                    let checks = self.bit_adjacencies.row(bit).unwrap();
+                    let logicals = logical_columns
+                        .row(bit)
+                        .unwrap()
+                        .non_trivial_positions()
+                        .filter(|_| with_logicals)
+                        .map(|logical| self.num_checks() + logical)
+                        .collect::<Vec<_>>();
+                    checks
+                        .non_trivial_positions()
+                        .chain(logicals)
+                        .collect::<Vec<_>>()
+                })
+                .filter(|row| !row.is_empty())
+                .collect();
+            SparseBinMat::new(width, rows).rank()
+        };
+        // A codeword supported on the erasure is a dependency between the checks
+        // of the erased bits. It flips a logical if and only if it is no longer
+        // a dependency once the logicals are appended.
+        rank(true) == rank(false)
+    }
+
+    /// Checks that the decoder corrects every error of weight at most `max_weight`
+    /// or returns the first error that is not corrected.
+    ///
+    /// An error is corrected if the decoder returns exactly the error
+    /// as correction for its syndrome.
+    /// Errors are enumerated by increasing weight.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::decoders::BpDecoder;
+    /// use ldpc::noise::Probability;
+    ///
+    /// let code = LinearCode::repetition_code(3);
+    /// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+    ///
+    /// assert!(code.corrects_all_errors_up_to(1, &decoder).is_ok());
+    ///
+    /// let counterexample = code.corrects_all_errors_up_to(2, &decoder).unwrap_err();
+    /// assert_eq!(counterexample.weight(), 2);
+    /// ```
+    ///
+    /// # Warning
+    ///
+    /// The number of errors to decode scales exponentially with the maximum weight.
+    pub fn corrects_all_errors_up_to<D>(
+        &self,
+        max_weight: usize,
+        decoder: &D,
+    ) -> Result<(), SparseBinVec>
+    where
+        D: for<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>,
+    {
+        for error in (0..=max_weight)
+            .flat_map(|weight| (0..self.len()).combinations(weight))
+            .map(|positions| SparseBinVec::new(self.len(), positions))
+        {
+            let syndrome = self.syndrome_of(&error);
+            if decoder.correction_for(syndrome.as_view()) != error {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
     /// Generates a random error with the given noise model.
     ///
     /// # Example
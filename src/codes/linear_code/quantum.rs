@@ -0,0 +1,196 @@
+use super::LinearCode;
+use crate::codes::{ProductMatrix, QuasiCyclicMatrix};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A construction of a quantum CSS code from a single classical code.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum QuantumConstruction {
+    /// The CSS code whose X and Z stabilizers are both the parity checks.
+    ///
+    /// This requires the code to be [dual-containing](LinearCode::is_dual_containing).
+    DualContaining,
+    /// The [hypergraph product](crate::codes::CssCode::hypergraph_product)
+    /// of the code with itself.
+    HypergraphProduct,
+    /// The [lifted product](ProductMatrix::lifted_product) of the quasi-cyclic
+    /// parity check matrix with itself.
+    ///
+    /// This requires the parity check matrix to be made of circulant permutation blocks
+    /// of the lift factor.
+    LiftedProduct { lift_factor: usize },
+}
+
+impl fmt::Display for QuantumConstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DualContaining => write!(f, "CSS from dual-containing code"),
+            Self::HypergraphProduct => write!(f, "hypergraph product"),
+            Self::LiftedProduct { lift_factor } => {
+                write!(f, "lifted product with lift factor {}", lift_factor)
+            }
+        }
+    }
+}
+
+/// A quantum construction that applies to a classical code
+/// with the parameters of the resulting quantum code.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct QuantumCandidate {
+    pub construction: QuantumConstruction,
+    pub num_qubits: usize,
+    pub num_logical_qubits: usize,
+}
+
+impl fmt::Display for QuantumCandidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: [[{}, {}]]",
+            self.construction, self.num_qubits, self.num_logical_qubits
+        )
+    }
+}
+
+impl LinearCode {
+    /// Checks if the code contains its dual code,
+    /// that is if the parity checks are orthogonal to each other.
+    ///
+    /// The parity checks of a dual-containing code can be used
+    /// as both the X and Z stabilizers of a CSS code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// assert!(LinearCode::hamming_code().is_dual_containing());
+    /// assert!(!LinearCode::repetition_code(3).is_dual_containing());
+    /// ```
+    pub fn is_dual_containing(&self) -> bool {
+        let checks = self.parity_check_matrix();
+        (checks * &checks.transposed()).is_zero()
+    }
+
+    /// Returns the quantum constructions that apply to the code
+    /// with the parameters of the resulting codes.
+    ///
+    /// The hypergraph product always applies.
+    /// The CSS code from the parity checks applies if the code is
+    /// [dual-containing](Self::is_dual_containing)
+    /// and the lifted product applies if the parity check matrix is quasi-cyclic
+    /// for a lift factor larger than 1, in which case the largest lift factor is used.
+    /// Constructions encoding no logical qubit are included,
+    /// such that they can be told apart from constructions that don't apply.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::codes::{QuantumCandidate, QuantumConstruction};
+    ///
+    /// let candidates = LinearCode::hamming_code().quantum_candidates();
+    /// assert_eq!(
+    ///     candidates[0],
+    ///     QuantumCandidate {
+    ///         construction: QuantumConstruction::DualContaining,
+    ///         num_qubits: 7,
+    ///         num_logical_qubits: 1,
+    ///     }
+    /// );
+    /// assert_eq!(candidates[1].to_string(), "hypergraph product: [[58, 16]]");
+    /// ```
+    pub fn quantum_candidates(&self) -> Vec<QuantumCandidate> {
+        let checks = self.parity_check_matrix();
+        let (num_bits, num_checks) = (self.len(), self.num_checks());
+        let rank = checks.rank();
+        let mut candidates = Vec::new();
+        if self.is_dual_containing() {
+            candidates.push(QuantumCandidate {
+                construction: QuantumConstruction::DualContaining,
+                num_qubits: num_bits,
+                num_logical_qubits: num_bits - 2 * rank,
+            });
+        }
+        // The hypergraph product encodes the codewords of the code and of its transpose.
+        let (dimension, transposed_dimension) = (num_bits - rank, num_checks - rank);
+        candidates.push(QuantumCandidate {
+            construction: QuantumConstruction::HypergraphProduct,
+            num_qubits: num_bits * num_bits + num_checks * num_checks,
+            num_logical_qubits: dimension * dimension + transposed_dimension * transposed_dimension,
+        });
+        if let Some(matrix) = self.largest_quasi_cyclic_lift() {
+            let product = ProductMatrix::lifted_product(&matrix, &matrix);
+            let x_checks = product.x.to_sparse_bin_mat();
+            let z_checks = product.z.to_sparse_bin_mat();
+            let num_qubits = x_checks.number_of_columns();
+            candidates.push(QuantumCandidate {
+                construction: QuantumConstruction::LiftedProduct {
+                    lift_factor: matrix.lift_factor(),
+                },
+                num_qubits,
+                num_logical_qubits: num_qubits - x_checks.rank() - z_checks.rank(),
+            });
+        }
+        candidates
+    }
+
+    fn largest_quasi_cyclic_lift(&self) -> Option<QuasiCyclicMatrix> {
+        let checks = self.parity_check_matrix();
+        let bound = gcd(checks.number_of_rows(), checks.number_of_columns());
+        (2..=bound)
+            .rev()
+            .find_map(|lift_factor| QuasiCyclicMatrix::from_matrix(checks, lift_factor))
+    }
+}
+
+fn gcd(first: usize, second: usize) -> usize {
+    if second == 0 {
+        first
+    } else {
+        gcd(second, first % second)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::CssCode;
+    use sparse_bin_mat::SparseBinMat;
+
+    #[test]
+    fn parameters_match_constructed_codes() {
+        let code = LinearCode::repetition_code(3);
+        let candidates = code.quantum_candidates();
+        assert_eq!(candidates.len(), 1);
+        let product = CssCode::hypergraph_product(&code, &code);
+        assert_eq!(candidates[0].num_qubits, product.len());
+        assert_eq!(
+            candidates[0].num_logical_qubits,
+            product.num_logical_qubits()
+        );
+    }
+
+    #[test]
+    fn lifted_product_applies_to_quasi_cyclic_checks() {
+        let matrix = QuasiCyclicMatrix::new(3, vec![vec![Some(0), Some(1), Some(2)]]);
+        let code = LinearCode::from_parity_check_matrix(matrix.expanded());
+        let candidate = code.quantum_candidates().pop().unwrap();
+        assert_eq!(
+            candidate.construction,
+            QuantumConstruction::LiftedProduct { lift_factor: 3 }
+        );
+        let checks = ProductMatrix::lifted_product(&matrix, &matrix)
+            .map(|checks| LinearCode::from_parity_check_matrix(checks.to_sparse_bin_mat()));
+        let product = CssCode::new(&checks.x, &checks.z);
+        assert_eq!(candidate.num_qubits, 18);
+        assert_eq!(candidate.num_logical_qubits, product.num_logical_qubits());
+    }
+
+    #[test]
+    fn dual_containing_requires_orthogonal_checks() {
+        let checks = SparseBinMat::new(4, vec![vec![0, 1, 2, 3], vec![0, 1]]);
+        assert!(LinearCode::from_parity_check_matrix(checks).is_dual_containing());
+        let checks = SparseBinMat::new(4, vec![vec![0, 1, 2], vec![0, 1]]);
+        assert!(!LinearCode::from_parity_check_matrix(checks).is_dual_containing());
+    }
+}
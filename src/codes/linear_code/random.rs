@@ -1,7 +1,9 @@
 use super::{LinearCode, SparseBinMat};
+use crate::codes::Provenance;
 use bigs::{error::InvalidParameters, graph::Graph, Sampler};
 use itertools::Itertools;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::error::Error;
 use std::fmt;
 
@@ -14,6 +16,7 @@ pub struct RandomRegularCode {
     num_checks: usize,
     bit_degree: usize,
     check_degree: usize,
+    seed: Option<u64>,
 }
 
 impl RandomRegularCode {
@@ -49,6 +52,79 @@ impl RandomRegularCode {
         self
     }
 
+    /// Fixes the seed of the random number generator used by [`sample`](Self::sample).
+    ///
+    /// Default is a random seed.
+    pub fn seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Samples a random code from the seed
+    /// or returns an error if the `n * b != m * c` where
+    /// `n` is the number of bits, `b` the bit's degree, `m` the number of checks
+    /// and `c` the check's degree.
+    ///
+    /// The seed is recorded in the [provenance](LinearCode::provenance) of the code
+    /// together with the parameters of the sampler,
+    /// such that the code can be sampled again from its serialization.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::random_regular_code()
+    ///     .num_bits(20)
+    ///     .num_checks(15)
+    ///     .bit_degree(3)
+    ///     .check_degree(4)
+    ///     .seed(123)
+    ///     .sample()
+    ///     .unwrap();
+    /// assert_eq!(code.seed(), Some(123));
+    ///
+    /// let same_code = LinearCode::random_regular_code()
+    ///     .num_bits(20)
+    ///     .num_checks(15)
+    ///     .bit_degree(3)
+    ///     .check_degree(4)
+    ///     .seed(code.seed().unwrap())
+    ///     .sample()
+    ///     .unwrap();
+    /// assert_eq!(same_code, code);
+    /// ```
+    pub fn sample(&self) -> Result<LinearCode, SamplingError> {
+        let seed = self.seed.unwrap_or_else(|| rand::thread_rng().gen());
+        self.sample_with(&mut StdRng::seed_from_u64(seed))
+            .map(|code| code.with_provenance(self.provenance(seed)))
+    }
+
+    /// Returns the sampler of a code with the given provenance
+    /// or None if it was not sampled by a random regular code sampler.
+    ///
+    /// The seed is fixed to the seed of the provenance.
+    /// See [`Provenance`] for an example.
+    pub fn from_provenance(provenance: &Provenance) -> Option<Self> {
+        if provenance.sampler() != SAMPLER {
+            return None;
+        }
+        Some(Self {
+            num_bits: provenance.parameter("num_bits")?,
+            num_checks: provenance.parameter("num_checks")?,
+            bit_degree: provenance.parameter("bit_degree")?,
+            check_degree: provenance.parameter("check_degree")?,
+            seed: Some(provenance.seed()),
+        })
+    }
+
+    fn provenance(&self, seed: u64) -> Provenance {
+        Provenance::new(SAMPLER, seed)
+            .with_parameter("num_bits", self.num_bits)
+            .with_parameter("num_checks", self.num_checks)
+            .with_parameter("bit_degree", self.bit_degree)
+            .with_parameter("check_degree", self.check_degree)
+    }
+
     /// Samples a random code with the given random number generator
     /// or returns an error if the `n * b != m * c` where
     /// `n` is the number of bits, `b` the bit's degree, `m` the number of checks
@@ -65,6 +141,9 @@ impl RandomRegularCode {
     }
 }
 
+// The name of the sampler in the provenance of the codes.
+const SAMPLER: &str = "random regular code";
+
 fn convert_graph_into_code(graph: Graph) -> LinearCode {
     let checks = graph
         .constraints()
@@ -75,6 +154,7 @@ fn convert_graph_into_code(graph: Graph) -> LinearCode {
     LinearCode::from_parity_check_matrix(parity_check_matrix)
 }
 
+/// An error when the degrees of a random regular code are incompatible.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct SamplingError {
     num_bits: usize,
@@ -105,3 +185,164 @@ impl fmt::Display for SamplingError {
 }
 
 impl Error for SamplingError {}
+
+impl LinearCode {
+    /// Samples a code whose bits have the given degree with the progressive edge growth
+    /// algorithm.
+    ///
+    /// The edges of each bit are added one at a time toward a check
+    /// as far as possible from the bit in the current Tanner graph,
+    /// which makes the shortest cycles long,
+    /// and ties are broken by the smallest check degree, then randomly.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the bit degree is larger than the number of checks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use rand::thread_rng;
+    ///
+    /// let code = LinearCode::progressive_edge_growth(40, 20, 3, &mut thread_rng());
+    ///
+    /// assert_eq!(code.parity_check_matrix().number_of_ones(), 120);
+    /// assert!(code.girth().unwrap() >= 6);
+    /// ```
+    pub fn progressive_edge_growth<R: Rng>(
+        num_bits: usize,
+        num_checks: usize,
+        bit_degree: usize,
+        rng: &mut R,
+    ) -> Self {
+        if bit_degree > num_checks {
+            panic!(
+                "bit degree {} is larger than the {} checks",
+                bit_degree, num_checks
+            );
+        }
+        let mut checks = vec![Vec::new(); num_checks];
+        let mut bits = vec![Vec::new(); num_bits];
+        for bit in 0..num_bits {
+            for _ in 0..bit_degree {
+                let candidates = farthest_checks(&checks, &bits, bit);
+                let min_degree = candidates
+                    .iter()
+                    .map(|check| checks[*check].len())
+                    .min()
+                    .unwrap();
+                let candidates = candidates
+                    .into_iter()
+                    .filter(|check| checks[*check].len() == min_degree)
+                    .collect::<Vec<_>>();
+                let check = candidates[rng.gen_range(0..candidates.len())];
+                checks[check].push(bit);
+                bits[bit].push(check);
+            }
+        }
+        LinearCode::from_parity_check_matrix(SparseBinMat::new(num_bits, checks))
+    }
+}
+
+// The checks not reached by a breadth first search from the bit
+// or, if every check is reached, the last checks reached.
+fn farthest_checks(checks: &[Vec<usize>], bits: &[Vec<usize>], bit: usize) -> Vec<usize> {
+    let mut is_reached = vec![false; checks.len()];
+    let mut is_visited = vec![false; bits.len()];
+    is_visited[bit] = true;
+    let mut frontier = vec![bit];
+    let mut num_reached = 0;
+    loop {
+        let mut reached = Vec::new();
+        for bit in frontier.iter() {
+            for check in bits[*bit].iter() {
+                if !is_reached[*check] {
+                    is_reached[*check] = true;
+                    reached.push(*check);
+                }
+            }
+        }
+        if reached.is_empty() {
+            return (0..checks.len())
+                .filter(|check| !is_reached[*check])
+                .collect();
+        }
+        num_reached += reached.len();
+        if num_reached == checks.len() {
+            return reached;
+        }
+        frontier = reached
+            .iter()
+            .flat_map(|check| checks[*check].iter())
+            .filter(|bit| !is_visited[**bit])
+            .cloned()
+            .collect();
+        frontier.sort_unstable();
+        frontier.dedup();
+        for bit in frontier.iter() {
+            is_visited[*bit] = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sampler() -> RandomRegularCode {
+        let mut sampler = LinearCode::random_regular_code();
+        sampler
+            .num_bits(12)
+            .num_checks(9)
+            .bit_degree(3)
+            .check_degree(4);
+        sampler
+    }
+
+    #[test]
+    fn seed_is_recorded_in_serialized_code() {
+        let code = sampler().seed(7).sample().unwrap();
+        let json = code.as_json().unwrap();
+        let deserialized: LinearCode = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.seed(), Some(7));
+        assert_eq!(
+            sampler().seed(7).sample().unwrap(),
+            sampler()
+                .sample_with(&mut StdRng::seed_from_u64(7))
+                .unwrap()
+                .with_provenance(sampler().provenance(7))
+        );
+    }
+
+    #[test]
+    fn provenance_doesnt_change_code_identity() {
+        let code = sampler().seed(7).sample().unwrap();
+        let same_code = sampler()
+            .sample_with(&mut StdRng::seed_from_u64(7))
+            .unwrap();
+        assert_eq!(same_code.provenance(), None);
+        assert_eq!(code, same_code);
+    }
+
+    #[test]
+    fn codes_of_other_samplers_are_not_resampled() {
+        let provenance = Provenance::new("other sampler", 7);
+        assert_eq!(RandomRegularCode::from_provenance(&provenance), None);
+        let provenance = sampler().provenance(7);
+        assert_eq!(
+            RandomRegularCode::from_provenance(&provenance),
+            Some(*sampler().seed(7))
+        );
+    }
+
+    #[test]
+    fn random_seed_is_recorded() {
+        let code = sampler().sample().unwrap();
+        let seed = code.seed().unwrap();
+        assert_eq!(sampler().seed(seed).sample().unwrap(), code);
+        let unseeded = sampler().sample_with(&mut rand::thread_rng()).unwrap();
+        assert_eq!(unseeded.seed(), None);
+        assert!(!unseeded.as_json().unwrap().contains("seed"));
+    }
+}
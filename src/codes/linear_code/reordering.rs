@@ -0,0 +1,284 @@
+use super::LinearCode;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec, SparseBinVecBase};
+use std::collections::VecDeque;
+
+/// A linear code whose bits and checks are relabeled,
+/// together with the permutations from the original labels.
+///
+/// Bit `i` of the original code is bit `bits()[i]` of the reordered code
+/// and check `j` of the original code is check `checks()[j]` of the reordered code.
+/// Errors and syndromes are moved between both codes with
+/// [`reorder_bits`](ReorderedCode::reorder_bits),
+/// [`reorder_checks`](ReorderedCode::reorder_checks) and
+/// [`restore_bits`](ReorderedCode::restore_bits).
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct ReorderedCode {
+    code: LinearCode,
+    bits: Vec<usize>,
+    checks: Vec<usize>,
+}
+
+impl ReorderedCode {
+    // Orders the nodes of the Tanner graph by a Cuthill-McKee breadth first search.
+    //
+    // Each connected component starts from a node far from a node of minimal degree
+    // and the neighbors of a node are visited by increasing degree.
+    // Bits and checks are then labeled in the order they are visited.
+    pub(super) fn cuthill_mckee(code: &LinearCode) -> Self {
+        let graph = TannerGraph { code };
+        let mut is_visited = vec![false; graph.num_nodes()];
+        let mut bits = vec![0; code.len()];
+        let mut checks = vec![0; code.num_checks()];
+        let mut num_bits = 0;
+        let mut num_checks = 0;
+        let mut nodes_by_degree = (0..graph.num_nodes()).collect::<Vec<_>>();
+        nodes_by_degree.sort_by_key(|node| graph.degree(*node));
+        for root in nodes_by_degree {
+            if is_visited[root] {
+                continue;
+            }
+            let component = graph.breadth_first_order(root, &mut is_visited);
+            let start = *component.last().unwrap();
+            for node in component {
+                is_visited[node] = false;
+            }
+            for node in graph.breadth_first_order(start, &mut is_visited) {
+                if node < code.len() {
+                    bits[node] = num_bits;
+                    num_bits += 1;
+                } else {
+                    checks[node - code.len()] = num_checks;
+                    num_checks += 1;
+                }
+            }
+        }
+        Self {
+            code: code.permuted(&bits, &checks),
+            bits,
+            checks,
+        }
+    }
+
+    /// Returns the reordered code.
+    pub fn code(&self) -> &LinearCode {
+        &self.code
+    }
+
+    /// Returns the reordered code, dropping the permutations.
+    pub fn into_code(self) -> LinearCode {
+        self.code
+    }
+
+    /// Returns the position in the reordered code of each bit of the original code.
+    pub fn bits(&self) -> &[usize] {
+        &self.bits
+    }
+
+    /// Returns the position in the reordered code of each check of the original code.
+    pub fn checks(&self) -> &[usize] {
+        &self.checks
+    }
+
+    /// Maps a vector of the original code to the reordered code.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vector length is different from the length of the code.
+    pub fn reorder_bits<T>(&self, vector: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        permute(&self.bits, vector)
+    }
+
+    /// Maps a syndrome of the original code to the reordered code.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    pub fn reorder_checks<T>(&self, syndrome: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        permute(&self.checks, syndrome)
+    }
+
+    /// Maps a vector of the reordered code back to the original code.
+    ///
+    /// This is used to apply a correction found by decoding the reordered code.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vector length is different from the length of the code.
+    pub fn restore_bits<T>(&self, vector: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        let mut original = vec![0; self.bits.len()];
+        for (bit, position) in self.bits.iter().enumerate() {
+            original[*position] = bit;
+        }
+        permute(&original, vector)
+    }
+}
+
+impl LinearCode {
+    // The code where bit i is moved to bits[i] and check j to checks[j].
+    fn permuted(&self, bits: &[usize], checks: &[usize]) -> Self {
+        let generators = self
+            .generator_matrix
+            .rows()
+            .map(|row| permute(bits, &row).non_trivial_positions().collect())
+            .collect();
+        let mut rows = vec![Vec::new(); checks.len()];
+        for (check, row) in self.parity_check_matrix.rows().enumerate() {
+            rows[checks[check]] = permute(bits, &row).non_trivial_positions().collect();
+        }
+        let parity_check_matrix = SparseBinMat::new(self.len(), rows);
+        Self {
+            generator_matrix: SparseBinMat::new(self.len(), generators),
+            bit_adjacencies: parity_check_matrix.transposed(),
+            parity_check_matrix,
+            known_distance: self.known_distance,
+            provenance: None,
+        }
+    }
+}
+
+fn permute<T>(permutation: &[usize], vector: &SparseBinVecBase<T>) -> SparseBinVec
+where
+    T: std::ops::Deref<Target = [usize]>,
+{
+    if vector.len() != permutation.len() {
+        panic!(
+            "vector of length {} for permutation of length {}",
+            vector.len(),
+            permutation.len()
+        );
+    }
+    let mut positions = vector
+        .non_trivial_positions()
+        .map(|position| permutation[position])
+        .collect::<Vec<_>>();
+    positions.sort_unstable();
+    SparseBinVec::new(vector.len(), positions)
+}
+
+// The Tanner graph where the bits are the first nodes followed by the checks.
+struct TannerGraph<'a> {
+    code: &'a LinearCode,
+}
+
+impl<'a> TannerGraph<'a> {
+    fn num_nodes(&self) -> usize {
+        self.code.len() + self.code.num_checks()
+    }
+
+    fn neighbors(&self, node: usize) -> Vec<usize> {
+        let length = self.code.len();
+        if node < length {
+            self.code
+                .bit_adjacencies
+                .row(node)
+                .unwrap()
+                .non_trivial_positions()
+                .map(|check| check + length)
+                .collect()
+        } else {
+            self.code
+                .parity_check_matrix
+                .row(node - length)
+                .unwrap()
+                .non_trivial_positions()
+                .collect()
+        }
+    }
+
+    fn degree(&self, node: usize) -> usize {
+        let length = self.code.len();
+        if node < length {
+            self.code.bit_adjacencies.row(node).unwrap().weight()
+        } else {
+            self.code
+                .parity_check_matrix
+                .row(node - length)
+                .unwrap()
+                .weight()
+        }
+    }
+
+    // The unvisited nodes of the component of the root in the order of
+    // a breadth first search visiting the neighbors of each node by increasing degree.
+    // These nodes are marked as visited.
+    fn breadth_first_order(&self, root: usize, is_visited: &mut [bool]) -> Vec<usize> {
+        let mut order = vec![root];
+        is_visited[root] = true;
+        let mut queue = VecDeque::from([root]);
+        while let Some(node) = queue.pop_front() {
+            let mut neighbors = self
+                .neighbors(node)
+                .into_iter()
+                .filter(|neighbor| !is_visited[*neighbor])
+                .collect::<Vec<_>>();
+            neighbors.sort_by_key(|neighbor| self.degree(*neighbor));
+            for neighbor in neighbors {
+                is_visited[neighbor] = true;
+                order.push(neighbor);
+                queue.push_back(neighbor);
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    #[test]
+    fn reordering_shuffled_chain_restores_locality() {
+        let mut bits = (0..20).collect::<Vec<_>>();
+        bits.shuffle(&mut StdRng::seed_from_u64(123));
+        let checks = (0..19).map(|check| {
+            let mut check = vec![bits[check], bits[check + 1]];
+            check.sort_unstable();
+            check
+        });
+        let code = LinearCode::from_parity_check_matrix(SparseBinMat::new(20, checks.collect()));
+        assert!(code.bandwidth() > 1);
+
+        let reordered = code.reordered_for_decoding();
+        assert_eq!(reordered.code().bandwidth(), 1);
+        for check in 0..code.num_checks() {
+            let row = code.check(check).unwrap();
+            assert_eq!(
+                reordered.reorder_bits(&row).as_view(),
+                reordered.code().check(reordered.checks()[check]).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn codewords_and_syndromes_are_preserved() {
+        let code = LinearCode::hamming_code().tensor_product(&LinearCode::repetition_code(3));
+        let reordered = code.reordered_for_decoding();
+        assert_eq!(reordered.code().dimension(), code.dimension());
+        for codeword in code.generator_matrix().rows() {
+            assert!(reordered
+                .code()
+                .has_codeword(&reordered.reorder_bits(&codeword)));
+        }
+        let error = SparseBinVec::new(code.len(), vec![0, 4, 10]);
+        let syndrome = code.syndrome_of(&error);
+        let reordered_error = reordered.reorder_bits(&error);
+        assert_eq!(
+            reordered.code().syndrome_of(&reordered_error),
+            reordered.reorder_checks(&syndrome)
+        );
+        assert_eq!(reordered.restore_bits(&reordered_error), error);
+    }
+}
@@ -0,0 +1,273 @@
+use super::LinearCode;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinMat;
+
+// Norms below this are considered zero and eigenvalues closer than this equal.
+const TOLERANCE: f64 = 1e-9;
+
+// The number of bisection steps to locate an eigenvalue of the tridiagonal matrix.
+const NUM_BISECTIONS: usize = 100;
+
+/// The largest eigenvalues of the adjacency matrix of the Tanner graph of a code,
+/// together with expansion estimates derived from them.
+///
+/// The Tanner graph is bipartite, so its spectrum is symmetric around 0
+/// and only the non-negative eigenvalues are kept.
+/// These are the singular values of the parity check matrix.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TannerSpectrum {
+    eigenvalues: Vec<f64>,
+    bit_degree: usize,
+    check_degree: usize,
+    is_regular: bool,
+}
+
+impl TannerSpectrum {
+    // Runs the Lanczos algorithm with full reorthogonalization on the Gram matrix
+    // of the smaller side of the Tanner graph.
+    //
+    // When a Krylov subspace is exhausted, it restarts from a random vector
+    // orthogonal to the previous ones such that repeated eigenvalues are found.
+    pub(super) fn lanczos(code: &LinearCode, num_eigenvalues: usize) -> Self {
+        let profile = code.degree_profile();
+        let side = if code.num_checks() <= code.len() {
+            code.parity_check_matrix()
+        } else {
+            code.bit_adjacencies()
+        };
+        let dimension = side.number_of_rows();
+        let num_steps = dimension.min(3 * num_eigenvalues + 30);
+        // A fixed seed makes the estimates reproducible.
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut basis: Vec<Vec<f64>> = Vec::with_capacity(num_steps);
+        let mut diagonal = Vec::with_capacity(num_steps);
+        let mut off_diagonal = Vec::with_capacity(num_steps);
+        let mut vector = random_orthonormal_vector(dimension, &basis, &mut rng);
+        while let Some(current) = vector {
+            let mut next = gram_product(side, &current);
+            diagonal.push(dot(&next, &current));
+            basis.push(current);
+            if basis.len() == num_steps {
+                break;
+            }
+            orthogonalize(&mut next, &basis);
+            let norm = dot(&next, &next).sqrt();
+            if norm > TOLERANCE {
+                next.iter_mut().for_each(|value| *value /= norm);
+                off_diagonal.push(norm);
+                vector = Some(next);
+            } else {
+                off_diagonal.push(0.0);
+                vector = random_orthonormal_vector(dimension, &basis, &mut rng);
+            }
+        }
+        let eigenvalues = (0..num_eigenvalues.min(diagonal.len()))
+            .map(|index| {
+                largest_tridiagonal_eigenvalue(&diagonal, &off_diagonal, index)
+                    .max(0.0)
+                    .sqrt()
+            })
+            .collect();
+        Self {
+            eigenvalues,
+            bit_degree: profile.bits.max().unwrap_or(0),
+            check_degree: profile.checks.max().unwrap_or(0),
+            is_regular: profile.is_regular(),
+        }
+    }
+
+    /// Returns the computed eigenvalues in decreasing order.
+    pub fn eigenvalues(&self) -> &[f64] {
+        &self.eigenvalues
+    }
+
+    /// Returns the largest eigenvalue or None if none were computed.
+    ///
+    /// For a code where all bits have degree `dv` and all checks have degree `dc`,
+    /// this is `sqrt(dv * dc)`.
+    pub fn largest(&self) -> Option<f64> {
+        self.eigenvalues.first().cloned()
+    }
+
+    /// Returns the second largest eigenvalue or None if less than 2 eigenvalues
+    /// were computed.
+    pub fn second_largest(&self) -> Option<f64> {
+        self.eigenvalues.get(1).cloned()
+    }
+
+    /// Returns the difference between the two largest eigenvalues.
+    ///
+    /// A larger gap means a better expander.
+    pub fn spectral_gap(&self) -> Option<f64> {
+        Some(self.largest()? - self.second_largest()?)
+    }
+
+    /// Returns the bound `sqrt(dv - 1) + sqrt(dc - 1)`
+    /// where `dv` and `dc` are the maximum degrees of the bits and the checks.
+    ///
+    /// Large biregular graphs have a second eigenvalue at least this bound
+    /// up to a vanishing term.
+    pub fn ramanujan_bound(&self) -> f64 {
+        (self.bit_degree.saturating_sub(1) as f64).sqrt()
+            + (self.check_degree.saturating_sub(1) as f64).sqrt()
+    }
+
+    /// Checks if the second largest eigenvalue is at most the
+    /// [Ramanujan bound](Self::ramanujan_bound).
+    ///
+    /// Returns true if less than 2 eigenvalues were computed.
+    pub fn is_ramanujan(&self) -> bool {
+        self.second_largest()
+            .is_none_or(|eigenvalue| eigenvalue <= self.ramanujan_bound() + TOLERANCE)
+    }
+
+    /// Returns Tanner's lower bound on the ratio between the number of checks
+    /// connected to a set of bits and the size of the set,
+    /// for sets containing the given fraction of the bits.
+    ///
+    /// The bound is `dv^2 / (λ^2 + (dv dc - λ^2) α)` where `λ` is the second largest
+    /// eigenvalue and `α` the fraction.
+    /// It only holds if all bits have the same degree and all checks have the same degree,
+    /// so None is returned for other codes or if the second eigenvalue is not computed.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the fraction is not in the interval (0, 1].
+    pub fn bit_expansion_bound(&self, fraction: f64) -> Option<f64> {
+        if !(fraction > 0.0 && fraction <= 1.0) {
+            panic!("invalid fraction of bits {}", fraction);
+        }
+        if !self.is_regular {
+            return None;
+        }
+        let second = self.second_largest()?.powi(2);
+        let bit_degree = self.bit_degree as f64;
+        let check_degree = self.check_degree as f64;
+        Some(bit_degree.powi(2) / (second + (bit_degree * check_degree - second) * fraction))
+    }
+}
+
+// The product of the matrix with its transpose applied to the vector.
+fn gram_product(matrix: &SparseBinMat, vector: &[f64]) -> Vec<f64> {
+    let mut transposed = vec![0.0; matrix.number_of_columns()];
+    for (row, value) in matrix.rows().zip(vector) {
+        for column in row.non_trivial_positions() {
+            transposed[column] += value;
+        }
+    }
+    matrix
+        .rows()
+        .map(|row| {
+            row.non_trivial_positions()
+                .map(|column| transposed[column])
+                .sum()
+        })
+        .collect()
+}
+
+fn dot(first: &[f64], second: &[f64]) -> f64 {
+    first.iter().zip(second).map(|(a, b)| a * b).sum()
+}
+
+// Removes the components along the basis twice for numerical stability.
+fn orthogonalize(vector: &mut [f64], basis: &[Vec<f64>]) {
+    for _ in 0..2 {
+        for element in basis {
+            let component = dot(vector, element);
+            vector
+                .iter_mut()
+                .zip(element)
+                .for_each(|(value, element)| *value -= component * element);
+        }
+    }
+}
+
+// A random unit vector orthogonal to the basis
+// or None if the basis spans the whole space.
+fn random_orthonormal_vector<R: Rng>(
+    dimension: usize,
+    basis: &[Vec<f64>],
+    rng: &mut R,
+) -> Option<Vec<f64>> {
+    if basis.len() >= dimension {
+        return None;
+    }
+    let mut vector = (0..dimension)
+        .map(|_| rng.gen_range(-1.0..1.0))
+        .collect::<Vec<f64>>();
+    orthogonalize(&mut vector, basis);
+    let norm = dot(&vector, &vector).sqrt();
+    (norm > TOLERANCE).then(|| vector.into_iter().map(|value| value / norm).collect())
+}
+
+// The eigenvalue with the given index from the largest of a symmetric tridiagonal matrix,
+// located by bisection on the Sturm sequence counts.
+fn largest_tridiagonal_eigenvalue(diagonal: &[f64], off_diagonal: &[f64], index: usize) -> f64 {
+    let radius = |row: usize| {
+        off_diagonal.get(row).map_or(0.0, |value| value.abs())
+            + row
+                .checked_sub(1)
+                .map_or(0.0, |row| off_diagonal[row].abs())
+    };
+    let mut low = (0..diagonal.len())
+        .map(|row| diagonal[row] - radius(row))
+        .fold(f64::INFINITY, f64::min);
+    let mut high = (0..diagonal.len())
+        .map(|row| diagonal[row] + radius(row))
+        .fold(f64::NEG_INFINITY, f64::max);
+    // The eigenvalue has exactly this number of eigenvalues below it.
+    let position = diagonal.len() - 1 - index;
+    for _ in 0..NUM_BISECTIONS {
+        let middle = (low + high) / 2.0;
+        if num_eigenvalues_below(diagonal, off_diagonal, middle) > position {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+fn num_eigenvalues_below(diagonal: &[f64], off_diagonal: &[f64], value: f64) -> usize {
+    let mut pivot = 1.0;
+    let mut count = 0;
+    for (row, entry) in diagonal.iter().enumerate() {
+        let coupling = row.checked_sub(1).map_or(0.0, |row| off_diagonal[row]);
+        pivot = entry - value - coupling * coupling / pivot;
+        if pivot == 0.0 {
+            pivot = -f64::MIN_POSITIVE;
+        }
+        if pivot < 0.0 {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn repeated_eigenvalues_of_hamming_code() {
+        let spectrum = LinearCode::hamming_code().tanner_spectrum(5);
+        let expected = [8.0_f64.sqrt(), 2.0_f64.sqrt(), 2.0_f64.sqrt()];
+        assert_eq!(spectrum.eigenvalues().len(), 3);
+        for (eigenvalue, expected) in spectrum.eigenvalues().iter().zip(expected) {
+            assert!((eigenvalue - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn complete_bipartite_graph_is_a_perfect_expander() {
+        let code =
+            LinearCode::from_parity_check_matrix(SparseBinMat::new(4, vec![vec![0, 1, 2, 3]; 3]));
+        let spectrum = code.tanner_spectrum(2);
+        assert!((spectrum.largest().unwrap() - 12.0_f64.sqrt()).abs() < 1e-6);
+        assert!(spectrum.second_largest().unwrap().abs() < 1e-6);
+        assert!(spectrum.is_ramanujan());
+        assert!((spectrum.bit_expansion_bound(0.25).unwrap() - 3.0).abs() < 1e-6);
+    }
+}
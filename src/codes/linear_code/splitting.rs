@@ -0,0 +1,251 @@
+use super::LinearCode;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec, SparseBinVecBase};
+
+/// A linear code whose high-weight checks are split into chains
+/// of lower-weight checks using auxiliary bits,
+/// together with the mapping from the original checks.
+///
+/// The original bits keep their labels and the auxiliary bits are appended after them.
+/// A check of weight above the limit is split into pieces `S_1, ..., S_m`
+/// and auxiliary bits `a_1, ..., a_{m-1}` such that the checks of the chain are
+/// `S_1 + a_1`, `a_1 + S_2 + a_2`, ..., `a_{m-1} + S_m`.
+/// The auxiliary bit `a_i` of a codeword is the parity of the bits in `S_1, ..., S_i`
+/// and the sum of the checks of a chain is the original check.
+/// Checks within the limit are kept as they are.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct SplitCode {
+    code: LinearCode,
+    original_length: usize,
+    // The checks of the split code forming each original check.
+    chains: Vec<Vec<usize>>,
+    // The original bits on which each auxiliary bit is the parity.
+    auxiliary_supports: Vec<Vec<usize>>,
+}
+
+impl SplitCode {
+    pub(super) fn with_max_weight(code: &LinearCode, max_weight: usize) -> Self {
+        if max_weight < 3 {
+            panic!("can't split checks to weight {} below 3", max_weight);
+        }
+        let mut rows = Vec::new();
+        let mut chains = Vec::with_capacity(code.num_checks());
+        let mut auxiliary_supports = Vec::new();
+        for check in code.parity_check_matrix().rows() {
+            let bits = check.non_trivial_positions().collect::<Vec<_>>();
+            if bits.len() <= max_weight {
+                chains.push(vec![rows.len()]);
+                rows.push(bits);
+                continue;
+            }
+            let mut chain = Vec::new();
+            let mut support = Vec::new();
+            let mut remaining = bits.as_slice();
+            let mut previous = None;
+            loop {
+                let capacity = max_weight - 1 - previous.is_some() as usize;
+                let is_last = remaining.len() < max_weight && previous.is_some();
+                let size = if is_last { remaining.len() } else { capacity };
+                let (piece, rest) = remaining.split_at(size);
+                remaining = rest;
+                support.extend_from_slice(piece);
+                let mut row = piece.to_vec();
+                if let Some(auxiliary) = previous {
+                    row.push(auxiliary);
+                }
+                chain.push(rows.len());
+                if is_last {
+                    rows.push(row);
+                    break;
+                }
+                let auxiliary = code.len() + auxiliary_supports.len();
+                auxiliary_supports.push(support.clone());
+                row.push(auxiliary);
+                rows.push(row);
+                previous = Some(auxiliary);
+            }
+            chains.push(chain);
+        }
+        let num_bits = code.len() + auxiliary_supports.len();
+        let rows = rows
+            .into_iter()
+            .map(|mut row| {
+                row.sort_unstable();
+                row
+            })
+            .collect();
+        Self {
+            code: LinearCode::from_parity_check_matrix(SparseBinMat::new(num_bits, rows)),
+            original_length: code.len(),
+            chains,
+            auxiliary_supports,
+        }
+    }
+
+    /// Returns the split code.
+    pub fn code(&self) -> &LinearCode {
+        &self.code
+    }
+
+    /// Returns the split code, dropping the mapping.
+    pub fn into_code(self) -> LinearCode {
+        self.code
+    }
+
+    /// Returns the length of the original code.
+    pub fn original_length(&self) -> usize {
+        self.original_length
+    }
+
+    /// Returns the number of auxiliary bits appended to the original bits.
+    pub fn num_auxiliary_bits(&self) -> usize {
+        self.auxiliary_supports.len()
+    }
+
+    /// Returns the checks of the split code whose sum is the given original check
+    /// or None if the check is out of bound.
+    pub fn chain_of(&self, check: usize) -> Option<&[usize]> {
+        self.chains.get(check).map(|chain| chain.as_slice())
+    }
+
+    /// Maps a vector of the original code to the split code
+    /// by setting each auxiliary bit to the parity of the bits it tracks.
+    ///
+    /// Codewords are mapped to codewords.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vector length is different from the length of the original code.
+    pub fn lift<T>(&self, vector: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if vector.len() != self.original_length {
+            panic!(
+                "vector of length {} for code of length {}",
+                vector.len(),
+                self.original_length
+            );
+        }
+        let auxiliaries = self
+            .auxiliary_supports
+            .iter()
+            .enumerate()
+            .filter(|(_, support)| {
+                support
+                    .iter()
+                    .filter(|bit| vector.is_one_at(**bit).unwrap())
+                    .count()
+                    % 2
+                    == 1
+            })
+            .map(|(auxiliary, _)| self.original_length + auxiliary);
+        let positions = vector.non_trivial_positions().chain(auxiliaries).collect();
+        SparseBinVec::new(self.code.len(), positions)
+    }
+
+    /// Restricts a vector of the split code to the original bits.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vector length is different from the length of the split code.
+    pub fn restrict<T>(&self, vector: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if vector.len() != self.code.len() {
+            panic!(
+                "vector of length {} for code of length {}",
+                vector.len(),
+                self.code.len()
+            );
+        }
+        let positions = vector
+            .non_trivial_positions()
+            .take_while(|bit| *bit < self.original_length)
+            .collect();
+        SparseBinVec::new(self.original_length, positions)
+    }
+
+    /// Maps a syndrome of the split code to the original code
+    /// by summing the outcomes of the checks of each chain.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks of the split code.
+    pub fn merge_syndrome<T>(&self, syndrome: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if syndrome.len() != self.code.num_checks() {
+            panic!(
+                "syndrome of length {} for {} checks",
+                syndrome.len(),
+                self.code.num_checks()
+            );
+        }
+        let positions = self
+            .chains
+            .iter()
+            .enumerate()
+            .filter(|(_, chain)| {
+                chain
+                    .iter()
+                    .filter(|check| syndrome.is_one_at(**check).unwrap())
+                    .count()
+                    % 2
+                    == 1
+            })
+            .map(|(check, _)| check)
+            .collect();
+        SparseBinVec::new(self.chains.len(), positions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn splitting_a_single_check_of_weight_7() {
+        let code =
+            LinearCode::from_parity_check_matrix(SparseBinMat::new(7, vec![(0..7).collect()]));
+        let split = code.split_checks(3);
+        assert_eq!(split.num_auxiliary_bits(), 4);
+        assert_eq!(
+            split.code().parity_check_matrix(),
+            &SparseBinMat::new(
+                11,
+                vec![
+                    vec![0, 1, 7],
+                    vec![2, 7, 8],
+                    vec![3, 8, 9],
+                    vec![4, 9, 10],
+                    vec![5, 6, 10]
+                ]
+            )
+        );
+        assert_eq!(split.chain_of(0), Some(&[0, 1, 2, 3, 4][..]));
+        assert_eq!(split.code().dimension(), code.dimension());
+    }
+
+    #[test]
+    fn codewords_are_lifted_to_codewords() {
+        let code = LinearCode::hamming_code();
+        let split = code.split_checks(3);
+        assert!(split
+            .code()
+            .parity_check_matrix()
+            .rows()
+            .all(|check| check.weight() <= 3));
+        assert_eq!(split.code().dimension(), code.dimension());
+        for codeword in code.generator_matrix().rows() {
+            let lifted = split.lift(&codeword);
+            assert!(split.code().has_codeword(&lifted));
+            assert_eq!(split.restrict(&lifted).as_view(), codeword);
+        }
+        let error = SparseBinVec::new(7, vec![2]);
+        let syndrome = split.code().syndrome_of(&split.lift(&error));
+        assert_eq!(split.merge_syndrome(&syndrome), code.syndrome_of(&error));
+    }
+}
@@ -0,0 +1,307 @@
+use super::LinearCode;
+use crate::codes::InformationSet;
+use itertools::Itertools;
+use sparse_bin_mat::SparseBinMat;
+use std::fmt;
+
+impl LinearCode {
+    /// Checks the internal consistency of the code
+    /// and returns every inconsistency found.
+    ///
+    /// That is, the parity check and generator matrices have the same length,
+    /// every generator satisfies every check,
+    /// the generators are linearly independent and span all codewords,
+    /// the bit adjacencies are the transposed parity check matrix
+    /// and there is neither an empty check nor an unchecked bit.
+    /// If the lengths are different, the other checks are skipped.
+    ///
+    /// This is useful for codes loaded from a file since the matrices are stored
+    /// independently and are not verified on deserialization
+    /// unless the `checked-serde` feature is enabled.
+    /// An inconsistent code can be [repaired](Self::repair)
+    /// from its parity check matrix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::codes::LinearCodeInconsistency;
+    ///
+    /// let code = LinearCode::hamming_code();
+    /// assert_eq!(code.validate(), Ok(()));
+    ///
+    /// // Dropping the last generator.
+    /// let generators = code.generator_matrix().keep_only_rows(&[0, 1, 2]).unwrap();
+    /// let parity_checks = code.parity_check_matrix().clone();
+    /// let mut code = LinearCode::from_both_matrices(generators, parity_checks);
+    /// assert_eq!(
+    ///     code.validate(),
+    ///     Err(vec![LinearCodeInconsistency::MissingGenerators(4, 3)])
+    /// );
+    ///
+    /// code.repair();
+    /// assert_eq!(code.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<LinearCodeInconsistency>> {
+        let length = self.parity_check_matrix.number_of_columns();
+        let other_lengths = [
+            self.generator_matrix.number_of_columns(),
+            self.bit_adjacencies.number_of_rows(),
+        ];
+        if let Some(other) = other_lengths.into_iter().find(|other| *other != length) {
+            return Err(vec![LinearCodeInconsistency::DifferentLengths(
+                length, other,
+            )]);
+        }
+        let mut inconsistencies = Vec::new();
+        if !self.generator_matrix.is_empty() && !self.parity_check_matrix.is_empty() {
+            inconsistencies.extend(
+                (&self.parity_check_matrix * &self.generator_matrix.transposed())
+                    .non_trivial_elements()
+                    .map(|(check, generator)| {
+                        LinearCodeInconsistency::UnsatisfiedCheck(check, generator)
+                    }),
+            );
+        }
+        let generator_rank = rank(&self.generator_matrix);
+        if generator_rank < self.generator_matrix.number_of_rows() {
+            inconsistencies.push(LinearCodeInconsistency::DependentGenerators(
+                self.generator_matrix.number_of_rows(),
+                generator_rank,
+            ));
+        }
+        let dimension = length - rank(&self.parity_check_matrix);
+        if generator_rank < dimension {
+            inconsistencies.push(LinearCodeInconsistency::MissingGenerators(
+                dimension,
+                generator_rank,
+            ));
+        }
+        let bit_adjacencies = self.parity_check_matrix.transposed();
+        if self.bit_adjacencies != bit_adjacencies {
+            inconsistencies.push(LinearCodeInconsistency::StaleBitAdjacencies);
+        }
+        inconsistencies.extend(
+            self.parity_check_matrix
+                .rows()
+                .positions(|check| check.is_zero())
+                .map(LinearCodeInconsistency::EmptyCheck),
+        );
+        inconsistencies.extend(
+            bit_adjacencies
+                .rows()
+                .positions(|bit| bit.is_zero())
+                .map(LinearCodeInconsistency::UncheckedBit),
+        );
+        if inconsistencies.is_empty() {
+            Ok(())
+        } else {
+            Err(inconsistencies)
+        }
+    }
+
+    /// Recomputes the generator matrix and the bit adjacencies
+    /// from the parity check matrix.
+    ///
+    /// This fixes every inconsistency reported by [`validate`](Self::validate)
+    /// except for empty checks and unchecked bits
+    /// which are part of the parity check matrix.
+    pub fn repair(&mut self) {
+        // The nullspace can't be computed with empty rows.
+        let checks = self
+            .parity_check_matrix
+            .rows()
+            .positions(|check| !check.is_zero())
+            .collect::<Vec<_>>();
+        self.generator_matrix = self
+            .parity_check_matrix
+            .keep_only_rows(&checks)
+            .unwrap()
+            .nullspace();
+        self.bit_adjacencies = self.parity_check_matrix.transposed();
+    }
+}
+
+// The fields of a serialized code before they are validated.
+#[cfg(feature = "checked-serde")]
+#[derive(serde::Deserialize)]
+pub(super) struct UncheckedLinearCode {
+    parity_check_matrix: SparseBinMat,
+    generator_matrix: SparseBinMat,
+    #[serde(default)]
+    provenance: Option<crate::codes::Provenance>,
+}
+
+#[cfg(feature = "checked-serde")]
+impl TryFrom<UncheckedLinearCode> for LinearCode {
+    type Error = String;
+
+    fn try_from(code: UncheckedLinearCode) -> Result<Self, Self::Error> {
+        let code = LinearCode {
+            bit_adjacencies: code.parity_check_matrix.transposed(),
+            parity_check_matrix: code.parity_check_matrix,
+            generator_matrix: code.generator_matrix,
+            known_distance: None,
+            provenance: code.provenance,
+        };
+        // Empty checks and unchecked bits are degenerate but consistent.
+        let inconsistencies = code
+            .validate()
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|inconsistency| {
+                !matches!(
+                    inconsistency,
+                    LinearCodeInconsistency::EmptyCheck(_)
+                        | LinearCodeInconsistency::UncheckedBit(_)
+                )
+            })
+            .map(|inconsistency| inconsistency.to_string())
+            .collect::<Vec<_>>();
+        if inconsistencies.is_empty() {
+            Ok(code)
+        } else {
+            Err(format!("inconsistent code: {}", inconsistencies.join(", ")))
+        }
+    }
+}
+
+fn rank(matrix: &SparseBinMat) -> usize {
+    let columns = (0..matrix.number_of_columns()).collect::<Vec<_>>();
+    InformationSet::new(matrix, &columns).rank()
+}
+
+/// An inconsistency between the matrices of a [`LinearCode`]
+/// found by [`validate`](LinearCode::validate).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum LinearCodeInconsistency {
+    /// The parity check matrix and another matrix have different lengths.
+    DifferentLengths(usize, usize),
+    /// A check is not satisfied by a generator.
+    UnsatisfiedCheck(usize, usize),
+    /// The number of generators and their rank.
+    DependentGenerators(usize, usize),
+    /// The dimension of the code and the rank of the generators.
+    MissingGenerators(usize, usize),
+    /// The bit adjacencies are not the transposed parity check matrix.
+    StaleBitAdjacencies,
+    /// A check without any bit.
+    EmptyCheck(usize),
+    /// A bit in no check.
+    UncheckedBit(usize),
+}
+
+impl fmt::Display for LinearCodeInconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DifferentLengths(parity_length, other_length) => write!(
+                f,
+                "parity check matrix and other matrix of different lengths: {} & {}",
+                parity_length, other_length
+            ),
+            Self::UnsatisfiedCheck(check, generator) => write!(
+                f,
+                "check {} is not satisfied by generator {}",
+                check, generator
+            ),
+            Self::DependentGenerators(num_generators, rank) => {
+                write!(f, "{} generators of rank {}", num_generators, rank)
+            }
+            Self::MissingGenerators(dimension, rank) => write!(
+                f,
+                "generators of rank {} for code of dimension {}",
+                rank, dimension
+            ),
+            Self::StaleBitAdjacencies => write!(f, "bit adjacencies are stale"),
+            Self::EmptyCheck(check) => write!(f, "check {} is empty", check),
+            Self::UncheckedBit(bit) => write!(f, "bit {} is not checked", bit),
+        }
+    }
+}
+
+impl std::error::Error for LinearCodeInconsistency {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn standard_codes_are_consistent() {
+        let codes = [
+            LinearCode::repetition_code(5),
+            LinearCode::hamming_code(),
+            LinearCode::extended_golay_code(),
+            LinearCode::simplex_code(3),
+        ];
+        for code in codes {
+            assert_eq!(code.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn corrupted_generators_are_repaired() {
+        let mut code = LinearCode::hamming_code();
+        code.generator_matrix = SparseBinMat::new(7, vec![vec![0, 1], vec![0, 1]]);
+        assert_eq!(
+            code.validate(),
+            Err(vec![
+                LinearCodeInconsistency::UnsatisfiedCheck(1, 0),
+                LinearCodeInconsistency::UnsatisfiedCheck(1, 1),
+                LinearCodeInconsistency::UnsatisfiedCheck(2, 0),
+                LinearCodeInconsistency::UnsatisfiedCheck(2, 1),
+                LinearCodeInconsistency::DependentGenerators(2, 1),
+                LinearCodeInconsistency::MissingGenerators(4, 1),
+            ])
+        );
+        code.repair();
+        assert_eq!(code.validate(), Ok(()));
+        assert!(code.has_same_codespace(&LinearCode::hamming_code()));
+    }
+
+    #[test]
+    fn degenerate_parity_checks_are_reported() {
+        let mut code = LinearCode::from_parity_check_matrix(SparseBinMat::new(4, vec![vec![0, 1]]));
+        code.parity_check_matrix = SparseBinMat::new(4, vec![vec![0, 1], vec![]]);
+        assert_eq!(
+            code.validate(),
+            Err(vec![
+                LinearCodeInconsistency::StaleBitAdjacencies,
+                LinearCodeInconsistency::EmptyCheck(1),
+                LinearCodeInconsistency::UncheckedBit(2),
+                LinearCodeInconsistency::UncheckedBit(3),
+            ])
+        );
+        code.repair();
+        assert_eq!(code.dimension(), 3);
+        assert_eq!(
+            code.validate(),
+            Err(vec![
+                LinearCodeInconsistency::EmptyCheck(1),
+                LinearCodeInconsistency::UncheckedBit(2),
+                LinearCodeInconsistency::UncheckedBit(3),
+            ])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "checked-serde")]
+    fn deserialization_rejects_inconsistent_generators() {
+        let code = LinearCode::hamming_code();
+        let mut json = serde_json::to_value(&code).unwrap();
+        assert_eq!(
+            serde_json::from_value::<LinearCode>(json.clone()).unwrap(),
+            code
+        );
+
+        json["bit_adjacencies"]["rows"] = serde_json::json!([]);
+        assert_eq!(
+            serde_json::from_value::<LinearCode>(json.clone()).unwrap(),
+            code
+        );
+
+        json["generator_matrix"]["rows"][0] = serde_json::json!([0]);
+        let error = serde_json::from_value::<LinearCode>(json).unwrap_err();
+        assert!(error.to_string().starts_with("inconsistent code"));
+    }
+}
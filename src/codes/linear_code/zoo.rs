@@ -0,0 +1,250 @@
+use super::LinearCode;
+use crate::codes::BinaryPolynomial;
+use sparse_bin_mat::SparseBinMat;
+
+// The exponents of the generator polynomial 1 + x^2 + x^4 + x^5 + x^6 + x^10 + x^11
+// of the cyclic [23, 12, 7] Golay code.
+const GOLAY_POLYNOMIAL: [usize; 7] = [0, 2, 4, 5, 6, 10, 11];
+
+impl LinearCode {
+    /// Returns the cyclic code of the given length generated by the polynomial.
+    ///
+    /// The codewords are the multiples of the generator modulo `x^length + 1`
+    /// and the generators of the code are the shifts `x^i g(x)`
+    /// for `i` smaller than the length minus the degree of `g`.
+    /// The generator of a cyclic code is a product of
+    /// [factors](BinaryPolynomial::factors) of `x^length + 1`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the generator doesn't divide `x^length + 1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::codes::{BinaryPolynomial, LinearCode};
+    ///
+    /// // The cyclic Hamming code.
+    /// let generator = BinaryPolynomial::new(vec![0, 1, 3]);
+    /// let code = LinearCode::cyclic_code(7, &generator);
+    ///
+    /// assert_eq!(code.parameters(), (7, 4, Some(3)));
+    /// ```
+    pub fn cyclic_code(length: usize, generator: &BinaryPolynomial) -> Self {
+        let modulus = BinaryPolynomial::cyclic_modulus(length);
+        if generator.is_zero() || !modulus.div_rem(generator).1.is_zero() {
+            panic!("{} doesn't divide {}", generator, modulus);
+        }
+        let exponents = generator.exponents();
+        let degree = generator.degree().unwrap();
+        let generators = (0..length - degree)
+            .map(|shift| exponents.iter().map(|exponent| exponent + shift).collect())
+            .collect();
+        Self::from_generator_matrix(SparseBinMat::new(length, generators))
+    }
+
+    /// Returns the [24, 12, 8] extended Golay code.
+    ///
+    /// The generators are the cyclic shifts of the generator polynomial
+    /// of the [23, 12, 7] Golay code extended by a parity bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::extended_golay_code();
+    ///
+    /// assert_eq!(code.dimension(), 12);
+    /// assert_eq!(code.known_distance(), Some(8));
+    /// ```
+    pub fn extended_golay_code() -> Self {
+        let generators = (0..12)
+            .map(|shift| {
+                // The generator polynomial has odd weight.
+                GOLAY_POLYNOMIAL
+                    .iter()
+                    .map(|exponent| exponent + shift)
+                    .chain([23])
+                    .collect()
+            })
+            .collect();
+        Self::from_generator_matrix(SparseBinMat::new(24, generators)).with_known_distance(8)
+    }
+
+    /// Returns the [2^m, m + 1, 2^(m-1)] first order Reed-Muller code.
+    ///
+    /// Bit `i` is the evaluation point whose coordinates are the bits of `i`.
+    /// The generators are the constant function and the `m` coordinates.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `m` is smaller than 2
+    /// since the code for a single variable has no check.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::reed_muller_code(4);
+    ///
+    /// assert_eq!(code.parameters(), (16, 5, Some(8)));
+    /// ```
+    pub fn reed_muller_code(m: usize) -> Self {
+        if m < 2 {
+            panic!("reed-muller code needs at least two variables");
+        }
+        let generators = std::iter::once((0..1 << m).collect())
+            .chain(coordinates(m, 0..1 << m))
+            .collect();
+        Self::from_generator_matrix(SparseBinMat::new(1 << m, generators))
+            .with_known_distance(1 << (m - 1))
+    }
+
+    /// Returns the [2^m - 1, m, 2^(m-1)] simplex code,
+    /// the dual of the Hamming code with `m` checks.
+    ///
+    /// Bit `i` is the binary vector representing `i + 1`
+    /// such that every codeword except 0 has weight `2^(m-1)`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `m` is smaller than 2
+    /// since the code of length 1 has no check.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::simplex_code(3);
+    ///
+    /// assert_eq!(code.parameters(), (7, 3, Some(4)));
+    /// assert!(code.generator_matrix().rows().all(|row| row.weight() == 4));
+    /// ```
+    pub fn simplex_code(m: usize) -> Self {
+        if m < 2 {
+            panic!("simplex code needs at least two generators");
+        }
+        let generators = coordinates(m, 1..1 << m)
+            .map(|generator| generator.into_iter().map(|bit| bit - 1).collect())
+            .collect();
+        Self::from_generator_matrix(SparseBinMat::new((1 << m) - 1, generators))
+            .with_known_distance(1 << (m - 1))
+    }
+
+    /// Returns the [2^m, m, 2^(m-1)] Hadamard code,
+    /// the simplex code extended by a bit that is always 0.
+    ///
+    /// Bit `i` is the evaluation point whose coordinates are the bits of `i`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `m` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// let code = LinearCode::hadamard_code(3);
+    ///
+    /// assert_eq!(code.parameters(), (8, 3, Some(4)));
+    /// ```
+    pub fn hadamard_code(m: usize) -> Self {
+        if m == 0 {
+            panic!("hadamard code needs at least one generator");
+        }
+        let generators = coordinates(m, 0..1 << m).collect();
+        Self::from_generator_matrix(SparseBinMat::new(1 << m, generators))
+            .with_known_distance(1 << (m - 1))
+    }
+}
+
+// For each of the m coordinates, the points whose bit of this coordinate is 1.
+fn coordinates(m: usize, points: std::ops::Range<usize>) -> impl Iterator<Item = Vec<usize>> {
+    (0..m).map(move |bit| {
+        points
+            .clone()
+            .filter(|point| point >> bit & 1 == 1)
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sparse_bin_mat::SparseBinVec;
+
+    #[test]
+    fn known_distances_are_exact() {
+        let codes = [
+            LinearCode::repetition_code(5),
+            LinearCode::hamming_code(),
+            LinearCode::extended_golay_code(),
+            LinearCode::reed_muller_code(2),
+            LinearCode::reed_muller_code(3),
+            LinearCode::simplex_code(4),
+            LinearCode::hadamard_code(4),
+        ];
+        for code in codes {
+            assert_eq!(code.known_distance(), code.minimal_distance());
+        }
+    }
+
+    #[test]
+    fn known_distance_is_not_deserialized() {
+        let json = LinearCode::hamming_code().as_json().unwrap();
+        let code: LinearCode = serde_json::from_str(&json).unwrap();
+        assert_eq!(code, LinearCode::hamming_code());
+        assert_eq!(code.known_distance(), None);
+        assert_eq!(code.parameters(), (7, 4, Some(3)));
+    }
+
+    #[test]
+    fn golay_code_is_self_dual() {
+        let code = LinearCode::extended_golay_code();
+        assert!((code.generator_matrix() * &code.generator_matrix().transposed()).is_zero());
+        assert!(code
+            .generator_matrix()
+            .rows()
+            .all(|row| row.weight() % 4 == 0));
+    }
+
+    #[test]
+    fn simplex_code_is_dual_of_hamming_code() {
+        let simplex_code = LinearCode::simplex_code(3);
+        let hamming_code = LinearCode::from_generator_matrix(
+            LinearCode::hamming_code().parity_check_matrix().clone(),
+        );
+        assert!(simplex_code.has_same_codespace(&hamming_code));
+    }
+
+    #[test]
+    fn unknown_distance_is_computed() {
+        let code = LinearCode::from_parity_check_matrix(SparseBinMat::new(
+            4,
+            vec![vec![0, 1], vec![2, 3]],
+        ));
+        assert_eq!(code.known_distance(), None);
+        assert_eq!(code.parameters(), (4, 2, Some(2)));
+    }
+
+    #[test]
+    fn cyclic_golay_code_punctures_the_extended_golay_code() {
+        let generator = BinaryPolynomial::new(GOLAY_POLYNOMIAL.to_vec());
+        let code = LinearCode::cyclic_code(23, &generator);
+        assert_eq!(code.parameters(), (23, 12, Some(7)));
+        let extended = LinearCode::extended_golay_code();
+        for generator in code.generator_matrix().rows() {
+            let positions = generator.non_trivial_positions().collect::<Vec<_>>();
+            let parity = (positions.len() % 2 == 1).then_some(23);
+            let extended_word =
+                SparseBinVec::new(24, positions.into_iter().chain(parity).collect());
+            assert!(extended.has_codeword(&extended_word));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn cyclic_generator_must_divide_the_modulus() {
+        LinearCode::cyclic_code(7, &BinaryPolynomial::new(vec![0, 1, 2]));
+    }
+}
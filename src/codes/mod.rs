@@ -1,5 +1,72 @@
 pub mod linear_code;
-pub use linear_code::{LinearCode, RandomRegularCode};
+pub use linear_code::{
+    hamming_distance, LinearCode, LinearCodeInconsistency, QuantumCandidate, QuantumConstruction,
+    RandomRegularCode, SamplingError,
+};
 
 pub mod css;
-pub use css::{CssCode, CssError};
+pub use css::{CssCode, CssError, CssInconsistency, Layout};
+
+mod stabilizer;
+pub use stabilizer::{StabilizerCode, StabilizerError};
+
+mod automorphism;
+pub use automorphism::Automorphism;
+
+mod registry;
+pub use registry::{CodeRegistry, RegisteredCode, RegistryError};
+
+mod fingerprint;
+pub use fingerprint::{Fingerprint, FingerprintParseError};
+pub(crate) use fingerprint::FingerprintHasher;
+
+mod provenance;
+pub use provenance::Provenance;
+
+mod polynomial;
+pub use polynomial::BinaryPolynomial;
+
+mod quasi_cyclic;
+pub use quasi_cyclic::QuasiCyclicMatrix;
+
+mod product_matrix;
+pub use product_matrix::ProductMatrix;
+
+mod symmetric_matrix;
+pub use symmetric_matrix::SymmetricMatrix;
+
+pub(crate) mod information_set;
+pub use information_set::InformationSet;
+
+mod data_syndrome;
+pub use data_syndrome::DataSyndromeCode;
+
+mod dense;
+pub use dense::{DenseCodeword, Word};
+
+mod backend;
+pub use backend::{BinaryMatrix, DenseBinMat};
+
+mod alist;
+pub use alist::{read_alist, AlistError};
+
+mod audit;
+pub use audit::{CssCodeAudit, LinearCodeAudit};
+
+mod batch;
+pub(crate) use batch::{par_syndromes_of_batch, syndromes_of_batch};
+
+mod rank;
+pub use rank::approximate_rank;
+pub(crate) use rank::wiedemann_rank;
+
+#[cfg(feature = "ilp")]
+mod ilp;
+
+#[cfg(feature = "sat")]
+mod sat;
+
+#[cfg(feature = "petgraph")]
+mod graph;
+#[cfg(feature = "petgraph")]
+pub use graph::{CssTannerNode, TannerNode};
@@ -0,0 +1,487 @@
+use super::{BinaryMatrix, DenseBinMat};
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinMat;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+const WORD_BITS: usize = 64;
+
+/// A polynomial with binary coefficients.
+///
+/// Cyclic codes and the circulant blocks of bicycle codes are described
+/// by polynomials modulo `x^n + 1`, which is the same as `x^n - 1` over GF(2).
+/// The [circulant](BinaryPolynomial::circulant) of a polynomial is the matrix
+/// of the multiplication by this polynomial modulo `x^n + 1`,
+/// thus the algebra of the polynomials gives the ranks and the products
+/// of circulant matrices.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::BinaryPolynomial;
+///
+/// // x^7 + 1 = (1 + x) (1 + x + x^3) (1 + x^2 + x^3).
+/// let factors = BinaryPolynomial::cyclic_modulus(7).factors();
+///
+/// let expected = vec![
+///     (BinaryPolynomial::new(vec![0, 1]), 1),
+///     (BinaryPolynomial::new(vec![0, 1, 3]), 1),
+///     (BinaryPolynomial::new(vec![0, 2, 3]), 1),
+/// ];
+/// assert_eq!(factors, expected);
+/// assert_eq!(factors[1].0.to_string(), "1 + x + x^3");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct BinaryPolynomial {
+    words: Vec<u64>,
+}
+
+impl BinaryPolynomial {
+    /// Creates the sum of the monomials with the given exponents.
+    ///
+    /// Repeated exponents cancel each other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::codes::BinaryPolynomial;
+    ///
+    /// let polynomial = BinaryPolynomial::new(vec![3, 0, 1, 3, 5]);
+    ///
+    /// assert_eq!(polynomial.exponents(), vec![0, 1, 5]);
+    /// assert_eq!(polynomial.degree(), Some(5));
+    /// ```
+    pub fn new(exponents: Vec<usize>) -> Self {
+        let mut polynomial = Self::zero();
+        for exponent in exponents {
+            let word = exponent / WORD_BITS;
+            if polynomial.words.len() <= word {
+                polynomial.words.resize(word + 1, 0);
+            }
+            polynomial.words[word] ^= 1 << (exponent % WORD_BITS);
+        }
+        polynomial.normalize();
+        polynomial
+    }
+
+    /// Returns the zero polynomial.
+    pub fn zero() -> Self {
+        Self { words: Vec::new() }
+    }
+
+    /// Returns the constant polynomial 1.
+    pub fn one() -> Self {
+        Self::monomial(0)
+    }
+
+    /// Returns `x` to the given exponent.
+    pub fn monomial(exponent: usize) -> Self {
+        Self::new(vec![exponent])
+    }
+
+    /// Returns `x^length + 1`, the modulus of the cyclic codes of the given length.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length is 0.
+    pub fn cyclic_modulus(length: usize) -> Self {
+        if length == 0 {
+            panic!("length must be positive");
+        }
+        Self::new(vec![0, length])
+    }
+
+    /// Returns the degree or None for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.words.last().map(|word| {
+            (self.words.len() - 1) * WORD_BITS + WORD_BITS - 1 - word.leading_zeros() as usize
+        })
+    }
+
+    /// Checks if all the coefficients are zero.
+    pub fn is_zero(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns the coefficient of `x` to the given exponent.
+    pub fn coefficient(&self, exponent: usize) -> bool {
+        self.words
+            .get(exponent / WORD_BITS)
+            .is_some_and(|word| (word >> (exponent % WORD_BITS)) & 1 == 1)
+    }
+
+    /// Returns the exponents of the non zero coefficients in increasing order.
+    pub fn exponents(&self) -> Vec<usize> {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(index, word)| {
+                (0..WORD_BITS)
+                    .filter(move |bit| (word >> bit) & 1 == 1)
+                    .map(move |bit| index * WORD_BITS + bit)
+            })
+            .collect()
+    }
+
+    /// Returns the number of non zero coefficients.
+    pub fn weight(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns the quotient and the remainder of the division by the divisor.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the divisor is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let divisor_degree = divisor.degree().expect("division by zero");
+        let mut quotient = Self::zero();
+        let mut remainder = self.clone();
+        while let Some(degree) = remainder
+            .degree()
+            .filter(|degree| *degree >= divisor_degree)
+        {
+            let shift = degree - divisor_degree;
+            quotient.add_shifted(&Self::one(), shift);
+            remainder.add_shifted(divisor, shift);
+        }
+        (quotient, remainder)
+    }
+
+    /// Returns the greatest common divisor of both polynomials.
+    ///
+    /// It is zero only if both polynomials are zero.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut first, mut second) = (self.clone(), other.clone());
+        while !second.is_zero() {
+            let remainder = first.div_rem(&second).1;
+            first = second;
+            second = remainder;
+        }
+        first
+    }
+
+    /// Returns the polynomial modulo `x^length + 1`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length is 0.
+    pub fn cyclic_reduction(&self, length: usize) -> Self {
+        if length == 0 {
+            panic!("length must be positive");
+        }
+        Self::new(
+            self.exponents()
+                .into_iter()
+                .map(|exponent| exponent % length)
+                .collect(),
+        )
+    }
+
+    /// Returns the product of both polynomials modulo `x^length + 1`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::codes::BinaryPolynomial;
+    ///
+    /// let first = BinaryPolynomial::new(vec![0, 3]);
+    /// let second = BinaryPolynomial::new(vec![1, 4]);
+    ///
+    /// assert_eq!(first.cyclic_product(&second, 5), BinaryPolynomial::new(vec![1, 2]));
+    /// assert_eq!(
+    ///     first.cyclic_product(&second, 5).circulant(5),
+    ///     &first.circulant(5) * &second.circulant(5)
+    /// );
+    /// ```
+    pub fn cyclic_product(&self, other: &Self, length: usize) -> Self {
+        (self * other).cyclic_reduction(length)
+    }
+
+    /// Returns the circulant matrix of the multiplication by the polynomial
+    /// modulo `x^length + 1`.
+    ///
+    /// Row `i` has a one in column `(i + e) % length` for each exponent `e`
+    /// of the reduced polynomial, as a sum of the circulant permutations
+    /// of a [`QuasiCyclicMatrix`](crate::codes::QuasiCyclicMatrix).
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length is 0.
+    pub fn circulant(&self, length: usize) -> SparseBinMat {
+        let exponents = self.cyclic_reduction(length).exponents();
+        let rows = (0..length)
+            .map(|row| {
+                let mut columns: Vec<_> = exponents
+                    .iter()
+                    .map(|exponent| (row + exponent) % length)
+                    .collect();
+                columns.sort_unstable();
+                columns
+            })
+            .collect();
+        SparseBinMat::new(length, rows)
+    }
+
+    /// Returns the irreducible factors of the polynomial with their multiplicities
+    /// sorted by degree and then by exponents.
+    ///
+    /// The repeated factors are separated by a square-free factorization
+    /// and the square-free parts are split with Berlekamp's algorithm,
+    /// which is practical for degrees up to a few thousands.
+    /// The factors of a constant polynomial are empty.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the polynomial is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::codes::BinaryPolynomial;
+    ///
+    /// // x^6 + 1 = (x^3 + 1)^2 = (1 + x)^2 (1 + x + x^2)^2.
+    /// let factors = BinaryPolynomial::cyclic_modulus(6).factors();
+    ///
+    /// assert_eq!(factors[0], (BinaryPolynomial::new(vec![0, 1]), 2));
+    /// assert_eq!(factors[1], (BinaryPolynomial::new(vec![0, 1, 2]), 2));
+    /// ```
+    pub fn factors(&self) -> Vec<(Self, usize)> {
+        if self.is_zero() {
+            panic!("can't factor the zero polynomial");
+        }
+        let mut factors: Vec<_> = self
+            .square_free_factors()
+            .into_iter()
+            .flat_map(|(factor, multiplicity)| {
+                factor
+                    .berlekamp_factors()
+                    .into_iter()
+                    .map(move |factor| (factor, multiplicity))
+            })
+            .collect();
+        factors.sort_by_key(|(factor, _)| (factor.degree(), factor.exponents()));
+        factors
+    }
+
+    /// Checks if the polynomial is not constant and has no non trivial factor.
+    pub fn is_irreducible(&self) -> bool {
+        if self.degree().unwrap_or(0) == 0 {
+            return false;
+        }
+        matches!(self.factors().as_slice(), [(_, 1)])
+    }
+
+    // Removes the leading zero words.
+    fn normalize(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    // Adds the other polynomial multiplied by x to the given shift.
+    fn add_shifted(&mut self, other: &Self, shift: usize) {
+        let (word_shift, bit_shift) = (shift / WORD_BITS, shift % WORD_BITS);
+        let length = other.words.len() + word_shift + 1;
+        if self.words.len() < length {
+            self.words.resize(length, 0);
+        }
+        for (index, word) in other.words.iter().enumerate() {
+            self.words[index + word_shift] ^= word << bit_shift;
+            if bit_shift > 0 {
+                self.words[index + word_shift + 1] ^= word >> (WORD_BITS - bit_shift);
+            }
+        }
+        self.normalize();
+    }
+
+    fn derivative(&self) -> Self {
+        Self::new(
+            self.exponents()
+                .into_iter()
+                .filter(|exponent| exponent % 2 == 1)
+                .map(|exponent| exponent - 1)
+                .collect(),
+        )
+    }
+
+    // The square root of a polynomial with only even exponents.
+    fn square_root(&self) -> Self {
+        Self::new(
+            self.exponents()
+                .into_iter()
+                .map(|exponent| exponent / 2)
+                .collect(),
+        )
+    }
+
+    // The square-free polynomials whose product with multiplicities is the polynomial.
+    fn square_free_factors(&self) -> Vec<(Self, usize)> {
+        let mut factors = Vec::new();
+        let mut repeated = self.gcd(&self.derivative());
+        let mut remaining = self.div_rem(&repeated).0;
+        let mut multiplicity = 1;
+        while remaining != Self::one() {
+            let common = remaining.gcd(&repeated);
+            let factor = remaining.div_rem(&common).0;
+            if factor != Self::one() {
+                factors.push((factor, multiplicity));
+            }
+            repeated = repeated.div_rem(&common).0;
+            remaining = common;
+            multiplicity += 1;
+        }
+        if repeated != Self::one() {
+            let square_root = repeated.square_root();
+            factors.extend(
+                square_root
+                    .square_free_factors()
+                    .into_iter()
+                    .map(|(factor, multiplicity)| (factor, 2 * multiplicity)),
+            );
+        }
+        factors
+    }
+
+    // The irreducible factors of a square-free polynomial.
+    //
+    // The polynomials v with v^2 = v modulo f form a space whose dimension
+    // is the number of factors of f.
+    // Each factor divides either v or v + 1, thus the gcds with the elements of a basis
+    // separate the factors.
+    fn berlekamp_factors(&self) -> Vec<Self> {
+        let degree = self.degree().unwrap_or(0);
+        if degree <= 1 {
+            return vec![self.clone()];
+        }
+        // Row i is x^(2i) + x^i modulo the polynomial.
+        let mut power = Self::one();
+        let rows = (0..degree)
+            .map(|row| {
+                let mut entry = power.clone();
+                entry.add_shifted(&Self::one(), row);
+                power = (&power * &Self::monomial(2)).div_rem(self).1;
+                entry.exponents()
+            })
+            .collect();
+        let basis = DenseBinMat::from_rows(degree, rows)
+            .transposed()
+            .nullspace();
+        let mut factors = vec![self.clone()];
+        for vector in basis.rows() {
+            if factors.len() == basis.number_of_rows() {
+                break;
+            }
+            let vector = Self::new(vector);
+            if vector.degree().unwrap_or(0) == 0 {
+                continue;
+            }
+            factors = factors
+                .into_iter()
+                .flat_map(|factor| {
+                    let common = factor.gcd(&vector);
+                    if common == Self::one() || common == factor {
+                        vec![factor]
+                    } else {
+                        let quotient = factor.div_rem(&common).0;
+                        vec![common, quotient]
+                    }
+                })
+                .collect();
+        }
+        factors
+    }
+}
+
+impl Add<&BinaryPolynomial> for &BinaryPolynomial {
+    type Output = BinaryPolynomial;
+
+    fn add(self, other: &BinaryPolynomial) -> BinaryPolynomial {
+        let mut sum = self.clone();
+        sum.add_shifted(other, 0);
+        sum
+    }
+}
+
+impl Mul<&BinaryPolynomial> for &BinaryPolynomial {
+    type Output = BinaryPolynomial;
+
+    fn mul(self, other: &BinaryPolynomial) -> BinaryPolynomial {
+        let mut product = BinaryPolynomial::zero();
+        for exponent in self.exponents() {
+            product.add_shifted(other, exponent);
+        }
+        product
+    }
+}
+
+impl fmt::Display for BinaryPolynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let terms = self.exponents().into_iter().map(|exponent| match exponent {
+            0 => "1".to_string(),
+            1 => "x".to_string(),
+            _ => format!("x^{}", exponent),
+        });
+        write!(f, "{}", terms.collect::<Vec<_>>().join(" + "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn product(factors: &[(BinaryPolynomial, usize)]) -> BinaryPolynomial {
+        factors
+            .iter()
+            .flat_map(|(factor, multiplicity)| (0..*multiplicity).map(move |_| factor))
+            .fold(BinaryPolynomial::one(), |product, factor| &product * factor)
+    }
+
+    #[test]
+    fn division_recovers_the_dividend() {
+        let dividend = BinaryPolynomial::new(vec![0, 3, 64, 65, 130]);
+        let divisor = BinaryPolynomial::new(vec![1, 2, 70]);
+        let (quotient, remainder) = dividend.div_rem(&divisor);
+        assert!(remainder.degree() < divisor.degree());
+        assert_eq!(&(&quotient * &divisor) + &remainder, dividend);
+        let common = BinaryPolynomial::new(vec![0, 1, 3]);
+        let gcd = (&common * &dividend).gcd(&(&common * &divisor));
+        assert_eq!(gcd.div_rem(&common).1, BinaryPolynomial::zero());
+    }
+
+    #[test]
+    fn golay_polynomial_divides_x23_plus_1() {
+        let factors = BinaryPolynomial::cyclic_modulus(23).factors();
+        let degrees: Vec<_> = factors
+            .iter()
+            .map(|(factor, multiplicity)| (factor.degree().unwrap(), *multiplicity))
+            .collect();
+        assert_eq!(degrees, vec![(1, 1), (11, 1), (11, 1)]);
+        assert!(factors
+            .iter()
+            .any(|(factor, _)| factor == &BinaryPolynomial::new(vec![0, 2, 4, 5, 6, 10, 11])));
+        assert!(factors.iter().all(|(factor, _)| factor.is_irreducible()));
+    }
+
+    #[test]
+    fn factors_multiply_back() {
+        for length in 1..70 {
+            let modulus = BinaryPolynomial::cyclic_modulus(length);
+            assert_eq!(product(&modulus.factors()), modulus);
+        }
+        let polynomial = BinaryPolynomial::new(vec![0, 2, 3, 7, 8, 9, 13, 100]);
+        assert_eq!(product(&polynomial.factors()), polynomial);
+        assert!(BinaryPolynomial::one().factors().is_empty());
+        assert!(!BinaryPolynomial::new(vec![0, 2]).is_irreducible());
+    }
+}
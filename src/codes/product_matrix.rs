@@ -0,0 +1,565 @@
+use super::{wiedemann_rank, LinearCode, QuasiCyclicMatrix};
+use crate::css::Css;
+use rand::Rng;
+use sparse_bin_mat::SparseBinMat;
+use std::io::{self, Write};
+
+/// A check matrix of a product code whose rows and columns
+/// are generated one at a time.
+///
+/// The matrix is a horizontal concatenation of Kronecker products of
+/// small base matrices, possibly lifted by circulant permutation matrices.
+/// Only the base matrices are stored,
+/// such that the checks of codes with millions of qubits
+/// can be written to disk in the [alist](ProductMatrix::write_alist) format
+/// without ever holding the full matrix in memory.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::{CssCode, LinearCode, ProductMatrix};
+/// let code = LinearCode::repetition_code(3);
+/// let checks = ProductMatrix::hypergraph_product(&code, &code);
+///
+/// assert_eq!(checks.x.number_of_columns(), 13);
+/// assert_eq!(checks.x.number_of_rows(), 6);
+/// assert_eq!(
+///     checks.x.to_sparse_bin_mat(),
+///     CssCode::hypergraph_product(&code, &code).stabilizers.x
+/// );
+///
+/// let mut alist = Vec::new();
+/// checks.z.write_alist(&mut alist).unwrap();
+/// assert!(String::from_utf8(alist).unwrap().starts_with("13 6\n"));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ProductMatrix {
+    lift_factor: usize,
+    num_block_rows: usize,
+    blocks: Vec<KroneckerBlock>,
+}
+
+impl ProductMatrix {
+    /// Returns the X and Z checks of the hypergraph product of two linear codes.
+    ///
+    /// The checks are the same as the ones of
+    /// [`CssCode::hypergraph_product`](crate::codes::CssCode::hypergraph_product).
+    pub fn hypergraph_product(first_code: &LinearCode, second_code: &LinearCode) -> Css<Self> {
+        let first = LiftedBase::from_binary(first_code.parity_check_matrix());
+        let second = LiftedBase::from_binary(second_code.parity_check_matrix());
+        let first_transposed = first.transposed(1);
+        let second_transposed = second.transposed(1);
+        Css {
+            x: Self::new(
+                1,
+                vec![
+                    (LiftedBase::identity(first.num_columns), second.clone()),
+                    (first_transposed, LiftedBase::identity(second.num_rows())),
+                ],
+            ),
+            z: Self::new(
+                1,
+                vec![
+                    (first.clone(), LiftedBase::identity(second.num_columns)),
+                    (LiftedBase::identity(first.num_rows()), second_transposed),
+                ],
+            ),
+        }
+    }
+
+    /// Returns the X and Z checks of the lifted product of two quasi-cyclic matrices.
+    ///
+    /// For base matrices `A` and `B`, the X checks are `[A ⊗ I, I ⊗ B]`
+    /// and the Z checks are `[I ⊗ B*, A* ⊗ I]` where `*` is the transpose
+    /// with each circulant replaced by its transpose.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the matrices have different lift factors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::{ProductMatrix, QuasiCyclicMatrix};
+    /// let matrix = QuasiCyclicMatrix::new(5, vec![vec![Some(0), Some(1), Some(3)]]);
+    /// let checks = ProductMatrix::lifted_product(&matrix, &matrix);
+    ///
+    /// let x_checks = checks.x.to_sparse_bin_mat();
+    /// let z_checks = checks.z.to_sparse_bin_mat();
+    /// assert_eq!(x_checks.number_of_columns(), (3 + 3) * 5);
+    /// assert!((&x_checks * &z_checks.transposed()).is_zero());
+    /// ```
+    pub fn lifted_product(first: &QuasiCyclicMatrix, second: &QuasiCyclicMatrix) -> Css<Self> {
+        if first.lift_factor() != second.lift_factor() {
+            panic!(
+                "lift factors {} and {} are different",
+                first.lift_factor(),
+                second.lift_factor()
+            );
+        }
+        let lift_factor = first.lift_factor();
+        let first = LiftedBase::from_quasi_cyclic(first);
+        let second = LiftedBase::from_quasi_cyclic(second);
+        let first_transposed = first.transposed(lift_factor);
+        let second_transposed = second.transposed(lift_factor);
+        Css {
+            x: Self::new(
+                lift_factor,
+                vec![
+                    (first.clone(), LiftedBase::identity(second.num_rows())),
+                    (LiftedBase::identity(first.num_rows()), second.clone()),
+                ],
+            ),
+            z: Self::new(
+                lift_factor,
+                vec![
+                    (LiftedBase::identity(first.num_columns), second_transposed),
+                    (first_transposed, LiftedBase::identity(second.num_columns)),
+                ],
+            ),
+        }
+    }
+
+    // All blocks must have the same number of rows.
+    fn new(lift_factor: usize, blocks: Vec<(LiftedBase, LiftedBase)>) -> Self {
+        let num_block_rows = blocks
+            .first()
+            .map(|(left, right)| left.num_rows() * right.num_rows())
+            .unwrap_or(0);
+        let mut offset = 0;
+        let blocks = blocks
+            .into_iter()
+            .map(|(left, right)| {
+                let block = KroneckerBlock::new(lift_factor, left, right, offset);
+                offset += block.num_block_columns();
+                block
+            })
+            .collect();
+        Self {
+            lift_factor,
+            num_block_rows,
+            blocks,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn number_of_rows(&self) -> usize {
+        self.num_block_rows * self.lift_factor
+    }
+
+    /// Returns the number of columns.
+    pub fn number_of_columns(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| block.num_block_columns())
+            .sum::<usize>()
+            * self.lift_factor
+    }
+
+    /// Returns the positions of the ones in the given row in increasing order.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the row is out of bound.
+    pub fn row(&self, row: usize) -> Vec<usize> {
+        self.assert_row_in_bound(row);
+        self.blocks
+            .iter()
+            .flat_map(|block| block.row(row))
+            .collect()
+    }
+
+    /// Returns the positions of the ones in the given column in increasing order.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the column is out of bound.
+    pub fn column(&self, column: usize) -> Vec<usize> {
+        self.block_of_column(column).column(column)
+    }
+
+    /// Returns an iterator over the rows.
+    pub fn rows(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        (0..self.number_of_rows()).map(move |row| self.row(row))
+    }
+
+    /// Returns an iterator over the columns.
+    pub fn columns(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        (0..self.number_of_columns()).map(move |column| self.column(column))
+    }
+
+    /// Returns the number of ones in the given row.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the row is out of bound.
+    pub fn row_weight(&self, row: usize) -> usize {
+        self.assert_row_in_bound(row);
+        self.blocks.iter().map(|block| block.row_weight(row)).sum()
+    }
+
+    /// Returns the number of ones in the given column.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the column is out of bound.
+    pub fn column_weight(&self, column: usize) -> usize {
+        self.block_of_column(column).column_weight(column)
+    }
+
+    /// Returns the rank of the matrix with high probability
+    /// computed one row at a time.
+    ///
+    /// This is [`approximate_rank`](crate::codes::approximate_rank)
+    /// without holding the matrix in memory.
+    /// For example, the number of logical qubits of a product code
+    /// is the length minus the ranks of both check matrices.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::codes::{LinearCode, ProductMatrix};
+    /// use rand::thread_rng;
+    ///
+    /// let code = LinearCode::repetition_code(5);
+    /// let checks = ProductMatrix::hypergraph_product(&code, &code);
+    /// let mut rng = thread_rng();
+    ///
+    /// let num_logicals = checks.x.number_of_columns()
+    ///     - checks.x.approximate_rank(&mut rng)
+    ///     - checks.z.approximate_rank(&mut rng);
+    /// assert_eq!(num_logicals, 1);
+    /// ```
+    pub fn approximate_rank<R: Rng>(&self, rng: &mut R) -> usize {
+        wiedemann_rank(
+            self.number_of_rows(),
+            self.number_of_columns(),
+            |apply| {
+                for (index, row) in self.rows().enumerate() {
+                    apply(index, &row);
+                }
+            },
+            rng,
+        )
+    }
+
+    /// Returns the matrix held in memory.
+    ///
+    /// This is only practical for matrices small enough to fit in memory.
+    pub fn to_sparse_bin_mat(&self) -> SparseBinMat {
+        SparseBinMat::new(self.number_of_columns(), self.rows().collect())
+    }
+
+    /// Writes the matrix in the alist format one row or column at a time.
+    ///
+    /// The first line is the number of columns and rows,
+    /// the second line is the maximum column and row weights,
+    /// followed by the line of column weights, the line of row weights,
+    /// then one line for each column and one line for each row
+    /// with the positions of their ones counted from 1
+    /// and padded with zeros to the maximum weight.
+    ///
+    /// The writer should be buffered for large matrices.
+    pub fn write_alist<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let column_weights =
+            || (0..self.number_of_columns()).map(|column| self.column_weight(column));
+        let row_weights = || (0..self.number_of_rows()).map(|row| self.row_weight(row));
+        let max_column_weight = column_weights().max().unwrap_or(0);
+        let max_row_weight = row_weights().max().unwrap_or(0);
+        writeln!(
+            writer,
+            "{} {}",
+            self.number_of_columns(),
+            self.number_of_rows()
+        )?;
+        writeln!(writer, "{} {}", max_column_weight, max_row_weight)?;
+        write_line(&mut writer, column_weights())?;
+        write_line(&mut writer, row_weights())?;
+        for column in self.columns() {
+            write_line(&mut writer, padded(column, max_column_weight))?;
+        }
+        for row in self.rows() {
+            write_line(&mut writer, padded(row, max_row_weight))?;
+        }
+        writer.flush()
+    }
+
+    fn assert_row_in_bound(&self, row: usize) {
+        if row >= self.number_of_rows() {
+            panic!(
+                "row {} is out of bound for {} rows",
+                row,
+                self.number_of_rows()
+            );
+        }
+    }
+
+    fn block_of_column(&self, column: usize) -> &KroneckerBlock {
+        let block_column = column / self.lift_factor;
+        self.blocks
+            .iter()
+            .find(|block| block_column < block.offset + block.num_block_columns())
+            .unwrap_or_else(|| {
+                panic!(
+                    "column {} is out of bound for {} columns",
+                    column,
+                    self.number_of_columns()
+                )
+            })
+    }
+}
+
+fn write_line<W: Write, I: Iterator<Item = usize>>(writer: &mut W, values: I) -> io::Result<()> {
+    let mut is_first = true;
+    for value in values {
+        if !is_first {
+            write!(writer, " ")?;
+        }
+        write!(writer, "{}", value)?;
+        is_first = false;
+    }
+    writeln!(writer)
+}
+
+// The positions counted from 1 followed by zeros up to the given weight.
+fn padded(positions: Vec<usize>, weight: usize) -> impl Iterator<Item = usize> {
+    let num_zeros = weight - positions.len();
+    positions
+        .into_iter()
+        .map(|position| position + 1)
+        .chain((0..num_zeros).map(|_| 0))
+}
+
+// The Kronecker product of two lifted base matrices whose block columns
+// start at the given offset in the full matrix.
+// The transposes are kept to generate the columns.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct KroneckerBlock {
+    lift_factor: usize,
+    left: LiftedBase,
+    right: LiftedBase,
+    left_transposed: LiftedBase,
+    right_transposed: LiftedBase,
+    offset: usize,
+}
+
+impl KroneckerBlock {
+    fn new(lift_factor: usize, left: LiftedBase, right: LiftedBase, offset: usize) -> Self {
+        Self {
+            lift_factor,
+            left_transposed: left.transposed(lift_factor),
+            right_transposed: right.transposed(lift_factor),
+            left,
+            right,
+            offset,
+        }
+    }
+
+    fn num_block_columns(&self) -> usize {
+        self.left.num_columns * self.right.num_columns
+    }
+
+    // The block row (a, b) with entries (c, s) in row a of the left matrix
+    // and (d, t) in row b of the right matrix has a circulant
+    // of shift s + t in block column (c, d).
+    fn row(&self, row: usize) -> impl Iterator<Item = usize> + '_ {
+        let (left_row, right_row, shift) = self.split_row(row);
+        self.left.rows[left_row]
+            .iter()
+            .flat_map(move |(left_column, left_shift)| {
+                self.right.rows[right_row]
+                    .iter()
+                    .map(move |(right_column, right_shift)| {
+                        let block_column =
+                            self.offset + left_column * self.right.num_columns + right_column;
+                        block_column * self.lift_factor
+                            + (shift + left_shift + right_shift) % self.lift_factor
+                    })
+            })
+    }
+
+    fn column(&self, column: usize) -> Vec<usize> {
+        let block_column = column / self.lift_factor - self.offset;
+        let (left_column, right_column) = (
+            block_column / self.right.num_columns,
+            block_column % self.right.num_columns,
+        );
+        let shift = column % self.lift_factor;
+        self.left_transposed.rows[left_column]
+            .iter()
+            .flat_map(|(left_row, left_shift)| {
+                self.right_transposed.rows[right_column].iter().map(
+                    move |(right_row, right_shift)| {
+                        let block_row = left_row * self.right.num_rows() + right_row;
+                        block_row * self.lift_factor
+                            + (shift + left_shift + right_shift) % self.lift_factor
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn row_weight(&self, row: usize) -> usize {
+        let (left_row, right_row, _) = self.split_row(row);
+        self.left.rows[left_row].len() * self.right.rows[right_row].len()
+    }
+
+    fn column_weight(&self, column: usize) -> usize {
+        let block_column = column / self.lift_factor - self.offset;
+        self.left_transposed.rows[block_column / self.right.num_columns].len()
+            * self.right_transposed.rows[block_column % self.right.num_columns].len()
+    }
+
+    fn split_row(&self, row: usize) -> (usize, usize, usize) {
+        let block_row = row / self.lift_factor;
+        (
+            block_row / self.right.num_rows(),
+            block_row % self.right.num_rows(),
+            row % self.lift_factor,
+        )
+    }
+}
+
+// A base matrix storing the column and shift of the circulant
+// of each non zero block of each row.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct LiftedBase {
+    num_columns: usize,
+    rows: Vec<Vec<(usize, usize)>>,
+}
+
+impl LiftedBase {
+    fn from_binary(matrix: &SparseBinMat) -> Self {
+        Self {
+            num_columns: matrix.number_of_columns(),
+            rows: matrix
+                .rows()
+                .map(|row| {
+                    row.non_trivial_positions()
+                        .map(|column| (column, 0))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    fn from_quasi_cyclic(matrix: &QuasiCyclicMatrix) -> Self {
+        let mut rows = vec![Vec::new(); matrix.num_block_rows()];
+        for (row, column, shift) in matrix.blocks() {
+            rows[row].push((column, shift));
+        }
+        Self {
+            num_columns: matrix.num_block_columns(),
+            rows,
+        }
+    }
+
+    fn identity(length: usize) -> Self {
+        Self {
+            num_columns: length,
+            rows: (0..length).map(|row| vec![(row, 0)]).collect(),
+        }
+    }
+
+    fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    // The transpose of a circulant of shift s is the circulant of shift -s.
+    fn transposed(&self, lift_factor: usize) -> Self {
+        let mut rows = vec![Vec::new(); self.num_columns];
+        for (row, entries) in self.rows.iter().enumerate() {
+            for (column, shift) in entries {
+                rows[*column].push((row, (lift_factor - shift) % lift_factor));
+            }
+        }
+        Self {
+            num_columns: self.num_rows(),
+            rows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::CssCode;
+    use rand::SeedableRng;
+
+    #[test]
+    fn hypergraph_product_matches_in_memory_construction() {
+        let first = LinearCode::repetition_code(4);
+        let second = LinearCode::hamming_code();
+        let checks = ProductMatrix::hypergraph_product(&first, &second);
+        let code = CssCode::hypergraph_product(&first, &second);
+        assert_eq!(checks.x.to_sparse_bin_mat(), code.stabilizers.x);
+        assert_eq!(checks.z.to_sparse_bin_mat(), code.stabilizers.z);
+    }
+
+    #[test]
+    fn approximate_rank_matches_elimination() {
+        let first = QuasiCyclicMatrix::new(
+            5,
+            vec![
+                vec![Some(0), None, Some(3)],
+                vec![Some(1), Some(2), Some(4)],
+            ],
+        );
+        let second = QuasiCyclicMatrix::new(5, vec![vec![Some(1), Some(0)]]);
+        let checks = ProductMatrix::lifted_product(&first, &second);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(123);
+        for matrix in [&checks.x, &checks.z] {
+            let code = LinearCode::from_parity_check_matrix(matrix.to_sparse_bin_mat());
+            assert_eq!(
+                matrix.approximate_rank(&mut rng),
+                code.len() - code.dimension()
+            );
+        }
+    }
+
+    #[test]
+    fn columns_are_rows_of_the_transpose() {
+        let first = QuasiCyclicMatrix::new(
+            4,
+            vec![vec![Some(0), None, Some(3)], vec![Some(1), Some(2), None]],
+        );
+        let second = QuasiCyclicMatrix::new(4, vec![vec![Some(1), Some(0)]]);
+        let checks = ProductMatrix::lifted_product(&first, &second);
+        for matrix in [&checks.x, &checks.z] {
+            let transposed = matrix.to_sparse_bin_mat().transposed();
+            for (column, positions) in matrix.columns().enumerate() {
+                let expected = transposed
+                    .row(column)
+                    .unwrap()
+                    .non_trivial_positions()
+                    .collect::<Vec<_>>();
+                assert_eq!(positions, expected);
+                assert_eq!(matrix.column_weight(column), expected.len());
+            }
+        }
+        let x_checks = checks.x.to_sparse_bin_mat();
+        assert!((&x_checks * &checks.z.to_sparse_bin_mat().transposed()).is_zero());
+    }
+
+    #[test]
+    fn alist_of_repetition_product() {
+        let code = LinearCode::repetition_code(2);
+        let checks = ProductMatrix::hypergraph_product(&code, &code);
+        let mut alist = Vec::new();
+        checks.x.write_alist(&mut alist).unwrap();
+        // X checks of the product of two repetition codes of length 2
+        // are [[0, 1, 4], [2, 3, 4]].
+        let expected = "5 2\n\
+                        2 3\n\
+                        1 1 1 1 2\n\
+                        3 3\n\
+                        1 0\n\
+                        1 0\n\
+                        2 0\n\
+                        2 0\n\
+                        1 2\n\
+                        1 2 5\n\
+                        3 4 5\n";
+        assert_eq!(String::from_utf8(alist).unwrap(), expected);
+    }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// How a random code was sampled, such that it can be sampled again.
+///
+/// The provenance records the sampler, its parameters, the seed
+/// of the random number generator and the version of the crate that sampled the code.
+/// It is kept in the serialization of the code,
+/// which is enough to reconstruct the exact code used in numerical results
+/// as long as the sampler of the recorded version is used.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::{LinearCode, RandomRegularCode};
+/// let code = LinearCode::random_regular_code()
+///     .num_bits(20)
+///     .num_checks(15)
+///     .bit_degree(3)
+///     .check_degree(4)
+///     .seed(123)
+///     .sample()
+///     .unwrap();
+///
+/// let provenance = code.provenance().unwrap();
+/// assert_eq!(provenance.sampler(), "random regular code");
+/// assert_eq!(provenance.parameter("bit_degree"), Some(3));
+/// assert_eq!(provenance.seed(), 123);
+/// assert_eq!(provenance.crate_version(), env!("CARGO_PKG_VERSION"));
+///
+/// let same_code = RandomRegularCode::from_provenance(provenance)
+///     .unwrap()
+///     .sample()
+///     .unwrap();
+/// assert_eq!(same_code, code);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct Provenance {
+    sampler: String,
+    parameters: BTreeMap<String, usize>,
+    seed: u64,
+    crate_version: String,
+}
+
+impl Provenance {
+    /// Creates the provenance of a code sampled by the given sampler from the given seed
+    /// with the current version of the crate.
+    pub fn new<S: Into<String>>(sampler: S, seed: u64) -> Self {
+        Self {
+            sampler: sampler.into(),
+            parameters: BTreeMap::new(),
+            seed,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Records the value of a parameter of the sampler.
+    pub fn with_parameter<S: Into<String>>(mut self, name: S, value: usize) -> Self {
+        self.parameters.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the name of the sampler.
+    pub fn sampler(&self) -> &str {
+        &self.sampler
+    }
+
+    /// Returns the parameters of the sampler sorted by name.
+    pub fn parameters(&self) -> &BTreeMap<String, usize> {
+        &self.parameters
+    }
+
+    /// Returns the value of the given parameter or None if it was not recorded.
+    pub fn parameter(&self, name: &str) -> Option<usize> {
+        self.parameters.get(name).copied()
+    }
+
+    /// Returns the seed of the random number generator.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the version of the crate that sampled the code.
+    pub fn crate_version(&self) -> &str {
+        &self.crate_version
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (", self.sampler)?;
+        for (index, (name, value)) in self.parameters.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} = {}", name, value)?;
+        }
+        write!(
+            f,
+            ") from seed {} with ldpc {}",
+            self.seed, self.crate_version
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_lists_sorted_parameters() {
+        let provenance = Provenance::new("sampler", 7)
+            .with_parameter("b", 2)
+            .with_parameter("a", 1);
+        assert_eq!(
+            provenance.to_string(),
+            format!(
+                "sampler (a = 1, b = 2) from seed 7 with ldpc {}",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec, SparseBinVecBase};
+
+/// A quasi-cyclic matrix stored as a base matrix of shifts.
+///
+/// Each entry of the base matrix is either a zero block
+/// or a circulant permutation matrix of size `lift_factor`
+/// given by its shift.
+/// The circulant with shift `s` has a one in row `i` and column `(i + s) % lift_factor`.
+///
+/// Only the base matrix is stored,
+/// thus the memory is reduced by the lift factor compared to a [`SparseBinMat`].
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::QuasiCyclicMatrix;
+/// use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+///
+/// let matrix = QuasiCyclicMatrix::new(3, vec![vec![Some(0), Some(1), None]]);
+/// let expected = SparseBinMat::new(9, vec![vec![0, 4], vec![1, 5], vec![2, 3]]);
+/// assert_eq!(matrix.expanded(), expected);
+///
+/// let vector = SparseBinVec::new(9, vec![0, 3]);
+/// assert_eq!(matrix.syndrome_of(&vector), &expected * &vector);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedQuasiCyclicMatrix")]
+pub struct QuasiCyclicMatrix {
+    lift_factor: usize,
+    num_block_columns: usize,
+    shifts: Vec<Vec<Option<usize>>>,
+}
+
+impl QuasiCyclicMatrix {
+    /// Creates a quasi-cyclic matrix from the lift factor and the shifts of each block.
+    /// None represents a zero block.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the lift factor is 0, if the rows of the base matrix
+    /// have different lengths or if a shift is not smaller than the lift factor.
+    pub fn new(lift_factor: usize, shifts: Vec<Vec<Option<usize>>>) -> Self {
+        let num_block_columns = shifts.first().map(|row| row.len()).unwrap_or(0);
+        Self::try_with_num_block_columns(lift_factor, num_block_columns, shifts)
+            .unwrap_or_else(|reason| panic!("{}", reason))
+    }
+
+    // Checks that the lift factor is positive, that every row of the base matrix
+    // has the given length and that every shift is smaller than the lift factor.
+    fn try_with_num_block_columns(
+        lift_factor: usize,
+        num_block_columns: usize,
+        shifts: Vec<Vec<Option<usize>>>,
+    ) -> Result<Self, String> {
+        if lift_factor == 0 {
+            return Err("lift factor must be positive".to_string());
+        }
+        if shifts.iter().any(|row| row.len() != num_block_columns) {
+            return Err(format!(
+                "rows of the base matrix don't all have {} blocks",
+                num_block_columns
+            ));
+        }
+        if let Some(shift) = shifts
+            .iter()
+            .flatten()
+            .flatten()
+            .find(|shift| **shift >= lift_factor)
+        {
+            return Err(format!(
+                "shift {} is out of bound for lift factor {}",
+                shift, lift_factor
+            ));
+        }
+        Ok(Self {
+            lift_factor,
+            num_block_columns,
+            shifts,
+        })
+    }
+
+    /// Recovers the base matrix of a matrix made of circulant permutation blocks
+    /// of the given size or returns None if the matrix doesn't have this structure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::codes::QuasiCyclicMatrix;
+    /// use sparse_bin_mat::SparseBinMat;
+    ///
+    /// let matrix = QuasiCyclicMatrix::new(3, vec![vec![Some(0), Some(1), None]]);
+    /// let expanded = matrix.expanded();
+    ///
+    /// assert_eq!(QuasiCyclicMatrix::from_matrix(&expanded, 3), Some(matrix));
+    /// assert_eq!(QuasiCyclicMatrix::from_matrix(&expanded, 2), None);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the lift factor is 0.
+    pub fn from_matrix(matrix: &SparseBinMat, lift_factor: usize) -> Option<Self> {
+        if lift_factor == 0 {
+            panic!("lift factor must be positive");
+        }
+        if matrix.number_of_rows() % lift_factor != 0
+            || matrix.number_of_columns() % lift_factor != 0
+        {
+            return None;
+        }
+        let num_block_columns = matrix.number_of_columns() / lift_factor;
+        let mut shifts = vec![vec![None; num_block_columns]; matrix.number_of_rows() / lift_factor];
+        let mut counts = vec![vec![0; num_block_columns]; shifts.len()];
+        for (row, column) in matrix.non_trivial_elements() {
+            let (block_row, block_column) = (row / lift_factor, column / lift_factor);
+            let shift = (column % lift_factor + lift_factor - row % lift_factor) % lift_factor;
+            if *shifts[block_row][block_column].get_or_insert(shift) != shift {
+                return None;
+            }
+            counts[block_row][block_column] += 1;
+        }
+        // Distinct columns of a row have distinct shifts in a block,
+        // such that a block with a single shift has at most one entry per row.
+        let is_circulant = counts
+            .iter()
+            .flatten()
+            .all(|count| *count == 0 || *count == lift_factor);
+        is_circulant.then_some(Self {
+            lift_factor,
+            num_block_columns,
+            shifts,
+        })
+    }
+
+    /// Returns the size of each circulant block.
+    pub fn lift_factor(&self) -> usize {
+        self.lift_factor
+    }
+
+    /// Returns the number of rows of the base matrix.
+    pub fn num_block_rows(&self) -> usize {
+        self.shifts.len()
+    }
+
+    /// Returns the number of columns of the base matrix.
+    pub fn num_block_columns(&self) -> usize {
+        self.num_block_columns
+    }
+
+    /// Returns the shift of the given block, None if the block is zero
+    /// or out of bound.
+    pub fn shift(&self, block_row: usize, block_column: usize) -> Option<usize> {
+        self.shifts
+            .get(block_row)
+            .and_then(|row| row.get(block_column))
+            .cloned()
+            .flatten()
+    }
+
+    /// Returns an iterator over the block row, block column and shift
+    /// of each non zero block.
+    pub fn blocks(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.shifts.iter().enumerate().flat_map(|(row, shifts)| {
+            shifts
+                .iter()
+                .enumerate()
+                .filter_map(move |(column, shift)| shift.map(|shift| (row, column, shift)))
+        })
+    }
+
+    /// Returns the number of rows of the expanded matrix.
+    pub fn number_of_rows(&self) -> usize {
+        self.num_block_rows() * self.lift_factor
+    }
+
+    /// Returns the number of columns of the expanded matrix.
+    pub fn number_of_columns(&self) -> usize {
+        self.num_block_columns * self.lift_factor
+    }
+
+    /// Returns the number of ones in the expanded matrix.
+    pub fn number_of_ones(&self) -> usize {
+        self.blocks().count() * self.lift_factor
+    }
+
+    /// Returns the expanded matrix.
+    pub fn expanded(&self) -> SparseBinMat {
+        let rows = self
+            .shifts
+            .iter()
+            .flat_map(|shifts| {
+                (0..self.lift_factor).map(move |row| {
+                    let mut positions = shifts
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(column, shift)| {
+                            shift.map(|shift| {
+                                column * self.lift_factor + (row + shift) % self.lift_factor
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    positions.sort_unstable();
+                    positions
+                })
+            })
+            .collect();
+        SparseBinMat::new(self.number_of_columns(), rows)
+    }
+
+    /// Returns the product of the matrix with the vector,
+    /// computed block by block without expanding the matrix.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vector length is different from the number of columns.
+    pub fn syndrome_of<T>(&self, vector: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if vector.len() != self.number_of_columns() {
+            panic!(
+                "vector of length {} for matrix with {} columns",
+                vector.len(),
+                self.number_of_columns()
+            );
+        }
+        let mut column_blocks = vec![Vec::new(); self.num_block_columns];
+        for (row, column, shift) in self.blocks() {
+            column_blocks[column].push((row, shift));
+        }
+        let mut syndrome = vec![false; self.number_of_rows()];
+        for bit in vector.non_trivial_positions() {
+            let offset = bit % self.lift_factor;
+            for (row, shift) in column_blocks[bit / self.lift_factor].iter() {
+                let check =
+                    row * self.lift_factor + (offset + self.lift_factor - shift) % self.lift_factor;
+                syndrome[check] ^= true;
+            }
+        }
+        let positions = syndrome
+            .into_iter()
+            .enumerate()
+            .filter(|(_, is_one)| *is_one)
+            .map(|(check, _)| check)
+            .collect();
+        SparseBinVec::new(self.number_of_rows(), positions)
+    }
+}
+
+// The fields of a serialized quasi-cyclic matrix before they are validated.
+#[derive(Deserialize)]
+struct UncheckedQuasiCyclicMatrix {
+    lift_factor: usize,
+    num_block_columns: usize,
+    shifts: Vec<Vec<Option<usize>>>,
+}
+
+impl TryFrom<UncheckedQuasiCyclicMatrix> for QuasiCyclicMatrix {
+    type Error = String;
+
+    fn try_from(matrix: UncheckedQuasiCyclicMatrix) -> Result<Self, Self::Error> {
+        Self::try_with_num_block_columns(
+            matrix.lift_factor,
+            matrix.num_block_columns,
+            matrix.shifts,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_matrix() -> QuasiCyclicMatrix {
+        let mut rng = StdRng::seed_from_u64(123);
+        let shifts = (0..3)
+            .map(|_| {
+                (0..6)
+                    .map(|_| {
+                        if rng.gen_bool(0.8) {
+                            Some(rng.gen_range(0..7))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        QuasiCyclicMatrix::new(7, shifts)
+    }
+
+    #[test]
+    fn expanded_matrix_has_circulant_blocks() {
+        let matrix = random_matrix();
+        let expanded = matrix.expanded();
+        assert_eq!(expanded.number_of_rows(), 21);
+        assert_eq!(expanded.number_of_columns(), 42);
+        assert_eq!(expanded.number_of_ones(), matrix.number_of_ones());
+        for (row, column, shift) in matrix.blocks() {
+            for offset in 0..7 {
+                assert_eq!(
+                    expanded.is_one_at(row * 7 + offset, column * 7 + (offset + shift) % 7),
+                    Some(true)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn base_matrix_is_recovered_from_expanded_matrix() {
+        let matrix = random_matrix();
+        assert_eq!(
+            QuasiCyclicMatrix::from_matrix(&matrix.expanded(), 7),
+            Some(matrix)
+        );
+        // A block with two ones in a row is not a permutation.
+        let matrix = SparseBinMat::new(4, vec![vec![0, 1], vec![1]]);
+        assert_eq!(QuasiCyclicMatrix::from_matrix(&matrix, 2), None);
+    }
+
+    #[test]
+    fn deserialized_matrix_is_validated() {
+        let matrix = random_matrix();
+        let json = serde_json::to_string(&matrix).unwrap();
+        assert_eq!(
+            serde_json::from_str::<QuasiCyclicMatrix>(&json).unwrap(),
+            matrix
+        );
+        for invalid in [
+            r#"{"lift_factor":3,"num_block_columns":1,"shifts":[[7]]}"#,
+            r#"{"lift_factor":0,"num_block_columns":0,"shifts":[]}"#,
+            r#"{"lift_factor":3,"num_block_columns":2,"shifts":[[0]]}"#,
+        ] {
+            assert!(serde_json::from_str::<QuasiCyclicMatrix>(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn structured_syndrome_matches_expanded_matrix() {
+        let matrix = random_matrix();
+        let expanded = matrix.expanded();
+        let mut rng = StdRng::seed_from_u64(123);
+        for _ in 0..10 {
+            let positions = (0..42).filter(|_| rng.gen_bool(0.2)).collect();
+            let vector = SparseBinVec::new(42, positions);
+            assert_eq!(matrix.syndrome_of(&vector), &expanded * &vector);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn shifts_must_be_smaller_than_lift_factor() {
+        QuasiCyclicMatrix::new(3, vec![vec![Some(3)]]);
+    }
+}
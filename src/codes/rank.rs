@@ -0,0 +1,276 @@
+use rand::Rng;
+use sparse_bin_mat::SparseBinMat;
+
+/// Returns the rank over GF(2) of a matrix with high probability
+/// without Gaussian elimination.
+///
+/// This is a randomized Wiedemann algorithm.
+/// Since the rank doesn't change when extending the field,
+/// it is computed over GF(2^64) for the matrix `D A^T E A`
+/// where `D` and `E` are random diagonal matrices.
+/// The rank of `A` is read from the degree of the minimal polynomial
+/// of this matrix which is found by Berlekamp-Massey from a random projection
+/// of the sequence of its powers applied to a random vector.
+///
+/// The memory is linear in the size of the matrix
+/// and the time is about the rank times the number of ones.
+/// This makes it practical for sparse matrices too large for elimination.
+/// The result is never larger than the rank
+/// and is wrong with a probability of about `n^2 / 2^64` for `n` columns.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::{approximate_rank, LinearCode};
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let code = LinearCode::random_regular_code()
+///     .num_bits(40)
+///     .num_checks(30)
+///     .bit_degree(3)
+///     .check_degree(4)
+///     .sample_with(&mut StdRng::seed_from_u64(123))
+///     .unwrap();
+/// let matrix = code.parity_check_matrix();
+///
+/// let rank = approximate_rank(matrix, &mut StdRng::seed_from_u64(123));
+/// assert_eq!(rank, code.len() - code.dimension());
+/// ```
+pub fn approximate_rank<R: Rng>(matrix: &SparseBinMat, rng: &mut R) -> usize {
+    wiedemann_rank(
+        matrix.number_of_rows(),
+        matrix.number_of_columns(),
+        |apply| {
+            for (index, row) in matrix.rows().enumerate() {
+                apply(index, row.as_slice());
+            }
+        },
+        rng,
+    )
+}
+
+// Consecutive zero discrepancies once the sequence is twice as long as
+// the linear complexity before assuming that the minimal polynomial is found.
+const EARLY_TERMINATION: usize = 16;
+
+// The rank of a matrix given by a function calling back with
+// the index and the positions of each row.
+pub(crate) fn wiedemann_rank<F, R>(
+    num_rows: usize,
+    num_columns: usize,
+    for_each_row: F,
+    rng: &mut R,
+) -> usize
+where
+    F: Fn(&mut dyn FnMut(usize, &[usize])),
+    R: Rng,
+{
+    if num_rows == 0 || num_columns == 0 {
+        return 0;
+    }
+    let column_scaling = random_nonzero_elements(num_columns, rng);
+    let row_scaling = random_nonzero_elements(num_rows, rng);
+    let projection = (0..num_columns).map(|_| rng.gen()).collect::<Vec<u64>>();
+    let mut vector = (0..num_columns).map(|_| rng.gen()).collect::<Vec<u64>>();
+    let mut row_values = vec![0; num_rows];
+    let mut berlekamp_massey = BerlekampMassey::new();
+    for _ in 0..2 * num_columns + EARLY_TERMINATION {
+        berlekamp_massey.push(dot(&projection, &vector));
+        if berlekamp_massey.is_stable() {
+            break;
+        }
+        // vector <- D A^T E A vector
+        for_each_row(&mut |row, positions| {
+            let value = positions
+                .iter()
+                .fold(0, |value, position| value ^ vector[*position]);
+            row_values[row] = multiply(row_scaling[row], value);
+        });
+        vector.iter_mut().for_each(|value| *value = 0);
+        for_each_row(&mut |row, positions| {
+            for position in positions {
+                vector[*position] ^= row_values[row];
+            }
+        });
+        for (value, scaling) in vector.iter_mut().zip(column_scaling.iter()) {
+            *value = multiply(*value, *scaling);
+        }
+    }
+    berlekamp_massey.rank()
+}
+
+fn random_nonzero_elements<R: Rng>(length: usize, rng: &mut R) -> Vec<u64> {
+    (0..length)
+        .map(|_| loop {
+            let element = rng.gen();
+            if element != 0 {
+                break element;
+            }
+        })
+        .collect()
+}
+
+fn dot(first: &[u64], second: &[u64]) -> u64 {
+    first
+        .iter()
+        .zip(second)
+        .fold(0, |sum, (first, second)| sum ^ multiply(*first, *second))
+}
+
+// The product in GF(2^64) defined by the irreducible polynomial
+// x^64 + x^4 + x^3 + x + 1.
+fn multiply(mut first: u64, mut second: u64) -> u64 {
+    let mut product = 0;
+    while second != 0 {
+        if second & 1 == 1 {
+            product ^= first;
+        }
+        second >>= 1;
+        let overflow = first >> 63;
+        first <<= 1;
+        if overflow == 1 {
+            first ^= 0x1b;
+        }
+    }
+    product
+}
+
+// The inverse is x^(2^64 - 2) since the multiplicative group has 2^64 - 1 elements.
+fn inverse(element: u64) -> u64 {
+    let mut result = 1;
+    let mut power = element;
+    let mut exponent = u64::MAX - 1;
+    while exponent != 0 {
+        if exponent & 1 == 1 {
+            result = multiply(result, power);
+        }
+        power = multiply(power, power);
+        exponent >>= 1;
+    }
+    result
+}
+
+// Finds the shortest linear recurrence of a sequence given one term at a time.
+struct BerlekampMassey {
+    sequence: Vec<u64>,
+    connection: Vec<u64>,
+    previous_connection: Vec<u64>,
+    previous_discrepancy: u64,
+    shift: usize,
+    complexity: usize,
+    num_zero_discrepancies: usize,
+}
+
+impl BerlekampMassey {
+    fn new() -> Self {
+        Self {
+            sequence: Vec::new(),
+            connection: vec![1],
+            previous_connection: vec![1],
+            previous_discrepancy: 1,
+            shift: 1,
+            complexity: 0,
+            num_zero_discrepancies: 0,
+        }
+    }
+
+    fn push(&mut self, term: u64) {
+        self.sequence.push(term);
+        let length = self.sequence.len();
+        let discrepancy = self
+            .connection
+            .iter()
+            .enumerate()
+            .take(self.complexity + 1)
+            .fold(0, |sum, (index, coefficient)| {
+                sum ^ multiply(*coefficient, self.sequence[length - 1 - index])
+            });
+        if discrepancy == 0 {
+            self.shift += 1;
+            self.num_zero_discrepancies += 1;
+            return;
+        }
+        self.num_zero_discrepancies = 0;
+        let factor = multiply(discrepancy, inverse(self.previous_discrepancy));
+        let mut connection = self.connection.clone();
+        let needed = self.previous_connection.len() + self.shift;
+        if connection.len() < needed {
+            connection.resize(needed, 0);
+        }
+        for (index, coefficient) in self.previous_connection.iter().enumerate() {
+            connection[index + self.shift] ^= multiply(factor, *coefficient);
+        }
+        if 2 * self.complexity < length {
+            self.previous_connection = std::mem::replace(&mut self.connection, connection);
+            self.complexity = length - self.complexity;
+            self.previous_discrepancy = discrepancy;
+            self.shift = 1;
+        } else {
+            self.connection = connection;
+            self.shift += 1;
+        }
+    }
+
+    fn is_stable(&self) -> bool {
+        self.sequence.len() >= 2 * self.complexity + EARLY_TERMINATION
+            && self.num_zero_discrepancies >= EARLY_TERMINATION
+    }
+
+    // The minimal polynomial is the reversed connection polynomial.
+    // Its constant term is zero when the matrix is singular
+    // and then the rank is one less than its degree.
+    fn rank(&self) -> usize {
+        let constant = self.connection.get(self.complexity).cloned().unwrap_or(0);
+        if constant == 0 {
+            self.complexity.saturating_sub(1)
+        } else {
+            self.complexity
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn inverse_is_multiplicative_inverse() {
+        for element in [1, 2, 0x1b, u64::MAX, 123456789] {
+            assert_eq!(multiply(element, inverse(element)), 1);
+        }
+    }
+
+    #[test]
+    fn rank_of_special_matrices() {
+        let mut rng = StdRng::seed_from_u64(123);
+        assert_eq!(approximate_rank(&SparseBinMat::empty(), &mut rng), 0);
+        assert_eq!(approximate_rank(&SparseBinMat::zeros(4, 5), &mut rng), 0);
+        assert_eq!(approximate_rank(&SparseBinMat::identity(10), &mut rng), 10);
+        let repeated = SparseBinMat::new(3, vec![vec![0, 1], vec![0, 1], vec![1, 2], vec![0, 2]]);
+        assert_eq!(approximate_rank(&repeated, &mut rng), 2);
+    }
+
+    #[test]
+    fn rank_matches_elimination_for_random_codes() {
+        let mut rng = StdRng::seed_from_u64(123);
+        for (num_bits, num_checks, bit_degree, check_degree) in
+            [(20, 15, 3, 4), (30, 30, 4, 4), (24, 40, 5, 3)]
+        {
+            let code = LinearCode::random_regular_code()
+                .num_bits(num_bits)
+                .num_checks(num_checks)
+                .bit_degree(bit_degree)
+                .check_degree(check_degree)
+                .sample_with(&mut rng)
+                .unwrap();
+            assert_eq!(
+                approximate_rank(code.parity_check_matrix(), &mut rng),
+                code.len() - code.dimension()
+            );
+        }
+    }
+}
@@ -0,0 +1,416 @@
+use super::{BinaryPolynomial, CssCode, LinearCode};
+use indexmap::IndexMap;
+use sparse_bin_mat::SparseBinMat;
+use std::fmt;
+
+/// A code built by a [`CodeRegistry`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum RegisteredCode {
+    Linear(LinearCode),
+    Css(CssCode),
+}
+
+impl RegisteredCode {
+    /// Returns the linear code or None if it is a CSS code.
+    pub fn linear(self) -> Option<LinearCode> {
+        match self {
+            Self::Linear(code) => Some(code),
+            Self::Css(_) => None,
+        }
+    }
+
+    /// Returns the CSS code or None if it is a linear code.
+    pub fn css(self) -> Option<CssCode> {
+        match self {
+            Self::Linear(_) => None,
+            Self::Css(code) => Some(code),
+        }
+    }
+
+    /// Returns the number of bits or qubits of the code.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Linear(code) => code.len(),
+            Self::Css(code) => code.len(),
+        }
+    }
+
+    /// Checks if the code has no bit or qubit.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// An error when resolving a code from a string.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RegistryError {
+    /// The string is not of the form `name`, `name(a, ...)`,
+    /// `name[a, ...]` or `name[[a, ...]]`.
+    InvalidSyntax(String),
+    UnknownFamily(String),
+    InvalidParameters {
+        family: String,
+        parameters: Vec<usize>,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSyntax(spec) => write!(f, "invalid code specification: {}", spec),
+            Self::UnknownFamily(family) => write!(f, "unknown code family: {}", family),
+            Self::InvalidParameters { family, parameters } => {
+                write!(
+                    f,
+                    "invalid parameters {:?} for family {}",
+                    parameters, family
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+type Constructor = Box<dyn Fn(&[usize]) -> Option<RegisteredCode> + Send + Sync>;
+
+/// Named families of codes built from integer parameters.
+///
+/// A code is specified by a string such as `"hamming(4)"` or `"bb[[144,12,12]]"`
+/// where the parameters are given between parentheses, brackets or double brackets.
+/// This makes it possible to choose codes in configuration files
+/// or from the command line.
+///
+/// The [default](CodeRegistry::default) registry contains the families
+///
+/// - `repetition(n)` the repetition code of length `n`,
+/// - `hamming(r)` the Hamming code with `r` checks,
+/// - `steane` and `shor`,
+/// - `surface(d)` the planar surface code of distance `d`,
+/// - `toric(d)` the toric code of distance `d`,
+/// - `bb[[n,k,d]]` the bivariate bicycle codes of
+///   [Bravyi et al.](https://arxiv.org/abs/2308.07915).
+///
+/// Other families are added with [`register`](CodeRegistry::register).
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::{CodeRegistry, LinearCode, RegisteredCode};
+///
+/// let registry = CodeRegistry::default();
+///
+/// let code = registry.resolve("hamming(3)").unwrap();
+/// assert!(code.linear().unwrap().has_same_codespace(&LinearCode::hamming_code()));
+///
+/// let code = registry.resolve("surface(5)").unwrap().css().unwrap();
+/// assert_eq!(code.len(), 41);
+///
+/// assert!(registry.resolve("surface").is_err());
+/// assert!(registry.resolve("color(3)").is_err());
+/// ```
+pub struct CodeRegistry {
+    families: IndexMap<String, Constructor>,
+}
+
+impl CodeRegistry {
+    /// Creates a registry without any family.
+    pub fn empty() -> Self {
+        Self {
+            families: IndexMap::new(),
+        }
+    }
+
+    /// Adds a family of codes under the given name.
+    ///
+    /// The constructor returns None if the parameters are invalid.
+    /// A family with the same name is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::codes::{CodeRegistry, CssCode, LinearCode, RegisteredCode};
+    ///
+    /// let mut registry = CodeRegistry::empty();
+    /// registry.register("hgp_repetition", |parameters| match parameters {
+    ///     [first, second] if *first > 1 && *second > 1 => {
+    ///         Some(RegisteredCode::Css(CssCode::hypergraph_product(
+    ///             &LinearCode::repetition_code(*first),
+    ///             &LinearCode::repetition_code(*second),
+    ///         )))
+    ///     }
+    ///     _ => None,
+    /// });
+    ///
+    /// let code = registry.resolve("hgp_repetition(3, 4)").unwrap();
+    /// assert_eq!(code.len(), 3 * 4 + 2 * 3);
+    /// ```
+    pub fn register<F>(&mut self, name: &str, constructor: F) -> &mut Self
+    where
+        F: Fn(&[usize]) -> Option<RegisteredCode> + Send + Sync + 'static,
+    {
+        self.families
+            .insert(name.to_string(), Box::new(constructor));
+        self
+    }
+
+    /// Returns the names of the families in the order they were registered.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.families.keys().map(|name| name.as_str())
+    }
+
+    /// Checks if there is a family with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.families.contains_key(name)
+    }
+
+    /// Builds the code given by the specification.
+    pub fn resolve(&self, spec: &str) -> Result<RegisteredCode, RegistryError> {
+        let (family, parameters) = parse(spec)?;
+        let constructor = self
+            .families
+            .get(family)
+            .ok_or_else(|| RegistryError::UnknownFamily(family.to_string()))?;
+        constructor(&parameters).ok_or_else(|| RegistryError::InvalidParameters {
+            family: family.to_string(),
+            parameters,
+        })
+    }
+}
+
+impl Default for CodeRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register("repetition", |parameters| match parameters {
+                [length] if *length > 0 => {
+                    Some(RegisteredCode::Linear(LinearCode::repetition_code(*length)))
+                }
+                _ => None,
+            })
+            .register("hamming", |parameters| match parameters {
+                [num_checks] if (2..=MAX_HAMMING_CHECKS).contains(num_checks) => {
+                    Some(RegisteredCode::Linear(hamming_code(*num_checks)))
+                }
+                _ => None,
+            })
+            .register("steane", |parameters| {
+                parameters
+                    .is_empty()
+                    .then(|| RegisteredCode::Css(CssCode::steane_code()))
+            })
+            .register("shor", |parameters| {
+                parameters
+                    .is_empty()
+                    .then(|| RegisteredCode::Css(CssCode::shor_code()))
+            })
+            .register("surface", |parameters| match parameters {
+                [distance] if *distance > 1 => {
+                    let code = LinearCode::repetition_code(*distance);
+                    Some(RegisteredCode::Css(CssCode::hypergraph_product(
+                        &code, &code,
+                    )))
+                }
+                _ => None,
+            })
+            .register("toric", |parameters| match parameters {
+                [distance] if *distance > 1 => {
+                    Some(RegisteredCode::Css(CssCode::toric_code(*distance)))
+                }
+                _ => None,
+            })
+            .register("bb", |parameters| {
+                BIVARIATE_BICYCLE_CODES
+                    .iter()
+                    .find(|code| code.parameters == parameters)
+                    .map(|code| RegisteredCode::Css(code.build()))
+            });
+        registry
+    }
+}
+
+impl fmt::Debug for CodeRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodeRegistry")
+            .field("families", &self.families.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+// Splits a specification into the family name and the parameters.
+fn parse(spec: &str) -> Result<(&str, Vec<usize>), RegistryError> {
+    let invalid = || RegistryError::InvalidSyntax(spec.to_string());
+    let spec = spec.trim();
+    let (name, parameters) = match spec.find(['(', '[']) {
+        Some(start) => {
+            let (name, rest) = spec.split_at(start);
+            let parameters = ["[[", "(", "["]
+                .iter()
+                .zip(["]]", ")", "]"])
+                .find_map(|(open, close)| rest.strip_prefix(open)?.strip_suffix(close))
+                .ok_or_else(invalid)?;
+            (name.trim(), parameters)
+        }
+        None => (spec, ""),
+    };
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(invalid());
+    }
+    let parameters = if parameters.trim().is_empty() {
+        Vec::new()
+    } else {
+        parameters
+            .split(',')
+            .map(|parameter| parameter.trim().parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?
+    };
+    Ok((name, parameters))
+}
+
+// Larger Hamming codes have more than a million bits.
+const MAX_HAMMING_CHECKS: usize = 20;
+
+// The check i contains the bits whose index plus one has
+// the (r - 1 - i)-th binary digit set.
+fn hamming_code(num_checks: usize) -> LinearCode {
+    let length = (1 << num_checks) - 1;
+    let checks = (0..num_checks)
+        .map(|check| {
+            let digit = num_checks - 1 - check;
+            (0..length)
+                .filter(|bit| ((bit + 1) >> digit) & 1 == 1)
+                .collect()
+        })
+        .collect();
+    LinearCode::from_parity_check_matrix(SparseBinMat::new(length, checks))
+}
+
+// A bivariate bicycle code defined by the polynomials A and B
+// in x and y with x^l = y^m = 1, given as lists of monomials x^i y^j.
+struct BivariateBicycle {
+    parameters: [usize; 3],
+    l: usize,
+    m: usize,
+    a: [(usize, usize); 3],
+    b: [(usize, usize); 3],
+}
+
+const BIVARIATE_BICYCLE_CODES: [BivariateBicycle; 5] = [
+    BivariateBicycle {
+        parameters: [72, 12, 6],
+        l: 6,
+        m: 6,
+        a: [(3, 0), (0, 1), (0, 2)],
+        b: [(0, 3), (1, 0), (2, 0)],
+    },
+    BivariateBicycle {
+        parameters: [90, 8, 10],
+        l: 15,
+        m: 3,
+        a: [(9, 0), (0, 1), (0, 2)],
+        b: [(0, 0), (2, 0), (7, 0)],
+    },
+    BivariateBicycle {
+        parameters: [108, 8, 10],
+        l: 9,
+        m: 6,
+        a: [(3, 0), (0, 1), (0, 2)],
+        b: [(0, 3), (1, 0), (2, 0)],
+    },
+    BivariateBicycle {
+        parameters: [144, 12, 12],
+        l: 12,
+        m: 6,
+        a: [(3, 0), (0, 1), (0, 2)],
+        b: [(0, 3), (1, 0), (2, 0)],
+    },
+    BivariateBicycle {
+        parameters: [288, 12, 18],
+        l: 12,
+        m: 12,
+        a: [(3, 0), (0, 2), (0, 7)],
+        b: [(0, 3), (1, 0), (2, 0)],
+    },
+];
+
+impl BivariateBicycle {
+    // The X checks are [A | B] and the Z checks are [B^T | A^T].
+    fn build(&self) -> CssCode {
+        let (a, b) = (self.matrix(&self.a), self.matrix(&self.b));
+        CssCode::new(
+            &LinearCode::from_parity_check_matrix(a.horizontal_concat_with(&b)),
+            &LinearCode::from_parity_check_matrix(
+                b.transposed().horizontal_concat_with(&a.transposed()),
+            ),
+        )
+    }
+
+    // The sum of the Kronecker products of the circulants of x^i and y^j
+    // for each monomial of the polynomial.
+    fn matrix(&self, polynomial: &[(usize, usize)]) -> SparseBinMat {
+        let block = self.l * self.m;
+        polynomial
+            .iter()
+            .map(|(i, j)| {
+                BinaryPolynomial::monomial(*i)
+                    .circulant(self.l)
+                    .kron_with(&BinaryPolynomial::monomial(*j).circulant(self.m))
+            })
+            .fold(SparseBinMat::zeros(block, block), |sum, term| &sum + &term)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_all_delimiters() {
+        assert_eq!(parse("steane"), Ok(("steane", vec![])));
+        assert_eq!(parse(" surface( 5 ) "), Ok(("surface", vec![5])));
+        assert_eq!(parse("code[7, 4, 3]"), Ok(("code", vec![7, 4, 3])));
+        assert_eq!(parse("bb[[144,12,12]]"), Ok(("bb", vec![144, 12, 12])));
+        assert!(parse("bb[[144,12,12]").is_err());
+        assert!(parse("surface(-1)").is_err());
+        assert!(parse("(3)").is_err());
+    }
+
+    #[test]
+    fn hamming_codes_have_distance_three() {
+        for num_checks in 2..5 {
+            let code = hamming_code(num_checks);
+            assert_eq!(code.len(), (1 << num_checks) - 1);
+            assert_eq!(code.dimension(), code.len() - num_checks);
+            assert_eq!(code.minimal_distance(), Some(3));
+        }
+    }
+
+    #[test]
+    fn bivariate_bicycle_codes_have_expected_parameters() {
+        let registry = CodeRegistry::default();
+        for code in BIVARIATE_BICYCLE_CODES.iter() {
+            let [length, dimension, _] = code.parameters;
+            let resolved = registry
+                .resolve(&format!(
+                    "bb[[{},{},{}]]",
+                    length, dimension, code.parameters[2]
+                ))
+                .unwrap()
+                .css()
+                .unwrap();
+            assert_eq!(resolved.len(), length);
+            assert_eq!(resolved.num_x_logicals(), dimension);
+        }
+        assert_eq!(
+            registry.resolve("bb[[144,12,10]]"),
+            Err(RegistryError::InvalidParameters {
+                family: "bb".to_string(),
+                parameters: vec![144, 12, 10]
+            })
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+use varisat::solver::SolverError;
+use varisat::{ExtendFormula, Lit, Solver};
+
+// Finds a minimum weight binary vector in the kernel of the checks
+// with a SAT solver.
+//
+// Each parity constraint is a chain of XOR gates with auxiliary variables
+// and the weight is bounded by a sequential counter whose outputs
+// are true if at least some number of bits are 1.
+// The weight is then bisected by assuming the outputs false
+// such that the solver reuses what it learned between calls.
+// If an odd check is given, the vector must have an odd overlap with it.
+// Otherwise, the vector must be non zero.
+//
+// Returns None if no such vector exists.
+pub(crate) fn minimum_weight_kernel_vector(
+    checks: &SparseBinMat,
+    odd_check: Option<SparseBinSlice>,
+) -> Result<Option<SparseBinVec>, SolverError> {
+    let mut solver = Solver::new();
+    let bits = (0..checks.number_of_columns())
+        .map(|_| solver.new_lit())
+        .collect::<Vec<_>>();
+    let is_non_zero_required = odd_check.is_none();
+    for (check, parity) in checks
+        .rows()
+        .map(|check| (check, false))
+        .chain(odd_check.map(|check| (check, true)))
+    {
+        add_parity(&mut solver, &bits, check, parity);
+    }
+    if is_non_zero_required {
+        solver.add_clause(&bits);
+    }
+    let at_least = add_counter(&mut solver, &bits);
+
+    if !solver.solve()? {
+        return Ok(None);
+    }
+    let mut best = solution(&solver, &bits);
+    let mut lower_bound = 0;
+    while lower_bound < best.weight() {
+        let bound = (lower_bound + best.weight()) / 2;
+        // At most bound bits are 1.
+        solver.assume(&[!at_least[bound]]);
+        if solver.solve()? {
+            best = solution(&solver, &bits);
+        } else {
+            lower_bound = bound + 1;
+        }
+    }
+    Ok(Some(best))
+}
+
+// Constrains the sum of the bits of the check to the given parity.
+fn add_parity(solver: &mut Solver, bits: &[Lit], check: SparseBinSlice, parity: bool) {
+    let mut positions = check.non_trivial_positions();
+    let mut sum = match positions.next() {
+        Some(position) => bits[position],
+        None => {
+            if parity {
+                solver.add_clause(&[]);
+            }
+            return;
+        }
+    };
+    for position in positions {
+        let bit = bits[position];
+        let next = solver.new_lit();
+        solver.add_clause(&[!sum, !bit, !next]);
+        solver.add_clause(&[sum, bit, !next]);
+        solver.add_clause(&[sum, !bit, next]);
+        solver.add_clause(&[!sum, bit, next]);
+        sum = next;
+    }
+    solver.add_clause(&[if parity { sum } else { !sum }]);
+}
+
+// Returns the outputs of a sequential counter of the bits
+// where output j is true if at least j + 1 bits are 1.
+//
+// The outputs are only forced to be true,
+// which is enough to bound the weight from above by assuming them false.
+fn add_counter(solver: &mut Solver, bits: &[Lit]) -> Vec<Lit> {
+    let mut at_least: Vec<Lit> = Vec::new();
+    for bit in bits {
+        let mut next = Vec::with_capacity(at_least.len() + 1);
+        for count in 0..=at_least.len() {
+            let output = solver.new_lit();
+            if count == 0 {
+                solver.add_clause(&[!*bit, output]);
+            } else {
+                solver.add_clause(&[!*bit, !at_least[count - 1], output]);
+            }
+            if let Some(previous) = at_least.get(count) {
+                solver.add_clause(&[!*previous, output]);
+            }
+            next.push(output);
+        }
+        at_least = next;
+    }
+    at_least
+}
+
+fn solution(solver: &Solver, bits: &[Lit]) -> SparseBinVec {
+    let model = solver.model().unwrap();
+    let positions = bits
+        .iter()
+        .enumerate()
+        .filter(|(_, bit)| model[bit.var().index()].is_positive())
+        .map(|(position, _)| position)
+        .collect();
+    SparseBinVec::new(bits.len(), positions)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::codes::{CssCode, LinearCode};
+
+    #[test]
+    fn hamming_code_has_distance_3() {
+        let code = LinearCode::hamming_code();
+        assert_eq!(code.minimal_distance_sat().unwrap(), Some(3));
+    }
+
+    #[test]
+    fn toric_code_has_distance_3() {
+        let code = CssCode::toric_code(3);
+        let logical = code.minimum_weight_logical_sat().unwrap().unwrap();
+        assert_eq!(logical.weight(), 3);
+        assert!(code.has_logical(&logical));
+        assert!(!code.has_stabilizer(&logical));
+    }
+}
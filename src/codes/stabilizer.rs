@@ -0,0 +1,284 @@
+use super::InformationSet;
+use itertools::Itertools;
+use pauli::{Pauli, PauliOperator, X, Y, Z};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+
+/// A quantum stabilizer code defined by commuting Pauli operators.
+///
+/// Unlike a [`CssCode`](super::CssCode), the stabilizers can mix X and Z.
+/// They are stored in symplectic form where the first half of each row
+/// is the X part and the second half the Z part of a stabilizer.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::StabilizerCode;
+/// use pauli::{PauliOperator, X, Y, Z};
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = StabilizerCode::five_qubit_code();
+/// assert_eq!(code.num_logical_qubits(), 1);
+/// assert!(code.verify_distance_at_least(3));
+///
+/// let error = PauliOperator::new(5, vec![1], vec![Y]);
+/// assert_eq!(code.syndrome_of(&error), SparseBinVec::new(4, vec![0, 1, 3]));
+///
+/// let logical = PauliOperator::new(5, vec![0, 1, 2, 3, 4], vec![X; 5]);
+/// assert!(code.has_logical(&logical));
+/// assert!(!code.has_stabilizer(&logical));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct StabilizerCode {
+    stabilizers: SparseBinMat,
+}
+
+impl StabilizerCode {
+    /// Creates a code from its stabilizer generators.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the stabilizers have different lengths or don't commute.
+    pub fn new(stabilizers: &[PauliOperator]) -> Self {
+        Self::try_new(stabilizers).expect("[Error]")
+    }
+
+    /// Creates a code from its stabilizer generators or returns an error
+    /// if they have different lengths or don't commute.
+    pub fn try_new(stabilizers: &[PauliOperator]) -> Result<Self, StabilizerError> {
+        let length = stabilizers.first().map(|stabilizer| stabilizer.len());
+        if let Some(stabilizer) = stabilizers
+            .iter()
+            .find(|stabilizer| Some(stabilizer.len()) != length)
+        {
+            return Err(StabilizerError::DifferentLengths(
+                length.unwrap(),
+                stabilizer.len(),
+            ));
+        }
+        if let Some((first, second)) = (0..stabilizers.len())
+            .tuple_combinations()
+            .find(|(first, second)| !stabilizers[*first].commutes_with(&stabilizers[*second]))
+        {
+            return Err(StabilizerError::NonCommutingStabilizers(first, second));
+        }
+        let length = length.unwrap_or(0);
+        Ok(Self {
+            stabilizers: SparseBinMat::new(
+                2 * length,
+                stabilizers
+                    .iter()
+                    .map(|stabilizer| symplectic(stabilizer).non_trivial_positions().collect())
+                    .collect(),
+            ),
+        })
+    }
+
+    /// Returns the [[5,1,3]] code whose stabilizers are
+    /// the cyclic shifts of XZZXI.
+    pub fn five_qubit_code() -> Self {
+        let stabilizers = (0..4)
+            .map(|shift| {
+                let mut positions = (shift..shift + 4)
+                    .map(|position| position % 5)
+                    .zip([X, Z, Z, X])
+                    .collect::<Vec<_>>();
+                positions.sort_by_key(|(position, _)| *position);
+                let (positions, paulis) = positions.into_iter().unzip();
+                PauliOperator::new(5, positions, paulis)
+            })
+            .collect::<Vec<_>>();
+        Self::new(&stabilizers)
+    }
+
+    /// Returns the number of qubits in the code.
+    pub fn len(&self) -> usize {
+        self.stabilizers.number_of_columns() / 2
+    }
+
+    /// Checks if the code has no qubit.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of stabilizer generators.
+    pub fn num_stabilizers(&self) -> usize {
+        self.stabilizers.number_of_rows()
+    }
+
+    /// Returns the number of logical qubits encoded by the code.
+    pub fn num_logical_qubits(&self) -> usize {
+        let columns = (0..2 * self.len()).collect::<Vec<_>>();
+        self.len() - InformationSet::new(&self.stabilizers, &columns).rank()
+    }
+
+    /// Returns the stabilizer generators of the code.
+    pub fn stabilizers(&self) -> impl Iterator<Item = PauliOperator> + '_ {
+        self.stabilizers.rows().map(move |row| {
+            let paulis = (0..self.len())
+                .filter_map(|qubit| {
+                    let is_x = row.is_one_at(qubit) == Some(true);
+                    let is_z = row.is_one_at(qubit + self.len()) == Some(true);
+                    match (is_x, is_z) {
+                        (true, false) => Some((qubit, X)),
+                        (true, true) => Some((qubit, Y)),
+                        (false, true) => Some((qubit, Z)),
+                        (false, false) => None,
+                    }
+                })
+                .collect::<Vec<(usize, Pauli)>>();
+            let (positions, paulis) = paulis.into_iter().unzip();
+            PauliOperator::new(self.len(), positions, paulis)
+        })
+    }
+
+    /// Returns the syndrome of the given operator,
+    /// that is the stabilizers anticommuting with it.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the operator length is different from the code length.
+    pub fn syndrome_of(&self, operator: &PauliOperator) -> SparseBinVec {
+        if operator.len() != self.len() {
+            panic!(
+                "operator of length {} for code of length {}",
+                operator.len(),
+                self.len()
+            );
+        }
+        let x = operator.x_part().into_raw_positions();
+        let z = operator.z_part().into_raw_positions();
+        // Swapping the X and Z parts turns the symplectic product into a dot product.
+        let swapped = z
+            .into_iter()
+            .chain(x.into_iter().map(|qubit| qubit + self.len()))
+            .collect();
+        &self.stabilizers * &SparseBinVec::new(2 * self.len(), swapped)
+    }
+
+    /// Checks if an operator is a (potentially trivial) logical operator of the code.
+    pub fn has_logical(&self, operator: &PauliOperator) -> bool {
+        self.syndrome_of(operator).is_zero()
+    }
+
+    /// Checks if an operator is a stabilizer of the code, up to a phase.
+    pub fn has_stabilizer(&self, operator: &PauliOperator) -> bool {
+        let generators = (0..self.num_stabilizers()).collect::<Vec<_>>();
+        InformationSet::new(&self.stabilizers.transposed(), &generators)
+            .solve(symplectic(operator).as_view())
+            .is_some()
+    }
+
+    /// Checks that the code has no non trivial logical operator
+    /// of weight less than the given distance.
+    pub fn verify_distance_at_least(&self, distance: usize) -> bool {
+        self.logical_of_weight_less_than(distance).is_none()
+    }
+
+    /// Returns a non trivial logical operator of weight less than the given weight
+    /// or None if there is no such operator.
+    ///
+    /// The weight classes are searched in parallel
+    /// and the returned operator is the first one of the smallest weight class
+    /// in lexicographic order of the qubits.
+    ///
+    /// # Warning
+    ///
+    /// This enumerates all operators of weight less than the given weight.
+    pub fn logical_of_weight_less_than(&self, weight: usize) -> Option<PauliOperator> {
+        (1..weight).into_par_iter().find_map_first(|weight| {
+            (0..self.len()).into_par_iter().find_map_first(|first| {
+                (first + 1..self.len())
+                    .combinations(weight - 1)
+                    .flat_map(|rest| {
+                        let qubits = std::iter::once(first).chain(rest).collect::<Vec<_>>();
+                        (0..weight)
+                            .map(|_| [X, Y, Z])
+                            .multi_cartesian_product()
+                            .map(move |paulis| {
+                                PauliOperator::new(self.len(), qubits.clone(), paulis)
+                            })
+                    })
+                    .find(|operator| self.has_logical(operator) && !self.has_stabilizer(operator))
+            })
+        })
+    }
+}
+
+// The X part followed by the Z part of the operator.
+fn symplectic(operator: &PauliOperator) -> SparseBinVec {
+    let length = operator.len();
+    let positions = operator
+        .x_part()
+        .into_raw_positions()
+        .into_iter()
+        .chain(
+            operator
+                .z_part()
+                .into_raw_positions()
+                .into_iter()
+                .map(|qubit| qubit + length),
+        )
+        .collect();
+    SparseBinVec::new(2 * length, positions)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum StabilizerError {
+    DifferentLengths(usize, usize),
+    NonCommutingStabilizers(usize, usize),
+}
+
+impl std::fmt::Display for StabilizerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DifferentLengths(first, second) => {
+                write!(
+                    f,
+                    "stabilizers of different lengths: {} & {}",
+                    first, second
+                )
+            }
+            Self::NonCommutingStabilizers(first, second) => {
+                write!(f, "stabilizers {} and {} don't commute", first, second)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StabilizerError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn five_qubit_code_has_distance_3() {
+        let code = StabilizerCode::five_qubit_code();
+        assert_eq!(code.len(), 5);
+        assert_eq!(code.num_stabilizers(), 4);
+        assert!(code.verify_distance_at_least(3));
+        let logical = code.logical_of_weight_less_than(4).unwrap();
+        assert_eq!(logical.weight(), 3);
+        for stabilizer in code.stabilizers() {
+            assert!(code.has_stabilizer(&stabilizer));
+            assert!(code.has_logical(&stabilizer));
+        }
+    }
+
+    #[test]
+    fn stabilizers_must_commute() {
+        let x = PauliOperator::new(2, vec![0], vec![X]);
+        let z = PauliOperator::new(2, vec![0], vec![Z]);
+        assert_eq!(
+            StabilizerCode::try_new(&[x.clone(), z]),
+            Err(StabilizerError::NonCommutingStabilizers(0, 1))
+        );
+        let short = PauliOperator::new(1, vec![0], vec![X]);
+        assert_eq!(
+            StabilizerCode::try_new(&[x, short]),
+            Err(StabilizerError::DifferentLengths(2, 1))
+        );
+    }
+}
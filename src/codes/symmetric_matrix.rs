@@ -0,0 +1,276 @@
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec, SparseBinVecBase};
+
+/// A matrix invariant under a group of translations stored
+/// as one representative row per orbit.
+///
+/// The group is a product of cyclic groups given by their periods.
+/// An element of the group is indexed in mixed radix,
+/// the last period varying the fastest,
+/// such that the element `(u, v)` of periods `(l, m)` has index `u * m + v`.
+/// Rows and columns are split in orbits of the size of the group
+/// where row or column `o * g + e` is the image of the element `e`
+/// in the orbit `o` for a group of size `g`.
+/// The group acts by translating the elements of both rows and columns
+/// while preserving their orbits.
+///
+/// Only the rows of the identity element are stored,
+/// the other rows being reconstructed on demand.
+/// This is the layout of the toric code and of the bivariate bicycle codes
+/// whose stabilizers are split in two blocks of qubits.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::{CssCode, SymmetricMatrix};
+/// use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+///
+/// // The cyclic repetition code.
+/// let matrix = SparseBinMat::new(3, vec![vec![0, 1], vec![1, 2], vec![0, 2]]);
+/// let compressed = SymmetricMatrix::compress(&matrix, vec![3]).unwrap();
+/// assert_eq!(compressed.representatives(), &[vec![0, 1]]);
+/// assert_eq!(compressed.row(2), vec![0, 2]);
+/// assert_eq!(compressed.expanded(), matrix);
+///
+/// let vector = SparseBinVec::new(3, vec![0]);
+/// assert_eq!(compressed.syndrome_of(&vector), &matrix * &vector);
+///
+/// let code = CssCode::toric_code(10);
+/// let stabilizers = SymmetricMatrix::compress(code.x_stabs_binary(), vec![10, 10]).unwrap();
+/// assert_eq!(stabilizers.representatives().len(), 1);
+/// assert_eq!(stabilizers.number_of_rows(), 100);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct SymmetricMatrix {
+    periods: Vec<usize>,
+    num_column_orbits: usize,
+    representatives: Vec<Vec<usize>>,
+}
+
+impl SymmetricMatrix {
+    /// Creates a matrix from the periods of the translations,
+    /// the number of orbits of columns and the columns of the
+    /// representative row of each orbit of rows.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a period is 0 or if a column is out of bound.
+    pub fn new(
+        periods: Vec<usize>,
+        num_column_orbits: usize,
+        mut representatives: Vec<Vec<usize>>,
+    ) -> Self {
+        if periods.contains(&0) {
+            panic!("periods must be positive");
+        }
+        let num_columns = num_column_orbits * periods.iter().product::<usize>();
+        for row in representatives.iter_mut() {
+            if let Some(column) = row.iter().find(|column| **column >= num_columns) {
+                panic!(
+                    "column {} is out of bound for {} columns",
+                    column, num_columns
+                );
+            }
+            row.sort_unstable();
+            row.dedup();
+        }
+        Self {
+            periods,
+            num_column_orbits,
+            representatives,
+        }
+    }
+
+    /// Compresses a matrix invariant under the translations of the given periods
+    /// or returns None if it is not invariant.
+    pub fn compress(matrix: &SparseBinMat, periods: Vec<usize>) -> Option<Self> {
+        if periods.contains(&0) {
+            return None;
+        }
+        let group_size = periods.iter().product::<usize>();
+        if matrix.number_of_rows() % group_size != 0 || matrix.number_of_columns() % group_size != 0
+        {
+            return None;
+        }
+        let representatives = matrix
+            .rows()
+            .step_by(group_size)
+            .map(|row| row.non_trivial_positions().collect())
+            .collect();
+        let compressed = Self {
+            periods,
+            num_column_orbits: matrix.number_of_columns() / group_size,
+            representatives,
+        };
+        matrix
+            .rows()
+            .enumerate()
+            .all(|(row, positions)| positions.as_slice() == compressed.row(row))
+            .then_some(compressed)
+    }
+
+    /// Returns the periods of the translations.
+    pub fn periods(&self) -> &[usize] {
+        &self.periods
+    }
+
+    /// Returns the number of translations, which is the size of each orbit.
+    pub fn group_size(&self) -> usize {
+        self.periods.iter().product()
+    }
+
+    /// Returns the representative row of each orbit of rows.
+    pub fn representatives(&self) -> &[Vec<usize>] {
+        &self.representatives
+    }
+
+    /// Returns the number of orbits of rows.
+    pub fn num_row_orbits(&self) -> usize {
+        self.representatives.len()
+    }
+
+    /// Returns the number of orbits of columns.
+    pub fn num_column_orbits(&self) -> usize {
+        self.num_column_orbits
+    }
+
+    /// Returns the number of rows of the expanded matrix.
+    pub fn number_of_rows(&self) -> usize {
+        self.num_row_orbits() * self.group_size()
+    }
+
+    /// Returns the number of columns of the expanded matrix.
+    pub fn number_of_columns(&self) -> usize {
+        self.num_column_orbits * self.group_size()
+    }
+
+    /// Returns the number of ones in the expanded matrix.
+    pub fn number_of_ones(&self) -> usize {
+        self.representatives.iter().map(Vec::len).sum::<usize>() * self.group_size()
+    }
+
+    /// Returns the sorted columns of the given row.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the row is out of bound.
+    pub fn row(&self, row: usize) -> Vec<usize> {
+        if row >= self.number_of_rows() {
+            panic!(
+                "row {} is out of bound for {} rows",
+                row,
+                self.number_of_rows()
+            );
+        }
+        let group_size = self.group_size();
+        let element = row % group_size;
+        let mut columns = self.representatives[row / group_size]
+            .iter()
+            .map(|column| {
+                column - column % group_size + self.translate(column % group_size, element)
+            })
+            .collect::<Vec<_>>();
+        columns.sort_unstable();
+        columns
+    }
+
+    /// Returns an iterator over the sorted columns of each row.
+    pub fn rows(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        (0..self.number_of_rows()).map(|row| self.row(row))
+    }
+
+    /// Returns the expanded matrix.
+    pub fn expanded(&self) -> SparseBinMat {
+        SparseBinMat::new(self.number_of_columns(), self.rows().collect())
+    }
+
+    /// Returns the product of the matrix with the vector
+    /// without expanding the matrix.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vector length is different from the number of columns.
+    pub fn syndrome_of<T>(&self, vector: &SparseBinVecBase<T>) -> SparseBinVec
+    where
+        T: std::ops::Deref<Target = [usize]>,
+    {
+        if vector.len() != self.number_of_columns() {
+            panic!(
+                "vector of length {} for matrix with {} columns",
+                vector.len(),
+                self.number_of_columns()
+            );
+        }
+        let positions = (0..self.number_of_rows())
+            .filter(|row| {
+                self.row(*row)
+                    .into_iter()
+                    .filter(|column| vector.is_one_at(*column) == Some(true))
+                    .count()
+                    % 2
+                    == 1
+            })
+            .collect();
+        SparseBinVec::new(self.number_of_rows(), positions)
+    }
+
+    // The sum of two elements of the group.
+    fn translate(&self, element: usize, translation: usize) -> usize {
+        let mut element = element;
+        let mut translation = translation;
+        let mut sum = 0;
+        let mut stride = 1;
+        for period in self.periods.iter().rev() {
+            sum += (element % period + translation % period) % period * stride;
+            element /= period;
+            translation /= period;
+            stride *= period;
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::{CodeRegistry, CssCode};
+
+    #[test]
+    fn bivariate_bicycle_codes_are_compressed() {
+        let code = CodeRegistry::default()
+            .resolve("bb[[144,12,12]]")
+            .unwrap()
+            .css()
+            .unwrap();
+        for stabilizers in [code.x_stabs_binary(), code.z_stabs_binary()] {
+            let compressed = SymmetricMatrix::compress(stabilizers, vec![12, 6]).unwrap();
+            assert_eq!(compressed.num_row_orbits(), 1);
+            assert_eq!(compressed.num_column_orbits(), 2);
+            assert_eq!(compressed.number_of_ones(), stabilizers.number_of_ones());
+            assert_eq!(&compressed.expanded(), stabilizers);
+            assert!(
+                serde_json::to_string(&compressed).unwrap().len()
+                    < serde_json::to_string(stabilizers).unwrap().len() / 10
+            );
+        }
+    }
+
+    #[test]
+    fn syndrome_matches_expanded_matrix() {
+        let code = CssCode::toric_code(4);
+        let compressed = SymmetricMatrix::compress(code.z_stabs_binary(), vec![4, 4]).unwrap();
+        let vector = SparseBinVec::new(32, vec![0, 5, 17, 30]);
+        assert_eq!(
+            compressed.syndrome_of(&vector),
+            code.z_stabs_binary() * &vector
+        );
+    }
+
+    #[test]
+    fn matrix_without_symmetry_is_not_compressed() {
+        let matrix = SparseBinMat::new(4, vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![0, 1]]);
+        assert_eq!(SymmetricMatrix::compress(&matrix, vec![4]), None);
+        assert_eq!(SymmetricMatrix::compress(&matrix, vec![3]), None);
+        assert!(SymmetricMatrix::compress(&matrix, vec![1]).is_some());
+    }
+}
@@ -1,5 +1,6 @@
 use std::ops::Deref;
 
+use crate::decoders::{PackedCssSyndrome, PackedSyndrome};
 use pauli::{Pauli, PauliOperator};
 use serde::{Deserialize, Serialize};
 use sparse_bin_mat::{SparseBinSlice, SparseBinVec, SparseBinVecBase};
@@ -138,7 +139,11 @@ where
         self.both(|syndrome| syndrome.is_zero())
     }
 
-    pub fn as_view(&self) -> CssSyndromeView {
+    pub fn as_view(&self) -> CssSyndromeView<'_> {
         self.map(|syndrome| syndrome.as_view())
     }
+
+    pub fn pack(&self) -> PackedCssSyndrome {
+        self.map(PackedSyndrome::pack)
+    }
 }
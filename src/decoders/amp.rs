@@ -0,0 +1,280 @@
+use super::{
+    Capabilities, Capability, ClassicalSyndromeDecoder, DecoderCapabilities, LinearDecoder,
+    SoftSyndromeDecoder, SyndromeDecoder,
+};
+use crate::noise::Probability;
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+
+// Keeps the magnetizations away from ±1 such that the messages stay finite.
+const MAX_MAGNETIZATION: f64 = 1.0 - 1e-12;
+
+/// An approximate message passing decoder.
+///
+/// Instead of storing a message from each bit to each of its checks,
+/// the decoder only stores the posterior log-likelihood ratio of each bit
+/// and recovers the message to a check by removing the reaction of this check
+/// to first order, that is the Onsager correction of approximate message passing.
+/// This approximation is accurate when each check has many bits
+/// or when the priors are strongly structured such that every message is small,
+/// which is where belief propagation is the most expensive.
+/// The messages from the checks can be damped to help convergence
+/// on dense parity check matrices with many short cycles.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{AmpDecoder, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::hamming_code();
+/// let decoder = AmpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10)
+///     .with_damping(0.2);
+///
+/// // Belief propagation without damping fails on this error.
+/// let error = SparseBinVec::new(7, vec![6]);
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AmpDecoder {
+    parity_mat: SparseBinMat,
+    likelyhoods: Vec<f64>,
+    num_iterations: usize,
+    damping: f64,
+}
+
+impl AmpDecoder {
+    pub fn new(parity_mat: &SparseBinMat, probability: Probability, num_iterations: usize) -> Self {
+        Self {
+            parity_mat: parity_mat.clone(),
+            likelyhoods: vec![probability.llr(); parity_mat.number_of_columns()],
+            num_iterations,
+            damping: 0.0,
+        }
+    }
+
+    /// Sets the fraction of the previous message from each check
+    /// kept when the message is updated.
+    ///
+    /// A damping of 0, the default, replaces the messages at each iteration.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the damping is not between 0 (inclusive) and 1 (exclusive).
+    pub fn with_damping(mut self, damping: f64) -> Self {
+        if !(0.0..1.0).contains(&damping) {
+            panic!("damping {} is not between 0 and 1", damping);
+        }
+        self.damping = damping;
+        self
+    }
+
+    /// Returns the fraction of the previous messages kept at each iteration.
+    pub fn damping(&self) -> f64 {
+        self.damping
+    }
+
+    pub fn num_bits(&self) -> usize {
+        self.parity_mat.number_of_columns()
+    }
+
+    pub fn num_checks(&self) -> usize {
+        self.parity_mat.number_of_rows()
+    }
+
+    // Returns the posteriors after the messages converged to the syndrome
+    // or the maximum number of iterations is reached.
+    fn run(&self, syndrome: SparseBinSlice, likelyhoods: &[f64]) -> Vec<f64> {
+        let mut messages = self
+            .parity_mat
+            .rows()
+            .map(|check| vec![0.0; check.weight()])
+            .collect::<Vec<_>>();
+        let mut posteriors = likelyhoods.to_vec();
+        for _ in 0..self.num_iterations {
+            if self.satisfies(&posteriors, &syndrome) {
+                break;
+            }
+            self.update_checks(&mut messages, &posteriors, &syndrome);
+            posteriors = self.posteriors_from(&messages, likelyhoods);
+        }
+        posteriors
+    }
+
+    fn update_checks(
+        &self,
+        messages: &mut [Vec<f64>],
+        posteriors: &[f64],
+        syndrome: &SparseBinSlice,
+    ) {
+        for ((check, bits), messages) in self.parity_mat.rows().enumerate().zip(messages) {
+            // The magnetization of a bit without the reaction of the check.
+            let cavities = bits
+                .non_trivial_positions()
+                .zip(messages.iter())
+                .map(|(bit, message)| {
+                    let magnetization = (posteriors[bit] / 2.0).tanh();
+                    let onsager = (1.0 - magnetization * magnetization) * message / 2.0;
+                    (magnetization - onsager).clamp(-MAX_MAGNETIZATION, MAX_MAGNETIZATION)
+                })
+                .collect::<Vec<_>>();
+            let sign = if syndrome.is_one_at(check).unwrap() {
+                -1.0
+            } else {
+                1.0
+            };
+            // The products without each bit are computed from prefix and suffix products
+            // to avoid dividing by 0.
+            let mut suffixes = vec![1.0; cavities.len() + 1];
+            for (position, cavity) in cavities.iter().enumerate().rev() {
+                suffixes[position] = suffixes[position + 1] * cavity;
+            }
+            let mut prefix = 1.0;
+            for (position, message) in messages.iter_mut().enumerate() {
+                let updated = sign * 2.0 * (prefix * suffixes[position + 1]).atanh();
+                *message = self.damping * *message + (1.0 - self.damping) * updated;
+                prefix *= cavities[position];
+            }
+        }
+    }
+
+    fn posteriors_from(&self, messages: &[Vec<f64>], likelyhoods: &[f64]) -> Vec<f64> {
+        let mut posteriors = likelyhoods.to_vec();
+        for (bits, messages) in self.parity_mat.rows().zip(messages) {
+            for (bit, message) in bits.non_trivial_positions().zip(messages) {
+                posteriors[bit] += message;
+            }
+        }
+        posteriors
+    }
+
+    fn satisfies(&self, posteriors: &[f64], syndrome: &SparseBinSlice) -> bool {
+        &(&self.parity_mat * &hard_decision(posteriors)).as_view() == syndrome
+    }
+}
+
+fn hard_decision(posteriors: &[f64]) -> SparseBinVec {
+    SparseBinVec::new(
+        posteriors.len(),
+        posteriors
+            .iter()
+            .enumerate()
+            .filter(|(_, posterior)| **posterior < 0.0)
+            .map(|(bit, _)| bit)
+            .collect(),
+    )
+}
+
+impl LinearDecoder for AmpDecoder {
+    fn decode(&self, message: SparseBinSlice) -> SparseBinVec {
+        let syndrome = &self.parity_mat * &message;
+        let correction = self.correction_for(syndrome.as_view());
+        &message + &correction
+    }
+}
+
+/// A bit is flipped if its posterior log-likelihood ratio is negative.
+/// Decoding stops when the syndrome of the hard decision matches
+/// or when the maximum number of iterations is reached.
+impl<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for AmpDecoder {
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        hard_decision(&self.run(syndrome, &self.likelyhoods))
+    }
+}
+
+impl<'a> ClassicalSyndromeDecoder<'a> for AmpDecoder {
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.num_checks())
+    }
+}
+
+impl SoftSyndromeDecoder for AmpDecoder {
+    fn parity_check_matrix(&self) -> &SparseBinMat {
+        &self.parity_mat
+    }
+
+    fn posteriors_for(&self, syndrome: SparseBinSlice, priors: &[f64]) -> Vec<f64> {
+        if priors.len() != self.num_bits() {
+            panic!("{} priors for {} bits", priors.len(), self.num_bits());
+        }
+        self.run(syndrome, priors)
+    }
+}
+
+impl DecoderCapabilities for AmpDecoder {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none().with(Capability::SoftInput)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::decoders::BpDecoder;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn damping_corrects_the_single_error_missed_by_belief_propagation() {
+        let code = LinearCode::hamming_code();
+        let decoder = AmpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let bp_decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let damped_decoder = decoder.clone().with_damping(0.2);
+        assert_eq!(damped_decoder.syndrome_length(), Some(3));
+        for bit in 0..7 {
+            let error = SparseBinVec::new(7, vec![bit]);
+            let syndrome = code.syndrome_of(&error);
+            assert_eq!(
+                decoder.correction_for(syndrome.as_view()),
+                bp_decoder.correction_for(syndrome.as_view())
+            );
+            assert_eq!(damped_decoder.correction_for(syndrome.as_view()), error);
+        }
+    }
+
+    #[test]
+    fn damped_decoder_corrects_errors_of_random_code() {
+        let code = LinearCode::random_regular_code()
+            .num_bits(16)
+            .num_checks(12)
+            .bit_degree(3)
+            .check_degree(4)
+            .sample_with(&mut StdRng::seed_from_u64(123))
+            .unwrap();
+        let decoder = AmpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 20)
+            .with_damping(0.3);
+        for bits in [vec![0], vec![2], vec![0, 10]] {
+            let error = SparseBinVec::new(code.len(), bits);
+            let syndrome = code.syndrome_of(&error);
+            let correction = decoder.correction_for(syndrome.as_view());
+            assert_eq!(code.syndrome_of(&correction), syndrome);
+        }
+    }
+
+    #[test]
+    fn structured_priors_guide_the_correction() {
+        let code = LinearCode::repetition_code(3);
+        let decoder = AmpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        // Flipping the first bit or the last two explains the syndrome,
+        // but the priors only favor flipping the second bit.
+        let syndrome = SparseBinVec::new(2, vec![0]);
+        let posteriors = decoder.posteriors_for(syndrome.as_view(), &[5.0, -1.0, 0.5]);
+        assert!(posteriors[0] > 0.0);
+        assert!(posteriors[1] < 0.0 && posteriors[2] < 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn damping_must_be_smaller_than_one() {
+        let code = LinearCode::hamming_code();
+        AmpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10).with_damping(1.0);
+    }
+}
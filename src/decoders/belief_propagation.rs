@@ -1,4 +1,8 @@
-use super::{ClassicalSyndromeDecoder, LinearDecoder, SyndromeDecoder};
+use super::{
+    BpWeights, Budget, BudgetedCorrection, BudgetedSyndromeDecoder, Capabilities, Capability,
+    ClassicalSyndromeDecoder, DecoderCapabilities, FixedPointFormat, LinearDecoder,
+    SoftSyndromeDecoder, SyndromeDecoder,
+};
 use crate::noise::Probability;
 use itertools::Itertools;
 use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
@@ -9,6 +13,9 @@ pub struct BpDecoder {
     parity_mat: SparseBinMat,
     likelyhoods: Vec<f64>,
     num_iterations: usize,
+    format: Option<FixedPointFormat>,
+    // The weights of each iteration laid out as the messages from the checks.
+    weights: Vec<CsMat<f64>>,
 }
 
 impl LinearDecoder for BpDecoder {
@@ -19,48 +26,250 @@ impl LinearDecoder for BpDecoder {
     }
 }
 
+/// A bit is flipped if its posterior log-likelihood ratio is negative,
+/// such that ties at exactly 0 are never flipped.
+/// Messages are updated in the order of the parity check matrix
+/// and the decoder has no randomness, so the correction depends only on the syndrome.
+/// With the `deterministic-sum` feature, the messages and posteriors of the bits
+/// are compensated sums in increasing order of the checks,
+/// whose rounding doesn't depend on how the additions are scheduled.
 impl<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for BpDecoder {
     fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
-        self.initialize_from(syndrome.as_view())
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        let state = self
+            .initialize_from(syndrome.as_view())
             .update_until(|state| {
-                &(&self.parity_mat * &state.decode()).as_view() == &syndrome
+                (&self.parity_mat * &state.decode()).as_view() == syndrome
                     || state.num_iterations == self.num_iterations
+            });
+        #[cfg(feature = "tracing")]
+        self.trace(&state);
+        state.decode()
+    }
+}
+
+/// Decoding stops when the syndrome of the hard decision matches
+/// or when either the budget or the maximum number of iterations is exhausted.
+impl<'a> BudgetedSyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for BpDecoder {
+    fn correction_within(
+        &self,
+        syndrome: SparseBinSlice,
+        budget: Budget,
+    ) -> BudgetedCorrection<SparseBinVec> {
+        let clock = budget.start();
+        let correction = self
+            .initialize_from(syndrome.as_view())
+            .update_until(|state| {
+                (&self.parity_mat * &state.decode()).as_view() == syndrome
+                    || state.num_iterations == self.num_iterations
+                    || clock.is_expired(state.num_iterations)
             })
-            .decode()
+            .decode();
+        if (&self.parity_mat * &correction).as_view() == syndrome {
+            BudgetedCorrection::Found(correction)
+        } else {
+            BudgetedCorrection::Expired(correction)
+        }
+    }
+}
+
+impl<'a> ClassicalSyndromeDecoder<'a> for BpDecoder {
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.num_checks())
     }
 }
 
-impl<'a> ClassicalSyndromeDecoder<'a> for BpDecoder {}
+/// Decoding stops when the syndrome of the hard decision matches
+/// or when the maximum number of iterations is reached.
+impl SoftSyndromeDecoder for BpDecoder {
+    fn parity_check_matrix(&self) -> &SparseBinMat {
+        &self.parity_mat
+    }
+
+    fn posteriors_for(&self, syndrome: SparseBinSlice, priors: &[f64]) -> Vec<f64> {
+        if priors.len() != self.num_bits() {
+            panic!("{} priors for {} bits", priors.len(), self.num_bits());
+        }
+        let state = self
+            .initialize_with(syndrome.as_view(), priors)
+            .update_until(|state| {
+                (&self.parity_mat * &state.decode()).as_view() == syndrome
+                    || state.num_iterations == self.num_iterations
+            });
+        #[cfg(feature = "tracing")]
+        self.trace(&state);
+        state.posteriors()
+    }
+}
 
 impl BpDecoder {
     pub fn new(parity_mat: &SparseBinMat, probability: Probability, num_iterations: usize) -> Self {
-        let probability = probability.value();
-        let likelyhoods = std::iter::repeat(((1.0 - probability) / probability).ln())
-            .take(parity_mat.number_of_columns())
-            .collect();
+        let likelyhoods = vec![probability.llr(); parity_mat.number_of_columns()];
         Self {
             parity_mat: parity_mat.clone(),
             likelyhoods,
             num_iterations,
+            format: None,
+            weights: Vec::new(),
+        }
+    }
+
+    /// Replaces the prior log-likelihood ratios by those of the given probability
+    /// while keeping the parity check matrix.
+    ///
+    /// This is useful to reuse a decoder across a sweep of probabilities.
+    pub fn with_probability(mut self, probability: Probability) -> Self {
+        let likelyhood = probability.llr();
+        for value in self.likelyhoods.iter_mut() {
+            *value = likelyhood;
+        }
+        self
+    }
+
+    /// Replaces the prior log-likelihood ratios by those of the given probability of each bit
+    /// while keeping the parity check matrix.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of probabilities is different from the number of bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::decoders::{BpDecoder, SyndromeDecoder};
+    /// use ldpc::noise::Probability;
+    /// use sparse_bin_mat::SparseBinVec;
+    ///
+    /// let code = LinearCode::repetition_code(3);
+    /// let probabilities = [0.1, 0.01, 0.2].map(Probability::new);
+    /// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10)
+    ///     .with_bit_probabilities(&probabilities);
+    ///
+    /// // Flipping the first and last bits is more likely than flipping the middle one.
+    /// let syndrome = SparseBinVec::new(2, vec![0, 1]);
+    /// assert_eq!(
+    ///     decoder.correction_for(syndrome.as_view()),
+    ///     SparseBinVec::new(3, vec![0, 2])
+    /// );
+    /// ```
+    pub fn with_bit_probabilities(mut self, probabilities: &[Probability]) -> Self {
+        if probabilities.len() != self.num_bits() {
+            panic!(
+                "{} probabilities for {} bits",
+                probabilities.len(),
+                self.num_bits()
+            );
+        }
+        self.likelyhoods = probabilities
+            .iter()
+            .map(|probability| probability.llr())
+            .collect();
+        self
+    }
+
+    /// Simulates a decoder where the log-likelihood ratios
+    /// and all the messages are stored in the given fixed-point format.
+    ///
+    /// Every value is quantized right after being computed
+    /// such that the implementation loss of a hardware decoder can be estimated
+    /// by comparing its performance with the floating point decoder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::decoders::{BpDecoder, FixedPointFormat, SyndromeDecoder};
+    /// use ldpc::noise::Probability;
+    /// use sparse_bin_mat::SparseBinVec;
+    ///
+    /// let code = LinearCode::repetition_code(3);
+    /// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10)
+    ///     .with_fixed_point(FixedPointFormat::new(3, 2));
+    ///
+    /// let error = SparseBinVec::new(3, vec![1]);
+    /// let syndrome = code.syndrome_of(&error);
+    /// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+    /// ```
+    pub fn with_fixed_point(mut self, format: FixedPointFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Multiplies each message from a check to a bit by the weight of its edge
+    /// in the current iteration, as in neural belief propagation.
+    ///
+    /// See [`BpWeights`] for the order of the edges.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of weighted edges is different
+    /// from the number of edges of the parity check matrix.
+    pub fn with_weights(mut self, weights: BpWeights) -> Self {
+        if weights.num_edges() != self.parity_mat.number_of_ones() {
+            panic!(
+                "{} weights for {} edges",
+                weights.num_edges(),
+                self.parity_mat.number_of_ones()
+            );
         }
+        self.weights = (0..weights.num_iterations())
+            .map(|iteration| {
+                let mut messages = TriMat::new((self.num_checks(), self.num_bits()));
+                for ((check, bit), weight) in self
+                    .parity_mat
+                    .non_trivial_elements()
+                    .zip(weights.for_iteration(iteration))
+                {
+                    messages.add_triplet(check, bit, *weight);
+                }
+                messages.to_csc()
+            })
+            .collect();
+        self
+    }
+
+    /// Returns the prior log-likelihood ratio of each bit.
+    pub fn priors(&self) -> &[f64] {
+        &self.likelyhoods
+    }
+
+    /// Returns the fixed-point format of the decoder
+    /// or None if it uses floating point numbers.
+    pub fn fixed_point_format(&self) -> Option<FixedPointFormat> {
+        self.format
     }
 
     fn initialize_from<'a>(&'a self, syndrome: SparseBinSlice<'a>) -> BpState<'a> {
+        self.initialize_with(syndrome, &self.likelyhoods)
+    }
+
+    fn initialize_with<'a>(
+        &'a self,
+        syndrome: SparseBinSlice<'a>,
+        likelyhoods: &'a [f64],
+    ) -> BpState<'a> {
         BpState {
             messages: Messages {
-                bits: self.initialize_bits(),
+                bits: self.initialize_bits(likelyhoods),
                 checks: self.initialize_checks(),
             },
             syndrome,
-            likelyhoods: &self.likelyhoods,
+            likelyhoods,
             num_iterations: 0,
+            format: self.format,
+            weights: &self.weights,
         }
     }
 
-    fn initialize_bits(&self) -> CsMat<f64> {
+    fn initialize_bits(&self, likelyhoods: &[f64]) -> CsMat<f64> {
         let mut messages = TriMat::new((self.num_checks(), self.num_bits()));
         for (check, bit) in self.parity_mat.non_trivial_elements() {
-            messages.add_triplet(check, bit, self.likelyhoods[bit]);
+            messages.add_triplet(check, bit, quantize(self.format, likelyhoods[bit]));
         }
         messages.to_csr()
     }
@@ -75,6 +284,16 @@ impl BpDecoder {
         messages.to_csc()
     }
 
+    #[cfg(feature = "tracing")]
+    fn trace(&self, state: &BpState) {
+        let converged = (&self.parity_mat * &state.decode()).as_view() == state.syndrome;
+        tracing::trace!(
+            num_iterations = state.num_iterations,
+            converged,
+            "belief propagation finished"
+        );
+    }
+
     pub fn num_bits(&self) -> usize {
         self.parity_mat.number_of_columns()
     }
@@ -94,16 +313,13 @@ struct BpState<'a> {
     likelyhoods: &'a [f64],
     messages: Messages,
     num_iterations: usize,
+    format: Option<FixedPointFormat>,
+    weights: &'a [CsMat<f64>],
 }
 
 impl<'a> BpState<'a> {
     fn decode(&self) -> SparseBinVec {
-        let mut likelyhoods = self.likelyhoods.to_owned();
-        for (bit, cols) in self.messages.checks.outer_iterator().enumerate() {
-            for (_, value) in cols.iter() {
-                likelyhoods[bit] += value;
-            }
-        }
+        let likelyhoods = self.posteriors();
         SparseBinVec::new(
             likelyhoods.len(),
             likelyhoods
@@ -113,6 +329,35 @@ impl<'a> BpState<'a> {
         )
     }
 
+    fn posteriors(&self) -> Vec<f64> {
+        #[cfg(feature = "deterministic-sum")]
+        if self.format.is_none() {
+            return self
+                .messages
+                .checks
+                .outer_iterator()
+                .enumerate()
+                .map(|(bit, cols)| {
+                    compensated_sum(
+                        std::iter::once(self.likelyhoods[bit])
+                            .chain(cols.iter().map(|(_, value)| *value)),
+                    )
+                })
+                .collect();
+        }
+        let mut likelyhoods = self
+            .likelyhoods
+            .iter()
+            .map(|likelyhood| quantize(self.format, *likelyhood))
+            .collect_vec();
+        for (bit, cols) in self.messages.checks.outer_iterator().enumerate() {
+            for (_, value) in cols.iter() {
+                likelyhoods[bit] = quantize(self.format, likelyhoods[bit] + value);
+            }
+        }
+        likelyhoods
+    }
+
     fn update_while<F>(mut self, condition: F) -> Self
     where
         F: Fn(&BpState) -> bool,
@@ -131,11 +376,15 @@ impl<'a> BpState<'a> {
     }
 
     fn update_once(mut self) -> Self {
+        let weights = self.weights.get(
+            self.num_iterations
+                .min(self.weights.len().saturating_sub(1)),
+        );
         self.num_iterations += 1;
         self.messages = self
             .messages
-            .update_checks(self.syndrome.clone())
-            .update_bits(self.likelyhoods);
+            .update_checks(self.syndrome.clone(), weights, self.format)
+            .update_bits(self.likelyhoods, self.format);
         self
     }
 }
@@ -147,7 +396,12 @@ struct Messages {
 }
 
 impl Messages {
-    fn update_checks(mut self, syndrome: SparseBinSlice) -> Self {
+    fn update_checks(
+        mut self,
+        syndrome: SparseBinSlice,
+        weights: Option<&CsMat<f64>>,
+        format: Option<FixedPointFormat>,
+    ) -> Self {
         let products = self
             .bits
             .outer_iterator()
@@ -155,17 +409,34 @@ impl Messages {
             .collect::<Vec<f64>>();
         for (bit, mut checks) in self.checks.outer_iterator_mut().enumerate() {
             for (check, value) in checks.iter_mut() {
-                let inner = products[check] / (self.bits.get(check, bit).unwrap() / 2.0).tanh();
+                let own = (self.bits.get(check, bit).unwrap() / 2.0).tanh();
+                // The product is recomputed without the bit to avoid dividing by 0.
+                let inner = if own != 0.0 {
+                    products[check] / own
+                } else {
+                    self.bits
+                        .outer_view(check)
+                        .unwrap()
+                        .iter()
+                        .filter(|(other, _)| *other != bit)
+                        .map(|(_, v)| (v / 2.0).tanh())
+                        .product()
+                };
                 *value = 2.0 * inner.atanh();
                 if syndrome.get(check).unwrap().is_one() {
                     *value *= -1.0;
                 }
+                if let Some(weights) = weights {
+                    *value *= weights.get(check, bit).unwrap();
+                }
+                *value = quantize(format, *value);
             }
         }
         self
     }
 
-    fn update_bits(mut self, likelyhoods: &[f64]) -> Self {
+    #[cfg(not(feature = "deterministic-sum"))]
+    fn update_bits(mut self, likelyhoods: &[f64], format: Option<FixedPointFormat>) -> Self {
         let sums = self
             .checks
             .outer_iterator()
@@ -173,13 +444,68 @@ impl Messages {
             .collect_vec();
         for (check, mut bits) in self.bits.outer_iterator_mut().enumerate() {
             for (bit, value) in bits.iter_mut() {
-                *value = sums[bit] - self.checks.get(check, bit).unwrap() + likelyhoods[bit];
+                let likelyhood = quantize(format, likelyhoods[bit]);
+                *value = quantize(
+                    format,
+                    sums[bit] - self.checks.get(check, bit).unwrap() + likelyhood,
+                );
+            }
+        }
+        self
+    }
+
+    // Each message is the compensated sum of the prior followed by the messages
+    // of the other checks in increasing order, instead of the total minus its own message.
+    #[cfg(feature = "deterministic-sum")]
+    fn update_bits(mut self, likelyhoods: &[f64], format: Option<FixedPointFormat>) -> Self {
+        for (check, mut bits) in self.bits.outer_iterator_mut().enumerate() {
+            for (bit, value) in bits.iter_mut() {
+                let others = self.checks.outer_view(bit).unwrap();
+                let sum = compensated_sum(
+                    std::iter::once(quantize(format, likelyhoods[bit])).chain(
+                        others
+                            .iter()
+                            .filter(|(other, _)| *other != check)
+                            .map(|(_, v)| *v),
+                    ),
+                );
+                *value = quantize(format, sum);
             }
         }
         self
     }
 }
 
+// Neumaier's summation carrying the rounding errors of the additions in order,
+// which is exact for sums of fixed-point values.
+#[cfg(feature = "deterministic-sum")]
+fn compensated_sum<I: IntoIterator<Item = f64>>(values: I) -> f64 {
+    let mut sum = 0.0;
+    let mut compensation = 0.0;
+    for value in values {
+        let next = sum + value;
+        if f64::abs(sum) >= f64::abs(value) {
+            compensation += (sum - next) + value;
+        } else {
+            compensation += (value - next) + sum;
+        }
+        sum = next;
+    }
+    sum + compensation
+}
+
+fn quantize(format: Option<FixedPointFormat>, value: f64) -> f64 {
+    format.map_or(value, |format| format.quantize(value))
+}
+
+impl DecoderCapabilities for BpDecoder {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none()
+            .with(Capability::SoftInput)
+            .with(Capability::Budget)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -193,6 +519,7 @@ mod test {
         let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
         let error = SparseBinVec::new(7, Vec::new());
         assert_eq!(decoder.decode(error.as_view()), SparseBinVec::zeros(7));
+        assert_eq!(decoder.syndrome_length(), Some(3));
     }
 
     #[test]
@@ -229,6 +556,113 @@ mod test {
         assert_eq!(decoded, expected);
     }
 
+    #[cfg(feature = "deterministic-sum")]
+    #[test]
+    fn compensated_sum_keeps_small_terms() {
+        assert_eq!(compensated_sum([1e16, 1.0, -1e16]), 1.0);
+        assert_eq!([1e16, 1.0, -1e16].iter().sum::<f64>(), 0.0);
+        assert_eq!(compensated_sum([0.1; 10]), 1.0);
+    }
+
+    #[test]
+    fn fixed_point_decoder_with_enough_precision_matches_floating_point() {
+        let code = random_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let fixed_point_decoder = decoder
+            .clone()
+            .with_fixed_point(FixedPointFormat::new(6, 8));
+        for bits in [vec![0], vec![2], vec![0, 10]] {
+            let error = SparseBinVec::new(code.len(), bits);
+            let syndrome = code.syndrome_of(&error);
+            assert_eq!(
+                fixed_point_decoder.correction_for(syndrome.as_view()),
+                decoder.correction_for(syndrome.as_view())
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_point_decoder_with_single_level_makes_no_correction() {
+        let code = LinearCode::hamming_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10)
+            .with_fixed_point(FixedPointFormat::new(0, 0));
+        let syndrome = code.syndrome_of(&SparseBinVec::new(7, vec![0]));
+        assert_eq!(
+            decoder.correction_for(syndrome.as_view()),
+            SparseBinVec::zeros(7)
+        );
+    }
+
+    #[test]
+    fn budgeted_decoding_expires_without_iterations() {
+        let code = LinearCode::hamming_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let syndrome = code.syndrome_of(&SparseBinVec::new(7, vec![0]));
+        assert_eq!(
+            decoder.correction_within(syndrome.as_view(), Budget::Iterations(0)),
+            BudgetedCorrection::Expired(SparseBinVec::zeros(7))
+        );
+        assert_eq!(
+            decoder
+                .correction_within(syndrome.as_view(), Budget::Iterations(10))
+                .found(),
+            Some(decoder.correction_for(syndrome.as_view()))
+        );
+    }
+
+    #[test]
+    fn unit_weights_match_unweighted_decoder() {
+        let code = random_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let num_edges = code.parity_check_matrix().number_of_ones();
+        let weighted_decoder = decoder
+            .clone()
+            .with_weights(BpWeights::new(vec![vec![1.0; num_edges]]));
+        let priors = vec![Probability::new(0.1).llr(); code.len()];
+        for bits in [vec![0], vec![2], vec![0, 10]] {
+            let syndrome = code.syndrome_of(&SparseBinVec::new(code.len(), bits));
+            assert_eq!(
+                weighted_decoder.posteriors_for(syndrome.as_view(), &priors),
+                decoder.posteriors_for(syndrome.as_view(), &priors)
+            );
+        }
+    }
+
+    #[test]
+    fn zero_weights_in_first_iteration_delay_decoding() {
+        let code = LinearCode::hamming_code();
+        let weights = BpWeights::new(vec![vec![0.0; 12], vec![1.0; 12]]);
+        let priors = vec![Probability::new(0.1).llr(); 7];
+        for bits in [vec![0], vec![3, 5]] {
+            let syndrome = code.syndrome_of(&SparseBinVec::new(7, bits));
+            for num_iterations in 1..5 {
+                let decoder = BpDecoder::new(
+                    code.parity_check_matrix(),
+                    Probability::new(0.1),
+                    num_iterations,
+                );
+                let weighted_decoder = BpDecoder::new(
+                    code.parity_check_matrix(),
+                    Probability::new(0.1),
+                    num_iterations + 1,
+                )
+                .with_weights(weights.clone());
+                assert_eq!(
+                    weighted_decoder.posteriors_for(syndrome.as_view(), &priors),
+                    decoder.posteriors_for(syndrome.as_view(), &priors)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn weights_must_match_edges() {
+        let code = LinearCode::hamming_code();
+        BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10)
+            .with_weights(BpWeights::new(vec![vec![1.0; 11]]));
+    }
+
     fn random_code() -> LinearCode {
         LinearCode::random_regular_code()
             .num_bits(16)
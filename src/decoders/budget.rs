@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+/// A limit on the resources a decoder can use to find a correction.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{BpDecoder, Budget, BudgetedSyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+/// use std::time::Duration;
+///
+/// let code = LinearCode::repetition_code(3);
+/// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+/// let error = SparseBinVec::new(3, vec![1]);
+/// let syndrome = code.syndrome_of(&error);
+///
+/// let correction = decoder.correction_within(syndrome.as_view(), Budget::Time(Duration::from_secs(1)));
+/// assert_eq!(correction.found(), Some(error));
+///
+/// let correction = decoder.correction_within(syndrome.as_view(), Budget::Iterations(0));
+/// assert!(!correction.is_found());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Budget {
+    /// The decoder stops after the given number of iterations.
+    Iterations(usize),
+    /// The decoder stops when the given duration elapsed since the start of decoding.
+    Time(Duration),
+    /// The decoder stops at the given instant.
+    Deadline(Instant),
+}
+
+impl Budget {
+    // Converts time budgets into deadlines starting now
+    // such that a budget can be shared between many decoders.
+    pub(super) fn with_fixed_deadline(self) -> Budget {
+        match self {
+            Budget::Time(duration) => Budget::Deadline(Instant::now() + duration),
+            budget => budget,
+        }
+    }
+
+    pub(super) fn start(self) -> BudgetClock {
+        match self {
+            Budget::Iterations(max_iterations) => BudgetClock::Iterations(max_iterations),
+            Budget::Time(duration) => BudgetClock::Deadline(Instant::now() + duration),
+            Budget::Deadline(deadline) => BudgetClock::Deadline(deadline),
+        }
+    }
+}
+
+pub(super) enum BudgetClock {
+    Iterations(usize),
+    Deadline(Instant),
+}
+
+impl BudgetClock {
+    pub(super) fn is_expired(&self, num_iterations: usize) -> bool {
+        match self {
+            BudgetClock::Iterations(max_iterations) => num_iterations >= *max_iterations,
+            BudgetClock::Deadline(deadline) => Instant::now() >= *deadline,
+        }
+    }
+}
+
+/// The outcome of decoding with a budget.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BudgetedCorrection<C> {
+    /// A correction with the same syndrome as the error
+    /// was found within the budget.
+    Found(C),
+    /// The budget expired before finding a correction with the same syndrome
+    /// as the error. This contains the best guess of the decoder at that time.
+    Expired(C),
+}
+
+impl<C> BudgetedCorrection<C> {
+    /// Checks if a correction consistent with the syndrome was found.
+    pub fn is_found(&self) -> bool {
+        matches!(self, Self::Found(_))
+    }
+
+    /// Returns the correction if it is consistent with the syndrome.
+    pub fn found(self) -> Option<C> {
+        match self {
+            Self::Found(correction) => Some(correction),
+            Self::Expired(_) => None,
+        }
+    }
+
+    /// Returns the correction whether it is consistent with the syndrome or not.
+    pub fn into_correction(self) -> C {
+        match self {
+            Self::Found(correction) | Self::Expired(correction) => correction,
+        }
+    }
+
+    /// Applies the function to the correction keeping the outcome.
+    pub fn map<F, T>(self, func: F) -> BudgetedCorrection<T>
+    where
+        F: FnOnce(C) -> T,
+    {
+        match self {
+            Self::Found(correction) => BudgetedCorrection::Found(func(correction)),
+            Self::Expired(correction) => BudgetedCorrection::Expired(func(correction)),
+        }
+    }
+}
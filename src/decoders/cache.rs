@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cache of decoders keyed by their code.
+///
+/// Setting up a decoder can be more expensive than decoding a few syndromes.
+/// When a simulation sweeps over many noise parameters or repeats short runs
+/// on the same codes, the cache builds each decoder only once per code.
+///
+/// Two codes with the same matrices have the same key,
+/// even if they are distinct instances.
+/// The cache keeps a copy of each code such that codes with colliding hashes
+/// are told apart by comparing them.
+///
+/// Codes are keyed by their content and not by their [`Fingerprint`](crate::codes::Fingerprint).
+/// A fingerprint ignores the order of the checks,
+/// but the bits of a syndrome are indexed by the checks,
+/// such that a decoder of reordered checks would decode the wrong syndromes.
+/// The content of a linear code also includes its generator matrix,
+/// thus the same checks with different generator matrices are set up twice,
+/// which costs a setup but never returns the wrong decoder.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{BpDecoder, DecoderCache};
+/// use ldpc::noise::Probability;
+///
+/// let mut cache = DecoderCache::new();
+/// for probability in [0.01, 0.02, 0.05] {
+///     let code = LinearCode::hamming_code();
+///     let decoder = cache
+///         .get_or_insert_with(&code, || {
+///             BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10)
+///         })
+///         .clone()
+///         .with_probability(Probability::new(probability));
+/// }
+///
+/// assert_eq!(cache.len(), 1);
+/// assert_eq!(cache.num_misses(), 1);
+/// assert_eq!(cache.num_hits(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecoderCache<C, D> {
+    decoders: HashMap<C, D>,
+    num_hits: usize,
+    num_misses: usize,
+}
+
+impl<C, D> DecoderCache<C, D>
+where
+    C: Hash + Eq + Clone,
+{
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self {
+            decoders: HashMap::new(),
+            num_hits: 0,
+            num_misses: 0,
+        }
+    }
+
+    /// Returns the decoder of the code,
+    /// setting it up with the given function if it is not in the cache.
+    ///
+    /// The code is cloned only when the decoder is set up.
+    pub fn get_or_insert_with<F>(&mut self, code: &C, setup: F) -> &D
+    where
+        F: FnOnce() -> D,
+    {
+        if self.decoders.contains_key(code) {
+            self.num_hits += 1;
+        } else {
+            self.num_misses += 1;
+            self.decoders.insert(code.clone(), setup());
+        }
+        &self.decoders[code]
+    }
+
+    /// Returns the decoder of the code or None if it is not in the cache.
+    pub fn get(&self, code: &C) -> Option<&D> {
+        self.decoders.get(code)
+    }
+
+    /// Checks if the decoder of the code is in the cache.
+    pub fn contains(&self, code: &C) -> bool {
+        self.decoders.contains_key(code)
+    }
+
+    /// Removes the decoder of the code from the cache and returns it if any.
+    pub fn remove(&mut self, code: &C) -> Option<D> {
+        self.decoders.remove(code)
+    }
+
+    /// Returns the number of decoders in the cache.
+    pub fn len(&self) -> usize {
+        self.decoders.len()
+    }
+
+    /// Checks if the cache contains no decoder.
+    pub fn is_empty(&self) -> bool {
+        self.decoders.is_empty()
+    }
+
+    /// Returns the number of requests answered from the cache.
+    pub fn num_hits(&self) -> usize {
+        self.num_hits
+    }
+
+    /// Returns the number of requests that required a setup.
+    pub fn num_misses(&self) -> usize {
+        self.num_misses
+    }
+
+    /// Removes all the decoders and resets the statistics.
+    pub fn clear(&mut self) {
+        self.decoders.clear();
+        self.num_hits = 0;
+        self.num_misses = 0;
+    }
+}
+
+impl<C, D> Default for DecoderCache<C, D>
+where
+    C: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::{CssCode, LinearCode};
+
+    #[test]
+    fn setup_runs_once_per_code() {
+        let mut cache = DecoderCache::new();
+        let mut num_setups = 0;
+        for code in [
+            LinearCode::hamming_code(),
+            LinearCode::repetition_code(3),
+            LinearCode::hamming_code(),
+        ] {
+            cache.get_or_insert_with(&code, || {
+                num_setups += 1;
+                code.len()
+            });
+        }
+        assert_eq!(num_setups, 2);
+        assert_eq!(cache.get(&LinearCode::repetition_code(3)), Some(&3));
+        assert!(!cache.contains(&LinearCode::repetition_code(5)));
+        assert_eq!((cache.num_hits(), cache.num_misses()), (1, 2));
+    }
+
+    #[test]
+    fn css_codes_are_keyed_by_content() {
+        let mut cache = DecoderCache::new();
+        cache.get_or_insert_with(&CssCode::steane_code(), || 7);
+        assert_eq!(cache.remove(&CssCode::steane_code()), Some(7));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn reordered_checks_have_distinct_decoders() {
+        let code = LinearCode::repetition_code(3);
+        let reordered = LinearCode::from_parity_check_matrix(
+            sparse_bin_mat::SparseBinMat::new(3, vec![vec![1, 2], vec![0, 1]]),
+        );
+        assert_eq!(code.fingerprint(), reordered.fingerprint());
+        let mut cache = DecoderCache::new();
+        cache.get_or_insert_with(&code, || 1);
+        cache.get_or_insert_with(&reordered, || 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    // A code whose hash ignores its content.
+    #[derive(PartialEq, Eq, Clone)]
+    struct CollidingCode(usize);
+
+    impl Hash for CollidingCode {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            0.hash(state);
+        }
+    }
+
+    #[test]
+    fn colliding_codes_have_distinct_decoders() {
+        let mut cache = DecoderCache::new();
+        cache.get_or_insert_with(&CollidingCode(1), || 1);
+        assert_eq!(cache.get_or_insert_with(&CollidingCode(2), || 2), &2);
+        assert_eq!(cache.get(&CollidingCode(1)), Some(&1));
+        assert_eq!(cache.num_misses(), 2);
+    }
+}
@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A feature of a decoder beyond decoding a single hard syndrome.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum Capability {
+    /// The decoder takes prior log-likelihood ratios and returns posteriors,
+    /// see [`SoftSyndromeDecoder`](super::SoftSyndromeDecoder).
+    SoftInput,
+    /// The decoder recovers errors supported on known erased bits,
+    /// see [`ErasureDecoder`](super::ErasureDecoder).
+    Erasures,
+    /// The decoder corrects syndromes measured with errors over several rounds,
+    /// see [`StreamingDecoder`](super::StreamingDecoder).
+    NoisySyndromes,
+    /// The decoder returns its best guess when a budget expires,
+    /// see [`BudgetedSyndromeDecoder`](super::BudgetedSyndromeDecoder).
+    Budget,
+}
+
+impl Capability {
+    /// All the capabilities.
+    pub const ALL: [Capability; 4] = [
+        Capability::SoftInput,
+        Capability::Erasures,
+        Capability::NoisySyndromes,
+        Capability::Budget,
+    ];
+
+    fn flag(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SoftInput => write!(f, "soft input"),
+            Self::Erasures => write!(f, "erasures"),
+            Self::NoisySyndromes => write!(f, "noisy syndromes"),
+            Self::Budget => write!(f, "budget"),
+        }
+    }
+}
+
+/// The set of capabilities of a decoder.
+///
+/// This is used by generic experiments to check that a decoder fits a configuration
+/// before running instead of failing in the middle of a run.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{BpDecoder, Capabilities, Capability, DecoderCapabilities, IsdAlgorithm, IsdDecoder};
+/// use ldpc::noise::Probability;
+///
+/// let code = LinearCode::hamming_code();
+/// let required = Capabilities::none().with(Capability::SoftInput);
+///
+/// let bp = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+/// assert!(bp.capabilities().require(required).is_ok());
+///
+/// let isd = IsdDecoder::new(code.parity_check_matrix(), IsdAlgorithm::Prange, 10);
+/// let error = isd.capabilities().require(required).unwrap_err();
+/// assert_eq!(error.to_string(), "decoder doesn't support soft input");
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct Capabilities {
+    flags: u8,
+}
+
+impl Capabilities {
+    /// Returns the empty set of capabilities.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Adds a capability to the set.
+    pub fn with(mut self, capability: Capability) -> Self {
+        self.flags |= capability.flag();
+        self
+    }
+
+    /// Checks if the set contains the capability.
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.flags & capability.flag() != 0
+    }
+
+    /// Returns the capabilities in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            flags: self.flags & other.flags,
+        }
+    }
+
+    /// Returns an iterator over the capabilities of the set.
+    pub fn iter(&self) -> impl Iterator<Item = Capability> + '_ {
+        Capability::ALL
+            .into_iter()
+            .filter(move |capability| self.supports(*capability))
+    }
+
+    /// Returns an error with the missing capabilities
+    /// if the set doesn't contain all the required capabilities.
+    pub fn require(&self, required: Capabilities) -> Result<(), MissingCapabilities> {
+        let missing = Self {
+            flags: required.flags & !self.flags,
+        };
+        if missing.flags == 0 {
+            Ok(())
+        } else {
+            Err(MissingCapabilities(missing.iter().collect()))
+        }
+    }
+}
+
+impl FromIterator<Capability> for Capabilities {
+    fn from_iter<I: IntoIterator<Item = Capability>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::none(), Self::with)
+    }
+}
+
+/// The required capabilities that a decoder doesn't support.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MissingCapabilities(pub Vec<Capability>);
+
+impl fmt::Display for MissingCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let capabilities = self
+            .0
+            .iter()
+            .map(|capability| capability.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "decoder doesn't support {}", capabilities.join(", "))
+    }
+}
+
+impl std::error::Error for MissingCapabilities {}
+
+/// A decoder reporting its capabilities at runtime.
+pub trait DecoderCapabilities {
+    /// Returns the capabilities of the decoder.
+    fn capabilities(&self) -> Capabilities;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn missing_capabilities_are_listed_in_order() {
+        let capabilities = Capabilities::none().with(Capability::Erasures);
+        let required = [
+            Capability::Budget,
+            Capability::SoftInput,
+            Capability::Erasures,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            capabilities.require(required),
+            Err(MissingCapabilities(vec![
+                Capability::SoftInput,
+                Capability::Budget
+            ]))
+        );
+        assert!(capabilities.require(Capabilities::none()).is_ok());
+        assert_eq!(
+            capabilities
+                .intersection(&required)
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![Capability::Erasures]
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use super::{
+    BpDecoder, Capabilities, Capability, ClassicalSyndromeDecoder, DecoderCapabilities,
+    SoftSyndromeDecoder, SyndromeDecoder,
+};
+use crate::codes::{InformationSet, LinearCode};
+use crate::noise::Probability;
+use itertools::Itertools;
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+
+/// A hierarchical decoder for the [concatenation](LinearCode::concatenation)
+/// of an outer code with an inner code.
+///
+/// At each iteration, the inner decoder decodes each block from its part of the syndrome
+/// and passes the log-likelihood ratios of the message bits to the outer decoder,
+/// which decodes them from the syndrome of the outer checks.
+/// The extrinsic information of the outer decoder on the message bits is added
+/// to the priors of the blocks at the next iteration.
+/// By default, the inner level outputs its soft posteriors.
+/// With [hard outputs](ConcatenatedDecoder::with_hard_outputs),
+/// it only outputs its decisions with the reliability of the priors,
+/// as a decoder for the inner code without soft output would,
+/// and decoding stops after the first iteration.
+///
+/// The correction is the hard decision of the inner level where the message bits
+/// disagreeing with the decision of the outer level are flipped
+/// together with a codeword of the inner code,
+/// such that the syndrome of each block is preserved.
+/// Decoding stops when the correction matches the full syndrome
+/// or when the maximum number of iterations is reached.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{ConcatenatedDecoder, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let outer = LinearCode::hamming_code();
+/// let inner = LinearCode::repetition_code(3);
+/// let code = outer.concatenation(&inner);
+/// let decoder = ConcatenatedDecoder::for_concatenation(&outer, &inner, Probability::new(0.1), 5);
+///
+/// // Two errors in the first block are wrongly corrected by the inner code,
+/// // but the outer code fixes the message of this block.
+/// let error = SparseBinVec::new(21, vec![0, 1, 5]);
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConcatenatedDecoder<Inner, Outer> {
+    inner: Inner,
+    outer: Outer,
+    parity_mat: SparseBinMat,
+    messages: Vec<usize>,
+    encoder: InformationSet,
+    likelyhood: f64,
+    num_iterations: usize,
+    has_hard_outputs: bool,
+}
+
+impl<Inner, Outer> ConcatenatedDecoder<Inner, Outer>
+where
+    Inner: SoftSyndromeDecoder,
+    Outer: SoftSyndromeDecoder,
+{
+    /// Creates a decoder for the concatenation of the outer code with the inner code
+    /// using the inner decoder on each block and the outer decoder on the message bits,
+    /// assuming each bit flips independently with the given probability.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the codes can't be concatenated
+    /// or if a decoder doesn't have the checks of its code.
+    pub fn new(
+        outer_code: &LinearCode,
+        inner_code: &LinearCode,
+        inner: Inner,
+        outer: Outer,
+        probability: Probability,
+        num_iterations: usize,
+    ) -> Self {
+        for (decoder, code, level) in [
+            (inner.parity_check_matrix(), inner_code, "inner"),
+            (outer.parity_check_matrix(), outer_code, "outer"),
+        ] {
+            if decoder != code.parity_check_matrix() {
+                panic!(
+                    "{} decoder doesn't have the checks of the {} code",
+                    level, level
+                );
+            }
+        }
+        let (inner_checks, outer_checks) = outer_code.concatenation_checks(inner_code);
+        let columns = (0..inner_code.len()).collect::<Vec<_>>();
+        Self {
+            inner,
+            outer,
+            parity_mat: inner_checks.vertical_concat_with(&outer_checks),
+            messages: inner_code.message_positions(),
+            encoder: InformationSet::new(inner_code.parity_check_matrix(), &columns),
+            likelyhood: probability.llr(),
+            num_iterations,
+            has_hard_outputs: false,
+        }
+    }
+
+    /// Makes the inner level output hard decisions instead of posteriors.
+    pub fn with_hard_outputs(mut self) -> Self {
+        self.has_hard_outputs = true;
+        self
+    }
+
+    /// Returns the inner decoder.
+    pub fn inner(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Returns the outer decoder.
+    pub fn outer(&self) -> &Outer {
+        &self.outer
+    }
+
+    /// Returns the number of bits of the concatenated code.
+    pub fn num_bits(&self) -> usize {
+        self.parity_mat.number_of_columns()
+    }
+
+    /// Returns the number of inner and outer checks.
+    pub fn num_checks(&self) -> usize {
+        self.parity_mat.number_of_rows()
+    }
+
+    fn block_len(&self) -> usize {
+        self.inner.parity_check_matrix().number_of_columns()
+    }
+
+    fn num_blocks(&self) -> usize {
+        self.num_bits() / self.block_len()
+    }
+
+    // The syndrome of each block followed by the syndrome of the outer checks.
+    fn split(&self, syndrome: &SparseBinSlice) -> (Vec<SparseBinVec>, SparseBinVec) {
+        let num_block_checks = self.inner.parity_check_matrix().number_of_rows();
+        let num_inner_checks = num_block_checks * self.num_blocks();
+        let mut blocks = vec![Vec::new(); self.num_blocks()];
+        let mut outer = Vec::new();
+        for check in syndrome.non_trivial_positions() {
+            if check < num_inner_checks {
+                blocks[check / num_block_checks].push(check % num_block_checks);
+            } else {
+                outer.push(check - num_inner_checks);
+            }
+        }
+        (
+            blocks
+                .into_iter()
+                .map(|checks| SparseBinVec::new(num_block_checks, checks))
+                .collect(),
+            SparseBinVec::new(self.num_checks() - num_inner_checks, outer),
+        )
+    }
+
+    // The bit of the full code carrying the given message bit of the outer code.
+    fn bit_of(&self, message_bit: usize) -> usize {
+        let (block, bit) = (
+            message_bit / self.messages.len(),
+            message_bit % self.messages.len(),
+        );
+        block * self.block_len() + self.messages[bit]
+    }
+
+    // The posteriors of the bits with the message bits informed by the outer level
+    // and the correction of the last iteration.
+    fn decode(&self, syndrome: SparseBinSlice, priors: &[f64]) -> (Vec<f64>, SparseBinVec) {
+        if syndrome.len() != self.num_checks() {
+            panic!(
+                "syndrome of length {} for {} checks",
+                syndrome.len(),
+                self.num_checks()
+            );
+        }
+        let (block_syndromes, outer_syndrome) = self.split(&syndrome);
+        let num_message_bits = self.messages.len() * self.num_blocks();
+        let mut outer_extrinsic = vec![0.0; num_message_bits];
+        let mut posteriors = priors.to_vec();
+        let mut correction = SparseBinVec::zeros(self.num_bits());
+        for _ in 0..self.num_iterations {
+            let mut inner_priors = priors.to_vec();
+            for (message_bit, extrinsic) in outer_extrinsic.iter().enumerate() {
+                inner_priors[self.bit_of(message_bit)] += extrinsic;
+            }
+            posteriors = block_syndromes
+                .iter()
+                .zip(inner_priors.chunks(self.block_len()))
+                .flat_map(|(syndrome, priors)| {
+                    self.inner.posteriors_for(syndrome.as_view(), priors)
+                })
+                .collect();
+            let outer_priors = (0..num_message_bits)
+                .map(|message_bit| {
+                    let bit = self.bit_of(message_bit);
+                    if self.has_hard_outputs {
+                        priors[bit].abs().copysign(posteriors[bit])
+                    } else {
+                        posteriors[bit] - outer_extrinsic[message_bit]
+                    }
+                })
+                .collect::<Vec<_>>();
+            let outer_posteriors = self
+                .outer
+                .posteriors_for(outer_syndrome.as_view(), &outer_priors);
+            outer_extrinsic = extrinsic(&outer_posteriors, &outer_priors);
+            let inner_decision = hard_decision(&posteriors);
+            for (message_bit, extrinsic) in outer_extrinsic.iter().enumerate() {
+                posteriors[self.bit_of(message_bit)] += extrinsic;
+            }
+            correction = &inner_decision + &self.message_fix(&inner_decision, &outer_posteriors);
+            // The hard decisions of the inner level don't improve with the extrinsic
+            // information of the outer level, which only cancels their reliability.
+            if self.has_hard_outputs || (&self.parity_mat * &correction).as_view() == syndrome {
+                break;
+            }
+        }
+        (posteriors, correction)
+    }
+
+    // The codewords of the blocks flipping the message bits of the inner decision
+    // that disagree with the outer decision.
+    fn message_fix(&self, inner_decision: &SparseBinVec, outer_posteriors: &[f64]) -> SparseBinVec {
+        let mut flips = vec![Vec::new(); self.num_blocks()];
+        for (message_bit, posterior) in outer_posteriors.iter().enumerate() {
+            let bit = self.bit_of(message_bit);
+            if (*posterior < 0.0) != inner_decision.is_one_at(bit).unwrap() {
+                flips[bit / self.block_len()].push(bit % self.block_len());
+            }
+        }
+        let zero_syndrome = SparseBinVec::zeros(self.inner.parity_check_matrix().number_of_rows());
+        let positions = flips
+            .into_iter()
+            .enumerate()
+            .filter(|(_, flips)| !flips.is_empty())
+            .flat_map(|(block, flips)| {
+                let fixed = SparseBinVec::new(self.block_len(), flips);
+                self.encoder
+                    .solve_with(zero_syndrome.as_view(), fixed.as_view())
+                    .unwrap()
+                    .non_trivial_positions()
+                    .map(|bit| block * self.block_len() + bit)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        SparseBinVec::new(self.num_bits(), positions)
+    }
+}
+
+impl ConcatenatedDecoder<BpDecoder, BpDecoder> {
+    /// Creates a soft-decision decoder for the
+    /// [concatenation](LinearCode::concatenation) of the outer code with the inner code.
+    ///
+    /// Both levels and the exchange between them use belief propagation
+    /// and run up to the given number of iterations.
+    pub fn for_concatenation(
+        outer_code: &LinearCode,
+        inner_code: &LinearCode,
+        probability: Probability,
+        num_iterations: usize,
+    ) -> Self {
+        Self::new(
+            outer_code,
+            inner_code,
+            BpDecoder::new(
+                inner_code.parity_check_matrix(),
+                probability,
+                num_iterations,
+            ),
+            BpDecoder::new(
+                outer_code.parity_check_matrix(),
+                probability,
+                num_iterations,
+            ),
+            probability,
+            num_iterations,
+        )
+    }
+}
+
+// Infinite posteriors and priors carry no extrinsic information.
+fn extrinsic(posteriors: &[f64], priors: &[f64]) -> Vec<f64> {
+    posteriors
+        .iter()
+        .zip(priors.iter())
+        .map(|(posterior, prior)| {
+            let value = posterior - prior;
+            if value.is_nan() {
+                0.0
+            } else {
+                value
+            }
+        })
+        .collect()
+}
+
+fn hard_decision(likelyhoods: &[f64]) -> SparseBinVec {
+    SparseBinVec::new(
+        likelyhoods.len(),
+        likelyhoods
+            .iter()
+            .positions(|likelyhood| *likelyhood < 0.0)
+            .collect(),
+    )
+}
+
+impl<Inner, Outer> SoftSyndromeDecoder for ConcatenatedDecoder<Inner, Outer>
+where
+    Inner: SoftSyndromeDecoder,
+    Outer: SoftSyndromeDecoder,
+{
+    fn parity_check_matrix(&self) -> &SparseBinMat {
+        &self.parity_mat
+    }
+
+    fn posteriors_for(&self, syndrome: SparseBinSlice, priors: &[f64]) -> Vec<f64> {
+        self.decode(syndrome, priors).0
+    }
+}
+
+impl<'a, Inner, Outer> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>
+    for ConcatenatedDecoder<Inner, Outer>
+where
+    Inner: SoftSyndromeDecoder,
+    Outer: SoftSyndromeDecoder,
+{
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the total number of checks.
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        let priors = vec![self.likelyhood; self.num_bits()];
+        self.decode(syndrome, &priors).1
+    }
+}
+
+impl<'a, Inner, Outer> ClassicalSyndromeDecoder<'a> for ConcatenatedDecoder<Inner, Outer>
+where
+    Inner: SoftSyndromeDecoder,
+    Outer: SoftSyndromeDecoder,
+{
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.parity_mat.number_of_rows())
+    }
+}
+
+impl<Inner, Outer> DecoderCapabilities for ConcatenatedDecoder<Inner, Outer> {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none().with(Capability::SoftInput)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decoder_checks_match_concatenated_code() {
+        let outer = LinearCode::repetition_code(8);
+        let inner = LinearCode::hamming_code();
+        let code = outer.concatenation(&inner);
+        assert_eq!(code.len(), 14);
+        assert_eq!(code.dimension(), 1);
+        let decoder =
+            ConcatenatedDecoder::for_concatenation(&outer, &inner, Probability::new(0.05), 5);
+        assert_eq!(decoder.parity_check_matrix(), code.parity_check_matrix());
+        assert_eq!(decoder.syndrome_length(), Some(code.num_checks()));
+    }
+
+    // The hard decisions of a block are wrong only if it has at least (d + 1) / 2 errors,
+    // thus the outer code fixes every error of weight less than
+    // ((d_in + 1) / 2) * ((d_out + 1) / 2).
+    #[test]
+    fn hard_outputs_correct_errors_up_to_the_hierarchical_radius() {
+        for (outer_distance, inner_distance, radius) in [(3, 3, 3), (5, 3, 5), (3, 5, 5)] {
+            let outer = LinearCode::repetition_code(outer_distance);
+            let inner = LinearCode::repetition_code(inner_distance);
+            let code = outer.concatenation(&inner);
+            let decoder =
+                ConcatenatedDecoder::for_concatenation(&outer, &inner, Probability::new(0.1), 10)
+                    .with_hard_outputs();
+            assert_eq!(code.corrects_all_errors_up_to(radius, &decoder), Ok(()));
+        }
+    }
+
+    #[test]
+    fn message_fixes_are_inner_codewords() {
+        let outer = LinearCode::extended_golay_code();
+        let inner = LinearCode::hamming_code();
+        let code = outer.concatenation(&inner);
+        let decoder =
+            ConcatenatedDecoder::for_concatenation(&outer, &inner, Probability::new(0.05), 1);
+        let inner_decision = SparseBinVec::new(42, vec![0, 3, 9, 20, 21, 40]);
+        let outer_posteriors: Vec<_> = (0..24)
+            .map(|bit| if bit % 3 == 0 { -1.0 } else { 1.0 })
+            .collect();
+        let fix = decoder.message_fix(&inner_decision, &outer_posteriors);
+        let num_inner_checks = 6 * inner.num_checks();
+        assert!(code
+            .syndrome_of(&fix)
+            .non_trivial_positions()
+            .all(|check| check >= num_inner_checks));
+        let fixed = &inner_decision + &fix;
+        for (message_bit, posterior) in outer_posteriors.iter().enumerate() {
+            assert_eq!(
+                fixed.is_one_at(decoder.bit_of(message_bit)),
+                Some(*posterior < 0.0)
+            );
+        }
+    }
+}
@@ -0,0 +1,603 @@
+use super::{
+    Capabilities, ClassicalSyndromeDecoder, CssDecoder, DecoderCapabilities, Determinism,
+    SyndromeDecoder,
+};
+use crate::codes::{CssCode, DenseCodeword, InformationSet};
+use crate::css::Css;
+use crate::noise::{BinarySymmetricChannel, MetropolisChain, NoiseModel, Probability};
+use rand::Rng;
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec, SparseBinVecBase};
+
+/// A Metropolis sampler over the errors of one sector of a CSS code
+/// consistent with a syndrome.
+///
+/// Each step of the chain proposes to add a stabilizer generator
+/// or a logical generator of the same type as the errors,
+/// such that the syndrome never changes.
+/// The fraction of the steps spent in each logical coset estimates
+/// the probability of the coset given the syndrome,
+/// and decoding to the most likely coset is maximum likelihood decoding
+/// in the limit of long chains.
+/// This covers mid-size codes whose cosets are too large to enumerate.
+///
+/// Logical cosets are indexed as in [`CosetWeightDistribution`](crate::codes::CosetWeightDistribution),
+/// that is by the bit mask of the logicals of the other type anticommuting with the errors.
+///
+/// # Warning
+///
+/// At low noise, proposals adding a logical generator are rarely accepted
+/// such that the chain needs many steps to move between cosets.
+/// The [free energy differences](CosetSampler::free_energy_differences)
+/// between cosets don't have this problem since each coset is sampled by its own chains.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::decoders::{CosetSampler, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use pauli::{PauliOperator, X};
+/// use rand::thread_rng;
+///
+/// let code = CssCode::toric_code(3);
+/// let decoder = CosetSampler::from_css_code(&code, Probability::new(0.05));
+///
+/// let error = PauliOperator::new(code.len(), vec![4], vec![X]);
+/// let syndrome = code.syndrome_of(&error);
+///
+/// // The X errors are sampled by the Z decoder from the Z syndrome.
+/// let probabilities = decoder
+///     .z
+///     .coset_probabilities(syndrome.z.as_view(), &mut thread_rng())
+///     .unwrap();
+/// assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+///
+/// let correction = PauliOperator::from(decoder.correction_for(syndrome.as_view()));
+/// assert!(code.has_stabilizer(&(&correction * &error)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CosetSampler {
+    checks: SparseBinMat,
+    // The stabilizer generators followed by the logical generators.
+    moves: Vec<Vec<usize>>,
+    num_stabilizers: usize,
+    // The bit mask of the class logicals anticommuting with each move.
+    move_classes: Vec<usize>,
+    class_logicals: SparseBinMat,
+    probability: Probability,
+    llrs: Vec<f64>,
+    burn_in: usize,
+    num_steps: usize,
+    determinism: Determinism,
+}
+
+impl CosetSampler {
+    /// Creates a sampler for each sector of the code
+    /// where each qubit suffers an error with the given probability
+    /// independently in each sector.
+    ///
+    /// The samplers follow the convention of [`CssDecoder`],
+    /// such that the X sampler corrects Z errors from the X syndrome and conversely.
+    /// By default, each chain runs 1000 steps before counting
+    /// and then counts 10000 steps.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the probability is 0 or 1.
+    pub fn from_css_code(code: &CssCode, probability: Probability) -> CssDecoder<Self> {
+        Css {
+            x: Self::new(
+                &code.stabilizers.x,
+                &code.stabilizers.z,
+                &code.logicals.z,
+                &code.logicals.x,
+                probability,
+            ),
+            z: Self::new(
+                &code.stabilizers.z,
+                &code.stabilizers.x,
+                &code.logicals.x,
+                &code.logicals.z,
+                probability,
+            ),
+        }
+    }
+
+    fn new(
+        checks: &SparseBinMat,
+        stabilizers: &SparseBinMat,
+        logicals: &SparseBinMat,
+        class_logicals: &SparseBinMat,
+        probability: Probability,
+    ) -> Self {
+        if !probability.llr().is_finite() {
+            panic!("can't sample errors of probability {}", probability);
+        }
+        let generators = stabilizers.rows().chain(logicals.rows());
+        Self {
+            checks: checks.clone(),
+            moves: generators
+                .clone()
+                .map(|generator| generator.non_trivial_positions().collect())
+                .collect(),
+            num_stabilizers: stabilizers.number_of_rows(),
+            move_classes: generators
+                .map(|generator| class_of(&generator, class_logicals))
+                .collect(),
+            class_logicals: class_logicals.clone(),
+            probability,
+            llrs: vec![probability.llr(); checks.number_of_columns()],
+            burn_in: 1000,
+            num_steps: 10000,
+            determinism: Determinism::default(),
+        }
+    }
+
+    /// Sets the number of steps of the chain before counting the cosets.
+    pub fn with_burn_in(mut self, burn_in: usize) -> Self {
+        self.burn_in = burn_in;
+        self
+    }
+
+    /// Sets the number of steps of the chain counted to estimate the probabilities.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of steps is 0.
+    pub fn with_num_steps(mut self, num_steps: usize) -> Self {
+        if num_steps == 0 {
+            panic!("can't estimate probabilities from 0 steps");
+        }
+        self.num_steps = num_steps;
+        self
+    }
+
+    /// Sets the seed of the chain used by [`correction_for`](SyndromeDecoder::correction_for).
+    pub fn with_determinism(mut self, determinism: Determinism) -> Self {
+        self.determinism = determinism;
+        self
+    }
+
+    /// Returns the number of logical cosets.
+    pub fn num_classes(&self) -> usize {
+        1 << self.class_logicals.number_of_rows()
+    }
+
+    /// Estimates the probability of each logical coset given the syndrome
+    /// or returns None if no error has this syndrome.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    pub fn coset_probabilities<R: Rng>(
+        &self,
+        syndrome: SparseBinSlice,
+        rng: &mut R,
+    ) -> Option<Vec<f64>> {
+        let visits = self.run(syndrome, rng)?;
+        Some(
+            visits
+                .counts
+                .into_iter()
+                .map(|count| count as f64 / self.num_steps as f64)
+                .collect(),
+        )
+    }
+
+    /// Estimates the free energy of each logical coset given the syndrome
+    /// relative to the most likely coset
+    /// or returns None if no error has this syndrome.
+    ///
+    /// The free energy of a coset is minus the logarithm of its probability,
+    /// such that the difference for a coset is `ln(P(best) / P(coset))`.
+    /// It is 0 for the most likely coset and infinite for cosets
+    /// that can't be reached with the stored logical generators.
+    ///
+    /// The free energies are estimated by thermodynamic integration.
+    /// For each coset, chains moving only by stabilizers sample the coset
+    /// at the given number of inverse temperatures evenly spaced from 0 to 1
+    /// and the average log probability of the errors is integrated over the inverse temperature
+    /// with the trapezoidal rule.
+    /// All cosets have the same size, so their free energies are equal at inverse temperature 0.
+    /// Each chain runs the burn-in and number of steps of the sampler.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::decoders::CosetSampler;
+    /// use ldpc::noise::Probability;
+    /// use pauli::{PauliOperator, X};
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let code = CssCode::steane_code();
+    /// let sampler = CosetSampler::from_css_code(&code, Probability::new(0.1))
+    ///     .z
+    ///     .with_num_steps(2000);
+    ///
+    /// let error = PauliOperator::new(7, vec![0], vec![X]);
+    /// let syndrome = code.syndrome_of(&error);
+    /// let mut rng = StdRng::seed_from_u64(27);
+    /// let differences = sampler
+    ///     .free_energy_differences(syndrome.z.as_view(), 5, &mut rng)
+    ///     .unwrap();
+    ///
+    /// // The coset of the single flip is about 3.1 times more likely than the other coset
+    /// // whose most likely errors are 3 errors of weight 2.
+    /// let gap = differences[0] + differences[1];
+    /// assert!(differences.contains(&0.0));
+    /// assert!((gap - 3.1_f64.ln()).abs() < 0.2);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks
+    /// or if there are less than 2 temperatures.
+    pub fn free_energy_differences<R: Rng>(
+        &self,
+        syndrome: SparseBinSlice,
+        num_temperatures: usize,
+        rng: &mut R,
+    ) -> Option<Vec<f64>> {
+        if num_temperatures < 2 {
+            panic!("can't integrate over {} temperatures", num_temperatures);
+        }
+        let solution = self.solve(syndrome)?;
+        let stabilizers = &self.moves[..self.num_stabilizers];
+        let log_probabilities = self
+            .coset_representatives(&solution)
+            .into_iter()
+            .map(|representative| {
+                let representative = representative?;
+                let mut chain = MetropolisChain::new(&self.llrs, &representative);
+                let averages = (0..num_temperatures)
+                    .map(|temperature| {
+                        chain.set_inverse_temperature(
+                            temperature as f64 / (num_temperatures - 1) as f64,
+                        );
+                        for _ in 0..self.burn_in {
+                            chain.step(stabilizers, rng);
+                        }
+                        let total = (0..self.num_steps)
+                            .map(|_| {
+                                chain.step(stabilizers, rng);
+                                chain.log_weight()
+                            })
+                            .sum::<f64>();
+                        total / self.num_steps as f64
+                    })
+                    .collect::<Vec<_>>();
+                let integral = averages
+                    .windows(2)
+                    .map(|pair| (pair[0] + pair[1]) / 2.0)
+                    .sum::<f64>()
+                    / (num_temperatures - 1) as f64;
+                Some(integral)
+            })
+            .collect::<Vec<_>>();
+        let max = log_probabilities
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        Some(
+            log_probabilities
+                .into_iter()
+                .map(|log_probability| log_probability.map_or(f64::INFINITY, |log| max - log))
+                .collect(),
+        )
+    }
+
+    /// Samples errors from the channel of the sampler
+    /// and estimates the free energy differences between the cosets for their syndromes.
+    ///
+    /// Syndromes whose two most likely cosets have close free energies
+    /// are hard to decode, even for a maximum likelihood decoder.
+    /// See [`free_energy_differences`](CosetSampler::free_energy_differences).
+    ///
+    /// # Panic
+    ///
+    /// Panics if there are less than 2 temperatures.
+    pub fn sample_hardness<R: Rng>(
+        &self,
+        num_errors: usize,
+        num_temperatures: usize,
+        rng: &mut R,
+    ) -> Vec<SyndromeHardness> {
+        let noise = BinarySymmetricChannel::with_probability(self.probability);
+        (0..num_errors)
+            .map(|_| {
+                let error = noise.sample_error_of_length(self.checks.number_of_columns(), rng);
+                let syndrome = &self.checks * &error;
+                let free_energy_differences = self
+                    .free_energy_differences(syndrome.as_view(), num_temperatures, rng)
+                    .unwrap();
+                SyndromeHardness {
+                    error_class: class_of(&error, &self.class_logicals),
+                    error,
+                    syndrome,
+                    free_energy_differences,
+                }
+            })
+            .collect()
+    }
+
+    // Returns an error with the syndrome in each coset
+    // built from the solution and the logical generators.
+    fn coset_representatives(&self, solution: &SparseBinVec) -> Vec<Option<SparseBinVec>> {
+        let logicals = self.moves[self.num_stabilizers..]
+            .iter()
+            .map(|logical| {
+                DenseCodeword::from(&SparseBinVec::new(
+                    self.checks.number_of_columns(),
+                    logical.clone(),
+                ))
+            })
+            .collect::<Vec<_>>();
+        let logical_classes = &self.move_classes[self.num_stabilizers..];
+        let mut representatives = vec![None; self.num_classes()];
+        let mut current = DenseCodeword::from(solution);
+        let mut class = class_of(solution, &self.class_logicals);
+        representatives[class] = Some(solution.clone());
+        // Gray code order such that each step adds a single logical generator.
+        for step in 1_usize..1 << logicals.len() {
+            let logical = step.trailing_zeros() as usize;
+            current += &logicals[logical];
+            class ^= logical_classes[logical];
+            if representatives[class].is_none() {
+                representatives[class] = Some(SparseBinVec::from(&current));
+            }
+        }
+        representatives
+    }
+
+    fn solve(&self, syndrome: SparseBinSlice) -> Option<SparseBinVec> {
+        if syndrome.len() != self.checks.number_of_rows() {
+            panic!(
+                "syndrome of length {} is invalid for {} checks",
+                syndrome.len(),
+                self.checks.number_of_rows()
+            );
+        }
+        let columns = (0..self.checks.number_of_columns()).collect::<Vec<_>>();
+        InformationSet::new(&self.checks, &columns).solve(syndrome)
+    }
+
+    fn run<R: Rng>(&self, syndrome: SparseBinSlice, rng: &mut R) -> Option<CosetVisits> {
+        let solution = self.solve(syndrome)?;
+        let mut class = class_of(&solution, &self.class_logicals);
+        let mut chain = MetropolisChain::new(&self.llrs, &solution);
+        let mut step = |chain: &mut MetropolisChain, class: &mut usize| {
+            if let Some(index) = chain.step(&self.moves, rng) {
+                *class ^= self.move_classes[index];
+            }
+        };
+        for _ in 0..self.burn_in {
+            step(&mut chain, &mut class);
+        }
+        let mut counts = vec![0; self.num_classes()];
+        let mut best: Vec<Option<(f64, DenseCodeword)>> = vec![None; self.num_classes()];
+        for _ in 0..self.num_steps {
+            step(&mut chain, &mut class);
+            counts[class] += 1;
+            if best[class]
+                .as_ref()
+                .is_none_or(|(log_weight, _)| chain.log_weight() > *log_weight)
+            {
+                best[class] = Some((chain.log_weight(), chain.error().clone()));
+            }
+        }
+        Some(CosetVisits { counts, best })
+    }
+}
+
+/// The free energy differences between the logical cosets
+/// for the syndrome of a sampled error.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SyndromeHardness {
+    error: SparseBinVec,
+    error_class: usize,
+    syndrome: SparseBinVec,
+    free_energy_differences: Vec<f64>,
+}
+
+impl SyndromeHardness {
+    /// Returns the sampled error.
+    pub fn error(&self) -> &SparseBinVec {
+        &self.error
+    }
+
+    /// Returns the logical coset of the sampled error.
+    pub fn error_class(&self) -> usize {
+        self.error_class
+    }
+
+    /// Returns the syndrome of the sampled error.
+    pub fn syndrome(&self) -> &SparseBinVec {
+        &self.syndrome
+    }
+
+    /// Returns the free energy of each coset relative to the most likely coset.
+    pub fn free_energy_differences(&self) -> &[f64] {
+        &self.free_energy_differences
+    }
+
+    /// Returns the most likely coset.
+    pub fn most_likely_class(&self) -> usize {
+        (0..self.free_energy_differences.len())
+            .min_by(|first, second| {
+                self.free_energy_differences[*first]
+                    .total_cmp(&self.free_energy_differences[*second])
+            })
+            .unwrap()
+    }
+
+    /// Returns the free energy difference between the two most likely cosets
+    /// or infinity if there is a single coset.
+    pub fn gap(&self) -> f64 {
+        let mut differences = self.free_energy_differences.clone();
+        differences.sort_by(f64::total_cmp);
+        differences.get(1).copied().unwrap_or(f64::INFINITY)
+    }
+
+    /// Checks if the gap between the two most likely cosets is smaller than the threshold,
+    /// that is if the most likely coset is at most `exp(threshold)` times
+    /// more likely than the second one.
+    pub fn is_hard(&self, threshold: f64) -> bool {
+        self.gap() < threshold
+    }
+
+    /// Checks if a maximum likelihood decoder fails for the sampled error.
+    pub fn is_failure(&self) -> bool {
+        self.most_likely_class() != self.error_class
+    }
+}
+
+// The number of steps spent in each coset
+// and the most likely error visited in each coset with its log weight.
+struct CosetVisits {
+    counts: Vec<usize>,
+    best: Vec<Option<(f64, DenseCodeword)>>,
+}
+
+// Returns the bit mask of the logicals anticommuting with the vector.
+fn class_of<T>(vector: &SparseBinVecBase<T>, logicals: &SparseBinMat) -> usize
+where
+    T: std::ops::Deref<Target = [usize]>,
+{
+    logicals
+        .rows()
+        .enumerate()
+        .filter(|(_, logical)| logical.dot_with(vector).unwrap().is_one())
+        .fold(0, |class, (index, _)| class | 1 << index)
+}
+
+/// The correction is the most likely error visited in the most visited coset
+/// or the zero vector if no error has the syndrome.
+/// The chain is seeded from the [`Determinism`] of the sampler,
+/// such that the correction depends only on the syndrome.
+impl<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for CosetSampler {
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        self.run(syndrome, &mut self.determinism.rng())
+            .and_then(|visits| {
+                let class = (0..visits.counts.len()).max_by_key(|class| visits.counts[*class])?;
+                visits.best[class]
+                    .as_ref()
+                    .map(|(_, error)| SparseBinVec::from(error))
+            })
+            .unwrap_or_else(|| SparseBinVec::zeros(self.checks.number_of_columns()))
+    }
+}
+
+impl<'a> ClassicalSyndromeDecoder<'a> for CosetSampler {
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.checks.number_of_rows())
+    }
+}
+
+impl DecoderCapabilities for CosetSampler {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use pauli::{PauliOperator, Y};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn coset_probabilities_of_repetition_code_match_exact_values() {
+        // A repetition code without stabilizers whose logical is the all ones vector
+        // has one error of each coset with a given syndrome.
+        let code = LinearCode::repetition_code(3);
+        let checks = code.parity_check_matrix();
+        let logicals = SparseBinMat::new(3, vec![vec![0, 1, 2]]);
+        let class_logicals = SparseBinMat::new(3, vec![vec![0]]);
+        let sampler = CosetSampler::new(
+            checks,
+            &SparseBinMat::empty(),
+            &logicals,
+            &class_logicals,
+            Probability::new(0.2),
+        )
+        .with_num_steps(50000);
+        assert_eq!(sampler.syndrome_length(), Some(2));
+        let syndrome = SparseBinVec::new(2, vec![1]);
+        // The errors are [2] with weight 0.2 * 0.8^2 and [0, 1] with weight 0.2^2 * 0.8.
+        let probabilities = sampler
+            .coset_probabilities(syndrome.as_view(), &mut StdRng::seed_from_u64(3))
+            .unwrap();
+        assert!((probabilities[0] - 0.8).abs() < 0.02);
+        assert!((probabilities[1] - 0.2).abs() < 0.02);
+        assert_eq!(
+            sampler.correction_for(syndrome.as_view()),
+            SparseBinVec::new(3, vec![2])
+        );
+    }
+
+    #[test]
+    fn free_energy_differences_without_stabilizers_are_exact() {
+        let code = LinearCode::repetition_code(3);
+        let sampler = CosetSampler::new(
+            code.parity_check_matrix(),
+            &SparseBinMat::empty(),
+            &SparseBinMat::new(3, vec![vec![0, 1, 2]]),
+            &SparseBinMat::new(3, vec![vec![0]]),
+            Probability::new(0.2),
+        )
+        .with_num_steps(10);
+        let syndrome = SparseBinVec::new(2, vec![1]);
+        let differences = sampler
+            .free_energy_differences(syndrome.as_view(), 3, &mut StdRng::seed_from_u64(0))
+            .unwrap();
+        assert_eq!(differences[0], 0.0);
+        assert!((differences[1] - 4_f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn hardness_of_sampled_errors() {
+        let code = CssCode::toric_code(3);
+        let sampler = CosetSampler::from_css_code(&code, Probability::new(0.1))
+            .z
+            .with_burn_in(100)
+            .with_num_steps(500);
+        let hardness = sampler.sample_hardness(5, 3, &mut StdRng::seed_from_u64(8));
+        assert_eq!(hardness.len(), 5);
+        for hardness in hardness.iter() {
+            assert_eq!(
+                &(code.z_stabs_binary() * hardness.error()),
+                hardness.syndrome()
+            );
+            assert_eq!(hardness.free_energy_differences().len(), 4);
+            assert_eq!(
+                hardness.free_energy_differences()[hardness.most_likely_class()],
+                0.0
+            );
+            assert!(hardness.gap() >= 0.0);
+            assert!(hardness.is_hard(f64::INFINITY));
+        }
+    }
+
+    #[test]
+    fn corrects_single_errors_of_steane_code() {
+        let code = CssCode::steane_code();
+        let decoder = CosetSampler::from_css_code(&code, Probability::new(0.05))
+            .map(|decoder| decoder.clone().with_num_steps(2000));
+        for qubit in 0..7 {
+            let error = PauliOperator::new(7, vec![qubit], vec![Y]);
+            let syndrome = code.syndrome_of(&error);
+            let correction = PauliOperator::from(decoder.correction_for(syndrome.as_view()));
+            assert!(code.has_stabilizer(&(&correction * &error)));
+        }
+    }
+}
@@ -1,20 +1,200 @@
 use crate::css::{Css, CssOperator, CssSyndromeView};
+use pauli::Pauli;
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
 
-use super::{ClassicalSyndromeDecoder, SyndromeDecoder};
-pub type CssDecoder<D> = Css<D>;
+use super::{
+    Budget, BudgetedCorrection, BudgetedSyndromeDecoder, Capabilities, ClassicalSyndromeDecoder,
+    DecoderCapabilities, SoftSyndromeDecoder, SyndromeDecoder, SyndromeLengthError,
+};
 
-impl<'a, D> SyndromeDecoder<CssSyndromeView<'a>, CssOperator> for CssDecoder<D>
+/// A decoder of CSS codes from a decoder of the X stabilizers
+/// and a decoder of the Z stabilizers.
+///
+/// The decoder of the X stabilizers returns the Z part of the correction and vice-versa.
+/// The two decoders can have different types,
+/// for example to use a stronger decoder for the more likely type of errors
+/// under biased noise.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::decoders::{BpDecoder, CssDecoder, IsdAlgorithm, IsdDecoder, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use pauli::{PauliOperator, X, Z};
+///
+/// let code = CssCode::steane_code();
+/// let decoder = CssDecoder {
+///     x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.1), 10),
+///     z: IsdDecoder::new(&code.stabilizers.z, IsdAlgorithm::Prange, 10),
+/// };
+///
+/// let error = PauliOperator::new(7, vec![0, 3], vec![X, Z]);
+/// let correction = decoder.correction_for(code.syndrome_of(&error).as_view());
+/// assert!(code.has_stabilizer(&(&PauliOperator::from(correction) * &error)));
+/// ```
+pub type CssDecoder<X, Z = X> = Css<X, Z>;
+
+impl<'a, X, Z> SyndromeDecoder<CssSyndromeView<'a>, CssOperator> for CssDecoder<X, Z>
 where
-    D: ClassicalSyndromeDecoder<'a>,
+    X: ClassicalSyndromeDecoder<'a>,
+    Z: ClassicalSyndromeDecoder<'a>,
 {
     fn correction_for(&self, syndrome: CssSyndromeView<'a>) -> CssOperator {
-        self.as_ref()
-            .pair(syndrome)
-            .map(|(decoder, syndrome)| decoder.correction_for(syndrome.clone()))
-            .swap_xz()
+        Css {
+            x: self.z.correction_for(syndrome.z),
+            z: self.x.correction_for(syndrome.x),
+        }
+    }
+}
+
+impl<'a, X, Z> CssDecoder<X, Z>
+where
+    X: ClassicalSyndromeDecoder<'a>,
+    Z: ClassicalSyndromeDecoder<'a>,
+{
+    /// Returns a correction for the syndrome
+    /// or an error if the length of the X or Z part of the syndrome
+    /// is different from the number of checks of the corresponding decoder.
+    pub fn try_correction_for(
+        &self,
+        syndrome: CssSyndromeView<'a>,
+    ) -> Result<CssOperator, SyndromeLengthError> {
+        SyndromeLengthError::check(Some(Pauli::X), syndrome.x.len(), self.x.syndrome_length())?;
+        SyndromeLengthError::check(Some(Pauli::Z), syndrome.z.len(), self.z.syndrome_length())?;
+        Ok(self.correction_for(syndrome))
+    }
+}
+
+/// Both decoders share the same budget.
+/// The correction is found only if both decoders found their part.
+impl<'a, X, Z> BudgetedSyndromeDecoder<CssSyndromeView<'a>, CssOperator> for CssDecoder<X, Z>
+where
+    X: ClassicalSyndromeDecoder<'a> + BudgetedSyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>,
+    Z: ClassicalSyndromeDecoder<'a> + BudgetedSyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>,
+{
+    fn correction_within(
+        &self,
+        syndrome: CssSyndromeView<'a>,
+        budget: Budget,
+    ) -> BudgetedCorrection<CssOperator> {
+        let budget = budget.with_fixed_deadline();
+        let z = self.x.correction_within(syndrome.x, budget);
+        let x = self.z.correction_within(syndrome.z, budget);
+        let is_found = x.is_found() && z.is_found();
+        let correction = Css {
+            x: x.into_correction(),
+            z: z.into_correction(),
+        };
+        if is_found {
+            BudgetedCorrection::Found(correction)
+        } else {
+            BudgetedCorrection::Expired(correction)
+        }
+    }
+}
+
+/// The capabilities supported by both decoders.
+impl<X, Z> DecoderCapabilities for CssDecoder<X, Z>
+where
+    X: DecoderCapabilities,
+    Z: DecoderCapabilities,
+{
+    fn capabilities(&self) -> Capabilities {
+        self.x.capabilities().intersection(&self.z.capabilities())
+    }
+}
+
+impl<X, Z> CssDecoder<X, Z>
+where
+    X: SoftSyndromeDecoder,
+    Z: SoftSyndromeDecoder,
+{
+    /// Returns the qubits sorted from the most to the least reliable
+    /// for both parts of the correction
+    /// given the syndrome and the prior log-likelihood ratios of each part.
+    ///
+    /// As for corrections, the X part is given by the Z decoder
+    /// from the Z syndrome and conversely.
+    /// See [`reliability_order_for`](SoftSyndromeDecoder::reliability_order_for).
+    ///
+    /// # Panic
+    ///
+    /// Panics if the numbers of priors are different from the number of qubits.
+    pub fn reliability_orders_for(
+        &self,
+        syndrome: CssSyndromeView,
+        priors: Css<&[f64]>,
+    ) -> Css<Vec<usize>> {
+        Css {
+            x: self.z.reliability_order_for(syndrome.z, priors.x),
+            z: self.x.reliability_order_for(syndrome.x, priors.z),
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::*;
+    use crate::codes::CssCode;
+    use crate::decoders::{AmpDecoder, BpDecoder, Capability};
+    use crate::noise::Probability;
+    use pauli::PauliOperator;
+
+    #[test]
+    fn reliability_orders_follow_priors_of_each_part() {
+        let code = CssCode::steane_code();
+        let decoder = CssDecoder {
+            x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.1), 10),
+            z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.1), 10),
+        };
+        let syndrome = code.syndrome_of(&PauliOperator::new(7, Vec::new(), Vec::new()));
+        let x_priors = [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 5.0];
+        let z_priors = [5.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+        let orders = decoder.reliability_orders_for(
+            syndrome.as_view(),
+            Css {
+                x: &x_priors,
+                z: &z_priors,
+            },
+        );
+        assert_eq!(orders.x[0], 6);
+        assert_eq!(orders.z[0], 0);
+        let mut qubits = orders.x.clone();
+        qubits.sort();
+        assert_eq!(qubits, (0..7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn asymmetric_decoders_share_their_common_capabilities() {
+        let code = CssCode::steane_code();
+        let decoder = CssDecoder {
+            x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.1), 10),
+            z: AmpDecoder::new(&code.stabilizers.z, Probability::new(0.1), 10),
+        };
+        let capabilities = decoder.capabilities();
+        assert!(capabilities.supports(Capability::SoftInput));
+        assert!(!capabilities.supports(Capability::Budget));
+        assert!(decoder.x.capabilities().supports(Capability::Budget));
+    }
+
+    #[test]
+    fn syndrome_of_wrong_length_is_rejected() {
+        let code = CssCode::steane_code();
+        let decoder = CssDecoder {
+            x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.1), 10),
+            z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.1), 10),
+        };
+        let mut syndrome = code.syndrome_of(&PauliOperator::new(7, vec![0], vec![pauli::X]));
+        assert!(decoder.try_correction_for(syndrome.as_view()).is_ok());
+        syndrome.z = SparseBinVec::zeros(4);
+        assert_eq!(
+            decoder.try_correction_for(syndrome.as_view()),
+            Err(SyndromeLengthError {
+                stabilizers: Some(Pauli::Z),
+                found: 4,
+                expected: 3
+            })
+        );
+    }
 }
@@ -1,7 +1,9 @@
 use sparse_bin_mat::{SparseBinMat, SparseBinSlice};
 
-use super::ErasureDecoder;
+use super::erasure::erased_correction;
+use super::{Capabilities, Capability, DecoderCapabilities, ErasureDecoder};
 use crate::codes::CssCode;
+use crate::css::{Css, CssOperator, CssSyndromeView};
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct CssErasureDecoder<'c> {
@@ -57,15 +59,33 @@ impl<'c> CssErasureDecoder<'c> {
     }
 }
 
-impl<'c> ErasureDecoder for CssErasureDecoder<'c> {
+impl<'a, 'c> ErasureDecoder<CssSyndromeView<'a>, CssOperator> for CssErasureDecoder<'c> {
     fn recovery_probability(&self, erasure: SparseBinSlice) -> f64 {
         let errors = self.error_basis(erasure);
         1.0 / 2.0_f64.powi((self.num_bad_x_errors(&errors) + self.num_bad_z_errors(&errors)) as i32)
     }
+
+    fn correction_for(
+        &self,
+        erasure: SparseBinSlice,
+        syndrome: CssSyndromeView<'a>,
+    ) -> Option<CssOperator> {
+        Some(Css {
+            x: erased_correction(self.code.z_stabs_binary(), erasure.clone(), syndrome.z)?,
+            z: erased_correction(self.code.x_stabs_binary(), erasure, syndrome.x)?,
+        })
+    }
+}
+
+impl<'c> DecoderCapabilities for CssErasureDecoder<'c> {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none().with(Capability::Erasures)
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use pauli::{PauliOperator, X, Y, Z};
     use sparse_bin_mat::SparseBinVec;
 
     use super::*;
@@ -166,6 +186,25 @@ mod test {
         assert_eq!(decoder.recovery_probability(erasure.as_view()), 0.0625);
     }
 
+    #[test]
+    fn correction_of_recoverable_erasure_is_equivalent_to_the_error() {
+        let code = CssCode::toric_code(3);
+        let decoder = CssErasureDecoder::new(&code);
+        let erasure = SparseBinVec::new(18, vec![0, 1, 3, 4, 9, 10, 12, 13]);
+        let error = PauliOperator::new(18, vec![0, 4, 9, 13], vec![X, Y, Z, X]);
+        let syndrome = code.syndrome_of(&error);
+        let correction: PauliOperator = decoder
+            .correction_for(erasure.as_view(), syndrome.as_view())
+            .unwrap()
+            .into();
+        assert!(code.has_stabilizer(&(&error * &correction)));
+
+        let syndrome = code.syndrome_of(&PauliOperator::new(18, vec![17], vec![X]));
+        assert!(decoder
+            .correction_for(erasure.as_view(), syndrome.as_view())
+            .is_none());
+    }
+
     #[test]
     fn erasure_successes_in_toric_code() {
         let code = CssCode::toric_code(3);
@@ -0,0 +1,265 @@
+use super::{
+    Capabilities, ClassicalSyndromeDecoder, DecoderCapabilities, SoftSyndromeDecoder,
+    SyndromeDecoder,
+};
+use crate::noise::Probability;
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
+
+// The prior of a shortened bit is large enough to make a flip negligible
+// while keeping the messages of belief propagation finite.
+const KNOWN_ZERO_LLR: f64 = 30.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Kept,
+    Punctured,
+    Shortened,
+}
+
+/// A decoder for a code obtained by puncturing and shortening a mother code
+/// using a soft decoder of the mother code.
+///
+/// Punctured bits are never received, so they are treated as erasures
+/// with a prior log-likelihood ratio of 0.
+/// Shortened bits are known to be 0, so they get a large prior of 30.
+/// The other bits get the prior of the channel.
+///
+/// Syndromes are those of the mother code for the received bits
+/// padded with zeros on the punctured and shortened bits,
+/// see [`syndrome_of`](DerivedCodeDecoder::syndrome_of).
+/// Corrections are on the kept bits only, in increasing order.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{BpDecoder, DerivedCodeDecoder, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::repetition_code(5);
+/// let probability = Probability::new(0.1);
+/// let decoder = DerivedCodeDecoder::new(
+///     BpDecoder::new(code.parity_check_matrix(), probability, 10),
+///     probability,
+/// )
+/// .with_punctured(&[4]);
+/// assert_eq!(decoder.num_bits(), 4);
+///
+/// let error = SparseBinVec::new(4, vec![1]);
+/// let syndrome = decoder.syndrome_of(error.as_view());
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DerivedCodeDecoder<D> {
+    decoder: D,
+    likelyhood: f64,
+    roles: Vec<Role>,
+}
+
+impl<D: SoftSyndromeDecoder> DerivedCodeDecoder<D> {
+    /// Creates a decoder keeping all the bits of the mother code
+    /// assuming each kept bit flips independently with the given probability.
+    pub fn new(decoder: D, probability: Probability) -> Self {
+        let num_bits = decoder.parity_check_matrix().number_of_columns();
+        Self {
+            decoder,
+            likelyhood: probability.llr(),
+            roles: vec![Role::Kept; num_bits],
+        }
+    }
+
+    /// Marks the given bits of the mother code as punctured.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a bit is out of bound or already shortened.
+    pub fn with_punctured(self, bits: &[usize]) -> Self {
+        self.with_role(bits, Role::Punctured)
+    }
+
+    /// Marks the given bits of the mother code as shortened.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a bit is out of bound or already punctured.
+    pub fn with_shortened(self, bits: &[usize]) -> Self {
+        self.with_role(bits, Role::Shortened)
+    }
+
+    fn with_role(mut self, bits: &[usize], role: Role) -> Self {
+        for bit in bits.iter().cloned() {
+            match self.roles.get(bit) {
+                None => panic!("bit {} is out of bound for {} bits", bit, self.roles.len()),
+                Some(current) if *current != Role::Kept && *current != role => {
+                    panic!("bit {} is both punctured and shortened", bit)
+                }
+                _ => self.roles[bit] = role,
+            }
+        }
+        self
+    }
+
+    /// Returns the decoder of the mother code.
+    pub fn inner(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Returns the number of bits of the derived code.
+    pub fn num_bits(&self) -> usize {
+        self.kept_bits().count()
+    }
+
+    /// Returns the bits of the mother code that are kept, in increasing order.
+    pub fn kept_bits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits_with(Role::Kept)
+    }
+
+    /// Returns the punctured bits of the mother code in increasing order.
+    pub fn punctured_bits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits_with(Role::Punctured)
+    }
+
+    /// Returns the shortened bits of the mother code in increasing order.
+    pub fn shortened_bits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits_with(Role::Shortened)
+    }
+
+    fn bits_with(&self, role: Role) -> impl Iterator<Item = usize> + '_ {
+        self.roles
+            .iter()
+            .enumerate()
+            .filter(move |(_, current)| **current == role)
+            .map(|(bit, _)| bit)
+    }
+
+    /// Returns the syndrome in the mother code of an error on the kept bits.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the error length is different from the number of kept bits.
+    pub fn syndrome_of(&self, error: SparseBinSlice) -> SparseBinVec {
+        self.decoder.parity_check_matrix() * &self.pad(error)
+    }
+
+    /// Returns the prior log-likelihood ratios of the bits of the mother code
+    /// given the priors of the kept bits.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of priors is different from the number of kept bits.
+    pub fn mother_priors(&self, priors: &[f64]) -> Vec<f64> {
+        if priors.len() != self.num_bits() {
+            panic!("{} priors for {} kept bits", priors.len(), self.num_bits());
+        }
+        let mut priors = priors.iter();
+        self.roles
+            .iter()
+            .map(|role| match role {
+                Role::Kept => *priors.next().unwrap(),
+                Role::Punctured => 0.0,
+                Role::Shortened => KNOWN_ZERO_LLR,
+            })
+            .collect()
+    }
+
+    /// Returns a correction for the syndrome on the kept bits
+    /// given the prior log-likelihood ratios of the kept bits,
+    /// for example from a soft channel.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of priors is different from the number of kept bits.
+    pub fn correction_with_priors(&self, syndrome: SparseBinSlice, priors: &[f64]) -> SparseBinVec {
+        let posteriors = self
+            .decoder
+            .posteriors_for(syndrome, &self.mother_priors(priors));
+        let positions = self
+            .kept_bits()
+            .enumerate()
+            .filter(|(_, bit)| posteriors[*bit] < 0.0)
+            .map(|(position, _)| position)
+            .collect();
+        SparseBinVec::new(self.num_bits(), positions)
+    }
+
+    fn pad(&self, error: SparseBinSlice) -> SparseBinVec {
+        if error.len() != self.num_bits() {
+            panic!(
+                "error of length {} for {} kept bits",
+                error.len(),
+                self.num_bits()
+            );
+        }
+        let kept = self.kept_bits().collect::<Vec<_>>();
+        let positions = error
+            .non_trivial_positions()
+            .map(|position| kept[position])
+            .collect();
+        SparseBinVec::new(self.roles.len(), positions)
+    }
+}
+
+/// A bit is flipped if its posterior log-likelihood ratio in the mother code is negative.
+impl<'a, D: SoftSyndromeDecoder> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>
+    for DerivedCodeDecoder<D>
+{
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        self.correction_with_priors(syndrome, &vec![self.likelyhood; self.num_bits()])
+    }
+}
+
+impl<'a, D: SoftSyndromeDecoder> ClassicalSyndromeDecoder<'a> for DerivedCodeDecoder<D> {
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.decoder.parity_check_matrix().number_of_rows())
+    }
+}
+
+impl<D> DecoderCapabilities for DerivedCodeDecoder<D> {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::decoders::BpDecoder;
+
+    #[test]
+    fn shortened_bits_are_never_flipped() {
+        let code = LinearCode::repetition_code(3);
+        let probability = Probability::new(0.1);
+        let bp = BpDecoder::new(code.parity_check_matrix(), probability, 10);
+        let syndrome = SparseBinVec::new(2, vec![1]);
+        assert_eq!(
+            bp.correction_for(syndrome.as_view()),
+            SparseBinVec::new(3, vec![2])
+        );
+
+        let decoder = DerivedCodeDecoder::new(bp, probability).with_shortened(&[2]);
+        assert_eq!(decoder.shortened_bits().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(decoder.syndrome_length(), Some(2));
+        assert_eq!(
+            decoder.correction_for(syndrome.as_view()),
+            SparseBinVec::new(2, vec![0, 1])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_bit_cannot_be_punctured_and_shortened() {
+        let code = LinearCode::repetition_code(3);
+        let probability = Probability::new(0.1);
+        let bp = BpDecoder::new(code.parity_check_matrix(), probability, 10);
+        DerivedCodeDecoder::new(bp, probability)
+            .with_punctured(&[0])
+            .with_shortened(&[0]);
+    }
+}
@@ -0,0 +1,125 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinVec;
+
+/// The controls making the output of a randomized decoder reproducible.
+///
+/// A decoder configured with the same determinism returns identical
+/// corrections for identical inputs,
+/// since its random number generator is seeded at each decoding
+/// and ties are broken by a fixed rule.
+/// The generator is ChaCha12, whose stream is specified,
+/// such that the random choices are the same on every platform
+/// and version of the crate.
+///
+/// The [`IsdDecoder`](super::IsdDecoder) and the [`CosetSampler`](super::CosetSampler)
+/// are the only randomized decoders and take a determinism.
+/// The other decoders have no randomness and visit bits and checks
+/// in the order of their matrices,
+/// such that their output depends only on their input.
+/// Decoders working with floating point numbers may still differ in the last bits
+/// between platforms whose math libraries round differently.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{Determinism, IsdAlgorithm, IsdDecoder, SyndromeDecoder, TieBreaking};
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::hamming_code();
+/// let determinism = Determinism::new(42).with_tie_breaking(TieBreaking::LowestSupport);
+/// let decoder = IsdDecoder::new(code.parity_check_matrix(), IsdAlgorithm::Prange, 20)
+///     .with_determinism(determinism);
+///
+/// let syndrome = code.syndrome_of(&SparseBinVec::new(7, vec![0, 1]));
+/// assert_eq!(
+///     decoder.correction_for(syndrome.as_view()),
+///     decoder.correction_for(syndrome.as_view())
+/// );
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct Determinism {
+    seed: u64,
+    tie_breaking: TieBreaking,
+}
+
+impl Determinism {
+    /// Creates the controls seeding the decoder with the given seed
+    /// and keeping the first correction found among equally good ones.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            tie_breaking: TieBreaking::default(),
+        }
+    }
+
+    /// Sets the rule choosing between equally good corrections.
+    pub fn with_tie_breaking(mut self, tie_breaking: TieBreaking) -> Self {
+        self.tie_breaking = tie_breaking;
+        self
+    }
+
+    /// Returns the seed of the decoder.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the rule choosing between equally good corrections.
+    pub fn tie_breaking(&self) -> TieBreaking {
+        self.tie_breaking
+    }
+
+    /// Returns a new random number generator seeded with the seed.
+    pub fn rng(&self) -> ChaCha12Rng {
+        ChaCha12Rng::seed_from_u64(self.seed)
+    }
+}
+
+/// The rule choosing between corrections of the same weight.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum TieBreaking {
+    /// Keeps the first correction found.
+    ///
+    /// The choice depends on the order the corrections are enumerated.
+    #[default]
+    FirstFound,
+    /// Keeps the correction whose sorted support is the smallest
+    /// in lexicographic order.
+    ///
+    /// The choice doesn't depend on the order the corrections are enumerated,
+    /// but only on the set of corrections found.
+    LowestSupport,
+}
+
+impl TieBreaking {
+    // Checks if the candidate should replace the best correction found so far.
+    pub(crate) fn prefers(self, candidate: &SparseBinVec, best: &SparseBinVec) -> bool {
+        match candidate.weight().cmp(&best.weight()) {
+            std::cmp::Ordering::Less => true,
+            std::cmp::Ordering::Greater => false,
+            std::cmp::Ordering::Equal => match self {
+                Self::FirstFound => false,
+                Self::LowestSupport => candidate.as_slice() < best.as_slice(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ties_are_broken_by_the_rule() {
+        let first = SparseBinVec::new(5, vec![1, 4]);
+        let second = SparseBinVec::new(5, vec![1, 3]);
+        let lighter = SparseBinVec::new(5, vec![4]);
+        assert!(!TieBreaking::FirstFound.prefers(&second, &first));
+        assert!(TieBreaking::LowestSupport.prefers(&second, &first));
+        assert!(!TieBreaking::LowestSupport.prefers(&first, &second));
+        assert!(TieBreaking::FirstFound.prefers(&lighter, &second));
+        assert!(!TieBreaking::LowestSupport.prefers(&first, &lighter));
+    }
+}
@@ -0,0 +1,244 @@
+use super::{CssErasureDecoder, ErasureDecoder};
+use crate::codes::{CssCode, InformationSet, LinearCode};
+use crate::css::{CssOperator, CssSyndromeView};
+use crate::noise::Probability;
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+
+/// The probability is `2^-k` where `k` is the number of independent
+/// codewords supported on the erasure since the correction is off by
+/// a uniformly random one of them.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::LinearCode;
+/// use ldpc::decoders::ErasureDecoder;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::hamming_code();
+/// let erasure = SparseBinVec::new(7, vec![0, 1, 3]);
+/// assert!(code.is_recoverable(erasure.as_view()));
+///
+/// let error = SparseBinVec::new(7, vec![1, 3]);
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(code.correction_for(erasure.as_view(), syndrome.as_view()), Some(error));
+///
+/// // Support of the codeword 0011001.
+/// let erasure = SparseBinVec::new(7, vec![2, 3, 6]);
+/// assert_eq!(code.recovery_probability(erasure.as_view()), 0.5);
+/// ```
+///
+/// # Panic
+///
+/// Panics if the erasure length is different from the length of the code
+/// or if the syndrome length is different from the number of checks.
+impl<'a> ErasureDecoder<SparseBinSlice<'a>, SparseBinVec> for LinearCode {
+    fn is_recoverable(&self, erasure: SparseBinSlice) -> bool {
+        self.can_recover_erasure(&erasure)
+    }
+
+    fn recovery_probability(&self, erasure: SparseBinSlice) -> f64 {
+        0.5_f64.powi(num_erased_codewords(self.parity_check_matrix(), erasure) as i32)
+    }
+
+    fn correction_for(
+        &self,
+        erasure: SparseBinSlice,
+        syndrome: SparseBinSlice<'a>,
+    ) -> Option<SparseBinVec> {
+        erased_correction(self.parity_check_matrix(), erasure, syndrome)
+    }
+}
+
+/// Same as [`CssErasureDecoder`].
+impl<'a> ErasureDecoder<CssSyndromeView<'a>, CssOperator> for CssCode {
+    fn recovery_probability(&self, erasure: SparseBinSlice) -> f64 {
+        CssErasureDecoder::new(self).recovery_probability(erasure)
+    }
+
+    fn correction_for(
+        &self,
+        erasure: SparseBinSlice,
+        syndrome: CssSyndromeView<'a>,
+    ) -> Option<CssOperator> {
+        CssErasureDecoder::new(self).correction_for(erasure, syndrome)
+    }
+}
+
+/// Returns the prior log-likelihood ratio of each bit given the erased bits
+/// for a soft decoder such as [`BpDecoder`](super::BpDecoder).
+///
+/// An erased bit is flipped with probability 1/2, so its ratio is 0,
+/// and the other bits are flipped with the probability of the channel.
+/// The priors are used with [`SoftSyndromeDecoder::posteriors_for`](super::SoftSyndromeDecoder::posteriors_for).
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{erasure_priors, BpDecoder, SoftSyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::repetition_code(3);
+/// let probability = Probability::new(0.1);
+/// let decoder = BpDecoder::new(code.parity_check_matrix(), probability, 10);
+///
+/// // Both erased bits are flipped, which looks like a single flip on the other bit.
+/// let erasure = SparseBinVec::new(3, vec![0, 1]);
+/// let priors = erasure_priors(erasure.as_view(), probability);
+/// assert_eq!(priors[0], 0.0);
+/// assert_eq!(priors[2], probability.llr());
+///
+/// let syndrome = SparseBinVec::new(2, vec![1]);
+/// let posteriors = decoder.posteriors_for(syndrome.as_view(), &priors);
+/// let correction = posteriors.iter().map(|posterior| *posterior < 0.0).collect::<Vec<_>>();
+/// assert_eq!(correction, vec![true, true, false]);
+/// ```
+pub fn erasure_priors(erasure: SparseBinSlice, probability: Probability) -> Vec<f64> {
+    let mut priors = vec![probability.llr(); erasure.len()];
+    for bit in erasure.non_trivial_positions() {
+        priors[bit] = 0.0;
+    }
+    priors
+}
+
+// An error supported on the erasure with the given syndrome.
+pub(super) fn erased_correction(
+    checks: &SparseBinMat,
+    erasure: SparseBinSlice,
+    syndrome: SparseBinSlice,
+) -> Option<SparseBinVec> {
+    erased_information_set(checks, erasure).solve(syndrome)
+}
+
+// The number of independent non trivial vectors on the erasure
+// that are orthogonal to all checks.
+pub(super) fn num_erased_codewords(checks: &SparseBinMat, erasure: SparseBinSlice) -> usize {
+    erasure.weight() - erased_information_set(checks, erasure).rank()
+}
+
+fn erased_information_set(checks: &SparseBinMat, erasure: SparseBinSlice) -> InformationSet {
+    if erasure.len() != checks.number_of_columns() {
+        panic!(
+            "erasure of length {} is invalid for code with length {}",
+            erasure.len(),
+            checks.number_of_columns()
+        );
+    }
+    InformationSet::new(checks, erasure.as_slice())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::{BpDecoder, SoftSyndromeDecoder};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn erasure_priors_improve_belief_propagation() {
+        let code = LinearCode::random_regular_code()
+            .num_bits(40)
+            .num_checks(30)
+            .bit_degree(3)
+            .check_degree(4)
+            .sample_with(&mut StdRng::seed_from_u64(7))
+            .unwrap();
+        let probability = Probability::new(0.02);
+        let decoder = BpDecoder::new(code.parity_check_matrix(), probability, 20);
+        let plain_priors = vec![probability.llr(); 40];
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut num_plain_successes = 0;
+        let mut num_erasure_successes = 0;
+        for _ in 0..200 {
+            let erasure = SparseBinVec::new(40, (0..40).filter(|_| rng.gen_bool(0.15)).collect());
+            let error = SparseBinVec::new(
+                40,
+                (0..40)
+                    .filter(|bit| {
+                        if erasure.is_one_at(*bit).unwrap() {
+                            rng.gen_bool(0.5)
+                        } else {
+                            rng.gen_bool(probability.value())
+                        }
+                    })
+                    .collect(),
+            );
+            let syndrome = code.syndrome_of(&error);
+            let decode = |priors: &[f64]| {
+                let posteriors = decoder.posteriors_for(syndrome.as_view(), priors);
+                SparseBinVec::new(40, (0..40).filter(|bit| posteriors[*bit] < 0.0).collect())
+            };
+            num_plain_successes += (decode(&plain_priors) == error) as usize;
+            num_erasure_successes +=
+                (decode(&erasure_priors(erasure.as_view(), probability)) == error) as usize;
+        }
+        assert!(num_erasure_successes > num_plain_successes);
+    }
+
+    #[test]
+    fn classical_correction_has_the_syndrome_of_the_error() {
+        let code = LinearCode::random_regular_code()
+            .num_bits(24)
+            .num_checks(18)
+            .bit_degree(3)
+            .check_degree(4)
+            .sample_with(&mut StdRng::seed_from_u64(123))
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(456);
+        for _ in 0..100 {
+            let erasure = SparseBinVec::new(24, (0..24).filter(|_| rng.gen_bool(0.4)).collect());
+            let error = SparseBinVec::new(
+                24,
+                erasure
+                    .non_trivial_positions()
+                    .filter(|_| rng.gen_bool(0.5))
+                    .collect(),
+            );
+            let syndrome = code.syndrome_of(&error);
+            let correction = code
+                .correction_for(erasure.as_view(), syndrome.as_view())
+                .unwrap();
+            assert_eq!(code.syndrome_of(&correction), syndrome);
+            assert!(correction
+                .non_trivial_positions()
+                .all(|bit| erasure.is_one_at(bit).unwrap()));
+            if code.is_recoverable(erasure.as_view()) {
+                assert_eq!(correction, error);
+            }
+            assert_eq!(
+                code.is_recoverable(erasure.as_view()),
+                code.recovery_probability(erasure.as_view()) == 1.0
+            );
+        }
+    }
+
+    #[test]
+    fn syndrome_outside_the_erasure_has_no_correction() {
+        let code = LinearCode::repetition_code(5);
+        let erasure = SparseBinVec::new(5, vec![0]);
+        let syndrome = code.syndrome_of(&SparseBinVec::new(5, vec![2]));
+        assert_eq!(
+            code.correction_for(erasure.as_view(), syndrome.as_view()),
+            None
+        );
+        let empty = SparseBinVec::zeros(5);
+        assert_eq!(
+            code.correction_for(empty.as_view(), syndrome.as_view()),
+            None
+        );
+        assert_eq!(
+            code.correction_for(empty.as_view(), SparseBinVec::zeros(4).as_view()),
+            Some(empty)
+        );
+    }
+
+    #[test]
+    fn bits_without_checks_are_never_recovered() {
+        let code = LinearCode::from_parity_check_matrix(SparseBinMat::new(3, vec![vec![0, 1]]));
+        let erasure = SparseBinVec::new(3, vec![0, 2]);
+        assert!(!code.is_recoverable(erasure.as_view()));
+        assert_eq!(code.recovery_probability(erasure.as_view()), 0.5);
+    }
+}
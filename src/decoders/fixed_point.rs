@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+/// How values outside the range of a fixed-point format are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Overflow {
+    /// Values are clamped to the closest representable value.
+    #[default]
+    Saturate,
+    /// Values wrap around as in two's complement arithmetic.
+    Wrap,
+}
+
+/// A signed fixed-point number format used to simulate
+/// decoders running with finite-precision log-likelihood ratios.
+///
+/// Numbers are stored in two's complement using a sign bit,
+/// the given number of integer bits and the given number of fractional bits.
+/// The representable values are the multiples of `2^-fractional_bits`
+/// from `-2^integer_bits` to `2^integer_bits - 2^-fractional_bits`.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::decoders::{FixedPointFormat, Overflow};
+/// let format = FixedPointFormat::new(2, 1);
+///
+/// assert_eq!(format.quantize(1.3), 1.5);
+/// assert_eq!(format.quantize(10.0), 3.5);
+/// assert_eq!(format.quantize(-10.0), -4.0);
+///
+/// let format = format.with_overflow(Overflow::Wrap);
+/// assert_eq!(format.quantize(4.0), -4.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FixedPointFormat {
+    integer_bits: u32,
+    fractional_bits: u32,
+    overflow: Overflow,
+}
+
+impl FixedPointFormat {
+    /// Creates a saturating format with the given number of integer
+    /// and fractional bits.
+    pub fn new(integer_bits: u32, fractional_bits: u32) -> Self {
+        Self {
+            integer_bits,
+            fractional_bits,
+            overflow: Overflow::default(),
+        }
+    }
+
+    /// Sets how the values outside the range of the format are handled.
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Returns the number of integer bits.
+    pub fn integer_bits(&self) -> u32 {
+        self.integer_bits
+    }
+
+    /// Returns the number of fractional bits.
+    pub fn fractional_bits(&self) -> u32 {
+        self.fractional_bits
+    }
+
+    /// Returns the total number of bits including the sign bit.
+    pub fn num_bits(&self) -> u32 {
+        1 + self.integer_bits + self.fractional_bits
+    }
+
+    /// Returns how the values outside the range of the format are handled.
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    /// Returns the difference between two consecutive representable values.
+    pub fn resolution(&self) -> f64 {
+        2.0_f64.powi(-(self.fractional_bits as i32))
+    }
+
+    /// Returns the largest representable value.
+    pub fn max_value(&self) -> f64 {
+        self.max_level() * self.resolution()
+    }
+
+    /// Returns the smallest representable value.
+    pub fn min_value(&self) -> f64 {
+        self.min_level() * self.resolution()
+    }
+
+    /// Rounds the value to the closest representable value,
+    /// handling overflows according to the format.
+    ///
+    /// Infinite values always saturate.
+    pub fn quantize(&self, value: f64) -> f64 {
+        let level = (value / self.resolution()).round();
+        let (min_level, max_level) = (self.min_level(), self.max_level());
+        let level = match self.overflow {
+            Overflow::Wrap if level.is_finite() => {
+                let num_levels = max_level - min_level + 1.0;
+                (level - min_level).rem_euclid(num_levels) + min_level
+            }
+            _ => level.clamp(min_level, max_level),
+        };
+        level * self.resolution()
+    }
+
+    // Levels are the representable values in units of the resolution.
+    fn max_level(&self) -> f64 {
+        -self.min_level() - 1.0
+    }
+
+    fn min_level(&self) -> f64 {
+        -(2.0_f64.powi((self.integer_bits + self.fractional_bits) as i32))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn representable_values_are_unchanged() {
+        let format = FixedPointFormat::new(3, 2);
+        for level in -32..32 {
+            let value = level as f64 * 0.25;
+            assert_eq!(format.quantize(value), value);
+        }
+    }
+
+    #[test]
+    fn saturation() {
+        let format = FixedPointFormat::new(3, 2);
+        assert_eq!(format.quantize(f64::INFINITY), 7.75);
+        assert_eq!(format.quantize(f64::NEG_INFINITY), -8.0);
+        assert_eq!(format.quantize(100.0), format.max_value());
+    }
+
+    #[test]
+    fn wrapping() {
+        let format = FixedPointFormat::new(3, 2).with_overflow(Overflow::Wrap);
+        assert_eq!(format.quantize(8.0), -8.0);
+        assert_eq!(format.quantize(8.5), -7.5);
+        assert_eq!(format.quantize(-8.25), 7.75);
+        assert_eq!(format.quantize(16.0), 0.0);
+        assert_eq!(format.quantize(f64::INFINITY), 7.75);
+    }
+}
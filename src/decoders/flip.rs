@@ -1,5 +1,6 @@
+use super::{ClassicalSyndromeDecoder, SyndromeDecoder};
 use crate::codes::LinearCode;
-use sparse_bin_mat::{SparseBinVec, SparseBinVecBase};
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec, SparseBinVecBase};
 use std::borrow::Borrow;
 use std::fmt;
 
@@ -22,8 +23,14 @@ where
     where
         T: std::ops::Deref<Target = [usize]>,
     {
-        let mut syndrome = self.code().syndrome_of(message);
-        let mut output = SparseBinVec::new(message.len(), message.as_slice().to_vec());
+        let syndrome = self.code().syndrome_of(message);
+        let output = SparseBinVec::new(message.len(), message.as_slice().to_vec());
+        self.flip(syndrome, output)
+    }
+
+    // Flips the bits of the output until no bit is adjacent to a majority of
+    // unsatisfied checks.
+    fn flip(&self, mut syndrome: SparseBinVec, mut output: SparseBinVec) -> SparseBinVec {
         while let Some(bit) = self.find_flippable(&syndrome) {
             let update = SparseBinVec::new(self.code().len(), vec![bit]);
             syndrome = &syndrome + &self.code().syndrome_of(&update);
@@ -47,6 +54,30 @@ where
     }
 }
 
+impl<'a, Code> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for FlipDecoder<Code>
+where
+    Code: Borrow<LinearCode>,
+{
+    fn correction_for(&self, syndrome: SparseBinSlice<'a>) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        let syndrome = SparseBinVec::new(syndrome.len(), syndrome.as_slice().to_vec());
+        self.flip(syndrome, SparseBinVec::zeros(self.code().len()))
+    }
+}
+
+impl<'a, Code> ClassicalSyndromeDecoder<'a> for FlipDecoder<Code>
+where
+    Code: Borrow<LinearCode>,
+{
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.code().num_checks())
+    }
+}
+
 impl<T> fmt::Display for FlipDecoder<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Flip decoder")
@@ -63,6 +94,7 @@ mod test {
         let decoder = FlipDecoder::new(code);
         let error = SparseBinVec::new(7, Vec::new());
         assert_eq!(decoder.decode(&error), SparseBinVec::zeros(7));
+        assert_eq!(decoder.syndrome_length(), Some(3));
     }
 
     #[test]
@@ -0,0 +1,275 @@
+use crate::codes::CssCode;
+use crate::css::{Css, CssSyndrome};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+
+/// The syndromes of a CSS code measured over several rounds.
+///
+/// With noisy measurements, a syndrome alone doesn't locate errors.
+/// Instead, the difference syndrome of a round is the sum of its syndrome
+/// with the previous one, the syndrome before the first round being trivial.
+/// A data error then flips the difference syndrome of the round it appears in
+/// while a measurement error flips the same check in two consecutive rounds.
+///
+/// The difference syndromes of all rounds are
+/// [flattened](SyndromeHistory::flatten) into a single syndrome of the
+/// [space-time matrix](SyndromeHistory::space_time_matrix).
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::css::Css;
+/// use ldpc::decoders::SyndromeHistory;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = CssCode::steane_code();
+/// let mut history = SyndromeHistory::new(&code);
+/// let syndrome = |x: Vec<usize>, z: Vec<usize>| Css {
+///     x: SparseBinVec::new(3, x),
+///     z: SparseBinVec::new(3, z),
+/// };
+///
+/// // A data error before the second round and a measurement error
+/// // of the first Z stabilizer in the third round.
+/// history.push(syndrome(vec![], vec![]));
+/// history.push(syndrome(vec![1], vec![]));
+/// history.push(syndrome(vec![1], vec![0]));
+/// history.push(syndrome(vec![1], vec![]));
+///
+/// let differences = history.difference_syndromes();
+/// assert_eq!(differences[1], syndrome(vec![1], vec![]));
+/// assert_eq!(differences[2], syndrome(vec![], vec![0]));
+///
+/// let events = history.measurement_error_events();
+/// assert_eq!(events.x, vec![]);
+/// assert_eq!(events.z, vec![(2, 0)]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyndromeHistory {
+    num_checks: Css<usize>,
+    rounds: Vec<CssSyndrome>,
+}
+
+impl SyndromeHistory {
+    /// Creates an empty history for the syndromes of the code.
+    pub fn new(code: &CssCode) -> Self {
+        Self {
+            num_checks: Css {
+                x: code.num_x_stabs(),
+                z: code.num_z_stabs(),
+            },
+            rounds: Vec::new(),
+        }
+    }
+
+    /// Adds the syndrome of the next round.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length of the X or Z syndrome is different
+    /// from the number of stabilizers of the same type.
+    pub fn push(&mut self, syndrome: CssSyndrome) {
+        if syndrome.x.len() != self.num_checks.x || syndrome.z.len() != self.num_checks.z {
+            panic!(
+                "syndrome of lengths ({}, {}) for ({}, {}) stabilizers",
+                syndrome.x.len(),
+                syndrome.z.len(),
+                self.num_checks.x,
+                self.num_checks.z
+            );
+        }
+        self.rounds.push(syndrome);
+    }
+
+    /// Returns the number of rounds.
+    pub fn num_rounds(&self) -> usize {
+        self.rounds.len()
+    }
+
+    /// Checks if there is no round.
+    pub fn is_empty(&self) -> bool {
+        self.rounds.is_empty()
+    }
+
+    /// Returns the syndrome of the given round or None if it is out of bound.
+    pub fn round(&self, round: usize) -> Option<&CssSyndrome> {
+        self.rounds.get(round)
+    }
+
+    /// Returns the syndromes of all rounds in order.
+    pub fn rounds(&self) -> &[CssSyndrome] {
+        &self.rounds
+    }
+
+    /// Returns the difference syndrome of each round.
+    pub fn difference_syndromes(&self) -> Vec<CssSyndrome> {
+        let mut previous = Css {
+            x: SparseBinVec::zeros(self.num_checks.x),
+            z: SparseBinVec::zeros(self.num_checks.z),
+        };
+        self.rounds
+            .iter()
+            .map(|syndrome| {
+                let difference = Css {
+                    x: &syndrome.x + &previous.x,
+                    z: &syndrome.z + &previous.z,
+                };
+                previous = syndrome.clone();
+                difference
+            })
+            .collect()
+    }
+
+    /// Returns the pairs of round and stabilizer for which
+    /// the outcome differs from the previous and the next rounds,
+    /// which is the signature of a single measurement error.
+    ///
+    /// The last round is never included since it has no next round.
+    pub fn measurement_error_events(&self) -> Css<Vec<(usize, usize)>> {
+        let differences = self.difference_syndromes();
+        Css {
+            x: repeated_flips(differences.iter().map(|difference| &difference.x)),
+            z: repeated_flips(differences.iter().map(|difference| &difference.z)),
+        }
+    }
+
+    /// Concatenates the difference syndromes of all rounds
+    /// such that the outcome of stabilizer `s` at round `r` is at position
+    /// `r * m + s` where `m` is the number of stabilizers of the same type.
+    pub fn flatten(&self) -> CssSyndrome {
+        let differences = self.difference_syndromes();
+        Css {
+            x: concatenate(
+                differences.iter().map(|difference| &difference.x),
+                self.num_checks.x,
+            ),
+            z: concatenate(
+                differences.iter().map(|difference| &difference.z),
+                self.num_checks.z,
+            ),
+        }
+    }
+
+    /// Returns the parity check matrix relating the errors of a number of rounds
+    /// to the [flattened](SyndromeHistory::flatten) difference syndromes.
+    ///
+    /// For `n` bits, `m` checks and `r` rounds,
+    /// the first `r * n` columns are the data errors appearing
+    /// before each round and the last `(r - 1) * m` columns are
+    /// the measurement errors of all rounds except the last one,
+    /// which is assumed to be perfect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::decoders::SyndromeHistory;
+    ///
+    /// let code = LinearCode::repetition_code(3);
+    /// let matrix = SyndromeHistory::space_time_matrix(code.parity_check_matrix(), 4);
+    ///
+    /// assert_eq!(matrix.number_of_rows(), 4 * 2);
+    /// assert_eq!(matrix.number_of_columns(), 4 * 3 + 3 * 2);
+    /// ```
+    pub fn space_time_matrix(parity_mat: &SparseBinMat, num_rounds: usize) -> SparseBinMat {
+        let num_bits = parity_mat.number_of_columns();
+        let num_checks = parity_mat.number_of_rows();
+        let first_measurement = num_rounds * num_bits;
+        let rows = (0..num_rounds)
+            .flat_map(|round| {
+                parity_mat.rows().enumerate().map(move |(check, row)| {
+                    let data = row
+                        .non_trivial_positions()
+                        .map(|bit| round * num_bits + bit);
+                    let measurements = [round.checked_sub(1), Some(round)]
+                        .into_iter()
+                        .flatten()
+                        .filter(|measurement_round| measurement_round + 1 < num_rounds)
+                        .map(|measurement_round| {
+                            first_measurement + measurement_round * num_checks + check
+                        });
+                    data.chain(measurements).collect()
+                })
+            })
+            .collect();
+        SparseBinMat::new(
+            first_measurement + num_rounds.saturating_sub(1) * num_checks,
+            rows,
+        )
+    }
+}
+
+// The pairs of round and check flipped in both this round and the next one.
+fn repeated_flips<'a>(differences: impl Iterator<Item = &'a SparseBinVec>) -> Vec<(usize, usize)> {
+    let differences = differences.collect::<Vec<_>>();
+    differences
+        .windows(2)
+        .enumerate()
+        .flat_map(|(round, pair)| {
+            pair[0]
+                .non_trivial_positions()
+                .filter(|check| pair[1].is_one_at(*check) == Some(true))
+                .map(move |check| (round, check))
+        })
+        .collect()
+}
+
+fn concatenate<'a>(
+    syndromes: impl Iterator<Item = &'a SparseBinVec>,
+    num_checks: usize,
+) -> SparseBinVec {
+    let mut num_rounds = 0;
+    let mut positions = Vec::new();
+    for syndrome in syndromes {
+        positions.extend(
+            syndrome
+                .non_trivial_positions()
+                .map(|check| num_rounds * num_checks + check),
+        );
+        num_rounds += 1;
+    }
+    SparseBinVec::new(num_rounds * num_checks, positions)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+
+    #[test]
+    fn space_time_matrix_explains_flattened_history() {
+        let code = LinearCode::repetition_code(3);
+        let parity_code =
+            LinearCode::from_parity_check_matrix(SparseBinMat::new(3, vec![vec![0, 1, 2]]));
+        let css_code = CssCode::new(&code, &parity_code);
+        let num_rounds = 3;
+        // Bit 0 flips before round 1 and check 1 is wrongly measured in round 0.
+        let data_errors = [vec![], vec![0], vec![]];
+        let measurement_errors = [vec![1], vec![], vec![]];
+        let mut history = SyndromeHistory::new(&css_code);
+        let mut data = SparseBinVec::zeros(3);
+        for round in 0..num_rounds {
+            data = &data + &SparseBinVec::new(3, data_errors[round].clone());
+            let syndrome =
+                &code.syndrome_of(&data) + &SparseBinVec::new(2, measurement_errors[round].clone());
+            history.push(Css {
+                x: syndrome,
+                z: SparseBinVec::zeros(1),
+            });
+        }
+        let error = SparseBinVec::new(3 * 3 + 2 * 2, vec![3, 3 * 3 + 1]);
+        let matrix = SyndromeHistory::space_time_matrix(code.parity_check_matrix(), num_rounds);
+        assert_eq!(&matrix * &error, history.flatten().x);
+        assert_eq!(history.measurement_error_events().x, vec![(0, 1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn syndrome_length_must_match() {
+        let mut history = SyndromeHistory::new(&CssCode::steane_code());
+        history.push(Css {
+            x: SparseBinVec::zeros(3),
+            z: SparseBinVec::zeros(4),
+        });
+    }
+}
@@ -0,0 +1,405 @@
+use super::{
+    Capabilities, ClassicalSyndromeDecoder, DecoderCapabilities, Determinism, SyndromeDecoder,
+    TieBreaking,
+};
+use crate::codes::information_set::{add_to, is_one_at, weight};
+use crate::codes::InformationSet;
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+use std::collections::HashMap;
+
+/// An algorithm of information set decoding.
+///
+/// Each iteration chooses the pivots of a random information set
+/// and tries a few low weight patterns on the other bits,
+/// keeping the lowest weight solution found.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum IsdAlgorithm {
+    /// Tries the solution supported on the pivots.
+    ///
+    /// When searching for codewords, tries each bit outside the pivots instead.
+    Prange,
+    /// Splits the bits outside the pivots in two halves and tries
+    /// each pair of patterns of at most `weight` bits in each half
+    /// whose solution vanishes on `window` of the pivots.
+    ///
+    /// The window is at most 64.
+    Stern { weight: usize, window: usize },
+}
+
+/// A decoder returning the lowest weight error found by information set decoding.
+///
+/// This is close to maximum likelihood decoding of the binary symmetric channel
+/// with enough iterations, but the number of iterations needed grows exponentially
+/// with the length of the code.
+/// The random number generator is seeded at each decoding,
+/// such that the correction of a syndrome is always the same.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{IsdAlgorithm, IsdDecoder, SyndromeDecoder};
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::hamming_code();
+/// let decoder = IsdDecoder::new(code.parity_check_matrix(), IsdAlgorithm::Prange, 20);
+///
+/// let error = SparseBinVec::new(7, vec![4]);
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct IsdDecoder {
+    parity_mat: SparseBinMat,
+    algorithm: IsdAlgorithm,
+    num_iterations: usize,
+    determinism: Determinism,
+}
+
+impl IsdDecoder {
+    pub fn new(parity_mat: &SparseBinMat, algorithm: IsdAlgorithm, num_iterations: usize) -> Self {
+        Self {
+            parity_mat: parity_mat.clone(),
+            algorithm,
+            num_iterations,
+            determinism: Determinism::default(),
+        }
+    }
+
+    /// Sets the seed of the random information sets.
+    ///
+    /// Default is 0.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.determinism =
+            Determinism::new(seed).with_tie_breaking(self.determinism.tie_breaking());
+        self
+    }
+
+    /// Sets the seed of the random information sets
+    /// and the rule choosing between solutions of the same weight.
+    ///
+    /// Default is a seed of 0 keeping the first solution found.
+    pub fn with_determinism(mut self, determinism: Determinism) -> Self {
+        self.determinism = determinism;
+        self
+    }
+}
+
+/// Returns the zero vector if the syndrome is not the syndrome of any error.
+impl<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for IsdDecoder {
+    fn correction_for(&self, syndrome: SparseBinSlice<'a>) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        let zeros = SparseBinVec::zeros(self.parity_mat.number_of_columns());
+        if syndrome.is_zero() {
+            return zeros;
+        }
+        lowest_weight_solution(
+            &self.parity_mat,
+            syndrome,
+            self.algorithm,
+            self.num_iterations,
+            self.determinism.tie_breaking(),
+            &mut self.determinism.rng(),
+        )
+        .unwrap_or(zeros)
+    }
+}
+
+impl<'a> ClassicalSyndromeDecoder<'a> for IsdDecoder {
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.parity_mat.number_of_rows())
+    }
+}
+
+// The lowest weight non trivial vector with the given syndrome found by
+// information set decoding or None if there is no such vector.
+pub(crate) fn lowest_weight_solution<R: Rng>(
+    matrix: &SparseBinMat,
+    syndrome: SparseBinSlice,
+    algorithm: IsdAlgorithm,
+    num_iterations: usize,
+    tie_breaking: TieBreaking,
+    rng: &mut R,
+) -> Option<SparseBinVec> {
+    if syndrome.len() != matrix.number_of_rows() {
+        panic!(
+            "syndrome of length {} for {} rows",
+            syndrome.len(),
+            matrix.number_of_rows()
+        );
+    }
+    let mut order = (0..matrix.number_of_columns()).collect::<Vec<_>>();
+    let mut best: Option<SparseBinVec> = None;
+    for _ in 0..num_iterations {
+        order.shuffle(rng);
+        let information_set = InformationSet::new(matrix, &order);
+        let iteration = Iteration::new(&information_set, syndrome.clone(), &order)?;
+        let candidate = match algorithm {
+            IsdAlgorithm::Prange if syndrome.is_zero() => (0..iteration.non_pivots.len())
+                .filter_map(|bit| iteration.solution(&[bit]))
+                .reduce(|best, candidate| {
+                    if tie_breaking.prefers(&candidate, &best) {
+                        candidate
+                    } else {
+                        best
+                    }
+                }),
+            IsdAlgorithm::Prange => iteration.solution(&[]),
+            IsdAlgorithm::Stern { weight, window } => iteration.stern(weight, window, tie_breaking),
+        };
+        if let Some(candidate) = candidate {
+            match &best {
+                Some(best) if !tie_breaking.prefers(&candidate, best) => (),
+                _ => best = Some(candidate),
+            }
+        }
+    }
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        num_iterations,
+        weight = best.as_ref().map(|best| best.weight()),
+        "information set decoding finished"
+    );
+    best
+}
+
+// The reduced syndrome and columns of an information set.
+struct Iteration<'a> {
+    information_set: &'a InformationSet,
+    syndrome: Vec<u64>,
+    num_columns: usize,
+    non_pivots: Vec<usize>,
+    columns: Vec<Vec<u64>>,
+}
+
+impl<'a> Iteration<'a> {
+    fn new(
+        information_set: &'a InformationSet,
+        syndrome: SparseBinSlice,
+        order: &[usize],
+    ) -> Option<Self> {
+        let syndrome = information_set.reduced_syndrome(syndrome)?;
+        let mut is_pivot = vec![false; order.len()];
+        for pivot in information_set.pivots() {
+            is_pivot[*pivot] = true;
+        }
+        let non_pivots = order
+            .iter()
+            .cloned()
+            .filter(|column| !is_pivot[*column])
+            .collect::<Vec<_>>();
+        let columns = non_pivots
+            .iter()
+            .map(|column| information_set.reduced_column(*column))
+            .collect();
+        Some(Self {
+            information_set,
+            syndrome,
+            num_columns: order.len(),
+            non_pivots,
+            columns,
+        })
+    }
+
+    // The non trivial solution flipping the given non pivots.
+    fn solution(&self, flipped: &[usize]) -> Option<SparseBinVec> {
+        let mut pivots = self.syndrome.clone();
+        for bit in flipped {
+            add_to(&mut pivots, &self.columns[*bit]);
+        }
+        if flipped.is_empty() && weight(&pivots) == 0 {
+            return None;
+        }
+        let mut positions = flipped
+            .iter()
+            .map(|bit| self.non_pivots[*bit])
+            .chain(
+                self.information_set
+                    .pivots()
+                    .iter()
+                    .enumerate()
+                    .filter(|(row, _)| is_one_at(&pivots, *row))
+                    .map(|(_, pivot)| *pivot),
+            )
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        Some(SparseBinVec::new(self.num_columns, positions))
+    }
+
+    fn stern(
+        &self,
+        weight: usize,
+        window: usize,
+        tie_breaking: TieBreaking,
+    ) -> Option<SparseBinVec> {
+        let window = window.min(64).min(self.information_set.rank());
+        let mask = if window == 64 {
+            u64::MAX
+        } else {
+            (1 << window) - 1
+        };
+        let key = |bits: &[usize]| {
+            bits.iter()
+                .fold(self.syndrome.first().cloned().unwrap_or(0), |key, bit| {
+                    key ^ self.columns[*bit].first().cloned().unwrap_or(0)
+                })
+                & mask
+        };
+        let half = self.non_pivots.len() / 2;
+        let mut first_patterns = HashMap::<u64, Vec<Vec<usize>>>::new();
+        for pattern in (0..=weight).flat_map(|weight| (0..half).combinations(weight)) {
+            first_patterns
+                .entry(key(&pattern))
+                .or_default()
+                .push(pattern);
+        }
+        let mut best: Option<SparseBinVec> = None;
+        for second in
+            (0..=weight).flat_map(|weight| (half..self.non_pivots.len()).combinations(weight))
+        {
+            // The key of both patterns is the key of the syndrome when they collide.
+            let collision = key(&second) ^ key(&[]);
+            for first in first_patterns.get(&collision).into_iter().flatten() {
+                let pattern = first
+                    .iter()
+                    .chain(second.iter())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if let Some(candidate) = self.solution(&pattern) {
+                    match &best {
+                        Some(best) if !tie_breaking.prefers(&candidate, best) => (),
+                        _ => best = Some(candidate),
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+impl DecoderCapabilities for IsdDecoder {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::noise::{BinarySymmetricChannel, Probability};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn both_algorithms_find_low_weight_errors() {
+        let code = LinearCode::random_regular_code()
+            .num_bits(40)
+            .num_checks(30)
+            .bit_degree(3)
+            .check_degree(4)
+            .sample_with(&mut StdRng::seed_from_u64(123))
+            .unwrap();
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.05));
+        let mut rng = StdRng::seed_from_u64(456);
+        for algorithm in [
+            IsdAlgorithm::Prange,
+            IsdAlgorithm::Stern {
+                weight: 1,
+                window: 4,
+            },
+        ] {
+            let decoder = IsdDecoder::new(code.parity_check_matrix(), algorithm, 200);
+            assert_eq!(decoder.syndrome_length(), Some(30));
+            for _ in 0..20 {
+                let error = code.random_error(&noise, &mut rng);
+                let syndrome = code.syndrome_of(&error);
+                let correction = decoder.correction_for(syndrome.as_view());
+                assert_eq!(code.syndrome_of(&correction), syndrome);
+                assert!(correction.weight() <= error.weight());
+            }
+        }
+    }
+
+    #[test]
+    fn zero_syndrome_gives_low_weight_codewords() {
+        let code = LinearCode::hamming_code();
+        let mut rng = StdRng::seed_from_u64(123);
+        let zero = SparseBinVec::zeros(3);
+        for algorithm in [
+            IsdAlgorithm::Prange,
+            IsdAlgorithm::Stern {
+                weight: 1,
+                window: 2,
+            },
+        ] {
+            let codeword = lowest_weight_solution(
+                code.parity_check_matrix(),
+                zero.as_view(),
+                algorithm,
+                50,
+                TieBreaking::FirstFound,
+                &mut rng,
+            )
+            .unwrap();
+            assert!(code.has_codeword(&codeword));
+            assert_eq!(codeword.weight(), 3);
+        }
+    }
+
+    #[test]
+    fn unreachable_syndrome_has_no_solution() {
+        let matrix = SparseBinMat::new(3, vec![vec![0, 1], vec![0, 1]]);
+        let syndrome = SparseBinVec::new(2, vec![0]);
+        assert_eq!(
+            lowest_weight_solution(
+                &matrix,
+                syndrome.as_view(),
+                IsdAlgorithm::Prange,
+                10,
+                TieBreaking::FirstFound,
+                &mut StdRng::seed_from_u64(123)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn lowest_support_ties_dont_depend_on_seed() {
+        let code = LinearCode::repetition_code(4);
+        let first = SparseBinVec::new(4, vec![0, 1]);
+        let second = SparseBinVec::new(4, vec![2, 3]);
+        let syndrome = code.syndrome_of(&first);
+        assert_eq!(code.syndrome_of(&second), syndrome);
+
+        let corrections_for = |tie_breaking, num_iterations| {
+            (0..20)
+                .map(|seed| {
+                    let determinism = Determinism::new(seed).with_tie_breaking(tie_breaking);
+                    IsdDecoder::new(
+                        code.parity_check_matrix(),
+                        IsdAlgorithm::Prange,
+                        num_iterations,
+                    )
+                    .with_determinism(determinism)
+                    .correction_for(syndrome.as_view())
+                })
+                .collect::<Vec<_>>()
+        };
+        // With a single information set, the correction depends on the seed.
+        let first_found = corrections_for(TieBreaking::FirstFound, 1);
+        assert!(first_found.contains(&first));
+        assert!(first_found.contains(&second));
+        assert!(corrections_for(TieBreaking::LowestSupport, 50)
+            .iter()
+            .all(|correction| *correction == first));
+    }
+}
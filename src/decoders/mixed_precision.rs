@@ -0,0 +1,240 @@
+use super::{
+    BpDecoder, Capabilities, Capability, ClassicalSyndromeDecoder, DecoderCapabilities,
+    FixedPointFormat, Overflow, SoftSyndromeDecoder, SyndromeDecoder,
+};
+use itertools::Itertools;
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A belief propagation decoder simulating a low precision decoder
+/// that falls back to floating point numbers when the low precision is not enough.
+///
+/// Each syndrome is first decoded with log-likelihood ratios and messages
+/// rounded to the given [fixed-point format](FixedPointFormat).
+/// The syndrome is decoded again with the floating point decoder
+/// if the low precision decoding stalls, that is its correction doesn't match the syndrome
+/// within the maximum number of iterations,
+/// or if a posterior log-likelihood ratio saturates,
+/// that is its magnitude reaches the largest representable value,
+/// with the opposite sign of its prior.
+/// Such a bit was overturned by messages clipped to the range of the format,
+/// while bits saturating in the direction of their prior only become more reliable.
+///
+/// The low precision decoding is simulated with floating point numbers,
+/// so this is not faster than the [`BpDecoder`] and each fallback decodes twice.
+/// It measures how often a hardware decoder using the format would need a fallback,
+/// such that the format can be tuned for the fallbacks to be rare.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{BpDecoder, FixedPointFormat, MixedPrecisionBpDecoder, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::hamming_code();
+/// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+/// let decoder = MixedPrecisionBpDecoder::new(decoder, FixedPointFormat::new(5, 2));
+///
+/// let error = SparseBinVec::new(7, vec![2]);
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+///
+/// assert_eq!(decoder.num_decodings(), 1);
+/// assert_eq!(decoder.num_fallbacks(), 0);
+/// ```
+#[derive(Debug)]
+pub struct MixedPrecisionBpDecoder {
+    low_precision: BpDecoder,
+    full_precision: BpDecoder,
+    num_decodings: AtomicUsize,
+    num_stalls: AtomicUsize,
+    num_saturations: AtomicUsize,
+}
+
+impl MixedPrecisionBpDecoder {
+    /// Creates a decoder running the given decoder in the given format
+    /// and falling back to the given decoder in floating point numbers.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the given decoder already uses a fixed-point format
+    /// or if the format [wraps](Overflow::Wrap) on overflow,
+    /// since saturations can't be detected.
+    pub fn new(decoder: BpDecoder, format: FixedPointFormat) -> Self {
+        if let Some(other) = decoder.fixed_point_format() {
+            panic!("decoder already uses the fixed-point format {:?}", other);
+        }
+        if format.overflow() == Overflow::Wrap {
+            panic!("format {:?} wraps on overflow", format);
+        }
+        Self {
+            low_precision: decoder.clone().with_fixed_point(format),
+            full_precision: decoder,
+            num_decodings: AtomicUsize::new(0),
+            num_stalls: AtomicUsize::new(0),
+            num_saturations: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the format of the low precision decoding.
+    pub fn format(&self) -> FixedPointFormat {
+        self.low_precision.fixed_point_format().unwrap()
+    }
+
+    /// Returns the number of decoded syndromes.
+    pub fn num_decodings(&self) -> usize {
+        self.num_decodings.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of syndromes decoded again
+    /// because the low precision decoding stalled.
+    pub fn num_stalls(&self) -> usize {
+        self.num_stalls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of syndromes decoded again
+    /// because a posterior saturated against its prior
+    /// while the low precision decoding converged.
+    pub fn num_saturations(&self) -> usize {
+        self.num_saturations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of syndromes decoded again in floating point numbers.
+    pub fn num_fallbacks(&self) -> usize {
+        self.num_stalls() + self.num_saturations()
+    }
+
+    /// Returns the fraction of the syndromes decoded again in floating point numbers
+    /// or None if no syndrome was decoded.
+    pub fn fallback_rate(&self) -> Option<f64> {
+        let num_decodings = self.num_decodings();
+        (num_decodings > 0).then(|| self.num_fallbacks() as f64 / num_decodings as f64)
+    }
+
+    /// Resets the counts of decodings and fallbacks to 0.
+    pub fn reset_counts(&self) {
+        self.num_decodings.store(0, Ordering::Relaxed);
+        self.num_stalls.store(0, Ordering::Relaxed);
+        self.num_saturations.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Clone for MixedPrecisionBpDecoder {
+    fn clone(&self) -> Self {
+        Self {
+            low_precision: self.low_precision.clone(),
+            full_precision: self.full_precision.clone(),
+            num_decodings: AtomicUsize::new(self.num_decodings()),
+            num_stalls: AtomicUsize::new(self.num_stalls()),
+            num_saturations: AtomicUsize::new(self.num_saturations()),
+        }
+    }
+}
+
+impl<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for MixedPrecisionBpDecoder {
+    fn correction_for(&self, syndrome: SparseBinSlice<'a>) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        self.num_decodings.fetch_add(1, Ordering::Relaxed);
+        let posteriors = self
+            .low_precision
+            .posteriors_for(syndrome.clone(), self.low_precision.priors());
+        let correction = SparseBinVec::new(
+            posteriors.len(),
+            posteriors
+                .iter()
+                .positions(|posterior| *posterior < 0.0)
+                .collect(),
+        );
+        let format = self.format();
+        let saturates_against_prior = |(posterior, prior): (&f64, &f64)| {
+            posterior.abs() >= format.max_value() && posterior * prior < 0.0
+        };
+        if (self.low_precision.parity_check_matrix() * &correction).as_view() != syndrome {
+            self.num_stalls.fetch_add(1, Ordering::Relaxed);
+        } else if posteriors
+            .iter()
+            .zip(self.low_precision.priors())
+            .any(saturates_against_prior)
+        {
+            self.num_saturations.fetch_add(1, Ordering::Relaxed);
+        } else {
+            return correction;
+        }
+        self.full_precision.correction_for(syndrome)
+    }
+}
+
+impl<'a> ClassicalSyndromeDecoder<'a> for MixedPrecisionBpDecoder {
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.low_precision.num_checks())
+    }
+}
+
+impl DecoderCapabilities for MixedPrecisionBpDecoder {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none().with(Capability::SoftInput)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::noise::Probability;
+
+    #[test]
+    fn posteriors_saturated_against_priors_fall_back_to_full_precision() {
+        let code = LinearCode::repetition_code(5);
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.01), 10);
+        let mixed = MixedPrecisionBpDecoder::new(decoder.clone(), FixedPointFormat::new(1, 2));
+        assert_eq!(mixed.syndrome_length(), Some(4));
+
+        // The priors saturate, but in their own direction.
+        let syndrome = SparseBinVec::zeros(4);
+        assert_eq!(
+            mixed.correction_for(syndrome.as_view()),
+            SparseBinVec::zeros(5)
+        );
+        assert_eq!(mixed.num_fallbacks(), 0);
+
+        let error = SparseBinVec::new(5, vec![2]);
+        let syndrome = code.syndrome_of(&error);
+        assert_eq!(
+            mixed.correction_for(syndrome.as_view()),
+            decoder.correction_for(syndrome.as_view())
+        );
+        assert_eq!(mixed.num_saturations(), 1);
+        assert_eq!(mixed.fallback_rate(), Some(0.5));
+        mixed.reset_counts();
+        assert_eq!(mixed.fallback_rate(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrapping_formats_are_rejected() {
+        let code = LinearCode::hamming_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        MixedPrecisionBpDecoder::new(
+            decoder,
+            FixedPointFormat::new(4, 2).with_overflow(Overflow::Wrap),
+        );
+    }
+
+    #[test]
+    fn stalled_decodings_fall_back_to_full_precision() {
+        let code = LinearCode::hamming_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        // Without fractional bits, the messages round to 0 and nothing is corrected.
+        let mixed = MixedPrecisionBpDecoder::new(decoder.clone(), FixedPointFormat::new(8, 0));
+        let syndrome = code.syndrome_of(&SparseBinVec::new(7, vec![0]));
+        let correction = mixed.correction_for(syndrome.as_view());
+        assert_eq!(code.syndrome_of(&correction), syndrome);
+        assert_eq!((mixed.num_stalls(), mixed.num_saturations()), (1, 0));
+    }
+}
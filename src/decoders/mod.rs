@@ -1,3 +1,28 @@
+//! Decoders for classical and quantum codes.
+//!
+//! Every decoder reports its [`Capabilities`](Capabilities) at runtime
+//! through the [`DecoderCapabilities`](DecoderCapabilities) trait,
+//! such that an experiment can check a configuration before running.
+//!
+//! | Decoder | Soft input | Erasures | Noisy syndromes | Budget |
+//! |---|---|---|---|---|
+//! | [`BpDecoder`](BpDecoder) | yes | | | yes |
+//! | [`AmpDecoder`](AmpDecoder) | yes | | | |
+//! | [`TurboDecoder`](TurboDecoder) | yes | | | |
+//! | [`ConcatenatedDecoder`](ConcatenatedDecoder) | yes | | | |
+//! | [`QuasiCyclicBpDecoder`](QuasiCyclicBpDecoder) | | | | |
+//! | [`IsdDecoder`](IsdDecoder) | | | | |
+//! | [`OsdDecoder`](OsdDecoder) | | | | |
+//! | [`PolarDecoder`](PolarDecoder) | | | | |
+//! | [`ProductDecoder`](ProductDecoder) | | | | |
+//! | [`PermutationDecoder`](PermutationDecoder) | | | | |
+//! | [`DerivedCodeDecoder`](DerivedCodeDecoder) | | | | |
+//! | [`CosetSampler`](CosetSampler) | | | | |
+//! | [`CssErasureDecoder`](CssErasureDecoder) | | yes | | |
+//! | [`WindowedDecoder`](WindowedDecoder) | | | yes | |
+//!
+//! A [`CssDecoder`](CssDecoder) has the capabilities shared by its two decoders.
+
 mod flip;
 pub use flip::FlipDecoder;
 
@@ -7,10 +32,83 @@ pub use css::CssDecoder;
 mod belief_propagation;
 pub use belief_propagation::BpDecoder;
 
+mod weights;
+pub use weights::{BpWeights, BpWeightsError};
+
+mod amp;
+pub use amp::AmpDecoder;
+
+mod cache;
+pub use cache::DecoderCache;
+
+mod capabilities;
+pub use capabilities::{Capabilities, Capability, DecoderCapabilities, MissingCapabilities};
+
+mod budget;
+pub use budget::{Budget, BudgetedCorrection};
+
+mod fixed_point;
+pub use fixed_point::{FixedPointFormat, Overflow};
+
+mod mixed_precision;
+pub use mixed_precision::MixedPrecisionBpDecoder;
+
+mod polar;
+pub use polar::PolarDecoder;
+
+mod quasi_cyclic;
+pub use quasi_cyclic::QuasiCyclicBpDecoder;
+
+mod product;
+pub use product::ProductDecoder;
+
+mod turbo;
+pub use turbo::TurboDecoder;
+
+mod concatenated;
+pub use concatenated::ConcatenatedDecoder;
+
+mod erasure;
+pub use erasure::erasure_priors;
+
 mod css_erasure;
 pub use css_erasure::CssErasureDecoder;
 
-use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
+mod windowed;
+pub use windowed::WindowedDecoder;
+
+mod history;
+pub use history::SyndromeHistory;
+
+mod schedule;
+pub use schedule::MeasurementSchedule;
+
+mod packed;
+pub use packed::{PackedCssSyndrome, PackedSyndrome};
+
+mod permutation;
+pub use permutation::PermutationDecoder;
+
+mod derived;
+pub use derived::DerivedCodeDecoder;
+
+mod coset_sampler;
+pub use coset_sampler::{CosetSampler, SyndromeHardness};
+
+mod determinism;
+pub use determinism::{Determinism, TieBreaking};
+
+mod syndrome_length;
+pub use syndrome_length::SyndromeLengthError;
+
+mod isd;
+pub(crate) use isd::lowest_weight_solution;
+pub use isd::{IsdAlgorithm, IsdDecoder};
+
+mod osd;
+pub use osd::{OsdDecoder, OsdMethod};
+
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
 
 pub trait LinearDecoder {
     fn decode(&self, message: SparseBinSlice) -> SparseBinVec;
@@ -20,8 +118,192 @@ pub trait SyndromeDecoder<Syndrome, Correction> {
     fn correction_for(&self, syndrome: Syndrome) -> Correction;
 }
 
-pub trait ClassicalSyndromeDecoder<'a>: SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> {}
+/// A syndrome decoder returning its best guess when a budget expires.
+pub trait BudgetedSyndromeDecoder<Syndrome, Correction>:
+    SyndromeDecoder<Syndrome, Correction>
+{
+    /// Returns a correction for the syndrome found within the budget
+    /// or the best guess of the decoder when the budget expired.
+    fn correction_within(
+        &self,
+        syndrome: Syndrome,
+        budget: Budget,
+    ) -> BudgetedCorrection<Correction>;
+}
+
+/// A syndrome decoder returning soft information
+/// that can be exchanged with other decoders.
+///
+/// Log-likelihood ratios are `ln(P(0) / P(1))` for each bit of the error,
+/// such that a negative value means that the bit is likely flipped.
+pub trait SoftSyndromeDecoder {
+    /// Returns the parity check matrix defining the syndromes.
+    fn parity_check_matrix(&self) -> &SparseBinMat;
+
+    /// Returns the posterior log-likelihood ratio of each bit
+    /// given the syndrome and the prior log-likelihood ratios.
+    fn posteriors_for(&self, syndrome: SparseBinSlice, priors: &[f64]) -> Vec<f64>;
+
+    /// Returns the bits sorted from the most to the least reliable
+    /// given the syndrome and the prior log-likelihood ratios.
+    ///
+    /// The reliability of a bit is the absolute value of its posterior
+    /// log-likelihood ratio and bits of equal reliability keep their order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::decoders::{BpDecoder, SoftSyndromeDecoder};
+    /// use ldpc::noise::Probability;
+    /// use sparse_bin_mat::SparseBinVec;
+    ///
+    /// let code = LinearCode::repetition_code(3);
+    /// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+    ///
+    /// // The last bit is known to be noisier.
+    /// let priors = [2.0, 2.0, 0.5];
+    /// let syndrome = SparseBinVec::new(2, vec![1]);
+    /// let order = decoder.reliability_order_for(syndrome.as_view(), &priors);
+    /// assert_eq!(order.last(), Some(&2));
+    /// ```
+    fn reliability_order_for(&self, syndrome: SparseBinSlice, priors: &[f64]) -> Vec<usize> {
+        let reliabilities = self
+            .posteriors_for(syndrome, priors)
+            .into_iter()
+            .map(f64::abs)
+            .collect::<Vec<_>>();
+        let mut order = (0..reliabilities.len()).collect::<Vec<_>>();
+        order.sort_by(|first, second| reliabilities[*second].total_cmp(&reliabilities[*first]));
+        order
+    }
+}
+
+/// A decoder of the syndromes of a classical code.
+///
+/// The [`correction_for`](SyndromeDecoder::correction_for) method trusts its syndrome
+/// and may panic or return a meaningless correction if the syndrome has the wrong length.
+/// The decoders of this crate check the length only with debug assertions.
+/// Use [`try_correction_for`](Self::try_correction_for) to decode syndromes
+/// whose length is not known in advance.
+pub trait ClassicalSyndromeDecoder<'a>: SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> {
+    /// Returns the length of the syndromes, that is the number of checks,
+    /// or None if the decoder doesn't know it.
+    ///
+    /// The default implementation returns None,
+    /// such that syndromes are decoded without being checked.
+    fn syndrome_length(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns a correction for the syndrome
+    /// or an error if the syndrome length is different from the
+    /// [number of checks](Self::syndrome_length).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::decoders::{BpDecoder, ClassicalSyndromeDecoder, SyndromeLengthError};
+    /// use ldpc::noise::Probability;
+    /// use sparse_bin_mat::SparseBinVec;
+    ///
+    /// let code = LinearCode::hamming_code();
+    /// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+    ///
+    /// let syndrome = SparseBinVec::new(4, vec![0]);
+    /// assert_eq!(
+    ///     decoder.try_correction_for(syndrome.as_view()),
+    ///     Err(SyndromeLengthError { stabilizers: None, found: 4, expected: 3 })
+    /// );
+    /// ```
+    fn try_correction_for(
+        &self,
+        syndrome: SparseBinSlice<'a>,
+    ) -> Result<SparseBinVec, SyndromeLengthError> {
+        SyndromeLengthError::check(None, syndrome.len(), self.syndrome_length())?;
+        Ok(self.correction_for(syndrome))
+    }
+}
+
+/// A decoder of errors supported on a known set of erased bits.
+///
+/// Every error on the erasure is equally likely,
+/// such that an erasure is recovered with certainty only if all errors
+/// on the erasure with the same syndrome are equivalent.
+pub trait ErasureDecoder<Syndrome, Correction> {
+    /// Checks if every error on the erasure is recovered from its syndrome.
+    fn is_recoverable(&self, erasure: SparseBinSlice) -> bool {
+        self.recovery_probability(erasure) == 1.0
+    }
 
-pub trait ErasureDecoder {
+    /// Returns the probability that a uniformly random error on the erasure
+    /// is recovered from its syndrome.
     fn recovery_probability(&self, erasure: SparseBinSlice) -> f64;
+
+    /// Returns an error supported on the erasure with the given syndrome
+    /// or None if there is no such error.
+    fn correction_for(&self, erasure: SparseBinSlice, syndrome: Syndrome) -> Option<Correction>;
+}
+
+/// A decoder receiving syndromes round by round
+/// and emitting corrections with a bounded latency.
+pub trait StreamingDecoder<Syndrome, Correction> {
+    /// Pushes the syndrome of the next measurement round
+    /// and returns the correction committed during this round if any.
+    fn push_round(&mut self, syndrome: Syndrome) -> Option<Correction>;
+
+    /// Commits a correction for all the rounds still waiting in the decoder.
+    fn flush(&mut self) -> Correction;
+
+    /// Returns the maximum number of rounds pushed after a round
+    /// before its correction is committed.
+    fn latency(&self) -> usize;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::noise::Probability;
+
+    // Decodes every syndrome to the zero correction without knowing the number of checks.
+    struct ZeroDecoder;
+
+    impl<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for ZeroDecoder {
+        fn correction_for(&self, _: SparseBinSlice<'a>) -> SparseBinVec {
+            SparseBinVec::zeros(3)
+        }
+    }
+
+    impl<'a> ClassicalSyndromeDecoder<'a> for ZeroDecoder {}
+
+    #[test]
+    fn syndrome_lengths_are_checked_only_if_known() {
+        let code = LinearCode::hamming_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let syndrome = SparseBinVec::new(4, vec![3]);
+        assert_eq!(
+            decoder.try_correction_for(syndrome.as_view()),
+            Err(SyndromeLengthError {
+                stabilizers: None,
+                found: 4,
+                expected: 3,
+            })
+        );
+        assert_eq!(ZeroDecoder.syndrome_length(), None);
+        assert_eq!(
+            ZeroDecoder.try_correction_for(syndrome.as_view()),
+            Ok(SparseBinVec::zeros(3))
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "syndrome of the wrong length")]
+    fn unchecked_decoding_asserts_the_length_in_debug() {
+        let code = LinearCode::hamming_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        decoder.correction_for(SparseBinVec::new(4, vec![3]).as_view());
+    }
 }
@@ -0,0 +1,335 @@
+use super::{
+    Capabilities, ClassicalSyndromeDecoder, DecoderCapabilities, SoftSyndromeDecoder,
+    SyndromeDecoder,
+};
+use crate::codes::information_set::{add_to, is_one_at};
+use crate::codes::InformationSet;
+use crate::noise::Probability;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
+
+/// The reprocessing of ordered statistics decoding
+/// after the solution supported on the most likely flipped basis.
+///
+/// Both methods flip a few bits outside the basis,
+/// starting from the bits most likely to be flipped,
+/// and keep the most likely correction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum OsdMethod {
+    /// Tries every pattern of at most `order` flipped bits outside the basis.
+    ///
+    /// The order 0 is the plain solution on the basis, known as OSD-0.
+    Exhaustive { order: usize },
+    /// Tries every single flip outside the basis and every pair of flips
+    /// among the `order` bits outside the basis most likely to be flipped.
+    ///
+    /// This is the combination sweep, known as OSD-CS, used for quantum LDPC codes.
+    CombinationSweep { order: usize },
+}
+
+impl Default for OsdMethod {
+    fn default() -> Self {
+        Self::Exhaustive { order: 0 }
+    }
+}
+
+/// A decoder running ordered statistics decoding (OSD)
+/// when a soft decoder fails to match the syndrome.
+///
+/// The bits are sorted from the most to the least likely flipped
+/// according to the posteriors of the soft decoder
+/// and the first independent columns of the parity check matrix form the basis.
+/// The candidates of the [method](OsdMethod) are compared by their likelihood under the priors,
+/// that is the sum of the prior log-likelihood ratios of their flipped bits.
+///
+/// If the hard decision of the soft decoder already has the syndrome,
+/// it is returned without reprocessing.
+/// If no error has the syndrome, the hard decision is returned.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{BpDecoder, OsdDecoder, OsdMethod, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::hamming_code();
+/// let probability = Probability::new(0.05);
+/// let decoder = OsdDecoder::new(
+///     BpDecoder::new(code.parity_check_matrix(), probability, 1),
+///     probability,
+/// )
+/// .with_method(OsdMethod::CombinationSweep { order: 3 })
+/// .with_search_budget(100);
+///
+/// let error = SparseBinVec::new(7, vec![3]);
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OsdDecoder<D> {
+    decoder: D,
+    likelyhoods: Vec<f64>,
+    method: OsdMethod,
+    search_budget: Option<usize>,
+}
+
+impl<D: SoftSyndromeDecoder> OsdDecoder<D> {
+    /// Creates an OSD-0 decoder with no search budget
+    /// assuming each bit flips independently with the given probability.
+    pub fn new(decoder: D, probability: Probability) -> Self {
+        let num_bits = decoder.parity_check_matrix().number_of_columns();
+        Self {
+            decoder,
+            likelyhoods: vec![probability.llr(); num_bits],
+            method: OsdMethod::default(),
+            search_budget: None,
+        }
+    }
+
+    /// Sets the reprocessing method.
+    pub fn with_method(mut self, method: OsdMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Limits the number of candidates tried in addition to the solution on the basis.
+    ///
+    /// The candidates with fewer flips and more likely flipped bits are tried first,
+    /// such that a budget keeps the most promising part of the search.
+    pub fn with_search_budget(mut self, max_candidates: usize) -> Self {
+        self.search_budget = Some(max_candidates);
+        self
+    }
+
+    /// Returns the soft decoder.
+    pub fn inner(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Returns the reprocessing method.
+    pub fn method(&self) -> OsdMethod {
+        self.method
+    }
+
+    /// Returns the maximum number of candidates tried in addition to the solution on the basis
+    /// or None if there is no limit.
+    pub fn search_budget(&self) -> Option<usize> {
+        self.search_budget
+    }
+
+    /// Returns a correction for the syndrome given the prior log-likelihood ratios,
+    /// for example from a soft channel.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of priors is different from the number of bits.
+    pub fn correction_with_priors(&self, syndrome: SparseBinSlice, priors: &[f64]) -> SparseBinVec {
+        let checks = self.decoder.parity_check_matrix();
+        if priors.len() != checks.number_of_columns() {
+            panic!(
+                "{} priors for {} bits",
+                priors.len(),
+                checks.number_of_columns()
+            );
+        }
+        let posteriors = self.decoder.posteriors_for(syndrome.clone(), priors);
+        let hard_decision = SparseBinVec::new(
+            posteriors.len(),
+            (0..posteriors.len())
+                .filter(|bit| posteriors[*bit] < 0.0)
+                .collect(),
+        );
+        if (checks * &hard_decision).as_view() == syndrome {
+            return hard_decision;
+        }
+        let flip_likelihoods = posteriors.iter().map(|value| -value).collect::<Vec<_>>();
+        let basis = InformationSet::most_reliable(checks, &flip_likelihoods);
+        let Some(reduced_syndrome) = basis.reduced_syndrome(syndrome) else {
+            return hard_decision;
+        };
+        let mut is_pivot = vec![false; posteriors.len()];
+        for pivot in basis.pivots() {
+            is_pivot[*pivot] = true;
+        }
+        let mut non_pivots = (0..posteriors.len())
+            .filter(|bit| !is_pivot[*bit])
+            .collect::<Vec<_>>();
+        non_pivots.sort_by(|first, second| posteriors[*first].total_cmp(&posteriors[*second]));
+        let reprocessing = Reprocessing {
+            basis: &basis,
+            priors,
+            reduced_syndrome,
+            columns: non_pivots
+                .iter()
+                .map(|bit| basis.reduced_column(*bit))
+                .collect(),
+            non_pivots,
+        };
+        let mut best = reprocessing.candidate(&[]);
+        let patterns = self
+            .patterns(reprocessing.non_pivots.len())
+            .take(self.search_budget.unwrap_or(usize::MAX));
+        for pattern in patterns {
+            let candidate = reprocessing.candidate(&pattern);
+            if candidate.1 < best.1 {
+                best = candidate;
+            }
+        }
+        #[cfg(feature = "tracing")]
+        tracing::trace!(cost = best.1, "ordered statistics decoding finished");
+        best.0
+    }
+
+    // The flipped positions among the non pivots of each candidate
+    // after the solution on the basis.
+    fn patterns(&self, num_non_pivots: usize) -> Box<dyn Iterator<Item = Vec<usize>>> {
+        match self.method {
+            OsdMethod::Exhaustive { order } => Box::new(
+                (1..=order.min(num_non_pivots))
+                    .flat_map(move |weight| (0..num_non_pivots).combinations(weight)),
+            ),
+            OsdMethod::CombinationSweep { order } => Box::new(
+                (0..num_non_pivots)
+                    .map(|bit| vec![bit])
+                    .chain((0..order.min(num_non_pivots)).combinations(2)),
+            ),
+        }
+    }
+}
+
+// The reduced syndrome and columns of the basis.
+struct Reprocessing<'a> {
+    basis: &'a InformationSet,
+    priors: &'a [f64],
+    reduced_syndrome: Vec<u64>,
+    non_pivots: Vec<usize>,
+    columns: Vec<Vec<u64>>,
+}
+
+impl Reprocessing<'_> {
+    // The solution flipping the given non pivots together with its cost.
+    fn candidate(&self, flipped: &[usize]) -> (SparseBinVec, f64) {
+        let mut pivots = self.reduced_syndrome.clone();
+        for bit in flipped {
+            add_to(&mut pivots, &self.columns[*bit]);
+        }
+        let mut positions = flipped
+            .iter()
+            .map(|bit| self.non_pivots[*bit])
+            .chain(
+                self.basis
+                    .pivots()
+                    .iter()
+                    .enumerate()
+                    .filter(|(row, _)| is_one_at(&pivots, *row))
+                    .map(|(_, pivot)| *pivot),
+            )
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        let cost = positions.iter().map(|bit| self.priors[*bit]).sum();
+        (SparseBinVec::new(self.priors.len(), positions), cost)
+    }
+}
+
+impl<'a, D: SoftSyndromeDecoder> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>
+    for OsdDecoder<D>
+{
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        self.correction_with_priors(syndrome, &self.likelyhoods)
+    }
+}
+
+impl<'a, D: SoftSyndromeDecoder> ClassicalSyndromeDecoder<'a> for OsdDecoder<D> {
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.decoder.parity_check_matrix().number_of_rows())
+    }
+}
+
+impl<D> DecoderCapabilities for OsdDecoder<D> {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::decoders::BpDecoder;
+    use crate::noise::{BinarySymmetricChannel, NoiseModel};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn reprocessing_never_increases_the_cost() {
+        let code = LinearCode::random_regular_code()
+            .num_bits(40)
+            .num_checks(30)
+            .bit_degree(3)
+            .check_degree(4)
+            .sample_with(&mut StdRng::seed_from_u64(7))
+            .unwrap();
+        let probability = Probability::new(0.1);
+        let osd = |method| {
+            OsdDecoder::new(
+                BpDecoder::new(code.parity_check_matrix(), probability, 2),
+                probability,
+            )
+            .with_method(method)
+        };
+        let decoders = [
+            osd(OsdMethod::Exhaustive { order: 0 }),
+            osd(OsdMethod::Exhaustive { order: 2 }),
+            osd(OsdMethod::CombinationSweep { order: 10 }),
+            osd(OsdMethod::Exhaustive { order: 2 }).with_search_budget(0),
+        ];
+        assert_eq!(decoders[0].syndrome_length(), Some(30));
+        let noise = BinarySymmetricChannel::with_probability(probability);
+        let mut rng = StdRng::seed_from_u64(8);
+        for _ in 0..30 {
+            let error = noise.sample_error_of_length(code.len(), &mut rng);
+            let syndrome = code.syndrome_of(&error);
+            let corrections = decoders
+                .iter()
+                .map(|decoder| decoder.correction_for(syndrome.as_view()))
+                .collect::<Vec<_>>();
+            for correction in corrections.iter() {
+                assert_eq!(code.syndrome_of(correction), syndrome);
+            }
+            assert!(corrections[1].weight() <= corrections[0].weight());
+            assert!(corrections[2].weight() <= corrections[0].weight());
+            assert_eq!(corrections[3], corrections[0]);
+        }
+    }
+
+    #[test]
+    fn combination_sweep_patterns() {
+        let code = LinearCode::hamming_code();
+        let probability = Probability::new(0.1);
+        let decoder = OsdDecoder::new(
+            BpDecoder::new(code.parity_check_matrix(), probability, 1),
+            probability,
+        )
+        .with_method(OsdMethod::CombinationSweep { order: 3 });
+        assert_eq!(
+            decoder.patterns(4).collect::<Vec<_>>(),
+            vec![
+                vec![0],
+                vec![1],
+                vec![2],
+                vec![3],
+                vec![0, 1],
+                vec![0, 2],
+                vec![1, 2]
+            ]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use crate::css::{Css, CssSyndrome};
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinVec, SparseBinVecBase};
+use std::io::{self, Read, Write};
+use std::ops::Deref;
+
+/// A syndrome packed into bytes with 8 checks per byte.
+///
+/// Check `c` is bit `c % 8` of byte `c / 8` and
+/// the unused bits of the last byte are always 0.
+/// This takes `ceil(m / 8)` bytes for `m` checks regardless of the weight,
+/// which is much more compact than the positions of a [`SparseBinVec`]
+/// when logging many syndromes or receiving them from hardware.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::decoders::PackedSyndrome;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let syndrome = SparseBinVec::new(10, vec![0, 3, 9]);
+/// let packed = PackedSyndrome::pack(&syndrome);
+///
+/// assert_eq!(packed.bytes(), &[0b0000_1001, 0b0000_0010]);
+/// assert_eq!(packed.weight(), 3);
+/// assert_eq!(packed.unpack(), syndrome);
+///
+/// let mut stream = Vec::new();
+/// packed.write_to(&mut stream).unwrap();
+/// packed.write_to(&mut stream).unwrap();
+/// let mut reader = stream.as_slice();
+/// assert_eq!(PackedSyndrome::read_from(10, &mut reader).unwrap(), Some(packed.clone()));
+/// assert_eq!(PackedSyndrome::read_from(10, &mut reader).unwrap(), Some(packed));
+/// assert_eq!(PackedSyndrome::read_from(10, &mut reader).unwrap(), None);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedPackedSyndrome")]
+pub struct PackedSyndrome {
+    len: usize,
+    bytes: Vec<u8>,
+}
+
+/// The packed X and Z syndromes of a CSS code.
+pub type PackedCssSyndrome = Css<PackedSyndrome>;
+
+impl PackedSyndrome {
+    /// Packs the given syndrome.
+    pub fn pack<T>(syndrome: &SparseBinVecBase<T>) -> Self
+    where
+        T: Deref<Target = [usize]>,
+    {
+        let mut bytes = vec![0; num_bytes(syndrome.len())];
+        for check in syndrome.non_trivial_positions() {
+            bytes[check / 8] |= 1 << (check % 8);
+        }
+        Self {
+            len: syndrome.len(),
+            bytes,
+        }
+    }
+
+    /// Creates a packed syndrome of the given length from its bytes
+    /// or returns None if the number of bytes doesn't match the length
+    /// or if an unused bit of the last byte is 1.
+    pub fn from_bytes(len: usize, bytes: Vec<u8>) -> Option<Self> {
+        let has_padding_ones =
+            len % 8 != 0 && bytes.last().is_some_and(|byte| byte >> (len % 8) != 0);
+        (bytes.len() == num_bytes(len) && !has_padding_ones).then_some(Self { len, bytes })
+    }
+
+    /// Reads the next packed syndrome of the given length
+    /// or returns None if the reader is exhausted.
+    ///
+    /// This returns an error of kind
+    /// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof)
+    /// if the reader ends in the middle of a syndrome and of kind
+    /// [`InvalidData`](io::ErrorKind::InvalidData)
+    /// if an unused bit of the last byte is 1.
+    pub fn read_from<R: Read>(len: usize, reader: &mut R) -> io::Result<Option<Self>> {
+        let mut bytes = vec![0; num_bytes(len)];
+        let mut filled = 0;
+        while filled < bytes.len() {
+            match reader.read(&mut bytes[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(read) => filled += read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Self::from_bytes(len, bytes)
+            .map(Some)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "non zero padding bits"))
+    }
+
+    /// Writes the bytes of the syndrome.
+    ///
+    /// The length is not written and must be known to read the syndrome back.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.bytes)
+    }
+
+    /// Returns the number of checks.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if there is no check.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bytes of the syndrome.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the bytes of the syndrome, consuming it.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Returns the number of flipped checks.
+    pub fn weight(&self) -> usize {
+        self.bytes
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum()
+    }
+
+    /// Checks if no check is flipped.
+    pub fn is_zero(&self) -> bool {
+        self.bytes.iter().all(|byte| *byte == 0)
+    }
+
+    /// Checks if the given check is flipped or returns None if it is out of bound.
+    pub fn is_one_at(&self, check: usize) -> Option<bool> {
+        (check < self.len).then(|| self.bytes[check / 8] >> (check % 8) & 1 == 1)
+    }
+
+    /// Returns the flipped checks in increasing order.
+    pub fn non_trivial_positions(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bytes.iter().enumerate().flat_map(|(index, byte)| {
+            (0..8)
+                .filter(move |bit| byte >> bit & 1 == 1)
+                .map(move |bit| 8 * index + bit)
+        })
+    }
+
+    /// Unpacks the syndrome into a sparse vector.
+    pub fn unpack(&self) -> SparseBinVec {
+        SparseBinVec::new(self.len, self.non_trivial_positions().collect())
+    }
+}
+
+impl PackedCssSyndrome {
+    /// Unpacks the X and Z syndromes.
+    pub fn unpack(&self) -> CssSyndrome {
+        self.map(PackedSyndrome::unpack)
+    }
+}
+
+// The fields of a serialized packed syndrome before they are validated.
+#[derive(Deserialize)]
+struct UncheckedPackedSyndrome {
+    len: usize,
+    bytes: Vec<u8>,
+}
+
+impl TryFrom<UncheckedPackedSyndrome> for PackedSyndrome {
+    type Error = String;
+
+    fn try_from(syndrome: UncheckedPackedSyndrome) -> Result<Self, Self::Error> {
+        let num_bytes = syndrome.bytes.len();
+        Self::from_bytes(syndrome.len, syndrome.bytes).ok_or_else(|| {
+            format!(
+                "{} bytes with zero padding can't pack a syndrome of length {}",
+                num_bytes, syndrome.len
+            )
+        })
+    }
+}
+
+fn num_bytes(len: usize) -> usize {
+    len.div_ceil(8)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn packing_round_trips() {
+        for len in [0, 1, 7, 8, 9, 64, 100] {
+            let syndrome = SparseBinVec::new(len, (0..len).filter(|c| c % 3 != 1).collect());
+            let packed = PackedSyndrome::pack(&syndrome);
+            assert_eq!(packed.bytes().len(), len.div_ceil(8));
+            assert_eq!(packed.weight(), syndrome.weight());
+            assert_eq!(packed.unpack(), syndrome);
+            let bytes = packed.clone().into_bytes();
+            assert_eq!(PackedSyndrome::from_bytes(len, bytes), Some(packed));
+        }
+    }
+
+    #[test]
+    fn padding_bits_must_be_zero() {
+        assert!(PackedSyndrome::from_bytes(4, vec![0b0000_1111]).is_some());
+        assert!(PackedSyndrome::from_bytes(4, vec![0b0001_0000]).is_none());
+        assert!(PackedSyndrome::from_bytes(4, vec![0, 0]).is_none());
+        let mut reader: &[u8] = &[0b1000_0000];
+        assert_eq!(
+            PackedSyndrome::read_from(4, &mut reader)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn deserialized_bytes_must_match_the_length() {
+        let packed = PackedSyndrome::pack(&SparseBinVec::new(10, vec![0, 3, 9]));
+        let json = serde_json::to_string(&packed).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PackedSyndrome>(&json).unwrap(),
+            packed
+        );
+        assert!(serde_json::from_str::<PackedSyndrome>(r#"{"len":100,"bytes":[]}"#).is_err());
+        assert!(serde_json::from_str::<PackedSyndrome>(r#"{"len":4,"bytes":[16]}"#).is_err());
+    }
+
+    #[test]
+    fn truncated_stream_is_an_error() {
+        let mut reader: &[u8] = &[1, 2, 3];
+        assert!(PackedSyndrome::read_from(16, &mut reader)
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            PackedSyndrome::read_from(16, &mut reader)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn css_syndrome_round_trips() {
+        let syndrome = Css {
+            x: SparseBinVec::new(3, vec![1]),
+            z: SparseBinVec::new(12, vec![0, 11]),
+        };
+        let packed = syndrome.pack();
+        assert_eq!(packed.z.bytes(), &[1, 0b0000_1000]);
+        assert_eq!(packed.unpack(), syndrome);
+    }
+}
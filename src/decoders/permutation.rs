@@ -0,0 +1,207 @@
+use super::{
+    Capabilities, ClassicalSyndromeDecoder, DecoderCapabilities, LinearDecoder, SyndromeDecoder,
+};
+use crate::codes::Automorphism;
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+
+/// A decoder running an inner decoder on the syndrome permuted
+/// by each automorphism of the code and keeping the best correction.
+///
+/// The correction of each permuted syndrome is permuted back
+/// and the correction of minimum weight among those matching the syndrome is kept,
+/// starting with the one of the syndrome itself.
+/// If no correction matches, the inner decoder output is returned.
+///
+/// This helps decoders that are sensitive to the location of errors,
+/// for example belief propagation on short cyclic codes.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::Automorphism;
+/// use ldpc::decoders::{BpDecoder, PermutationDecoder, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+///
+/// // The cyclic Hamming code with all shifts of a check.
+/// let checks = (0..7)
+///     .map(|shift| {
+///         let mut check = vec![0, 2, 3, 4].into_iter().map(|bit| (bit + shift) % 7).collect::<Vec<_>>();
+///         check.sort();
+///         check
+///     })
+///     .collect();
+/// let parity_mat = SparseBinMat::new(7, checks);
+/// let inner = BpDecoder::new(&parity_mat, Probability::new(0.05), 10);
+/// let decoder = PermutationDecoder::new(&parity_mat, inner, Automorphism::cyclic_shifts(&parity_mat));
+/// assert_eq!(decoder.num_automorphisms(), 6);
+///
+/// let error = SparseBinVec::new(7, vec![3]);
+/// let syndrome = &parity_mat * &error;
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PermutationDecoder<D> {
+    parity_mat: SparseBinMat,
+    decoder: D,
+    automorphisms: Vec<Automorphism>,
+}
+
+impl<D> PermutationDecoder<D> {
+    /// Creates a permutation decoder for the parity check matrix
+    /// from an inner decoder and automorphisms of the matrix.
+    ///
+    /// # Panic
+    ///
+    /// Panics if an automorphism has a different number of bits or checks
+    /// than the matrix.
+    pub fn new(parity_mat: &SparseBinMat, decoder: D, automorphisms: Vec<Automorphism>) -> Self {
+        for automorphism in automorphisms.iter() {
+            if automorphism.bits().len() != parity_mat.number_of_columns()
+                || automorphism.checks().len() != parity_mat.number_of_rows()
+            {
+                panic!(
+                    "automorphism on {} bits and {} checks for {} bits and {} checks",
+                    automorphism.bits().len(),
+                    automorphism.checks().len(),
+                    parity_mat.number_of_columns(),
+                    parity_mat.number_of_rows()
+                );
+            }
+        }
+        Self {
+            parity_mat: parity_mat.clone(),
+            decoder,
+            automorphisms,
+        }
+    }
+
+    /// Returns the number of automorphisms, excluding the identity.
+    pub fn num_automorphisms(&self) -> usize {
+        self.automorphisms.len()
+    }
+
+    /// Returns the inner decoder.
+    pub fn inner(&self) -> &D {
+        &self.decoder
+    }
+}
+
+impl<'a, D> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for PermutationDecoder<D>
+where
+    D: for<'s> SyndromeDecoder<SparseBinSlice<'s>, SparseBinVec>,
+{
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        let matches =
+            |correction: &SparseBinVec| (&self.parity_mat * correction).as_view() == syndrome;
+        let mut best = self.decoder.correction_for(syndrome.as_view());
+        let mut is_valid = matches(&best);
+        for automorphism in self.automorphisms.iter() {
+            let permuted_syndrome = automorphism.permute_checks(syndrome.as_view());
+            let permuted_correction = self.decoder.correction_for(permuted_syndrome.as_view());
+            let correction = automorphism
+                .inverse()
+                .permute_bits(permuted_correction.as_view());
+            if matches(&correction) && (!is_valid || correction.weight() < best.weight()) {
+                best = correction;
+                is_valid = true;
+            }
+        }
+        best
+    }
+}
+
+impl<'a, D> ClassicalSyndromeDecoder<'a> for PermutationDecoder<D>
+where
+    D: for<'s> SyndromeDecoder<SparseBinSlice<'s>, SparseBinVec>,
+{
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.parity_mat.number_of_rows())
+    }
+}
+
+impl<D> LinearDecoder for PermutationDecoder<D>
+where
+    D: for<'s> SyndromeDecoder<SparseBinSlice<'s>, SparseBinVec>,
+{
+    fn decode(&self, message: SparseBinSlice) -> SparseBinVec {
+        let syndrome = &self.parity_mat * &message;
+        let correction = self.correction_for(syndrome.as_view());
+        &message + &correction
+    }
+}
+
+impl<D> DecoderCapabilities for PermutationDecoder<D> {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    // Only knows the syndromes of the errors on the first bit.
+    struct FirstBitDecoder {
+        table: HashMap<Vec<usize>, SparseBinVec>,
+        length: usize,
+    }
+
+    impl FirstBitDecoder {
+        fn new(parity_mat: &SparseBinMat) -> Self {
+            let error = SparseBinVec::new(parity_mat.number_of_columns(), vec![0]);
+            let syndrome = (parity_mat * &error).non_trivial_positions().collect();
+            Self {
+                table: std::iter::once((syndrome, error)).collect(),
+                length: parity_mat.number_of_columns(),
+            }
+        }
+    }
+
+    impl<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for FirstBitDecoder {
+        fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+            self.table
+                .get(&syndrome.non_trivial_positions().collect::<Vec<_>>())
+                .cloned()
+                .unwrap_or_else(|| SparseBinVec::zeros(self.length))
+        }
+    }
+
+    #[test]
+    fn shifts_move_every_error_to_the_first_bit() {
+        let checks = (0..5)
+            .map(|bit| vec![bit, (bit + 1) % 5])
+            .collect::<Vec<_>>();
+        let parity_mat = SparseBinMat::new(
+            5,
+            checks
+                .into_iter()
+                .map(|mut check| {
+                    check.sort_unstable();
+                    check
+                })
+                .collect(),
+        );
+        let decoder = PermutationDecoder::new(
+            &parity_mat,
+            FirstBitDecoder::new(&parity_mat),
+            Automorphism::cyclic_shifts(&parity_mat),
+        );
+        assert_eq!(decoder.syndrome_length(), Some(5));
+        for bit in 0..5 {
+            let error = SparseBinVec::new(5, vec![bit]);
+            assert_eq!(decoder.decode(error.as_view()), SparseBinVec::zeros(5));
+        }
+        // Without automorphisms, only the first bit is corrected.
+        let decoder =
+            PermutationDecoder::new(&parity_mat, FirstBitDecoder::new(&parity_mat), vec![]);
+        let error = SparseBinVec::new(5, vec![2]);
+        assert_eq!(decoder.decode(error.as_view()), error);
+    }
+}
@@ -0,0 +1,305 @@
+use super::{
+    Capabilities, ClassicalSyndromeDecoder, DecoderCapabilities, LinearDecoder, SyndromeDecoder,
+};
+use crate::noise::Probability;
+use itertools::Itertools;
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
+
+/// A successive-cancellation decoder for the
+/// [polar codes](crate::codes::LinearCode::polar_code).
+///
+/// By default, a single decoding path is kept.
+/// With a list size greater than 1, the decoder keeps the most likely paths
+/// when deciding each input outside the frozen set
+/// and returns the most likely path at the end.
+///
+/// Since the checks of a polar code are the frozen inputs,
+/// the syndrome gives the values of the frozen inputs of the error.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{PolarDecoder, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let frozen_set = [0, 1, 2, 4];
+/// let code = LinearCode::polar_code(8, &frozen_set);
+/// let decoder = PolarDecoder::new(8, &frozen_set, Probability::new(0.05)).with_list_size(4);
+///
+/// let error = SparseBinVec::new(8, vec![5]);
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolarDecoder {
+    length: usize,
+    frozen_set: Vec<usize>,
+    likelyhood: f64,
+    list_size: usize,
+}
+
+impl PolarDecoder {
+    /// Creates a decoder for the polar code with the given length and frozen set
+    /// assuming each bit flips independently with the given probability.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length is not a power of 2 or if a frozen input is out of bound.
+    pub fn new(length: usize, frozen_set: &[usize], probability: Probability) -> Self {
+        if !length.is_power_of_two() {
+            panic!("polar code length {} is not a power of 2", length);
+        }
+        if let Some(input) = frozen_set.iter().find(|input| **input >= length) {
+            panic!(
+                "frozen input {} is out of bound for length {}",
+                input, length
+            );
+        }
+        Self {
+            length,
+            frozen_set: frozen_set.iter().cloned().sorted().dedup().collect(),
+            likelyhood: probability.llr(),
+            list_size: 1,
+        }
+    }
+
+    /// Keeps up to the given number of decoding paths.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the list size is 0.
+    pub fn with_list_size(mut self, list_size: usize) -> Self {
+        if list_size == 0 {
+            panic!("list size must be positive");
+        }
+        self.list_size = list_size;
+        self
+    }
+
+    /// Returns the maximum number of decoding paths.
+    pub fn list_size(&self) -> usize {
+        self.list_size
+    }
+
+    /// Returns the sorted frozen inputs.
+    pub fn frozen_set(&self) -> &[usize] {
+        &self.frozen_set
+    }
+
+    // Decodes the inputs from offset to offset + the length of the log-likelihood ratios
+    // for each path and returns the re-encoded bits of each surviving path
+    // together with the path it comes from.
+    fn decode_node(
+        &self,
+        ratios: Vec<Vec<f64>>,
+        offset: usize,
+        frozen_values: &[Option<bool>],
+        metrics: &mut Vec<f64>,
+    ) -> (Vec<Vec<bool>>, Vec<usize>) {
+        let size = ratios[0].len();
+        if size == 1 {
+            return self.decide_input(&ratios, frozen_values[offset], metrics);
+        }
+        let half = size / 2;
+        let left_ratios = ratios
+            .iter()
+            .map(|ratio| {
+                (0..half)
+                    .map(|bit| check_node(ratio[bit], ratio[bit + half]))
+                    .collect()
+            })
+            .collect();
+        let (left_bits, left_origins) =
+            self.decode_node(left_ratios, offset, frozen_values, metrics);
+        let right_ratios = left_origins
+            .iter()
+            .zip(left_bits.iter())
+            .map(|(origin, bits)| {
+                let ratio = &ratios[*origin];
+                (0..half)
+                    .map(|bit| bit_node(ratio[bit], ratio[bit + half], bits[bit]))
+                    .collect()
+            })
+            .collect();
+        let (right_bits, right_origins) =
+            self.decode_node(right_ratios, offset + half, frozen_values, metrics);
+        let bits = right_origins
+            .iter()
+            .zip(right_bits)
+            .map(|(origin, right)| {
+                left_bits[*origin]
+                    .iter()
+                    .zip(right.iter())
+                    .map(|(left, right)| left ^ right)
+                    .chain(right.iter().cloned())
+                    .collect()
+            })
+            .collect();
+        let origins = right_origins
+            .into_iter()
+            .map(|origin| left_origins[origin])
+            .collect();
+        (bits, origins)
+    }
+
+    fn decide_input(
+        &self,
+        ratios: &[Vec<f64>],
+        frozen_value: Option<bool>,
+        metrics: &mut Vec<f64>,
+    ) -> (Vec<Vec<bool>>, Vec<usize>) {
+        let candidate = |path: usize, value: bool| {
+            (path, value, metrics[path] + penalty(ratios[path][0], value))
+        };
+        let candidates = match frozen_value {
+            Some(value) => (0..ratios.len())
+                .map(|path| candidate(path, value))
+                .collect_vec(),
+            None => (0..ratios.len())
+                .flat_map(|path| [candidate(path, false), candidate(path, true)])
+                .sorted_by(|first, second| first.2.total_cmp(&second.2))
+                .take(self.list_size)
+                .collect_vec(),
+        };
+        *metrics = candidates.iter().map(|(_, _, metric)| *metric).collect();
+        candidates
+            .into_iter()
+            .map(|(path, value, _)| (vec![value], path))
+            .unzip()
+    }
+
+    // The value of a frozen input is the parity of the bits in its check.
+    fn syndrome_of(&self, message: &SparseBinSlice) -> SparseBinVec {
+        let positions = self
+            .frozen_set
+            .iter()
+            .enumerate()
+            .filter(|(_, input)| {
+                message
+                    .non_trivial_positions()
+                    .filter(|bit| bit & **input == **input)
+                    .count()
+                    % 2
+                    == 1
+            })
+            .map(|(check, _)| check)
+            .collect();
+        SparseBinVec::new(self.frozen_set.len(), positions)
+    }
+}
+
+// The log-likelihood ratio of the sum of two bits.
+fn check_node(first: f64, second: f64) -> f64 {
+    first.signum() * second.signum() * first.abs().min(second.abs())
+}
+
+// The log-likelihood ratio of a bit given by two copies, one of them flipped by the partial sum.
+fn bit_node(first: f64, second: f64, partial_sum: bool) -> f64 {
+    if partial_sum {
+        second - first
+    } else {
+        second + first
+    }
+}
+
+// The negative log-probability of the value given the log-likelihood ratio.
+fn penalty(ratio: f64, value: bool) -> f64 {
+    let ratio = if value { -ratio } else { ratio };
+    if ratio >= 0.0 {
+        (-ratio).exp().ln_1p()
+    } else {
+        -ratio + ratio.exp().ln_1p()
+    }
+}
+
+impl<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for PolarDecoder {
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the size of the frozen set.
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        if syndrome.len() != self.frozen_set.len() {
+            panic!(
+                "syndrome of length {} for {} frozen inputs",
+                syndrome.len(),
+                self.frozen_set.len()
+            );
+        }
+        let mut frozen_values = vec![None; self.length];
+        for (check, input) in self.frozen_set.iter().enumerate() {
+            frozen_values[*input] = syndrome.is_one_at(check);
+        }
+        let mut metrics = vec![0.0];
+        let (bits, _) = self.decode_node(
+            vec![vec![self.likelyhood; self.length]],
+            0,
+            &frozen_values,
+            &mut metrics,
+        );
+        let best = (0..bits.len())
+            .min_by(|first, second| metrics[*first].total_cmp(&metrics[*second]))
+            .unwrap();
+        let positions = bits[best].iter().positions(|bit| *bit).collect();
+        SparseBinVec::new(self.length, positions)
+    }
+}
+
+impl<'a> ClassicalSyndromeDecoder<'a> for PolarDecoder {
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.frozen_set.len())
+    }
+}
+
+impl LinearDecoder for PolarDecoder {
+    fn decode(&self, message: SparseBinSlice) -> SparseBinVec {
+        let syndrome = self.syndrome_of(&message);
+        let correction = self.correction_for(syndrome.as_view());
+        &message + &correction
+    }
+}
+
+impl DecoderCapabilities for PolarDecoder {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+
+    const REED_MULLER_FROZEN_SET: [usize; 5] = [0, 1, 2, 4, 8];
+
+    #[test]
+    fn syndrome_matches_polar_code() {
+        let code = LinearCode::polar_code(16, &REED_MULLER_FROZEN_SET);
+        let decoder = PolarDecoder::new(16, &REED_MULLER_FROZEN_SET, Probability::new(0.1));
+        let message = SparseBinVec::new(16, vec![1, 6, 7, 12, 15]);
+        assert_eq!(
+            decoder.syndrome_of(&message.as_view()),
+            code.syndrome_of(&message)
+        );
+        assert_eq!(decoder.syndrome_length(), Some(code.num_checks()));
+    }
+
+    #[test]
+    fn list_decoder_corrects_single_errors() {
+        let code = LinearCode::polar_code(16, &REED_MULLER_FROZEN_SET);
+        let decoder = PolarDecoder::new(16, &REED_MULLER_FROZEN_SET, Probability::new(0.05))
+            .with_list_size(8);
+        assert_eq!(code.corrects_all_errors_up_to(1, &decoder), Ok(()));
+    }
+
+    #[test]
+    fn decoding_returns_a_codeword() {
+        let code = LinearCode::polar_code(16, &REED_MULLER_FROZEN_SET);
+        for list_size in [1, 4] {
+            let decoder = PolarDecoder::new(16, &REED_MULLER_FROZEN_SET, Probability::new(0.1))
+                .with_list_size(list_size);
+            let message = SparseBinVec::new(16, vec![0, 3, 9]);
+            assert!(code.has_codeword(&decoder.decode(message.as_view())));
+        }
+    }
+}
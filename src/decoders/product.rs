@@ -0,0 +1,232 @@
+use super::{Capabilities, ClassicalSyndromeDecoder, DecoderCapabilities, SyndromeDecoder};
+use crate::codes::LinearCode;
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+
+/// A serial hard-decision decoder for the
+/// [tensor product](LinearCode::tensor_product) of two codes.
+///
+/// At each iteration, the decoder of the second code corrects each row
+/// and then the decoder of the first code corrects each column
+/// using the syndrome left by the previous corrections.
+/// Decoding stops when the full syndrome is corrected
+/// or when the maximum number of iterations is reached.
+///
+/// For soft-decision decoding, use the
+/// [`TurboDecoder::for_tensor_product`](super::TurboDecoder::for_tensor_product)
+/// to exchange extrinsic information between the rows and the columns.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{BpDecoder, ProductDecoder, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let repetition_code = LinearCode::repetition_code(3);
+/// let code = repetition_code.tensor_product(&repetition_code);
+/// let probability = Probability::new(0.1);
+/// let component = BpDecoder::new(repetition_code.parity_check_matrix(), probability, 10);
+/// let decoder = ProductDecoder::new(
+///     &repetition_code,
+///     &repetition_code,
+///     component.clone(),
+///     component,
+///     5,
+/// );
+///
+/// // The second error is wrongly corrected in the row, but fixed in the columns.
+/// let error = SparseBinVec::new(9, vec![0, 1]);
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProductDecoder<First, Second> {
+    first: First,
+    second: Second,
+    first_code_len: usize,
+    second_code_len: usize,
+    num_first_checks: usize,
+    num_second_checks: usize,
+    parity_mat: SparseBinMat,
+    num_iterations: usize,
+}
+
+impl<First, Second> ProductDecoder<First, Second> {
+    /// Creates a decoder for the tensor product of the first and second codes
+    /// using the first decoder on the columns and the second decoder on the rows.
+    pub fn new(
+        first_code: &LinearCode,
+        second_code: &LinearCode,
+        first: First,
+        second: Second,
+        num_iterations: usize,
+    ) -> Self {
+        let (row_checks, column_checks) = first_code.tensor_product_checks(second_code);
+        Self {
+            first,
+            second,
+            first_code_len: first_code.len(),
+            second_code_len: second_code.len(),
+            num_first_checks: first_code.num_checks(),
+            num_second_checks: second_code.num_checks(),
+            parity_mat: row_checks.vertical_concat_with(&column_checks),
+            num_iterations,
+        }
+    }
+
+    /// Returns the length of the corrections.
+    pub fn num_bits(&self) -> usize {
+        self.parity_mat.number_of_columns()
+    }
+
+    /// Returns the length of the syndromes.
+    pub fn num_checks(&self) -> usize {
+        self.parity_mat.number_of_rows()
+    }
+
+    fn num_row_checks(&self) -> usize {
+        self.first_code_len * self.num_second_checks
+    }
+
+    fn row_syndrome(&self, syndrome: &SparseBinVec, row: usize) -> SparseBinVec {
+        let start = row * self.num_second_checks;
+        let positions = syndrome
+            .non_trivial_positions()
+            .filter(|check| (start..start + self.num_second_checks).contains(check))
+            .map(|check| check - start)
+            .collect();
+        SparseBinVec::new(self.num_second_checks, positions)
+    }
+
+    fn column_syndrome(&self, syndrome: &SparseBinVec, column: usize) -> SparseBinVec {
+        let positions = syndrome
+            .non_trivial_positions()
+            .filter_map(|check| check.checked_sub(self.num_row_checks()))
+            .filter(|check| check % self.second_code_len == column)
+            .map(|check| check / self.second_code_len)
+            .collect();
+        SparseBinVec::new(self.num_first_checks, positions)
+    }
+
+    fn residual_syndrome(
+        &self,
+        syndrome: &SparseBinSlice,
+        correction: &SparseBinVec,
+    ) -> SparseBinVec {
+        syndrome + &(&self.parity_mat * correction)
+    }
+}
+
+impl<'a, First, Second> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>
+    for ProductDecoder<First, Second>
+where
+    First: for<'b> SyndromeDecoder<SparseBinSlice<'b>, SparseBinVec>,
+    Second: for<'b> SyndromeDecoder<SparseBinSlice<'b>, SparseBinVec>,
+{
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        if syndrome.len() != self.num_checks() {
+            panic!(
+                "syndrome of length {} for {} checks",
+                syndrome.len(),
+                self.num_checks()
+            );
+        }
+        let mut correction = SparseBinVec::zeros(self.num_bits());
+        for _ in 0..self.num_iterations {
+            let residual = self.residual_syndrome(&syndrome, &correction);
+            if residual.is_zero() {
+                break;
+            }
+            for row in 0..self.first_code_len {
+                let row_syndrome = self.row_syndrome(&residual, row);
+                if row_syndrome.is_zero() {
+                    continue;
+                }
+                let positions = self
+                    .second
+                    .correction_for(row_syndrome.as_view())
+                    .non_trivial_positions()
+                    .map(|bit| row * self.second_code_len + bit)
+                    .collect();
+                correction = &correction + &SparseBinVec::new(self.num_bits(), positions);
+            }
+            let residual = self.residual_syndrome(&syndrome, &correction);
+            for column in 0..self.second_code_len {
+                let column_syndrome = self.column_syndrome(&residual, column);
+                if column_syndrome.is_zero() {
+                    continue;
+                }
+                let mut positions = self
+                    .first
+                    .correction_for(column_syndrome.as_view())
+                    .non_trivial_positions()
+                    .map(|bit| bit * self.second_code_len + column)
+                    .collect::<Vec<_>>();
+                positions.sort_unstable();
+                correction = &correction + &SparseBinVec::new(self.num_bits(), positions);
+            }
+        }
+        correction
+    }
+}
+
+impl<'a, First, Second> ClassicalSyndromeDecoder<'a> for ProductDecoder<First, Second>
+where
+    First: for<'b> SyndromeDecoder<SparseBinSlice<'b>, SparseBinVec>,
+    Second: for<'b> SyndromeDecoder<SparseBinSlice<'b>, SparseBinVec>,
+{
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.parity_mat.number_of_rows())
+    }
+}
+
+impl<First, Second> DecoderCapabilities for ProductDecoder<First, Second> {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::BpDecoder;
+    use crate::noise::Probability;
+
+    fn decoder(code: &LinearCode) -> ProductDecoder<BpDecoder, BpDecoder> {
+        let component = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        ProductDecoder::new(code, code, component.clone(), component, 5)
+    }
+
+    #[test]
+    fn corrects_all_errors_of_weight_up_to_2_on_repetition_product() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = repetition_code.tensor_product(&repetition_code);
+        assert_eq!(
+            code.corrects_all_errors_up_to(2, &decoder(&repetition_code)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn column_syndromes_follow_tensor_product_layout() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = repetition_code.tensor_product(&repetition_code);
+        let decoder = decoder(&repetition_code);
+        assert_eq!(decoder.syndrome_length(), Some(code.num_checks()));
+        // The bit in row 1 and column 2.
+        let syndrome = code.syndrome_of(&SparseBinVec::new(9, vec![5]));
+        assert_eq!(
+            decoder.row_syndrome(&syndrome, 1),
+            SparseBinVec::new(2, vec![1])
+        );
+        assert_eq!(
+            decoder.column_syndrome(&syndrome, 2),
+            SparseBinVec::new(2, vec![0, 1])
+        );
+        assert!(decoder.column_syndrome(&syndrome, 1).is_zero());
+    }
+}
@@ -0,0 +1,212 @@
+use super::{Capabilities, ClassicalSyndromeDecoder, DecoderCapabilities, SyndromeDecoder};
+use crate::codes::QuasiCyclicMatrix;
+use crate::noise::Probability;
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
+
+/// A belief propagation decoder for quasi-cyclic codes
+/// processing each circulant block at once.
+///
+/// The messages of a block are stored in a single array
+/// indexed by the row of the block,
+/// such that the neighbors of each check and bit are found
+/// from the shifts without expanding the parity check matrix.
+/// The updates are the same as for the [`BpDecoder`](super::BpDecoder).
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::QuasiCyclicMatrix;
+/// use ldpc::decoders::{QuasiCyclicBpDecoder, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let matrix = QuasiCyclicMatrix::new(
+///     5,
+///     vec![vec![Some(0), Some(1), Some(2)], vec![Some(0), Some(2), Some(4)]],
+/// );
+/// let decoder = QuasiCyclicBpDecoder::new(&matrix, Probability::new(0.05), 10);
+///
+/// let error = SparseBinVec::new(15, vec![7]);
+/// let syndrome = matrix.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuasiCyclicBpDecoder {
+    matrix: QuasiCyclicMatrix,
+    blocks: Vec<(usize, usize, usize)>,
+    likelyhood: f64,
+    num_iterations: usize,
+}
+
+impl QuasiCyclicBpDecoder {
+    pub fn new(
+        matrix: &QuasiCyclicMatrix,
+        probability: Probability,
+        num_iterations: usize,
+    ) -> Self {
+        Self {
+            matrix: matrix.clone(),
+            blocks: matrix.blocks().collect(),
+            likelyhood: probability.llr(),
+            num_iterations,
+        }
+    }
+
+    /// Returns the length of the corrections.
+    pub fn num_bits(&self) -> usize {
+        self.matrix.number_of_columns()
+    }
+
+    /// Returns the length of the syndromes.
+    pub fn num_checks(&self) -> usize {
+        self.matrix.number_of_rows()
+    }
+
+    fn lift_factor(&self) -> usize {
+        self.matrix.lift_factor()
+    }
+
+    // The bit connected to the check at the given offset in the block.
+    fn bit_offset(&self, offset: usize, shift: usize) -> usize {
+        (offset + shift) % self.lift_factor()
+    }
+
+    fn update_checks(&self, bit_messages: &[Vec<f64>], syndrome: &[bool]) -> Vec<Vec<f64>> {
+        let lift_factor = self.lift_factor();
+        let mut check_messages = vec![vec![0.0; lift_factor]; self.blocks.len()];
+        for block_row in 0..self.matrix.num_block_rows() {
+            let blocks = self
+                .blocks
+                .iter()
+                .enumerate()
+                .filter(|(_, (row, _, _))| *row == block_row)
+                .map(|(block, _)| block)
+                .collect::<Vec<_>>();
+            for offset in 0..lift_factor {
+                let sign = if syndrome[block_row * lift_factor + offset] {
+                    -1.0
+                } else {
+                    1.0
+                };
+                for block in blocks.iter() {
+                    // The product is computed without the block to avoid dividing by 0.
+                    let inner: f64 = blocks
+                        .iter()
+                        .filter(|other| *other != block)
+                        .map(|other| (bit_messages[*other][offset] / 2.0).tanh())
+                        .product();
+                    check_messages[*block][offset] = sign * 2.0 * inner.atanh();
+                }
+            }
+        }
+        check_messages
+    }
+
+    // Returns the messages from the bits to the checks and the posteriors.
+    fn update_bits(&self, check_messages: &[Vec<f64>]) -> (Vec<Vec<f64>>, Vec<f64>) {
+        let lift_factor = self.lift_factor();
+        let mut posteriors = vec![self.likelyhood; self.num_bits()];
+        for (block, (_, column, shift)) in self.blocks.iter().enumerate() {
+            for offset in 0..lift_factor {
+                posteriors[column * lift_factor + self.bit_offset(offset, *shift)] +=
+                    check_messages[block][offset];
+            }
+        }
+        let bit_messages = self
+            .blocks
+            .iter()
+            .zip(check_messages)
+            .map(|((_, column, shift), messages)| {
+                (0..lift_factor)
+                    .map(|offset| {
+                        posteriors[column * lift_factor + self.bit_offset(offset, *shift)]
+                            - messages[offset]
+                    })
+                    .collect()
+            })
+            .collect();
+        (bit_messages, posteriors)
+    }
+}
+
+impl<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec> for QuasiCyclicBpDecoder {
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        if syndrome.len() != self.num_checks() {
+            panic!(
+                "syndrome of length {} for {} checks",
+                syndrome.len(),
+                self.num_checks()
+            );
+        }
+        let mut is_flipped = vec![false; self.num_checks()];
+        for check in syndrome.non_trivial_positions() {
+            is_flipped[check] = true;
+        }
+        let mut bit_messages = vec![vec![self.likelyhood; self.lift_factor()]; self.blocks.len()];
+        let mut correction = SparseBinVec::zeros(self.num_bits());
+        for _ in 0..self.num_iterations {
+            if self.matrix.syndrome_of(&correction).as_view() == syndrome {
+                break;
+            }
+            let check_messages = self.update_checks(&bit_messages, &is_flipped);
+            let (messages, posteriors) = self.update_bits(&check_messages);
+            bit_messages = messages;
+            let positions = posteriors
+                .iter()
+                .enumerate()
+                .filter(|(_, posterior)| **posterior < 0.0)
+                .map(|(bit, _)| bit)
+                .collect();
+            correction = SparseBinVec::new(self.num_bits(), positions);
+        }
+        correction
+    }
+}
+
+impl<'a> ClassicalSyndromeDecoder<'a> for QuasiCyclicBpDecoder {
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.num_checks())
+    }
+}
+
+impl DecoderCapabilities for QuasiCyclicBpDecoder {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::BpDecoder;
+
+    fn matrix() -> QuasiCyclicMatrix {
+        QuasiCyclicMatrix::new(
+            7,
+            vec![
+                vec![Some(0), Some(1), Some(2), Some(4), None, Some(3)],
+                vec![Some(0), Some(3), None, Some(6), Some(5), Some(1)],
+                vec![None, Some(0), Some(4), Some(2), Some(1), Some(6)],
+            ],
+        )
+    }
+
+    #[test]
+    fn matches_bp_decoder_on_expanded_matrix() {
+        let matrix = matrix();
+        let probability = Probability::new(0.05);
+        let decoder = QuasiCyclicBpDecoder::new(&matrix, probability, 10);
+        let expanded_decoder = BpDecoder::new(&matrix.expanded(), probability, 10);
+        assert_eq!(decoder.syndrome_length(), Some(21));
+        for positions in [vec![], vec![0], vec![9], vec![3, 30], vec![12, 20, 41]] {
+            let syndrome = matrix.syndrome_of(&SparseBinVec::new(42, positions));
+            assert_eq!(
+                decoder.correction_for(syndrome.as_view()),
+                expanded_decoder.correction_for(syndrome.as_view())
+            );
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+
+/// The checks measured at each round of a periodic measurement schedule.
+///
+/// Round `r` measures the checks of round `r % p` of the schedule
+/// where `p` is the [period](MeasurementSchedule::period).
+/// A detector is the sum of two consecutive outcomes of the same check,
+/// the outcome before its first measurement being trivial.
+/// A data error then flips the next measurement of each of its checks
+/// while a measurement error flips two consecutive detectors of the same check.
+///
+/// The detectors of all rounds are related to the errors by the
+/// [space-time matrix](MeasurementSchedule::space_time_matrix) of the schedule,
+/// which generalizes the one of a [`SyndromeHistory`](super::SyndromeHistory)
+/// where every check is measured at each round.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::MeasurementSchedule;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::repetition_code(5);
+/// // Even checks are measured at even rounds and odd checks at odd rounds.
+/// let schedule = MeasurementSchedule::alternating(4, 2);
+/// assert_eq!(schedule.measured_checks(0), &[0, 2]);
+/// assert_eq!(schedule.measured_checks(3), &[1, 3]);
+///
+/// // Bit 2 flips before the second round.
+/// let syndrome = code.syndrome_of(&SparseBinVec::new(5, vec![2]));
+/// let syndromes = vec![SparseBinVec::zeros(4), syndrome.clone(), syndrome];
+/// let detectors = schedule.detectors(&syndromes);
+///
+/// // Check 1 is flipped at its first measurement and check 2 at its second one.
+/// assert_eq!(detectors, SparseBinVec::new(6, vec![2, 5]));
+///
+/// let matrix = schedule.space_time_matrix(code.parity_check_matrix(), 3);
+/// let error = SparseBinVec::new(matrix.number_of_columns(), vec![5 + 2]);
+/// assert_eq!(&matrix * &error, detectors);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct MeasurementSchedule {
+    num_checks: usize,
+    rounds: Vec<Vec<usize>>,
+}
+
+impl MeasurementSchedule {
+    /// Creates a schedule repeating the given rounds of measured checks.
+    ///
+    /// A round may be empty, in which case no check is measured.
+    ///
+    /// # Panic
+    ///
+    /// Panics if there is no round or if a check is out of bound.
+    pub fn new(num_checks: usize, mut rounds: Vec<Vec<usize>>) -> Self {
+        if rounds.is_empty() {
+            panic!("a schedule needs at least one round");
+        }
+        for checks in rounds.iter_mut() {
+            if let Some(check) = checks.iter().find(|check| **check >= num_checks) {
+                panic!("check {} is out of bound for {} checks", check, num_checks);
+            }
+            checks.sort_unstable();
+            checks.dedup();
+        }
+        Self { num_checks, rounds }
+    }
+
+    /// Creates a schedule measuring every check at each round.
+    pub fn every_round(num_checks: usize) -> Self {
+        Self::new(num_checks, vec![(0..num_checks).collect()])
+    }
+
+    /// Creates a schedule measuring the checks in alternation
+    /// where check `c` is measured at the rounds `r` such that
+    /// `r % num_groups == c % num_groups`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of groups is 0.
+    pub fn alternating(num_checks: usize, num_groups: usize) -> Self {
+        if num_groups == 0 {
+            panic!("checks must be split in at least one group");
+        }
+        let rounds = (0..num_groups)
+            .map(|group| (group..num_checks).step_by(num_groups).collect())
+            .collect();
+        Self::new(num_checks, rounds)
+    }
+
+    /// Returns the number of checks of the code.
+    pub fn num_checks(&self) -> usize {
+        self.num_checks
+    }
+
+    /// Returns the number of rounds before the schedule repeats.
+    pub fn period(&self) -> usize {
+        self.rounds.len()
+    }
+
+    /// Returns the sorted checks measured at the given round.
+    pub fn measured_checks(&self, round: usize) -> &[usize] {
+        &self.rounds[round % self.period()]
+    }
+
+    /// Checks if the given check is measured at the given round.
+    pub fn is_measured(&self, round: usize, check: usize) -> bool {
+        self.measured_checks(round).binary_search(&check).is_ok()
+    }
+
+    /// Returns the number of measurements in the given number of rounds.
+    pub fn num_measurements(&self, num_rounds: usize) -> usize {
+        (0..num_rounds)
+            .map(|round| self.measured_checks(round).len())
+            .sum()
+    }
+
+    /// Returns the detectors of the measurements of a number of rounds given
+    /// the syndrome of each round.
+    ///
+    /// The detectors are ordered by round and then by check,
+    /// which is the order of the rows of the
+    /// [space-time matrix](MeasurementSchedule::space_time_matrix).
+    /// The outcomes of the checks not measured at a round are ignored.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length of a syndrome is different from the number of checks.
+    pub fn detectors(&self, syndromes: &[SparseBinVec]) -> SparseBinVec {
+        let mut previous = vec![false; self.num_checks];
+        let mut detectors = Vec::new();
+        let mut measurement = 0;
+        for (round, syndrome) in syndromes.iter().enumerate() {
+            if syndrome.len() != self.num_checks {
+                panic!(
+                    "syndrome of length {} for {} checks",
+                    syndrome.len(),
+                    self.num_checks
+                );
+            }
+            for check in self.measured_checks(round) {
+                let outcome = syndrome.is_one_at(*check) == Some(true);
+                if outcome != previous[*check] {
+                    detectors.push(measurement);
+                }
+                previous[*check] = outcome;
+                measurement += 1;
+            }
+        }
+        SparseBinVec::new(measurement, detectors)
+    }
+
+    /// Returns the parity check matrix relating the errors of a number of rounds
+    /// to the [detectors](MeasurementSchedule::detectors).
+    ///
+    /// For `n` bits and `r` rounds, the first `r * n` columns are the data errors
+    /// appearing before each round and the other columns are the errors
+    /// of each measurement except those of the last round,
+    /// which are assumed to be perfect.
+    /// A data error before a round where one of its checks is not measured
+    /// is detected by the next measurement of this check.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of rows of the parity check matrix is
+    /// different from the number of checks of the schedule.
+    pub fn space_time_matrix(&self, parity_mat: &SparseBinMat, num_rounds: usize) -> SparseBinMat {
+        if parity_mat.number_of_rows() != self.num_checks {
+            panic!(
+                "matrix with {} rows for a schedule of {} checks",
+                parity_mat.number_of_rows(),
+                self.num_checks
+            );
+        }
+        let num_bits = parity_mat.number_of_columns();
+        let first_measurement = num_rounds * num_bits;
+        // The round and index of the last measurement of each check.
+        let mut previous: Vec<Option<(usize, usize)>> = vec![None; self.num_checks];
+        let mut rows = Vec::with_capacity(self.num_measurements(num_rounds));
+        let mut measurement = 0;
+        for round in 0..num_rounds {
+            for check in self.measured_checks(round) {
+                let bits = parity_mat.row(*check).unwrap();
+                let first_round = previous[*check].map_or(0, |(round, _)| round + 1);
+                let mut row = (first_round..=round)
+                    .flat_map(|round| {
+                        bits.non_trivial_positions()
+                            .map(move |bit| round * num_bits + bit)
+                    })
+                    .collect::<Vec<_>>();
+                if let Some((_, previous_measurement)) = previous[*check] {
+                    row.push(first_measurement + previous_measurement);
+                }
+                if round + 1 < num_rounds {
+                    row.push(first_measurement + measurement);
+                }
+                rows.push(row);
+                previous[*check] = Some((round, measurement));
+                measurement += 1;
+            }
+        }
+        let num_measurement_errors = self.num_measurements(num_rounds.saturating_sub(1));
+        SparseBinMat::new(first_measurement + num_measurement_errors, rows)
+    }
+
+    /// Returns the total data error of a space-time error
+    /// of a number of rounds for a code with the given number of bits.
+    ///
+    /// This maps a correction of the detectors to a correction of the code.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the space-time error is too short for the data errors of all rounds.
+    pub fn data_error(
+        &self,
+        space_time_error: &SparseBinVec,
+        num_bits: usize,
+        num_rounds: usize,
+    ) -> SparseBinVec {
+        if space_time_error.len() < num_rounds * num_bits {
+            panic!(
+                "space-time error of length {} for {} rounds of {} bits",
+                space_time_error.len(),
+                num_rounds,
+                num_bits
+            );
+        }
+        let mut is_flipped = vec![false; num_bits];
+        for position in space_time_error
+            .non_trivial_positions()
+            .take_while(|position| *position < num_rounds * num_bits)
+        {
+            is_flipped[position % num_bits] ^= true;
+        }
+        let positions = is_flipped
+            .into_iter()
+            .enumerate()
+            .filter(|(_, is_flipped)| *is_flipped)
+            .map(|(bit, _)| bit)
+            .collect();
+        SparseBinVec::new(num_bits, positions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::decoders::SyndromeHistory;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn measuring_every_round_matches_syndrome_history() {
+        let code = LinearCode::hamming_code();
+        let schedule = MeasurementSchedule::every_round(3);
+        assert_eq!(
+            schedule.space_time_matrix(code.parity_check_matrix(), 4),
+            SyndromeHistory::space_time_matrix(code.parity_check_matrix(), 4)
+        );
+    }
+
+    #[test]
+    fn space_time_matrix_explains_detectors_of_random_errors() {
+        let code = LinearCode::repetition_code(6);
+        let schedule = MeasurementSchedule::new(5, vec![vec![0, 1, 2], vec![3, 4], vec![]]);
+        let num_rounds = 7;
+        let matrix = schedule.space_time_matrix(code.parity_check_matrix(), num_rounds);
+        let mut rng = StdRng::seed_from_u64(123);
+        for _ in 0..20 {
+            let mut data = SparseBinVec::zeros(6);
+            let mut syndromes = Vec::new();
+            let mut error = Vec::new();
+            let mut measurement = 0;
+            for round in 0..num_rounds {
+                let data_error = (0..6).filter(|_| rng.gen_bool(0.1)).collect::<Vec<_>>();
+                error.extend(data_error.iter().map(|bit| round * 6 + bit));
+                data = &data + &SparseBinVec::new(6, data_error);
+                let mut flips = Vec::new();
+                for check in schedule.measured_checks(round) {
+                    if round + 1 < num_rounds && rng.gen_bool(0.1) {
+                        flips.push(*check);
+                        error.push(num_rounds * 6 + measurement);
+                    }
+                    measurement += 1;
+                }
+                syndromes.push(&code.syndrome_of(&data) + &SparseBinVec::new(5, flips));
+            }
+            error.sort_unstable();
+            let error = SparseBinVec::new(matrix.number_of_columns(), error);
+            assert_eq!(&matrix * &error, schedule.detectors(&syndromes));
+            assert_eq!(schedule.data_error(&error, 6, num_rounds), data);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn checks_must_be_in_bound() {
+        MeasurementSchedule::new(3, vec![vec![0, 3]]);
+    }
+}
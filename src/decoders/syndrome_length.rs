@@ -0,0 +1,46 @@
+use pauli::Pauli;
+use std::fmt;
+
+/// An error when a syndrome doesn't have one bit per check of the decoder.
+///
+/// For CSS decoders, the error gives the type of the stabilizers
+/// whose syndrome has the wrong length.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct SyndromeLengthError {
+    pub stabilizers: Option<Pauli>,
+    pub found: usize,
+    pub expected: usize,
+}
+
+impl SyndromeLengthError {
+    // Returns an error if the expected length is known and different.
+    pub(crate) fn check(
+        stabilizers: Option<Pauli>,
+        found: usize,
+        expected: Option<usize>,
+    ) -> Result<(), Self> {
+        match expected {
+            Some(expected) if expected != found => Err(Self {
+                stabilizers,
+                found,
+                expected,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for SyndromeLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(stabilizers) = self.stabilizers {
+            write!(f, "{:?} ", stabilizers)?;
+        }
+        write!(
+            f,
+            "syndrome of length {} is invalid for decoder with {} checks",
+            self.found, self.expected
+        )
+    }
+}
+
+impl std::error::Error for SyndromeLengthError {}
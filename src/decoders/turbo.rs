@@ -0,0 +1,368 @@
+use super::{
+    BpDecoder, Capabilities, Capability, ClassicalSyndromeDecoder, DecoderCapabilities,
+    SoftSyndromeDecoder, SyndromeDecoder,
+};
+use crate::codes::LinearCode;
+use crate::noise::Probability;
+use itertools::Itertools;
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+
+/// A decoder exchanging extrinsic information between two component decoders
+/// acting on the same bits.
+///
+/// This is useful for product and concatenated codes where the checks
+/// are split between two component codes with efficient decoders,
+/// such as the row and column codes of a product code.
+///
+/// The syndrome contains the syndrome of the first component
+/// followed by the syndrome of the second one.
+/// At each iteration, each component decodes its part of the syndrome
+/// using the channel log-likelihood ratios plus the extrinsic information
+/// of the other component, that is, its posteriors minus its priors.
+/// Decoding stops when the hard decision matches the full syndrome
+/// or when the maximum number of iterations is reached.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::decoders::{BpDecoder, SyndromeDecoder, TurboDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+///
+/// // The product of two repetition codes of length 3.
+/// let rows = SparseBinMat::new(9, vec![
+///     vec![0, 1], vec![1, 2], vec![3, 4], vec![4, 5], vec![6, 7], vec![7, 8],
+/// ]);
+/// let columns = SparseBinMat::new(9, vec![
+///     vec![0, 3], vec![3, 6], vec![1, 4], vec![4, 7], vec![2, 5], vec![5, 8],
+/// ]);
+/// let probability = Probability::new(0.1);
+/// let decoder = TurboDecoder::new(
+///     BpDecoder::new(&rows, probability, 10),
+///     BpDecoder::new(&columns, probability, 10),
+///     probability,
+///     5,
+/// );
+///
+/// // The row decoder alone would fail on two errors in the same row.
+/// let error = SparseBinVec::new(9, vec![0, 1]);
+/// let syndrome = &rows.vertical_concat_with(&columns) * &error;
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TurboDecoder<First, Second> {
+    first: First,
+    second: Second,
+    parity_mat: SparseBinMat,
+    likelyhood: f64,
+    num_iterations: usize,
+}
+
+impl<First, Second> TurboDecoder<First, Second>
+where
+    First: SoftSyndromeDecoder,
+    Second: SoftSyndromeDecoder,
+{
+    /// Creates a decoder from two components
+    /// assuming each bit flips independently with the given probability.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the components have a different number of bits.
+    pub fn new(
+        first: First,
+        second: Second,
+        probability: Probability,
+        num_iterations: usize,
+    ) -> Self {
+        let first_matrix = first.parity_check_matrix();
+        let second_matrix = second.parity_check_matrix();
+        if first_matrix.number_of_columns() != second_matrix.number_of_columns() {
+            panic!(
+                "components have {} and {} bits",
+                first_matrix.number_of_columns(),
+                second_matrix.number_of_columns()
+            );
+        }
+        let parity_mat = first_matrix.vertical_concat_with(second_matrix);
+        Self {
+            first,
+            second,
+            parity_mat,
+            likelyhood: probability.llr(),
+            num_iterations,
+        }
+    }
+
+    /// Returns the first component decoder.
+    pub fn first(&self) -> &First {
+        &self.first
+    }
+
+    /// Returns the second component decoder.
+    pub fn second(&self) -> &Second {
+        &self.second
+    }
+
+    /// Returns the length of the corrections.
+    pub fn num_bits(&self) -> usize {
+        self.parity_mat.number_of_columns()
+    }
+
+    /// Returns the length of the syndromes.
+    pub fn num_checks(&self) -> usize {
+        self.parity_mat.number_of_rows()
+    }
+
+    fn split(&self, syndrome: &SparseBinSlice) -> (SparseBinVec, SparseBinVec) {
+        let num_first_checks = self.first.parity_check_matrix().number_of_rows();
+        let (first, second): (Vec<usize>, Vec<usize>) = syndrome
+            .non_trivial_positions()
+            .partition(|check| *check < num_first_checks);
+        (
+            SparseBinVec::new(num_first_checks, first),
+            SparseBinVec::new(
+                self.num_checks() - num_first_checks,
+                second
+                    .into_iter()
+                    .map(|check| check - num_first_checks)
+                    .collect(),
+            ),
+        )
+    }
+}
+
+impl TurboDecoder<BpDecoder, BpDecoder> {
+    /// Creates a soft-decision decoder for the
+    /// [tensor product](LinearCode::tensor_product) of two codes.
+    ///
+    /// The first component decodes all the rows with belief propagation
+    /// and the second component decodes all the columns.
+    /// Both components and the exchange between them
+    /// run up to the given number of iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::decoders::{SyndromeDecoder, TurboDecoder};
+    /// use ldpc::noise::Probability;
+    /// use sparse_bin_mat::SparseBinVec;
+    ///
+    /// let repetition_code = LinearCode::repetition_code(3);
+    /// let code = repetition_code.tensor_product(&repetition_code);
+    /// let decoder = TurboDecoder::for_tensor_product(
+    ///     &repetition_code,
+    ///     &repetition_code,
+    ///     Probability::new(0.1),
+    ///     10,
+    /// );
+    ///
+    /// let error = SparseBinVec::new(9, vec![0, 1]);
+    /// let syndrome = code.syndrome_of(&error);
+    /// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+    /// ```
+    pub fn for_tensor_product(
+        first_code: &LinearCode,
+        second_code: &LinearCode,
+        probability: Probability,
+        num_iterations: usize,
+    ) -> Self {
+        let (row_checks, column_checks) = first_code.tensor_product_checks(second_code);
+        Self::new(
+            BpDecoder::new(&row_checks, probability, num_iterations),
+            BpDecoder::new(&column_checks, probability, num_iterations),
+            probability,
+            num_iterations,
+        )
+    }
+}
+
+impl<First, Second> SoftSyndromeDecoder for TurboDecoder<First, Second>
+where
+    First: SoftSyndromeDecoder,
+    Second: SoftSyndromeDecoder,
+{
+    fn parity_check_matrix(&self) -> &SparseBinMat {
+        &self.parity_mat
+    }
+
+    fn posteriors_for(&self, syndrome: SparseBinSlice, priors: &[f64]) -> Vec<f64> {
+        if syndrome.len() != self.num_checks() {
+            panic!(
+                "syndrome of length {} for {} checks",
+                syndrome.len(),
+                self.num_checks()
+            );
+        }
+        let (first_syndrome, second_syndrome) = self.split(&syndrome);
+        let mut second_extrinsic = vec![0.0; self.num_bits()];
+        let mut posteriors = priors.to_vec();
+        for _ in 0..self.num_iterations {
+            let first_priors = sum(priors, &second_extrinsic);
+            let first_posteriors = self
+                .first
+                .posteriors_for(first_syndrome.as_view(), &first_priors);
+            let first_extrinsic = extrinsic(&first_posteriors, &first_priors);
+            let second_priors = sum(priors, &first_extrinsic);
+            posteriors = self
+                .second
+                .posteriors_for(second_syndrome.as_view(), &second_priors);
+            second_extrinsic = extrinsic(&posteriors, &second_priors);
+            if (&self.parity_mat * &hard_decision(&posteriors)).as_view() == syndrome {
+                break;
+            }
+        }
+        posteriors
+    }
+}
+
+fn sum(first: &[f64], second: &[f64]) -> Vec<f64> {
+    first
+        .iter()
+        .zip(second.iter())
+        .map(|(first, second)| first + second)
+        .collect()
+}
+
+// Infinite posteriors and priors carry no extrinsic information.
+fn extrinsic(posteriors: &[f64], priors: &[f64]) -> Vec<f64> {
+    posteriors
+        .iter()
+        .zip(priors.iter())
+        .map(|(posterior, prior)| {
+            let value = posterior - prior;
+            if value.is_nan() {
+                0.0
+            } else {
+                value
+            }
+        })
+        .collect()
+}
+
+fn hard_decision(likelyhoods: &[f64]) -> SparseBinVec {
+    SparseBinVec::new(
+        likelyhoods.len(),
+        likelyhoods
+            .iter()
+            .positions(|likelyhood| *likelyhood < 0.0)
+            .collect(),
+    )
+}
+
+impl<'a, First, Second> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>
+    for TurboDecoder<First, Second>
+where
+    First: SoftSyndromeDecoder,
+    Second: SoftSyndromeDecoder,
+{
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the total number of checks.
+    fn correction_for(&self, syndrome: SparseBinSlice) -> SparseBinVec {
+        debug_assert_eq!(
+            Some(syndrome.len()),
+            self.syndrome_length(),
+            "syndrome of the wrong length"
+        );
+        let priors = vec![self.likelyhood; self.num_bits()];
+        hard_decision(&self.posteriors_for(syndrome, &priors))
+    }
+}
+
+impl<'a, First, Second> ClassicalSyndromeDecoder<'a> for TurboDecoder<First, Second>
+where
+    First: SoftSyndromeDecoder,
+    Second: SoftSyndromeDecoder,
+{
+    fn syndrome_length(&self) -> Option<usize> {
+        Some(self.parity_mat.number_of_rows())
+    }
+}
+
+impl<First, Second> DecoderCapabilities for TurboDecoder<First, Second> {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none().with(Capability::SoftInput)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::decoders::BpDecoder;
+
+    // The checks of the product of two repetition codes of length 3 on the rows and columns.
+    fn product_checks() -> (SparseBinMat, SparseBinMat) {
+        let rows = (0..3)
+            .flat_map(|row| (0..2).map(move |column| vec![3 * row + column, 3 * row + column + 1]))
+            .collect();
+        let columns = (0..3)
+            .flat_map(|column| (0..2).map(move |row| vec![3 * row + column, 3 * row + column + 3]))
+            .collect();
+        (SparseBinMat::new(9, rows), SparseBinMat::new(9, columns))
+    }
+
+    fn decoder() -> TurboDecoder<BpDecoder, BpDecoder> {
+        let (rows, columns) = product_checks();
+        let probability = Probability::new(0.1);
+        TurboDecoder::new(
+            BpDecoder::new(&rows, probability, 10),
+            BpDecoder::new(&columns, probability, 10),
+            probability,
+            5,
+        )
+    }
+
+    #[test]
+    fn corrects_all_errors_of_weight_up_to_2_on_product_code() {
+        let decoder = decoder();
+        let code = LinearCode::from_parity_check_matrix(decoder.parity_check_matrix().clone());
+        assert_eq!(code.corrects_all_errors_up_to(2, &decoder), Ok(()));
+        assert_eq!(decoder.syndrome_length(), Some(12));
+    }
+
+    #[test]
+    fn tensor_product_decoder_matches_product_code() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = repetition_code.tensor_product(&repetition_code);
+        let decoder = TurboDecoder::for_tensor_product(
+            &repetition_code,
+            &repetition_code,
+            Probability::new(0.1),
+            10,
+        );
+        assert_eq!(decoder.parity_check_matrix(), code.parity_check_matrix());
+        assert_eq!(code.corrects_all_errors_up_to(2, &decoder), Ok(()));
+    }
+
+    #[test]
+    fn no_syndrome_gives_no_correction() {
+        let decoder = decoder();
+        assert_eq!(
+            decoder.correction_for(SparseBinVec::zeros(12).as_view()),
+            SparseBinVec::zeros(9)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn components_must_have_the_same_number_of_bits() {
+        let probability = Probability::new(0.1);
+        TurboDecoder::new(
+            BpDecoder::new(
+                LinearCode::repetition_code(3).parity_check_matrix(),
+                probability,
+                10,
+            ),
+            BpDecoder::new(
+                LinearCode::hamming_code().parity_check_matrix(),
+                probability,
+                10,
+            ),
+            probability,
+            5,
+        );
+    }
+}
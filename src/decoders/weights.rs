@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Multiplicative weights on the messages from the checks to the bits
+/// of a belief propagation decoder, for example trained offline as a neural decoder.
+///
+/// There is one weight for each edge of the Tanner graph in each iteration.
+/// The edges are ordered as the non trivial elements of the parity check matrix,
+/// that is by check and then by bit.
+/// Iterations after the last set of weights reuse the last set,
+/// such that a single set shares the weights between all iterations.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{BpDecoder, BpWeights, SyndromeDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// // The repetition code of length 3 has 4 edges.
+/// let json = r#"{ "iterations": [[0.8, 0.8, 0.8, 0.8], [1.0, 1.2, 1.2, 1.0]] }"#;
+/// let weights = BpWeights::from_json(json).unwrap();
+/// assert_eq!(weights.num_edges(), 4);
+///
+/// let code = LinearCode::repetition_code(3);
+/// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10)
+///     .with_weights(weights);
+///
+/// let error = SparseBinVec::new(3, vec![1]);
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(syndrome.as_view()), error);
+/// ```
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BpWeights {
+    iterations: Vec<Vec<f64>>,
+}
+
+impl BpWeights {
+    /// Creates weights from the weights of the edges in each iteration.
+    ///
+    /// # Panic
+    ///
+    /// Panics if there is no iteration, if iterations have different numbers of edges
+    /// or if a weight is not finite.
+    pub fn new(iterations: Vec<Vec<f64>>) -> Self {
+        Self::try_new(iterations).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Creates weights from their JSON representation,
+    /// an object whose `iterations` field is the list of the weights of the edges
+    /// in each iteration.
+    pub fn from_json(json: &str) -> Result<Self, BpWeightsError> {
+        let weights: Self = serde_json::from_str(json)
+            .map_err(|error| BpWeightsError::InvalidJson(error.to_string()))?;
+        Self::try_new(weights.iterations)
+    }
+
+    /// Returns the JSON representation of the weights.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    fn try_new(iterations: Vec<Vec<f64>>) -> Result<Self, BpWeightsError> {
+        let num_edges = iterations.first().ok_or(BpWeightsError::NoIteration)?.len();
+        for (iteration, weights) in iterations.iter().enumerate() {
+            if weights.len() != num_edges {
+                return Err(BpWeightsError::DifferentNumberOfEdges {
+                    iteration,
+                    found: weights.len(),
+                    expected: num_edges,
+                });
+            }
+            if weights.iter().any(|weight| !weight.is_finite()) {
+                return Err(BpWeightsError::NonFiniteWeight(iteration));
+            }
+        }
+        Ok(Self { iterations })
+    }
+
+    /// Returns the number of edges weighted in each iteration.
+    pub fn num_edges(&self) -> usize {
+        self.iterations[0].len()
+    }
+
+    /// Returns the number of iterations with their own weights.
+    pub fn num_iterations(&self) -> usize {
+        self.iterations.len()
+    }
+
+    /// Returns the weights of the edges in the given iteration starting from 0.
+    pub fn for_iteration(&self, iteration: usize) -> &[f64] {
+        &self.iterations[iteration.min(self.iterations.len() - 1)]
+    }
+}
+
+/// An error when building belief propagation weights.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BpWeightsError {
+    InvalidJson(String),
+    NoIteration,
+    DifferentNumberOfEdges {
+        iteration: usize,
+        found: usize,
+        expected: usize,
+    },
+    NonFiniteWeight(usize),
+}
+
+impl fmt::Display for BpWeightsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidJson(error) => write!(f, "invalid weights: {}", error),
+            Self::NoIteration => write!(f, "no iteration of weights"),
+            Self::DifferentNumberOfEdges {
+                iteration,
+                found,
+                expected,
+            } => write!(
+                f,
+                "iteration {} has {} weights instead of {}",
+                iteration, found, expected
+            ),
+            Self::NonFiniteWeight(iteration) => {
+                write!(f, "iteration {} has a non finite weight", iteration)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BpWeightsError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn last_weights_are_reused() {
+        let weights = BpWeights::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        assert_eq!(weights.for_iteration(0), &[1.0, 2.0]);
+        assert_eq!(weights.for_iteration(5), &[3.0, 4.0]);
+        assert_eq!(BpWeights::from_json(&weights.to_json()), Ok(weights));
+    }
+
+    #[test]
+    fn invalid_weights_are_rejected() {
+        assert_eq!(
+            BpWeights::from_json(r#"{ "iterations": [] }"#),
+            Err(BpWeightsError::NoIteration)
+        );
+        assert_eq!(
+            BpWeights::from_json(r#"{ "iterations": [[1.0], [1.0, 2.0]] }"#),
+            Err(BpWeightsError::DifferentNumberOfEdges {
+                iteration: 1,
+                found: 2,
+                expected: 1
+            })
+        );
+        assert!(matches!(
+            BpWeights::from_json(r#"{ "weights": [] }"#),
+            Err(BpWeightsError::InvalidJson(_))
+        ));
+    }
+}
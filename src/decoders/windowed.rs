@@ -0,0 +1,262 @@
+use super::{Capabilities, Capability, DecoderCapabilities, StreamingDecoder, SyndromeDecoder};
+use crate::css::{Css, CssOperator, CssSyndrome};
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+use std::collections::VecDeque;
+
+/// A streaming decoder committing a correction for each round
+/// once a window of later rounds has been measured.
+///
+/// Syndromes are the raw outcomes of each measurement round
+/// and may contain measurement errors.
+/// The corrections are never applied to the measured system,
+/// they are accumulated in a frame tracked by the decoder.
+///
+/// When the window is full, the syndrome of each check is replaced by
+/// its strict majority over the window after removing the syndrome
+/// of the corrections already emitted.
+/// This filtered syndrome is decoded by the inner decoder
+/// and the oldest round leaves the window.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::{BpDecoder, StreamingDecoder, WindowedDecoder};
+/// use ldpc::noise::Probability;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::repetition_code(3);
+/// let inner = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+/// let mut decoder = WindowedDecoder::new(code.parity_check_matrix(), inner, 3);
+///
+/// let error = SparseBinVec::new(3, vec![1]);
+/// let syndrome = code.syndrome_of(&error);
+/// let measurement_error = SparseBinVec::new(2, vec![0]);
+///
+/// assert_eq!(decoder.push_round(syndrome.clone()), None);
+/// assert_eq!(decoder.push_round(&syndrome + &measurement_error), None);
+/// assert_eq!(decoder.push_round(syndrome.clone()), Some(error));
+/// assert_eq!(decoder.push_round(syndrome), Some(SparseBinVec::zeros(3)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WindowedDecoder<D> {
+    parity_mat: SparseBinMat,
+    decoder: D,
+    window_size: usize,
+    window: VecDeque<SparseBinVec>,
+    frame_syndrome: SparseBinVec,
+}
+
+impl<D> WindowedDecoder<D> {
+    /// Creates a windowed decoder for the code defined by the parity check matrix
+    /// using the inner decoder on majority filtered syndromes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the window size is 0.
+    pub fn new(parity_mat: &SparseBinMat, decoder: D, window_size: usize) -> Self {
+        if window_size == 0 {
+            panic!("window size must be positive");
+        }
+        Self {
+            parity_mat: parity_mat.clone(),
+            decoder,
+            window_size,
+            window: VecDeque::with_capacity(window_size),
+            frame_syndrome: SparseBinVec::zeros(parity_mat.number_of_rows()),
+        }
+    }
+
+    /// Returns the number of rounds in a full window.
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// Returns the number of rounds waiting for a correction.
+    pub fn num_pending_rounds(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Returns the length of the corrections.
+    pub fn num_bits(&self) -> usize {
+        self.parity_mat.number_of_columns()
+    }
+
+    /// Returns the length of the syndromes.
+    pub fn num_checks(&self) -> usize {
+        self.parity_mat.number_of_rows()
+    }
+
+    fn majority_syndrome(&self) -> SparseBinVec {
+        let mut counts = vec![0; self.num_checks()];
+        for syndrome in self.window.iter() {
+            let syndrome = syndrome + &self.frame_syndrome;
+            for check in syndrome.non_trivial_positions() {
+                counts[check] += 1;
+            }
+        }
+        let positions = counts
+            .into_iter()
+            .enumerate()
+            .filter(|(_, count)| 2 * count > self.window.len())
+            .map(|(check, _)| check)
+            .collect();
+        SparseBinVec::new(self.num_checks(), positions)
+    }
+}
+
+impl<D> WindowedDecoder<D>
+where
+    D: for<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>,
+{
+    fn commit(&mut self) -> SparseBinVec {
+        let correction = self
+            .decoder
+            .correction_for(self.majority_syndrome().as_view());
+        self.frame_syndrome = &self.frame_syndrome + &(&self.parity_mat * &correction);
+        correction
+    }
+}
+
+impl<D> StreamingDecoder<SparseBinVec, SparseBinVec> for WindowedDecoder<D>
+where
+    D: for<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>,
+{
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    fn push_round(&mut self, syndrome: SparseBinVec) -> Option<SparseBinVec> {
+        if syndrome.len() != self.num_checks() {
+            panic!(
+                "syndrome of length {} for {} checks",
+                syndrome.len(),
+                self.num_checks()
+            );
+        }
+        self.window.push_back(syndrome);
+        if self.window.len() < self.window_size {
+            return None;
+        }
+        let correction = self.commit();
+        self.window.pop_front();
+        Some(correction)
+    }
+
+    fn flush(&mut self) -> SparseBinVec {
+        if self.window.is_empty() {
+            return SparseBinVec::zeros(self.num_bits());
+        }
+        let correction = self.commit();
+        self.window.clear();
+        correction
+    }
+
+    fn latency(&self) -> usize {
+        self.window_size - 1
+    }
+}
+
+/// The X and Z syndromes are decoded by independent windowed decoders.
+///
+/// As for the [`CssDecoder`](super::CssDecoder),
+/// the decoder of the X stabilizers returns the Z part of the correction and vice-versa.
+/// A correction is emitted as soon as one of the decoders commits.
+impl<X, Z> StreamingDecoder<CssSyndrome, CssOperator>
+    for Css<WindowedDecoder<X>, WindowedDecoder<Z>>
+where
+    X: for<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>,
+    Z: for<'a> SyndromeDecoder<SparseBinSlice<'a>, SparseBinVec>,
+{
+    fn push_round(&mut self, syndrome: CssSyndrome) -> Option<CssOperator> {
+        let z = self.x.push_round(syndrome.x);
+        let x = self.z.push_round(syndrome.z);
+        if x.is_none() && z.is_none() {
+            return None;
+        }
+        Some(CssOperator {
+            x: x.unwrap_or_else(|| SparseBinVec::zeros(self.z.num_bits())),
+            z: z.unwrap_or_else(|| SparseBinVec::zeros(self.x.num_bits())),
+        })
+    }
+
+    fn flush(&mut self) -> CssOperator {
+        CssOperator {
+            x: self.z.flush(),
+            z: self.x.flush(),
+        }
+    }
+
+    fn latency(&self) -> usize {
+        self.x.latency().max(self.z.latency())
+    }
+}
+
+impl<D> DecoderCapabilities for WindowedDecoder<D> {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::none().with(Capability::NoisySyndromes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::{CssCode, LinearCode};
+    use crate::decoders::BpDecoder;
+    use crate::noise::Probability;
+    use pauli::{PauliOperator, X, Z};
+
+    fn repetition_decoder(window_size: usize) -> (LinearCode, WindowedDecoder<BpDecoder>) {
+        let code = LinearCode::repetition_code(3);
+        let inner = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let decoder = WindowedDecoder::new(code.parity_check_matrix(), inner, window_size);
+        (code, decoder)
+    }
+
+    #[test]
+    fn window_of_one_round_decodes_each_syndrome() {
+        let (code, mut decoder) = repetition_decoder(1);
+        assert_eq!(decoder.latency(), 0);
+        let error = SparseBinVec::new(3, vec![2]);
+        let syndrome = code.syndrome_of(&error);
+        assert_eq!(decoder.push_round(syndrome.clone()), Some(error));
+        assert_eq!(decoder.push_round(syndrome), Some(SparseBinVec::zeros(3)));
+    }
+
+    #[test]
+    fn flush_decodes_pending_rounds() {
+        let (code, mut decoder) = repetition_decoder(5);
+        let error = SparseBinVec::new(3, vec![0]);
+        let syndrome = code.syndrome_of(&error);
+        for _ in 0..3 {
+            assert_eq!(decoder.push_round(syndrome.clone()), None);
+        }
+        assert_eq!(decoder.num_pending_rounds(), 3);
+        assert_eq!(decoder.flush(), error);
+        assert_eq!(decoder.num_pending_rounds(), 0);
+        assert_eq!(decoder.flush(), SparseBinVec::zeros(3));
+    }
+
+    #[test]
+    fn css_windowed_decoder_swaps_sectors() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+        let probability = Probability::new(0.1);
+        let mut decoder = Css {
+            x: WindowedDecoder::new(
+                &code.stabilizers.x,
+                BpDecoder::new(&code.stabilizers.x, probability, 10),
+                2,
+            ),
+            z: WindowedDecoder::new(
+                &code.stabilizers.z,
+                BpDecoder::new(&code.stabilizers.z, probability, 10),
+                2,
+            ),
+        };
+        let error = PauliOperator::new(code.len(), vec![0, 4], vec![X, Z]);
+        let syndrome = code.syndrome_of(&error);
+        assert_eq!(decoder.push_round(syndrome.clone()), None);
+        let correction: PauliOperator = decoder.push_round(syndrome).unwrap().into();
+        assert!(code.has_stabilizer(&(&error * &correction)));
+    }
+}
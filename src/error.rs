@@ -0,0 +1,306 @@
+use crate::codes::css::WeightDistributionError;
+use crate::codes::{
+    AlistError, CssError, CssInconsistency, FingerprintParseError, LinearCodeInconsistency,
+    RegistryError, SamplingError, StabilizerError,
+};
+use crate::decoders::{BpWeightsError, MissingCapabilities, SyndromeLengthError};
+use crate::noise::EmpiricalNoiseError;
+use crate::simulation::MergeError;
+use std::fmt;
+use std::io;
+
+/// A result whose error can come from any module of the crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// An error from any module of the crate.
+///
+/// Each module keeps its own error types, which are variants of this error,
+/// such that code combining several modules can propagate all of them
+/// with the `?` operator.
+/// Every error type of the crate converts into this error,
+/// but strings don't since they carry no information about their origin.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::codes::{CodeRegistry, LinearCode, RegisteredCode};
+/// use ldpc::Error;
+///
+/// fn read_code(spec: &str, alist: &str) -> ldpc::Result<(RegisteredCode, LinearCode)> {
+///     let registered = CodeRegistry::default().resolve(spec)?;
+///     let code = LinearCode::from_alist(alist.as_bytes())?;
+///     Ok((registered, code))
+/// }
+///
+/// assert!(matches!(read_code("nope(3)", ""), Err(Error::Registry(_))));
+/// assert!(matches!(read_code("repetition(3)", "3"), Err(Error::Alist(_))));
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    /// The codes of a CSS code are incompatible.
+    Css(CssError),
+    /// The stabilizers of a stabilizer code are invalid.
+    Stabilizer(StabilizerError),
+    /// A CSS code is inconsistent.
+    InconsistentCss(Vec<CssInconsistency>),
+    /// A linear code is inconsistent.
+    InconsistentLinearCode(Vec<LinearCodeInconsistency>),
+    /// A code specification can't be resolved.
+    Registry(RegistryError),
+    /// A random code can't be sampled.
+    Sampling(SamplingError),
+    /// The weight distributions of a code can't be computed.
+    WeightDistribution(WeightDistributionError),
+    /// An empirical noise model can't be built from its counts.
+    EmpiricalNoise(EmpiricalNoiseError),
+    /// A syndrome has the wrong length for a decoder.
+    SyndromeLength(SyndromeLengthError),
+    /// A decoder doesn't have the capabilities required by an experiment.
+    MissingCapabilities(MissingCapabilities),
+    /// The weights of a belief propagation decoder are invalid.
+    BpWeights(BpWeightsError),
+    /// Shards of an experiment can't be merged.
+    Merge(MergeError),
+    /// A matrix can't be read in the alist format.
+    Alist(AlistError),
+    /// A fingerprint can't be parsed.
+    Fingerprint(FingerprintParseError),
+    /// The integer program of a distance computation can't be solved.
+    #[cfg(feature = "ilp")]
+    Ilp(good_lp::ResolutionError),
+    /// The SAT solver of a distance computation failed.
+    #[cfg(feature = "sat")]
+    Sat(varisat::solver::SolverError),
+    /// A value can't be serialized or deserialized in JSON.
+    Json(serde_json::Error),
+    /// An input or output operation failed.
+    Io(io::Error),
+    /// An input is invalid for the given reason.
+    InvalidInput(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Css(error) => write!(f, "{}", error),
+            Self::Stabilizer(error) => write!(f, "{}", error),
+            Self::InconsistentCss(inconsistencies) => {
+                write!(f, "inconsistent css code: ")?;
+                write_all(f, inconsistencies)
+            }
+            Self::InconsistentLinearCode(inconsistencies) => {
+                write!(f, "inconsistent linear code: ")?;
+                write_all(f, inconsistencies)
+            }
+            Self::Registry(error) => write!(f, "{}", error),
+            Self::Sampling(error) => write!(f, "{}", error),
+            Self::WeightDistribution(error) => write!(f, "{}", error),
+            Self::EmpiricalNoise(error) => write!(f, "{}", error),
+            Self::SyndromeLength(error) => write!(f, "{}", error),
+            Self::MissingCapabilities(error) => write!(f, "{}", error),
+            Self::BpWeights(error) => write!(f, "{}", error),
+            Self::Merge(error) => write!(f, "{}", error),
+            Self::Alist(error) => write!(f, "{}", error),
+            Self::Fingerprint(error) => write!(f, "{}", error),
+            #[cfg(feature = "ilp")]
+            Self::Ilp(error) => write!(f, "{}", error),
+            #[cfg(feature = "sat")]
+            Self::Sat(error) => write!(f, "{}", error),
+            Self::Json(error) => write!(f, "{}", error),
+            Self::Io(error) => write!(f, "{}", error),
+            Self::InvalidInput(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+// Writes the items separated by commas.
+fn write_all<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Css(error) => Some(error),
+            Self::Stabilizer(error) => Some(error),
+            Self::Registry(error) => Some(error),
+            Self::Sampling(error) => Some(error),
+            Self::WeightDistribution(error) => Some(error),
+            Self::EmpiricalNoise(error) => Some(error),
+            Self::SyndromeLength(error) => Some(error),
+            Self::MissingCapabilities(error) => Some(error),
+            Self::BpWeights(error) => Some(error),
+            Self::Merge(error) => Some(error),
+            Self::Alist(error) => Some(error),
+            Self::Fingerprint(error) => Some(error),
+            #[cfg(feature = "ilp")]
+            Self::Ilp(error) => Some(error),
+            #[cfg(feature = "sat")]
+            Self::Sat(error) => Some(error),
+            Self::Json(error) => Some(error),
+            Self::Io(error) => Some(error),
+            Self::InconsistentCss(_)
+            | Self::InconsistentLinearCode(_)
+            | Self::InvalidInput(_) => None,
+        }
+    }
+}
+
+impl From<CssError> for Error {
+    fn from(error: CssError) -> Self {
+        Self::Css(error)
+    }
+}
+
+impl From<StabilizerError> for Error {
+    fn from(error: StabilizerError) -> Self {
+        Self::Stabilizer(error)
+    }
+}
+
+impl From<Vec<CssInconsistency>> for Error {
+    fn from(inconsistencies: Vec<CssInconsistency>) -> Self {
+        Self::InconsistentCss(inconsistencies)
+    }
+}
+
+impl From<Vec<LinearCodeInconsistency>> for Error {
+    fn from(inconsistencies: Vec<LinearCodeInconsistency>) -> Self {
+        Self::InconsistentLinearCode(inconsistencies)
+    }
+}
+
+impl From<RegistryError> for Error {
+    fn from(error: RegistryError) -> Self {
+        Self::Registry(error)
+    }
+}
+
+impl From<SamplingError> for Error {
+    fn from(error: SamplingError) -> Self {
+        Self::Sampling(error)
+    }
+}
+
+impl From<WeightDistributionError> for Error {
+    fn from(error: WeightDistributionError) -> Self {
+        Self::WeightDistribution(error)
+    }
+}
+
+impl From<EmpiricalNoiseError> for Error {
+    fn from(error: EmpiricalNoiseError) -> Self {
+        Self::EmpiricalNoise(error)
+    }
+}
+
+impl From<SyndromeLengthError> for Error {
+    fn from(error: SyndromeLengthError) -> Self {
+        Self::SyndromeLength(error)
+    }
+}
+
+impl From<MissingCapabilities> for Error {
+    fn from(error: MissingCapabilities) -> Self {
+        Self::MissingCapabilities(error)
+    }
+}
+
+impl From<BpWeightsError> for Error {
+    fn from(error: BpWeightsError) -> Self {
+        Self::BpWeights(error)
+    }
+}
+
+impl From<MergeError> for Error {
+    fn from(error: MergeError) -> Self {
+        Self::Merge(error)
+    }
+}
+
+impl From<AlistError> for Error {
+    fn from(error: AlistError) -> Self {
+        Self::Alist(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<FingerprintParseError> for Error {
+    fn from(error: FingerprintParseError) -> Self {
+        Self::Fingerprint(error)
+    }
+}
+
+#[cfg(feature = "ilp")]
+impl From<good_lp::ResolutionError> for Error {
+    fn from(error: good_lp::ResolutionError) -> Self {
+        Self::Ilp(error)
+    }
+}
+
+#[cfg(feature = "sat")]
+impl From<varisat::solver::SolverError> for Error {
+    fn from(error: varisat::solver::SolverError) -> Self {
+        Self::Sat(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::{CssCode, Fingerprint, LinearCode};
+    use std::error::Error as _;
+
+    #[test]
+    fn module_errors_are_sources() {
+        let error: Error =
+            CssCode::try_new(&LinearCode::repetition_code(3), &LinearCode::hamming_code())
+                .unwrap_err()
+                .into();
+        assert!(matches!(
+            error,
+            Error::Css(CssError::DifferentXandZLength(3, 7))
+        ));
+        assert_eq!(
+            error.to_string(),
+            CssError::DifferentXandZLength(3, 7).to_string()
+        );
+        assert!(error.source().is_some());
+        assert!(Error::InvalidInput("bad input".to_string())
+            .source()
+            .is_none());
+    }
+
+    #[test]
+    fn only_fingerprint_parse_errors_are_fingerprint_errors() {
+        let error: Error = "xyz".parse::<Fingerprint>().unwrap_err().into();
+        assert!(matches!(error, Error::Fingerprint(_)));
+        assert_eq!(error.to_string(), "invalid fingerprint: xyz");
+    }
+
+    #[test]
+    fn inconsistencies_are_listed() {
+        let mut code = CssCode::steane_code();
+        code.logicals.x = sparse_bin_mat::SparseBinMat::new(7, vec![vec![0, 1]]);
+        let error = Error::from(code.validate().unwrap_err());
+        assert!(error.to_string().starts_with("inconsistent css code: "));
+        assert!(error.to_string().contains(", "));
+    }
+}
@@ -0,0 +1,184 @@
+use super::{used_qubits, CouplingGraph};
+use crate::codes::CssCode;
+use crate::css::Css;
+use sparse_bin_mat::SparseBinMat;
+
+/// Which stabilizers of a CSS code can be measured with a single ancilla
+/// given a placement of the data qubits on a coupling graph.
+///
+/// A stabilizer is local if there is a physical qubit not used by the data qubits
+/// coupled to every qubit in its support.
+/// Such a qubit is a candidate ancilla for the stabilizer.
+/// Ancillas are not assigned exclusively, thus two stabilizers may share
+/// a candidate if they are measured at different times.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::{CssCode, LinearCode};
+/// use ldpc::hardware::{ConnectivityReport, CouplingGraph};
+///
+/// // The surface code of distance 3 with the data qubits on a 5x5 grid
+/// // following the coordinates of the code.
+/// let repetition_code = LinearCode::repetition_code(3);
+/// let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+/// let placement = code
+///     .coordinates()
+///     .unwrap()
+///     .qubits()
+///     .iter()
+///     .map(|(row, column)| 5 * row + column)
+///     .collect::<Vec<_>>();
+/// let report = ConnectivityReport::new(&code, &CouplingGraph::grid(5, 5), &placement);
+///
+/// assert!(report.is_local());
+/// assert_eq!(report.x_candidates(0), &[1]);
+///
+/// // The Steane code needs weight 4 stabilizers on a graph of degree 3.
+/// let code = CssCode::steane_code();
+/// let graph = CouplingGraph::heavy_hex(3, 9);
+/// let report = ConnectivityReport::new(&code, &graph, &[0, 1, 2, 3, 4, 5, 6]);
+///
+/// assert!(!report.is_local());
+/// assert_eq!(report.num_violations(), 6);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ConnectivityReport {
+    candidates: Css<Vec<Vec<usize>>>,
+}
+
+impl ConnectivityReport {
+    /// Analyzes the stabilizers of the code when the data qubit `i`
+    /// is placed on the physical qubit `placement[i]`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the placement length is different from the code length,
+    /// if a physical qubit is out of bound or if it is used twice.
+    pub fn new(code: &CssCode, graph: &CouplingGraph, placement: &[usize]) -> Self {
+        let is_used = used_qubits(code, graph, placement);
+        let candidates_for = |stabilizers: &SparseBinMat| {
+            stabilizers
+                .rows()
+                .map(|stabilizer| {
+                    (0..graph.num_qubits())
+                        .filter(|ancilla| !is_used[*ancilla])
+                        .filter(|ancilla| {
+                            stabilizer
+                                .non_trivial_positions()
+                                .all(|qubit| graph.are_coupled(*ancilla, placement[qubit]))
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        Self {
+            candidates: Css {
+                x: candidates_for(&code.stabilizers.x),
+                z: candidates_for(&code.stabilizers.z),
+            },
+        }
+    }
+
+    /// Returns the physical qubits that can measure the given X stabilizer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the stabilizer is out of bound.
+    pub fn x_candidates(&self, stabilizer: usize) -> &[usize] {
+        &self.candidates.x[stabilizer]
+    }
+
+    /// Returns the physical qubits that can measure the given Z stabilizer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the stabilizer is out of bound.
+    pub fn z_candidates(&self, stabilizer: usize) -> &[usize] {
+        &self.candidates.z[stabilizer]
+    }
+
+    /// Returns the X and Z stabilizers without candidate ancilla.
+    pub fn violations(&self) -> Css<Vec<usize>> {
+        self.candidates.map(|candidates| {
+            candidates
+                .iter()
+                .enumerate()
+                .filter(|(_, ancillas)| ancillas.is_empty())
+                .map(|(stabilizer, _)| stabilizer)
+                .collect()
+        })
+    }
+
+    /// Returns the number of stabilizers without candidate ancilla.
+    pub fn num_violations(&self) -> usize {
+        let violations = self.violations();
+        violations.x.len() + violations.z.len()
+    }
+
+    /// Checks if every stabilizer has at least one candidate ancilla.
+    pub fn is_local(&self) -> bool {
+        self.num_violations() == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+
+    #[test]
+    fn toric_code_is_not_local_on_open_grid() {
+        let code = CssCode::toric_code(3);
+        let placement = code
+            .coordinates()
+            .unwrap()
+            .qubits()
+            .iter()
+            .map(|(row, column)| 6 * row + column)
+            .collect::<Vec<_>>();
+        let report = ConnectivityReport::new(&code, &CouplingGraph::grid(6, 6), &placement);
+        // Only the stabilizers wrapping around the boundary are violated.
+        assert_eq!(
+            report.violations(),
+            Css {
+                x: vec![0, 1, 2, 5, 8],
+                z: vec![0, 3, 6, 7, 8],
+            }
+        );
+    }
+
+    #[test]
+    fn surface_code_ancillas_are_at_stabilizer_coordinates() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+        let layout = code.coordinates().unwrap();
+        let index = |(row, column): (usize, usize)| 5 * row + column;
+        let placement = layout
+            .qubits()
+            .iter()
+            .cloned()
+            .map(index)
+            .collect::<Vec<_>>();
+        let report = ConnectivityReport::new(&code, &CouplingGraph::grid(5, 5), &placement);
+        for stabilizer in 0..code.num_x_stabs() {
+            assert_eq!(
+                report.x_candidates(stabilizer),
+                &[index(layout.x_stabilizer(stabilizer).unwrap())]
+            );
+        }
+        for stabilizer in 0..code.num_z_stabs() {
+            assert_eq!(
+                report.z_candidates(stabilizer),
+                &[index(layout.z_stabilizer(stabilizer).unwrap())]
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn physical_qubits_cannot_be_used_twice() {
+        let code = CssCode::steane_code();
+        ConnectivityReport::new(&code, &CouplingGraph::grid(3, 3), &[0, 1, 2, 3, 4, 5, 5]);
+    }
+}
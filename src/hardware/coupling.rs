@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// The graph of the physical qubits of a device
+/// with an edge between each pair of qubits supporting a two-qubit gate.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::hardware::CouplingGraph;
+///
+/// let graph = CouplingGraph::new(4, vec![(0, 1), (1, 2), (2, 3), (3, 0)]);
+///
+/// assert_eq!(graph.num_qubits(), 4);
+/// assert_eq!(graph.num_edges(), 4);
+/// assert_eq!(graph.neighbors(0), &[1, 3]);
+/// assert!(graph.are_coupled(2, 3));
+/// assert!(!graph.are_coupled(0, 2));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CouplingGraph {
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl CouplingGraph {
+    /// Creates a graph with the given number of qubits and edges.
+    /// Repeated edges are merged.
+    ///
+    /// # Panic
+    ///
+    /// Panics if an edge is out of bound or couples a qubit with itself.
+    pub fn new(num_qubits: usize, edges: Vec<(usize, usize)>) -> Self {
+        let mut adjacency = vec![Vec::new(); num_qubits];
+        for (first, second) in edges {
+            if first >= num_qubits || second >= num_qubits {
+                panic!(
+                    "edge ({}, {}) is out of bound for {} qubits",
+                    first, second, num_qubits
+                );
+            }
+            if first == second {
+                panic!("qubit {} is coupled with itself", first);
+            }
+            adjacency[first].push(second);
+            adjacency[second].push(first);
+        }
+        for neighbors in adjacency.iter_mut() {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+        Self { adjacency }
+    }
+
+    /// Returns a square grid with the given number of rows and columns.
+    ///
+    /// The qubit in row `r` and column `c` is `r * num_columns + c`.
+    pub fn grid(num_rows: usize, num_columns: usize) -> Self {
+        let horizontal = (0..num_rows).flat_map(|row| {
+            (1..num_columns)
+                .map(move |column| (row * num_columns + column - 1, row * num_columns + column))
+        });
+        let vertical = (1..num_rows).flat_map(|row| {
+            (0..num_columns)
+                .map(move |column| ((row - 1) * num_columns + column, row * num_columns + column))
+        });
+        Self::new(num_rows * num_columns, horizontal.chain(vertical).collect())
+    }
+
+    /// Returns a heavy-hexagon lattice made of chains of qubits
+    /// linked by bridge qubits.
+    ///
+    /// The qubit at position `c` of chain `r` is `r * chain_length + c`.
+    /// The bridges between chains `r` and `r + 1` are at every fourth position,
+    /// starting at position 0 for even `r` and at position 2 for odd `r`.
+    /// They are numbered after the chain qubits, in order of chains and positions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::hardware::CouplingGraph;
+    ///
+    /// // Two hexagons of 12 qubits sharing 3 qubits.
+    /// let graph = CouplingGraph::heavy_hex(2, 9);
+    ///
+    /// assert_eq!(graph.num_qubits(), 21);
+    /// assert_eq!(graph.neighbors(18), &[0, 9]);
+    /// assert_eq!(graph.neighbors(4), &[3, 5, 19]);
+    /// ```
+    pub fn heavy_hex(num_chains: usize, chain_length: usize) -> Self {
+        let mut edges: Vec<(usize, usize)> = (0..num_chains)
+            .flat_map(|chain| {
+                (1..chain_length).map(move |position| {
+                    (
+                        chain * chain_length + position - 1,
+                        chain * chain_length + position,
+                    )
+                })
+            })
+            .collect();
+        let mut num_qubits = num_chains * chain_length;
+        for chain in 1..num_chains {
+            let offset = if chain % 2 == 1 { 0 } else { 2 };
+            for position in (offset..chain_length).step_by(4) {
+                edges.push(((chain - 1) * chain_length + position, num_qubits));
+                edges.push((chain * chain_length + position, num_qubits));
+                num_qubits += 1;
+            }
+        }
+        Self::new(num_qubits, edges)
+    }
+
+    /// Returns the number of physical qubits.
+    pub fn num_qubits(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Returns the number of couplings.
+    pub fn num_edges(&self) -> usize {
+        self.adjacency
+            .iter()
+            .map(|neighbors| neighbors.len())
+            .sum::<usize>()
+            / 2
+    }
+
+    /// Returns the sorted qubits coupled to the given qubit.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the qubit is out of bound.
+    pub fn neighbors(&self, qubit: usize) -> &[usize] {
+        &self.adjacency[qubit]
+    }
+
+    /// Returns the number of couplings on a shortest path from the given qubit
+    /// to each qubit or None for the qubits that can't be reached.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the qubit is out of bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::hardware::CouplingGraph;
+    ///
+    /// let graph = CouplingGraph::new(4, vec![(0, 1), (1, 2)]);
+    ///
+    /// assert_eq!(graph.distances_from(0), vec![Some(0), Some(1), Some(2), None]);
+    /// ```
+    pub fn distances_from(&self, qubit: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.num_qubits()];
+        distances[qubit] = Some(0);
+        let mut queue = VecDeque::from([qubit]);
+        while let Some(qubit) = queue.pop_front() {
+            let distance = distances[qubit].map(|distance| distance + 1);
+            for neighbor in self.neighbors(qubit) {
+                if distances[*neighbor].is_none() {
+                    distances[*neighbor] = distance;
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Checks if there is a coupling between both qubits.
+    pub fn are_coupled(&self, first: usize, second: usize) -> bool {
+        self.adjacency
+            .get(first)
+            .map(|neighbors| neighbors.binary_search(&second).is_ok())
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn grid_has_four_neighbors_in_the_bulk() {
+        let graph = CouplingGraph::grid(3, 4);
+        assert_eq!(graph.num_qubits(), 12);
+        assert_eq!(graph.num_edges(), 3 * 3 + 2 * 4);
+        assert_eq!(graph.neighbors(5), &[1, 4, 6, 9]);
+        assert_eq!(graph.neighbors(0), &[1, 4]);
+    }
+
+    #[test]
+    fn heavy_hex_qubits_have_degree_at_most_3() {
+        let graph = CouplingGraph::heavy_hex(5, 15);
+        assert!((0..graph.num_qubits()).all(|qubit| graph.neighbors(qubit).len() <= 3));
+        // Bridges have exactly 2 neighbors in adjacent chains.
+        for bridge in 75..graph.num_qubits() {
+            let neighbors = graph.neighbors(bridge);
+            assert_eq!(neighbors.len(), 2);
+            assert_eq!(neighbors[1] - neighbors[0], 15);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn self_loops_are_not_allowed() {
+        CouplingGraph::new(2, vec![(1, 1)]);
+    }
+}
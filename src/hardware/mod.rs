@@ -0,0 +1,48 @@
+//! Constraints of physical devices on the implementation of codes.
+//!
+//! A [`CouplingGraph`](CouplingGraph) describes which pairs of physical qubits
+//! can interact and the [`ConnectivityReport`](ConnectivityReport) tells which
+//! stabilizers of a code can be measured with a single ancilla on such a device.
+//! When they can't, the [`RoutingEstimate`](RoutingEstimate) evaluates
+//! the number of SWAPs needed to measure them anyway.
+//! A [`TailoredCode`](TailoredCode) goes the other way around
+//! and lays out a code for a family of devices
+//! with the schedule measuring each stabilizer.
+
+use crate::codes::CssCode;
+
+mod connectivity;
+pub use connectivity::ConnectivityReport;
+
+mod coupling;
+pub use coupling::CouplingGraph;
+
+mod routing;
+pub use routing::{CheckRouting, RoutingEstimate};
+
+mod tailored;
+pub use tailored::{CheckSchedule, TailoredCode};
+
+// Flags the physical qubits used by the data qubits of the code.
+fn used_qubits(code: &CssCode, graph: &CouplingGraph, placement: &[usize]) -> Vec<bool> {
+    if placement.len() != code.len() {
+        panic!(
+            "placement of length {} for {} qubits",
+            placement.len(),
+            code.len()
+        );
+    }
+    let mut is_used = vec![false; graph.num_qubits()];
+    for qubit in placement.iter() {
+        match is_used.get_mut(*qubit) {
+            None => panic!(
+                "physical qubit {} is out of bound for {} qubits",
+                qubit,
+                graph.num_qubits()
+            ),
+            Some(true) => panic!("physical qubit {} is used twice", qubit),
+            Some(used) => *used = true,
+        }
+    }
+    is_used
+}
@@ -0,0 +1,322 @@
+use super::{used_qubits, CouplingGraph};
+use crate::codes::CssCode;
+use crate::css::Css;
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice};
+use std::collections::VecDeque;
+
+/// An estimate of the routing overhead to measure each stabilizer of a CSS code
+/// given a placement of the data qubits on a coupling graph.
+///
+/// For each stabilizer, the ancilla is the free physical qubit minimizing
+/// the sum of the distances to the qubits in the support.
+/// Then, a Steiner tree connecting the ancilla to the support is built
+/// by repeatedly adding a shortest path to the closest remaining qubit.
+/// The ancilla is moved along this tree with SWAPs until it has been coupled
+/// to every qubit in the support.
+///
+/// This is a heuristic and the overhead of an optimal routing can be smaller.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::hardware::{CouplingGraph, RoutingEstimate};
+///
+/// // The Shor code on a line with a free qubit between each pair of data qubits.
+/// let code = CssCode::shor_code();
+/// let graph = CouplingGraph::new(17, (1..17).map(|qubit| (qubit - 1, qubit)).collect());
+/// let placement = (0..9).map(|qubit| 2 * qubit).collect::<Vec<_>>();
+/// let estimate = RoutingEstimate::new(&code, &graph, &placement);
+///
+/// // The weight 2 Z stabilizers are measured without SWAP.
+/// let check = estimate.z_check(0).unwrap();
+/// assert_eq!(check.ancilla(), 1);
+/// assert_eq!(check.num_swaps(), 0);
+/// assert_eq!(check.depth(), 2);
+///
+/// // The ancilla of a weight 6 X stabilizer needs to travel along the line.
+/// assert!(estimate.x_check(0).unwrap().num_swaps() > 0);
+/// assert!(estimate.is_routable());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoutingEstimate {
+    checks: Css<Vec<Option<CheckRouting>>>,
+}
+
+impl RoutingEstimate {
+    /// Estimates the routing of the stabilizers of the code when the data qubit `i`
+    /// is placed on the physical qubit `placement[i]`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the placement length is different from the code length,
+    /// if a physical qubit is out of bound or if it is used twice.
+    pub fn new(code: &CssCode, graph: &CouplingGraph, placement: &[usize]) -> Self {
+        let is_used = used_qubits(code, graph, placement);
+        let routings_for = |stabilizers: &SparseBinMat| {
+            stabilizers
+                .rows()
+                .map(|stabilizer| CheckRouting::new(stabilizer, graph, placement, &is_used))
+                .collect()
+        };
+        Self {
+            checks: Css {
+                x: routings_for(&code.stabilizers.x),
+                z: routings_for(&code.stabilizers.z),
+            },
+        }
+    }
+
+    /// Returns the routing of the given X stabilizer
+    /// or None if its support can't be reached from a free qubit.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the stabilizer is out of bound.
+    pub fn x_check(&self, stabilizer: usize) -> Option<&CheckRouting> {
+        self.checks.x[stabilizer].as_ref()
+    }
+
+    /// Returns the routing of the given Z stabilizer
+    /// or None if its support can't be reached from a free qubit.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the stabilizer is out of bound.
+    pub fn z_check(&self, stabilizer: usize) -> Option<&CheckRouting> {
+        self.checks.z[stabilizer].as_ref()
+    }
+
+    /// Checks if every stabilizer can be routed.
+    pub fn is_routable(&self) -> bool {
+        self.routings().all(|routing| routing.is_some())
+    }
+
+    /// Returns the total number of SWAPs to measure all the routable stabilizers once.
+    pub fn total_swaps(&self) -> usize {
+        self.routings()
+            .flatten()
+            .map(|routing| routing.num_swaps())
+            .sum()
+    }
+
+    /// Returns the largest depth of a routable stabilizer
+    /// or 0 if there is no such stabilizer.
+    pub fn max_depth(&self) -> usize {
+        self.routings()
+            .flatten()
+            .map(|routing| routing.depth())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn routings(&self) -> impl Iterator<Item = &Option<CheckRouting>> {
+        self.checks.x.iter().chain(self.checks.z.iter())
+    }
+}
+
+/// The estimated routing of a single stabilizer.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CheckRouting {
+    ancilla: usize,
+    num_swaps: usize,
+    weight: usize,
+}
+
+impl CheckRouting {
+    fn new(
+        stabilizer: SparseBinSlice,
+        graph: &CouplingGraph,
+        placement: &[usize],
+        is_used: &[bool],
+    ) -> Option<Self> {
+        let terminals = stabilizer
+            .non_trivial_positions()
+            .map(|qubit| placement[qubit])
+            .collect::<Vec<_>>();
+        let ancilla = closest_free_qubit(&terminals, graph, is_used)?;
+        let tree = SteinerTree::new(ancilla, &terminals, graph)?;
+        Some(Self {
+            ancilla,
+            num_swaps: tree.num_swaps(&terminals),
+            weight: terminals.len(),
+        })
+    }
+
+    /// Returns the physical qubit used as ancilla.
+    pub fn ancilla(&self) -> usize {
+        self.ancilla
+    }
+
+    /// Returns the number of SWAPs moving the ancilla.
+    pub fn num_swaps(&self) -> usize {
+        self.num_swaps
+    }
+
+    /// Returns the number of two-qubit gates applied one after the other
+    /// to measure the stabilizer, counting each SWAP as 3 CNOTs.
+    pub fn depth(&self) -> usize {
+        self.weight + 3 * self.num_swaps
+    }
+}
+
+fn closest_free_qubit(
+    terminals: &[usize],
+    graph: &CouplingGraph,
+    is_used: &[bool],
+) -> Option<usize> {
+    let distances = terminals
+        .iter()
+        .map(|terminal| graph.distances_from(*terminal))
+        .collect::<Vec<_>>();
+    (0..graph.num_qubits())
+        .filter(|qubit| !is_used[*qubit])
+        .filter_map(|qubit| {
+            distances
+                .iter()
+                .map(|distances| distances[qubit])
+                .sum::<Option<usize>>()
+                .map(|total| (total, qubit))
+        })
+        .min()
+        .map(|(_, qubit)| qubit)
+}
+
+// A tree rooted at the ancilla where each node knows its parent and depth.
+struct SteinerTree {
+    parents: Vec<Option<usize>>,
+    depths: Vec<Option<usize>>,
+}
+
+impl SteinerTree {
+    fn new(root: usize, terminals: &[usize], graph: &CouplingGraph) -> Option<Self> {
+        let mut tree = Self {
+            parents: vec![None; graph.num_qubits()],
+            depths: vec![None; graph.num_qubits()],
+        };
+        tree.depths[root] = Some(0);
+        while let Some(path) = tree.path_to_closest_terminal(terminals, graph) {
+            for pair in path.windows(2) {
+                tree.parents[pair[1]] = Some(pair[0]);
+                tree.depths[pair[1]] = tree.depths[pair[0]].map(|depth| depth + 1);
+            }
+        }
+        if terminals.iter().all(|terminal| tree.contains(*terminal)) {
+            Some(tree)
+        } else {
+            None
+        }
+    }
+
+    fn contains(&self, qubit: usize) -> bool {
+        self.depths[qubit].is_some()
+    }
+
+    // Returns the path from the tree to the closest terminal outside the tree.
+    fn path_to_closest_terminal(
+        &self,
+        terminals: &[usize],
+        graph: &CouplingGraph,
+    ) -> Option<Vec<usize>> {
+        let mut previous = vec![None; graph.num_qubits()];
+        let mut is_visited = self.depths.iter().map(Option::is_some).collect::<Vec<_>>();
+        let mut queue = (0..graph.num_qubits())
+            .filter(|qubit| self.contains(*qubit))
+            .collect::<VecDeque<_>>();
+        while let Some(qubit) = queue.pop_front() {
+            if !self.contains(qubit) && terminals.contains(&qubit) {
+                let mut path = vec![qubit];
+                while let Some(qubit) = previous[*path.last().unwrap()] {
+                    path.push(qubit);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for neighbor in graph.neighbors(qubit) {
+                if !is_visited[*neighbor] {
+                    is_visited[*neighbor] = true;
+                    previous[*neighbor] = Some(qubit);
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+        None
+    }
+
+    // The ancilla must visit the parent of each terminal.
+    // Visiting all of them and stopping at the deepest one
+    // costs twice the edges leading to them minus the largest depth.
+    fn num_swaps(&self, terminals: &[usize]) -> usize {
+        let mut is_on_route = vec![false; self.parents.len()];
+        let mut max_depth = 0;
+        for terminal in terminals {
+            let mut node = self.parents[*terminal];
+            if let Some(parent) = node {
+                max_depth = max_depth.max(self.depths[parent].unwrap_or(0));
+            }
+            while let Some(qubit) = node {
+                if is_on_route[qubit] {
+                    break;
+                }
+                is_on_route[qubit] = true;
+                node = self.parents[qubit];
+            }
+        }
+        // The root has no parent edge.
+        let num_edges = is_on_route
+            .iter()
+            .zip(self.parents.iter())
+            .filter(|(is_on_route, parent)| **is_on_route && parent.is_some())
+            .count();
+        2 * num_edges - max_depth
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+
+    #[test]
+    fn surface_code_on_grid_needs_no_swap() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+        let layout = code.coordinates().unwrap();
+        let index = |(row, column): &(usize, usize)| 5 * row + column;
+        let placement = layout.qubits().iter().map(index).collect::<Vec<_>>();
+        let estimate = RoutingEstimate::new(&code, &CouplingGraph::grid(5, 5), &placement);
+        assert!(estimate.is_routable());
+        assert_eq!(estimate.total_swaps(), 0);
+        assert_eq!(estimate.max_depth(), 4);
+        for stabilizer in 0..code.num_x_stabs() {
+            assert_eq!(
+                estimate.x_check(stabilizer).unwrap().ancilla(),
+                index(&layout.x_stabilizer(stabilizer).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn ancilla_walks_along_a_line() {
+        // A weight 2 check with both qubits on the same side of the only free qubit.
+        let code = CssCode::new(
+            &LinearCode::from_parity_check_matrix(SparseBinMat::new(2, vec![vec![0, 1]])),
+            &LinearCode::from_parity_check_matrix(SparseBinMat::new(2, vec![vec![0, 1]])),
+        );
+        let graph = CouplingGraph::new(3, vec![(0, 1), (1, 2)]);
+        let estimate = RoutingEstimate::new(&code, &graph, &[1, 2]);
+        let check = estimate.x_check(0).unwrap();
+        assert_eq!(check.ancilla(), 0);
+        assert_eq!(check.num_swaps(), 1);
+        assert_eq!(check.depth(), 5);
+    }
+
+    #[test]
+    fn disconnected_support_is_not_routable() {
+        let code = CssCode::steane_code();
+        let graph = CouplingGraph::new(8, (1..7).map(|qubit| (qubit - 1, qubit)).collect());
+        let estimate = RoutingEstimate::new(&code, &graph, &[0, 1, 2, 3, 4, 5, 6]);
+        assert!(!estimate.is_routable());
+        assert_eq!(estimate.x_check(0), None);
+    }
+}
@@ -0,0 +1,393 @@
+use super::CouplingGraph;
+use crate::codes::css::rotated_surface_corners;
+use crate::codes::CssCode;
+use crate::css::Css;
+use serde::{Deserialize, Serialize};
+
+/// The CNOTs measuring a stabilizer on a device.
+///
+/// The CNOTs are (control, target) pairs of physical qubits grouped in time steps.
+/// An ancilla controlling its first CNOT is prepared and measured in the X basis
+/// and the other ancillas in the Z basis.
+/// The outcome of the stabilizer is the product of the outcomes of the measured qubits
+/// and the flags have a trivial outcome unless a fault occurred.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CheckSchedule {
+    measured: Vec<usize>,
+    flags: Vec<usize>,
+    steps: Vec<Vec<(usize, usize)>>,
+}
+
+impl CheckSchedule {
+    /// Returns the physical qubits whose outcomes give the stabilizer.
+    pub fn measured(&self) -> &[usize] {
+        &self.measured
+    }
+
+    /// Returns the physical qubits flagging faults spreading to the data.
+    pub fn flags(&self) -> &[usize] {
+        &self.flags
+    }
+
+    /// Returns the CNOTs of each time step.
+    pub fn steps(&self) -> &[Vec<(usize, usize)>] {
+        &self.steps
+    }
+
+    /// Returns the number of time steps.
+    pub fn depth(&self) -> usize {
+        self.steps.len()
+    }
+}
+
+/// A CSS code tailored to a device with the placement of its data qubits
+/// and the schedule measuring each stabilizer.
+///
+/// Physical qubits are positioned on the grid of the [coordinates](CssCode::coordinates)
+/// of the code such that the data qubit `i` is at the position of the qubit `i`
+/// in the layout of the code.
+/// All the stabilizers of the same type have schedules of the same depth
+/// with each CNOT coupling a pair of qubits of the device
+/// and no qubit used twice in a time step,
+/// thus they are measured in parallel.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::hardware::TailoredCode;
+///
+/// let tailored = TailoredCode::heavy_hex_code(5);
+///
+/// assert_eq!(tailored.code(), &CssCode::rotated_surface_code(5));
+/// for schedule in tailored.x_schedules().iter().chain(tailored.z_schedules()) {
+///     for (control, target) in schedule.steps().iter().flatten() {
+///         assert!(tailored.graph().are_coupled(*control, *target));
+///     }
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TailoredCode {
+    code: CssCode,
+    graph: CouplingGraph,
+    positions: Vec<(usize, usize)>,
+    placement: Vec<usize>,
+    schedules: Css<Vec<CheckSchedule>>,
+}
+
+impl TailoredCode {
+    /// Returns the [rotated surface code](CssCode::rotated_surface_code)
+    /// with the given distance on a square grid of degree 4.
+    ///
+    /// The data qubits are the physical qubits 0 to `distance * distance - 1`
+    /// followed by an ancilla for each X stabilizer and then each Z stabilizer,
+    /// each at the position of its stabilizer.
+    /// Each ancilla is coupled to the qubits of its stabilizer.
+    ///
+    /// The schedules have 4 steps visiting the corners of each face
+    /// in the order north west, south west, north east and south east for the X stabilizers
+    /// and north west, north east, south west and south east for the Z stabilizers.
+    /// Hence, the errors spreading from an ancilla to two data qubits are perpendicular
+    /// to the logical operators of the same type and don't reduce the distance.
+    /// Missing corners on the boundaries leave empty steps.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the distance is less than 2.
+    pub fn rotated_surface_code(distance: usize) -> Self {
+        let code = CssCode::rotated_surface_code(distance);
+        let layout = code.coordinates().unwrap();
+        let stabilizers = layout.stabilizers();
+        let positions: Vec<_> = layout
+            .qubits()
+            .iter()
+            .chain(stabilizers.x.iter())
+            .chain(stabilizers.z.iter())
+            .cloned()
+            .collect();
+        let schedules_for = |faces: &[(usize, usize)], first_ancilla: usize, is_x: bool| {
+            let order = if is_x { [0, 2, 1, 3] } else { [0, 1, 2, 3] };
+            faces
+                .iter()
+                .enumerate()
+                .map(|(stabilizer, position)| {
+                    let ancilla = first_ancilla + stabilizer;
+                    let corners = rotated_surface_corners(distance, face_of(*position));
+                    let steps = order
+                        .iter()
+                        .map(|corner| {
+                            corners[*corner]
+                                .map(|qubit| {
+                                    if is_x {
+                                        (ancilla, qubit)
+                                    } else {
+                                        (qubit, ancilla)
+                                    }
+                                })
+                                .into_iter()
+                                .collect()
+                        })
+                        .collect();
+                    CheckSchedule {
+                        measured: vec![ancilla],
+                        flags: Vec::new(),
+                        steps,
+                    }
+                })
+                .collect()
+        };
+        let schedules = Css {
+            x: schedules_for(stabilizers.x, code.len(), true),
+            z: schedules_for(stabilizers.z, code.len() + stabilizers.x.len(), false),
+        };
+        let graph = CouplingGraph::new(positions.len(), edges_of(&schedules));
+        let placement = (0..code.len()).collect();
+        Self {
+            code,
+            graph,
+            positions,
+            placement,
+            schedules,
+        }
+    }
+
+    /// Returns the [rotated surface code](CssCode::rotated_surface_code)
+    /// with the given distance on a [heavy-hexagon lattice](CouplingGraph::heavy_hex)
+    /// of degree 3.
+    ///
+    /// There is a chain of `2 * distance + 1` physical qubits for each row of data qubits,
+    /// alternating between flags and data qubits.
+    /// The qubit of chain `r` at position `p` is at position `(2r + 1, p)`
+    /// and each bridge is at the position of an X stabilizer.
+    ///
+    /// An X stabilizer is measured with its bridge and the two flags coupled to it
+    /// in 6 steps: the bridge entangles with both flags,
+    /// the flags apply CNOTs to the west and then to the east qubits of the face
+    /// and the bridge disentangles from the flags.
+    /// A Z stabilizer is measured in 2 steps as the product of the weight 2 operators
+    /// on the north and south qubits of its face, each collected by a flag.
+    ///
+    /// The flags of an X stabilizer are also used by Z stabilizers,
+    /// thus the X and Z stabilizers must be measured in alternating rounds.
+    /// Since the weight 2 Z operators don't commute with the X stabilizers,
+    /// only the products of the X stabilizers along each row of faces,
+    /// supported on two full rows of qubits, are deterministic between rounds,
+    /// as in the heavy-hexagon code of Chamberland et al.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the distance is less than 2.
+    pub fn heavy_hex_code(distance: usize) -> Self {
+        let code = CssCode::rotated_surface_code(distance);
+        let layout = code.coordinates().unwrap();
+        let stabilizers = layout.stabilizers();
+        let chain_length = 2 * distance + 1;
+        let num_chain_qubits = distance * chain_length;
+        let positions: Vec<_> = (0..num_chain_qubits)
+            .map(|qubit| (2 * (qubit / chain_length) + 1, qubit % chain_length))
+            .chain(stabilizers.x.iter().cloned())
+            .collect();
+        let placement: Vec<_> = (0..code.len())
+            .map(|qubit| (qubit / distance) * chain_length + 2 * (qubit % distance) + 1)
+            .collect();
+        // The flag at position 2b of the given chain or None if the chain doesn't exist.
+        let flag = |chain: Option<usize>, b: usize| {
+            chain
+                .filter(|chain| *chain < distance)
+                .map(|chain| chain * chain_length + 2 * b)
+        };
+        // The physical qubits of the corners of a face and its north and south flags.
+        let corners_and_flags = |position: (usize, usize)| {
+            let (a, b) = face_of(position);
+            let corners = rotated_surface_corners(distance, (a, b))
+                .map(|corner| corner.map(|qubit| placement[qubit]));
+            (corners, [flag(a.checked_sub(1), b), flag(Some(a), b)])
+        };
+        let x_schedules = stabilizers
+            .x
+            .iter()
+            .enumerate()
+            .map(|(stabilizer, position)| {
+                let bridge = num_chain_qubits + stabilizer;
+                let (corners, flags) = corners_and_flags(*position);
+                let [north, south] = flags.map(|flag| flag.unwrap());
+                let spread = |north_corner: Option<usize>, south_corner: Option<usize>| {
+                    north_corner
+                        .map(|qubit| (north, qubit))
+                        .into_iter()
+                        .chain(south_corner.map(|qubit| (south, qubit)))
+                        .collect()
+                };
+                CheckSchedule {
+                    measured: vec![bridge],
+                    flags: vec![north, south],
+                    steps: vec![
+                        vec![(bridge, north)],
+                        vec![(bridge, south)],
+                        spread(corners[0], corners[2]),
+                        spread(corners[1], corners[3]),
+                        vec![(bridge, south)],
+                        vec![(bridge, north)],
+                    ],
+                }
+            })
+            .collect();
+        let z_schedules = stabilizers
+            .z
+            .iter()
+            .map(|position| {
+                let (corners, [north, south]) = corners_and_flags(*position);
+                let collect = |north_corner: Option<usize>, south_corner: Option<usize>| {
+                    north_corner
+                        .zip(north)
+                        .into_iter()
+                        .chain(south_corner.zip(south))
+                        .collect()
+                };
+                CheckSchedule {
+                    measured: north.into_iter().chain(south).collect(),
+                    flags: Vec::new(),
+                    steps: vec![
+                        collect(corners[0], corners[2]),
+                        collect(corners[1], corners[3]),
+                    ],
+                }
+            })
+            .collect();
+        Self {
+            code,
+            graph: CouplingGraph::heavy_hex(distance, chain_length),
+            positions,
+            placement,
+            schedules: Css {
+                x: x_schedules,
+                z: z_schedules,
+            },
+        }
+    }
+
+    /// Returns the code with its coordinates.
+    pub fn code(&self) -> &CssCode {
+        &self.code
+    }
+
+    /// Returns the coupling graph of the device.
+    pub fn graph(&self) -> &CouplingGraph {
+        &self.graph
+    }
+
+    /// Returns the position of each physical qubit.
+    pub fn positions(&self) -> &[(usize, usize)] {
+        &self.positions
+    }
+
+    /// Returns the physical qubit of each data qubit.
+    pub fn placement(&self) -> &[usize] {
+        &self.placement
+    }
+
+    /// Returns the schedule of each X stabilizer.
+    pub fn x_schedules(&self) -> &[CheckSchedule] {
+        &self.schedules.x
+    }
+
+    /// Returns the schedule of each Z stabilizer.
+    pub fn z_schedules(&self) -> &[CheckSchedule] {
+        &self.schedules.z
+    }
+}
+
+// The face (a, b) of the rotated surface code at the given position of its layout.
+fn face_of((row, column): (usize, usize)) -> (usize, usize) {
+    (row / 2, column / 2)
+}
+
+fn edges_of(schedules: &Css<Vec<CheckSchedule>>) -> Vec<(usize, usize)> {
+    schedules
+        .x
+        .iter()
+        .chain(schedules.z.iter())
+        .flat_map(|schedule| schedule.steps.iter().flatten().cloned())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hardware::ConnectivityReport;
+    use std::collections::HashSet;
+
+    // Checks that the CNOTs are on the device, that each step uses a qubit at most once
+    // and that the data qubits of each schedule are the support of its stabilizer.
+    fn assert_schedules_are_valid(tailored: &TailoredCode) {
+        let data: Vec<_> = (0..tailored.graph().num_qubits())
+            .map(|physical| tailored.placement().iter().position(|q| *q == physical))
+            .collect();
+        let stabilizers = Css {
+            x: tailored.code().x_stabs_binary(),
+            z: tailored.code().z_stabs_binary(),
+        };
+        let pairs = tailored.schedules.as_ref().pair(stabilizers);
+        for (schedules, matrix) in [pairs.x, pairs.z] {
+            assert_eq!(schedules.len(), matrix.number_of_rows());
+            for (schedule, stabilizer) in schedules.iter().zip(matrix.rows()) {
+                assert_eq!(schedule.depth(), schedules[0].depth());
+                let mut support = HashSet::new();
+                for step in schedule.steps() {
+                    let mut used = HashSet::new();
+                    for (control, target) in step.iter() {
+                        assert!(tailored.graph().are_coupled(*control, *target));
+                        assert!(used.insert(*control) && used.insert(*target));
+                        support.extend(data[*control].into_iter().chain(data[*target]));
+                    }
+                }
+                let mut support: Vec<_> = support.into_iter().collect();
+                support.sort_unstable();
+                assert_eq!(
+                    support,
+                    stabilizer.non_trivial_positions().collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn square_grid_measures_every_stabilizer_locally() {
+        let tailored = TailoredCode::rotated_surface_code(5);
+        assert_schedules_are_valid(&tailored);
+        assert_eq!(tailored.graph().num_qubits(), 49);
+        assert!((0..49).all(|qubit| tailored.graph().neighbors(qubit).len() <= 4));
+        assert!(tailored.x_schedules().iter().all(|s| s.depth() == 4));
+        let report =
+            ConnectivityReport::new(tailored.code(), tailored.graph(), tailored.placement());
+        assert!(report.is_local());
+    }
+
+    #[test]
+    fn heavy_hex_code_has_degree_at_most_3() {
+        for distance in [2, 3, 5] {
+            let tailored = TailoredCode::heavy_hex_code(distance);
+            assert_schedules_are_valid(&tailored);
+            let graph = tailored.graph();
+            assert_eq!(tailored.positions().len(), graph.num_qubits());
+            assert!((0..graph.num_qubits()).all(|qubit| graph.neighbors(qubit).len() <= 3));
+            for (qubit, physical) in tailored.placement().iter().enumerate() {
+                assert_eq!(
+                    tailored.positions()[*physical],
+                    tailored.code().coordinates().unwrap().qubit(qubit).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn heavy_hex_z_stabilizers_are_products_of_flag_outcomes() {
+        let tailored = TailoredCode::heavy_hex_code(3);
+        let weights: Vec<_> = tailored
+            .z_schedules()
+            .iter()
+            .map(|schedule| schedule.measured().len())
+            .collect();
+        assert_eq!(weights, vec![1, 2, 2, 1]);
+    }
+}
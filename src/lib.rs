@@ -1,16 +1,42 @@
 //! A toolbox for classical and quantum LDPC codes.
 //!
-//! The crate is divided into three modules.
+//! The crate is divided into several modules.
 //!
-//! The [classical module](classical) contains a [linear code](classical::LinearCode)
-//! implementation and some decoders for it.
+//! The [codes module](codes) contains the classical [linear codes](codes::LinearCode)
+//! and the quantum [CSS codes](codes::CssCode).
 //!
-//! For now, the [quantum module](quantum) contains only a [CSS code](quantum::CssCode)
-//! implementation.
+//! The [decoders module](decoders) contains decoders for both kinds of codes.
 //!
-//! Finally, the [noise model module](noise_model) contains a generic trait for noise generation.
+//! The [noise module](noise) contains a generic trait for noise generation
+//! and many noise models.
+//!
+//! The [circuits module](circuits) generates the circuits measuring
+//! the stabilizers of codes.
+//!
+//! The [hardware module](hardware) checks codes against the connectivity
+//! of physical devices.
+//!
+//! The [simulation module](simulation) contains Monte Carlo experiments
+//! combining codes, noise models and decoders.
+//!
+//! The [non-binary module](nonbinary) contains codes and decoders
+//! over finite fields larger than GF(2).
+//!
+//! Finally, the [visualization module](visualization) draws small codes
+//! with errors and syndromes.
+//!
+//! The errors of all modules convert into the crate [`Error`](Error)
+//! to be propagated together as a [`Result`](Result).
 
+pub mod circuits;
 pub mod codes;
 pub mod css;
 pub mod decoders;
+pub mod hardware;
 pub mod noise;
+pub mod nonbinary;
+pub mod simulation;
+pub mod visualization;
+
+mod error;
+pub use error::{Error, Result};
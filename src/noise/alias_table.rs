@@ -0,0 +1,79 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// A table to sample from a discrete distribution in constant time
+// using Vose's alias method.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(super) struct AliasTable {
+    thresholds: Vec<f64>,
+    aliases: Vec<usize>,
+}
+
+impl AliasTable {
+    // The weights don't need to be normalized, but their sum must be positive.
+    pub(super) fn new(weights: &[f64]) -> Self {
+        let total: f64 = weights.iter().sum();
+        let num_outcomes = weights.len();
+        let mut thresholds: Vec<f64> = weights
+            .iter()
+            .map(|weight| weight * num_outcomes as f64 / total)
+            .collect();
+        let mut aliases: Vec<usize> = (0..num_outcomes).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..num_outcomes).partition(|outcome| thresholds[*outcome] < 1.0);
+        while let (Some(lower), Some(upper)) = (small.pop(), large.pop()) {
+            aliases[lower] = upper;
+            thresholds[upper] -= 1.0 - thresholds[lower];
+            if thresholds[upper] < 1.0 {
+                small.push(upper);
+            } else {
+                large.push(upper);
+            }
+        }
+        // Remaining outcomes are only left because of rounding errors.
+        for outcome in small.into_iter().chain(large) {
+            thresholds[outcome] = 1.0;
+        }
+        Self {
+            thresholds,
+            aliases,
+        }
+    }
+
+    pub(super) fn sample<R: Rng>(&self, rng: &mut R) -> usize {
+        let outcome = rng.gen_range(0..self.thresholds.len());
+        if rng.gen::<f64>() < self.thresholds[outcome] {
+            outcome
+        } else {
+            self.aliases[outcome]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn deterministic_distribution() {
+        let table = AliasTable::new(&[0.0, 0.0, 1.0, 0.0]);
+        let mut rng = StdRng::seed_from_u64(123);
+        assert!((0..100).all(|_| table.sample(&mut rng) == 2));
+    }
+
+    #[test]
+    fn frequencies_match_weights() {
+        let weights = [0.1, 0.2, 0.3, 0.4];
+        let table = AliasTable::new(&weights);
+        let mut rng = StdRng::seed_from_u64(123);
+        let mut counts = [0; 4];
+        for _ in 0..100_000 {
+            counts[table.sample(&mut rng)] += 1;
+        }
+        for (count, weight) in counts.iter().zip(weights.iter()) {
+            assert!((*count as f64 / 100_000.0 - weight).abs() < 0.01);
+        }
+    }
+}
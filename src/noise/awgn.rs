@@ -0,0 +1,230 @@
+use super::{BinarySymmetricChannel, ExpectedWeight, NoiseModel, Probability};
+use crate::decoders::FixedPointFormat;
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinVec;
+use std::fmt;
+
+/// An additive white Gaussian noise channel with BPSK modulation.
+///
+/// Each bit is sent as +1 if it is 0 and -1 if it is 1
+/// and the channel adds Gaussian noise of the given standard deviation.
+/// As for the other noise models, the all-zero codeword is sent
+/// such that the outputs describe the error.
+///
+/// The [outputs](AwgnChannel::sample_outputs) of a single noise realization
+/// can be turned into hard decisions, exact log-likelihood ratios
+/// or quantized log-likelihood ratios to study the loss of quantization.
+/// As a noise model, the channel returns the hard decisions,
+/// which follow a [binary symmetric channel](AwgnChannel::hard_decision_channel).
+///
+/// # Example
+///
+/// ```
+/// use ldpc::decoders::FixedPointFormat;
+/// use ldpc::noise::AwgnChannel;
+/// use rand::thread_rng;
+///
+/// let channel = AwgnChannel::with_standard_deviation(0.8);
+/// let outputs = channel.sample_outputs(100, &mut thread_rng());
+///
+/// let hard = outputs.hard_decisions();
+/// let soft = outputs.llrs();
+/// let quantized = outputs.quantized_llrs(FixedPointFormat::new(2, 0));
+/// for bit in 0..100 {
+///     assert_eq!(hard.is_one_at(bit), Some(soft[bit] < 0.0));
+///     assert!(quantized[bit] >= -4.0 && quantized[bit] <= 3.0);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AwgnChannel {
+    standard_deviation: f64,
+}
+
+impl AwgnChannel {
+    /// Creates a channel adding Gaussian noise of the given standard deviation.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the standard deviation is not positive and finite.
+    pub fn with_standard_deviation(standard_deviation: f64) -> Self {
+        if !(standard_deviation > 0.0 && standard_deviation.is_finite()) {
+            panic!("invalid standard deviation {}", standard_deviation);
+        }
+        Self { standard_deviation }
+    }
+
+    /// Creates a channel from the signal-to-noise ratio `Eb/N0` in decibels
+    /// for a code of the given rate.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the rate is not positive.
+    pub fn from_snr_db(snr_db: f64, rate: f64) -> Self {
+        if rate <= 0.0 {
+            panic!("invalid rate {}", rate);
+        }
+        let snr = 10.0_f64.powf(snr_db / 10.0);
+        Self::with_standard_deviation((2.0 * rate * snr).recip().sqrt())
+    }
+
+    /// Returns the standard deviation of the noise.
+    pub fn standard_deviation(&self) -> f64 {
+        self.standard_deviation
+    }
+
+    /// Returns the probability that the hard decision of a bit is wrong,
+    /// that is the probability that the noise is smaller than -1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::noise::AwgnChannel;
+    ///
+    /// let channel = AwgnChannel::with_standard_deviation(1.0);
+    /// assert!((channel.hard_decision_probability().value() - 0.158655).abs() < 1e-6);
+    /// ```
+    pub fn hard_decision_probability(&self) -> Probability {
+        Probability::new(0.5 * erfc(1.0 / (self.standard_deviation * std::f64::consts::SQRT_2)))
+    }
+
+    /// Returns the binary symmetric channel followed by the hard decisions.
+    pub fn hard_decision_channel(&self) -> BinarySymmetricChannel {
+        BinarySymmetricChannel::with_probability(self.hard_decision_probability())
+    }
+
+    /// Returns the outputs of the channel for the given number of bits.
+    pub fn sample_outputs<R: Rng>(&self, length: usize, rng: &mut R) -> AwgnOutputs {
+        let normal = Normal::new(1.0, self.standard_deviation).unwrap();
+        AwgnOutputs {
+            values: (0..length).map(|_| normal.sample(rng)).collect(),
+            standard_deviation: self.standard_deviation,
+        }
+    }
+}
+
+impl NoiseModel for AwgnChannel {
+    type Error = SparseBinVec;
+
+    fn sample_error_of_length<R: Rng>(&self, length: usize, rng: &mut R) -> Self::Error {
+        self.sample_outputs(length, rng).hard_decisions()
+    }
+}
+
+impl ExpectedWeight for AwgnChannel {
+    fn expected_weight(&self, length: usize) -> f64 {
+        self.hard_decision_probability().value() * length as f64
+    }
+}
+
+impl fmt::Display for AwgnChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AWGN({})", self.standard_deviation)
+    }
+}
+
+/// The outputs of an [`AwgnChannel`] for a block of bits.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AwgnOutputs {
+    values: Vec<f64>,
+    standard_deviation: f64,
+}
+
+impl AwgnOutputs {
+    /// Returns the received value of each bit.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks if there is no bit.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the bits whose received value is negative.
+    pub fn hard_decisions(&self) -> SparseBinVec {
+        let positions = self
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value < 0.0)
+            .map(|(bit, _)| bit)
+            .collect();
+        SparseBinVec::new(self.len(), positions)
+    }
+
+    /// Returns the log-likelihood ratio `ln(P(0) / P(1))` of each bit,
+    /// that is `2 y / σ²` for the received value `y`.
+    pub fn llrs(&self) -> Vec<f64> {
+        let scale = 2.0 / self.standard_deviation.powi(2);
+        self.values.iter().map(|value| scale * value).collect()
+    }
+
+    /// Returns the log-likelihood ratios rounded to the given fixed-point format.
+    pub fn quantized_llrs(&self, format: FixedPointFormat) -> Vec<f64> {
+        self.llrs()
+            .into_iter()
+            .map(|llr| format.quantize(llr))
+            .collect()
+    }
+}
+
+// The complementary error function with a fractional error below 1.2e-7
+// from the Chebyshev fit of Numerical Recipes.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let coefficients = [
+        -1.26551223,
+        1.00002368,
+        0.37409196,
+        0.09678418,
+        -0.18628806,
+        0.27886807,
+        -1.13520398,
+        1.48851587,
+        -0.82215223,
+        0.17087277,
+    ];
+    let polynomial = coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |sum, coefficient| sum * t + coefficient);
+    let value = t * (-z * z + polynomial).exp();
+    if x >= 0.0 {
+        value
+    } else {
+        2.0 - value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn hard_decisions_follow_the_equivalent_channel() {
+        let channel = AwgnChannel::from_snr_db(2.0, 0.5);
+        let mut rng = StdRng::seed_from_u64(3);
+        let num_flips = (0..100)
+            .map(|_| channel.sample_error_of_length(1000, &mut rng).weight())
+            .sum::<usize>();
+        let expected = channel.hard_decision_probability().value();
+        assert!((num_flips as f64 / 100000.0 - expected).abs() < 0.005);
+    }
+
+    #[test]
+    fn erfc_matches_known_values() {
+        assert!((erfc(0.0) - 1.0).abs() < 1e-7);
+        assert!((erfc(1.0) - 0.157299207).abs() < 1e-7);
+        assert!((erfc(-1.0) - 1.842700793).abs() < 1e-7);
+    }
+}
@@ -1,9 +1,11 @@
-use super::{NoiseModel, Probability};
-use sparse_bin_mat::SparseBinVec;
-use itertools::Itertools;
-use rand::distributions::{Bernoulli, Distribution};
+use super::{
+    sample_positions, sample_positions_into, DriftingNoise, ExpectedWeight, NoiseModel,
+    Probability, SamplingStrategy,
+};
+use rand::distributions::Bernoulli;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinVec;
 use std::fmt;
 
 /// A binary symmetric channel flips at bit with
@@ -15,6 +17,8 @@ use std::fmt;
 pub struct BinarySymmetricChannel {
     distribution: Bernoulli,
     probability: f64,
+    #[serde(default)]
+    strategy: SamplingStrategy,
 }
 
 impl BinarySymmetricChannel {
@@ -24,23 +28,63 @@ impl BinarySymmetricChannel {
             .map(|distribution| Self {
                 distribution,
                 probability: probability.value(),
+                strategy: SamplingStrategy::default(),
             })
             .unwrap()
     }
+
+    /// Sets the strategy used to sample the errors.
+    ///
+    /// Default is [`SamplingStrategy::PerPosition`](SamplingStrategy::PerPosition).
+    pub fn with_sampling_strategy(mut self, strategy: SamplingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
 }
 
 impl NoiseModel for BinarySymmetricChannel {
     type Error = SparseBinVec;
 
     fn sample_error_of_length<R: Rng>(&self, block_size: usize, rng: &mut R) -> Self::Error {
-        let positions = self
-            .distribution
-            .sample_iter(rng)
-            .take(block_size)
-            .positions(|error| error)
-            .collect();
+        let positions = sample_positions(
+            self.strategy,
+            self.distribution,
+            self.probability,
+            block_size,
+            rng,
+        );
         SparseBinVec::new(block_size, positions)
     }
+
+    fn sample_error_into<R: Rng>(&self, length: usize, buffer: &mut Self::Error, rng: &mut R) {
+        sample_positions_into(
+            self.strategy,
+            self.distribution,
+            self.probability,
+            length,
+            buffer,
+            rng,
+        );
+    }
+}
+
+impl ExpectedWeight for BinarySymmetricChannel {
+    fn expected_weight(&self, length: usize) -> f64 {
+        self.probability * length as f64
+    }
+}
+
+/// The sampling strategy is kept.
+impl DriftingNoise for BinarySymmetricChannel {
+    fn with_drifted_probability(&self, probability: Probability) -> Self {
+        Bernoulli::new(probability.value())
+            .map(|distribution| Self {
+                distribution,
+                probability: probability.value(),
+                ..*self
+            })
+            .unwrap()
+    }
 }
 
 impl fmt::Display for BinarySymmetricChannel {
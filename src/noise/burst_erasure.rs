@@ -0,0 +1,94 @@
+use super::{ExpectedWeight, NoiseModel, Probability};
+use rand::distributions::{Bernoulli, Distribution};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinVec;
+use std::fmt;
+
+/// An erasure channel erasing contiguous blocks of bits.
+///
+/// Each bit starts a burst with the given probability
+/// and a burst erases this bit and the following ones
+/// up to the burst length.
+/// Bursts reaching the end of the block are truncated.
+///
+/// This noise model returns a `SparseBinVec` where
+/// the positions of each 1 is an erasure.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::noise::{BurstErasureChannel, ExpectedWeight, NoiseModel, Probability};
+/// use rand::thread_rng;
+///
+/// let noise = BurstErasureChannel::new(Probability::new(1.0), 3);
+/// let erasure = noise.sample_error_of_length(5, &mut thread_rng());
+///
+/// assert_eq!(erasure.weight(), 5);
+/// assert_eq!(noise.expected_weight(5), 5.0);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BurstErasureChannel {
+    distribution: Bernoulli,
+    probability: f64,
+    burst_length: usize,
+}
+
+impl BurstErasureChannel {
+    /// Creates a new burst erasure channel where bursts of the given length
+    /// start at each bit with the given probability.
+    pub fn new(probability: Probability, burst_length: usize) -> Self {
+        Bernoulli::new(probability.value())
+            .map(|distribution| Self {
+                distribution,
+                probability: probability.value(),
+                burst_length,
+            })
+            .unwrap()
+    }
+
+    /// Returns the number of bits erased by a burst.
+    pub fn burst_length(&self) -> usize {
+        self.burst_length
+    }
+}
+
+impl NoiseModel for BurstErasureChannel {
+    type Error = SparseBinVec;
+
+    fn sample_error_of_length<R: Rng>(&self, length: usize, rng: &mut R) -> Self::Error {
+        let mut positions = Vec::new();
+        let mut erased_until = 0;
+        for start in 0..length {
+            if self.distribution.sample(rng) {
+                let end = usize::min(start + self.burst_length, length);
+                positions.extend(usize::max(start, erased_until)..end);
+                erased_until = usize::max(end, erased_until);
+            }
+        }
+        SparseBinVec::new(length, positions)
+    }
+}
+
+impl ExpectedWeight for BurstErasureChannel {
+    /// A bit is erased if a burst starts at this bit
+    /// or at one of the previous bits within the burst length.
+    fn expected_weight(&self, length: usize) -> f64 {
+        (0..length)
+            .map(|bit| {
+                let num_starts = usize::min(bit + 1, self.burst_length);
+                1.0 - (1.0 - self.probability).powi(num_starts as i32)
+            })
+            .sum()
+    }
+}
+
+impl fmt::Display for BurstErasureChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "BurstErasure({}, {})",
+            self.probability, self.burst_length
+        )
+    }
+}
@@ -0,0 +1,121 @@
+use super::Probability;
+use crate::codes::Layout;
+use pauli::{PauliOperator, X, Y, Z};
+use rand::distributions::{Bernoulli, Distribution};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A model of rare events depolarizing a whole region of qubits at once,
+/// such as a cosmic ray hitting a chip.
+///
+/// At each round, an event happens with the given rate.
+/// Its center is a uniformly random qubit
+/// and all the qubits within the given radius of the center,
+/// measured with the positions of the [layout](Layout) of the code,
+/// become maximally mixed, that is they receive a uniformly random Pauli.
+///
+/// Events are injected round by round,
+/// for example by a [`MemoryExperiment`](crate::simulation::MemoryExperiment).
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::Layout;
+/// use ldpc::noise::{BurstEventModel, Probability};
+/// use rand::thread_rng;
+///
+/// let layout = Layout::rotated_surface_code(5);
+/// let events = BurstEventModel::new(Probability::new(1.0), 2.0);
+///
+/// // The qubits are 2 units apart, so this is the center of the patch and its 4 neighbors.
+/// assert_eq!(events.region(&layout, 12), vec![7, 11, 12, 13, 17]);
+///
+/// let error = events.sample_event(&layout, &mut thread_rng()).unwrap();
+/// assert!(error.weight() <= 5);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BurstEventModel {
+    distribution: Bernoulli,
+    rate: f64,
+    radius: f64,
+}
+
+impl BurstEventModel {
+    /// Creates a model where an event happens at each round with the given rate
+    /// and depolarizes the qubits within the given radius of its center.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the radius is negative or not finite.
+    pub fn new(rate: Probability, radius: f64) -> Self {
+        if !radius.is_finite() || radius < 0.0 {
+            panic!("{} is not a valid radius", radius);
+        }
+        Bernoulli::new(rate.value())
+            .map(|distribution| Self {
+                distribution,
+                rate: rate.value(),
+                radius,
+            })
+            .unwrap()
+    }
+
+    /// Returns the probability of an event at each round.
+    pub fn rate(&self) -> Probability {
+        Probability::new(self.rate)
+    }
+
+    /// Returns the radius of the region depolarized by an event.
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Returns the qubits within the radius of the given qubit in increasing order.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the center is not a qubit of the layout.
+    pub fn region(&self, layout: &Layout, center: usize) -> Vec<usize> {
+        let (row, column) = layout
+            .qubit(center)
+            .unwrap_or_else(|| panic!("qubit {} is not in the layout", center));
+        layout
+            .qubits()
+            .iter()
+            .enumerate()
+            .filter(|(_, (other_row, other_column))| {
+                let row_offset = row.abs_diff(*other_row) as f64;
+                let column_offset = column.abs_diff(*other_column) as f64;
+                row_offset.hypot(column_offset) <= self.radius
+            })
+            .map(|(qubit, _)| qubit)
+            .collect()
+    }
+
+    /// Samples the error of an event on the qubits of the layout
+    /// or returns None if no event happens this round.
+    pub fn sample_event<R: Rng>(&self, layout: &Layout, rng: &mut R) -> Option<PauliOperator> {
+        let num_qubits = layout.qubits().len();
+        if num_qubits == 0 || !self.distribution.sample(rng) {
+            return None;
+        }
+        let center = rng.gen_range(0..num_qubits);
+        let (positions, paulis) = self
+            .region(layout, center)
+            .into_iter()
+            .filter_map(|qubit| {
+                let pauli = [None, Some(X), Some(Y), Some(Z)].choose(rng).unwrap();
+                pauli.map(|pauli| (qubit, pauli))
+            })
+            .unzip();
+        Some(PauliOperator::new(num_qubits, positions, paulis))
+    }
+}
+
+impl fmt::Display for BurstEventModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BurstEvents({}, radius {})", self.rate, self.radius)
+    }
+}
@@ -0,0 +1,280 @@
+use super::metropolis::{log_weight, MetropolisChain};
+use super::Probability;
+use crate::codes::{DenseCodeword, InformationSet, LinearCode};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
+
+/// Samples errors from independent bit flips conditioned on producing a given syndrome.
+///
+/// The errors with a syndrome are the sums of a fixed solution with the codewords,
+/// that is a coset of the code.
+/// For codes of small dimension, the probability of every error of the coset
+/// is computed and errors are sampled exactly.
+/// For larger codes, errors are sampled from a Metropolis chain
+/// whose moves add a generator of the code to the current error,
+/// such that the chain never leaves the coset.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::noise::{ConditionalErrorSampler, Probability};
+/// use rand::thread_rng;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let code = LinearCode::repetition_code(5);
+/// let sampler = ConditionalErrorSampler::new(&code, Probability::new(0.1));
+///
+/// let syndrome = SparseBinVec::new(4, vec![1]);
+/// let errors = sampler.samples(syndrome.as_view(), 100, &mut thread_rng()).unwrap();
+/// for error in errors.iter() {
+///     assert_eq!(code.syndrome_of(error), syndrome);
+/// }
+/// // An error of weight 2 is 81 times more likely than an error of weight 3.
+/// let num_light_errors = errors.iter().filter(|error| error.weight() == 2).count();
+/// assert!(num_light_errors > 80);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConditionalErrorSampler<'code> {
+    code: &'code LinearCode,
+    llrs: Vec<f64>,
+    max_exact_dimension: usize,
+    burn_in: usize,
+    thinning: usize,
+}
+
+impl<'code> ConditionalErrorSampler<'code> {
+    /// Creates a sampler of errors flipping each bit of the code independently
+    /// with the given probability.
+    ///
+    /// By default, errors are sampled exactly for codes of dimension at most 16.
+    /// Otherwise, the chain runs 1000 steps before the first sample
+    /// and 10 steps between samples.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the probability is 0 or 1.
+    pub fn new(code: &'code LinearCode, probability: Probability) -> Self {
+        Self::with_bit_probabilities(code, &vec![probability; code.len()])
+    }
+
+    /// Creates a sampler of errors flipping each bit of the code independently
+    /// with its own probability.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of probabilities is different from the length of the code
+    /// or if a probability is 0 or 1.
+    pub fn with_bit_probabilities(code: &'code LinearCode, probabilities: &[Probability]) -> Self {
+        if probabilities.len() != code.len() {
+            panic!(
+                "{} probabilities for code of length {}",
+                probabilities.len(),
+                code.len()
+            );
+        }
+        let llrs = probabilities
+            .iter()
+            .map(|probability| {
+                if !probability.llr().is_finite() {
+                    panic!("can't condition on errors of probability {}", probability);
+                }
+                probability.llr()
+            })
+            .collect();
+        Self {
+            code,
+            llrs,
+            max_exact_dimension: 16,
+            burn_in: 1000,
+            thinning: 10,
+        }
+    }
+
+    /// Sets the largest dimension of a code for which errors are sampled exactly.
+    pub fn with_max_exact_dimension(mut self, dimension: usize) -> Self {
+        self.max_exact_dimension = dimension;
+        self
+    }
+
+    /// Sets the number of steps of the chain before the first sample.
+    pub fn with_burn_in(mut self, burn_in: usize) -> Self {
+        self.burn_in = burn_in;
+        self
+    }
+
+    /// Sets the number of steps of the chain between two samples.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the thinning is 0.
+    pub fn with_thinning(mut self, thinning: usize) -> Self {
+        if thinning == 0 {
+            panic!("can't take samples 0 steps apart");
+        }
+        self.thinning = thinning;
+        self
+    }
+
+    /// Checks if errors are sampled exactly instead of from a chain.
+    pub fn is_exact(&self) -> bool {
+        self.code.dimension() <= self.max_exact_dimension
+    }
+
+    /// Returns a random error with the given syndrome
+    /// or None if no error has this syndrome.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    pub fn sample<R: Rng>(&self, syndrome: SparseBinSlice, rng: &mut R) -> Option<SparseBinVec> {
+        self.samples(syndrome, 1, rng)?.pop()
+    }
+
+    /// Returns the given number of random errors with the given syndrome
+    /// or None if no error has this syndrome.
+    ///
+    /// Errors sampled from a chain are correlated,
+    /// but successive samples are taken from a single chain
+    /// such that the burn-in is paid only once.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    pub fn samples<R: Rng>(
+        &self,
+        syndrome: SparseBinSlice,
+        num_samples: usize,
+        rng: &mut R,
+    ) -> Option<Vec<SparseBinVec>> {
+        if syndrome.len() != self.code.num_checks() {
+            panic!(
+                "syndrome of length {} is invalid for code with {} checks",
+                syndrome.len(),
+                self.code.num_checks()
+            );
+        }
+        let columns = (0..self.code.len()).collect::<Vec<_>>();
+        let solution =
+            InformationSet::new(self.code.parity_check_matrix(), &columns).solve(syndrome)?;
+        let samples = if self.is_exact() {
+            self.exact_samples(&solution, num_samples, rng)
+        } else {
+            self.chain_samples(&solution, num_samples, rng)
+        };
+        Some(samples)
+    }
+
+    fn exact_samples<R: Rng>(
+        &self,
+        solution: &SparseBinVec,
+        num_samples: usize,
+        rng: &mut R,
+    ) -> Vec<SparseBinVec> {
+        let generators = self
+            .code
+            .generator_matrix()
+            .rows()
+            .map(|generator| DenseCodeword::from(&generator))
+            .collect::<Vec<_>>();
+        let mut current = DenseCodeword::from(solution);
+        let mut coset = vec![current.clone()];
+        // Visits every coset element by adding a single generator at a time in Gray code order.
+        for step in 1_usize..1 << generators.len() {
+            current += &generators[step.trailing_zeros() as usize];
+            coset.push(current.clone());
+        }
+        let log_weights = coset
+            .iter()
+            .map(|error| log_weight(&self.llrs, error.non_trivial_positions()))
+            .collect::<Vec<_>>();
+        let max = log_weights
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let distribution =
+            WeightedIndex::new(log_weights.iter().map(|weight| (weight - max).exp())).unwrap();
+        (0..num_samples)
+            .map(|_| SparseBinVec::from(&coset[distribution.sample(rng)]))
+            .collect()
+    }
+
+    fn chain_samples<R: Rng>(
+        &self,
+        solution: &SparseBinVec,
+        num_samples: usize,
+        rng: &mut R,
+    ) -> Vec<SparseBinVec> {
+        let generators = self
+            .code
+            .generator_matrix()
+            .rows()
+            .map(|generator| generator.non_trivial_positions().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut chain = MetropolisChain::new(&self.llrs, solution);
+        for _ in 0..self.burn_in {
+            chain.step(&generators, rng);
+        }
+        let mut samples = Vec::with_capacity(num_samples);
+        for sample in 0..num_samples {
+            if sample > 0 {
+                for _ in 0..self.thinning {
+                    chain.step(&generators, rng);
+                }
+            }
+            samples.push(SparseBinVec::from(chain.error()));
+        }
+        samples
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Returns the fraction of samples equal to the error.
+    fn frequency_of(samples: &[SparseBinVec], error: &SparseBinVec) -> f64 {
+        samples.iter().filter(|sample| *sample == error).count() as f64 / samples.len() as f64
+    }
+
+    #[test]
+    fn exact_and_chain_samples_follow_the_conditional_distribution() {
+        let code = LinearCode::repetition_code(3);
+        let probabilities = [0.1, 0.2, 0.3].map(Probability::new);
+        let syndrome = SparseBinVec::new(2, vec![0]);
+        // The coset contains [0] with probability 0.1 * 0.8 * 0.7 = 0.056
+        // and [1, 2] with probability 0.9 * 0.2 * 0.3 = 0.054.
+        let expected = 0.056 / 0.11;
+        let error = SparseBinVec::new(3, vec![0]);
+        let exact = ConditionalErrorSampler::with_bit_probabilities(&code, &probabilities);
+        let chain = exact.clone().with_max_exact_dimension(0).with_thinning(3);
+        assert!(exact.is_exact());
+        assert!(!chain.is_exact());
+        for sampler in [exact, chain] {
+            let samples = sampler
+                .samples(syndrome.as_view(), 20000, &mut StdRng::seed_from_u64(5))
+                .unwrap();
+            assert!(samples
+                .iter()
+                .all(|sample| code.syndrome_of(sample) == syndrome));
+            assert!((frequency_of(&samples, &error) - expected).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    fn no_sample_for_unreachable_syndrome() {
+        let code = LinearCode::from_parity_check_matrix(sparse_bin_mat::SparseBinMat::new(
+            3,
+            vec![vec![0, 1], vec![0, 1]],
+        ));
+        let sampler = ConditionalErrorSampler::new(&code, Probability::new(0.1));
+        let syndrome = SparseBinVec::new(2, vec![0]);
+        assert_eq!(
+            sampler.sample(syndrome.as_view(), &mut StdRng::seed_from_u64(0)),
+            None
+        );
+    }
+}
@@ -1,18 +1,42 @@
-use super::{NoiseModel, Probability};
-use serde::{Serialize, Deserialize};
+use super::{
+    sample_paulis_weight_first, DriftingNoise, ExpectedWeight, NoiseModel, Probability,
+    SamplingStrategy,
+};
 use pauli::{Pauli, PauliOperator, X, Y, Z};
 use rand::distributions::{Bernoulli, Distribution};
 use rand::seq::SliceRandom;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A depolarizing noise channel apply one of the 3 non-trivial Pauli
 /// operator with the given probrability.
+///
+/// The probability `p` is the probability that a qubit has an error,
+/// such that each of X, Y and Z has probability `p / 3`.
+/// Other conventions are converted with
+/// [`with_pauli_probability`](DepolarizingNoise::with_pauli_probability)
+/// and [`with_depolarizing_parameter`](DepolarizingNoise::with_depolarizing_parameter).
+///
+/// # Example
+///
+/// ```
+/// use ldpc::noise::{DepolarizingNoise, Probability};
+///
+/// let noise = DepolarizingNoise::with_probability(Probability::new(0.3));
+/// assert!((noise.pauli_probability().value() - 0.1).abs() < 1e-12);
+///
+/// // The channel mapping a state to (1 - 0.4) times itself plus 0.4 times the maximally mixed state.
+/// let noise = DepolarizingNoise::with_depolarizing_parameter(Probability::new(0.4));
+/// assert!((noise.probability().value() - 0.3).abs() < 1e-12);
+/// ```
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct DepolarizingNoise {
     distribution: Bernoulli,
     probability: f64,
     non_trivial_paulis: [Pauli; 3],
+    #[serde(default)]
+    strategy: SamplingStrategy,
 }
 
 impl DepolarizingNoise {
@@ -23,22 +47,88 @@ impl DepolarizingNoise {
                 distribution,
                 probability: probability.value(),
                 non_trivial_paulis: [X, Y, Z],
+                strategy: SamplingStrategy::default(),
             })
             .unwrap()
     }
+
+    /// Creates a new depolarizing noise where each of X, Y and Z
+    /// has the given probability.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the probability is greater than 1/3.
+    pub fn with_pauli_probability(probability: Probability) -> Self {
+        let total = Probability::try_new(3.0 * probability.value())
+            .expect("pauli probability is greater than 1/3");
+        Self::with_probability(total)
+    }
+
+    /// Creates a new depolarizing noise mapping each qubit to the maximally mixed state
+    /// with the given probability.
+    ///
+    /// That is, the state `ρ` becomes `(1 - λ) ρ + λ I / 2` for the parameter `λ`.
+    /// Since the maximally mixed state is the uniform mixture of I, X, Y and Z,
+    /// each of X, Y and Z has probability `λ / 4`.
+    pub fn with_depolarizing_parameter(parameter: Probability) -> Self {
+        Self::with_probability(Probability::new(0.75 * parameter.value()))
+    }
+
+    /// Returns the probability that a qubit has an error.
+    pub fn probability(&self) -> Probability {
+        Probability::new(self.probability)
+    }
+
+    /// Returns the probability of each of X, Y and Z on a qubit.
+    pub fn pauli_probability(&self) -> Probability {
+        Probability::new(self.probability / 3.0)
+    }
+
+    /// Sets the strategy used to sample the positions of the errors.
+    ///
+    /// Default is [`SamplingStrategy::PerPosition`](SamplingStrategy::PerPosition).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::noise::{DepolarizingNoise, NoiseModel, Probability, SamplingStrategy};
+    /// use rand::thread_rng;
+    ///
+    /// let noise = DepolarizingNoise::with_probability(Probability::new(0.0001))
+    ///     .with_sampling_strategy(SamplingStrategy::WeightFirst);
+    /// let error = noise.sample_error_of_length(100_000, &mut thread_rng());
+    ///
+    /// assert_eq!(error.len(), 100_000);
+    /// ```
+    pub fn with_sampling_strategy(mut self, strategy: SamplingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
 }
 
 impl NoiseModel for DepolarizingNoise {
     type Error = PauliOperator;
 
     fn sample_error_of_length<R: Rng>(&self, length: usize, rng: &mut R) -> Self::Error {
+        match self.strategy {
+            SamplingStrategy::PerPosition => self.sample_per_position(length, rng),
+            SamplingStrategy::WeightFirst => self.sample_weight_first(length, rng),
+        }
+    }
+}
+
+impl ExpectedWeight for DepolarizingNoise {
+    fn expected_weight(&self, length: usize) -> f64 {
+        self.probability * length as f64
+    }
+}
+
+impl DepolarizingNoise {
+    fn sample_per_position<R: Rng>(&self, length: usize, rng: &mut R) -> PauliOperator {
         let (positions, paulis) = (0..length)
             .filter_map(|position| {
                 if self.distribution.sample(rng) {
-                    Some((
-                        position,
-                        self.non_trivial_paulis.choose(rng).cloned().unwrap(),
-                    ))
+                    Some((position, self.random_pauli(rng)))
                 } else {
                     None
                 }
@@ -46,6 +136,29 @@ impl NoiseModel for DepolarizingNoise {
             .unzip();
         PauliOperator::new(length, positions, paulis)
     }
+
+    fn sample_weight_first<R: Rng>(&self, length: usize, rng: &mut R) -> PauliOperator {
+        let pauli_probability = self.probability / 3.0;
+        sample_paulis_weight_first([pauli_probability; 3], length, rng)
+    }
+
+    fn random_pauli<R: Rng>(&self, rng: &mut R) -> Pauli {
+        self.non_trivial_paulis.choose(rng).cloned().unwrap()
+    }
+}
+
+/// The probability is the total probability of an error on a qubit
+/// and the sampling strategy is kept.
+impl DriftingNoise for DepolarizingNoise {
+    fn with_drifted_probability(&self, probability: Probability) -> Self {
+        Bernoulli::new(probability.value())
+            .map(|distribution| Self {
+                distribution,
+                probability: probability.value(),
+                ..*self
+            })
+            .unwrap()
+    }
 }
 
 impl fmt::Display for DepolarizingNoise {
@@ -53,3 +166,31 @@ impl fmt::Display for DepolarizingNoise {
         write!(f, "Depolarizing Noise (prob = {})", self.probability)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn weight_first_pauli_frequencies_match_the_probability() {
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.3))
+            .with_sampling_strategy(SamplingStrategy::WeightFirst);
+        let error = noise.sample_error_of_length(100_000, &mut StdRng::seed_from_u64(123));
+        let y = (error.x_part().weight() + error.z_part().weight() - error.weight()) as f64;
+        let x = error.x_part().weight() as f64 - y;
+        let z = error.z_part().weight() as f64 - y;
+        for frequency in [x, y, z] {
+            assert!((frequency / 100_000.0 - 0.1).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn weight_first_sampling_handles_certain_errors() {
+        let noise = DepolarizingNoise::with_probability(Probability::new(1.0))
+            .with_sampling_strategy(SamplingStrategy::WeightFirst);
+        let error = noise.sample_error_of_length(1000, &mut StdRng::seed_from_u64(123));
+        assert_eq!(error.weight(), 1000);
+    }
+}
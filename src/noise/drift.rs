@@ -0,0 +1,174 @@
+use super::{NoiseModel, Probability};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::fmt;
+
+/// How the probability of a noise model changes from round to round,
+/// for example to model the calibration drift of a device.
+///
+/// The probabilities are always clamped between 0 and 1.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::noise::DriftSchedule;
+/// use rand::thread_rng;
+///
+/// let schedule = DriftSchedule::Sinusoidal { mean: 0.01, amplitude: 0.005, period: 4 };
+/// let probabilities = schedule
+///     .sample_trajectory(4, &mut thread_rng())
+///     .into_iter()
+///     .map(|probability| probability.value())
+///     .collect::<Vec<_>>();
+///
+/// assert!((probabilities[0] - 0.015).abs() < 1e-12);
+/// assert!((probabilities[2] - 0.005).abs() < 1e-12);
+/// assert!((probabilities[3] - 0.01).abs() < 1e-12);
+/// ```
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum DriftSchedule {
+    /// The same probability at each round.
+    Constant(f64),
+    /// The probability `mean + amplitude * sin(2π t / period)` at round `t`.
+    Sinusoidal {
+        mean: f64,
+        amplitude: f64,
+        period: usize,
+    },
+    /// A probability starting from `initial` and moving up or down by `step`
+    /// with equal chances at each round, while staying between `min` and `max`.
+    RandomWalk {
+        initial: f64,
+        step: f64,
+        min: f64,
+        max: f64,
+    },
+    /// The given probability at each round,
+    /// repeating the last one after the end of the list.
+    Explicit(Vec<f64>),
+}
+
+impl DriftSchedule {
+    /// Returns the probability of each round from round 1 to the given number of rounds.
+    ///
+    /// Only the random walk uses the random number generator,
+    /// such that each call gives a different trajectory.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a sinusoidal period is 0 or if an explicit list is empty.
+    pub fn sample_trajectory<R: Rng>(&self, num_rounds: usize, rng: &mut R) -> Vec<Probability> {
+        let probabilities = match self {
+            Self::Constant(probability) => vec![*probability; num_rounds],
+            Self::Sinusoidal {
+                mean,
+                amplitude,
+                period,
+            } => {
+                if *period == 0 {
+                    panic!("sinusoidal drift with a period of 0 rounds");
+                }
+                (1..=num_rounds)
+                    .map(|round| {
+                        mean + amplitude * (2.0 * PI * round as f64 / *period as f64).sin()
+                    })
+                    .collect()
+            }
+            Self::RandomWalk {
+                initial,
+                step,
+                min,
+                max,
+            } => {
+                let mut probability = *initial;
+                (0..num_rounds)
+                    .map(|_| {
+                        let current = probability;
+                        let direction = if rng.gen() { 1.0 } else { -1.0 };
+                        probability = (probability + direction * step).clamp(*min, *max);
+                        current
+                    })
+                    .collect()
+            }
+            Self::Explicit(probabilities) => {
+                let last = *probabilities
+                    .last()
+                    .expect("explicit drift without probabilities");
+                (0..num_rounds)
+                    .map(|round| probabilities.get(round).cloned().unwrap_or(last))
+                    .collect()
+            }
+        };
+        probabilities
+            .into_iter()
+            .map(|probability| Probability::new(probability.clamp(0.0, 1.0)))
+            .collect()
+    }
+}
+
+impl fmt::Display for DriftSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Constant(probability) => write!(f, "Constant({})", probability),
+            Self::Sinusoidal {
+                mean,
+                amplitude,
+                period,
+            } => write!(f, "Sinusoidal({}, {}, {})", mean, amplitude, period),
+            Self::RandomWalk {
+                initial,
+                step,
+                min,
+                max,
+            } => write!(f, "RandomWalk({}, {}, {}, {})", initial, step, min, max),
+            Self::Explicit(probabilities) => write!(f, "Explicit({:?})", probabilities),
+        }
+    }
+}
+
+/// A noise model driven by a single probability that can drift over time.
+pub trait DriftingNoise: NoiseModel + Sized {
+    /// Returns the same noise model with the given probability.
+    fn with_drifted_probability(&self, probability: Probability) -> Self;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn random_walk_stays_within_bounds() {
+        let schedule = DriftSchedule::RandomWalk {
+            initial: 0.01,
+            step: 0.01,
+            min: 0.0,
+            max: 0.03,
+        };
+        let trajectory = schedule.sample_trajectory(1000, &mut StdRng::seed_from_u64(2));
+        assert_eq!(trajectory[0].value(), 0.01);
+        assert!(trajectory
+            .iter()
+            .all(|probability| probability.value() >= 0.0 && probability.value() <= 0.03));
+        assert!(trajectory
+            .windows(2)
+            .all(|pair| (pair[0].value() - pair[1].value()).abs() <= 0.01 + 1e-12));
+    }
+
+    #[test]
+    fn explicit_schedule_repeats_the_last_probability() {
+        let schedule = DriftSchedule::Explicit(vec![0.1, 2.0]);
+        let trajectory = schedule.sample_trajectory(4, &mut StdRng::seed_from_u64(0));
+        assert_eq!(
+            trajectory,
+            vec![
+                Probability::new(0.1),
+                Probability::new(1.0),
+                Probability::new(1.0),
+                Probability::new(1.0)
+            ]
+        );
+    }
+}
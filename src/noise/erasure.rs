@@ -1,9 +1,11 @@
-use super::{NoiseModel, Probability};
-use sparse_bin_mat::SparseBinVec;
-use itertools::Itertools;
-use rand::distributions::{Bernoulli, Distribution};
+use super::{
+    sample_positions, sample_positions_into, DriftingNoise, ExpectedWeight, NoiseModel,
+    Probability, SamplingStrategy,
+};
+use rand::distributions::Bernoulli;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinVec;
 use std::fmt;
 
 /// An erasure channel working for both classical and quantum codes.
@@ -14,6 +16,8 @@ use std::fmt;
 pub struct ErasureChannel {
     distribution: Bernoulli,
     probability: f64,
+    #[serde(default)]
+    strategy: SamplingStrategy,
 }
 
 impl ErasureChannel {
@@ -23,23 +27,63 @@ impl ErasureChannel {
             .map(|distribution| Self {
                 distribution,
                 probability: probability.value(),
+                strategy: SamplingStrategy::default(),
             })
             .unwrap()
     }
+
+    /// Sets the strategy used to sample the errors.
+    ///
+    /// Default is [`SamplingStrategy::PerPosition`](SamplingStrategy::PerPosition).
+    pub fn with_sampling_strategy(mut self, strategy: SamplingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
 }
 
 impl NoiseModel for ErasureChannel {
     type Error = SparseBinVec;
 
     fn sample_error_of_length<R: Rng>(&self, length: usize, rng: &mut R) -> Self::Error {
-        let positions = self
-            .distribution
-            .sample_iter(rng)
-            .take(length)
-            .positions(|error| error)
-            .collect();
+        let positions = sample_positions(
+            self.strategy,
+            self.distribution,
+            self.probability,
+            length,
+            rng,
+        );
         SparseBinVec::new(length, positions)
     }
+
+    fn sample_error_into<R: Rng>(&self, length: usize, buffer: &mut Self::Error, rng: &mut R) {
+        sample_positions_into(
+            self.strategy,
+            self.distribution,
+            self.probability,
+            length,
+            buffer,
+            rng,
+        );
+    }
+}
+
+impl ExpectedWeight for ErasureChannel {
+    fn expected_weight(&self, length: usize) -> f64 {
+        self.probability * length as f64
+    }
+}
+
+/// The sampling strategy is kept.
+impl DriftingNoise for ErasureChannel {
+    fn with_drifted_probability(&self, probability: Probability) -> Self {
+        Bernoulli::new(probability.value())
+            .map(|distribution| Self {
+                distribution,
+                probability: probability.value(),
+                ..*self
+            })
+            .unwrap()
+    }
 }
 
 impl fmt::Display for ErasureChannel {
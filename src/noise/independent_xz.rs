@@ -0,0 +1,121 @@
+use super::{
+    BinarySymmetricChannel, ExpectedWeight, NoiseModel, PauliProbabilities, Probability,
+    SamplingStrategy,
+};
+use crate::css::CssOperator;
+use pauli::PauliOperator;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A noise channel applying X and Z independently on each qubit.
+///
+/// A qubit gets an X with probability `px` and a Z with probability `pz`,
+/// thus a Y with probability `px * pz`.
+/// Unlike the [`DepolarizingNoise`](super::DepolarizingNoise),
+/// the X and Z parts of the errors are uncorrelated,
+/// such that the X and Z stabilizers of a CSS code can be decoded separately without loss.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::noise::{ExpectedWeight, IndependentXZNoise, NoiseModel, Probability};
+/// use rand::thread_rng;
+///
+/// let noise = IndependentXZNoise::new(Probability::new(0.1), Probability::new(0.2));
+/// let probabilities = noise.pauli_probabilities();
+/// assert!((probabilities.y() - 0.02).abs() < 1e-12);
+/// assert!((noise.expected_weight(100) - 28.0).abs() < 1e-12);
+///
+/// let error = noise.sample_error_of_length(100, &mut thread_rng());
+/// assert_eq!(error.len(), 100);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct IndependentXZNoise {
+    x: BinarySymmetricChannel,
+    z: BinarySymmetricChannel,
+    x_probability: f64,
+    z_probability: f64,
+}
+
+impl IndependentXZNoise {
+    /// Creates a new channel with the given probabilities of X and Z.
+    pub fn new(x_probability: Probability, z_probability: Probability) -> Self {
+        Self {
+            x: BinarySymmetricChannel::with_probability(x_probability),
+            z: BinarySymmetricChannel::with_probability(z_probability),
+            x_probability: x_probability.value(),
+            z_probability: z_probability.value(),
+        }
+    }
+
+    /// Creates a new channel with the same probability of X and Z.
+    pub fn with_probability(probability: Probability) -> Self {
+        Self::new(probability, probability)
+    }
+
+    /// Sets the strategy used to sample the X and Z parts of the errors.
+    ///
+    /// Default is [`SamplingStrategy::PerPosition`](SamplingStrategy::PerPosition).
+    pub fn with_sampling_strategy(mut self, strategy: SamplingStrategy) -> Self {
+        self.x = self.x.with_sampling_strategy(strategy);
+        self.z = self.z.with_sampling_strategy(strategy);
+        self
+    }
+
+    /// Returns the probability of applying each of X, Y and Z on a qubit.
+    pub fn pauli_probabilities(&self) -> PauliProbabilities {
+        PauliProbabilities::new(
+            self.x_probability * (1.0 - self.z_probability),
+            self.x_probability * self.z_probability,
+            (1.0 - self.x_probability) * self.z_probability,
+        )
+    }
+}
+
+impl NoiseModel for IndependentXZNoise {
+    type Error = PauliOperator;
+
+    fn sample_error_of_length<R: Rng>(&self, length: usize, rng: &mut R) -> Self::Error {
+        PauliOperator::from(CssOperator {
+            x: self.x.sample_error_of_length(length, rng),
+            z: self.z.sample_error_of_length(length, rng),
+        })
+    }
+}
+
+impl ExpectedWeight for IndependentXZNoise {
+    fn expected_weight(&self, length: usize) -> f64 {
+        self.pauli_probabilities().total() * length as f64
+    }
+}
+
+impl fmt::Display for IndependentXZNoise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Independent XZ Noise (px = {}, pz = {})",
+            self.x_probability, self.z_probability
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn pauli_frequencies_match_independent_flips() {
+        let noise = IndependentXZNoise::new(Probability::new(0.3), Probability::new(0.5));
+        let error = noise.sample_error_of_length(100_000, &mut StdRng::seed_from_u64(123));
+        let x = error.x_part().weight() as f64 / 100_000.0;
+        let z = error.z_part().weight() as f64 / 100_000.0;
+        let y =
+            (error.x_part().weight() + error.z_part().weight() - error.weight()) as f64 / 100_000.0;
+        assert!((x - 0.3).abs() < 0.01);
+        assert!((z - 0.5).abs() < 0.01);
+        assert!((y - 0.15).abs() < 0.01);
+    }
+}
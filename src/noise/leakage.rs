@@ -0,0 +1,301 @@
+use super::Probability;
+use crate::codes::CssCode;
+use crate::css::CssSyndrome;
+use pauli::{PauliOperator, X, Y, Z};
+use rand::distributions::{Bernoulli, Distribution};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+use std::fmt;
+
+/// A model of qubits leaking out of the computational subspace.
+///
+/// At each round, every qubit that is not leaked leaks with the given probability.
+/// A leaked qubit stays leaked until it is reset according to the [`ResetStrategy`].
+/// While leaked, a qubit is maximally mixed,
+/// that is it receives a uniformly random Pauli at each round,
+/// and the outcomes of the checks acting on it are uniformly random.
+///
+/// The leaked qubits are tracked in a [`LeakageState`] updated round by round,
+/// for example by a [`MemoryExperiment`](crate::simulation::MemoryExperiment).
+///
+/// # Example
+///
+/// ```
+/// use ldpc::noise::{LeakageModel, LeakageState, Probability, ResetStrategy};
+/// use rand::thread_rng;
+///
+/// let leakage = LeakageModel::new(Probability::new(1.0)).with_reset(ResetStrategy::Periodic(3));
+/// let mut state = LeakageState::new(10);
+///
+/// leakage.advance(&mut state, &mut thread_rng());
+/// assert_eq!(state.num_leaked(), 10);
+///
+/// // The third round resets every qubit before they leak again.
+/// leakage.advance(&mut state, &mut thread_rng());
+/// leakage.advance(&mut state, &mut thread_rng());
+/// assert_eq!(state.num_resets(), 10);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LeakageModel {
+    distribution: Bernoulli,
+    probability: f64,
+    reset: ResetStrategy,
+}
+
+impl LeakageModel {
+    /// Creates a leakage model where each qubit leaks with the given probability
+    /// at each round and is never reset.
+    pub fn new(probability: Probability) -> Self {
+        Bernoulli::new(probability.value())
+            .map(|distribution| Self {
+                distribution,
+                probability: probability.value(),
+                reset: ResetStrategy::Never,
+            })
+            .unwrap()
+    }
+
+    /// Sets the strategy resetting the leaked qubits.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the strategy has a period of 0 rounds.
+    pub fn with_reset(mut self, reset: ResetStrategy) -> Self {
+        if matches!(
+            reset,
+            ResetStrategy::Periodic(0) | ResetStrategy::AfterLeakedRounds(0)
+        ) {
+            panic!("can't reset every 0 rounds");
+        }
+        self.reset = reset;
+        self
+    }
+
+    /// Returns the probability that a qubit leaks at each round.
+    pub fn probability(&self) -> Probability {
+        Probability::new(self.probability)
+    }
+
+    /// Returns the strategy resetting the leaked qubits.
+    pub fn reset_strategy(&self) -> ResetStrategy {
+        self.reset
+    }
+
+    /// Advances the state by one round.
+    ///
+    /// The leaked qubits are first reset according to the strategy
+    /// and then each qubit that is not leaked leaks with the probability of the model.
+    pub fn advance<R: Rng>(&self, state: &mut LeakageState, rng: &mut R) {
+        state.round += 1;
+        let round = state.round;
+        for leaked_for in state.leaked_for.iter_mut() {
+            if let Some(rounds) = leaked_for {
+                let is_reset = match self.reset {
+                    ResetStrategy::Never => false,
+                    ResetStrategy::Periodic(period) => round % period == 0,
+                    ResetStrategy::AfterLeakedRounds(max_rounds) => *rounds >= max_rounds,
+                };
+                if is_reset {
+                    *leaked_for = None;
+                    state.num_resets += 1;
+                } else {
+                    *rounds += 1;
+                }
+            }
+            if leaked_for.is_none() && self.distribution.sample(rng) {
+                *leaked_for = Some(1);
+            }
+        }
+    }
+
+    /// Returns a uniformly random Pauli operator on the leaked qubits.
+    pub fn sample_leaked_error<R: Rng>(&self, state: &LeakageState, rng: &mut R) -> PauliOperator {
+        let (positions, paulis) = state
+            .leaked_qubits()
+            .filter_map(|qubit| {
+                let pauli = [None, Some(X), Some(Y), Some(Z)].choose(rng).unwrap();
+                pauli.map(|pauli| (qubit, pauli))
+            })
+            .unzip();
+        PauliOperator::new(state.num_qubits(), positions, paulis)
+    }
+
+    /// Replaces the outcomes of the checks acting on a leaked qubit
+    /// by uniformly random bits.
+    pub fn randomize_syndrome<R: Rng>(
+        &self,
+        code: &CssCode,
+        state: &LeakageState,
+        syndrome: &mut CssSyndrome,
+        rng: &mut R,
+    ) {
+        syndrome.x = randomize(&code.stabilizers.x, state, &syndrome.x, rng);
+        syndrome.z = randomize(&code.stabilizers.z, state, &syndrome.z, rng);
+    }
+}
+
+fn randomize<R: Rng>(
+    checks: &SparseBinMat,
+    state: &LeakageState,
+    syndrome: &SparseBinVec,
+    rng: &mut R,
+) -> SparseBinVec {
+    let positions = checks
+        .rows()
+        .enumerate()
+        .filter(|(check, row)| {
+            if row
+                .non_trivial_positions()
+                .any(|qubit| state.is_leaked(qubit))
+            {
+                rng.gen()
+            } else {
+                syndrome.is_one_at(*check).unwrap()
+            }
+        })
+        .map(|(check, _)| check)
+        .collect();
+    SparseBinVec::new(syndrome.len(), positions)
+}
+
+impl fmt::Display for LeakageModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Leakage({}, {})", self.probability, self.reset)
+    }
+}
+
+/// The strategy returning leaked qubits to the computational subspace.
+///
+/// A reset qubit can leak again in the same round.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum ResetStrategy {
+    /// Leaked qubits are never reset.
+    #[default]
+    Never,
+    /// Every leaked qubit is reset at the rounds multiple of the period.
+    Periodic(usize),
+    /// Each leaked qubit is reset once it has been leaked for the given number of rounds,
+    /// for example by leakage reduction units triggered by detection.
+    AfterLeakedRounds(usize),
+}
+
+impl fmt::Display for ResetStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Never => write!(f, "never"),
+            Self::Periodic(period) => write!(f, "every {} rounds", period),
+            Self::AfterLeakedRounds(rounds) => write!(f, "after {} leaked rounds", rounds),
+        }
+    }
+}
+
+/// The leaked qubits of a code during a simulation.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct LeakageState {
+    // The number of rounds each leaked qubit has been leaked for.
+    leaked_for: Vec<Option<usize>>,
+    round: usize,
+    num_resets: usize,
+}
+
+impl LeakageState {
+    /// Creates a state where none of the given number of qubits is leaked.
+    pub fn new(num_qubits: usize) -> Self {
+        Self {
+            leaked_for: vec![None; num_qubits],
+            round: 0,
+            num_resets: 0,
+        }
+    }
+
+    /// Returns the number of qubits.
+    pub fn num_qubits(&self) -> usize {
+        self.leaked_for.len()
+    }
+
+    /// Checks if a qubit is leaked.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the qubit is out of bound.
+    pub fn is_leaked(&self, qubit: usize) -> bool {
+        self.leaked_for[qubit].is_some()
+    }
+
+    /// Returns an iterator over the leaked qubits in increasing order.
+    pub fn leaked_qubits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.leaked_for
+            .iter()
+            .enumerate()
+            .filter(|(_, leaked_for)| leaked_for.is_some())
+            .map(|(qubit, _)| qubit)
+    }
+
+    /// Returns the number of leaked qubits.
+    pub fn num_leaked(&self) -> usize {
+        self.leaked_qubits().count()
+    }
+
+    /// Returns the number of rounds the state advanced.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    /// Returns the number of qubit resets so far.
+    pub fn num_resets(&self) -> usize {
+        self.num_resets
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn qubits_are_reset_after_being_leaked_long_enough() {
+        let leakage = LeakageModel::new(Probability::new(0.0))
+            .with_reset(ResetStrategy::AfterLeakedRounds(2));
+        let mut state = LeakageState::new(3);
+        state.leaked_for[1] = Some(1);
+        let mut rng = StdRng::seed_from_u64(0);
+        leakage.advance(&mut state, &mut rng);
+        assert!(state.is_leaked(1));
+        leakage.advance(&mut state, &mut rng);
+        assert_eq!(state.num_leaked(), 0);
+        assert_eq!(state.num_resets(), 1);
+        assert_eq!(state.round(), 2);
+    }
+
+    #[test]
+    fn only_checks_on_leaked_qubits_are_randomized() {
+        let code = CssCode::shor_code();
+        let leakage = LeakageModel::new(Probability::new(0.0));
+        let mut state = LeakageState::new(9);
+        state.leaked_for[0] = Some(1);
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut num_flips = 0;
+        for _ in 0..100 {
+            let mut syndrome = CssSyndrome {
+                x: SparseBinVec::new(2, vec![0, 1]),
+                z: SparseBinVec::new(6, vec![0, 1, 2, 3, 4, 5]),
+            };
+            leakage.randomize_syndrome(&code, &state, &mut syndrome, &mut rng);
+            assert!(syndrome.x.is_one_at(1).unwrap());
+            assert_eq!(
+                syndrome
+                    .z
+                    .non_trivial_positions()
+                    .filter(|check| *check > 0)
+                    .count(),
+                5
+            );
+            num_flips += !syndrome.x.is_one_at(0).unwrap() as usize;
+            assert!(leakage.sample_leaked_error(&state, &mut rng).weight() <= 1);
+        }
+        assert!(num_flips > 30 && num_flips < 70);
+    }
+}
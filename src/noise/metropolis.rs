@@ -0,0 +1,81 @@
+use crate::codes::DenseCodeword;
+use rand::Rng;
+use sparse_bin_mat::SparseBinVec;
+
+// A Metropolis chain over binary errors flipping each bit independently
+// where each step proposes to add a uniformly random move to the error.
+//
+// The proposal is symmetric, such that the chain samples errors
+// from the distribution restricted to the span of the moves around the start.
+// At inverse temperature β, the probability of each error is raised to the power β.
+#[derive(Debug, Clone)]
+pub(crate) struct MetropolisChain<'a> {
+    llrs: &'a [f64],
+    error: DenseCodeword,
+    log_weight: f64,
+    inverse_temperature: f64,
+}
+
+impl<'a> MetropolisChain<'a> {
+    // Starts the chain from the error where the log-likelihood ratios
+    // are ln((1 - p) / p) for each bit.
+    pub(crate) fn new(llrs: &'a [f64], start: &SparseBinVec) -> Self {
+        Self {
+            llrs,
+            error: DenseCodeword::from(start),
+            log_weight: log_weight(llrs, start.non_trivial_positions()),
+            inverse_temperature: 1.0,
+        }
+    }
+
+    pub(crate) fn set_inverse_temperature(&mut self, inverse_temperature: f64) {
+        self.inverse_temperature = inverse_temperature;
+    }
+
+    // Proposes to add a uniformly random move to the error
+    // and returns the index of the move if it is accepted.
+    pub(crate) fn step<R: Rng>(&mut self, moves: &[Vec<usize>], rng: &mut R) -> Option<usize> {
+        if moves.is_empty() {
+            return None;
+        }
+        let index = rng.gen_range(0..moves.len());
+        // Adding a bit to the error costs its log-likelihood ratio
+        // and removing a bit from the error gains it.
+        let log_ratio = moves[index]
+            .iter()
+            .map(|bit| {
+                if self.error.is_one_at(*bit).unwrap() {
+                    self.llrs[*bit]
+                } else {
+                    -self.llrs[*bit]
+                }
+            })
+            .sum::<f64>();
+        let scaled_log_ratio = self.inverse_temperature * log_ratio;
+        if scaled_log_ratio >= 0.0 || rng.gen::<f64>() < scaled_log_ratio.exp() {
+            for bit in moves[index].iter() {
+                self.error.flip(*bit);
+            }
+            self.log_weight += log_ratio;
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn error(&self) -> &DenseCodeword {
+        &self.error
+    }
+
+    // The log of the probability of the current error at inverse temperature 1
+    // up to a constant shared by all errors.
+    pub(crate) fn log_weight(&self) -> f64 {
+        self.log_weight
+    }
+}
+
+// The log of the probability of the error with the given positions
+// up to a constant shared by all errors.
+pub(crate) fn log_weight(llrs: &[f64], positions: impl Iterator<Item = usize>) -> f64 {
+    -positions.map(|bit| llrs[bit]).sum::<f64>()
+}
@@ -10,23 +10,228 @@
 //! are implemented.
 use std::fmt;
 
+use itertools::Itertools;
+use pauli::{PauliOperator, X, Y, Z};
+use rand::distributions::{Bernoulli, Distribution};
 use rand::Rng;
+use rand_distr::Binomial;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinVec;
+
+mod alias_table;
+
+mod awgn;
+pub use awgn::{AwgnChannel, AwgnOutputs};
 
 mod binary_symmetric_channel;
 pub use binary_symmetric_channel::BinarySymmetricChannel;
 
+mod metropolis;
+pub(crate) use metropolis::MetropolisChain;
+
+mod conditional;
+pub use conditional::ConditionalErrorSampler;
+
+mod burst_event;
+pub use burst_event::BurstEventModel;
+
+mod burst_erasure;
+pub use burst_erasure::BurstErasureChannel;
+
 mod depolarizing;
 pub use depolarizing::DepolarizingNoise;
 
+mod drift;
+pub use drift::{DriftSchedule, DriftingNoise};
+
 mod erasure;
 pub use erasure::ErasureChannel;
 
+mod independent_xz;
+pub use independent_xz::IndependentXZNoise;
+
+mod leakage;
+pub use leakage::{LeakageModel, LeakageState, ResetStrategy};
+
+mod node_failure;
+pub use node_failure::NodeFailureChannel;
+
+mod pauli_channel;
+pub use pauli_channel::{EmpiricalNoiseError, PauliCounts, PauliProbabilities, PerQubitPauliNoise};
+
 pub trait NoiseModel {
     /// The type of the generated errors.
     type Error;
 
     /// Generates a random error of the given length.
     fn sample_error_of_length<R: Rng>(&self, length: usize, rng: &mut R) -> Self::Error;
+
+    /// Generates a random error of the given length and stores it in the buffer.
+    ///
+    /// The default implementation allocates a new error,
+    /// but noise models can override it to reuse the memory of the buffer.
+    /// This avoids an allocation per trial in long simulations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::noise::{BinarySymmetricChannel, NoiseModel, Probability};
+    /// use rand::thread_rng;
+    /// use sparse_bin_mat::SparseBinVec;
+    ///
+    /// let noise = BinarySymmetricChannel::with_probability(Probability::new(0.2));
+    /// let mut error = SparseBinVec::empty();
+    /// for _ in 0..10 {
+    ///     noise.sample_error_into(100, &mut error, &mut thread_rng());
+    ///     assert_eq!(error.len(), 100);
+    /// }
+    /// ```
+    fn sample_error_into<R: Rng>(&self, length: usize, buffer: &mut Self::Error, rng: &mut R) {
+        *buffer = self.sample_error_of_length(length, rng);
+    }
+}
+
+/// A noise model whose errors have a known average weight.
+///
+/// This is separate from [`NoiseModel`] such that noise models
+/// without a closed form for their average weight don't need to provide one.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::noise::{DepolarizingNoise, ExpectedWeight, Probability};
+///
+/// let noise = DepolarizingNoise::with_probability(Probability::new(0.25));
+/// assert_eq!(noise.expected_weight(12), 3.0);
+/// ```
+pub trait ExpectedWeight: NoiseModel {
+    /// Returns the average weight of the errors of the given length.
+    fn expected_weight(&self, length: usize) -> f64;
+}
+
+/// The strategy used to sample the positions of the errors
+/// in noise models acting independently on each position.
+///
+/// Both strategies sample from the same distribution.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum SamplingStrategy {
+    /// Draws a Bernoulli sample for each position.
+    #[default]
+    PerPosition,
+    /// Draws the total weight of the error from a binomial distribution
+    /// and then places the errors uniformly at random.
+    ///
+    /// For Pauli noise, the number of each of X, Y and Z is drawn jointly
+    /// from a multinomial distribution instead.
+    ///
+    /// This is much faster than per position sampling for small probabilities.
+    WeightFirst,
+}
+
+// Samples the sorted positions of a random subset where each position
+// is included independently with the probability of the Bernoulli distribution.
+fn sample_positions<R: Rng>(
+    strategy: SamplingStrategy,
+    distribution: Bernoulli,
+    probability: f64,
+    length: usize,
+    rng: &mut R,
+) -> Vec<usize> {
+    let mut positions = Vec::new();
+    extend_with_sampled_positions(
+        &mut positions,
+        strategy,
+        distribution,
+        probability,
+        length,
+        rng,
+    );
+    positions
+}
+
+// Same as sample_positions, but reuses the memory of the buffer.
+fn sample_positions_into<R: Rng>(
+    strategy: SamplingStrategy,
+    distribution: Bernoulli,
+    probability: f64,
+    length: usize,
+    buffer: &mut SparseBinVec,
+    rng: &mut R,
+) {
+    let mut positions = std::mem::replace(buffer, SparseBinVec::empty()).to_positions_vec();
+    positions.clear();
+    extend_with_sampled_positions(
+        &mut positions,
+        strategy,
+        distribution,
+        probability,
+        length,
+        rng,
+    );
+    *buffer = SparseBinVec::new(length, positions);
+}
+
+fn extend_with_sampled_positions<R: Rng>(
+    positions: &mut Vec<usize>,
+    strategy: SamplingStrategy,
+    distribution: Bernoulli,
+    probability: f64,
+    length: usize,
+    rng: &mut R,
+) {
+    match strategy {
+        SamplingStrategy::PerPosition => positions.extend(
+            distribution
+                .sample_iter(rng)
+                .take(length)
+                .positions(|sample| sample),
+        ),
+        SamplingStrategy::WeightFirst => {
+            let weight = Binomial::new(length as u64, probability)
+                .unwrap()
+                .sample(rng) as usize;
+            positions.extend(rand::seq::index::sample(rng, length, weight).iter());
+            positions.sort_unstable();
+        }
+    }
+}
+
+// Samples a Pauli error where each position gets an X, a Y or a Z
+// with the given probabilities.
+//
+// The numbers of X, Y and Z are drawn from a multinomial distribution
+// as a sequence of binomials conditioned on the previous counts.
+// Then, the Paulis are placed at uniformly random positions.
+fn sample_paulis_weight_first<R: Rng>(
+    probabilities: [f64; 3],
+    length: usize,
+    rng: &mut R,
+) -> PauliOperator {
+    let mut counts = [0; 3];
+    let mut remaining_length = length as u64;
+    let mut remaining_probability = 1.0;
+    for (count, probability) in counts.iter_mut().zip(probabilities) {
+        if remaining_length > 0 && remaining_probability > 0.0 {
+            let conditional_probability = f64::min(probability / remaining_probability, 1.0);
+            *count = Binomial::new(remaining_length, conditional_probability)
+                .unwrap()
+                .sample(rng);
+        }
+        remaining_length -= *count;
+        remaining_probability -= probability;
+    }
+    let paulis = [X, Y, Z]
+        .into_iter()
+        .zip(counts)
+        .flat_map(|(pauli, count)| (0..count).map(move |_| pauli));
+    let weight = counts.iter().sum::<u64>() as usize;
+    let mut errors = rand::seq::index::sample(rng, length, weight)
+        .into_iter()
+        .zip(paulis)
+        .collect::<Vec<_>>();
+    errors.sort_unstable_by_key(|(position, _)| *position);
+    let (positions, paulis) = errors.into_iter().unzip();
+    PauliOperator::new(length, positions, paulis)
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -48,6 +253,88 @@ impl Probability {
     pub fn value(&self) -> f64 {
         self.0
     }
+
+    /// Returns the probability that the event doesn't happen.
+    pub fn complement(self) -> Self {
+        Self(1.0 - self.0)
+    }
+
+    /// Returns the probability that both independent events happen.
+    pub fn and(self, other: Self) -> Self {
+        Self(self.0 * other.0)
+    }
+
+    /// Returns the probability that at least one of two independent events happens.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::noise::Probability;
+    ///
+    /// let probability = Probability::new(0.5).or_independent(Probability::new(0.5));
+    /// assert_eq!(probability, Probability::new(0.75));
+    /// ```
+    pub fn or_independent(self, other: Self) -> Self {
+        self.complement().and(other.complement()).complement()
+    }
+
+    /// Returns the probability that exactly one of two independent events happens.
+    ///
+    /// This is the flip probability of two binary symmetric channels applied in sequence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::noise::Probability;
+    ///
+    /// let probability = Probability::new(0.1).xor_independent(Probability::new(0.2));
+    /// assert!((probability.value() - 0.26).abs() < 1e-12);
+    /// ```
+    pub fn xor_independent(self, other: Self) -> Self {
+        let bias = (1.0 - 2.0 * self.0) * (1.0 - 2.0 * other.0);
+        Self(((1.0 - bias) / 2.0).clamp(0.0, 1.0))
+    }
+
+    /// Returns the log-likelihood ratio `ln((1 - p) / p)`.
+    ///
+    /// This is infinite for the probabilities 0 and 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::noise::Probability;
+    ///
+    /// assert_eq!(Probability::new(0.5).llr(), 0.0);
+    /// assert_eq!(Probability::new(0.0).llr(), f64::INFINITY);
+    /// ```
+    pub fn llr(self) -> f64 {
+        ((1.0 - self.0) / self.0).ln()
+    }
+
+    /// Returns the probability with the given log-likelihood ratio
+    /// or None if the ratio is NaN.
+    ///
+    /// Infinite ratios are mapped to the probabilities 0 and 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::noise::Probability;
+    ///
+    /// let probability = Probability::new(0.1);
+    /// let recovered = Probability::from_llr(probability.llr()).unwrap();
+    /// assert!((recovered.value() - 0.1).abs() < 1e-12);
+    ///
+    /// assert_eq!(Probability::from_llr(f64::NEG_INFINITY), Some(Probability::new(1.0)));
+    /// assert_eq!(Probability::from_llr(f64::NAN), None);
+    /// ```
+    pub fn from_llr(llr: f64) -> Option<Self> {
+        if llr.is_nan() {
+            None
+        } else {
+            Some(Self(1.0 / (1.0 + llr.exp())))
+        }
+    }
 }
 
 impl fmt::Display for Probability {
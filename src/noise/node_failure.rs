@@ -0,0 +1,240 @@
+use super::{ExpectedWeight, NoiseModel, Probability};
+use crate::codes::{CssCode, LinearCode};
+use itertools::Itertools;
+use rand::distributions::{Bernoulli, Distribution};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+use std::fmt;
+
+/// An erasure channel where groups of bits fail together.
+///
+/// Each group, for example all the bits stored on the same storage node,
+/// fails independently with the given probability.
+/// When a group fails, all its bits are erased.
+///
+/// This noise model returns a `SparseBinVec` where
+/// the positions of each 1 is an erasure.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::noise::{NodeFailureChannel, NoiseModel, Probability};
+/// use rand::thread_rng;
+/// use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+///
+/// let nodes = vec![vec![0, 3], vec![1, 4], vec![2, 5]];
+/// let noise = NodeFailureChannel::new(nodes, Probability::new(1.0));
+/// let erasure = noise.sample_error_of_length(6, &mut thread_rng());
+///
+/// assert_eq!(erasure, SparseBinVec::new(6, vec![0, 1, 2, 3, 4, 5]));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeFailureChannel {
+    nodes: Vec<Vec<usize>>,
+    distribution: Bernoulli,
+    probability: f64,
+}
+
+impl NodeFailureChannel {
+    /// Creates a new node failure channel where each of the given groups of bits
+    /// fails with the given probability.
+    ///
+    /// Groups may overlap.
+    pub fn new(nodes: Vec<Vec<usize>>, probability: Probability) -> Self {
+        Bernoulli::new(probability.value())
+            .map(|distribution| Self {
+                nodes,
+                distribution,
+                probability: probability.value(),
+            })
+            .unwrap()
+    }
+
+    /// Creates a new node failure channel where the bits are split
+    /// into consecutive nodes of the given size.
+    ///
+    /// The last node is smaller if the size doesn't divide the length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::noise::{NodeFailureChannel, Probability};
+    /// let noise = NodeFailureChannel::with_contiguous_nodes(7, 3, Probability::new(0.1));
+    ///
+    /// assert_eq!(noise.nodes(), &[vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the node size is 0.
+    pub fn with_contiguous_nodes(
+        length: usize,
+        node_size: usize,
+        probability: Probability,
+    ) -> Self {
+        if node_size == 0 {
+            panic!("node size must be positive");
+        }
+        let nodes = (0..length)
+            .chunks(node_size)
+            .into_iter()
+            .map(|node| node.collect())
+            .collect();
+        Self::new(nodes, probability)
+    }
+
+    /// Creates a new node failure channel where the support of each check of the code
+    /// fails with the given probability.
+    ///
+    /// This models the loss of all the bits involved in the measurement of a check,
+    /// for example when the ancilla measuring it disturbs its neighbors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::noise::{NodeFailureChannel, Probability};
+    /// use ldpc::simulation::ErasureExperiment;
+    /// use rand::thread_rng;
+    ///
+    /// // The checks of the Hamming code are also codewords,
+    /// // thus erasing the support of any check is never repaired.
+    /// let code = LinearCode::hamming_code();
+    /// let noise = NodeFailureChannel::with_check_supports(&code, Probability::new(0.1));
+    /// let recovery = ErasureExperiment::new(&code, &noise).run_with(2000, &mut thread_rng());
+    ///
+    /// assert_eq!(noise.nodes()[0], vec![3, 4, 5, 6]);
+    /// assert!((recovery.failure_rate() - (1.0 - 0.9_f64.powi(3))).abs() < 0.05);
+    /// ```
+    pub fn with_check_supports(code: &LinearCode, probability: Probability) -> Self {
+        Self::new(rows_of(code.parity_check_matrix()), probability)
+    }
+
+    /// Creates a new node failure channel where each bit of the code fails
+    /// together with its neighborhood with the given probability.
+    ///
+    /// The neighborhood of a bit is the bits sharing a check with it,
+    /// that is the bits at distance 2 in the Tanner graph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::noise::{NodeFailureChannel, Probability};
+    ///
+    /// let code = LinearCode::repetition_code(4);
+    /// let noise = NodeFailureChannel::with_bit_neighborhoods(&code, Probability::new(0.1));
+    ///
+    /// assert_eq!(noise.nodes()[0], vec![0, 1]);
+    /// assert_eq!(noise.nodes()[2], vec![1, 2, 3]);
+    /// ```
+    pub fn with_bit_neighborhoods(code: &LinearCode, probability: Probability) -> Self {
+        let nodes = (0..code.len())
+            .map(|bit| {
+                code.checks_adjacent_to_bit(bit)
+                    .unwrap()
+                    .non_trivial_positions()
+                    .flat_map(|check| {
+                        code.check(check)
+                            .unwrap()
+                            .non_trivial_positions()
+                            .collect::<Vec<_>>()
+                    })
+                    .chain(std::iter::once(bit))
+                    .sorted()
+                    .dedup()
+                    .collect()
+            })
+            .collect();
+        Self::new(nodes, probability)
+    }
+
+    /// Creates a new node failure channel where the support of each stabilizer
+    /// of the code fails with the given probability.
+    ///
+    /// The nodes are the supports of the X stabilizers followed by the Z stabilizers.
+    /// This models correlated loss events on a patch of qubits,
+    /// such as a burst of radiation, to be repaired by the
+    /// [CSS erasure decoder](crate::decoders::CssErasureDecoder).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::noise::{NodeFailureChannel, Probability};
+    ///
+    /// let code = CssCode::steane_code();
+    /// let noise = NodeFailureChannel::with_stabilizer_supports(&code, Probability::new(0.1));
+    ///
+    /// assert_eq!(noise.nodes().len(), 6);
+    /// assert_eq!(noise.nodes()[0], noise.nodes()[3]);
+    /// ```
+    pub fn with_stabilizer_supports(code: &CssCode, probability: Probability) -> Self {
+        let nodes = rows_of(code.x_stabs_binary())
+            .into_iter()
+            .chain(rows_of(code.z_stabs_binary()))
+            .collect();
+        Self::new(nodes, probability)
+    }
+
+    /// Returns the groups of bits failing together.
+    pub fn nodes(&self) -> &[Vec<usize>] {
+        &self.nodes
+    }
+}
+
+impl NoiseModel for NodeFailureChannel {
+    type Error = SparseBinVec;
+
+    /// Generates a random erasure of the given length.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a node contains a bit outside the given length.
+    fn sample_error_of_length<R: Rng>(&self, length: usize, rng: &mut R) -> Self::Error {
+        let positions = self
+            .nodes
+            .iter()
+            .filter(|_| self.distribution.sample(rng))
+            .flatten()
+            .cloned()
+            .sorted()
+            .dedup()
+            .collect();
+        SparseBinVec::try_new(length, positions).expect("node contains a bit out of bound")
+    }
+}
+
+impl ExpectedWeight for NodeFailureChannel {
+    /// A bit is erased if any of the nodes containing it fails.
+    fn expected_weight(&self, length: usize) -> f64 {
+        let mut num_nodes_per_bit = vec![0; length];
+        for bit in self.nodes.iter().flatten().filter(|bit| **bit < length) {
+            num_nodes_per_bit[*bit] += 1;
+        }
+        num_nodes_per_bit
+            .into_iter()
+            .map(|num_nodes| 1.0 - (1.0 - self.probability).powi(num_nodes))
+            .sum()
+    }
+}
+
+// The positions of the ones in each row of the matrix.
+fn rows_of(matrix: &SparseBinMat) -> Vec<Vec<usize>> {
+    matrix
+        .rows()
+        .map(|row| row.non_trivial_positions().collect())
+        .collect()
+}
+
+impl fmt::Display for NodeFailureChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "NodeFailure({}, {} nodes)",
+            self.probability,
+            self.nodes.len()
+        )
+    }
+}
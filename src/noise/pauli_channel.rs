@@ -0,0 +1,375 @@
+use super::alias_table::AliasTable;
+use super::{ExpectedWeight, NoiseModel};
+use pauli::{Pauli, PauliOperator, X, Y, Z};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// The rounding error allowed on the sum of the probabilities,
+// such as for frequencies of counts without identity.
+const SUM_TOLERANCE: f64 = 1e-12;
+
+/// The probabilities of applying each non-trivial Pauli on a qubit.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedPauliProbabilities")]
+pub struct PauliProbabilities {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl PauliProbabilities {
+    /// Creates the probabilities of applying X, Y and Z.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a probability is negative
+    /// or if they sum to more than 1 beyond rounding errors.
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self::try_new(x, y, z).expect("invalid Pauli probabilities")
+    }
+
+    /// Creates the probabilities of applying X, Y and Z or returns None
+    /// if a probability is negative or if they sum to more than 1 beyond rounding errors.
+    pub fn try_new(x: f64, y: f64, z: f64) -> Option<Self> {
+        if x >= 0.0 && y >= 0.0 && z >= 0.0 && x + y + z <= 1.0 + SUM_TOLERANCE {
+            Some(Self { x, y, z })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the probability of applying X.
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// Returns the probability of applying Y.
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    /// Returns the probability of applying Z.
+    pub fn z(&self) -> f64 {
+        self.z
+    }
+
+    /// Returns the probability of applying a non-trivial Pauli.
+    pub fn total(&self) -> f64 {
+        self.x + self.y + self.z
+    }
+}
+
+// The fields of serialized Pauli probabilities before they are validated.
+#[derive(Deserialize)]
+struct UncheckedPauliProbabilities {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl TryFrom<UncheckedPauliProbabilities> for PauliProbabilities {
+    type Error = String;
+
+    fn try_from(probs: UncheckedPauliProbabilities) -> Result<Self, Self::Error> {
+        Self::try_new(probs.x, probs.y, probs.z).ok_or_else(|| {
+            format!(
+                "invalid Pauli probabilities x = {}, y = {} and z = {}",
+                probs.x, probs.y, probs.z
+            )
+        })
+    }
+}
+
+/// A Pauli channel where each qubit has its own Pauli probabilities.
+///
+/// The probabilities are preprocessed into alias tables
+/// such that sampling a Pauli takes constant time for each qubit
+/// whatever the distribution.
+/// Only the probabilities are serialized and the tables are rebuilt when deserialized.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::noise::{ExpectedWeight, NoiseModel, PauliProbabilities, PerQubitPauliNoise};
+/// use pauli::{PauliOperator, X, Z};
+/// use rand::thread_rng;
+///
+/// let noise = PerQubitPauliNoise::new(vec![
+///     PauliProbabilities::new(1.0, 0.0, 0.0),
+///     PauliProbabilities::new(0.0, 0.0, 0.0),
+///     PauliProbabilities::new(0.0, 0.0, 1.0),
+/// ]);
+/// let error = noise.sample_error_of_length(3, &mut thread_rng());
+///
+/// assert_eq!(error, PauliOperator::new(3, vec![0, 2], vec![X, Z]));
+/// assert_eq!(noise.expected_weight(3), 2.0);
+/// ```
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(from = "UncheckedPerQubitPauliNoise")]
+pub struct PerQubitPauliNoise {
+    probabilities: Vec<PauliProbabilities>,
+    #[serde(skip_serializing)]
+    tables: Vec<AliasTable>,
+}
+
+// The probabilities of a serialized channel whose tables are rebuilt.
+#[derive(Deserialize)]
+struct UncheckedPerQubitPauliNoise {
+    probabilities: Vec<PauliProbabilities>,
+}
+
+impl From<UncheckedPerQubitPauliNoise> for PerQubitPauliNoise {
+    fn from(noise: UncheckedPerQubitPauliNoise) -> Self {
+        Self::new(noise.probabilities)
+    }
+}
+
+impl PerQubitPauliNoise {
+    /// Creates a new channel with the given probabilities for each qubit.
+    pub fn new(probabilities: Vec<PauliProbabilities>) -> Self {
+        let tables = probabilities
+            .iter()
+            .map(|probs| {
+                AliasTable::new(&[(1.0 - probs.total()).max(0.0), probs.x, probs.y, probs.z])
+            })
+            .collect();
+        Self {
+            probabilities,
+            tables,
+        }
+    }
+
+    /// Returns the number of qubits of the channel.
+    pub fn num_qubits(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    /// Returns the Pauli probabilities of each qubit.
+    pub fn probabilities(&self) -> &[PauliProbabilities] {
+        &self.probabilities
+    }
+
+    fn assert_length(&self, length: usize) {
+        if length != self.num_qubits() {
+            panic!(
+                "length {} is different from the {} qubits of the channel",
+                length,
+                self.num_qubits()
+            );
+        }
+    }
+
+    /// Creates the empirical channel whose probabilities are the observed frequencies
+    /// of each Pauli on each qubit.
+    ///
+    /// Returns an error if a qubit has no sample.
+    pub fn from_counts(counts: &[PauliCounts]) -> Result<Self, EmpiricalNoiseError> {
+        counts
+            .iter()
+            .enumerate()
+            .map(|(qubit, counts)| {
+                counts
+                    .frequencies()
+                    .ok_or(EmpiricalNoiseError::NoSample(qubit))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::new)
+    }
+
+    /// Creates the empirical channel from the counts of each qubit in JSON.
+    ///
+    /// The counts are given as a list of objects
+    /// with the number of times I, X, Y and Z were observed,
+    /// where missing counts are 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ldpc::noise::{ExpectedWeight, PerQubitPauliNoise};
+    ///
+    /// let json = r#"{
+    ///     "qubits": [
+    ///         { "i": 970, "x": 10, "y": 5, "z": 15 },
+    ///         { "i": 1000 },
+    ///         { "i": 490, "z": 10 }
+    ///     ]
+    /// }"#;
+    /// let noise = PerQubitPauliNoise::from_json(json).unwrap();
+    ///
+    /// assert_eq!(noise.num_qubits(), 3);
+    /// assert_eq!(noise.probabilities()[0].x(), 0.01);
+    /// assert_eq!(noise.probabilities()[2].z(), 0.02);
+    /// assert!((noise.expected_weight(3) - 0.05).abs() < 1e-12);
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, EmpiricalNoiseError> {
+        let counts: QubitCounts = serde_json::from_str(json)
+            .map_err(|error| EmpiricalNoiseError::InvalidJson(error.to_string()))?;
+        Self::from_counts(&counts.qubits)
+    }
+}
+
+/// The number of times each Pauli was observed on a qubit,
+/// for example in a calibration experiment.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct PauliCounts {
+    #[serde(default)]
+    pub i: u64,
+    #[serde(default)]
+    pub x: u64,
+    #[serde(default)]
+    pub y: u64,
+    #[serde(default)]
+    pub z: u64,
+}
+
+impl PauliCounts {
+    /// Returns the number of samples.
+    pub fn total(&self) -> u64 {
+        self.i + self.x + self.y + self.z
+    }
+
+    /// Returns the frequency of each non-trivial Pauli or None if there is no sample.
+    pub fn frequencies(&self) -> Option<PauliProbabilities> {
+        let total = self.total() as f64;
+        (self.total() > 0).then(|| {
+            PauliProbabilities::new(
+                self.x as f64 / total,
+                self.y as f64 / total,
+                self.z as f64 / total,
+            )
+        })
+    }
+}
+
+// The schema of the counts in JSON.
+#[derive(Deserialize)]
+struct QubitCounts {
+    qubits: Vec<PauliCounts>,
+}
+
+/// An error when building an empirical noise model.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EmpiricalNoiseError {
+    InvalidJson(String),
+    NoSample(usize),
+}
+
+impl fmt::Display for EmpiricalNoiseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidJson(error) => write!(f, "invalid counts: {}", error),
+            Self::NoSample(qubit) => write!(f, "qubit {} has no sample", qubit),
+        }
+    }
+}
+
+impl std::error::Error for EmpiricalNoiseError {}
+
+impl NoiseModel for PerQubitPauliNoise {
+    type Error = PauliOperator;
+
+    /// Generates a random error of the given length.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length is different from the number of qubits of the channel.
+    fn sample_error_of_length<R: Rng>(&self, length: usize, rng: &mut R) -> Self::Error {
+        self.assert_length(length);
+        let (positions, paulis) = self
+            .tables
+            .iter()
+            .enumerate()
+            .filter_map(|(position, table)| {
+                pauli_from_outcome(table.sample(rng)).map(|pauli| (position, pauli))
+            })
+            .unzip();
+        PauliOperator::new(length, positions, paulis)
+    }
+}
+
+impl ExpectedWeight for PerQubitPauliNoise {
+    /// Returns the average weight of the errors of the given length.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length is different from the number of qubits of the channel.
+    fn expected_weight(&self, length: usize) -> f64 {
+        self.assert_length(length);
+        self.probabilities
+            .iter()
+            .map(PauliProbabilities::total)
+            .sum()
+    }
+}
+
+// The outcomes of the alias tables are in the order I, X, Y, Z.
+fn pauli_from_outcome(outcome: usize) -> Option<Pauli> {
+    match outcome {
+        1 => Some(X),
+        2 => Some(Y),
+        3 => Some(Z),
+        _ => None,
+    }
+}
+
+impl fmt::Display for PerQubitPauliNoise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Per-qubit Pauli noise ({} qubits)", self.num_qubits())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn frequencies_of_a_qubit_that_always_errs() {
+        let counts = PauliCounts {
+            i: 0,
+            x: 6,
+            y: 23,
+            z: 1,
+        };
+        let noise = PerQubitPauliNoise::from_counts(&[counts]).unwrap();
+        let probabilities = noise.probabilities()[0];
+        assert!((probabilities.total() - 1.0).abs() < 1e-12);
+        assert!((probabilities.y() - 23.0 / 30.0).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn expected_weight_requires_one_qubit_per_position() {
+        let noise = PerQubitPauliNoise::new(vec![PauliProbabilities::new(0.1, 0.0, 0.0); 3]);
+        noise.expected_weight(2);
+    }
+
+    #[test]
+    fn deserialized_channel_is_validated_and_rebuilt() {
+        let noise = PerQubitPauliNoise::from_counts(&[
+            PauliCounts {
+                i: 0,
+                x: 6,
+                y: 23,
+                z: 1,
+            },
+            PauliCounts {
+                i: 90,
+                x: 10,
+                y: 0,
+                z: 0,
+            },
+        ])
+        .unwrap();
+        let json = serde_json::to_string(&noise).unwrap();
+        assert!(!json.contains("tables"));
+        assert_eq!(
+            serde_json::from_str::<PerQubitPauliNoise>(&json).unwrap(),
+            noise
+        );
+        let invalid = r#"{"probabilities":[{"x":0.5,"y":0.5,"z":0.5}]}"#;
+        assert!(serde_json::from_str::<PerQubitPauliNoise>(invalid).is_err());
+        let negative = r#"{"x":-0.1,"y":0.0,"z":0.0}"#;
+        assert!(serde_json::from_str::<PauliProbabilities>(negative).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use crate::noise::{ExpectedWeight, NoiseModel, Probability};
+use rand::distributions::{Bernoulli, Distribution};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A q-ary symmetric channel replaces each symbol by one
+/// of the `q - 1` other symbols with the given probability.
+///
+/// This noise model returns a dense vector of field elements
+/// where each non zero symbol is added to the transmitted word.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::nonbinary::QarySymmetricChannel;
+/// use ldpc::noise::{NoiseModel, Probability};
+/// use rand::thread_rng;
+///
+/// let noise = QarySymmetricChannel::new(4, Probability::new(1.0));
+/// let error = noise.sample_error_of_length(10, &mut thread_rng());
+///
+/// assert!(error.iter().all(|symbol| *symbol > 0 && *symbol < 4));
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct QarySymmetricChannel {
+    order: usize,
+    distribution: Bernoulli,
+    probability: f64,
+}
+
+impl QarySymmetricChannel {
+    /// Creates a new channel over an alphabet with the given number of symbols.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the order is less than 2.
+    pub fn new(order: usize, probability: Probability) -> Self {
+        if order < 2 {
+            panic!("alphabet must have at least 2 symbols");
+        }
+        Bernoulli::new(probability.value())
+            .map(|distribution| Self {
+                order,
+                distribution,
+                probability: probability.value(),
+            })
+            .unwrap()
+    }
+
+    /// Returns the number of symbols in the alphabet.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+}
+
+impl NoiseModel for QarySymmetricChannel {
+    type Error = Vec<usize>;
+
+    fn sample_error_of_length<R: Rng>(&self, length: usize, rng: &mut R) -> Self::Error {
+        (0..length)
+            .map(|_| {
+                if self.distribution.sample(rng) {
+                    rng.gen_range(1..self.order)
+                } else {
+                    0
+                }
+            })
+            .collect()
+    }
+}
+
+impl ExpectedWeight for QarySymmetricChannel {
+    fn expected_weight(&self, length: usize) -> f64 {
+        self.probability * length as f64
+    }
+}
+
+impl fmt::Display for QarySymmetricChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "QarySymmetric({}, {})", self.order, self.probability)
+    }
+}
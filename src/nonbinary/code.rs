@@ -0,0 +1,153 @@
+use super::GaloisField;
+use crate::codes::LinearCode;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// A linear code over a finite field defined by a sparse parity check matrix.
+///
+/// Each check is a list of pairs of bits and non zero coefficients.
+/// A word is a codeword if, for each check, the sum of its symbols
+/// weighted by the coefficients is 0.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::nonbinary::{GaloisField, NonBinaryCode};
+///
+/// let field = GaloisField::new(3);
+/// let code = NonBinaryCode::new(field, 3, vec![vec![(0, 1), (1, 2)], vec![(1, 1), (2, 1)]]);
+///
+/// assert!(code.has_codeword(&[1, 1, 2]));
+/// assert_eq!(code.syndrome_of(&[1, 0, 0]), vec![1, 0]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NonBinaryCode {
+    field: GaloisField,
+    num_bits: usize,
+    checks: Vec<Vec<(usize, usize)>>,
+}
+
+impl NonBinaryCode {
+    /// Creates a code from the coefficients of each check.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a bit is out of bound, if a bit appears twice in a check
+    /// or if a coefficient is 0 or not in the field.
+    pub fn new(field: GaloisField, num_bits: usize, checks: Vec<Vec<(usize, usize)>>) -> Self {
+        let checks = checks
+            .into_iter()
+            .map(|mut check| {
+                check.sort_unstable();
+                for (bit, coefficient) in check.iter() {
+                    if *bit >= num_bits {
+                        panic!("bit {} is out of bound for {} bits", bit, num_bits);
+                    }
+                    if *coefficient == 0 || !field.contains(*coefficient) {
+                        panic!("invalid coefficient {} for {}", coefficient, field);
+                    }
+                }
+                if check.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+                    panic!("a bit appears twice in the same check");
+                }
+                check
+            })
+            .collect();
+        Self {
+            field,
+            num_bits,
+            checks,
+        }
+    }
+
+    /// Creates a code with the same Tanner graph as the binary code
+    /// where each edge has a uniformly random non zero coefficient.
+    ///
+    /// This is the usual way to build non-binary LDPC codes from sampled binary codes.
+    pub fn with_random_coefficients<R: Rng>(
+        code: &LinearCode,
+        field: GaloisField,
+        rng: &mut R,
+    ) -> Self {
+        let checks = code
+            .parity_check_matrix()
+            .rows()
+            .map(|check| {
+                check
+                    .non_trivial_positions()
+                    .map(|bit| (bit, rng.gen_range(1..field.order())))
+                    .collect()
+            })
+            .collect();
+        Self::new(field, code.len(), checks)
+    }
+
+    /// Returns the field of the code.
+    pub fn field(&self) -> &GaloisField {
+        &self.field
+    }
+
+    /// Returns the number of bits in the code.
+    pub fn len(&self) -> usize {
+        self.num_bits
+    }
+
+    /// Checks if the code has no bit.
+    pub fn is_empty(&self) -> bool {
+        self.num_bits == 0
+    }
+
+    /// Returns the number of checks in the code.
+    pub fn num_checks(&self) -> usize {
+        self.checks.len()
+    }
+
+    /// Returns the bits and coefficients of the given check
+    /// or None if the check is out of bound.
+    pub fn check(&self, index: usize) -> Option<&[(usize, usize)]> {
+        self.checks.get(index).map(|check| check.as_slice())
+    }
+
+    /// Returns an iterator over the bits and coefficients of each check.
+    pub fn checks(&self) -> impl Iterator<Item = &[(usize, usize)]> {
+        self.checks.iter().map(|check| check.as_slice())
+    }
+
+    /// Returns the weighted sum of the symbols of the word for each check.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the word length is different from the code length
+    /// or if a symbol is not in the field.
+    pub fn syndrome_of(&self, word: &[usize]) -> Vec<usize> {
+        if word.len() != self.len() {
+            panic!(
+                "word of length {} for code of length {}",
+                word.len(),
+                self.len()
+            );
+        }
+        if let Some(symbol) = word.iter().find(|symbol| !self.field.contains(**symbol)) {
+            panic!("symbol {} is not in {}", symbol, self.field);
+        }
+        self.checks
+            .iter()
+            .map(|check| {
+                check.iter().fold(0, |sum, (bit, coefficient)| {
+                    self.field
+                        .add(sum, self.field.mul(*coefficient, word[*bit]))
+                })
+            })
+            .collect()
+    }
+
+    /// Checks if the word satisfies all checks.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the word length is different from the code length
+    /// or if a symbol is not in the field.
+    pub fn has_codeword(&self, word: &[usize]) -> bool {
+        self.syndrome_of(word).into_iter().all(|value| value == 0)
+    }
+}
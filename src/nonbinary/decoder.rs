@@ -0,0 +1,261 @@
+use super::{GaloisField, NonBinaryCode};
+use crate::decoders::SyndromeDecoder;
+use crate::noise::Probability;
+
+/// A belief propagation decoder for codes over finite fields.
+///
+/// Messages are probability distributions over the field elements.
+/// Check updates combine the distributions of the other bits
+/// with forward and backward convolutions over the field,
+/// such that a check of degree d costs `O(d q^2)` operations.
+///
+/// The prior assumes a q-ary symmetric channel where each symbol
+/// is replaced by one of the `q - 1` other symbols with the given probability.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::decoders::SyndromeDecoder;
+/// use ldpc::nonbinary::{GaloisField, NonBinaryBpDecoder, NonBinaryCode};
+/// use ldpc::noise::Probability;
+///
+/// let field = GaloisField::new(4);
+/// let checks = vec![vec![(0, 1), (1, 2)], vec![(1, 1), (2, 3)]];
+/// let code = NonBinaryCode::new(field, 3, checks);
+/// let decoder = NonBinaryBpDecoder::new(&code, Probability::new(0.1), 10);
+///
+/// let error = vec![0, 3, 0];
+/// let syndrome = code.syndrome_of(&error);
+/// assert_eq!(decoder.correction_for(&syndrome), error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NonBinaryBpDecoder {
+    code: NonBinaryCode,
+    prior: Vec<f64>,
+    num_iterations: usize,
+    // The checks and positions in these checks of each bit.
+    bit_edges: Vec<Vec<(usize, usize)>>,
+}
+
+impl NonBinaryBpDecoder {
+    pub fn new(code: &NonBinaryCode, probability: Probability, num_iterations: usize) -> Self {
+        let order = code.field().order();
+        let mut prior = vec![probability.value() / (order - 1) as f64; order];
+        prior[0] = 1.0 - probability.value();
+        let mut bit_edges = vec![Vec::new(); code.len()];
+        for (check, bits) in code.checks().enumerate() {
+            for (position, (bit, _)) in bits.iter().enumerate() {
+                bit_edges[*bit].push((check, position));
+            }
+        }
+        Self {
+            code: code.clone(),
+            prior,
+            num_iterations,
+            bit_edges,
+        }
+    }
+
+    fn field(&self) -> &GaloisField {
+        self.code.field()
+    }
+
+    fn order(&self) -> usize {
+        self.field().order()
+    }
+
+    // The message from a check to each of its bits is the distribution of the
+    // value the bit must take to satisfy the check given the other bits.
+    fn update_checks(
+        &self,
+        bit_messages: &[Vec<Vec<f64>>],
+        syndrome: &[usize],
+    ) -> Vec<Vec<Vec<f64>>> {
+        self.code
+            .checks()
+            .zip(bit_messages)
+            .zip(syndrome)
+            .map(|((bits, messages), value)| {
+                // Distributions of coefficient * symbol for each bit.
+                let weighted = bits
+                    .iter()
+                    .zip(messages)
+                    .map(|((_, coefficient), message)| {
+                        let mut distribution = vec![0.0; self.order()];
+                        for (symbol, probability) in message.iter().enumerate() {
+                            distribution[self.field().mul(*coefficient, symbol)] = *probability;
+                        }
+                        distribution
+                    })
+                    .collect::<Vec<_>>();
+                let mut forward = vec![self.delta()];
+                for distribution in weighted.iter() {
+                    forward.push(self.convolve(forward.last().unwrap(), distribution));
+                }
+                let mut backward = vec![self.delta()];
+                for distribution in weighted.iter().rev() {
+                    backward.push(self.convolve(backward.last().unwrap(), distribution));
+                }
+                backward.reverse();
+                bits.iter()
+                    .enumerate()
+                    .map(|(position, (_, coefficient))| {
+                        let others = self.convolve(&forward[position], &backward[position + 1]);
+                        let message = (0..self.order())
+                            .map(|symbol| {
+                                let needed = self
+                                    .field()
+                                    .sub(*value, self.field().mul(*coefficient, symbol));
+                                others[needed]
+                            })
+                            .collect();
+                        normalized(message)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn update_bits(&self, check_messages: &[Vec<Vec<f64>>]) -> Vec<Vec<Vec<f64>>> {
+        let mut bit_messages = check_messages.to_vec();
+        for edges in self.bit_edges.iter() {
+            for (check, position) in edges.iter() {
+                let message = (0..self.order())
+                    .map(|symbol| {
+                        edges
+                            .iter()
+                            .filter(|edge| *edge != &(*check, *position))
+                            .map(|(other, other_position)| {
+                                check_messages[*other][*other_position][symbol]
+                            })
+                            .product::<f64>()
+                            * self.prior[symbol]
+                    })
+                    .collect();
+                bit_messages[*check][*position] = normalized(message);
+            }
+        }
+        bit_messages
+    }
+
+    fn decide(&self, check_messages: &[Vec<Vec<f64>>]) -> Vec<usize> {
+        self.bit_edges
+            .iter()
+            .map(|edges| {
+                let posterior = (0..self.order()).map(|symbol| {
+                    edges
+                        .iter()
+                        .map(|(check, position)| check_messages[*check][*position][symbol])
+                        .product::<f64>()
+                        * self.prior[symbol]
+                });
+                // The first maximum is kept such that ties favor 0.
+                posterior
+                    .enumerate()
+                    .fold((0, f64::NEG_INFINITY), |best, (symbol, probability)| {
+                        if probability > best.1 {
+                            (symbol, probability)
+                        } else {
+                            best
+                        }
+                    })
+                    .0
+            })
+            .collect()
+    }
+
+    fn delta(&self) -> Vec<f64> {
+        let mut delta = vec![0.0; self.order()];
+        delta[0] = 1.0;
+        delta
+    }
+
+    // The distribution of the sum of two independent field elements.
+    fn convolve(&self, first: &[f64], second: &[f64]) -> Vec<f64> {
+        let mut result = vec![0.0; self.order()];
+        for (a, p) in first.iter().enumerate().filter(|(_, p)| **p > 0.0) {
+            for (b, q) in second.iter().enumerate() {
+                result[self.field().add(a, b)] += p * q;
+            }
+        }
+        result
+    }
+}
+
+fn normalized(mut distribution: Vec<f64>) -> Vec<f64> {
+    let total: f64 = distribution.iter().sum();
+    if total > 0.0 {
+        for probability in distribution.iter_mut() {
+            *probability /= total;
+        }
+    }
+    distribution
+}
+
+impl<'a> SyndromeDecoder<&'a [usize], Vec<usize>> for NonBinaryBpDecoder {
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    fn correction_for(&self, syndrome: &'a [usize]) -> Vec<usize> {
+        if syndrome.len() != self.code.num_checks() {
+            panic!(
+                "syndrome of length {} for {} checks",
+                syndrome.len(),
+                self.code.num_checks()
+            );
+        }
+        let mut bit_messages = self
+            .code
+            .checks()
+            .map(|bits| vec![self.prior.clone(); bits.len()])
+            .collect::<Vec<_>>();
+        let mut correction = vec![0; self.code.len()];
+        for _ in 0..self.num_iterations {
+            if self.code.syndrome_of(&correction) == syndrome {
+                break;
+            }
+            let check_messages = self.update_checks(&bit_messages, syndrome);
+            correction = self.decide(&check_messages);
+            bit_messages = self.update_bits(&check_messages);
+        }
+        correction
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn no_error_gives_no_correction() {
+        let code = NonBinaryCode::new(GaloisField::new(5), 3, vec![vec![(0, 1), (1, 2), (2, 3)]]);
+        let decoder = NonBinaryBpDecoder::new(&code, Probability::new(0.1), 10);
+        assert_eq!(decoder.correction_for(&[0]), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn single_symbol_errors_are_corrected_on_random_code() {
+        let binary_code = LinearCode::random_regular_code()
+            .num_bits(16)
+            .num_checks(12)
+            .bit_degree(3)
+            .check_degree(4)
+            .sample_with(&mut StdRng::seed_from_u64(123))
+            .unwrap();
+        let code = NonBinaryCode::with_random_coefficients(
+            &binary_code,
+            GaloisField::new(8),
+            &mut StdRng::seed_from_u64(123),
+        );
+        let decoder = NonBinaryBpDecoder::new(&code, Probability::new(0.05), 20);
+        for bit in [0, 5, 11] {
+            let mut error = vec![0; 16];
+            error[bit] = 6;
+            let correction = decoder.correction_for(&code.syndrome_of(&error));
+            assert_eq!(code.syndrome_of(&correction), code.syndrome_of(&error));
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A finite field with a prime power number of elements.
+///
+/// Elements are represented by integers from 0 to `order - 1`.
+/// The base `p` digits of an element are the coefficients of a polynomial
+/// over the prime field GF(p), starting with the constant coefficient.
+/// Thus, 0 and 1 are always the additive and multiplicative identities
+/// and, for a prime field, elements are simply integers modulo p.
+///
+/// Multiplications use logarithm tables built from a primitive polynomial
+/// found when creating the field.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::nonbinary::GaloisField;
+///
+/// let field = GaloisField::new(4);
+///
+/// assert_eq!(field.characteristic(), 2);
+/// assert_eq!(field.add(2, 3), 1);
+/// assert_eq!(field.mul(2, 2), 3);
+/// assert_eq!(field.mul(3, field.inv(3)), 1);
+/// ```
+///
+/// A field is serialized as its order and rebuilt when deserialized.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+#[serde(try_from = "usize", into = "usize")]
+pub struct GaloisField {
+    characteristic: usize,
+    degree: u32,
+    exp: Vec<usize>,
+    log: Vec<usize>,
+}
+
+impl GaloisField {
+    /// Creates the field with the given number of elements.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the order is not a prime power.
+    pub fn new(order: usize) -> Self {
+        Self::try_new(order).unwrap_or_else(|| panic!("{} is not a prime power", order))
+    }
+
+    /// Creates the field with the given number of elements
+    /// or returns None if the order is not a prime power.
+    pub fn try_new(order: usize) -> Option<Self> {
+        let (characteristic, degree) = prime_power_decomposition(order)?;
+        let mut field = Self {
+            characteristic,
+            degree,
+            exp: Vec::new(),
+            log: Vec::new(),
+        };
+        // Polynomials x^m + g(x) are tried until x generates all non zero elements.
+        for reduction in 1..order {
+            if reduction % characteristic == 0 {
+                continue;
+            }
+            if let Some(exp) = field.powers_of_x(reduction) {
+                let mut log = vec![0; order];
+                for (power, element) in exp.iter().enumerate() {
+                    log[*element] = power;
+                }
+                field.exp = exp;
+                field.log = log;
+                return Some(field);
+            }
+        }
+        None
+    }
+
+    // Returns the successive powers of x modulo x^m + g(x) where the digits of reduction
+    // are the coefficients of g or None if x doesn't generate the multiplicative group.
+    fn powers_of_x(&self, reduction: usize) -> Option<Vec<usize>> {
+        let mut powers = Vec::with_capacity(self.order() - 1);
+        let mut is_visited = vec![false; self.order()];
+        let mut element = 1;
+        while !is_visited[element] {
+            is_visited[element] = true;
+            powers.push(element);
+            element = self.times_x(element, reduction);
+        }
+        if element == 1 && powers.len() == self.order() - 1 {
+            Some(powers)
+        } else {
+            None
+        }
+    }
+
+    fn times_x(&self, element: usize, reduction: usize) -> usize {
+        let p = self.characteristic;
+        let top = self.order() / p;
+        let overflow = element / top;
+        let shifted = (element % top) * p;
+        let correction = self.mul_by_scalar(reduction, (p - overflow) % p);
+        self.add(shifted, correction)
+    }
+
+    fn mul_by_scalar(&self, element: usize, scalar: usize) -> usize {
+        self.map_digits(element, 0, |digit, _| digit * scalar)
+    }
+
+    // Applies the function to each pair of digits and reduces the result modulo p.
+    fn map_digits<F>(&self, first: usize, second: usize, func: F) -> usize
+    where
+        F: Fn(usize, usize) -> usize,
+    {
+        let p = self.characteristic;
+        let (mut first, mut second) = (first, second);
+        let mut result = 0;
+        let mut place = 1;
+        for _ in 0..self.degree {
+            result += (func(first % p, second % p) % p) * place;
+            first /= p;
+            second /= p;
+            place *= p;
+        }
+        result
+    }
+
+    /// Returns the number of elements of the field.
+    pub fn order(&self) -> usize {
+        self.characteristic.pow(self.degree)
+    }
+
+    /// Returns the characteristic p of the field.
+    pub fn characteristic(&self) -> usize {
+        self.characteristic
+    }
+
+    /// Returns the degree m of the field as an extension of GF(p).
+    pub fn degree(&self) -> u32 {
+        self.degree
+    }
+
+    /// Returns the sum of the two elements.
+    pub fn add(&self, first: usize, second: usize) -> usize {
+        if self.characteristic == 2 {
+            first ^ second
+        } else {
+            self.map_digits(first, second, |a, b| a + b)
+        }
+    }
+
+    /// Returns the additive inverse of the element.
+    pub fn neg(&self, element: usize) -> usize {
+        let p = self.characteristic;
+        self.map_digits(element, 0, |digit, _| p - digit)
+    }
+
+    /// Returns the difference of the two elements.
+    pub fn sub(&self, first: usize, second: usize) -> usize {
+        self.add(first, self.neg(second))
+    }
+
+    /// Returns the product of the two elements.
+    pub fn mul(&self, first: usize, second: usize) -> usize {
+        if first == 0 || second == 0 {
+            return 0;
+        }
+        self.exp[(self.log[first] + self.log[second]) % (self.order() - 1)]
+    }
+
+    /// Returns the multiplicative inverse of the element.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the element is 0.
+    pub fn inv(&self, element: usize) -> usize {
+        if element == 0 {
+            panic!("0 has no inverse");
+        }
+        self.exp[(self.order() - 1 - self.log[element]) % (self.order() - 1)]
+    }
+
+    /// Returns the quotient of the two elements.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the divisor is 0.
+    pub fn div(&self, dividend: usize, divisor: usize) -> usize {
+        self.mul(dividend, self.inv(divisor))
+    }
+
+    /// Checks if the integer represents an element of the field.
+    pub fn contains(&self, element: usize) -> bool {
+        element < self.order()
+    }
+}
+
+impl fmt::Display for GaloisField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GF({})", self.order())
+    }
+}
+
+fn prime_power_decomposition(order: usize) -> Option<(usize, u32)> {
+    if order < 2 {
+        return None;
+    }
+    let prime = (2..)
+        .take_while(|divisor| divisor * divisor <= order)
+        .find(|divisor| order % divisor == 0)
+        .unwrap_or(order);
+    let mut remainder = order;
+    let mut degree = 0;
+    while remainder % prime == 0 {
+        remainder /= prime;
+        degree += 1;
+    }
+    if remainder == 1 {
+        Some((prime, degree))
+    } else {
+        None
+    }
+}
+
+impl TryFrom<usize> for GaloisField {
+    type Error = String;
+
+    fn try_from(order: usize) -> Result<Self, Self::Error> {
+        Self::try_new(order).ok_or_else(|| format!("{} is not a prime power", order))
+    }
+}
+
+impl From<GaloisField> for usize {
+    fn from(field: GaloisField) -> Self {
+        field.order()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check_field_axioms(field: &GaloisField) {
+        let q = field.order();
+        for a in 0..q {
+            assert_eq!(field.add(a, 0), a);
+            assert_eq!(field.mul(a, 1), a);
+            assert_eq!(field.add(a, field.neg(a)), 0);
+            if a != 0 {
+                assert_eq!(field.mul(a, field.inv(a)), 1);
+            }
+            for b in 0..q {
+                assert_eq!(field.add(a, b), field.add(b, a));
+                assert_eq!(field.mul(a, b), field.mul(b, a));
+                for c in 0..q {
+                    assert_eq!(
+                        field.mul(a, field.add(b, c)),
+                        field.add(field.mul(a, b), field.mul(a, c))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fields_satisfy_axioms() {
+        for order in [2, 3, 4, 5, 7, 8, 9, 16, 25, 27] {
+            check_field_axioms(&GaloisField::new(order));
+        }
+    }
+
+    #[test]
+    fn prime_field_is_integers_modulo_p() {
+        let field = GaloisField::new(7);
+        assert_eq!(field.add(5, 4), 2);
+        assert_eq!(field.mul(5, 4), 6);
+        assert_eq!(field.sub(2, 5), 4);
+    }
+
+    #[test]
+    fn only_prime_powers_are_fields() {
+        assert!(GaloisField::try_new(0).is_none());
+        assert!(GaloisField::try_new(1).is_none());
+        assert!(GaloisField::try_new(6).is_none());
+        assert!(GaloisField::try_new(12).is_none());
+        assert!(GaloisField::try_new(256).is_some());
+    }
+
+    #[test]
+    fn fields_are_serialized_as_their_order() {
+        let field = GaloisField::new(4);
+        let json = serde_json::to_string(&field).unwrap();
+        assert_eq!(json, "4");
+        assert_eq!(serde_json::from_str::<GaloisField>(&json).unwrap(), field);
+        assert!(serde_json::from_str::<GaloisField>("6").is_err());
+        let tables = r#"{"characteristic":2,"degree":2,"exp":[1,1,1],"log":[0,0,0,0]}"#;
+        assert!(serde_json::from_str::<GaloisField>(tables).is_err());
+    }
+}
@@ -0,0 +1,20 @@
+//! Codes over finite fields larger than GF(2).
+//!
+//! The [`GaloisField`](GaloisField) type implements the arithmetic of any
+//! field with a prime power number of elements.
+//! A [`NonBinaryCode`](NonBinaryCode) is defined by a sparse parity check matrix
+//! over such a field and can be decoded with the
+//! [`NonBinaryBpDecoder`](NonBinaryBpDecoder).
+//! Words, errors and syndromes are dense vectors of field elements.
+
+mod channel;
+pub use channel::QarySymmetricChannel;
+
+mod code;
+pub use code::NonBinaryCode;
+
+mod decoder;
+pub use decoder::NonBinaryBpDecoder;
+
+mod field;
+pub use field::GaloisField;
@@ -0,0 +1,385 @@
+use super::FailureCount;
+use crate::codes::LinearCode;
+use crate::decoders::SoftSyndromeDecoder;
+use crate::noise::{NoiseModel, Probability};
+use itertools::Itertools;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+use std::collections::VecDeque;
+
+/// Per-bit flip probabilities estimated from the most recent corrections.
+///
+/// The estimate of a bit is `(c + p) / (w + 1)` where `c` is the number of
+/// corrections flipping the bit among the last `w` recorded corrections
+/// and `p` is the initial probability.
+/// That is, the initial probability counts as a single extra correction,
+/// such that the estimate starts at `p` and is never exactly 0.
+///
+/// # Warning
+///
+/// The corrections are biased by the priors used to find them.
+/// A bit rarely flipped by the decoder gets a smaller prior
+/// making it even less likely to be flipped,
+/// such that a small window can lock some estimates close to 0.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::noise::Probability;
+/// use ldpc::simulation::AdaptivePriors;
+/// use sparse_bin_mat::SparseBinVec;
+///
+/// let mut priors = AdaptivePriors::new(3, Probability::new(0.1), 2);
+/// priors.record(&SparseBinVec::new(3, vec![0]));
+/// priors.record(&SparseBinVec::new(3, vec![0, 1]));
+/// priors.record(&SparseBinVec::new(3, vec![1]));
+///
+/// // Only the last 2 corrections are kept.
+/// let probabilities = priors.probabilities();
+/// assert!((probabilities[0] - 1.1 / 3.0).abs() < 1e-12);
+/// assert!((probabilities[1] - 2.1 / 3.0).abs() < 1e-12);
+/// assert!((probabilities[2] - 0.1 / 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AdaptivePriors {
+    initial_probability: f64,
+    window: usize,
+    corrections: VecDeque<SparseBinVec>,
+    counts: Vec<usize>,
+}
+
+impl AdaptivePriors {
+    /// Creates priors for the given number of bits starting from the given probability
+    /// and estimated from at most `window` corrections.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the window is 0.
+    pub fn new(num_bits: usize, initial_probability: Probability, window: usize) -> Self {
+        if window == 0 {
+            panic!("window must contain at least one correction");
+        }
+        Self {
+            initial_probability: initial_probability.value(),
+            window,
+            corrections: VecDeque::with_capacity(window),
+            counts: vec![0; num_bits],
+        }
+    }
+
+    /// Adds a correction to the window,
+    /// forgetting the oldest one if the window is full.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the correction length is different from the number of bits.
+    pub fn record(&mut self, correction: &SparseBinVec) {
+        if correction.len() != self.num_bits() {
+            panic!(
+                "correction of length {} for {} bits",
+                correction.len(),
+                self.num_bits()
+            );
+        }
+        if self.corrections.len() == self.window {
+            let oldest = self.corrections.pop_front().unwrap();
+            for bit in oldest.non_trivial_positions() {
+                self.counts[bit] -= 1;
+            }
+        }
+        for bit in correction.non_trivial_positions() {
+            self.counts[bit] += 1;
+        }
+        self.corrections.push_back(correction.clone());
+    }
+
+    /// Returns the number of bits.
+    pub fn num_bits(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the maximum number of corrections used in the estimate.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the number of corrections currently in the window.
+    pub fn num_recorded(&self) -> usize {
+        self.corrections.len()
+    }
+
+    /// Returns the estimated flip probability of each bit.
+    pub fn probabilities(&self) -> Vec<f64> {
+        let total = (self.corrections.len() + 1) as f64;
+        self.counts
+            .iter()
+            .map(|count| (*count as f64 + self.initial_probability) / total)
+            .collect()
+    }
+
+    /// Returns the log-likelihood ratio of each bit.
+    pub fn llrs(&self) -> Vec<f64> {
+        self.probabilities()
+            .into_iter()
+            .map(|probability| Probability::new(probability).llr())
+            .collect()
+    }
+}
+
+/// A classical decoding experiment where the priors of a soft decoder
+/// can be updated from its own recent corrections.
+///
+/// Without feedback, every syndrome is decoded with the initial probability as prior.
+/// With feedback, the priors are the [`AdaptivePriors`] estimated
+/// from the corrections of the previous trials within a sliding window.
+/// This models adaptive decoders tracking noise drifting over time in hardware
+/// since the estimate only uses the information available to the decoder.
+///
+/// A trial fails if the correction is different from the error
+/// or, if [logicals](Self::with_logicals) are given,
+/// if the residual error flips one of them.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::BpDecoder;
+/// use ldpc::noise::{BinarySymmetricChannel, Probability};
+/// use ldpc::simulation::AdaptiveExperiment;
+/// use rand::thread_rng;
+///
+/// let code = LinearCode::hamming_code();
+/// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.05), 10);
+/// let noise = BinarySymmetricChannel::with_probability(Probability::new(0.05));
+///
+/// let failures = AdaptiveExperiment::new(&code, &noise, &decoder, Probability::new(0.05))
+///     .with_feedback(100)
+///     .run_with(200, &mut thread_rng());
+///
+/// assert_eq!(failures.num_trials(), 200);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveExperiment<'a, N, D> {
+    code: &'a LinearCode,
+    noise: &'a N,
+    decoder: &'a D,
+    initial_probability: Probability,
+    window: Option<usize>,
+    logicals: Option<&'a SparseBinMat>,
+}
+
+impl<'a, N, D> AdaptiveExperiment<'a, N, D>
+where
+    N: NoiseModel<Error = SparseBinVec>,
+    D: SoftSyndromeDecoder,
+{
+    /// Creates a new experiment without feedback
+    /// where the priors are given by the initial probability.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the decoder is not for the parity check matrix of the code.
+    pub fn new(
+        code: &'a LinearCode,
+        noise: &'a N,
+        decoder: &'a D,
+        initial_probability: Probability,
+    ) -> Self {
+        if decoder.parity_check_matrix() != code.parity_check_matrix() {
+            panic!("decoder is not for the parity check matrix of the code");
+        }
+        Self {
+            code,
+            noise,
+            decoder,
+            initial_probability,
+            window: None,
+            logicals: None,
+        }
+    }
+
+    /// Updates the priors from the corrections of the last `window` trials.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the window is 0.
+    pub fn with_feedback(&mut self, window: usize) -> &mut Self {
+        if window == 0 {
+            panic!("window must contain at least one correction");
+        }
+        self.window = Some(window);
+        self
+    }
+
+    /// Only declares a failure when the residual error, that is the sum
+    /// of the error and the correction, flips one of the given logical observables.
+    ///
+    /// Each row of the logical matrix is an observable
+    /// given by its dot product with the residual error.
+    /// A residual error that is not a codeword can still be a success
+    /// if it doesn't flip any observable.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the logicals have a different length than the code.
+    pub fn with_logicals(&mut self, logicals: &'a SparseBinMat) -> &mut Self {
+        if logicals.number_of_columns() != self.code.len() {
+            panic!(
+                "logicals of length {} are invalid for code with length {}",
+                logicals.number_of_columns(),
+                self.code.len()
+            );
+        }
+        self.logicals = Some(logicals);
+        self
+    }
+
+    /// Runs a single trial decoding with the given priors
+    /// and returns the correction together with a flag indicating if it failed.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of priors is different from the code length.
+    pub fn run_once<R: Rng>(&self, priors: &[f64], rng: &mut R) -> (SparseBinVec, bool) {
+        let error = self.code.random_error(self.noise, rng);
+        let syndrome = self.code.syndrome_of(&error);
+        let posteriors = self.decoder.posteriors_for(syndrome.as_view(), priors);
+        let correction = SparseBinVec::new(
+            self.code.len(),
+            posteriors
+                .iter()
+                .positions(|posterior| *posterior < 0.0)
+                .collect(),
+        );
+        let has_failed = match self.logicals {
+            Some(logicals) => !(logicals * &(&correction + &error)).is_zero(),
+            None => correction != error,
+        };
+        (correction, has_failed)
+    }
+
+    /// Runs the given number of trials and counts the failures.
+    ///
+    /// With feedback, the priors start from the initial probability
+    /// and are updated after each trial.
+    pub fn run_with<R: Rng>(&self, num_trials: usize, rng: &mut R) -> FailureCount {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("adaptive_experiment", num_trials, window = self.window).entered();
+        match self.window {
+            Some(window) => {
+                let mut priors =
+                    AdaptivePriors::new(self.code.len(), self.initial_probability, window);
+                self.run_adapting(num_trials, &mut priors, rng)
+            }
+            None => {
+                let priors = vec![self.initial_probability.llr(); self.code.len()];
+                let num_failures = (0..num_trials)
+                    .filter(|_| self.run_once(&priors, rng).1)
+                    .count();
+                #[cfg(feature = "tracing")]
+                tracing::info!(num_failures, "adaptive experiment finished");
+                FailureCount::new(num_trials, num_failures)
+            }
+        }
+    }
+
+    /// Runs the given number of trials updating the given priors after each trial.
+    ///
+    /// This keeps the estimate across several runs,
+    /// for example when the noise changes between runs.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of bits of the priors is different from the code length.
+    pub fn run_adapting<R: Rng>(
+        &self,
+        num_trials: usize,
+        priors: &mut AdaptivePriors,
+        rng: &mut R,
+    ) -> FailureCount {
+        let mut num_failures = 0;
+        for _ in 0..num_trials {
+            let (correction, has_failed) = self.run_once(&priors.llrs(), rng);
+            priors.record(&correction);
+            num_failures += has_failed as usize;
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            num_failures,
+            num_recorded = priors.num_recorded(),
+            "adaptive experiment finished"
+        );
+        FailureCount::new(num_trials, num_failures)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::BpDecoder;
+    use crate::noise::BinarySymmetricChannel;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn window_forgets_oldest_corrections() {
+        let mut priors = AdaptivePriors::new(2, Probability::new(0.0), 2);
+        for _ in 0..5 {
+            priors.record(&SparseBinVec::new(2, vec![0]));
+        }
+        assert_eq!(priors.num_recorded(), 2);
+        assert_eq!(priors.probabilities(), vec![2.0 / 3.0, 0.0]);
+        priors.record(&SparseBinVec::new(2, vec![1]));
+        priors.record(&SparseBinVec::new(2, vec![1]));
+        assert_eq!(priors.probabilities(), vec![0.0, 2.0 / 3.0]);
+    }
+
+    #[test]
+    fn feedback_learns_a_noisier_channel() {
+        let code = LinearCode::repetition_code(5);
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.01), 10);
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.2));
+        let mut priors = AdaptivePriors::new(5, Probability::new(0.01), 500);
+        AdaptiveExperiment::new(&code, &noise, &decoder, Probability::new(0.01)).run_adapting(
+            500,
+            &mut priors,
+            &mut StdRng::seed_from_u64(123),
+        );
+        let mean = priors.probabilities().iter().sum::<f64>() / 5.0;
+        assert!(mean > 0.1);
+    }
+
+    #[test]
+    fn decoding_without_noise_never_fails() {
+        let code = LinearCode::hamming_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.0));
+        let failures = AdaptiveExperiment::new(&code, &noise, &decoder, Probability::new(0.1))
+            .with_feedback(10)
+            .run_with(50, &mut StdRng::seed_from_u64(123));
+        assert_eq!(failures, FailureCount::new(50, 0));
+    }
+
+    #[test]
+    fn failures_are_only_counted_on_logicals() {
+        let code = LinearCode::repetition_code(3);
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.4));
+        let no_logical = SparseBinMat::new(3, Vec::new());
+        let all_bits = SparseBinMat::identity(3);
+        let mut experiment =
+            AdaptiveExperiment::new(&code, &noise, &decoder, Probability::new(0.1));
+        let exact = experiment.run_with(200, &mut StdRng::seed_from_u64(123));
+        let identity = experiment
+            .with_logicals(&all_bits)
+            .run_with(200, &mut StdRng::seed_from_u64(123));
+        let trivial = experiment
+            .with_logicals(&no_logical)
+            .run_with(200, &mut StdRng::seed_from_u64(123));
+        assert!(exact.num_failures() > 0);
+        assert_eq!(identity, exact);
+        assert_eq!(trivial, FailureCount::new(200, 0));
+    }
+}
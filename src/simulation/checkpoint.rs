@@ -0,0 +1,294 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A simulation split in batches of trials whose progress is saved
+/// to a file after each batch.
+///
+/// The random number generator of each batch is seeded from
+/// the seed of the run and the index of the batch.
+/// Thus, the random state is fully described by the number of completed batches
+/// and an interrupted run resumed from its checkpoint gives exactly
+/// the same statistics as an uninterrupted one.
+///
+/// The checkpoint is a JSON file with the seed, the number of completed batches
+/// and trials, and the accumulated statistics.
+/// It is written to a temporary file first and then renamed,
+/// such that an interruption during the write never corrupts it.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::decoders::{BpDecoder, CssDecoder};
+/// use ldpc::noise::{DepolarizingNoise, Probability};
+/// use ldpc::simulation::{CheckpointedRun, MemoryExperiment, MemoryLifetime};
+///
+/// let code = CssCode::steane_code();
+/// let decoder = CssDecoder {
+///     x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.05), 10),
+///     z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.05), 10),
+/// };
+/// let noise = DepolarizingNoise::with_probability(Probability::new(0.05));
+/// let mut experiment = MemoryExperiment::new(&code, &noise, &decoder);
+/// experiment.max_rounds(10);
+///
+/// let path = std::env::temp_dir().join("ldpc_checkpoint_doc.json");
+/// # std::fs::remove_file(&path).ok();
+/// let lifetime = CheckpointedRun::new(&path, 123)
+///     .batch_size(25)
+///     .run(100, MemoryLifetime::new(10), |lifetime, num_trials, rng| {
+///         for _ in 0..num_trials {
+///             lifetime.record_outcome(experiment.run_once(rng));
+///         }
+///     })
+///     .unwrap();
+/// assert_eq!(lifetime.num_trials(), 100);
+///
+/// // Running again resumes from the checkpoint where all trials are already done.
+/// let resumed = CheckpointedRun::new(&path, 123)
+///     .batch_size(25)
+///     .run(100, MemoryLifetime::new(10), |_, _, _| unreachable!())
+///     .unwrap();
+/// assert_eq!(resumed, lifetime);
+/// # std::fs::remove_file(&path).ok();
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CheckpointedRun {
+    path: PathBuf,
+    seed: u64,
+    batch_size: usize,
+}
+
+impl CheckpointedRun {
+    /// Creates a run saving its progress to the given path
+    /// with the given seed for the random number generators.
+    pub fn new<P: AsRef<Path>>(path: P, seed: u64) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            seed,
+            batch_size: 1000,
+        }
+    }
+
+    /// Fixes the number of trials between checkpoints.
+    ///
+    /// Default is 1000.
+    /// The batch size is saved with the checkpoint
+    /// and resuming a run with another batch size is an error,
+    /// since it would change the statistics of an uninterrupted run.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the batch size is 0.
+    pub fn batch_size(&mut self, batch_size: usize) -> &mut Self {
+        if batch_size == 0 {
+            panic!("batch size must be positive");
+        }
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Returns the path of the checkpoint.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Runs batches of trials until the given number of trials is reached
+    /// and returns the accumulated statistics.
+    ///
+    /// The statistics start from the checkpoint if it exists and from
+    /// the initial statistics otherwise.
+    /// Only complete batches are saved, such that a last incomplete batch
+    /// is run again in full when the run is resumed with more trials.
+    /// If the checkpoint already has more trials, its statistics are returned.
+    /// Each batch calls `run_batch` with the statistics to update,
+    /// the number of trials of the batch and the random number generator
+    /// of the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint can't be read or written
+    /// or if it was made with a different seed or batch size.
+    pub fn run<S, F>(&self, num_trials: usize, initial: S, mut run_batch: F) -> io::Result<S>
+    where
+        S: Serialize + DeserializeOwned,
+        F: FnMut(&mut S, usize, &mut StdRng),
+    {
+        let mut checkpoint = match self.load()? {
+            Some(checkpoint) => checkpoint,
+            None => Checkpoint {
+                seed: self.seed,
+                batch_size: self.batch_size,
+                num_batches: 0,
+                num_trials: 0,
+                statistics: initial,
+            },
+        };
+        while checkpoint.num_trials + self.batch_size <= num_trials {
+            let mut rng = batch_rng(self.seed, checkpoint.num_batches);
+            run_batch(&mut checkpoint.statistics, self.batch_size, &mut rng);
+            checkpoint.num_batches += 1;
+            checkpoint.num_trials += self.batch_size;
+            self.save(&checkpoint)?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                num_batches = checkpoint.num_batches,
+                num_trials = checkpoint.num_trials,
+                path = %self.path.display(),
+                "checkpoint saved"
+            );
+        }
+        let mut statistics = checkpoint.statistics;
+        if checkpoint.num_trials < num_trials {
+            let mut rng = batch_rng(self.seed, checkpoint.num_batches);
+            run_batch(
+                &mut statistics,
+                num_trials - checkpoint.num_trials,
+                &mut rng,
+            );
+        }
+        Ok(statistics)
+    }
+
+    fn load<S: DeserializeOwned>(&self) -> io::Result<Option<Checkpoint<S>>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let checkpoint: Checkpoint<S> = serde_json::from_str(&content)?;
+        if checkpoint.seed != self.seed {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "checkpoint {} was made with seed {} instead of {}",
+                    self.path.display(),
+                    checkpoint.seed,
+                    self.seed
+                ),
+            ));
+        }
+        if checkpoint.batch_size != self.batch_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "checkpoint {} was made with batches of {} trials instead of {}",
+                    self.path.display(),
+                    checkpoint.batch_size,
+                    self.batch_size
+                ),
+            ));
+        }
+        Ok(Some(checkpoint))
+    }
+
+    fn save<S: Serialize>(&self, checkpoint: &Checkpoint<S>) -> io::Result<()> {
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        fs::write(&temporary, serde_json::to_string(checkpoint)?)?;
+        fs::rename(&temporary, &self.path)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Checkpoint<S> {
+    seed: u64,
+    batch_size: usize,
+    num_batches: usize,
+    num_trials: usize,
+    statistics: S,
+}
+
+// The generator of each batch is seeded by both the seed and the batch
+// such that different seeds never share batches.
+fn batch_rng(seed: u64, batch: usize) -> StdRng {
+    let mut bytes = [0; 32];
+    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+    bytes[8..16].copy_from_slice(&(batch as u64).to_le_bytes());
+    StdRng::from_seed(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::noise::{ErasureChannel, Probability};
+    use crate::simulation::{ErasureExperiment, ErasureRecovery};
+
+    fn run_until(path: &Path, num_trials: usize) -> ErasureRecovery {
+        let code = LinearCode::hamming_code();
+        let noise = ErasureChannel::with_probability(Probability::new(0.3));
+        let experiment = ErasureExperiment::new(&code, &noise);
+        CheckpointedRun::new(path, 123)
+            .batch_size(10)
+            .run(
+                num_trials,
+                ErasureRecovery::default(),
+                |recovery, num_trials, rng| {
+                    for _ in 0..num_trials {
+                        let (erasure, is_repairable) = experiment.run_once(rng);
+                        recovery.record(erasure.weight(), is_repairable);
+                    }
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn resumed_run_matches_uninterrupted_run() {
+        let directory = std::env::temp_dir();
+        let interrupted = directory.join("ldpc_checkpoint_interrupted.json");
+        let uninterrupted = directory.join("ldpc_checkpoint_uninterrupted.json");
+        fs::remove_file(&interrupted).ok();
+        fs::remove_file(&uninterrupted).ok();
+
+        let partial = run_until(&interrupted, 35);
+        assert_eq!(partial.num_trials(), 35);
+        let resumed = run_until(&interrupted, 100);
+        assert_eq!(resumed, run_until(&uninterrupted, 100));
+
+        fs::remove_file(&interrupted).ok();
+        fs::remove_file(&uninterrupted).ok();
+    }
+
+    #[test]
+    fn checkpoint_with_other_seed_is_rejected() {
+        let path = std::env::temp_dir().join("ldpc_checkpoint_other_seed.json");
+        fs::remove_file(&path).ok();
+        CheckpointedRun::new(&path, 1)
+            .batch_size(5)
+            .run(5, 0, |count, num_trials, _| *count += num_trials)
+            .unwrap();
+        let error = CheckpointedRun::new(&path, 2)
+            .batch_size(5)
+            .run(5, 0, |count: &mut usize, num_trials, _| {
+                *count += num_trials
+            })
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn checkpoint_with_other_batch_size_is_rejected() {
+        let path = std::env::temp_dir().join("ldpc_checkpoint_other_batch_size.json");
+        fs::remove_file(&path).ok();
+        CheckpointedRun::new(&path, 1)
+            .batch_size(5)
+            .run(10, 0, |count, num_trials, _| *count += num_trials)
+            .unwrap();
+        let error = CheckpointedRun::new(&path, 1)
+            .batch_size(2)
+            .run(10, 0, |count: &mut usize, num_trials, _| {
+                *count += num_trials
+            })
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).ok();
+    }
+}
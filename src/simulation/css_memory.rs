@@ -0,0 +1,238 @@
+use super::{FailureCount, TrialObservables, TrialStatistics};
+use crate::codes::CssCode;
+use crate::css::CssSyndrome;
+use crate::decoders::{BpDecoder, CssDecoder, SyndromeDecoder};
+use crate::noise::{DepolarizingNoise, Probability};
+use pauli::PauliOperator;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// The configuration of a [`run_css_memory_experiment`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CssMemoryConfig<'a> {
+    /// The code storing the logical qubits.
+    pub code: &'a CssCode,
+    /// The probability of the depolarizing noise on each qubit.
+    pub probability: Probability,
+    /// The maximum number of iterations of belief propagation.
+    pub max_iterations: usize,
+    /// The number of noise realizations to decode.
+    pub num_trials: usize,
+}
+
+impl<'a> CssMemoryConfig<'a> {
+    /// Creates a configuration with 100 iterations of belief propagation and 1000 trials.
+    pub fn new(code: &'a CssCode, probability: Probability) -> Self {
+        Self {
+            code,
+            probability,
+            max_iterations: 100,
+            num_trials: 1000,
+        }
+    }
+}
+
+/// Runs the canonical decoding experiment of a CSS code.
+///
+/// Each trial samples an error from depolarizing noise,
+/// decodes its syndrome with belief propagation on both the X and Z stabilizers
+/// and fails if the error times the correction is not a stabilizer.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::{CssCode, LinearCode};
+/// use ldpc::noise::Probability;
+/// use ldpc::simulation::{run_css_memory_experiment, CssMemoryConfig};
+/// use rand::thread_rng;
+///
+/// let repetition_code = LinearCode::repetition_code(3);
+/// let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+///
+/// let mut config = CssMemoryConfig::new(&code, Probability::new(0.01));
+/// config.num_trials = 100;
+/// let result = run_css_memory_experiment(&config, &mut thread_rng());
+///
+/// assert_eq!(result.num_trials(), 100);
+/// assert!(result.num_syndrome_mismatches() <= result.num_failures());
+/// ```
+pub fn run_css_memory_experiment<R: Rng>(config: &CssMemoryConfig, rng: &mut R) -> CssMemoryResult {
+    run_css_memory_experiment_with_observables(config, &TrialObservables::new(), rng).0
+}
+
+/// The data of a single trial of a [`run_css_memory_experiment`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssTrial {
+    /// The error sampled from the noise.
+    pub error: PauliOperator,
+    /// The syndrome of the error.
+    pub syndrome: CssSyndrome,
+    /// The correction of the decoder.
+    pub correction: PauliOperator,
+    /// The error times the correction.
+    pub residual: PauliOperator,
+    /// Whether the correction was wrong.
+    pub is_failure: bool,
+}
+
+/// Runs the canonical decoding experiment of a CSS code
+/// and records the given observables of each trial.
+///
+/// This is a [`run_css_memory_experiment`] extended with user-defined statistics.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::{CssCode, LinearCode};
+/// use ldpc::noise::Probability;
+/// use ldpc::simulation::{
+///     run_css_memory_experiment_with_observables, CssMemoryConfig, CssTrial, TrialObservables,
+/// };
+/// use rand::thread_rng;
+///
+/// let repetition_code = LinearCode::repetition_code(3);
+/// let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+///
+/// let observables = TrialObservables::new()
+///     .with("correction weight", |trial: &CssTrial| trial.correction.weight() as f64)
+///     .with("residual weight", |trial: &CssTrial| trial.residual.weight() as f64);
+///
+/// let mut config = CssMemoryConfig::new(&code, Probability::new(0.01));
+/// config.num_trials = 100;
+/// let (result, statistics) =
+///     run_css_memory_experiment_with_observables(&config, &observables, &mut thread_rng());
+///
+/// assert_eq!(statistics.values("residual weight").len(), result.num_trials());
+/// assert!(statistics.percentile("correction weight", 0.99).unwrap() <= 13.0);
+/// ```
+pub fn run_css_memory_experiment_with_observables<R: Rng>(
+    config: &CssMemoryConfig,
+    observables: &TrialObservables<CssTrial>,
+    rng: &mut R,
+) -> (CssMemoryResult, TrialStatistics) {
+    let code = config.code;
+    let decoder = CssDecoder {
+        x: BpDecoder::new(
+            &code.stabilizers.x,
+            config.probability,
+            config.max_iterations,
+        ),
+        z: BpDecoder::new(
+            &code.stabilizers.z,
+            config.probability,
+            config.max_iterations,
+        ),
+    };
+    let noise = DepolarizingNoise::with_probability(config.probability);
+    let mut result = CssMemoryResult::default();
+    let mut statistics = TrialStatistics::default();
+    for _ in 0..config.num_trials {
+        let error = code.random_error(&noise, rng);
+        let syndrome = code.syndrome_of(&error);
+        let correction: PauliOperator = decoder.correction_for(syndrome.as_view()).into();
+        let residual = &error * &correction;
+        result.num_trials += 1;
+        result.total_error_weight += error.weight();
+        let is_failure = !code.has_stabilizer(&residual);
+        if is_failure {
+            result.num_failures += 1;
+            if !code.syndrome_of(&residual).is_trivial() {
+                result.num_syndrome_mismatches += 1;
+            }
+        }
+        let trial = CssTrial {
+            error,
+            syndrome,
+            correction,
+            residual,
+            is_failure,
+        };
+        observables.observe(&trial, &mut statistics);
+    }
+    (result, statistics)
+}
+
+/// The statistics of a [`run_css_memory_experiment`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct CssMemoryResult {
+    num_trials: usize,
+    num_failures: usize,
+    num_syndrome_mismatches: usize,
+    total_error_weight: usize,
+}
+
+impl CssMemoryResult {
+    /// Returns the number of trials.
+    pub fn num_trials(&self) -> usize {
+        self.num_trials
+    }
+
+    /// Returns the number of trials where the correction was wrong.
+    pub fn num_failures(&self) -> usize {
+        self.num_failures
+    }
+
+    /// Returns the number of failures where the correction
+    /// didn't match the syndrome, as opposed to logical errors.
+    pub fn num_syndrome_mismatches(&self) -> usize {
+        self.num_syndrome_mismatches
+    }
+
+    /// Returns the fraction of trials where the correction was wrong.
+    pub fn failure_rate(&self) -> f64 {
+        self.failure_count().failure_rate()
+    }
+
+    /// Returns the number of trials and failures.
+    pub fn failure_count(&self) -> FailureCount {
+        FailureCount::new(self.num_trials, self.num_failures)
+    }
+
+    /// Returns the average weight of the sampled errors.
+    pub fn mean_error_weight(&self) -> f64 {
+        self.total_error_weight as f64 / self.num_trials as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn noiseless_memory_never_fails() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+        let mut config = CssMemoryConfig::new(&code, Probability::new(0.0));
+        config.num_trials = 10;
+        let result = run_css_memory_experiment(&config, &mut StdRng::seed_from_u64(0));
+        assert_eq!(result.failure_count(), FailureCount::new(10, 0));
+        assert_eq!(result.mean_error_weight(), 0.0);
+    }
+
+    #[test]
+    fn observables_see_each_trial() {
+        let code = CssCode::steane_code();
+        let mut config = CssMemoryConfig::new(&code, Probability::new(0.2));
+        config.num_trials = 50;
+        let observables = TrialObservables::new()
+            .with("error weight", |trial: &CssTrial| {
+                trial.error.weight() as f64
+            })
+            .with("failure", |trial: &CssTrial| {
+                trial.is_failure as usize as f64
+            });
+        let (result, statistics) = run_css_memory_experiment_with_observables(
+            &config,
+            &observables,
+            &mut StdRng::seed_from_u64(0),
+        );
+        assert_eq!(
+            statistics.mean("error weight"),
+            Some(result.mean_error_weight())
+        );
+        assert_eq!(statistics.mean("failure"), Some(result.failure_rate()));
+    }
+}
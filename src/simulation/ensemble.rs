@@ -0,0 +1,190 @@
+use super::FailureCount;
+use crate::codes::LinearCode;
+use crate::decoders::{BpDecoder, SyndromeDecoder};
+use crate::noise::{BinarySymmetricChannel, Probability};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// An experiment estimating how the performance of belief propagation
+/// depends on the parity check matrix representing a classical code.
+///
+/// The original code and codes from
+/// [`randomize_equivalent`](LinearCode::randomize_equivalent)
+/// all decode the same errors sampled from a binary symmetric channel,
+/// such that their differences are due to the representation and not to the sampling.
+/// A trial fails if the correction is different from the error.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::noise::Probability;
+/// use ldpc::simulation::RepresentationEnsemble;
+/// use rand::thread_rng;
+///
+/// let code = LinearCode::hamming_code();
+/// let statistics = RepresentationEnsemble::new(&code, Probability::new(0.05))
+///     .with_max_iterations(10)
+///     .run_with(5, 100, &mut thread_rng());
+///
+/// assert_eq!(statistics.original().num_trials(), 100);
+/// assert_eq!(statistics.randomized().len(), 5);
+/// assert!(statistics.failure_rate_deviation() >= 0.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RepresentationEnsemble<'a> {
+    code: &'a LinearCode,
+    probability: Probability,
+    max_iterations: usize,
+}
+
+impl<'a> RepresentationEnsemble<'a> {
+    /// Creates an ensemble experiment for the given code and channel probability
+    /// using 100 iterations of belief propagation.
+    pub fn new(code: &'a LinearCode, probability: Probability) -> Self {
+        Self {
+            code,
+            probability,
+            max_iterations: 100,
+        }
+    }
+
+    /// Sets the maximum number of iterations of belief propagation.
+    pub fn with_max_iterations(&mut self, max_iterations: usize) -> &mut Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Samples the given number of randomized representations
+    /// and decodes the given number of errors with each of them and with the original code.
+    pub fn run_with<R: Rng>(
+        &self,
+        num_representations: usize,
+        num_trials: usize,
+        rng: &mut R,
+    ) -> RepresentationStatistics {
+        let codes = std::iter::once(self.code.clone())
+            .chain((0..num_representations).map(|_| self.code.randomize_equivalent(rng)))
+            .collect::<Vec<_>>();
+        let decoders = codes
+            .iter()
+            .map(|code| {
+                BpDecoder::new(
+                    code.parity_check_matrix(),
+                    self.probability,
+                    self.max_iterations,
+                )
+            })
+            .collect::<Vec<_>>();
+        let noise = BinarySymmetricChannel::with_probability(self.probability);
+        let mut num_failures = vec![0; codes.len()];
+        for _ in 0..num_trials {
+            let error = self.code.random_error(&noise, rng);
+            for ((code, decoder), num_failures) in
+                codes.iter().zip(&decoders).zip(num_failures.iter_mut())
+            {
+                let correction = decoder.correction_for(code.syndrome_of(&error).as_view());
+                if correction != error {
+                    *num_failures += 1;
+                }
+            }
+        }
+        let mut counts = num_failures
+            .into_iter()
+            .map(|num_failures| FailureCount::new(num_trials, num_failures));
+        RepresentationStatistics {
+            original: counts.next().unwrap(),
+            randomized: counts.collect(),
+        }
+    }
+}
+
+/// The failure counts of the representations of a [`RepresentationEnsemble`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct RepresentationStatistics {
+    original: FailureCount,
+    randomized: Vec<FailureCount>,
+}
+
+impl RepresentationStatistics {
+    /// Returns the failure count of the original parity check matrix.
+    pub fn original(&self) -> FailureCount {
+        self.original
+    }
+
+    /// Returns the failure count of each randomized parity check matrix.
+    pub fn randomized(&self) -> &[FailureCount] {
+        &self.randomized
+    }
+
+    /// Returns the average failure rate of the randomized representations
+    /// or 0 if there is none.
+    pub fn mean_failure_rate(&self) -> f64 {
+        if self.randomized.is_empty() {
+            return 0.0;
+        }
+        self.randomized
+            .iter()
+            .map(|count| count.failure_rate())
+            .sum::<f64>()
+            / self.randomized.len() as f64
+    }
+
+    /// Returns the standard deviation of the failure rates
+    /// of the randomized representations or 0 if there is none.
+    pub fn failure_rate_deviation(&self) -> f64 {
+        if self.randomized.is_empty() {
+            return 0.0;
+        }
+        let mean = self.mean_failure_rate();
+        let variance = self
+            .randomized
+            .iter()
+            .map(|count| (count.failure_rate() - mean).powi(2))
+            .sum::<f64>()
+            / self.randomized.len() as f64;
+        variance.sqrt()
+    }
+
+    /// Returns the smallest and largest failure rates
+    /// of the randomized representations or None if there is none.
+    pub fn failure_rate_range(&self) -> Option<(f64, f64)> {
+        let rates = self.randomized.iter().map(|count| count.failure_rate());
+        let min = rates.clone().reduce(f64::min)?;
+        let max = rates.reduce(f64::max)?;
+        Some((min, max))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn randomized_checks_span_the_same_codespace() {
+        let code = LinearCode::extended_golay_code();
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..10 {
+            let randomized = code.randomize_equivalent(&mut rng);
+            let rebuilt =
+                LinearCode::from_parity_check_matrix(randomized.parity_check_matrix().clone());
+            assert_eq!(rebuilt.dimension(), code.dimension());
+            assert!(rebuilt.has_same_codespace(&code));
+        }
+    }
+
+    #[test]
+    fn noiseless_ensemble_never_fails() {
+        let code = LinearCode::hamming_code();
+        let statistics = RepresentationEnsemble::new(&code, Probability::new(0.0)).run_with(
+            3,
+            10,
+            &mut StdRng::seed_from_u64(0),
+        );
+        assert_eq!(statistics.original(), FailureCount::new(10, 0));
+        assert_eq!(statistics.failure_rate_range(), Some((0.0, 0.0)));
+        assert_eq!(statistics.mean_failure_rate(), 0.0);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::codes::LinearCode;
+use crate::noise::NoiseModel;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinVec};
+
+/// An experiment estimating how often the erasures sampled
+/// from a noise model can be repaired by a classical code.
+///
+/// This is well suited to correlated erasure models such as
+/// [`NodeFailureChannel`](crate::noise::NodeFailureChannel)
+/// to evaluate codes for distributed storage.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::noise::{NodeFailureChannel, Probability};
+/// use ldpc::simulation::ErasureExperiment;
+/// use rand::thread_rng;
+///
+/// let code = LinearCode::repetition_code(6);
+/// let noise = NodeFailureChannel::with_contiguous_nodes(6, 2, Probability::new(0.5));
+///
+/// let recovery = ErasureExperiment::new(&code, &noise).run_with(100, &mut thread_rng());
+///
+/// assert_eq!(recovery.num_trials(), 100);
+/// assert!(recovery.failure_rate() < 0.5);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ErasureExperiment<'a, N> {
+    code: &'a LinearCode,
+    noise: &'a N,
+    logicals: Option<&'a SparseBinMat>,
+}
+
+impl<'a, N> ErasureExperiment<'a, N>
+where
+    N: NoiseModel<Error = SparseBinVec>,
+{
+    /// Creates a new erasure experiment for the given code and erasure model.
+    pub fn new(code: &'a LinearCode, noise: &'a N) -> Self {
+        Self {
+            code,
+            noise,
+            logicals: None,
+        }
+    }
+
+    /// Only requires the values of the given logical observables
+    /// to be recovered instead of all erased bits.
+    ///
+    /// Each row of the logical matrix is an observable
+    /// given by its dot product with the codeword.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::LinearCode;
+    /// use ldpc::noise::{NodeFailureChannel, Probability};
+    /// use ldpc::simulation::ErasureExperiment;
+    /// use rand::thread_rng;
+    /// use sparse_bin_mat::SparseBinMat;
+    ///
+    /// // Two pairs of repeated bits where only the second pair is observed.
+    /// let checks = SparseBinMat::new(4, vec![vec![0, 1], vec![2, 3]]);
+    /// let code = LinearCode::from_parity_check_matrix(checks);
+    /// let logicals = SparseBinMat::new(4, vec![vec![2]]);
+    ///
+    /// // The first pair is always erased.
+    /// let noise = NodeFailureChannel::new(vec![vec![0, 1]], Probability::new(1.0));
+    ///
+    /// let recovery = ErasureExperiment::new(&code, &noise).run_with(10, &mut thread_rng());
+    /// assert_eq!(recovery.num_failures(), 10);
+    ///
+    /// let recovery = ErasureExperiment::new(&code, &noise)
+    ///     .with_logicals(&logicals)
+    ///     .run_with(10, &mut thread_rng());
+    /// assert_eq!(recovery.num_failures(), 0);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the logicals have a different length than the code.
+    pub fn with_logicals(&mut self, logicals: &'a SparseBinMat) -> &mut Self {
+        if logicals.number_of_columns() != self.code.len() {
+            panic!(
+                "logicals of length {} are invalid for code with length {}",
+                logicals.number_of_columns(),
+                self.code.len()
+            );
+        }
+        self.logicals = Some(logicals);
+        self
+    }
+
+    /// Samples a single erasure and returns it together with
+    /// a flag indicating if it can be repaired.
+    pub fn run_once<R: Rng>(&self, rng: &mut R) -> (SparseBinVec, bool) {
+        let erasure = self.code.random_error(self.noise, rng);
+        let is_repairable = match self.logicals {
+            Some(logicals) => self
+                .code
+                .can_recover_logicals_of_erasure(&erasure, logicals),
+            None => self.code.can_recover_erasure(&erasure),
+        };
+        (erasure, is_repairable)
+    }
+
+    /// Runs the given number of trials and collects the repairability statistics.
+    pub fn run_with<R: Rng>(&self, num_trials: usize, rng: &mut R) -> ErasureRecovery {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("erasure_experiment", num_trials).entered();
+        let mut recovery = ErasureRecovery::default();
+        for _ in 0..num_trials {
+            let (erasure, is_repairable) = self.run_once(rng);
+            #[cfg(feature = "tracing")]
+            if !is_repairable {
+                tracing::debug!(weight = erasure.weight(), "erasure not repairable");
+            }
+            recovery.record(erasure.weight(), is_repairable);
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            num_failures = recovery.num_failures(),
+            "erasure experiment finished"
+        );
+        recovery
+    }
+}
+
+/// Repairability statistics of an erasure experiment.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct ErasureRecovery {
+    num_trials: usize,
+    num_failures: usize,
+    num_erased_bits: usize,
+}
+
+impl ErasureRecovery {
+    /// Records a trial with the given number of erased bits.
+    pub fn record(&mut self, num_erased_bits: usize, is_repairable: bool) {
+        self.num_trials += 1;
+        self.num_erased_bits += num_erased_bits;
+        if !is_repairable {
+            self.num_failures += 1;
+        }
+    }
+
+    /// Returns the number of trials.
+    pub fn num_trials(&self) -> usize {
+        self.num_trials
+    }
+
+    /// Returns the number of trials where the erasure could not be repaired.
+    pub fn num_failures(&self) -> usize {
+        self.num_failures
+    }
+
+    /// Returns the number of trials where the erasure could be repaired.
+    pub fn num_repairs(&self) -> usize {
+        self.num_trials - self.num_failures
+    }
+
+    /// Returns the fraction of trials where the erasure could not be repaired
+    /// or 0 if there is no trial.
+    pub fn failure_rate(&self) -> f64 {
+        if self.num_trials == 0 {
+            0.0
+        } else {
+            self.num_failures as f64 / self.num_trials as f64
+        }
+    }
+
+    /// Returns the average number of erased bits per trial or 0 if there is no trial.
+    pub fn mean_erasure_weight(&self) -> f64 {
+        if self.num_trials == 0 {
+            0.0
+        } else {
+            self.num_erased_bits as f64 / self.num_trials as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::noise::{BurstErasureChannel, NodeFailureChannel, Probability};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn repetition_code_survives_all_but_one_node_failing() {
+        let code = LinearCode::repetition_code(6);
+        let nodes = vec![vec![0, 1, 2, 3, 4]];
+        let noise = NodeFailureChannel::new(nodes, Probability::new(1.0));
+        let recovery =
+            ErasureExperiment::new(&code, &noise).run_with(10, &mut StdRng::seed_from_u64(123));
+        assert_eq!(recovery.num_repairs(), 10);
+        assert_eq!(recovery.mean_erasure_weight(), 5.0);
+    }
+
+    #[test]
+    fn rates_without_trial_are_zero() {
+        let recovery = ErasureRecovery::default();
+        assert_eq!(recovery.failure_rate(), 0.0);
+        assert_eq!(recovery.mean_erasure_weight(), 0.0);
+    }
+
+    #[test]
+    fn hamming_code_fails_on_long_bursts() {
+        let code = LinearCode::hamming_code();
+        let noise = BurstErasureChannel::new(Probability::new(1.0), 4);
+        let recovery =
+            ErasureExperiment::new(&code, &noise).run_with(10, &mut StdRng::seed_from_u64(123));
+        assert_eq!(recovery.num_failures(), 10);
+        assert_eq!(recovery.failure_rate(), 1.0);
+    }
+}
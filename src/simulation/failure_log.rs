@@ -0,0 +1,361 @@
+use super::FailureCount;
+use crate::codes::LinearCode;
+use crate::decoders::SyndromeDecoder;
+use crate::noise::NoiseModel;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// The failed trials of a classical decoding simulation
+/// kept for post-mortem analysis.
+///
+/// A trial fails if the residual error, that is the error plus the correction,
+/// is not a codeword or, if [logicals](Self::with_logicals) are given,
+/// if it flips one of them.
+/// The log stores the code and the logicals with the failures,
+/// such that the failures can be [replayed](Self::replay) with another decoder
+/// without the original simulation.
+///
+/// The log is saved as JSON lines where every binary vector
+/// is the sorted list of its non trivial positions.
+/// The first line describes the code and each following line is a failure:
+///
+/// ```json
+/// {"num_bits":3,"checks":[[0,1],[1,2]],"logicals":[[0,1,2]]}
+/// {"error":[0,1],"syndrome":[1],"correction":[2],"flipped_logicals":[0]}
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::BpDecoder;
+/// use ldpc::noise::{BinarySymmetricChannel, Probability};
+/// use ldpc::simulation::FailureLog;
+/// use rand::thread_rng;
+///
+/// let code = LinearCode::repetition_code(5);
+/// let noise = BinarySymmetricChannel::with_probability(Probability::new(0.2));
+/// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.2), 10);
+///
+/// let mut log = FailureLog::new(&code);
+/// let count = log.run_with(&noise, &decoder, 200, &mut thread_rng());
+/// assert_eq!(count.num_failures(), log.failures().len());
+///
+/// let mut file = Vec::new();
+/// log.write_to(&mut file).unwrap();
+/// let loaded = FailureLog::read_from(file.as_slice()).unwrap();
+/// assert_eq!(loaded, log);
+///
+/// // The same decoder fails again on every logged failure.
+/// assert_eq!(loaded.replay(&decoder), log);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct FailureLog {
+    header: FailureLogHeader,
+    failures: Vec<FailureRecord>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+struct FailureLogHeader {
+    num_bits: usize,
+    checks: Vec<Vec<usize>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    logicals: Option<Vec<Vec<usize>>>,
+}
+
+/// A failed trial of a [`FailureLog`].
+///
+/// Each vector is the sorted list of its non trivial positions.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct FailureRecord {
+    /// The error sampled from the noise model.
+    pub error: Vec<usize>,
+    /// The syndrome of the error.
+    pub syndrome: Vec<usize>,
+    /// The correction returned by the decoder for the syndrome.
+    pub correction: Vec<usize>,
+    /// The indices of the logicals flipped by the residual error,
+    /// empty without logicals.
+    pub flipped_logicals: Vec<usize>,
+}
+
+impl FailureLog {
+    /// Creates an empty log for the code.
+    pub fn new(code: &LinearCode) -> Self {
+        Self {
+            header: FailureLogHeader {
+                num_bits: code.len(),
+                checks: rows_of(code.parity_check_matrix()),
+                logicals: None,
+            },
+            failures: Vec::new(),
+        }
+    }
+
+    /// Only declares a failure when the residual error flips one of the given logicals.
+    ///
+    /// Each row of the logical matrix is an observable
+    /// given by its dot product with the residual error.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the logicals have a different length than the code.
+    pub fn with_logicals(&mut self, logicals: &SparseBinMat) -> &mut Self {
+        if logicals.number_of_columns() != self.header.num_bits {
+            panic!(
+                "logicals of length {} are invalid for code with length {}",
+                logicals.number_of_columns(),
+                self.header.num_bits
+            );
+        }
+        self.header.logicals = Some(rows_of(logicals));
+        self
+    }
+
+    /// Returns the code of the failures.
+    pub fn code(&self) -> LinearCode {
+        LinearCode::from_parity_check_matrix(SparseBinMat::new(
+            self.header.num_bits,
+            self.header.checks.clone(),
+        ))
+    }
+
+    /// Returns the logicals or None if failures are the residual errors
+    /// that are not codewords.
+    pub fn logicals(&self) -> Option<SparseBinMat> {
+        self.header
+            .logicals
+            .as_ref()
+            .map(|logicals| SparseBinMat::new(self.header.num_bits, logicals.clone()))
+    }
+
+    /// Returns the logged failures in the order they happened.
+    pub fn failures(&self) -> &[FailureRecord] {
+        &self.failures
+    }
+
+    /// Logs the trial if the correction of the error fails
+    /// and returns true if it does.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the error or the correction have a different length than the code.
+    pub fn record_trial(&mut self, error: &SparseBinVec, correction: &SparseBinVec) -> bool {
+        let code = self.code();
+        let logicals = self.logicals();
+        self.record_with(&code, logicals.as_ref(), error, correction)
+    }
+
+    fn record_with(
+        &mut self,
+        code: &LinearCode,
+        logicals: Option<&SparseBinMat>,
+        error: &SparseBinVec,
+        correction: &SparseBinVec,
+    ) -> bool {
+        let residual = error + correction;
+        let (has_failed, flipped_logicals) = match logicals {
+            Some(logicals) => {
+                let flipped = (logicals * &residual).to_positions_vec();
+                (!flipped.is_empty(), flipped)
+            }
+            None => (!code.has_codeword(&residual), Vec::new()),
+        };
+        if has_failed {
+            self.failures.push(FailureRecord {
+                error: error.as_slice().to_vec(),
+                syndrome: code.syndrome_of(error).to_positions_vec(),
+                correction: correction.as_slice().to_vec(),
+                flipped_logicals,
+            });
+        }
+        has_failed
+    }
+
+    /// Runs the given number of trials decoding errors sampled from the noise model,
+    /// logs the failures and returns the number of trials and failures.
+    pub fn run_with<N, D, R>(
+        &mut self,
+        noise: &N,
+        decoder: &D,
+        num_trials: usize,
+        rng: &mut R,
+    ) -> FailureCount
+    where
+        N: NoiseModel<Error = SparseBinVec>,
+        D: for<'s> SyndromeDecoder<SparseBinSlice<'s>, SparseBinVec>,
+        R: Rng,
+    {
+        let code = self.code();
+        let logicals = self.logicals();
+        let num_failures = (0..num_trials)
+            .filter(|_| {
+                let error = code.random_error(noise, rng);
+                let correction = decoder.correction_for(code.syndrome_of(&error).as_view());
+                self.record_with(&code, logicals.as_ref(), &error, &correction)
+            })
+            .count();
+        FailureCount::new(num_trials, num_failures)
+    }
+
+    /// Decodes the logged errors again with the given decoder
+    /// and returns the log of those that still fail.
+    pub fn replay<D>(&self, decoder: &D) -> Self
+    where
+        D: for<'s> SyndromeDecoder<SparseBinSlice<'s>, SparseBinVec>,
+    {
+        let code = self.code();
+        let logicals = self.logicals();
+        let mut log = Self {
+            header: self.header.clone(),
+            failures: Vec::new(),
+        };
+        for failure in self.failures.iter() {
+            let error = SparseBinVec::new(code.len(), failure.error.clone());
+            let correction = decoder.correction_for(code.syndrome_of(&error).as_view());
+            log.record_with(&code, logicals.as_ref(), &error, &correction);
+        }
+        log
+    }
+
+    /// Writes the log as JSON lines.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        serde_json::to_writer(&mut writer, &self.header)?;
+        writeln!(writer)?;
+        for failure in self.failures.iter() {
+            serde_json::to_writer(&mut writer, failure)?;
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+
+    /// Reads a log written by [`write_to`](Self::write_to).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`InvalidData`](io::ErrorKind::InvalidData)
+    /// if a line doesn't follow the format or if a position is out of bound.
+    pub fn read_from<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut lines = reader.lines();
+        let header: FailureLogHeader = match lines.next() {
+            Some(line) => serde_json::from_str(&line?)?,
+            None => return Err(invalid_data("missing header")),
+        };
+        let num_bits = header.num_bits;
+        let is_in_bound = |positions: &[usize], length: usize| {
+            positions.iter().all(|position| *position < length)
+        };
+        let has_valid_rows =
+            |rows: &[Vec<usize>]| rows.iter().all(|row| is_in_bound(row, num_bits));
+        if !has_valid_rows(&header.checks)
+            || !has_valid_rows(header.logicals.as_deref().unwrap_or(&[]))
+        {
+            return Err(invalid_data("position out of bound in header"));
+        }
+        let num_checks = header.checks.len();
+        let num_logicals = header
+            .logicals
+            .as_ref()
+            .map_or(0, |logicals| logicals.len());
+        let mut failures = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let failure: FailureRecord = serde_json::from_str(&line)?;
+            let is_valid = is_in_bound(&failure.error, num_bits)
+                && is_in_bound(&failure.correction, num_bits)
+                && is_in_bound(&failure.syndrome, num_checks)
+                && is_in_bound(&failure.flipped_logicals, num_logicals);
+            if !is_valid {
+                return Err(invalid_data("position out of bound in failure"));
+            }
+            failures.push(failure);
+        }
+        Ok(Self { header, failures })
+    }
+
+    /// Saves the log to the given path.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+
+    /// Loads a log saved to the given path.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+}
+
+fn rows_of(matrix: &SparseBinMat) -> Vec<Vec<usize>> {
+    matrix.rows().map(|row| row.as_slice().to_vec()).collect()
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::BpDecoder;
+    use crate::noise::{BinarySymmetricChannel, Probability};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Always returns the trivial correction.
+    struct ZeroDecoder(usize);
+
+    impl SyndromeDecoder<SparseBinSlice<'_>, SparseBinVec> for ZeroDecoder {
+        fn correction_for(&self, _: SparseBinSlice) -> SparseBinVec {
+            SparseBinVec::zeros(self.0)
+        }
+    }
+
+    #[test]
+    fn better_decoder_fixes_failures() {
+        let code = LinearCode::repetition_code(5);
+        let logicals = SparseBinMat::new(5, vec![vec![0]]);
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.1));
+        let mut log = FailureLog::new(&code);
+        log.with_logicals(&logicals);
+        let count = log.run_with(&noise, &ZeroDecoder(5), 100, &mut StdRng::seed_from_u64(1));
+        assert!(count.num_failures() > 0);
+        assert!(log
+            .failures()
+            .iter()
+            .all(|failure| failure.error.contains(&0) && failure.flipped_logicals == vec![0]));
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+        let remaining = log.replay(&decoder);
+        assert!(remaining.failures().len() < log.failures().len());
+        assert_eq!(remaining.logicals(), Some(logicals));
+    }
+
+    #[test]
+    fn corrupted_logs_are_rejected() {
+        let header = r#"{"num_bits":3,"checks":[[0,1],[1,2]]}"#;
+        let valid = format!(
+            "{}\n{}\n",
+            header, r#"{"error":[0],"syndrome":[0],"correction":[],"flipped_logicals":[]}"#
+        );
+        assert_eq!(
+            FailureLog::read_from(valid.as_bytes())
+                .unwrap()
+                .failures()
+                .len(),
+            1
+        );
+        let out_of_bound = format!(
+            "{}\n{}\n",
+            header, r#"{"error":[3],"syndrome":[0],"correction":[],"flipped_logicals":[]}"#
+        );
+        let error = FailureLog::read_from(out_of_bound.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(FailureLog::read_from(&b""[..]).is_err());
+        assert!(FailureLog::read_from(&b"{\"num_bits\":3}"[..]).is_err());
+    }
+}
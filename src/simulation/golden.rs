@@ -0,0 +1,198 @@
+use crate::codes::{Fingerprint, FingerprintHasher, LinearCode};
+use crate::decoders::SyndromeDecoder;
+use crate::noise::Probability;
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
+
+/// A fixed set of codes and errors to pin the behavior of a decoder
+/// with a single [`Fingerprint`].
+///
+/// The errors are sampled with a generator implemented in this crate,
+/// not the one of `rand`, such that the same snapshot gives the same errors
+/// on every platform and in every version of this crate.
+/// The digest hashes the checks of each code with each error and the correction
+/// of the decoder for its syndrome.
+/// Storing the digest of a decoder in a test
+/// detects any change of its corrections after an upgrade.
+///
+/// By default, the codes are the 5 bits repetition code, the Hamming code
+/// and the extended Golay code with 100 errors each
+/// where each bit flips with probability 0.05.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::BpDecoder;
+/// use ldpc::noise::Probability;
+/// use ldpc::simulation::GoldenSnapshot;
+///
+/// let mut snapshot = GoldenSnapshot::new(42);
+/// snapshot
+///     .with_code(LinearCode::repetition_code(9))
+///     .with_num_errors(20);
+///
+/// let digest = snapshot.digest_of(|code| {
+///     BpDecoder::new(code.parity_check_matrix(), Probability::new(0.05), 10)
+/// });
+///
+/// // The digest is stored as a string in the tests of a downstream crate.
+/// assert_eq!(digest.to_string().parse(), Ok(digest));
+/// assert_ne!(
+///     digest,
+///     snapshot.digest_of(|code| {
+///         BpDecoder::new(code.parity_check_matrix(), Probability::new(0.05), 0)
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct GoldenSnapshot {
+    seed: u64,
+    codes: Vec<LinearCode>,
+    num_errors: usize,
+    probability: Probability,
+}
+
+impl GoldenSnapshot {
+    /// Creates a snapshot with the default codes whose errors are sampled from the seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            codes: vec![
+                LinearCode::repetition_code(5),
+                LinearCode::hamming_code(),
+                LinearCode::extended_golay_code(),
+            ],
+            num_errors: 100,
+            probability: Probability::new(0.05),
+        }
+    }
+
+    /// Adds a code after the previous ones.
+    pub fn with_code(&mut self, code: LinearCode) -> &mut Self {
+        self.codes.push(code);
+        self
+    }
+
+    /// Replaces all the codes.
+    pub fn with_codes(&mut self, codes: Vec<LinearCode>) -> &mut Self {
+        self.codes = codes;
+        self
+    }
+
+    /// Sets the number of errors sampled for each code.
+    pub fn with_num_errors(&mut self, num_errors: usize) -> &mut Self {
+        self.num_errors = num_errors;
+        self
+    }
+
+    /// Sets the probability that each bit of an error flips.
+    pub fn with_probability(&mut self, probability: Probability) -> &mut Self {
+        self.probability = probability;
+        self
+    }
+
+    /// Returns the codes of the snapshot.
+    pub fn codes(&self) -> &[LinearCode] {
+        &self.codes
+    }
+
+    /// Returns the errors sampled for each code.
+    ///
+    /// They are the same for every call, platform and version of this crate.
+    pub fn errors(&self) -> Vec<Vec<SparseBinVec>> {
+        let mut generator = SplitMix64::new(self.seed);
+        self.codes
+            .iter()
+            .map(|code| {
+                (0..self.num_errors)
+                    .map(|_| {
+                        let positions = (0..code.len())
+                            .filter(|_| generator.next_f64() < self.probability.value())
+                            .collect();
+                        SparseBinVec::new(code.len(), positions)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the digest of the corrections of the decoders
+    /// built for each code by the given function.
+    pub fn digest_of<D, F>(&self, mut decoder_for: F) -> Fingerprint
+    where
+        D: for<'s> SyndromeDecoder<SparseBinSlice<'s>, SparseBinVec>,
+        F: FnMut(&LinearCode) -> D,
+    {
+        let mut hasher = FingerprintHasher::new("GoldenSnapshot");
+        for (code, errors) in self.codes.iter().zip(self.errors()) {
+            let decoder = decoder_for(code);
+            hasher.write_checks(code.parity_check_matrix());
+            for error in errors {
+                let correction = decoder.correction_for(code.syndrome_of(&error).as_view());
+                hasher.write_positions(error.as_slice());
+                hasher.write_positions(correction.as_slice());
+            }
+        }
+        hasher.finish()
+    }
+}
+
+// The SplitMix64 generator whose outputs are fixed by its specification.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^ (value >> 31)
+    }
+
+    // A uniform value in [0, 1) with 53 random bits.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::BpDecoder;
+
+    // Always returns the trivial correction.
+    struct ZeroDecoder;
+
+    impl SyndromeDecoder<SparseBinSlice<'_>, SparseBinVec> for ZeroDecoder {
+        fn correction_for(&self, _: SparseBinSlice) -> SparseBinVec {
+            SparseBinVec::zeros(0)
+        }
+    }
+
+    #[test]
+    fn splitmix_reference_values() {
+        let mut generator = SplitMix64::new(1234567);
+        assert_eq!(generator.next_u64(), 6457827717110365317);
+        assert_eq!(generator.next_u64(), 3203168211198807973);
+    }
+
+    #[test]
+    fn digests_are_pinned() {
+        let snapshot = GoldenSnapshot::new(7);
+        assert_eq!(
+            snapshot.digest_of(|_| ZeroDecoder).to_string(),
+            "14ec2b81d78ce782537faa928d60eba7"
+        );
+        let bp = |code: &LinearCode| {
+            BpDecoder::new(code.parity_check_matrix(), Probability::new(0.05), 20)
+        };
+        assert_eq!(snapshot.digest_of(bp), snapshot.digest_of(bp));
+        assert_ne!(snapshot.digest_of(bp), GoldenSnapshot::new(8).digest_of(bp));
+    }
+}
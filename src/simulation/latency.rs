@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Wall-clock times of the decodings of a simulation.
+///
+/// To limit the overhead of reading the clock,
+/// only one out of every given number of decodings is timed.
+/// Latency percentiles are computed from the timed decodings.
+///
+/// This requires the `latency` feature.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::simulation::DecodeLatencies;
+/// use std::time::Duration;
+///
+/// let mut latencies = DecodeLatencies::new(1);
+/// for micros in 1..=100 {
+///     latencies.record(Duration::from_micros(micros));
+/// }
+///
+/// assert_eq!(latencies.percentile(0.5), Some(Duration::from_micros(50)));
+/// assert_eq!(latencies.percentile(0.99), Some(Duration::from_micros(99)));
+/// assert_eq!(latencies.max(), Some(Duration::from_micros(100)));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DecodeLatencies {
+    sample_every: usize,
+    num_decodings: usize,
+    samples: Vec<Duration>,
+}
+
+impl DecodeLatencies {
+    /// Creates empty latencies timing one out of every `sample_every` decodings.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `sample_every` is 0.
+    pub fn new(sample_every: usize) -> Self {
+        if sample_every == 0 {
+            panic!("can't time one out of every 0 decodings");
+        }
+        Self {
+            sample_every,
+            num_decodings: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Runs the given decoding and records its wall-clock time
+    /// if it is one of the sampled decodings.
+    pub fn time<T, F: FnOnce() -> T>(&mut self, decode: F) -> T {
+        let is_sampled = self.num_decodings % self.sample_every == 0;
+        self.num_decodings += 1;
+        if is_sampled {
+            let start = Instant::now();
+            let output = decode();
+            self.samples.push(start.elapsed());
+            output
+        } else {
+            decode()
+        }
+    }
+
+    /// Records the wall-clock time of a decoding that was timed.
+    pub fn record(&mut self, latency: Duration) {
+        self.num_decodings += 1;
+        self.samples.push(latency);
+    }
+
+    /// Returns the number of decodings out of which one is timed.
+    pub fn sample_every(&self) -> usize {
+        self.sample_every
+    }
+
+    /// Returns the number of decodings, timed or not.
+    pub fn num_decodings(&self) -> usize {
+        self.num_decodings
+    }
+
+    /// Returns the times of the timed decodings in the order they were recorded.
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+
+    /// Returns the smallest timed latency such that at least the given fraction
+    /// of the timed decodings were as fast or None if no decoding was timed.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the fraction is not between 0 and 1.
+    pub fn percentile(&self, fraction: f64) -> Option<Duration> {
+        if !(0.0..=1.0).contains(&fraction) {
+            panic!("fraction {} is not between 0 and 1", fraction);
+        }
+        let mut samples = self.samples.clone();
+        samples.sort_unstable();
+        let rank = (fraction * samples.len() as f64).ceil() as usize;
+        samples.get(rank.saturating_sub(1)).copied()
+    }
+
+    /// Returns the median latency or None if no decoding was timed.
+    pub fn median(&self) -> Option<Duration> {
+        self.percentile(0.5)
+    }
+
+    /// Returns the slowest latency or None if no decoding was timed.
+    pub fn max(&self) -> Option<Duration> {
+        self.samples.iter().max().copied()
+    }
+
+    /// Returns the average latency or None if no decoding was timed.
+    pub fn mean(&self) -> Option<Duration> {
+        if self.samples.is_empty() {
+            None
+        } else {
+            Some(self.samples.iter().sum::<Duration>() / self.samples.len() as u32)
+        }
+    }
+
+    /// Returns the latencies of the decodings of both simulations.
+    ///
+    /// The sampling rate of the first simulation is kept.
+    pub fn merged_with(&self, other: &Self) -> Self {
+        Self {
+            sample_every: self.sample_every,
+            num_decodings: self.num_decodings + other.num_decodings,
+            samples: self.samples.iter().chain(&other.samples).copied().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn only_sampled_decodings_are_timed() {
+        let mut latencies = DecodeLatencies::new(3);
+        let outputs = (0..7)
+            .map(|trial| latencies.time(|| trial))
+            .collect::<Vec<_>>();
+        assert_eq!(outputs, (0..7).collect::<Vec<_>>());
+        assert_eq!(latencies.num_decodings(), 7);
+        assert_eq!(latencies.samples().len(), 3);
+    }
+
+    #[test]
+    fn percentiles_of_empty_and_merged_latencies() {
+        let empty = DecodeLatencies::new(1);
+        assert_eq!(empty.percentile(0.9), None);
+        assert_eq!(empty.mean(), None);
+        let mut latencies = DecodeLatencies::new(1);
+        latencies.record(Duration::from_millis(4));
+        let merged = latencies.merged_with(&latencies);
+        assert_eq!(merged.num_decodings(), 2);
+        assert_eq!(merged.percentile(0.0), Some(Duration::from_millis(4)));
+        assert_eq!(merged.mean(), Some(Duration::from_millis(4)));
+    }
+}
@@ -0,0 +1,875 @@
+use crate::codes::CssCode;
+use crate::css::{CssOperator, CssSyndromeView};
+use crate::decoders::SyndromeDecoder;
+use crate::noise::{
+    BurstEventModel, DriftSchedule, DriftingNoise, LeakageModel, LeakageState, NoiseModel,
+    Probability,
+};
+use pauli::PauliOperator;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "latency")]
+use super::DecodeLatencies;
+
+// Maps a noise model to the same model with another probability.
+type Drifted<N> = fn(&N, Probability) -> N;
+
+/// A quantum memory experiment with Pauli frame tracking.
+///
+/// Each trial starts from a random element of the stabilizer group as Pauli frame.
+/// Then, at each round, an error is sampled from the noise model and applied to the frame,
+/// the syndrome of the frame is decoded and the correction is applied to the frame.
+/// The trial fails at the first round where the frame is a non-trivial logical operator
+/// and survives if this never happens during the maximum number of rounds.
+/// A correction leaving a non-zero syndrome doesn't end the trial,
+/// the leftover frame is carried to the next round and decoded together with the new errors.
+/// However, a trial still having a non-zero syndrome after the last round
+/// can't be read out and fails at that round.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::decoders::{BpDecoder, CssDecoder};
+/// use ldpc::noise::{DepolarizingNoise, Probability};
+/// use ldpc::simulation::MemoryExperiment;
+/// use rand::thread_rng;
+///
+/// let code = CssCode::steane_code();
+/// let decoder = CssDecoder {
+///     x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.05), 10),
+///     z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.05), 10),
+/// };
+/// let noise = DepolarizingNoise::with_probability(Probability::new(0.05));
+///
+/// let lifetime = MemoryExperiment::new(&code, &noise, &decoder)
+///     .max_rounds(20)
+///     .run_with(100, &mut thread_rng());
+///
+/// assert_eq!(lifetime.num_trials(), 100);
+/// assert_eq!(lifetime.num_failures() + lifetime.num_survivors(), 100);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryExperiment<'a, N, D> {
+    code: &'a CssCode,
+    noise: &'a N,
+    decoder: &'a D,
+    max_rounds: usize,
+    leakage: Option<&'a LeakageModel>,
+    burst_events: Option<&'a BurstEventModel>,
+    drift: Option<(&'a DriftSchedule, Drifted<N>)>,
+}
+
+impl<'a, N, D> MemoryExperiment<'a, N, D>
+where
+    N: NoiseModel<Error = PauliOperator>,
+    D: for<'s> SyndromeDecoder<CssSyndromeView<'s>, CssOperator>,
+{
+    /// Creates a new memory experiment for the given code, noise model and decoder.
+    pub fn new(code: &'a CssCode, noise: &'a N, decoder: &'a D) -> Self {
+        Self {
+            code,
+            noise,
+            decoder,
+            max_rounds: 1000,
+            leakage: None,
+            burst_events: None,
+            drift: None,
+        }
+    }
+
+    /// Fixes the maximum number of rounds of each trial.
+    ///
+    /// Default is 1000.
+    pub fn max_rounds(&mut self, max_rounds: usize) -> &mut Self {
+        self.max_rounds = max_rounds;
+        self
+    }
+
+    /// Adds leakage to the noise of each round.
+    ///
+    /// The leaked qubits are advanced at the start of each round,
+    /// then they receive a random Pauli together with the error of the noise model
+    /// and the outcomes of their checks are randomized before decoding.
+    /// Since the leftover frame is carried over,
+    /// leaked qubits keep corrupting the decoding of later rounds until they are reset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::decoders::{BpDecoder, CssDecoder};
+    /// use ldpc::noise::{DepolarizingNoise, LeakageModel, Probability, ResetStrategy};
+    /// use ldpc::simulation::MemoryExperiment;
+    /// use rand::thread_rng;
+    ///
+    /// let code = CssCode::steane_code();
+    /// let decoder = CssDecoder {
+    ///     x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.05), 10),
+    ///     z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.05), 10),
+    /// };
+    /// let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+    /// let leakage = LeakageModel::new(Probability::new(0.01)).with_reset(ResetStrategy::Periodic(5));
+    ///
+    /// let lifetime = MemoryExperiment::new(&code, &noise, &decoder)
+    ///     .max_rounds(20)
+    ///     .leakage(&leakage)
+    ///     .run_with(100, &mut thread_rng());
+    ///
+    /// assert_eq!(lifetime.num_trials(), 100);
+    /// ```
+    pub fn leakage(&mut self, leakage: &'a LeakageModel) -> &mut Self {
+        self.leakage = Some(leakage);
+        self
+    }
+
+    /// Adds burst events depolarizing whole regions of qubits to the noise of each round.
+    ///
+    /// The error of an event is applied together with the error of the noise model
+    /// and the regions are computed from the coordinates of the code.
+    /// The failures following the events are reported separately
+    /// by [`run_with_events`](MemoryExperiment::run_with_events).
+    ///
+    /// # Panic
+    ///
+    /// Panics if the code has no [coordinates](CssCode::coordinates).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::decoders::{BpDecoder, CssDecoder};
+    /// use ldpc::noise::{BurstEventModel, DepolarizingNoise, Probability};
+    /// use ldpc::simulation::MemoryExperiment;
+    /// use rand::thread_rng;
+    ///
+    /// let code = CssCode::rotated_surface_code(5);
+    /// let decoder = CssDecoder {
+    ///     x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.01), 20),
+    ///     z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.01), 20),
+    /// };
+    /// let noise = DepolarizingNoise::with_probability(Probability::new(0.001));
+    /// let events = BurstEventModel::new(Probability::new(0.05), 3.0);
+    ///
+    /// let statistics = MemoryExperiment::new(&code, &noise, &decoder)
+    ///     .max_rounds(20)
+    ///     .burst_events(&events)
+    ///     .run_with_events(100, 2, &mut thread_rng());
+    ///
+    /// assert_eq!(statistics.lifetime().num_trials(), 100);
+    /// assert_eq!(
+    ///     statistics.num_event_failures() + statistics.num_quiet_failures(),
+    ///     statistics.lifetime().num_failures()
+    /// );
+    /// ```
+    pub fn burst_events(&mut self, events: &'a BurstEventModel) -> &mut Self {
+        if self.code.coordinates().is_none() {
+            panic!("burst events require the coordinates of the code");
+        }
+        self.burst_events = Some(events);
+        self
+    }
+
+    /// Runs a single trial and returns the round at which the memory failed,
+    /// if it failed, together with the number of rounds whose correction
+    /// left a non-zero syndrome.
+    ///
+    /// Rounds are counted starting from 1.
+    pub fn run_once<R: Rng>(&self, rng: &mut R) -> MemoryOutcome {
+        self.run_once_decoding_with(rng, |syndrome| self.decoder.correction_for(syndrome))
+            .0
+    }
+
+    // The outcome and the rounds with a burst event.
+    fn run_once_decoding_with<R, F>(
+        &self,
+        rng: &mut R,
+        mut decode: F,
+    ) -> (MemoryOutcome, Vec<usize>)
+    where
+        R: Rng,
+        F: FnMut(CssSyndromeView) -> CssOperator,
+    {
+        let mut event_rounds = Vec::new();
+        let mut num_non_converged_rounds = 0;
+        let mut frame = self.code.random_stabilizer(rng);
+        let mut leakage_state = LeakageState::new(self.code.len());
+        let drifted_noises = self.drift.map(|(schedule, drifted)| {
+            schedule
+                .sample_trajectory(self.max_rounds, rng)
+                .into_iter()
+                .map(|probability| drifted(self.noise, probability))
+                .collect::<Vec<_>>()
+        });
+        for round in 1..=self.max_rounds {
+            let noise = drifted_noises
+                .as_ref()
+                .map_or(self.noise, |noises| &noises[round - 1]);
+            let error = self.code.random_error(noise, rng);
+            frame = &frame * &error;
+            if let Some(leakage) = self.leakage {
+                leakage.advance(&mut leakage_state, rng);
+                frame = &frame * &leakage.sample_leaked_error(&leakage_state, rng);
+            }
+            if let Some(events) = self.burst_events {
+                let layout = self.code.coordinates().unwrap();
+                if let Some(event) = events.sample_event(layout, rng) {
+                    frame = &frame * &event;
+                    event_rounds.push(round);
+                }
+            }
+            let mut syndrome = self.code.syndrome_of(&frame);
+            if let Some(leakage) = self.leakage {
+                leakage.randomize_syndrome(self.code, &leakage_state, &mut syndrome, rng);
+            }
+            let correction: PauliOperator = decode(syndrome.as_view()).into();
+            frame = &frame * &correction;
+            if !self.code.has_logical(&frame) {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(round, "decoder did not converge");
+                num_non_converged_rounds += 1;
+            } else if !self.code.has_stabilizer(&frame) {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(round, "memory failed");
+                let outcome = MemoryOutcome {
+                    failure_round: Some(round),
+                    num_non_converged_rounds,
+                };
+                return (outcome, event_rounds);
+            }
+        }
+        let outcome = MemoryOutcome {
+            failure_round: (!self.code.has_logical(&frame)).then_some(self.max_rounds),
+            num_non_converged_rounds,
+        };
+        (outcome, event_rounds)
+    }
+
+    /// Runs the given number of trials and collects the lifetime statistics.
+    pub fn run_with<R: Rng>(&self, num_trials: usize, rng: &mut R) -> MemoryLifetime {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "memory_experiment",
+            num_trials,
+            max_rounds = self.max_rounds
+        )
+        .entered();
+        let mut lifetime = MemoryLifetime::new(self.max_rounds);
+        for _ in 0..num_trials {
+            lifetime.record_outcome(self.run_once(rng));
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            num_failures = lifetime.num_failures(),
+            total_rounds = lifetime.total_rounds(),
+            "memory experiment finished"
+        );
+        lifetime
+    }
+
+    /// Runs the given number of trials and collects the lifetime statistics
+    /// split between the windows following the [burst events](MemoryExperiment::burst_events)
+    /// and the quiet rounds.
+    ///
+    /// A window contains the round of an event and the following rounds,
+    /// up to the given number of rounds in total,
+    /// since the errors of an event can survive a few imperfect corrections.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the window is 0 rounds.
+    pub fn run_with_events<R: Rng>(
+        &self,
+        num_trials: usize,
+        window: usize,
+        rng: &mut R,
+    ) -> BurstEventStatistics {
+        let mut statistics = BurstEventStatistics::new(self.max_rounds, window);
+        for _ in 0..num_trials {
+            let (outcome, event_rounds) =
+                self.run_once_decoding_with(rng, |syndrome| self.decoder.correction_for(syndrome));
+            statistics.record_outcome(outcome, &event_rounds);
+        }
+        statistics
+    }
+
+    /// Runs the given number of trials and collects the lifetime statistics
+    /// together with the wall-clock time of one out of every `sample_every` decodings.
+    ///
+    /// This requires the `latency` feature.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `sample_every` is 0.
+    #[cfg(feature = "latency")]
+    pub fn run_with_latencies<R: Rng>(
+        &self,
+        num_trials: usize,
+        sample_every: usize,
+        rng: &mut R,
+    ) -> (MemoryLifetime, DecodeLatencies) {
+        let mut lifetime = MemoryLifetime::new(self.max_rounds);
+        let mut latencies = DecodeLatencies::new(sample_every);
+        for _ in 0..num_trials {
+            lifetime.record_outcome(
+                self.run_once_decoding_with(rng, |syndrome| {
+                    latencies.time(|| self.decoder.correction_for(syndrome))
+                })
+                .0,
+            );
+        }
+        (lifetime, latencies)
+    }
+}
+
+impl<'a, N, D> MemoryExperiment<'a, N, D>
+where
+    N: DriftingNoise<Error = PauliOperator>,
+    D: for<'s> SyndromeDecoder<CssSyndromeView<'s>, CssOperator>,
+{
+    /// Drifts the probability of the noise model from round to round
+    /// following the schedule.
+    ///
+    /// A new trajectory of probabilities is sampled for each trial
+    /// and replaces the probability of the noise model at each round.
+    /// The decoder keeps its own priors, as a decoder unaware of the drift.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::codes::CssCode;
+    /// use ldpc::decoders::{BpDecoder, CssDecoder};
+    /// use ldpc::noise::{DepolarizingNoise, DriftSchedule, Probability};
+    /// use ldpc::simulation::MemoryExperiment;
+    /// use rand::thread_rng;
+    ///
+    /// let code = CssCode::steane_code();
+    /// let decoder = CssDecoder {
+    ///     x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.05), 10),
+    ///     z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.05), 10),
+    /// };
+    /// let noise = DepolarizingNoise::with_probability(Probability::new(0.05));
+    /// let drift = DriftSchedule::Sinusoidal { mean: 0.05, amplitude: 0.04, period: 10 };
+    ///
+    /// let lifetime = MemoryExperiment::new(&code, &noise, &decoder)
+    ///     .max_rounds(20)
+    ///     .drift(&drift)
+    ///     .run_with(100, &mut thread_rng());
+    ///
+    /// assert_eq!(lifetime.failure_distribution().iter().sum::<usize>(), lifetime.num_failures());
+    /// ```
+    pub fn drift(&mut self, schedule: &'a DriftSchedule) -> &mut Self {
+        self.drift = Some((schedule, N::with_drifted_probability));
+        self
+    }
+}
+
+/// The outcome of a trial of a memory experiment.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct MemoryOutcome {
+    /// The round at which the memory failed or None if it survived all rounds.
+    pub failure_round: Option<usize>,
+    /// The number of rounds whose correction left a non-zero syndrome.
+    pub num_non_converged_rounds: usize,
+}
+
+/// Time-to-failure statistics of a memory experiment.
+///
+/// Trials surviving all rounds are censored at the maximum number of rounds.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct MemoryLifetime {
+    max_rounds: usize,
+    failure_rounds: Vec<usize>,
+    num_survivors: usize,
+    #[serde(default)]
+    num_non_converged_rounds: usize,
+}
+
+impl MemoryLifetime {
+    /// Creates empty statistics for trials of at most the given number of rounds.
+    pub fn new(max_rounds: usize) -> Self {
+        Self {
+            max_rounds,
+            failure_rounds: Vec::new(),
+            num_survivors: 0,
+            num_non_converged_rounds: 0,
+        }
+    }
+
+    /// Records the outcome of a trial given the round where it failed
+    /// or None if it survived.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the failure round is 0 or greater than the maximum number of rounds.
+    pub fn record(&mut self, failure_round: Option<usize>) {
+        self.record_outcome(MemoryOutcome {
+            failure_round,
+            num_non_converged_rounds: 0,
+        });
+    }
+
+    /// Records the outcome of a trial.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the failure round is 0 or greater than the maximum number of rounds.
+    pub fn record_outcome(&mut self, outcome: MemoryOutcome) {
+        match outcome.failure_round {
+            Some(round) => {
+                if round == 0 || round > self.max_rounds {
+                    panic!(
+                        "failure round {} is invalid for at most {} rounds",
+                        round, self.max_rounds
+                    );
+                }
+                self.failure_rounds.push(round);
+            }
+            None => self.num_survivors += 1,
+        }
+        self.num_non_converged_rounds += outcome.num_non_converged_rounds;
+    }
+
+    /// Returns the maximum number of rounds of each trial.
+    pub fn max_rounds(&self) -> usize {
+        self.max_rounds
+    }
+
+    /// Returns the rounds at which each failing trial failed.
+    pub fn failure_rounds(&self) -> &[usize] {
+        &self.failure_rounds
+    }
+
+    /// Returns the number of trials.
+    pub fn num_trials(&self) -> usize {
+        self.num_failures() + self.num_survivors
+    }
+
+    /// Returns the number of trials that failed before the maximum number of rounds.
+    pub fn num_failures(&self) -> usize {
+        self.failure_rounds.len()
+    }
+
+    /// Returns the number of trials that survived all rounds.
+    pub fn num_survivors(&self) -> usize {
+        self.num_survivors
+    }
+
+    /// Returns the number of rounds over all trials whose correction
+    /// left a non-zero syndrome carried to the next round.
+    pub fn num_non_converged_rounds(&self) -> usize {
+        self.num_non_converged_rounds
+    }
+
+    /// Returns the total number of rounds over all trials.
+    ///
+    /// A failing trial contributes the rounds up to and including its failure.
+    pub fn total_rounds(&self) -> usize {
+        self.failure_rounds.iter().sum::<usize>() + self.num_survivors * self.max_rounds
+    }
+
+    /// Returns the fraction of trials still alive after the given number of rounds
+    /// or None if there is no trial.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::simulation::MemoryLifetime;
+    /// let mut lifetime = MemoryLifetime::new(10);
+    /// lifetime.record(Some(2));
+    /// lifetime.record(Some(5));
+    /// lifetime.record(None);
+    /// lifetime.record(None);
+    ///
+    /// assert_eq!(lifetime.survival_probability(1), Some(1.0));
+    /// assert_eq!(lifetime.survival_probability(2), Some(0.75));
+    /// assert_eq!(lifetime.survival_probability(10), Some(0.5));
+    /// assert_eq!(MemoryLifetime::new(10).survival_probability(1), None);
+    /// ```
+    pub fn survival_probability(&self, rounds: usize) -> Option<f64> {
+        let num_failed = self
+            .failure_rounds
+            .iter()
+            .filter(|failure| **failure <= rounds)
+            .count();
+        rate(self.num_trials() - num_failed, self.num_trials())
+    }
+
+    /// Returns the number of trials failing at each round,
+    /// starting from round 1 up to the maximum number of rounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::simulation::MemoryLifetime;
+    /// let mut lifetime = MemoryLifetime::new(4);
+    /// lifetime.record(Some(2));
+    /// lifetime.record(Some(4));
+    /// lifetime.record(Some(2));
+    /// lifetime.record(None);
+    ///
+    /// assert_eq!(lifetime.failure_distribution(), vec![0, 2, 0, 1]);
+    /// ```
+    pub fn failure_distribution(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.max_rounds];
+        for round in self.failure_rounds.iter() {
+            counts[round - 1] += 1;
+        }
+        counts
+    }
+
+    /// Returns the average number of rounds before failure
+    /// among the failing trials or None if no trial failed.
+    pub fn mean_rounds_to_failure(&self) -> Option<f64> {
+        if self.failure_rounds.is_empty() {
+            None
+        } else {
+            Some(self.failure_rounds.iter().sum::<usize>() as f64 / self.num_failures() as f64)
+        }
+    }
+
+    /// Returns the maximum likelihood estimate of the logical failure rate per round
+    /// assuming a constant failure rate or None if there is no round.
+    ///
+    /// That is, the number of failures divided by the total number of rounds.
+    pub fn logical_error_rate_per_round(&self) -> Option<f64> {
+        rate(self.num_failures(), self.total_rounds())
+    }
+}
+
+/// The statistics of a memory experiment with burst events,
+/// split between the rounds in the window of an event and the quiet rounds.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct BurstEventStatistics {
+    lifetime: MemoryLifetime,
+    window: usize,
+    num_events: usize,
+    num_event_rounds: usize,
+    num_event_failures: usize,
+}
+
+impl BurstEventStatistics {
+    /// Creates empty statistics for trials of at most the given number of rounds
+    /// where each event opens a window of the given number of rounds.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the window is 0 rounds.
+    pub fn new(max_rounds: usize, window: usize) -> Self {
+        if window == 0 {
+            panic!("windows of 0 rounds");
+        }
+        Self {
+            lifetime: MemoryLifetime::new(max_rounds),
+            window,
+            num_events: 0,
+            num_event_rounds: 0,
+            num_event_failures: 0,
+        }
+    }
+
+    /// Records the outcome of a trial given the round where it failed
+    /// or None if it survived and the rounds where an event happened.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the failure round is 0 or greater than the maximum number of rounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ldpc::simulation::BurstEventStatistics;
+    /// let mut statistics = BurstEventStatistics::new(10, 2);
+    /// statistics.record(Some(4), &[3]);
+    /// statistics.record(None, &[9]);
+    ///
+    /// assert_eq!(statistics.num_event_rounds(), 4);
+    /// assert_eq!(statistics.num_quiet_rounds(), 10);
+    /// assert_eq!(statistics.num_event_failures(), 1);
+    /// assert_eq!(statistics.event_failure_rate_per_round(), Some(0.25));
+    /// assert_eq!(statistics.quiet_failure_rate_per_round(), Some(0.0));
+    /// ```
+    pub fn record(&mut self, failure_round: Option<usize>, event_rounds: &[usize]) {
+        self.record_outcome(
+            MemoryOutcome {
+                failure_round,
+                num_non_converged_rounds: 0,
+            },
+            event_rounds,
+        );
+    }
+
+    /// Records the outcome of a trial and the rounds where an event happened.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the failure round is 0 or greater than the maximum number of rounds.
+    pub fn record_outcome(&mut self, outcome: MemoryOutcome, event_rounds: &[usize]) {
+        self.lifetime.record_outcome(outcome);
+        let failure_round = outcome.failure_round;
+        let last_round = failure_round.unwrap_or(self.lifetime.max_rounds());
+        let is_in_window = |round: usize| {
+            event_rounds
+                .iter()
+                .any(|event| *event <= round && round < event + self.window)
+        };
+        self.num_events += event_rounds.len();
+        self.num_event_rounds += (1..=last_round)
+            .filter(|round| is_in_window(*round))
+            .count();
+        if failure_round.is_some_and(is_in_window) {
+            self.num_event_failures += 1;
+        }
+    }
+
+    /// Returns the statistics of all the trials.
+    pub fn lifetime(&self) -> &MemoryLifetime {
+        &self.lifetime
+    }
+
+    /// Returns the number of rounds in the window of an event.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the number of events over all trials.
+    pub fn num_events(&self) -> usize {
+        self.num_events
+    }
+
+    /// Returns the number of rounds in the window of an event over all trials.
+    pub fn num_event_rounds(&self) -> usize {
+        self.num_event_rounds
+    }
+
+    /// Returns the number of rounds outside the windows of the events over all trials.
+    pub fn num_quiet_rounds(&self) -> usize {
+        self.lifetime.total_rounds() - self.num_event_rounds
+    }
+
+    /// Returns the number of trials failing in the window of an event.
+    pub fn num_event_failures(&self) -> usize {
+        self.num_event_failures
+    }
+
+    /// Returns the number of trials failing outside the windows of the events.
+    pub fn num_quiet_failures(&self) -> usize {
+        self.lifetime.num_failures() - self.num_event_failures
+    }
+
+    /// Returns the number of failures per round in the windows of the events
+    /// or None if there is no such round.
+    pub fn event_failure_rate_per_round(&self) -> Option<f64> {
+        rate(self.num_event_failures(), self.num_event_rounds())
+    }
+
+    /// Returns the number of failures per round outside the windows of the events
+    /// or None if there is no such round.
+    pub fn quiet_failure_rate_per_round(&self) -> Option<f64> {
+        rate(self.num_quiet_failures(), self.num_quiet_rounds())
+    }
+}
+
+fn rate(count: usize, total: usize) -> Option<f64> {
+    (total > 0).then(|| count as f64 / total as f64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::{BpDecoder, CssDecoder};
+    use crate::noise::{DepolarizingNoise, Probability, ResetStrategy};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use sparse_bin_mat::SparseBinVec;
+
+    fn decoder(code: &CssCode) -> CssDecoder<BpDecoder> {
+        CssDecoder {
+            x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.1), 10),
+            z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.1), 10),
+        }
+    }
+
+    #[test]
+    fn memory_never_fails_without_noise() {
+        let code = CssCode::steane_code();
+        let decoder = decoder(&code);
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+        let lifetime = MemoryExperiment::new(&code, &noise, &decoder)
+            .max_rounds(5)
+            .run_with(10, &mut StdRng::seed_from_u64(123));
+        assert_eq!(lifetime.num_survivors(), 10);
+        assert_eq!(lifetime.total_rounds(), 50);
+        assert_eq!(lifetime.logical_error_rate_per_round(), Some(0.0));
+        assert_eq!(lifetime.mean_rounds_to_failure(), None);
+    }
+
+    #[test]
+    fn leaked_qubits_destroy_the_memory() {
+        let code = CssCode::steane_code();
+        let decoder = decoder(&code);
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+        let leakage = LeakageModel::new(Probability::new(1.0));
+        let lifetime = MemoryExperiment::new(&code, &noise, &decoder)
+            .max_rounds(20)
+            .leakage(&leakage)
+            .run_with(10, &mut StdRng::seed_from_u64(123));
+        assert_eq!(lifetime.num_failures(), 10);
+        let leakage = LeakageModel::new(Probability::new(0.0));
+        let lifetime = MemoryExperiment::new(&code, &noise, &decoder)
+            .max_rounds(20)
+            .leakage(&leakage)
+            .run_with(10, &mut StdRng::seed_from_u64(123));
+        assert_eq!(lifetime.num_survivors(), 10);
+    }
+
+    #[test]
+    fn resetting_leaked_qubits_extends_the_memory_lifetime() {
+        let code = CssCode::steane_code();
+        let decoder = decoder(&code);
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+        let run = |leakage: &LeakageModel| {
+            MemoryExperiment::new(&code, &noise, &decoder)
+                .max_rounds(20)
+                .leakage(leakage)
+                .run_with(100, &mut StdRng::seed_from_u64(123))
+        };
+        let leakage = LeakageModel::new(Probability::new(0.01));
+        let without_reset = run(&leakage);
+        let with_reset = run(&leakage.with_reset(ResetStrategy::Periodic(1)));
+        assert!(without_reset.num_failures() > 50);
+        assert!(with_reset.num_failures() < 40);
+    }
+
+    #[test]
+    fn memory_only_fails_once_the_noise_drifts() {
+        let code = CssCode::steane_code();
+        let decoder = decoder(&code);
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+        let drift = DriftSchedule::Explicit(vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.5]);
+        let lifetime = MemoryExperiment::new(&code, &noise, &decoder)
+            .max_rounds(20)
+            .drift(&drift)
+            .run_with(20, &mut StdRng::seed_from_u64(123));
+        assert!(lifetime.num_failures() >= 19);
+        assert_eq!(&lifetime.failure_distribution()[..5], &[0; 5]);
+    }
+
+    #[test]
+    fn failures_are_split_between_event_windows_and_quiet_rounds() {
+        let code = CssCode::rotated_surface_code(3);
+        let decoder = decoder(&code);
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+        let events = BurstEventModel::new(Probability::new(1.0), 100.0);
+        let statistics = MemoryExperiment::new(&code, &noise, &decoder)
+            .max_rounds(10)
+            .burst_events(&events)
+            .run_with_events(20, 1, &mut StdRng::seed_from_u64(123));
+        assert_eq!(statistics.num_event_failures(), 20);
+        assert_eq!(statistics.num_quiet_failures(), 0);
+        assert_eq!(statistics.num_quiet_rounds(), 0);
+        assert_eq!(statistics.num_events(), statistics.num_event_rounds());
+        assert_eq!(statistics.quiet_failure_rate_per_round(), None);
+        let events = BurstEventModel::new(Probability::new(0.0), 100.0);
+        let statistics = MemoryExperiment::new(&code, &noise, &decoder)
+            .max_rounds(10)
+            .burst_events(&events)
+            .run_with_events(20, 1, &mut StdRng::seed_from_u64(123));
+        assert_eq!(statistics.num_events(), 0);
+        assert_eq!(statistics.num_quiet_rounds(), 200);
+        assert_eq!(statistics.event_failure_rate_per_round(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn burst_events_require_coordinates() {
+        let code = CssCode::steane_code();
+        let decoder = decoder(&code);
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+        let events = BurstEventModel::new(Probability::new(1.0), 1.0);
+        MemoryExperiment::new(&code, &noise, &decoder).burst_events(&events);
+    }
+
+    #[test]
+    fn statistics_of_recorded_trials() {
+        let mut lifetime = MemoryLifetime::new(4);
+        lifetime.record(Some(1));
+        lifetime.record(Some(3));
+        lifetime.record(None);
+        assert_eq!(lifetime.num_trials(), 3);
+        assert_eq!(lifetime.num_failures(), 2);
+        assert_eq!(lifetime.total_rounds(), 8);
+        assert_eq!(lifetime.mean_rounds_to_failure(), Some(2.0));
+        assert_eq!(lifetime.logical_error_rate_per_round(), Some(0.25));
+    }
+
+    #[test]
+    fn statistics_are_undefined_without_trials() {
+        let lifetime = MemoryLifetime::new(4);
+        assert_eq!(lifetime.survival_probability(2), None);
+        assert_eq!(lifetime.logical_error_rate_per_round(), None);
+    }
+
+    #[test]
+    fn non_converged_rounds_are_accumulated_over_trials() {
+        let mut lifetime = MemoryLifetime::new(4);
+        lifetime.record_outcome(MemoryOutcome {
+            failure_round: Some(2),
+            num_non_converged_rounds: 1,
+        });
+        lifetime.record_outcome(MemoryOutcome {
+            failure_round: None,
+            num_non_converged_rounds: 3,
+        });
+        assert_eq!(lifetime.num_trials(), 2);
+        assert_eq!(lifetime.num_failures(), 1);
+        assert_eq!(lifetime.num_non_converged_rounds(), 4);
+        assert_eq!(lifetime.total_rounds(), 6);
+    }
+
+    // A decoder that never corrects anything on the given number of qubits.
+    struct IdleDecoder(usize);
+
+    impl<'s> SyndromeDecoder<CssSyndromeView<'s>, CssOperator> for IdleDecoder {
+        fn correction_for(&self, _: CssSyndromeView<'s>) -> CssOperator {
+            CssOperator {
+                x: SparseBinVec::zeros(self.0),
+                z: SparseBinVec::zeros(self.0),
+            }
+        }
+    }
+
+    #[test]
+    fn non_converging_decoder_fails() {
+        let code = CssCode::steane_code();
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.3));
+        let lifetime = MemoryExperiment::new(&code, &noise, &IdleDecoder(code.len()))
+            .max_rounds(1000)
+            .run_with(10, &mut StdRng::seed_from_u64(123));
+        assert_eq!(lifetime.num_failures(), 10);
+        assert!(lifetime.num_non_converged_rounds() > 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn failure_after_max_rounds_is_invalid() {
+        let mut lifetime = MemoryLifetime::new(4);
+        lifetime.record(Some(5));
+    }
+
+    #[test]
+    #[cfg(feature = "latency")]
+    fn latencies_are_sampled_from_each_round() {
+        let code = CssCode::steane_code();
+        let decoder = decoder(&code);
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+        let (lifetime, latencies) = MemoryExperiment::new(&code, &noise, &decoder)
+            .max_rounds(5)
+            .run_with_latencies(10, 4, &mut StdRng::seed_from_u64(123));
+        assert_eq!(lifetime.num_survivors(), 10);
+        assert_eq!(latencies.num_decodings(), 50);
+        assert_eq!(latencies.samples().len(), 13);
+        assert!(latencies.percentile(0.99) <= latencies.max());
+    }
+}
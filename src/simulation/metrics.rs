@@ -0,0 +1,150 @@
+/// Returns the pseudo-threshold of a code, that is the physical error probability
+/// below which the logical error rate is smaller than the physical one,
+/// or None if the sweep doesn't cross the line where both rates are equal.
+///
+/// The points are pairs of physical error probability and logical error rate
+/// in any order.
+/// The crossing is linearly interpolated between the last point of the sweep
+/// where the logical error rate is smaller than the physical probability
+/// and the next point.
+/// For memory experiments, the logical error rate per round should be compared
+/// with the physical probability per round.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::simulation::pseudo_threshold;
+///
+/// let points = [(0.01, 0.001), (0.02, 0.01), (0.04, 0.05), (0.08, 0.2)];
+/// let threshold = pseudo_threshold(&points).unwrap();
+/// assert!((threshold - 0.03).abs() < 1e-12);
+///
+/// // The logical error rate is always smaller.
+/// assert_eq!(pseudo_threshold(&points[..2]), None);
+/// ```
+pub fn pseudo_threshold(points: &[(f64, f64)]) -> Option<f64> {
+    let mut points = points.to_vec();
+    points.sort_by(|first, second| first.0.total_cmp(&second.0));
+    let last_below = points
+        .iter()
+        .rposition(|(physical, logical)| logical < physical)?;
+    let (low_physical, low_logical) = points[last_below];
+    let (high_physical, high_logical) = *points.get(last_below + 1)?;
+    let low_gap = low_physical - low_logical;
+    let high_gap = high_physical - high_logical;
+    Some(low_physical + (high_physical - low_physical) * low_gap / (low_gap - high_gap))
+}
+
+/// Returns the error suppression factors Λ between consecutive distances.
+///
+/// The points are pairs of code distance and logical error rate
+/// at a fixed physical error probability in any order.
+/// The factor between distances `d` and `d'` is `(ε_d / ε_d') ^ (2 / (d' - d))`,
+/// that is the reduction of the logical error rate ε
+/// when the distance increases by 2.
+/// It is infinite if the logical error rate at the larger distance is 0.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::simulation::lambda_factors;
+///
+/// let points = [(3, 0.03), (5, 0.01), (9, 0.0025)];
+/// let factors = lambda_factors(&points);
+/// assert!((factors[0] - 3.0).abs() < 1e-12);
+/// assert!((factors[1] - 2.0).abs() < 1e-12);
+/// ```
+///
+/// # Panic
+///
+/// Panics if two points have the same distance.
+pub fn lambda_factors(points: &[(usize, f64)]) -> Vec<f64> {
+    sorted_by_distance(points)
+        .windows(2)
+        .map(|pair| {
+            let ((low_distance, low_rate), (high_distance, high_rate)) = (pair[0], pair[1]);
+            (low_rate / high_rate).powf(2.0 / (high_distance - low_distance) as f64)
+        })
+        .collect()
+}
+
+/// Returns the error suppression factor Λ fitted on all distances
+/// or None if there are less than 2 points with a non-zero logical error rate.
+///
+/// This is the least square fit of `ε_d = A / Λ^(d / 2)` on the logarithm
+/// of the logical error rates. See [`lambda_factors`] for the format of the points.
+/// Points with a logical error rate of 0 are ignored.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::simulation::fitted_lambda_factor;
+///
+/// let points = [(3, 0.1 / 4_f64.powf(1.5)), (5, 0.1 / 4_f64.powf(2.5)), (7, 0.0)];
+/// let factor = fitted_lambda_factor(&points).unwrap();
+/// assert!((factor - 4.0).abs() < 1e-9);
+/// ```
+///
+/// # Panic
+///
+/// Panics if two points have the same distance.
+pub fn fitted_lambda_factor(points: &[(usize, f64)]) -> Option<f64> {
+    let points = sorted_by_distance(points)
+        .into_iter()
+        .filter(|(_, rate)| *rate > 0.0)
+        .map(|(distance, rate)| (distance as f64 / 2.0, rate.ln()))
+        .collect::<Vec<_>>();
+    if points.len() < 2 {
+        return None;
+    }
+    let num_points = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / num_points;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / num_points;
+    let covariance = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+    Some((-covariance / variance).exp())
+}
+
+fn sorted_by_distance(points: &[(usize, f64)]) -> Vec<(usize, f64)> {
+    let mut points = points.to_vec();
+    points.sort_by_key(|(distance, _)| *distance);
+    if let Some(pair) = points.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        panic!("two points with distance {}", pair[0].0);
+    }
+    points
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pseudo_threshold_is_last_crossing_of_unsorted_sweep() {
+        // A statistical fluctuation crosses the line at low probability.
+        let points = [(0.3, 0.5), (0.1, 0.05), (0.02, 0.03), (0.2, 0.1)];
+        let threshold = pseudo_threshold(&points).unwrap();
+        assert!((threshold - 0.7 / 3.0).abs() < 1e-12);
+        assert_eq!(pseudo_threshold(&[(0.1, 0.2), (0.2, 0.3)]), None);
+        assert_eq!(pseudo_threshold(&[]), None);
+    }
+
+    #[test]
+    fn lambda_factors_handle_zero_rates_and_unsorted_points() {
+        let points = [(5, 0.0), (3, 0.01)];
+        assert_eq!(lambda_factors(&points), vec![f64::INFINITY]);
+        assert_eq!(fitted_lambda_factor(&points), None);
+        assert!(lambda_factors(&points[..1]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn lambda_factors_panic_on_repeated_distance() {
+        lambda_factors(&[(3, 0.1), (3, 0.2)]);
+    }
+}
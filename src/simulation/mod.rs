@@ -0,0 +1,107 @@
+//! Monte Carlo experiments built from codes, noise models and decoders.
+//!
+//! The [`MemoryExperiment`](MemoryExperiment) stores a logical state in a
+//! [`CssCode`](crate::codes::CssCode) for many rounds of noise and correction
+//! and reports how long the memory survives.
+//! With [burst events](crate::noise::BurstEventModel), it also reports the failures
+//! following the events in [`BurstEventStatistics`](BurstEventStatistics).
+//! The [`MultiBlockExperiment`](MultiBlockExperiment) does the same for several
+//! blocks sharing a noise process and coupled by logical operations.
+//! The canonical decoding workflow of a CSS code under depolarizing noise
+//! is a single call to [`run_css_memory_experiment`](run_css_memory_experiment).
+//! Any per-trial observable, such as the weight of the corrections,
+//! is aggregated in [`TrialStatistics`](TrialStatistics) by registering it
+//! in [`TrialObservables`](TrialObservables).
+//! The [`ErasureExperiment`](ErasureExperiment) estimates how often a classical
+//! code can repair the erasures sampled from a noise model.
+//! The [`AdaptiveExperiment`](AdaptiveExperiment) decodes a classical code
+//! with priors optionally estimated from the recent corrections of the decoder.
+//! The [`RepresentationEnsemble`](RepresentationEnsemble) compares the performance
+//! of belief propagation over equivalent parity check matrices of a classical code.
+//! A [`CodeSearch`](CodeSearch) samples many random codes of a [`CodeFamily`](CodeFamily)
+//! and keeps the Pareto front of a few objectives such as the girth or the performance of
+//! belief propagation.
+//! A [`SequentialTest`](SequentialTest) decides with as few trials as possible
+//! if a failure rate is below or above a target,
+//! which speeds up the search of a crossing with [`bisect_probability`](bisect_probability).
+//! The [`SyndromeStatistics`](SyndromeStatistics) of a noise model,
+//! the distribution of the syndrome weights and the firing rate of each check,
+//! are compared with the detector data of experiments.
+//! The failed trials of a classical decoder are saved in a [`FailureLog`](FailureLog)
+//! to be replayed later with another decoder.
+//! Long simulations can be split in batches saved to disk with a
+//! [`CheckpointedRun`](CheckpointedRun) to resume them after an interruption.
+//! Campaigns split in shards run on different machines are combined
+//! with [`merge_results`](merge_results).
+//! The pseudo-threshold and the error suppression factor Λ of a code family
+//! are computed from the logical error rates of a sweep with
+//! [`pseudo_threshold`](pseudo_threshold) and [`lambda_factors`](lambda_factors).
+//! With the `latency` feature, the wall-clock time of the decodings
+//! can be recorded as [`DecodeLatencies`](DecodeLatencies)
+//! to study the tail latency of a decoder.
+//! With the `test-vectors` feature, decoding [`TestVectors`](TestVectors)
+//! are exported as JSON to cross-validate other implementations.
+//! A [`GoldenSnapshot`](GoldenSnapshot) pins the corrections of a decoder
+//! on fixed errors with a single digest to detect changes across upgrades.
+//! The results can be summarized in a markdown or HTML [`Report`](Report).
+
+mod adaptive;
+pub use adaptive::{AdaptiveExperiment, AdaptivePriors};
+
+mod checkpoint;
+pub use checkpoint::CheckpointedRun;
+
+mod css_memory;
+pub use css_memory::{
+    run_css_memory_experiment, run_css_memory_experiment_with_observables, CssMemoryConfig,
+    CssMemoryResult, CssTrial,
+};
+
+mod ensemble;
+pub use ensemble::{RepresentationEnsemble, RepresentationStatistics};
+
+mod erasure;
+pub use erasure::{ErasureExperiment, ErasureRecovery};
+
+#[cfg(feature = "latency")]
+mod latency;
+#[cfg(feature = "latency")]
+pub use latency::DecodeLatencies;
+
+mod failure_log;
+pub use failure_log::{FailureLog, FailureRecord};
+
+mod golden;
+pub use golden::GoldenSnapshot;
+
+mod memory;
+pub use memory::{BurstEventStatistics, MemoryExperiment, MemoryLifetime, MemoryOutcome};
+
+mod metrics;
+pub use metrics::{fitted_lambda_factor, lambda_factors, pseudo_threshold};
+
+mod multi_block;
+pub use multi_block::{BlockFailure, BlockOperation, MultiBlockExperiment, MultiBlockLifetime};
+
+mod observables;
+pub use observables::{TrialObservables, TrialStatistics};
+
+mod search;
+pub use search::{CodeFamily, CodeSearch, SearchCandidate, SearchObjective};
+
+mod sequential;
+pub use sequential::{bisect_probability, SequentialDecision, SequentialOutcome, SequentialTest};
+
+mod shard;
+pub use shard::{merge_results, FailureCount, MergeError, ShardResult};
+
+mod syndrome_statistics;
+pub use syndrome_statistics::SyndromeStatistics;
+
+#[cfg(feature = "test-vectors")]
+mod test_vectors;
+#[cfg(feature = "test-vectors")]
+pub use test_vectors::{TestVector, TestVectors, TEST_VECTORS_SCHEMA_VERSION};
+
+mod report;
+pub use report::{AsciiPlot, Report, ReportTable};
@@ -0,0 +1,385 @@
+use super::MemoryLifetime;
+use crate::codes::CssCode;
+use crate::css::{CssOperator, CssSyndromeView};
+use crate::decoders::SyndromeDecoder;
+use crate::noise::NoiseModel;
+use pauli::PauliOperator;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinVec;
+
+/// An experiment tracking the Pauli frames of several code blocks
+/// sharing the same noise process.
+///
+/// As in a [`MemoryExperiment`](super::MemoryExperiment),
+/// each block starts from a random stabilizer.
+/// At each round, a single error is sampled on the qubits of all the blocks
+/// such that a noise model can correlate the blocks.
+/// Then, the block operations are applied in order, spreading the frames
+/// from one block to the other, and each block is decoded independently.
+/// The trial fails at the first round where a frame is not a stabilizer.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::decoders::{BpDecoder, CssDecoder};
+/// use ldpc::noise::{DepolarizingNoise, Probability};
+/// use ldpc::simulation::MultiBlockExperiment;
+/// use rand::thread_rng;
+///
+/// let code = CssCode::steane_code();
+/// let decoder = CssDecoder {
+///     x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.02), 10),
+///     z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.02), 10),
+/// };
+/// let noise = DepolarizingNoise::with_probability(Probability::new(0.02));
+///
+/// // A logical CNOT between two blocks at each round.
+/// let lifetime = MultiBlockExperiment::new(&noise)
+///     .add_block(&code, &decoder)
+///     .add_block(&code, &decoder)
+///     .transversal_cnot(0, 1)
+///     .max_rounds(10)
+///     .run_with(100, &mut thread_rng());
+///
+/// assert_eq!(lifetime.lifetime().num_trials(), 100);
+/// assert!(lifetime.num_correlated_failures() <= lifetime.lifetime().num_failures());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiBlockExperiment<'a, N, D> {
+    noise: &'a N,
+    blocks: Vec<(&'a CssCode, &'a D)>,
+    operations: Vec<BlockOperation>,
+    max_rounds: usize,
+}
+
+/// An operation acting on the frames of several blocks.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum BlockOperation {
+    /// A CNOT from each qubit of the control block
+    /// to the same qubit of the target block.
+    ///
+    /// X errors spread from the control to the target
+    /// and Z errors from the target to the control.
+    TransversalCnot { control: usize, target: usize },
+}
+
+impl<'a, N, D> MultiBlockExperiment<'a, N, D>
+where
+    N: NoiseModel<Error = PauliOperator>,
+    D: for<'s> SyndromeDecoder<CssSyndromeView<'s>, CssOperator>,
+{
+    /// Creates an experiment without blocks for the given noise model.
+    pub fn new(noise: &'a N) -> Self {
+        Self {
+            noise,
+            blocks: Vec::new(),
+            operations: Vec::new(),
+            max_rounds: 1000,
+        }
+    }
+
+    /// Adds a block of the code corrected by the decoder.
+    ///
+    /// Blocks are indexed in the order they are added.
+    pub fn add_block(&mut self, code: &'a CssCode, decoder: &'a D) -> &mut Self {
+        self.blocks.push((code, decoder));
+        self
+    }
+
+    /// Adds a transversal CNOT between two blocks applied at each round.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a block is out of bound, if the control is the target
+    /// or if the blocks don't have the same stabilizers.
+    pub fn transversal_cnot(&mut self, control: usize, target: usize) -> &mut Self {
+        if control >= self.num_blocks() || target >= self.num_blocks() {
+            panic!(
+                "blocks ({}, {}) are out of bound for {} blocks",
+                control,
+                target,
+                self.num_blocks()
+            );
+        }
+        if control == target {
+            panic!("transversal CNOT acts twice on block {}", control);
+        }
+        if self.blocks[control].0.stabilizers != self.blocks[target].0.stabilizers {
+            panic!("transversal CNOT between blocks of different codes");
+        }
+        self.operations
+            .push(BlockOperation::TransversalCnot { control, target });
+        self
+    }
+
+    /// Fixes the maximum number of rounds of each trial.
+    ///
+    /// Default is 1000.
+    pub fn max_rounds(&mut self, max_rounds: usize) -> &mut Self {
+        self.max_rounds = max_rounds;
+        self
+    }
+
+    /// Returns the number of blocks.
+    pub fn num_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the operations applied at each round.
+    pub fn operations(&self) -> &[BlockOperation] {
+        &self.operations
+    }
+
+    /// Runs a single trial and returns the first failure
+    /// or None if all blocks survived all rounds.
+    ///
+    /// Rounds are counted starting from 1.
+    pub fn run_once<R: Rng>(&self, rng: &mut R) -> Option<BlockFailure> {
+        let mut frames = self
+            .blocks
+            .iter()
+            .map(|(code, _)| code.random_stabilizer(rng))
+            .collect::<Vec<_>>();
+        let total_length = self.blocks.iter().map(|(code, _)| code.len()).sum();
+        for round in 1..=self.max_rounds {
+            let error = CssOperator::from(self.noise.sample_error_of_length(total_length, rng));
+            let mut offset = 0;
+            for frame in frames.iter_mut() {
+                *frame = &*frame * &block_part(&error, offset, frame.len());
+                offset += frame.len();
+            }
+            for operation in self.operations.iter() {
+                match *operation {
+                    BlockOperation::TransversalCnot { control, target } => {
+                        let control_frame = CssOperator::from(&frames[control]);
+                        let target_frame = CssOperator::from(&frames[target]);
+                        frames[control] = PauliOperator::from(CssOperator {
+                            x: control_frame.x.clone(),
+                            z: &control_frame.z + &target_frame.z,
+                        });
+                        frames[target] = PauliOperator::from(CssOperator {
+                            x: &target_frame.x + &control_frame.x,
+                            z: target_frame.z,
+                        });
+                    }
+                }
+            }
+            let mut failed_blocks = Vec::new();
+            for (block, (frame, (code, decoder))) in
+                frames.iter_mut().zip(self.blocks.iter()).enumerate()
+            {
+                let syndrome = code.syndrome_of(frame);
+                let correction: PauliOperator = decoder.correction_for(syndrome.as_view()).into();
+                *frame = &*frame * &correction;
+                if !code.has_stabilizer(frame) {
+                    failed_blocks.push(block);
+                }
+            }
+            if !failed_blocks.is_empty() {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(round, blocks = ?failed_blocks, "blocks failed");
+                return Some(BlockFailure {
+                    round,
+                    blocks: failed_blocks,
+                });
+            }
+        }
+        None
+    }
+
+    /// Runs the given number of trials and collects the failure statistics.
+    pub fn run_with<R: Rng>(&self, num_trials: usize, rng: &mut R) -> MultiBlockLifetime {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "multi_block_experiment",
+            num_trials,
+            num_blocks = self.num_blocks(),
+            max_rounds = self.max_rounds
+        )
+        .entered();
+        let mut lifetime = MultiBlockLifetime::new(self.num_blocks(), self.max_rounds);
+        for _ in 0..num_trials {
+            lifetime.record(self.run_once(rng));
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            num_failures = lifetime.lifetime().num_failures(),
+            "multi-block experiment finished"
+        );
+        lifetime
+    }
+}
+
+// The restriction of the operator to the qubits starting at the offset.
+fn block_part(operator: &CssOperator, offset: usize, length: usize) -> PauliOperator {
+    PauliOperator::from(operator.map(|component| {
+        let positions = component
+            .non_trivial_positions()
+            .filter(|qubit| (offset..offset + length).contains(qubit))
+            .map(|qubit| qubit - offset)
+            .collect();
+        SparseBinVec::new(length, positions)
+    }))
+}
+
+/// The round at which a multi-block trial failed and the blocks
+/// whose frame was not a stabilizer.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct BlockFailure {
+    round: usize,
+    blocks: Vec<usize>,
+}
+
+impl BlockFailure {
+    /// Returns the round of the failure, starting from 1.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    /// Returns the failed blocks in increasing order.
+    pub fn blocks(&self) -> &[usize] {
+        &self.blocks
+    }
+
+    /// Checks if more than one block failed at the same round.
+    pub fn is_correlated(&self) -> bool {
+        self.blocks.len() > 1
+    }
+}
+
+/// Failure statistics of a [`MultiBlockExperiment`](MultiBlockExperiment).
+///
+/// A trial fails when any of its blocks fails
+/// and the lifetime of the whole system is recorded as for a single memory.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct MultiBlockLifetime {
+    lifetime: MemoryLifetime,
+    block_failures: Vec<usize>,
+    num_correlated_failures: usize,
+}
+
+impl MultiBlockLifetime {
+    /// Creates empty statistics for the given number of blocks
+    /// and trials of at most the given number of rounds.
+    pub fn new(num_blocks: usize, max_rounds: usize) -> Self {
+        Self {
+            lifetime: MemoryLifetime::new(max_rounds),
+            block_failures: vec![0; num_blocks],
+            num_correlated_failures: 0,
+        }
+    }
+
+    /// Records the outcome of a trial.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the failure round is invalid or if a block is out of bound.
+    pub fn record(&mut self, failure: Option<BlockFailure>) {
+        if let Some(failure) = failure.as_ref() {
+            for block in failure.blocks() {
+                if *block >= self.block_failures.len() {
+                    panic!(
+                        "block {} is out of bound for {} blocks",
+                        block,
+                        self.block_failures.len()
+                    );
+                }
+                self.block_failures[*block] += 1;
+            }
+            if failure.is_correlated() {
+                self.num_correlated_failures += 1;
+            }
+        }
+        self.lifetime.record(failure.map(|failure| failure.round()));
+    }
+
+    /// Returns the lifetime of the system of all blocks.
+    pub fn lifetime(&self) -> &MemoryLifetime {
+        &self.lifetime
+    }
+
+    /// Returns the number of trials in which the given block failed.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the block is out of bound.
+    pub fn block_failures(&self, block: usize) -> usize {
+        self.block_failures[block]
+    }
+
+    /// Returns the number of trials where several blocks failed at the same round.
+    pub fn num_correlated_failures(&self) -> usize {
+        self.num_correlated_failures
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use crate::decoders::{BpDecoder, CssDecoder};
+    use crate::noise::{DepolarizingNoise, Probability};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn surface_code() -> CssCode {
+        let repetition_code = LinearCode::repetition_code(3);
+        CssCode::hypergraph_product(&repetition_code, &repetition_code)
+    }
+
+    fn decoder(code: &CssCode) -> CssDecoder<BpDecoder> {
+        CssDecoder {
+            x: BpDecoder::new(&code.stabilizers.x, Probability::new(0.05), 10),
+            z: BpDecoder::new(&code.stabilizers.z, Probability::new(0.05), 10),
+        }
+    }
+
+    #[test]
+    fn blocks_never_fail_without_noise() {
+        let code = surface_code();
+        let decoder = decoder(&code);
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+        let lifetime = MultiBlockExperiment::new(&noise)
+            .add_block(&code, &decoder)
+            .add_block(&code, &decoder)
+            .transversal_cnot(0, 1)
+            .max_rounds(5)
+            .run_with(10, &mut StdRng::seed_from_u64(123));
+        assert_eq!(lifetime.lifetime().num_survivors(), 10);
+        assert_eq!(lifetime.block_failures(0), 0);
+        assert_eq!(lifetime.num_correlated_failures(), 0);
+    }
+
+    #[test]
+    fn cnot_spreads_errors_to_the_target() {
+        let code = surface_code();
+        let decoder = decoder(&code);
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.05));
+        let mut rng = StdRng::seed_from_u64(123);
+        let mut experiment = MultiBlockExperiment::new(&noise);
+        experiment
+            .add_block(&code, &decoder)
+            .add_block(&code, &decoder)
+            .max_rounds(1);
+        let independent = experiment.run_with(2000, &mut rng);
+        experiment.transversal_cnot(0, 1);
+        let coupled = experiment.run_with(2000, &mut rng);
+        assert!(coupled.block_failures(1) > independent.block_failures(1));
+        assert!(coupled.num_correlated_failures() > 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn transversal_cnot_requires_the_same_code() {
+        let first = surface_code();
+        let second = CssCode::steane_code();
+        let (first_decoder, second_decoder) = (decoder(&first), decoder(&second));
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.0));
+        MultiBlockExperiment::new(&noise)
+            .add_block(&first, &first_decoder)
+            .add_block(&second, &second_decoder)
+            .transversal_cnot(0, 1);
+    }
+}
@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// User-defined statistics computed from each trial of a simulation.
+///
+/// An observable is a named function of the data of a trial,
+/// for example the weight of the correction or of the residual error.
+/// The values of all the trials are aggregated in [`TrialStatistics`]
+/// without modifying the simulation.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::simulation::{TrialObservables, TrialStatistics};
+///
+/// let observables = TrialObservables::new()
+///     .with("length", |word: &&str| word.len() as f64)
+///     .with("vowels", |word: &&str| word.matches(['a', 'e', 'i', 'o', 'u']).count() as f64);
+///
+/// let mut statistics = TrialStatistics::default();
+/// for word in ["parity", "decoder", "noise"] {
+///     observables.observe(&word, &mut statistics);
+/// }
+///
+/// assert_eq!(observables.names().collect::<Vec<_>>(), vec!["length", "vowels"]);
+/// assert_eq!(statistics.mean("length"), Some(6.0));
+/// assert_eq!(statistics.max("vowels"), Some(3.0));
+/// ```
+pub struct TrialObservables<'a, T> {
+    observables: Vec<(String, Observable<'a, T>)>,
+}
+
+// A function computing the value of an observable from a trial.
+type Observable<'a, T> = Box<dyn Fn(&T) -> f64 + 'a>;
+
+impl<'a, T> TrialObservables<'a, T> {
+    /// Creates an empty set of observables.
+    pub fn new() -> Self {
+        Self {
+            observables: Vec::new(),
+        }
+    }
+
+    /// Adds an observable with the given name.
+    ///
+    /// # Panic
+    ///
+    /// Panics if there is already an observable with the same name.
+    pub fn with<S, F>(mut self, name: S, observable: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&T) -> f64 + 'a,
+    {
+        let name = name.into();
+        if self.names().any(|other| other == name) {
+            panic!("observable {} is already registered", name);
+        }
+        self.observables.push((name, Box::new(observable)));
+        self
+    }
+
+    /// Returns the names of the observables in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.observables.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the number of observables.
+    pub fn len(&self) -> usize {
+        self.observables.len()
+    }
+
+    /// Checks if there is no observable.
+    pub fn is_empty(&self) -> bool {
+        self.observables.is_empty()
+    }
+
+    /// Records the value of each observable for the given trial.
+    pub fn observe(&self, trial: &T, statistics: &mut TrialStatistics) {
+        for (name, observable) in self.observables.iter() {
+            statistics.record(name, observable(trial));
+        }
+    }
+}
+
+impl<'a, T> Default for TrialObservables<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The values of the [observables](TrialObservables) of each trial.
+///
+/// The values of an observable are kept in the order of the trials
+/// and summarized by their mean, percentiles and histogram.
+/// All the summaries return None for an observable without values.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::simulation::TrialStatistics;
+///
+/// let mut statistics = TrialStatistics::default();
+/// for weight in [0.0, 1.0, 1.0, 2.0, 6.0] {
+///     statistics.record("weight", weight);
+/// }
+///
+/// assert_eq!(statistics.mean("weight"), Some(2.0));
+/// assert_eq!(statistics.median("weight"), Some(1.0));
+/// assert_eq!(statistics.percentile("weight", 0.8), Some(2.0));
+/// assert_eq!(
+///     statistics.histogram("weight", 2.0),
+///     Some(vec![(0.0, 3), (2.0, 1), (6.0, 1)])
+/// );
+/// assert_eq!(statistics.mean("iterations"), None);
+/// ```
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TrialStatistics {
+    values: BTreeMap<String, Vec<f64>>,
+}
+
+impl TrialStatistics {
+    /// Records a value of the given observable.
+    pub fn record(&mut self, name: &str, value: f64) {
+        match self.values.get_mut(name) {
+            Some(values) => values.push(value),
+            None => {
+                self.values.insert(name.to_string(), vec![value]);
+            }
+        }
+    }
+
+    /// Returns the names of the observables in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(|name| name.as_str())
+    }
+
+    /// Returns the values of the given observable in the order they were recorded.
+    pub fn values(&self, name: &str) -> &[f64] {
+        self.values
+            .get(name)
+            .map_or(&[], |values| values.as_slice())
+    }
+
+    /// Returns the average value of the given observable.
+    pub fn mean(&self, name: &str) -> Option<f64> {
+        let values = self.values(name);
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.iter().sum::<f64>() / values.len() as f64)
+        }
+    }
+
+    /// Returns the smallest value of the given observable such that
+    /// at least the given fraction of the values are smaller or equal.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the fraction is not between 0 and 1.
+    pub fn percentile(&self, name: &str, fraction: f64) -> Option<f64> {
+        if !(0.0..=1.0).contains(&fraction) {
+            panic!("fraction {} is not between 0 and 1", fraction);
+        }
+        let values = sorted(self.values(name));
+        let rank = (fraction * values.len() as f64).ceil() as usize;
+        values.get(rank.saturating_sub(1)).copied()
+    }
+
+    /// Returns the median value of the given observable.
+    pub fn median(&self, name: &str) -> Option<f64> {
+        self.percentile(name, 0.5)
+    }
+
+    /// Returns the smallest value of the given observable.
+    pub fn min(&self, name: &str) -> Option<f64> {
+        sorted(self.values(name)).first().copied()
+    }
+
+    /// Returns the largest value of the given observable.
+    pub fn max(&self, name: &str) -> Option<f64> {
+        sorted(self.values(name)).last().copied()
+    }
+
+    /// Returns the number of values of the given observable in each bin of the given width.
+    ///
+    /// A bin is given by its lower edge, a multiple of the width,
+    /// and contains the values from its lower edge up to, but excluding, the next edge.
+    /// Only the non-empty bins are returned in increasing order.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the width is not positive and finite.
+    pub fn histogram(&self, name: &str, bin_width: f64) -> Option<Vec<(f64, usize)>> {
+        if !bin_width.is_finite() || bin_width <= 0.0 {
+            panic!("{} is not a valid bin width", bin_width);
+        }
+        let values = self.values(name);
+        if values.is_empty() {
+            return None;
+        }
+        let mut counts = BTreeMap::new();
+        for value in values {
+            *counts
+                .entry((value / bin_width).floor() as i64)
+                .or_insert(0) += 1;
+        }
+        Some(
+            counts
+                .into_iter()
+                .map(|(bin, count)| (bin as f64 * bin_width, count))
+                .collect(),
+        )
+    }
+
+    /// Returns the values of both simulations.
+    ///
+    /// The values of the first simulation come first.
+    pub fn merged_with(&self, other: &Self) -> Self {
+        let mut merged = self.clone();
+        for (name, values) in other.values.iter() {
+            merged
+                .values
+                .entry(name.clone())
+                .or_default()
+                .extend(values);
+        }
+        merged
+    }
+}
+
+// The values in increasing order.
+fn sorted(values: &[f64]) -> Vec<f64> {
+    let mut values = values.to_vec();
+    values.sort_unstable_by(f64::total_cmp);
+    values
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn observables_have_unique_names() {
+        TrialObservables::new()
+            .with("weight", |trial: &usize| *trial as f64)
+            .with("weight", |trial: &usize| *trial as f64);
+    }
+
+    #[test]
+    fn negative_values_are_binned_below_zero() {
+        let mut statistics = TrialStatistics::default();
+        statistics.record("shift", -0.5);
+        statistics.record("shift", 0.5);
+        assert_eq!(
+            statistics.histogram("shift", 1.0),
+            Some(vec![(-1.0, 1), (0.0, 1)])
+        );
+        let merged = statistics.merged_with(&statistics);
+        assert_eq!(merged.values("shift"), &[-0.5, 0.5, -0.5, 0.5]);
+        assert_eq!(merged.min("shift"), Some(-0.5));
+    }
+}
@@ -0,0 +1,601 @@
+use super::{ErasureRecovery, MemoryLifetime, TrialStatistics};
+use crate::codes::{CssCode, LinearCode};
+use std::fmt::{self, Write};
+
+#[cfg(feature = "latency")]
+use super::DecodeLatencies;
+#[cfg(feature = "latency")]
+use std::time::Duration;
+
+/// A human-readable summary of simulation results
+/// rendered as markdown or HTML.
+///
+/// A report is a list of parameters, such as the code, the noise and the decoder
+/// configuration, followed by tables and ASCII plots.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::noise::{DepolarizingNoise, Probability};
+/// use ldpc::simulation::{MemoryLifetime, Report};
+///
+/// let mut lifetime = MemoryLifetime::new(10);
+/// lifetime.record(Some(4));
+/// lifetime.record(None);
+///
+/// let noise = DepolarizingNoise::with_probability(Probability::new(0.05));
+/// let mut report = Report::new("Steane memory");
+/// report
+///     .css_code("Steane", &CssCode::steane_code())
+///     .parameter("Noise", &noise)
+///     .parameter("Decoder", "BP with 10 iterations")
+///     .memory_sweep("Memory", &[(0.05, lifetime)]);
+///
+/// let markdown = report.to_markdown();
+/// assert!(markdown.starts_with("# Steane memory"));
+/// assert!(markdown.contains("| Noise | Depolarizing Noise (prob = 0.05) |"));
+/// assert!(report.to_html().contains("<h1>Steane memory</h1>"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    title: String,
+    parameters: Vec<(String, String)>,
+    sections: Vec<Section>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Section {
+    Table(ReportTable),
+    Plot(AsciiPlot),
+}
+
+impl Report {
+    /// Creates an empty report with the given title.
+    pub fn new<S: Into<String>>(title: S) -> Self {
+        Self {
+            title: title.into(),
+            parameters: Vec::new(),
+            sections: Vec::new(),
+        }
+    }
+
+    /// Adds a parameter of the experiment, such as the noise or the decoder configuration.
+    pub fn parameter<S, V>(&mut self, name: S, value: V) -> &mut Self
+    where
+        S: Into<String>,
+        V: fmt::Display,
+    {
+        self.parameters.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Adds the length, dimension, number of checks and fingerprint of a linear code.
+    pub fn linear_code<S: Into<String>>(&mut self, name: S, code: &LinearCode) -> &mut Self {
+        self.parameter(
+            "Code",
+            format!(
+                "{} [n = {}, k = {}, {} checks, fingerprint {}]",
+                name.into(),
+                code.len(),
+                code.dimension(),
+                code.num_checks(),
+                code.fingerprint()
+            ),
+        )
+    }
+
+    /// Adds the number of qubits, logical qubits, stabilizers and fingerprint of a CSS code.
+    pub fn css_code<S: Into<String>>(&mut self, name: S, code: &CssCode) -> &mut Self {
+        self.parameter(
+            "Code",
+            format!(
+                "{} [[n = {}, k = {}]], {} X and {} Z stabilizers, fingerprint {}",
+                name.into(),
+                code.len(),
+                code.num_x_logicals(),
+                code.num_x_stabs(),
+                code.num_z_stabs(),
+                code.fingerprint()
+            ),
+        )
+    }
+
+    /// Adds a table.
+    pub fn table(&mut self, table: ReportTable) -> &mut Self {
+        self.sections.push(Section::Table(table));
+        self
+    }
+
+    /// Adds a plot.
+    pub fn plot(&mut self, plot: AsciiPlot) -> &mut Self {
+        self.sections.push(Section::Plot(plot));
+        self
+    }
+
+    /// Adds a table and a plot of the logical error rate per round
+    /// of memory experiments for each physical error probability.
+    pub fn memory_sweep<S: Into<String>>(
+        &mut self,
+        title: S,
+        points: &[(f64, MemoryLifetime)],
+    ) -> &mut Self {
+        let title = title.into();
+        let mut table = ReportTable::new(
+            title.clone(),
+            vec![
+                "Probability",
+                "Trials",
+                "Failures",
+                "Non-converged rounds",
+                "Mean rounds to failure",
+                "Logical error rate per round",
+            ],
+        );
+        let mut plot = AsciiPlot::new(title, "probability", "logical error rate per round");
+        plot.log_scale(true);
+        for (probability, lifetime) in points {
+            table.row(vec![
+                probability.to_string(),
+                lifetime.num_trials().to_string(),
+                lifetime.num_failures().to_string(),
+                lifetime.num_non_converged_rounds().to_string(),
+                lifetime
+                    .mean_rounds_to_failure()
+                    .map(|rounds| format!("{:.2}", rounds))
+                    .unwrap_or_else(|| "-".to_string()),
+                lifetime
+                    .logical_error_rate_per_round()
+                    .map(|rate| format!("{:.3e}", rate))
+                    .unwrap_or_else(|| "-".to_string()),
+            ]);
+            if let Some(rate) = lifetime.logical_error_rate_per_round() {
+                plot.point(*probability, rate);
+            }
+        }
+        self.table(table).plot(plot)
+    }
+
+    /// Adds a table and a plot of the failure rate
+    /// of erasure experiments for each erasure probability.
+    pub fn erasure_sweep<S: Into<String>>(
+        &mut self,
+        title: S,
+        points: &[(f64, ErasureRecovery)],
+    ) -> &mut Self {
+        let title = title.into();
+        let mut table = ReportTable::new(
+            title.clone(),
+            vec![
+                "Probability",
+                "Trials",
+                "Failures",
+                "Mean erasure weight",
+                "Failure rate",
+            ],
+        );
+        let mut plot = AsciiPlot::new(title, "probability", "failure rate");
+        plot.log_scale(true);
+        for (probability, recovery) in points {
+            table.row(vec![
+                probability.to_string(),
+                recovery.num_trials().to_string(),
+                recovery.num_failures().to_string(),
+                format!("{:.2}", recovery.mean_erasure_weight()),
+                format!("{:.3e}", recovery.failure_rate()),
+            ]);
+            plot.point(*probability, recovery.failure_rate());
+        }
+        self.table(table).plot(plot)
+    }
+
+    /// Adds a table of the decoding latency percentiles
+    /// for each physical error probability.
+    ///
+    /// This requires the `latency` feature.
+    #[cfg(feature = "latency")]
+    pub fn latency_sweep<S: Into<String>>(
+        &mut self,
+        title: S,
+        points: &[(f64, DecodeLatencies)],
+    ) -> &mut Self {
+        let mut table = ReportTable::new(
+            title,
+            vec![
+                "Probability",
+                "Timed decodings",
+                "Mean",
+                "p50",
+                "p90",
+                "p99",
+                "p99.9",
+                "Max",
+            ],
+        );
+        let format = |latency: Option<Duration>| {
+            latency
+                .map(|latency| format!("{:.1?}", latency))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        for (probability, latencies) in points {
+            table.row(vec![
+                probability.to_string(),
+                latencies.samples().len().to_string(),
+                format(latencies.mean()),
+                format(latencies.percentile(0.5)),
+                format(latencies.percentile(0.9)),
+                format(latencies.percentile(0.99)),
+                format(latencies.percentile(0.999)),
+                format(latencies.max()),
+            ]);
+        }
+        self.table(table)
+    }
+
+    /// Adds a table of the mean and percentiles of each observable of the trials.
+    pub fn trial_statistics<S: Into<String>>(
+        &mut self,
+        title: S,
+        statistics: &TrialStatistics,
+    ) -> &mut Self {
+        let mut table = ReportTable::new(
+            title,
+            vec!["Observable", "Trials", "Mean", "p50", "p90", "p99", "Max"],
+        );
+        let format = |value: Option<f64>| {
+            value
+                .map(|value| format!("{:.3}", value))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        for name in statistics.names() {
+            table.row(vec![
+                name.to_string(),
+                statistics.values(name).len().to_string(),
+                format(statistics.mean(name)),
+                format(statistics.percentile(name, 0.5)),
+                format(statistics.percentile(name, 0.9)),
+                format(statistics.percentile(name, 0.99)),
+                format(statistics.max(name)),
+            ]);
+        }
+        self.table(table)
+    }
+
+    /// Renders the report as markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut output = format!("# {}\n", self.title);
+        if !self.parameters.is_empty() {
+            output.push_str("\n| Parameter | Value |\n|---|---|\n");
+            for (name, value) in self.parameters.iter() {
+                writeln!(output, "| {} | {} |", name, value).unwrap();
+            }
+        }
+        for section in self.sections.iter() {
+            match section {
+                Section::Table(table) => {
+                    writeln!(output, "\n## {}\n", table.title).unwrap();
+                    writeln!(output, "| {} |", table.headers.join(" | ")).unwrap();
+                    writeln!(output, "|{}", "---|".repeat(table.headers.len())).unwrap();
+                    for row in table.rows.iter() {
+                        writeln!(output, "| {} |", row.join(" | ")).unwrap();
+                    }
+                }
+                Section::Plot(plot) => {
+                    writeln!(output, "\n## {}\n\n```\n{}```", plot.title, plot.render()).unwrap();
+                }
+            }
+        }
+        output
+    }
+
+    /// Renders the report as a standalone HTML page.
+    pub fn to_html(&self) -> String {
+        let mut output = format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title></head>\n<body>\n<h1>{}</h1>\n",
+            escape(&self.title),
+            escape(&self.title)
+        );
+        if !self.parameters.is_empty() {
+            output.push_str("<table>\n<tr><th>Parameter</th><th>Value</th></tr>\n");
+            for (name, value) in self.parameters.iter() {
+                writeln!(
+                    output,
+                    "<tr><td>{}</td><td>{}</td></tr>",
+                    escape(name),
+                    escape(value)
+                )
+                .unwrap();
+            }
+            output.push_str("</table>\n");
+        }
+        for section in self.sections.iter() {
+            match section {
+                Section::Table(table) => {
+                    writeln!(output, "<h2>{}</h2>\n<table>", escape(&table.title)).unwrap();
+                    output.push_str("<tr>");
+                    for header in table.headers.iter() {
+                        write!(output, "<th>{}</th>", escape(header)).unwrap();
+                    }
+                    output.push_str("</tr>\n");
+                    for row in table.rows.iter() {
+                        output.push_str("<tr>");
+                        for cell in row.iter() {
+                            write!(output, "<td>{}</td>", escape(cell)).unwrap();
+                        }
+                        output.push_str("</tr>\n");
+                    }
+                    output.push_str("</table>\n");
+                }
+                Section::Plot(plot) => {
+                    writeln!(
+                        output,
+                        "<h2>{}</h2>\n<pre>{}</pre>",
+                        escape(&plot.title),
+                        escape(&plot.render())
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        output.push_str("</body>\n</html>\n");
+        output
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A table of a [`Report`](Report).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportTable {
+    title: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl ReportTable {
+    /// Creates an empty table with the given column headers.
+    pub fn new<S, H>(title: S, headers: Vec<H>) -> Self
+    where
+        S: Into<String>,
+        H: Into<String>,
+    {
+        Self {
+            title: title.into(),
+            headers: headers.into_iter().map(|header| header.into()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Adds a row to the table.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the number of cells is different from the number of columns.
+    pub fn row<C: Into<String>>(&mut self, cells: Vec<C>) -> &mut Self {
+        if cells.len() != self.headers.len() {
+            panic!(
+                "row with {} cells for {} columns",
+                cells.len(),
+                self.headers.len()
+            );
+        }
+        self.rows
+            .push(cells.into_iter().map(|cell| cell.into()).collect());
+        self
+    }
+
+    /// Returns the number of rows.
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+/// A scatter plot drawn with characters.
+///
+/// Points are marked with `*` on a grid of 60 columns and 15 rows.
+/// With a logarithmic scale, the points with a non positive value are not drawn.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::simulation::AsciiPlot;
+///
+/// let mut plot = AsciiPlot::new("Threshold", "p", "failure rate");
+/// plot.point(0.01, 0.001).point(0.02, 0.01).point(0.05, 0.1);
+/// plot.log_scale(true);
+///
+/// let drawing = plot.render();
+/// assert_eq!(drawing.matches('*').count(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsciiPlot {
+    title: String,
+    x_label: String,
+    y_label: String,
+    points: Vec<(f64, f64)>,
+    log_scale: bool,
+}
+
+impl AsciiPlot {
+    const WIDTH: usize = 60;
+    const HEIGHT: usize = 15;
+
+    /// Creates an empty plot with the given title and axis labels.
+    pub fn new<T, X, Y>(title: T, x_label: X, y_label: Y) -> Self
+    where
+        T: Into<String>,
+        X: Into<String>,
+        Y: Into<String>,
+    {
+        Self {
+            title: title.into(),
+            x_label: x_label.into(),
+            y_label: y_label.into(),
+            points: Vec::new(),
+            log_scale: false,
+        }
+    }
+
+    /// Adds a point to the plot.
+    pub fn point(&mut self, x: f64, y: f64) -> &mut Self {
+        self.points.push((x, y));
+        self
+    }
+
+    /// Uses a logarithmic scale for the vertical axis.
+    pub fn log_scale(&mut self, log_scale: bool) -> &mut Self {
+        self.log_scale = log_scale;
+        self
+    }
+
+    /// Draws the plot.
+    pub fn render(&self) -> String {
+        let points = self
+            .points
+            .iter()
+            .filter(|(x, y)| x.is_finite() && y.is_finite() && (!self.log_scale || *y > 0.0))
+            .map(|(x, y)| (*x, if self.log_scale { y.log10() } else { *y }))
+            .collect::<Vec<_>>();
+        if points.is_empty() {
+            return format!("{} vs {}: no data\n", self.y_label, self.x_label);
+        }
+        let (x_min, x_max) = range(points.iter().map(|(x, _)| *x));
+        let (y_min, y_max) = range(points.iter().map(|(_, y)| *y));
+        let mut grid = vec![vec![' '; Self::WIDTH]; Self::HEIGHT];
+        for (x, y) in points.iter() {
+            let column = scale(*x, x_min, x_max, Self::WIDTH);
+            let row = Self::HEIGHT - 1 - scale(*y, y_min, y_max, Self::HEIGHT);
+            grid[row][column] = '*';
+        }
+        let y_value = |value: f64| {
+            if self.log_scale {
+                10_f64.powf(value)
+            } else {
+                value
+            }
+        };
+        let top = format!("{:.2e}", y_value(y_max));
+        let bottom = format!("{:.2e}", y_value(y_min));
+        let margin = top.len().max(bottom.len());
+        let mut output = format!("{}{}\n", " ".repeat(margin + 2), self.y_label);
+        for (index, row) in grid.iter().enumerate() {
+            let label = if index == 0 {
+                top.as_str()
+            } else if index == Self::HEIGHT - 1 {
+                bottom.as_str()
+            } else {
+                ""
+            };
+            writeln!(
+                output,
+                "{:>margin$} |{}",
+                label,
+                row.iter().collect::<String>(),
+                margin = margin
+            )
+            .unwrap();
+        }
+        writeln!(
+            output,
+            "{} +{}",
+            " ".repeat(margin),
+            "-".repeat(Self::WIDTH)
+        )
+        .unwrap();
+        let x_min_label = format!("{:.2e}", x_min);
+        let x_max_label = format!("{:.2e}", x_max);
+        writeln!(
+            output,
+            "{}  {}{:>width$}",
+            " ".repeat(margin),
+            x_min_label,
+            x_max_label,
+            width = Self::WIDTH - x_min_label.len()
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "{}  {:^width$}",
+            " ".repeat(margin),
+            self.x_label,
+            width = Self::WIDTH
+        )
+        .unwrap();
+        output
+    }
+}
+
+fn range<I: Iterator<Item = f64>>(values: I) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+        (min.min(value), max.max(value))
+    })
+}
+
+// Maps the value to a cell between 0 and the number of cells
+// with a single point centered.
+fn scale(value: f64, min: f64, max: f64, num_cells: usize) -> usize {
+    if max > min {
+        (((value - min) / (max - min)) * (num_cells - 1) as f64).round() as usize
+    } else {
+        num_cells / 2
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn markdown_table_has_one_line_per_row() {
+        let mut table = ReportTable::new("Results", vec!["p", "rate"]);
+        table.row(vec!["0.1", "0.5"]).row(vec!["0.2", "0.7"]);
+        let mut report = Report::new("Test");
+        report.table(table);
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| p | rate |\n|---|---|\n| 0.1 | 0.5 |\n| 0.2 | 0.7 |\n"));
+    }
+
+    #[test]
+    fn trial_statistics_have_one_row_per_observable() {
+        let mut statistics = TrialStatistics::default();
+        statistics.record("weight", 2.0);
+        statistics.record("weight", 4.0);
+        statistics.record("iterations", 10.0);
+        let mut report = Report::new("Observables");
+        report.trial_statistics("Trials", &statistics);
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| iterations | 1 | 10.000 |"));
+        assert!(markdown.contains("| weight | 2 | 3.000 | 2.000 | 4.000 | 4.000 | 4.000 |"));
+    }
+
+    #[test]
+    fn html_is_escaped() {
+        let mut report = Report::new("a < b");
+        report.parameter("Decoder", "\"fast\" & small");
+        let html = report.to_html();
+        assert!(html.contains("<h1>a &lt; b</h1>"));
+        assert!(html.contains("<td>&quot;fast&quot; &amp; small</td>"));
+    }
+
+    #[test]
+    fn plot_places_extreme_points_in_corners() {
+        let mut plot = AsciiPlot::new("Plot", "x", "y");
+        plot.point(0.0, 0.0).point(1.0, 1.0);
+        let drawing = plot.render();
+        let lines = drawing.lines().collect::<Vec<_>>();
+        assert!(lines[1].ends_with('*'));
+        assert!(lines[AsciiPlot::HEIGHT].contains("|*"));
+    }
+
+    #[test]
+    fn log_scale_plot_skips_zero_values() {
+        let mut plot = AsciiPlot::new("Plot", "x", "y");
+        plot.point(0.1, 0.0).point(0.2, 0.01).log_scale(true);
+        assert_eq!(plot.render().matches('*').count(), 1);
+    }
+}
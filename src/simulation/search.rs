@@ -0,0 +1,359 @@
+use crate::codes::{LinearCode, QuasiCyclicMatrix, SamplingError};
+use crate::decoders::{BpDecoder, IsdAlgorithm, SyndromeDecoder};
+use crate::noise::{BinarySymmetricChannel, Probability};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::SparseBinMat;
+
+/// A family of random codes sampled by a [`CodeSearch`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CodeFamily {
+    /// The [random regular codes](LinearCode::random_regular_code).
+    Regular {
+        num_bits: usize,
+        num_checks: usize,
+        bit_degree: usize,
+        check_degree: usize,
+    },
+    /// The codes from [progressive edge growth](LinearCode::progressive_edge_growth).
+    ProgressiveEdgeGrowth {
+        num_bits: usize,
+        num_checks: usize,
+        bit_degree: usize,
+    },
+    /// The lifts of a protograph where each one of the base matrix
+    /// is replaced by a circulant permutation with a random shift
+    /// and each zero by a zero block.
+    Protograph {
+        base_matrix: SparseBinMat,
+        lift_factor: usize,
+    },
+}
+
+impl CodeFamily {
+    /// Samples a code of the family from the seed
+    /// or returns an error if the degrees of a regular code are incompatible.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the bit degree of progressive edge growth is larger than the number of checks
+    /// or if the lift factor of a protograph is 0.
+    pub fn sample(&self, seed: u64) -> Result<LinearCode, SamplingError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        match self {
+            Self::Regular {
+                num_bits,
+                num_checks,
+                bit_degree,
+                check_degree,
+            } => LinearCode::random_regular_code()
+                .num_bits(*num_bits)
+                .num_checks(*num_checks)
+                .bit_degree(*bit_degree)
+                .check_degree(*check_degree)
+                .seed(seed)
+                .sample(),
+            Self::ProgressiveEdgeGrowth {
+                num_bits,
+                num_checks,
+                bit_degree,
+            } => Ok(LinearCode::progressive_edge_growth(
+                *num_bits,
+                *num_checks,
+                *bit_degree,
+                &mut rng,
+            )),
+            Self::Protograph {
+                base_matrix,
+                lift_factor,
+            } => {
+                let shifts = base_matrix
+                    .rows()
+                    .map(|row| {
+                        (0..base_matrix.number_of_columns())
+                            .map(|column| {
+                                row.is_one_at(column)
+                                    .unwrap()
+                                    .then(|| rng.gen_range(0..*lift_factor))
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let matrix = QuasiCyclicMatrix::new(*lift_factor, shifts);
+                Ok(LinearCode::from_parity_check_matrix(matrix.expanded()))
+            }
+        }
+    }
+}
+
+/// A score of the codes of a [`CodeSearch`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SearchObjective {
+    /// The [girth](LinearCode::girth) of the Tanner graph to maximize.
+    ///
+    /// A graph without cycles has an infinite girth.
+    Girth,
+    /// The weight of the lightest codeword found by
+    /// [information set decoding](LinearCode::minimal_distance_isd) to maximize.
+    ///
+    /// It is an upper bound on the distance which is 0 if no codeword is found.
+    EstimatedDistance {
+        algorithm: IsdAlgorithm,
+        num_iterations: usize,
+    },
+    /// The failure rate of belief propagation on a binary symmetric channel to minimize.
+    ///
+    /// A trial fails if the correction is different from the error.
+    BpFailureRate {
+        probability: Probability,
+        num_trials: usize,
+        max_iterations: usize,
+    },
+}
+
+impl SearchObjective {
+    /// Checks if larger scores are better.
+    pub fn is_maximized(&self) -> bool {
+        !matches!(self, Self::BpFailureRate { .. })
+    }
+
+    /// Returns the score of the code.
+    ///
+    /// The random number generator is used by
+    /// the estimated distance and the failure rate.
+    pub fn score_with<R: Rng>(&self, code: &LinearCode, rng: &mut R) -> f64 {
+        match *self {
+            Self::Girth => code.girth().map_or(f64::INFINITY, |girth| girth as f64),
+            Self::EstimatedDistance {
+                algorithm,
+                num_iterations,
+            } => code
+                .minimal_distance_isd(algorithm, num_iterations, rng)
+                .unwrap_or(0) as f64,
+            Self::BpFailureRate {
+                probability,
+                num_trials,
+                max_iterations,
+            } => {
+                if num_trials == 0 {
+                    return 0.0;
+                }
+                let decoder =
+                    BpDecoder::new(code.parity_check_matrix(), probability, max_iterations);
+                let noise = BinarySymmetricChannel::with_probability(probability);
+                let num_failures = (0..num_trials)
+                    .filter(|_| {
+                        let error = code.random_error(&noise, rng);
+                        let correction = decoder.correction_for(code.syndrome_of(&error).as_view());
+                        correction != error
+                    })
+                    .count();
+                num_failures as f64 / num_trials as f64
+            }
+        }
+    }
+
+    // Checks if the first score is at least as good as the second.
+    fn is_at_least(&self, first: f64, second: f64) -> bool {
+        if self.is_maximized() {
+            first >= second
+        } else {
+            first <= second
+        }
+    }
+}
+
+/// A search sampling many random codes from a family
+/// and keeping the best ones according to a few objectives.
+///
+/// A code dominates another if it is at least as good for every objective
+/// and better for at least one of them.
+/// The search returns the Pareto front, that is the codes not dominated by any other.
+///
+/// Each candidate is sampled and scored with a random number generator
+/// seeded with its own seed, such that a candidate is reproduced from its seed alone.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::decoders::IsdAlgorithm;
+/// use ldpc::noise::Probability;
+/// use ldpc::simulation::{CodeFamily, CodeSearch, SearchObjective};
+/// use rand::thread_rng;
+///
+/// let family = CodeFamily::ProgressiveEdgeGrowth {
+///     num_bits: 24,
+///     num_checks: 12,
+///     bit_degree: 3,
+/// };
+/// let front = CodeSearch::new(family.clone())
+///     .with_objective(SearchObjective::Girth)
+///     .with_objective(SearchObjective::BpFailureRate {
+///         probability: Probability::new(0.05),
+///         num_trials: 50,
+///         max_iterations: 10,
+///     })
+///     .with_num_candidates(10)
+///     .run_with(&mut thread_rng())
+///     .unwrap();
+///
+/// assert!(!front.is_empty());
+/// for candidate in front.iter() {
+///     let code = family.sample(candidate.seed()).unwrap();
+///     assert_eq!(&code, candidate.code());
+///     assert_eq!(candidate.scores().len(), 2);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeSearch {
+    family: CodeFamily,
+    objectives: Vec<SearchObjective>,
+    num_candidates: usize,
+}
+
+impl CodeSearch {
+    /// Creates a search for 100 codes of the family without objectives.
+    pub fn new(family: CodeFamily) -> Self {
+        Self {
+            family,
+            objectives: Vec::new(),
+            num_candidates: 100,
+        }
+    }
+
+    /// Adds an objective after the previous ones.
+    pub fn with_objective(&mut self, objective: SearchObjective) -> &mut Self {
+        self.objectives.push(objective);
+        self
+    }
+
+    /// Sets the number of sampled codes.
+    pub fn with_num_candidates(&mut self, num_candidates: usize) -> &mut Self {
+        self.num_candidates = num_candidates;
+        self
+    }
+
+    /// Samples and scores the candidates with seeds from the random number generator
+    /// and returns the Pareto front in sampling order
+    /// or an error if the codes can't be sampled.
+    ///
+    /// Without objectives, every candidate is in the front.
+    pub fn run_with<R: Rng>(&self, rng: &mut R) -> Result<Vec<SearchCandidate>, SamplingError> {
+        let candidates = (0..self.num_candidates)
+            .map(|_| self.candidate(rng.gen()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let front = candidates
+            .iter()
+            .filter(|candidate| {
+                !candidates
+                    .iter()
+                    .any(|other| self.dominates(other, candidate))
+            })
+            .cloned()
+            .collect();
+        Ok(front)
+    }
+
+    fn candidate(&self, seed: u64) -> Result<SearchCandidate, SamplingError> {
+        let code = self.family.sample(seed)?;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let scores = self
+            .objectives
+            .iter()
+            .map(|objective| objective.score_with(&code, &mut rng))
+            .collect();
+        Ok(SearchCandidate { seed, code, scores })
+    }
+
+    fn dominates(&self, first: &SearchCandidate, second: &SearchCandidate) -> bool {
+        let pairs = || {
+            self.objectives
+                .iter()
+                .zip(first.scores.iter().zip(&second.scores))
+        };
+        pairs().all(|(objective, (first, second))| objective.is_at_least(*first, *second))
+            && pairs().any(|(objective, (first, second))| !objective.is_at_least(*second, *first))
+    }
+}
+
+/// A code of a [`CodeSearch`] with its seed and its scores.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SearchCandidate {
+    seed: u64,
+    code: LinearCode,
+    scores: Vec<f64>,
+}
+
+impl SearchCandidate {
+    /// Returns the seed sampling the code with [`CodeFamily::sample`].
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the code.
+    pub fn code(&self) -> &LinearCode {
+        &self.code
+    }
+
+    /// Returns the score of each objective in the order they were added.
+    pub fn scores(&self) -> &[f64] {
+        &self.scores
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn front_is_not_dominated() {
+        let family = CodeFamily::Regular {
+            num_bits: 20,
+            num_checks: 15,
+            bit_degree: 3,
+            check_degree: 4,
+        };
+        let mut search = CodeSearch::new(family);
+        search
+            .with_objective(SearchObjective::Girth)
+            .with_objective(SearchObjective::EstimatedDistance {
+                algorithm: IsdAlgorithm::Prange,
+                num_iterations: 50,
+            })
+            .with_num_candidates(30);
+        let mut rng = StdRng::seed_from_u64(5);
+        let front = search.run_with(&mut rng).unwrap();
+        assert!(!front.is_empty());
+        for candidate in front.iter() {
+            assert!(!front.iter().any(|other| search.dominates(other, candidate)));
+            assert_eq!(candidate.code().seed(), Some(candidate.seed()));
+        }
+    }
+
+    #[test]
+    fn protographs_are_lifted() {
+        let family = CodeFamily::Protograph {
+            base_matrix: SparseBinMat::new(3, vec![vec![0, 1, 2], vec![0, 1]]),
+            lift_factor: 5,
+        };
+        let code = family.sample(3).unwrap();
+        assert_eq!(code.len(), 15);
+        assert_eq!(code.num_checks(), 10);
+        assert_eq!(code.parity_check_matrix().number_of_ones(), 25);
+        assert_eq!(code, family.sample(3).unwrap());
+    }
+
+    #[test]
+    fn invalid_regular_family_fails() {
+        let family = CodeFamily::Regular {
+            num_bits: 10,
+            num_checks: 4,
+            bit_degree: 3,
+            check_degree: 4,
+        };
+        assert!(CodeSearch::new(family)
+            .run_with(&mut StdRng::seed_from_u64(0))
+            .is_err());
+    }
+}
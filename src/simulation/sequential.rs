@@ -0,0 +1,341 @@
+use super::FailureCount;
+use crate::codes::LinearCode;
+use crate::decoders::SyndromeDecoder;
+use crate::noise::{NoiseModel, Probability};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinSlice, SparseBinVec};
+
+/// The conclusion of a [`SequentialTest`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum SequentialDecision {
+    /// The failure rate is below the target.
+    Below,
+    /// The failure rate is above the target.
+    Above,
+    /// The maximum number of trials was reached before a decision.
+    Inconclusive,
+}
+
+/// The decision of a [`SequentialTest`] with the trials it needed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct SequentialOutcome {
+    pub decision: SequentialDecision,
+    pub count: FailureCount,
+}
+
+/// A sequential probability ratio test deciding if a failure rate
+/// is below or above a target with as few trials as possible.
+///
+/// The test compares the hypothesis that the rate is `target * (1 - indifference)`
+/// against the hypothesis that it is `target * (1 + indifference)`.
+/// After each trial, the log-likelihood ratio of the failures is updated
+/// and the test stops as soon as it crosses one of Wald's bounds.
+/// A rate below the lower hypothesis is declared above with probability at most `alpha`
+/// and a rate above the upper hypothesis is declared below with probability at most `beta`.
+/// Rates in between, the indifference region, can go either way.
+///
+/// Far from the target, the test stops after a few multiples of `1 / target` trials
+/// instead of the fixed number of trials required for a confidence interval,
+/// which makes it well suited for bisections, see [`bisect_probability`].
+///
+/// By default, the indifference is 0.2, both error rates are 0.05
+/// and the test has at most 1 000 000 trials.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::BpDecoder;
+/// use ldpc::noise::{BinarySymmetricChannel, Probability};
+/// use ldpc::simulation::{SequentialDecision, SequentialTest};
+/// use rand::thread_rng;
+///
+/// let code = LinearCode::hamming_code();
+/// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.01), 10);
+/// let noise = BinarySymmetricChannel::with_probability(Probability::new(0.01));
+///
+/// // The Hamming code fails about 0.2% of the time at this probability.
+/// let outcome =
+///     SequentialTest::new(0.1).decide_classical(&code, &noise, &decoder, &mut thread_rng());
+/// assert_eq!(outcome.decision, SequentialDecision::Below);
+/// assert!(outcome.count.num_trials() < 1000);
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SequentialTest {
+    target: f64,
+    indifference: f64,
+    alpha: f64,
+    beta: f64,
+    max_trials: usize,
+}
+
+impl SequentialTest {
+    /// Creates a test against the given target failure rate.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the target is not strictly between 0 and 1
+    /// or if the upper hypothesis `target * 1.2` is not below 1.
+    pub fn new(target: f64) -> Self {
+        let test = Self {
+            target,
+            indifference: 0.2,
+            alpha: 0.05,
+            beta: 0.05,
+            max_trials: 1_000_000,
+        };
+        test.validate();
+        test
+    }
+
+    /// Sets the relative half width of the indifference region around the target.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the indifference is not strictly between 0 and 1
+    /// or if the upper hypothesis is not below 1.
+    pub fn with_indifference(&mut self, indifference: f64) -> &mut Self {
+        self.indifference = indifference;
+        self.validate();
+        self
+    }
+
+    /// Sets the probabilities of declaring a rate above when it is below
+    /// the indifference region and below when it is above.
+    ///
+    /// # Panic
+    ///
+    /// Panics if an error rate is not strictly between 0 and 1/2.
+    pub fn with_error_rates(&mut self, alpha: f64, beta: f64) -> &mut Self {
+        self.alpha = alpha;
+        self.beta = beta;
+        self.validate();
+        self
+    }
+
+    /// Sets the number of trials after which the test is inconclusive.
+    pub fn with_max_trials(&mut self, max_trials: usize) -> &mut Self {
+        self.max_trials = max_trials;
+        self
+    }
+
+    /// Returns the target failure rate.
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    fn validate(&self) {
+        if !(self.target > 0.0 && self.target < 1.0) {
+            panic!("target {} is not strictly between 0 and 1", self.target);
+        }
+        if !(self.indifference > 0.0 && self.indifference < 1.0) {
+            panic!(
+                "indifference {} is not strictly between 0 and 1",
+                self.indifference
+            );
+        }
+        if self.upper_rate() >= 1.0 {
+            panic!("upper hypothesis {} is not below 1", self.upper_rate());
+        }
+        for error_rate in [self.alpha, self.beta] {
+            if !(error_rate > 0.0 && error_rate < 0.5) {
+                panic!(
+                    "error rate {} is not strictly between 0 and 1/2",
+                    error_rate
+                );
+            }
+        }
+    }
+
+    fn lower_rate(&self) -> f64 {
+        self.target * (1.0 - self.indifference)
+    }
+
+    fn upper_rate(&self) -> f64 {
+        self.target * (1.0 + self.indifference)
+    }
+
+    /// Runs trials until a decision where each call to the given function
+    /// runs a trial and returns true if it failed.
+    pub fn decide<F: FnMut() -> bool>(&self, mut has_failed: F) -> SequentialOutcome {
+        let (lower, upper) = (self.lower_rate(), self.upper_rate());
+        let failure_step = (upper / lower).ln();
+        let success_step = ((1.0 - upper) / (1.0 - lower)).ln();
+        let above_bound = ((1.0 - self.beta) / self.alpha).ln();
+        let below_bound = (self.beta / (1.0 - self.alpha)).ln();
+        let mut ratio = 0.0;
+        let mut num_failures = 0;
+        for num_trials in 1..=self.max_trials {
+            if has_failed() {
+                num_failures += 1;
+                ratio += failure_step;
+            } else {
+                ratio += success_step;
+            }
+            let decision = if ratio >= above_bound {
+                SequentialDecision::Above
+            } else if ratio <= below_bound {
+                SequentialDecision::Below
+            } else {
+                continue;
+            };
+            return SequentialOutcome {
+                decision,
+                count: FailureCount::new(num_trials, num_failures),
+            };
+        }
+        SequentialOutcome {
+            decision: SequentialDecision::Inconclusive,
+            count: FailureCount::new(self.max_trials, num_failures),
+        }
+    }
+
+    /// Decides if the decoder fails more often than the target
+    /// on errors of the classical code sampled from the noise model.
+    ///
+    /// A trial fails if the correction is different from the error.
+    pub fn decide_classical<N, D, R>(
+        &self,
+        code: &LinearCode,
+        noise: &N,
+        decoder: &D,
+        rng: &mut R,
+    ) -> SequentialOutcome
+    where
+        N: NoiseModel<Error = SparseBinVec>,
+        D: for<'s> SyndromeDecoder<SparseBinSlice<'s>, SparseBinVec>,
+        R: Rng,
+    {
+        self.decide(|| {
+            let error = code.random_error(noise, rng);
+            let correction = decoder.correction_for(code.syndrome_of(&error).as_view());
+            correction != error
+        })
+    }
+}
+
+/// Locates the probability where a failure rate increasing with the probability
+/// crosses a target by bisection.
+///
+/// The function decides if the failure rate at a probability is above or below the target,
+/// usually with a [`SequentialTest`].
+/// Starting from the given interval, each of the steps halves the interval
+/// toward the crossing and the final interval is returned.
+/// The bisection stops early at the first inconclusive decision.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::BpDecoder;
+/// use ldpc::noise::{BinarySymmetricChannel, Probability};
+/// use ldpc::simulation::{bisect_probability, SequentialTest};
+/// use rand::thread_rng;
+///
+/// let code = LinearCode::repetition_code(5);
+/// let test = SequentialTest::new(0.1);
+/// let decide_at = |probability| {
+///     let decoder = BpDecoder::new(code.parity_check_matrix(), probability, 10);
+///     let noise = BinarySymmetricChannel::with_probability(probability);
+///     test.decide_classical(&code, &noise, &decoder, &mut thread_rng())
+/// };
+/// let (low, high) =
+///     bisect_probability(Probability::new(0.01), Probability::new(0.5), 6, decide_at);
+///
+/// // The 5 bits repetition code fails 10% of the time around 0.25.
+/// assert!(low.value() < high.value());
+/// assert!(high.value() - low.value() < 0.01);
+/// ```
+pub fn bisect_probability<F>(
+    low: Probability,
+    high: Probability,
+    num_steps: usize,
+    mut decide_at: F,
+) -> (Probability, Probability)
+where
+    F: FnMut(Probability) -> SequentialOutcome,
+{
+    let (mut low, mut high) = (low, high);
+    for _ in 0..num_steps {
+        let middle = Probability::new((low.value() + high.value()) / 2.0);
+        match decide_at(middle).decision {
+            SequentialDecision::Below => low = middle,
+            SequentialDecision::Above => high = middle,
+            SequentialDecision::Inconclusive => break,
+        }
+    }
+    (low, high)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::BpDecoder;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Always flips the first two bits.
+    struct FirstTwoBits;
+
+    impl NoiseModel for FirstTwoBits {
+        type Error = SparseBinVec;
+
+        fn sample_error_of_length<R: Rng>(&self, length: usize, _: &mut R) -> SparseBinVec {
+            SparseBinVec::new(length, vec![0, 1])
+        }
+    }
+
+    #[test]
+    fn decisions_far_from_the_target() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let test = SequentialTest::new(0.1);
+        let below = test.decide(|| rng.gen_bool(0.02));
+        assert_eq!(below.decision, SequentialDecision::Below);
+        let above = test.decide(|| rng.gen_bool(0.3));
+        assert_eq!(above.decision, SequentialDecision::Above);
+        assert!(below.count.num_trials() < 500);
+        assert!(above.count.num_trials() < 500);
+    }
+
+    #[test]
+    fn error_rates_are_controlled() {
+        let mut rng = StdRng::seed_from_u64(12);
+        let test = SequentialTest::new(0.1);
+        let num_wrong = (0..200)
+            .filter(|_| test.decide(|| rng.gen_bool(0.08)).decision == SequentialDecision::Above)
+            .count();
+        assert!(num_wrong <= 20);
+    }
+
+    #[test]
+    fn too_few_trials_are_inconclusive() {
+        let outcome = SequentialTest::new(0.1).with_max_trials(3).decide(|| false);
+        assert_eq!(outcome.decision, SequentialDecision::Inconclusive);
+        assert_eq!(outcome.count, FailureCount::new(3, 0));
+    }
+
+    // The correction of a weight 2 error of the Hamming code differs from the error
+    // by a codeword, which is a failure.
+    #[test]
+    fn wrong_codewords_are_failures() {
+        let code = LinearCode::hamming_code();
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.01), 10);
+        let outcome = SequentialTest::new(0.1)
+            .with_max_trials(100)
+            .decide_classical(
+                &code,
+                &FirstTwoBits,
+                &decoder,
+                &mut StdRng::seed_from_u64(13),
+            );
+        assert_eq!(outcome.decision, SequentialDecision::Above);
+        assert_eq!(outcome.count.num_failures(), outcome.count.num_trials());
+    }
+
+    #[test]
+    #[should_panic]
+    fn upper_hypothesis_must_be_a_rate() {
+        SequentialTest::new(0.9);
+    }
+}
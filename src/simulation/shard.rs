@@ -0,0 +1,257 @@
+use crate::codes::{Fingerprint, FingerprintHasher};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The number of trials and failures at a point of a simulation.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedFailureCount")]
+pub struct FailureCount {
+    num_trials: usize,
+    num_failures: usize,
+}
+
+impl FailureCount {
+    /// Creates a count from the number of trials and failures.
+    ///
+    /// # Panic
+    ///
+    /// Panics if there are more failures than trials.
+    pub fn new(num_trials: usize, num_failures: usize) -> Self {
+        if num_failures > num_trials {
+            panic!("{} failures for {} trials", num_failures, num_trials);
+        }
+        Self {
+            num_trials,
+            num_failures,
+        }
+    }
+
+    /// Returns the number of trials.
+    pub fn num_trials(&self) -> usize {
+        self.num_trials
+    }
+
+    /// Returns the number of failures.
+    pub fn num_failures(&self) -> usize {
+        self.num_failures
+    }
+
+    /// Returns the fraction of trials that failed or 0 if there is no trial.
+    pub fn failure_rate(&self) -> f64 {
+        if self.num_trials == 0 {
+            0.0
+        } else {
+            self.num_failures as f64 / self.num_trials as f64
+        }
+    }
+
+    /// Returns the count of the trials of both counts.
+    pub fn merged_with(&self, other: &Self) -> Self {
+        Self {
+            num_trials: self.num_trials + other.num_trials,
+            num_failures: self.num_failures + other.num_failures,
+        }
+    }
+}
+
+// The fields of a serialized failure count before they are validated.
+#[derive(Deserialize)]
+struct UncheckedFailureCount {
+    num_trials: usize,
+    num_failures: usize,
+}
+
+impl TryFrom<UncheckedFailureCount> for FailureCount {
+    type Error = String;
+
+    fn try_from(count: UncheckedFailureCount) -> Result<Self, Self::Error> {
+        if count.num_failures > count.num_trials {
+            Err(format!(
+                "{} failures for {} trials",
+                count.num_failures, count.num_trials
+            ))
+        } else {
+            Ok(Self {
+                num_trials: count.num_trials,
+                num_failures: count.num_failures,
+            })
+        }
+    }
+}
+
+/// The results of a shard of a simulation campaign,
+/// that is a run of the same configuration with its own seed,
+/// usually on a different machine.
+///
+/// There is one failure count for each point of the simulation,
+/// for example each probability of a sweep.
+/// The configuration is identified by its [`Fingerprint`],
+/// such that only shards of the same configuration are merged
+/// with [`merge_results`].
+/// The configuration should describe everything but the seed and
+/// the number of trials which may differ between shards.
+///
+/// Shards are serialized with serde to be collected from different machines.
+///
+/// # Example
+///
+/// ```
+/// use ldpc::simulation::{merge_results, FailureCount, ShardResult};
+///
+/// let config = r#"{"code": "surface(5)", "sweep": [0.01, 0.02]}"#;
+/// let first = ShardResult::new(config, 1, vec![FailureCount::new(100, 3), FailureCount::new(100, 8)]);
+/// let second = ShardResult::new(config, 2, vec![FailureCount::new(50, 1), FailureCount::new(50, 5)]);
+///
+/// let merged = merge_results(&[first, second]).unwrap();
+/// assert_eq!(merged.seeds(), &[1, 2]);
+/// assert_eq!(merged.counts(), &[FailureCount::new(150, 4), FailureCount::new(150, 13)]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct ShardResult {
+    config: Fingerprint,
+    seeds: Vec<u64>,
+    counts: Vec<FailureCount>,
+}
+
+impl ShardResult {
+    /// Creates the result of a shard run with the given seed
+    /// from a description of its configuration.
+    pub fn new(config: &str, seed: u64, counts: Vec<FailureCount>) -> Self {
+        Self {
+            config: config_fingerprint(config),
+            seeds: vec![seed],
+            counts,
+        }
+    }
+
+    /// Returns the fingerprint of the configuration.
+    pub fn config(&self) -> Fingerprint {
+        self.config
+    }
+
+    /// Checks if the shard was run with the given configuration.
+    pub fn has_config(&self, config: &str) -> bool {
+        self.config == config_fingerprint(config)
+    }
+
+    /// Returns the seeds of the shards in increasing order.
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    /// Returns the failure count of each point.
+    pub fn counts(&self) -> &[FailureCount] {
+        &self.counts
+    }
+}
+
+/// Merges the results of shards of the same configuration by adding their counts.
+///
+/// # Errors
+///
+/// Returns an error if there is no shard, if two shards have
+/// different configurations or number of points,
+/// or if a seed is used by more than one shard since their trials
+/// would not be independent.
+pub fn merge_results(shards: &[ShardResult]) -> Result<ShardResult, MergeError> {
+    let (first, others) = shards.split_first().ok_or(MergeError::NoShard)?;
+    let mut merged = first.clone();
+    for shard in others {
+        if shard.config != merged.config {
+            return Err(MergeError::DifferentConfigs(merged.config, shard.config));
+        }
+        if shard.counts.len() != merged.counts.len() {
+            return Err(MergeError::DifferentNumberOfPoints(
+                merged.counts.len(),
+                shard.counts.len(),
+            ));
+        }
+        merged.seeds.extend_from_slice(&shard.seeds);
+        for (count, other) in merged.counts.iter_mut().zip(shard.counts.iter()) {
+            *count = count.merged_with(other);
+        }
+    }
+    merged.seeds.sort_unstable();
+    if let Some(seeds) = merged.seeds.windows(2).find(|seeds| seeds[0] == seeds[1]) {
+        return Err(MergeError::RepeatedSeed(seeds[0]));
+    }
+    Ok(merged)
+}
+
+fn config_fingerprint(config: &str) -> Fingerprint {
+    let mut hasher = FingerprintHasher::new("SimulationConfig");
+    hasher.write_bytes(config.as_bytes());
+    hasher.finish()
+}
+
+/// An error when merging shard results.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MergeError {
+    NoShard,
+    DifferentConfigs(Fingerprint, Fingerprint),
+    DifferentNumberOfPoints(usize, usize),
+    RepeatedSeed(u64),
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoShard => write!(f, "no shard to merge"),
+            Self::DifferentConfigs(first, second) => {
+                write!(f, "different configurations: {} & {}", first, second)
+            }
+            Self::DifferentNumberOfPoints(first, second) => {
+                write!(f, "different number of points: {} & {}", first, second)
+            }
+            Self::RepeatedSeed(seed) => write!(f, "seed {} is used by many shards", seed),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shards_must_be_compatible() {
+        let shard = |config: &str, seed: u64, num_points: usize| {
+            ShardResult::new(config, seed, vec![FailureCount::new(10, 1); num_points])
+        };
+        assert_eq!(merge_results(&[]), Err(MergeError::NoShard));
+        assert!(matches!(
+            merge_results(&[shard("a", 1, 2), shard("b", 2, 2)]),
+            Err(MergeError::DifferentConfigs(_, _))
+        ));
+        assert_eq!(
+            merge_results(&[shard("a", 1, 2), shard("a", 2, 3)]),
+            Err(MergeError::DifferentNumberOfPoints(2, 3))
+        );
+        let merged = merge_results(&[shard("a", 3, 2), shard("a", 1, 2)]).unwrap();
+        assert_eq!(
+            merge_results(&[merged.clone(), shard("a", 3, 2)]),
+            Err(MergeError::RepeatedSeed(3))
+        );
+        assert_eq!(merged.seeds(), &[1, 3]);
+        assert!(merged.has_config("a"));
+    }
+
+    #[test]
+    fn merged_shard_survives_serialization() {
+        let shard = ShardResult::new("config", 7, vec![FailureCount::new(20, 3)]);
+        let json = serde_json::to_string(&shard).unwrap();
+        assert_eq!(serde_json::from_str::<ShardResult>(&json).unwrap(), shard);
+    }
+
+    #[test]
+    fn deserialized_counts_have_at_most_one_failure_per_trial() {
+        let count = r#"{"num_trials":1,"num_failures":5}"#;
+        assert!(serde_json::from_str::<FailureCount>(count).is_err());
+        let count = r#"{"num_trials":5,"num_failures":1}"#;
+        assert_eq!(
+            serde_json::from_str::<FailureCount>(count).unwrap(),
+            FailureCount::new(5, 1)
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use crate::codes::{CssCode, LinearCode};
+use crate::noise::NoiseModel;
+use pauli::PauliOperator;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinVec, SparseBinVecBase};
+use std::ops::Deref;
+
+/// The distribution of the syndrome weights and the firing rate of each check
+/// over many sampled syndromes.
+///
+/// These are the observables of the detectors of an experiment,
+/// so comparing them with experimental data validates the choice of a noise model
+/// before decoding with it.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::noise::{BinarySymmetricChannel, Probability};
+/// use ldpc::simulation::SyndromeStatistics;
+/// use rand::thread_rng;
+///
+/// let code = LinearCode::repetition_code(5);
+/// let noise = BinarySymmetricChannel::with_probability(Probability::new(0.1));
+/// let statistics = SyndromeStatistics::sample_classical(&code, &noise, 1000, &mut thread_rng());
+///
+/// assert_eq!(statistics.num_samples(), 1000);
+/// assert_eq!(statistics.weight_counts().iter().sum::<usize>(), 1000);
+///
+/// // Each check fires when exactly one of its two bits flips.
+/// for rate in statistics.firing_rates() {
+///     assert!((rate - 0.18).abs() < 0.05);
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct SyndromeStatistics {
+    num_samples: usize,
+    weight_counts: Vec<usize>,
+    firing_counts: Vec<usize>,
+}
+
+impl SyndromeStatistics {
+    /// Creates empty statistics for syndromes of the given number of checks.
+    pub fn new(num_checks: usize) -> Self {
+        Self {
+            num_samples: 0,
+            weight_counts: vec![0; num_checks + 1],
+            firing_counts: vec![0; num_checks],
+        }
+    }
+
+    /// Samples the given number of errors of the classical code from the noise model
+    /// and records their syndromes.
+    pub fn sample_classical<N, R>(
+        code: &LinearCode,
+        noise: &N,
+        num_samples: usize,
+        rng: &mut R,
+    ) -> Self
+    where
+        N: NoiseModel<Error = SparseBinVec>,
+        R: Rng,
+    {
+        let mut statistics = Self::new(code.num_checks());
+        for _ in 0..num_samples {
+            let error = code.random_error(noise, rng);
+            statistics.record(&code.syndrome_of(&error));
+        }
+        statistics
+    }
+
+    /// Samples the given number of errors of the CSS code from the noise model
+    /// and records their syndromes.
+    ///
+    /// The checks are the X stabilizers followed by the Z stabilizers.
+    pub fn sample_css<N, R>(code: &CssCode, noise: &N, num_samples: usize, rng: &mut R) -> Self
+    where
+        N: NoiseModel<Error = PauliOperator>,
+        R: Rng,
+    {
+        let num_x_checks = code.x_stabs_binary().number_of_rows();
+        let num_checks = num_x_checks + code.z_stabs_binary().number_of_rows();
+        let mut statistics = Self::new(num_checks);
+        for _ in 0..num_samples {
+            let syndrome = code.syndrome_of(&code.random_error(noise, rng));
+            let positions = syndrome
+                .x
+                .non_trivial_positions()
+                .chain(
+                    syndrome
+                        .z
+                        .non_trivial_positions()
+                        .map(|check| check + num_x_checks),
+                )
+                .collect();
+            statistics.record(&SparseBinVec::new(num_checks, positions));
+        }
+        statistics
+    }
+
+    /// Records a syndrome.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the syndrome length is different from the number of checks.
+    pub fn record<T>(&mut self, syndrome: &SparseBinVecBase<T>)
+    where
+        T: Deref<Target = [usize]>,
+    {
+        if syndrome.len() != self.num_checks() {
+            panic!(
+                "syndrome of length {} for {} checks",
+                syndrome.len(),
+                self.num_checks()
+            );
+        }
+        self.num_samples += 1;
+        self.weight_counts[syndrome.weight()] += 1;
+        for check in syndrome.non_trivial_positions() {
+            self.firing_counts[check] += 1;
+        }
+    }
+
+    /// Returns the number of checks.
+    pub fn num_checks(&self) -> usize {
+        self.firing_counts.len()
+    }
+
+    /// Returns the number of recorded syndromes.
+    pub fn num_samples(&self) -> usize {
+        self.num_samples
+    }
+
+    /// Returns the number of syndromes of each weight from 0 to the number of checks.
+    pub fn weight_counts(&self) -> &[usize] {
+        &self.weight_counts
+    }
+
+    /// Returns the fraction of syndromes of each weight from 0 to the number of checks
+    /// or zeros if there is no sample.
+    pub fn weight_distribution(&self) -> Vec<f64> {
+        self.weight_counts
+            .iter()
+            .map(|count| self.fraction(*count))
+            .collect()
+    }
+
+    /// Returns the average syndrome weight or 0 if there is no sample.
+    pub fn mean_weight(&self) -> f64 {
+        self.firing_counts
+            .iter()
+            .map(|count| self.fraction(*count))
+            .sum()
+    }
+
+    /// Returns the number of syndromes where each check fires.
+    pub fn firing_counts(&self) -> &[usize] {
+        &self.firing_counts
+    }
+
+    /// Returns the fraction of syndromes where each check fires
+    /// or zeros if there is no sample.
+    pub fn firing_rates(&self) -> Vec<f64> {
+        self.firing_counts
+            .iter()
+            .map(|count| self.fraction(*count))
+            .collect()
+    }
+
+    /// Returns the statistics of the syndromes of both statistics.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the numbers of checks are different.
+    pub fn merged_with(&self, other: &Self) -> Self {
+        if self.num_checks() != other.num_checks() {
+            panic!(
+                "can't merge statistics of {} and {} checks",
+                self.num_checks(),
+                other.num_checks()
+            );
+        }
+        let add = |first: &[usize], second: &[usize]| {
+            first
+                .iter()
+                .zip(second)
+                .map(|(first, second)| first + second)
+                .collect()
+        };
+        Self {
+            num_samples: self.num_samples + other.num_samples,
+            weight_counts: add(&self.weight_counts, &other.weight_counts),
+            firing_counts: add(&self.firing_counts, &other.firing_counts),
+        }
+    }
+
+    fn fraction(&self, count: usize) -> f64 {
+        if self.num_samples == 0 {
+            0.0
+        } else {
+            count as f64 / self.num_samples as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::noise::{BinarySymmetricChannel, DepolarizingNoise, Probability};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn css_checks_are_concatenated() {
+        let code = CssCode::steane_code();
+        let noise = DepolarizingNoise::with_probability(Probability::new(0.1));
+        let mut rng = StdRng::seed_from_u64(4);
+        let statistics = SyndromeStatistics::sample_css(&code, &noise, 2000, &mut rng);
+        assert_eq!(statistics.num_checks(), 6);
+        assert_eq!(statistics.weight_counts().len(), 7);
+        let weights = statistics
+            .weight_distribution()
+            .iter()
+            .enumerate()
+            .map(|(weight, fraction)| weight as f64 * fraction)
+            .sum::<f64>();
+        assert!((weights - statistics.mean_weight()).abs() < 1e-9);
+        // The X and Z stabilizers of the Steane code have the same supports.
+        let rates = statistics.firing_rates();
+        for check in 0..3 {
+            assert!((rates[check] - rates[check + 3]).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn merged_statistics_add_up() {
+        let code = LinearCode::hamming_code();
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.0));
+        let mut rng = StdRng::seed_from_u64(5);
+        let first = SyndromeStatistics::sample_classical(&code, &noise, 10, &mut rng);
+        let mut second = SyndromeStatistics::new(3);
+        second.record(&SparseBinVec::new(3, vec![0, 2]));
+        let merged = first.merged_with(&second);
+        assert_eq!(merged.num_samples(), 11);
+        assert_eq!(merged.weight_counts(), &[10, 0, 1, 0]);
+        assert_eq!(merged.firing_counts(), &[1, 0, 1]);
+        assert_eq!(SyndromeStatistics::new(3).firing_rates(), vec![0.0; 3]);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::codes::LinearCode;
+use crate::decoders::SyndromeDecoder;
+use crate::noise::NoiseModel;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use sparse_bin_mat::{SparseBinMat, SparseBinSlice, SparseBinVec};
+
+/// The version of the JSON schema of [`TestVectors`].
+///
+/// It changes only when the schema is not backward compatible.
+pub const TEST_VECTORS_SCHEMA_VERSION: u32 = 1;
+
+/// Decoding test vectors of a classical code to cross-validate
+/// other implementations against this crate.
+///
+/// Each vector is an error sampled from a noise model together with its syndrome
+/// and the correction returned by a decoder.
+/// The vectors are serialized as JSON with the following stable schema
+/// where every binary vector is the sorted list of its non trivial positions:
+///
+/// ```json
+/// {
+///   "schema_version": 1,
+///   "decoder": "BP with 10 iterations",
+///   "seed": 123,
+///   "code": { "num_bits": 7, "checks": [[0, 1, 2, 4], [0, 1, 3, 5], [0, 2, 3, 6]] },
+///   "vectors": [{ "error": [1], "syndrome": [0, 1], "correction": [1] }]
+/// }
+/// ```
+///
+/// This requires the `test-vectors` feature.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::LinearCode;
+/// use ldpc::decoders::BpDecoder;
+/// use ldpc::noise::{BinarySymmetricChannel, Probability};
+/// use ldpc::simulation::TestVectors;
+///
+/// let code = LinearCode::hamming_code();
+/// let noise = BinarySymmetricChannel::with_probability(Probability::new(0.1));
+/// let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 10);
+///
+/// let vectors = TestVectors::generate(&code, &noise, &decoder, "BP with 10 iterations", 20, 123);
+/// let json = vectors.to_json();
+///
+/// // Another implementation reads the same vectors.
+/// let loaded = TestVectors::from_json(&json).unwrap();
+/// assert_eq!(loaded, vectors);
+/// assert_eq!(loaded.code().parity_check_matrix(), code.parity_check_matrix());
+/// assert!(loaded.mismatches(&decoder).is_empty());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TestVectors {
+    schema_version: u32,
+    decoder: String,
+    seed: u64,
+    code: TestVectorCode,
+    vectors: Vec<TestVector>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+struct TestVectorCode {
+    num_bits: usize,
+    checks: Vec<Vec<usize>>,
+}
+
+/// An error with its syndrome and the expected correction.
+///
+/// Each vector is the sorted list of its non trivial positions.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TestVector {
+    /// The error sampled from the noise model.
+    pub error: Vec<usize>,
+    /// The syndrome of the error.
+    pub syndrome: Vec<usize>,
+    /// The correction returned by the decoder for the syndrome.
+    pub correction: Vec<usize>,
+}
+
+impl TestVectors {
+    /// Samples the given number of errors from the noise model
+    /// with a random number generator seeded with the seed
+    /// and records their syndromes and the corrections of the decoder.
+    ///
+    /// The description of the decoder tells other implementations
+    /// how to configure their decoder.
+    pub fn generate<N, D, S>(
+        code: &LinearCode,
+        noise: &N,
+        decoder: &D,
+        decoder_description: S,
+        num_vectors: usize,
+        seed: u64,
+    ) -> Self
+    where
+        N: NoiseModel<Error = SparseBinVec>,
+        D: for<'s> SyndromeDecoder<SparseBinSlice<'s>, SparseBinVec>,
+        S: Into<String>,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let vectors = (0..num_vectors)
+            .map(|_| {
+                let error = code.random_error(noise, &mut rng);
+                let syndrome = code.syndrome_of(&error);
+                let correction = decoder.correction_for(syndrome.as_view());
+                TestVector {
+                    error: error.to_positions_vec(),
+                    syndrome: syndrome.to_positions_vec(),
+                    correction: correction.to_positions_vec(),
+                }
+            })
+            .collect();
+        Self {
+            schema_version: TEST_VECTORS_SCHEMA_VERSION,
+            decoder: decoder_description.into(),
+            seed,
+            code: TestVectorCode {
+                num_bits: code.len(),
+                checks: code
+                    .parity_check_matrix()
+                    .rows()
+                    .map(|check| check.to_vec().to_positions_vec())
+                    .collect(),
+            },
+            vectors,
+        }
+    }
+
+    /// Reads test vectors from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON doesn't follow the schema,
+    /// if its schema version is not supported or if a position is out of bound.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        use serde::de::Error;
+        let vectors: Self = serde_json::from_str(json)?;
+        if vectors.schema_version != TEST_VECTORS_SCHEMA_VERSION {
+            return Err(serde_json::Error::custom(format!(
+                "unsupported schema version {}",
+                vectors.schema_version
+            )));
+        }
+        let num_bits = vectors.code.num_bits;
+        let num_checks = vectors.code.checks.len();
+        let is_in_bound = |positions: &[usize], length: usize| {
+            positions.iter().all(|position| *position < length)
+        };
+        let is_valid = vectors
+            .code
+            .checks
+            .iter()
+            .all(|check| is_in_bound(check, num_bits))
+            && vectors.vectors.iter().all(|vector| {
+                is_in_bound(&vector.error, num_bits)
+                    && is_in_bound(&vector.syndrome, num_checks)
+                    && is_in_bound(&vector.correction, num_bits)
+            });
+        if !is_valid {
+            return Err(serde_json::Error::custom("position out of bound"));
+        }
+        Ok(vectors)
+    }
+
+    /// Returns the test vectors as pretty printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Returns the version of the schema.
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    /// Returns the description of the decoder.
+    pub fn decoder(&self) -> &str {
+        &self.decoder
+    }
+
+    /// Returns the seed used to sample the errors.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the code defined by the parity checks.
+    pub fn code(&self) -> LinearCode {
+        LinearCode::from_parity_check_matrix(SparseBinMat::new(
+            self.code.num_bits,
+            self.code.checks.clone(),
+        ))
+    }
+
+    /// Returns the vectors.
+    pub fn vectors(&self) -> &[TestVector] {
+        &self.vectors
+    }
+
+    /// Returns the indices of the vectors whose syndrome doesn't match their error
+    /// or whose correction is different from the one of the given decoder.
+    pub fn mismatches<D>(&self, decoder: &D) -> Vec<usize>
+    where
+        D: for<'s> SyndromeDecoder<SparseBinSlice<'s>, SparseBinVec>,
+    {
+        let code = self.code();
+        let vector_of =
+            |positions: &[usize], length: usize| SparseBinVec::new(length, positions.to_vec());
+        self.vectors
+            .iter()
+            .enumerate()
+            .filter(|(_, vector)| {
+                let error = vector_of(&vector.error, code.len());
+                let syndrome = vector_of(&vector.syndrome, code.num_checks());
+                let correction = vector_of(&vector.correction, code.len());
+                code.syndrome_of(&error) != syndrome
+                    || decoder.correction_for(syndrome.as_view()) != correction
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decoders::BpDecoder;
+    use crate::noise::{BinarySymmetricChannel, Probability};
+
+    #[test]
+    fn same_seed_generates_same_vectors() {
+        let code = LinearCode::hamming_code();
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.2));
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.2), 5);
+        let first = TestVectors::generate(&code, &noise, &decoder, "BP", 30, 7);
+        let second = TestVectors::generate(&code, &noise, &decoder, "BP", 30, 7);
+        assert_eq!(first, second);
+        assert!(first
+            .vectors()
+            .iter()
+            .any(|vector| !vector.error.is_empty()));
+    }
+
+    #[test]
+    fn corrupted_vectors_are_reported() {
+        let code = LinearCode::hamming_code();
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.0));
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 5);
+        let mut vectors = TestVectors::generate(&code, &noise, &decoder, "BP", 3, 0);
+        vectors.vectors[1].syndrome = vec![0];
+        vectors.vectors[2].correction = vec![6];
+        assert_eq!(vectors.mismatches(&decoder), vec![1, 2]);
+    }
+
+    #[test]
+    fn invalid_json_is_rejected() {
+        let code = LinearCode::repetition_code(3);
+        let noise = BinarySymmetricChannel::with_probability(Probability::new(0.0));
+        let decoder = BpDecoder::new(code.parity_check_matrix(), Probability::new(0.1), 5);
+        let vectors = TestVectors::generate(&code, &noise, &decoder, "BP", 1, 0);
+        let mut json = serde_json::to_value(&vectors).unwrap();
+        json["vectors"][0]["error"] = serde_json::json!([3]);
+        assert!(TestVectors::from_json(&json.to_string()).is_err());
+        json["schema_version"] = serde_json::json!(2);
+        assert!(TestVectors::from_json(&json.to_string()).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use super::{check_layout, pauli_at};
+use crate::codes::{CssCode, Layout};
+use crate::css::{CssOperator, CssSyndrome};
+use pauli::PauliOperator;
+use sparse_bin_mat::SparseBinVec;
+
+/// A terminal drawing of a CSS code on a grid with an error and its syndrome.
+///
+/// Each cell of the [`Layout`](crate::codes::Layout) is drawn with a single character:
+///
+/// - `o` is a qubit without error,
+/// - `X`, `Y` or `Z` is a qubit with the corresponding error,
+/// - `x` or `z` is a satisfied X or Z stabilizer,
+/// - `*` is a violated stabilizer.
+///
+/// Empty cells are left blank and cells are separated by a space.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::{CssCode, LinearCode};
+/// use ldpc::visualization::AsciiLattice;
+/// use pauli::{PauliOperator, X};
+///
+/// let repetition_code = LinearCode::repetition_code(3);
+/// let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+/// let lattice = AsciiLattice::from_code(&code).unwrap();
+///
+/// let error = PauliOperator::new(13, vec![4], vec![X]);
+/// let expected = "\
+/// o x o x o
+/// z o * o z
+/// o x X x o
+/// z o * o z
+/// o x o x o
+/// ";
+/// assert_eq!(lattice.render(&error), expected);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsciiLattice<'a> {
+    code: &'a CssCode,
+    layout: &'a Layout,
+}
+
+impl<'a> AsciiLattice<'a> {
+    /// Creates a drawing of the code using the positions of the given layout.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the layout has a different number of qubits or stabilizers than the code.
+    pub fn new(code: &'a CssCode, layout: &'a Layout) -> Self {
+        check_layout(code, layout);
+        Self { code, layout }
+    }
+
+    /// Creates a drawing of the code using its own
+    /// [coordinates](crate::codes::CssCode::coordinates)
+    /// or None if the code has no coordinates.
+    pub fn from_code(code: &'a CssCode) -> Option<Self> {
+        code.coordinates().map(|layout| Self::new(code, layout))
+    }
+
+    /// Renders the lattice with the given error and its syndrome.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the error length is different from the number of qubits.
+    pub fn render(&self, error: &PauliOperator) -> String {
+        let marks = Marks::new(self.code, error);
+        let mut grid = vec![vec![' '; self.layout.num_columns()]; self.layout.num_rows()];
+        for (qubit, (row, column)) in self.layout.qubits().iter().enumerate() {
+            grid[*row][*column] = marks.qubit(qubit);
+        }
+        let stabilizers = self.layout.stabilizers();
+        for (stabilizer, (row, column)) in stabilizers.x.iter().enumerate() {
+            grid[*row][*column] = marks.stabilizer(&marks.syndrome.x, stabilizer, 'x');
+        }
+        for (stabilizer, (row, column)) in stabilizers.z.iter().enumerate() {
+            grid[*row][*column] = marks.stabilizer(&marks.syndrome.z, stabilizer, 'z');
+        }
+        grid.into_iter()
+            .map(|row| {
+                let mut line = row
+                    .into_iter()
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                line.truncate(line.trim_end().len());
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+}
+
+/// A terminal drawing of the Tanner graph of any CSS code
+/// with an error and its syndrome.
+///
+/// This is a fallback for codes without a [`Layout`](crate::codes::Layout).
+/// The first line shows the error on each qubit with the same characters
+/// as the [`AsciiLattice`](AsciiLattice).
+/// Then, each line shows a stabilizer with its label, a `*` if it is violated
+/// and a `1` under each qubit in its support.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::CssCode;
+/// use ldpc::visualization::AsciiTannerGraph;
+/// use pauli::{PauliOperator, Z};
+///
+/// let code = CssCode::shor_code();
+/// let error = PauliOperator::new(9, vec![1], vec![Z]);
+/// let expected = "\
+/// error   oZooooooo
+/// x0    * 111111...
+/// x1      ...111111
+/// z0      11.......
+/// z1      .11......
+/// z2      ...11....
+/// z3      ....11...
+/// z4      ......11.
+/// z5      .......11
+/// ";
+/// assert_eq!(AsciiTannerGraph::new(&code).render(&error), expected);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsciiTannerGraph<'a> {
+    code: &'a CssCode,
+}
+
+impl<'a> AsciiTannerGraph<'a> {
+    /// Creates a drawing of the Tanner graph of the code.
+    pub fn new(code: &'a CssCode) -> Self {
+        Self { code }
+    }
+
+    /// Renders the Tanner graph with the given error and its syndrome.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the error length is different from the number of qubits.
+    pub fn render(&self, error: &PauliOperator) -> String {
+        let marks = Marks::new(self.code, error);
+        let largest_label = format!(
+            "x{}",
+            self.code
+                .num_x_stabs()
+                .max(self.code.num_z_stabs())
+                .saturating_sub(1)
+        );
+        let label_width = largest_label.len().max("error".len()) + 1;
+        let mut output = format!("{:<width$}  ", "error", width = label_width);
+        output.extend((0..self.code.len()).map(|qubit| marks.qubit(qubit)));
+        output.push('\n');
+        let stabilizers = [
+            ('x', &self.code.stabilizers.x, &marks.syndrome.x),
+            ('z', &self.code.stabilizers.z, &marks.syndrome.z),
+        ];
+        for (kind, matrix, syndrome) in stabilizers {
+            for (stabilizer, row) in matrix.rows().enumerate() {
+                let label = format!("{}{}", kind, stabilizer);
+                let mark = if syndrome.is_one_at(stabilizer) == Some(true) {
+                    '*'
+                } else {
+                    ' '
+                };
+                output.push_str(&format!("{:<width$}{} ", label, mark, width = label_width));
+                output.extend((0..self.code.len()).map(|qubit| {
+                    if row.is_one_at(qubit) == Some(true) {
+                        '1'
+                    } else {
+                        '.'
+                    }
+                }));
+                output.push('\n');
+            }
+        }
+        output
+    }
+}
+
+// The characters of the qubits and stabilizers for a given error.
+struct Marks {
+    error: CssOperator,
+    syndrome: CssSyndrome,
+}
+
+impl Marks {
+    fn new(code: &CssCode, error: &PauliOperator) -> Self {
+        if error.len() != code.len() {
+            panic!("error of length {} for {} qubits", error.len(), code.len());
+        }
+        Self {
+            error: CssOperator::from(error),
+            syndrome: code.syndrome_of(error),
+        }
+    }
+
+    fn qubit(&self, qubit: usize) -> char {
+        pauli_at(&self.error, qubit).unwrap_or('o')
+    }
+
+    fn stabilizer(&self, syndrome: &SparseBinVec, stabilizer: usize, idle: char) -> char {
+        if syndrome.is_one_at(stabilizer) == Some(true) {
+            '*'
+        } else {
+            idle
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codes::LinearCode;
+    use pauli::{Y, Z};
+
+    #[test]
+    fn toric_code_flags_stabilizers_across_the_boundary() {
+        let code = CssCode::toric_code(3);
+        let layout = Layout::toric_code(3);
+        assert_eq!(code.coordinates(), Some(&layout));
+        // The qubit at position (0, 0) is in the X stabilizers at (0, 1) and (0, 5).
+        let error = PauliOperator::new(18, vec![0], vec![Z]);
+        let expected = "\
+Z * o x o *
+z o z o z o
+o x o x o x
+z o z o z o
+o x o x o x
+z o z o z o
+";
+        assert_eq!(AsciiLattice::new(&code, &layout).render(&error), expected);
+    }
+
+    #[test]
+    fn y_errors_flag_both_kinds_of_stabilizers() {
+        let repetition_code = LinearCode::repetition_code(3);
+        let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+        let layout = Layout::hypergraph_product(&repetition_code, &repetition_code);
+        let error = PauliOperator::new(13, vec![0], vec![Y]);
+        let lattice = AsciiLattice::new(&code, &layout).render(&error);
+        assert_eq!(lattice.lines().next(), Some("Y * o x o"));
+        assert_eq!(lattice.lines().nth(1), Some("* o z o z"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn layout_must_match_code() {
+        let code = CssCode::toric_code(3);
+        let layout = Layout::toric_code(4);
+        AsciiLattice::new(&code, &layout);
+    }
+}
@@ -0,0 +1,39 @@
+//! Drawings of small codes with errors and syndromes.
+//!
+//! The [`AsciiLattice`](AsciiLattice) draws a code in the terminal using
+//! the positions of a [`Layout`](crate::codes::Layout), such as the usual
+//! picture of the surface and toric codes.
+//! Codes with [coordinates](crate::codes::CssCode::coordinates) are drawn
+//! with their own layout.
+//! Any other code can be drawn with the [`AsciiTannerGraph`](AsciiTannerGraph).
+//!
+//! The [`SvgLattice`](SvgLattice) exports the same pictures as SVG figures,
+//! optionally with a correction overlaid.
+
+use crate::codes::{CssCode, Layout};
+use crate::css::CssOperator;
+
+mod ascii;
+pub use ascii::{AsciiLattice, AsciiTannerGraph};
+
+mod svg;
+pub use svg::SvgLattice;
+
+fn check_layout(code: &CssCode, layout: &Layout) {
+    if !layout.fits(code) {
+        panic!("layout doesn't fit the code");
+    }
+}
+
+// The label of the Pauli acting on the given qubit or None for the identity.
+fn pauli_at(operator: &CssOperator, qubit: usize) -> Option<char> {
+    match (
+        operator.x.is_one_at(qubit) == Some(true),
+        operator.z.is_one_at(qubit) == Some(true),
+    ) {
+        (false, false) => None,
+        (true, false) => Some('X'),
+        (true, true) => Some('Y'),
+        (false, true) => Some('Z'),
+    }
+}
@@ -0,0 +1,253 @@
+use super::{check_layout, pauli_at};
+use crate::codes::{CssCode, Layout};
+use crate::css::{CssOperator, CssSyndrome};
+use pauli::PauliOperator;
+use sparse_bin_mat::SparseBinVec;
+use std::fmt::Write;
+
+const X_COLOR: &str = "#d6604d";
+const Z_COLOR: &str = "#4393c3";
+const ERROR_COLOR: &str = "#fddbc7";
+const CORRECTION_COLOR: &str = "#1b7837";
+
+/// An SVG drawing of a CSS code on a grid with an error, its syndrome and a correction.
+///
+/// Each qubit of the [`Layout`](crate::codes::Layout) is drawn as a circle
+/// labeled by its error, if any.
+/// Each stabilizer is drawn as a square, red for X stabilizers and blue for Z stabilizers,
+/// which is filled when the stabilizer is violated.
+/// The qubits in the support of the correction are circled with a dashed green line.
+///
+/// # Example
+///
+/// ```
+/// # use ldpc::codes::{CssCode, LinearCode};
+/// use ldpc::visualization::SvgLattice;
+/// use pauli::{PauliOperator, X};
+///
+/// let repetition_code = LinearCode::repetition_code(3);
+/// let code = CssCode::hypergraph_product(&repetition_code, &repetition_code);
+/// let mut lattice = SvgLattice::from_code(&code).unwrap();
+/// lattice.cell_size(50.0);
+///
+/// let error = PauliOperator::new(13, vec![4], vec![X]);
+/// let correction = PauliOperator::new(13, vec![4], vec![X]);
+/// let drawing = lattice.render_with_correction(&error, &correction);
+///
+/// assert!(drawing.starts_with("<svg"));
+/// assert_eq!(drawing.matches("<circle").count(), 13 + 1);
+/// assert_eq!(drawing.matches("<rect").count(), 12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SvgLattice<'a> {
+    code: &'a CssCode,
+    layout: &'a Layout,
+    cell_size: f64,
+}
+
+impl<'a> SvgLattice<'a> {
+    /// Creates a drawing of the code using the positions of the given layout.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the layout has a different number of qubits or stabilizers than the code.
+    pub fn new(code: &'a CssCode, layout: &'a Layout) -> Self {
+        check_layout(code, layout);
+        Self {
+            code,
+            layout,
+            cell_size: 40.0,
+        }
+    }
+
+    /// Creates a drawing of the code using its own
+    /// [coordinates](crate::codes::CssCode::coordinates)
+    /// or None if the code has no coordinates.
+    pub fn from_code(code: &'a CssCode) -> Option<Self> {
+        code.coordinates().map(|layout| Self::new(code, layout))
+    }
+
+    /// Sets the width and height in pixels of each cell of the grid.
+    /// The default is 40.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the size is not positive.
+    pub fn cell_size(&mut self, size: f64) -> &mut Self {
+        if size <= 0.0 || size.is_nan() {
+            panic!("cell size {} is not positive", size);
+        }
+        self.cell_size = size;
+        self
+    }
+
+    /// Renders the lattice with the given error and its syndrome.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the error length is different from the number of qubits.
+    pub fn render(&self, error: &PauliOperator) -> String {
+        self.render_with_correction(
+            error,
+            &PauliOperator::new(self.code.len(), Vec::new(), Vec::new()),
+        )
+    }
+
+    /// Renders the lattice with the given error, its syndrome and the given correction.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the error or correction length is different from the number of qubits.
+    pub fn render_with_correction(
+        &self,
+        error: &PauliOperator,
+        correction: &PauliOperator,
+    ) -> String {
+        if error.len() != self.code.len() || correction.len() != self.code.len() {
+            panic!(
+                "error of length {} and correction of length {} for {} qubits",
+                error.len(),
+                correction.len(),
+                self.code.len()
+            );
+        }
+        let error_parts = CssOperator::from(error);
+        let correction_parts = CssOperator::from(correction);
+        let syndrome = self.code.syndrome_of(error);
+        let mut output = String::new();
+        writeln!(
+            output,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#,
+            width = self.layout.num_columns() as f64 * self.cell_size,
+            height = self.layout.num_rows() as f64 * self.cell_size,
+        )
+        .unwrap();
+        self.write_stabilizers(&mut output, &syndrome);
+        for (qubit, position) in self.layout.qubits().iter().enumerate() {
+            self.write_qubit(&mut output, *position, pauli_at(&error_parts, qubit));
+            if pauli_at(&correction_parts, qubit).is_some() {
+                let (x, y) = self.center(*position);
+                writeln!(
+                    output,
+                    r#"  <circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="2" stroke-dasharray="4 2"/>"#,
+                    x,
+                    y,
+                    0.45 * self.cell_size,
+                    CORRECTION_COLOR
+                )
+                .unwrap();
+            }
+        }
+        output.push_str("</svg>\n");
+        output
+    }
+
+    fn write_stabilizers(&self, output: &mut String, syndrome: &CssSyndrome) {
+        let positions = self.layout.stabilizers();
+        let stabilizers = [
+            (positions.x, &syndrome.x, X_COLOR),
+            (positions.z, &syndrome.z, Z_COLOR),
+        ];
+        let side = 0.5 * self.cell_size;
+        for (positions, syndrome, color) in stabilizers {
+            for (stabilizer, position) in positions.iter().enumerate() {
+                let (x, y) = self.center(*position);
+                writeln!(
+                    output,
+                    r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="{}" stroke-width="2"/>"#,
+                    x - side / 2.0,
+                    y - side / 2.0,
+                    side,
+                    side,
+                    if is_violated(syndrome, stabilizer) { color } else { "white" },
+                    color
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    fn write_qubit(&self, output: &mut String, position: (usize, usize), error: Option<char>) {
+        let (x, y) = self.center(position);
+        writeln!(
+            output,
+            r#"  <circle cx="{}" cy="{}" r="{}" fill="{}" stroke="black"/>"#,
+            x,
+            y,
+            0.3 * self.cell_size,
+            if error.is_some() {
+                ERROR_COLOR
+            } else {
+                "white"
+            }
+        )
+        .unwrap();
+        if let Some(pauli) = error {
+            writeln!(
+                output,
+                r#"  <text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                x,
+                y,
+                0.35 * self.cell_size,
+                pauli
+            )
+            .unwrap();
+        }
+    }
+
+    fn center(&self, (row, column): (usize, usize)) -> (f64, f64) {
+        (
+            (column as f64 + 0.5) * self.cell_size,
+            (row as f64 + 0.5) * self.cell_size,
+        )
+    }
+}
+
+fn is_violated(syndrome: &SparseBinVec, stabilizer: usize) -> bool {
+    syndrome.is_one_at(stabilizer) == Some(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pauli::Z;
+
+    #[test]
+    fn violated_stabilizers_are_filled() {
+        let code = CssCode::toric_code(3);
+        let layout = Layout::toric_code(3);
+        let error = PauliOperator::new(18, vec![0], vec![Z]);
+        let drawing = SvgLattice::new(&code, &layout).render(&error);
+        assert_eq!(
+            drawing.matches(&format!(r#"fill="{}""#, X_COLOR)).count(),
+            2
+        );
+        assert_eq!(
+            drawing.matches(&format!(r#"fill="{}""#, Z_COLOR)).count(),
+            0
+        );
+        assert_eq!(drawing.matches(">Z</text>").count(), 1);
+        assert!(!drawing.contains(CORRECTION_COLOR));
+        assert!(drawing.contains(r#"width="240" height="240""#));
+    }
+
+    #[test]
+    fn corrections_are_circled() {
+        let code = CssCode::toric_code(3);
+        let layout = Layout::toric_code(3);
+        let error = PauliOperator::new(18, vec![0], vec![Z]);
+        let correction = PauliOperator::new(18, vec![1, 2], vec![Z, Z]);
+        let drawing = SvgLattice::new(&code, &layout).render_with_correction(&error, &correction);
+        assert_eq!(drawing.matches(CORRECTION_COLOR).count(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn correction_must_have_code_length() {
+        let code = CssCode::toric_code(3);
+        let layout = Layout::toric_code(3);
+        let error = PauliOperator::new(18, vec![0], vec![Z]);
+        let correction = PauliOperator::new(17, vec![0], vec![Z]);
+        SvgLattice::new(&code, &layout).render_with_correction(&error, &correction);
+    }
+}